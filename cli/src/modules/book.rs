@@ -0,0 +1,70 @@
+use crate::imports::*;
+use kaspa_wallet_core::storage::AddressBookEntry;
+
+#[derive(Default, Handler)]
+#[help("Manage the wallet address book")]
+pub struct Book;
+
+impl Book {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "list" => {
+                let search = (!argv.is_empty()).then(|| argv.join(" "));
+                let entries = ctx.wallet().address_book_enumerate(search).await?;
+                if entries.is_empty() {
+                    tprintln!(ctx, "No address book entries");
+                }
+                for AddressBookEntry { alias, title, address } in entries {
+                    tprintln!(ctx, "{}  {address}  {}", style(alias).cyan(), style(title).dim());
+                }
+            }
+            "add" => {
+                if argv.len() < 2 {
+                    tprintln!(ctx, "usage: 'book add <alias> <address> [<title>]'");
+                    return Ok(());
+                }
+                let alias = argv.remove(0);
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let entry = AddressBookEntry::new(alias, argv.join(" "), address);
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().address_book_update(wallet_secret, entry).await?;
+            }
+            "remove" => {
+                let Some(address) = argv.first() else {
+                    tprintln!(ctx, "usage: 'book remove <address>'");
+                    return Ok(());
+                };
+                let address = Address::try_from(address.as_str())?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().address_book_remove(wallet_secret, address).await?;
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("list [<search>]", "List the address book entries, optionally matching the search string"),
+                ("add <alias> <address> [<title>]", "Add a contact address or replace the entry of an existing address"),
+                ("remove <address>", "Remove the entry of an address"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -6,13 +6,18 @@ use kaspa_wallet_core::tx::PaymentDestination;
 pub struct Estimate;
 
 impl Estimate {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         let account = ctx.wallet().account()?;
 
+        let coin_control = try_take_coin_control(&mut argv)?;
         if argv.is_empty() {
-            tprintln!(ctx, "usage: estimate <amount> [<priority fee>]");
+            tprintln!(
+                ctx,
+                "usage: estimate <amount> [<priority fee>] [strategy=<strategy>] [include=<outpoints>] [exclude=<outpoints>]"
+            );
+            tprintln!(ctx, "strategies: default, largest, smallest, exact, random; outpoints: <txid>-<index>[,<txid>-<index>...]");
             return Ok(());
         }
 
@@ -23,7 +28,7 @@ impl Estimate {
         // just use any address for an estimate (change address)
         let change_address = account.change_address()?;
         let destination = PaymentDestination::PaymentOutputs(PaymentOutputs::from((change_address.clone(), amount_sompi)));
-        let estimate = account.estimate(destination, priority_fee_sompi.into(), None, &abortable).await?;
+        let estimate = account.estimate(destination, priority_fee_sompi.into(), None, coin_control, &abortable).await?;
 
         tprintln!(ctx, "Estimate - {estimate}");
 
@@ -0,0 +1,91 @@
+use crate::imports::*;
+use kaspa_wallet_core::storage::{Label as WalletLabel, LabelTarget};
+
+#[derive(Default, Handler)]
+#[help("Attach labels and notes to addresses, UTXOs and transactions")]
+pub struct Label;
+
+impl Label {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "list" => {
+                let search = (!argv.is_empty()).then(|| argv.join(" "));
+                let labels = ctx.wallet().labels_enumerate(search).await?;
+                if labels.is_empty() {
+                    tprintln!(ctx, "No labels");
+                }
+                for WalletLabel { target, label, note } in labels {
+                    tprintln!(ctx, "{target}  {}", style(label).cyan());
+                    if let Some(note) = note {
+                        tprintln!(ctx, "    {}", style(note).dim());
+                    }
+                }
+            }
+            "set" => {
+                if argv.len() < 2 {
+                    tprintln!(ctx, "usage: 'label set <target> <label>'");
+                    return Ok(());
+                }
+                let target = argv.remove(0).parse::<LabelTarget>()?;
+                let note = self.load(&ctx, &target).await?.and_then(|existing| existing.note);
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().labels_update(wallet_secret, WalletLabel::new(target, argv.join(" "), note)).await?;
+            }
+            "note" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: 'label note <target> [<note>]'");
+                    return Ok(());
+                }
+                let target = argv.remove(0).parse::<LabelTarget>()?;
+                let label = self.load(&ctx, &target).await?.map(|existing| existing.label).unwrap_or_default();
+                let note = (!argv.is_empty()).then(|| argv.join(" "));
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().labels_update(wallet_secret, WalletLabel::new(target, label, note)).await?;
+            }
+            "remove" => {
+                let Some(target) = argv.first() else {
+                    tprintln!(ctx, "usage: 'label remove <target>'");
+                    return Ok(());
+                };
+                let target = target.parse::<LabelTarget>()?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().labels_remove(wallet_secret, target).await?;
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn load(&self, ctx: &Arc<KaspaCli>, target: &LabelTarget) -> Result<Option<WalletLabel>> {
+        let labels = ctx.wallet().labels_enumerate(Some(target.to_string())).await?;
+        Ok(labels.into_iter().find(|label| label.target == *target))
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("list [<search>]", "List labels and notes, optionally matching the search string"),
+                ("set <target> <label>", "Set the label of a target, keeping its note"),
+                ("note <target> [<note>]", "Set or clear the note of a target, keeping its label"),
+                ("remove <target>", "Remove the label and the note of a target"),
+            ],
+            None,
+        )?;
+
+        tprintln!(ctx, "Targets are addresses, UTXO outpoints in the '<txid>-<index>' format, or transaction ids");
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use crate::imports::*;
 
 pub mod account;
 pub mod address;
+pub mod book;
 pub mod broadcast;
 pub mod close;
 pub mod connect;
@@ -17,6 +18,7 @@ pub mod halt;
 pub mod help;
 pub mod history;
 // pub mod import;
+pub mod label;
 pub mod list;
 pub mod message;
 pub mod miner;
@@ -26,6 +28,7 @@ pub mod network;
 pub mod node;
 pub mod open;
 pub mod ping;
+pub mod pskt;
 pub mod reload;
 pub mod rpc;
 pub mod select;
@@ -33,13 +36,17 @@ pub mod send;
 pub mod server;
 pub mod settings;
 pub mod sign;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sink;
 pub mod start;
 pub mod stop;
 pub mod sweep;
 // pub mod test;
 pub mod theme;
+pub mod timelock;
 pub mod track;
 pub mod transfer;
+pub mod utxo;
 pub mod wallet;
 
 // this module is registered manually within
@@ -56,13 +63,16 @@ pub fn register_handlers(cli: &Arc<KaspaCli>) -> Result<()> {
         cli,
         cli.handlers(),
         [
-            account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, reload, select, send, server, settings, sweep, track, transfer,
-            wallet,
+            account, address, book, close, connect, details, disconnect, estimate, exit, export, guide, help, history, label, rpc,
+            list, miner, message, monitor, mute, network, node, open, ping, pskt, reload, select, send, server, settings, sweep,
+            timelock, track, transfer, utxo, wallet,
             // halt,
             // theme,  start, stop
         ]
     );
 
+    #[cfg(not(target_arch = "wasm32"))]
+    register_handlers!(cli, cli.handlers(), [sink]);
+
     Ok(())
 }
@@ -0,0 +1,160 @@
+use crate::imports::*;
+use kaspa_consensus_core::tx::PopulatedTransaction;
+use kaspa_rpc_core::RpcTransaction;
+use kaspa_txscript::trace::trace_transaction_scripts;
+
+#[derive(Default)]
+pub struct Pskt;
+
+#[async_trait]
+impl Handler for Pskt {
+    fn verb(&self, _ctx: &Arc<dyn Context>) -> Option<&'static str> {
+        Some("pskt")
+    }
+
+    fn help(&self, _ctx: &Arc<dyn Context>) -> &'static str {
+        "Create, sign, combine, trace and broadcast partially signed transactions"
+    }
+
+    async fn handle(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, cmd: &str) -> cli::Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        self.main(ctx, argv, cmd).await.map_err(|e| e.into())
+    }
+}
+
+impl Pskt {
+    async fn main(self: Arc<Self>, ctx: Arc<KaspaCli>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        match argv.first().unwrap().as_str() {
+            "create" => {
+                if argv.len() < 3 {
+                    return self.display_help(ctx, argv).await;
+                }
+
+                let account = ctx.wallet().account()?;
+                let address = Address::try_from(argv[1].as_str())?;
+                let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(2))?;
+                let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(3))?.unwrap_or(0);
+                let outputs = PaymentOutputs::from((address, amount_sompi));
+                let abortable = Abortable::default();
+
+                let (summary, pskts) = account.create_pskt(outputs.into(), priority_fee_sompi.into(), None, &abortable).await?;
+                tprintln!(ctx, "Created {} PSKT(s) - {summary}", pskts.len());
+                if pskts.len() > 1 {
+                    tprintln!(ctx, "The transactions spend each other's outputs and must be broadcast in this order");
+                }
+                for pskt in pskts {
+                    tprintln!(ctx, "\r\n{pskt}");
+                }
+            }
+            "sign" => {
+                if argv.len() != 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+
+                let mut pskt = argv[1].parse::<kaspa_wallet_core::tx::Pskt>()?;
+                let account = ctx.wallet().account()?;
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                let count = account.sign_pskt(&mut pskt, wallet_secret, payment_secret).await?;
+                if count == 0 {
+                    tprintln!(ctx, "No inputs of this PSKT can be signed by the selected account");
+                    return Ok(());
+                }
+                tprintln!(ctx, "Added {count} signature(s)\r\n\r\n{pskt}");
+            }
+            "combine" => {
+                if argv.len() < 3 {
+                    return self.display_help(ctx, argv).await;
+                }
+
+                let mut pskts = argv[1..].iter().map(|pskt| pskt.parse::<kaspa_wallet_core::tx::Pskt>());
+                let first = pskts.next().unwrap()?;
+                let pskt = pskts.try_fold(first, |combined, pskt| combined.combine(pskt?))?;
+                tprintln!(ctx, "{pskt}");
+            }
+            "finalize" => {
+                if argv.len() != 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+
+                let mut pskt = argv[1].parse::<kaspa_wallet_core::tx::Pskt>()?;
+                pskt.finalize()?;
+                tprintln!(ctx, "{pskt}");
+            }
+            "broadcast" => {
+                if argv.len() != 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+
+                let mut pskt = argv[1].parse::<kaspa_wallet_core::tx::Pskt>()?;
+                pskt.finalize()?;
+                let transaction = pskt.extract()?;
+                let id = ctx.wallet().rpc_api().submit_transaction(RpcTransaction::from(&transaction), false).await?;
+                tprintln!(ctx, "Submitted transaction {id}");
+            }
+            "trace" => {
+                if argv.len() != 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+
+                // Inputs that can not be finalized (missing signatures, unsupported scripts) are
+                // traced with the signature scripts they already carry
+                let pskt = argv[1].parse::<kaspa_wallet_core::tx::Pskt>()?;
+                let mut finalized = pskt.clone();
+                let transaction = match finalized.finalize().and_then(|_| finalized.extract()) {
+                    Ok(transaction) => transaction,
+                    Err(err) => {
+                        tprintln!(ctx, "PSKT can not be finalized ({err}), tracing the existing signature scripts");
+                        pskt.partial_transaction()?
+                    }
+                };
+                let populated = PopulatedTransaction::new(&transaction, pskt.utxo_entries()?);
+                for input in trace_transaction_scripts(&populated) {
+                    match &input.result {
+                        Ok(()) => tprintln!(ctx, "input {}: success", input.input_index),
+                        Err(err) => tprintln!(ctx, "input {}: failed: {err}", input.input_index),
+                    }
+                    for step in input.steps {
+                        let data_stack = step.data_stack.iter().map(|item| faster_hex::hex_string(item)).collect::<Vec<_>>().join(" ");
+                        tprintln!(
+                            ctx,
+                            "  {:?}[{}] {}{} | cond: {:?} | stack: [{}]{}",
+                            step.script,
+                            step.index,
+                            step.opcode_name(),
+                            if step.executed { "" } else { " (skipped)" },
+                            step.cond_stack,
+                            data_stack,
+                            step.error.as_ref().map(|err| format!(" | error: {err}")).unwrap_or_default()
+                        );
+                    }
+                }
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("create <address> <amount> [<priority fee>]", "Create unsigned PSKT(s) paying the address from the selected account"),
+                ("sign <pskt>", "Add the signatures of the selected account to a PSKT"),
+                ("combine <pskt> <pskt> [...]", "Merge the signatures of several copies of the same PSKT"),
+                ("finalize <pskt>", "Build the signature scripts of a fully signed PSKT"),
+                ("broadcast <pskt>", "Finalize a PSKT and submit its transaction to the network"),
+                ("trace <pskt>", "Print the execution trace of the scripts of every input, finalizing the PSKT when possible"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -170,18 +170,34 @@ impl Rpc {
             }
             RpcApiOps::Ban => {
                 if argv.is_empty() {
-                    return Err(Error::custom("Please specify peer IP address"));
+                    return Err(Error::custom(
+                        "Please specify peer IP address or subnet, then optionally the ban seconds (0 for permanent) and reason",
+                    ));
                 }
-                let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.ban_call(BanRequest { ip }).await?;
+                let subnet = argv.remove(0).parse::<RpcIpSubnet>().map_err(|err| Error::custom(err.to_string()))?;
+                let duration = if argv.is_empty() { None } else { Some(argv.remove(0).parse::<u64>()?) };
+                let reason = if argv.is_empty() { None } else { Some(argv.join(" ")) };
+                let result = rpc.ban_call(BanRequest { subnet, duration, reason }).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::Unban => {
                 if argv.is_empty() {
-                    return Err(Error::custom("Please specify peer IP address"));
+                    return Err(Error::custom("Please specify peer IP address or subnet"));
                 }
-                let ip: RpcIpAddress = argv.remove(0).parse()?;
-                let result = rpc.unban_call(UnbanRequest { ip }).await?;
+                let subnet = argv.remove(0).parse::<RpcIpSubnet>().map_err(|err| Error::custom(err.to_string()))?;
+                let result = rpc.unban_call(UnbanRequest { subnet }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::ListBans => {
+                let result = rpc.list_bans_call(ListBansRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::SaveMempool => {
+                let result = rpc.save_mempool_call(SaveMempoolRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::LoadMempool => {
+                let result = rpc.load_mempool_call(LoadMempoolRequest {}).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetInfo => {
@@ -229,6 +245,77 @@ impl Rpc {
                     }
                 }
             }
+            RpcApiOps::GetTransaction => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = RpcTransactionId::from_hex(argv.remove(0).as_str())?;
+                let result = rpc.get_transaction_call(GetTransactionRequest { transaction_id, include_transaction: true }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetTransactionsByIds => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Please specify at least one transaction id"));
+                }
+                let transaction_ids =
+                    argv.iter().map(|s| RpcTransactionId::from_hex(s.as_str())).collect::<std::result::Result<Vec<_>, _>>()?;
+                let result = rpc
+                    .get_transactions_by_ids_call(GetTransactionsByIdsRequest { transaction_ids, include_transactions: true })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAddressTransactions => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let start_daa_score = argv
+                    .first()
+                    .map(|s| s.parse::<u64>())
+                    .transpose()
+                    .map_err(|_| Error::custom("Invalid start DAA score"))?
+                    .unwrap_or(0);
+                let limit =
+                    argv.get(1).map(|s| s.parse::<u32>()).transpose().map_err(|_| Error::custom("Invalid limit"))?.unwrap_or(100);
+                let start_transaction_id = argv.get(2).map(|s| RpcTransactionId::from_hex(s.as_str())).transpose()?;
+                let result = rpc
+                    .get_address_transactions_call(GetAddressTransactionsRequest {
+                        address,
+                        start_daa_score,
+                        start_transaction_id,
+                        limit,
+                    })
+                    .await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetAddressTransactionCount => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing address argument"));
+                }
+                let address = Address::try_from(argv.remove(0).as_str())?;
+                let result = rpc.get_address_transaction_count_call(GetAddressTransactionCountRequest { address }).await?;
+                self.println(&ctx, result.count);
+            }
+            RpcApiOps::GetTransactionMerkleProof => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = RpcTransactionId::from_hex(argv.remove(0).as_str())?;
+                let block_hash = argv.first().map(|s| RpcHash::from_hex(s.as_str())).transpose()?;
+                let result =
+                    rpc.get_transaction_merkle_proof_call(GetTransactionMerkleProofRequest { transaction_id, block_hash }).await?;
+                let verified = result.proof.verify();
+                self.println(&ctx, result);
+                match verified {
+                    Some(chain_block) => tprintln!(ctx, "proof verified, leading to chain block {chain_block}"),
+                    None => tprintln!(ctx, "proof verification failed"),
+                }
+            }
+            RpcApiOps::GetFeeEstimate => {
+                let verbose = argv.first().map(|s| s.parse::<bool>().unwrap_or(false)).unwrap_or(false);
+                let result = rpc.get_fee_estimate_call(GetFeeEstimateRequest { verbose }).await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
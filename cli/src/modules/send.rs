@@ -5,14 +5,19 @@ use crate::imports::*;
 pub struct Send;
 
 impl Send {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         // address, amount, priority fee
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         let account = ctx.wallet().account()?;
 
+        let coin_control = try_take_coin_control(&mut argv)?;
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+            tprintln!(
+                ctx,
+                "usage: send <address> <amount> <priority fee> [strategy=<strategy>] [include=<outpoints>] [exclude=<outpoints>]"
+            );
+            tprintln!(ctx, "strategies: default, largest, smallest, exact, random; outpoints: <txid>-<index>[,<txid>-<index>...]");
             return Ok(());
         }
 
@@ -29,6 +34,7 @@ impl Send {
                 outputs.into(),
                 priority_fee_sompi.into(),
                 None,
+                coin_control,
                 wallet_secret,
                 payment_secret,
                 &abortable,
@@ -0,0 +1,163 @@
+use crate::imports::*;
+use kaspa_wallet_core::events::EventKind;
+use kaspa_wallet_core::sink::{EventSinks, SinkConfig, SinkTarget, SinksConfig};
+
+pub struct Sink {
+    sinks: EventSinks,
+}
+
+impl Default for Sink {
+    fn default() -> Self {
+        Sink { sinks: EventSinks::try_new(&SinksConfig::default()).expect("Failed to create event sinks") }
+    }
+}
+
+#[async_trait]
+impl Handler for Sink {
+    fn verb(&self, _ctx: &Arc<dyn Context>) -> Option<&'static str> {
+        Some("sink")
+    }
+
+    fn help(&self, _ctx: &Arc<dyn Context>) -> &'static str {
+        "Forward wallet events to webhooks, Unix sockets or files"
+    }
+
+    async fn start(self: Arc<Self>, ctx: &Arc<dyn Context>) -> cli::Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        self.sinks.start(ctx.wallet().multiplexer()).await.map_err(|err| err.to_string())?;
+        match SinksConfig::try_load().await {
+            Ok(config) => self.sinks.update(&config).await.map_err(|err| err.to_string())?,
+            Err(err) => log_error!("Unable to load event sink configuration: {err}"),
+        }
+
+        Ok(())
+    }
+
+    async fn stop(self: Arc<Self>, _ctx: &Arc<dyn Context>) -> cli::Result<()> {
+        self.sinks.stop().await.map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
+    async fn handle(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, cmd: &str) -> cli::Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        self.main(ctx, argv, cmd).await.map_err(|e| e.into())
+    }
+}
+
+impl Sink {
+    async fn main(self: Arc<Self>, ctx: Arc<KaspaCli>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        match argv.remove(0).as_str() {
+            "list" => {
+                let sinks = self.sinks.sinks();
+                if sinks.is_empty() {
+                    tprintln!(ctx, "No event sinks");
+                }
+                for SinkConfig { name, target, events, accounts } in sinks {
+                    tprintln!(ctx, "{}: {target}", style(name).cyan());
+                    if !events.is_empty() {
+                        tprintln!(ctx, "    events: {}", events.iter().map(|kind| kind.to_string()).collect::<Vec<_>>().join(", "));
+                    }
+                    if !accounts.is_empty() {
+                        tprintln!(ctx, "    accounts: {}", accounts.iter().map(|id| id.short()).collect::<Vec<_>>().join(", "));
+                    }
+                }
+                let pending = self.sinks.pending().await;
+                if pending > 0 {
+                    tprintln!(ctx, "{pending} notification(s) pending delivery");
+                }
+            }
+            "add" => {
+                if argv.len() < 3 {
+                    tprintln!(ctx, "usage: 'sink add <name> <webhook|socket|file> <url|path> [options]'");
+                    return Ok(());
+                }
+                let name = argv.remove(0);
+                let kind = argv.remove(0);
+                let destination = argv.remove(0);
+
+                let mut secret = None;
+                let mut events = vec![];
+                let mut accounts = vec![];
+                for option in argv {
+                    let Some((key, value)) = option.split_once('=') else {
+                        return Err(Error::custom(format!("invalid option: '{option}'")));
+                    };
+                    match key {
+                        "secret" => secret = Some(value.to_string()),
+                        "events" => {
+                            events = value
+                                .split(',')
+                                .map(|kind| kind.trim().parse::<EventKind>())
+                                .collect::<std::result::Result<Vec<_>, _>>()?
+                        }
+                        "accounts" => {
+                            for pat in value.split(',') {
+                                let account = if pat == "selected" {
+                                    ctx.account().await?
+                                } else {
+                                    ctx.find_accounts_by_name_or_id(pat).await?
+                                };
+                                accounts.push(*account.id());
+                            }
+                        }
+                        _ => return Err(Error::custom(format!("unknown option: '{key}'"))),
+                    }
+                }
+
+                if secret.is_some() && kind != "webhook" {
+                    return Err(Error::custom("the 'secret' option applies only to webhooks"));
+                }
+                let target = match kind.as_str() {
+                    "webhook" => SinkTarget::Webhook { url: destination, secret },
+                    "socket" => SinkTarget::UnixSocket { path: destination },
+                    "file" => SinkTarget::File { path: destination },
+                    v => return Err(Error::custom(format!("unknown sink type: '{v}'"))),
+                };
+
+                let mut config = SinksConfig::try_load().await?;
+                config.insert(SinkConfig::new(name, target).with_events(events).with_accounts(accounts))?;
+                self.sinks.update(&config).await?;
+                config.try_store().await?;
+            }
+            "remove" => {
+                let Some(name) = argv.first() else {
+                    tprintln!(ctx, "usage: 'sink remove <name>'");
+                    return Ok(());
+                };
+                let mut config = SinksConfig::try_load().await?;
+                config.remove(name)?;
+                self.sinks.update(&config).await?;
+                config.try_store().await?;
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("list", "List event sinks and the number of pending notifications"),
+                ("add <name> webhook <url> [options]", "POST events to a webhook, signed if 'secret=<secret>' is supplied"),
+                ("add <name> socket <path> [options]", "Write events as JSON lines to a Unix socket"),
+                ("add <name> file <path> [options]", "Append events as JSON lines to a file"),
+                ("remove <name>", "Remove an event sink"),
+            ],
+            None,
+        )?;
+
+        tprintln!(ctx, "Options: 'events=<kind>,..' (balance and transaction events by default, '*' for all)");
+        tprintln!(ctx, "         'accounts=<account>,..' (account names or ids, 'selected' for the selected account)");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::imports::*;
+use kaspa_wallet_core::tx::{LockTime, TimeLock};
+
+#[derive(Default, Handler)]
+#[help("Lock funds until a DAA score or a timestamp and claim them once unlocked")]
+pub struct Timelock;
+
+impl Timelock {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let account = ctx.wallet().account()?;
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "list" => {
+                let timelocks = account.timelocks();
+                if timelocks.is_empty() {
+                    tprintln!(ctx, "No time locks");
+                }
+                for timelock in timelocks {
+                    tprintln!(ctx, "{}  {}  {}", timelock.address()?, timelock.lock_time, style(&timelock.owner).dim());
+                }
+                if let Some(balance) = account.balance() {
+                    tprintln!(
+                        ctx,
+                        "locked: {} ({} UTXOs), claimable: {} ({} UTXOs)",
+                        sompi_to_kaspa_string(balance.locked),
+                        balance.locked_utxo_count,
+                        sompi_to_kaspa_string(balance.claimable),
+                        balance.claimable_utxo_count
+                    );
+                }
+            }
+            "create" | "add" => {
+                let (owner, lock_time) = if action == "create" {
+                    (account.receive_address()?, argv.first())
+                } else {
+                    (Address::try_from(argv.first().ok_or(Error::custom("missing owner address"))?.as_str())?, argv.get(1))
+                };
+                let Some(lock_time) = lock_time else {
+                    tprintln!(ctx, "usage: 'timelock create <lock time>' or 'timelock add <owner address> <lock time>'");
+                    return Ok(());
+                };
+                let timelock = TimeLock::try_new(owner, self.parse_lock_time(&ctx, lock_time)?)?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                let address = account.add_timelock(&wallet_secret, timelock.clone()).await?;
+                tprintln!(ctx, "Funds sent to {address} are locked until {}", timelock.lock_time);
+            }
+            "send" => {
+                if argv.len() < 3 {
+                    tprintln!(ctx, "usage: 'timelock send <address> <lock time> <amount> <priority fee>'");
+                    return Ok(());
+                }
+                let owner = Address::try_from(argv[0].as_str())?;
+                let timelock = TimeLock::try_new(owner, self.parse_lock_time(&ctx, &argv[1])?)?;
+                let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(2))?;
+                let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(3))?.unwrap_or(0);
+                let outputs = PaymentOutputs::from((timelock.address()?, amount_sompi));
+                let abortable = Abortable::default();
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+
+                let (summary, _ids) = account
+                    .send(outputs.into(), priority_fee_sompi.into(), None, None, wallet_secret, payment_secret, &abortable, None)
+                    .await?;
+
+                tprintln!(ctx, "Send - {summary}");
+                tprintln!(
+                    ctx,
+                    "The recipient can track these funds using 'timelock add {} {}'",
+                    timelock.owner,
+                    timelock.lock_time.value()
+                );
+            }
+            "claim" => {
+                let abortable = Abortable::default();
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                let (summaries, _ids) = account.clone().claim_timelocked(wallet_secret, payment_secret, &abortable, None).await?;
+                for summary in summaries {
+                    tprintln!(ctx, "Claim - {summary}");
+                }
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses an absolute lock time or a `+<n>` DAA score offset from the current DAA score.
+    fn parse_lock_time(&self, ctx: &Arc<KaspaCli>, lock_time: &str) -> Result<LockTime> {
+        if let Some(offset) = lock_time.strip_prefix('+') {
+            let offset = offset.parse::<u64>().map_err(|_| Error::custom(format!("Invalid DAA score offset '{offset}'")))?;
+            let current_daa_score = ctx.wallet().current_daa_score().ok_or(Error::custom("wallet is not connected"))?;
+            Ok(LockTime::try_new(current_daa_score + offset)?)
+        } else {
+            Ok(lock_time.parse::<LockTime>()?)
+        }
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("list", "List the time locks tracked by the selected account"),
+                ("create <lock time>", "Create a time lock owned by the account receive address"),
+                ("add <owner address> <lock time>", "Track a time lock owned by the account, created by a sender"),
+                ("send <address> <lock time> <amount> <priority fee>", "Send funds to an address, locked until the lock time"),
+                ("claim", "Claim the unlocked funds into the account"),
+            ],
+            None,
+        )?;
+
+        tprintln!(ctx, "Lock times are DAA scores, '+<n>' DAA scores from now, or unix timestamps in milliseconds");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use crate::imports::*;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+use kaspa_wallet_core::utxo::UtxoIterator;
+
+#[derive(Default, Handler)]
+#[help("List, freeze and unfreeze the UTXOs of the selected account")]
+pub struct Utxo;
+
+impl Utxo {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let account = ctx.wallet().account()?;
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "list" => {
+                let frozen_utxos = account.frozen_utxos();
+                let utxos = UtxoIterator::new(account.utxo_context()).collect::<Vec<_>>();
+                if utxos.is_empty() {
+                    tprintln!(ctx, "No mature UTXOs");
+                }
+                for utxo in utxos {
+                    let id = utxo.id();
+                    let frozen = frozen_utxos.contains(&TransactionOutpoint::new(id.transaction_id, id.index));
+                    tprintln!(
+                        ctx,
+                        "{id}  {}{}",
+                        sompi_to_kaspa_string(utxo.amount()),
+                        if frozen { style("  frozen").dim().to_string() } else { "".to_string() }
+                    );
+                }
+            }
+            "frozen" => {
+                let frozen_utxos = account.frozen_utxos();
+                if frozen_utxos.is_empty() {
+                    tprintln!(ctx, "No frozen UTXOs");
+                }
+                for outpoint in frozen_utxos {
+                    tprintln!(ctx, "{}-{}", outpoint.transaction_id, outpoint.index);
+                }
+            }
+            "freeze" | "unfreeze" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: 'utxo {action} <txid>-<index> [<txid>-<index> ...]'");
+                    return Ok(());
+                }
+                let outpoints = argv.iter().map(|outpoint| try_parse_outpoint(outpoint)).collect::<Result<Vec<_>>>()?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                let frozen_utxos = if action == "freeze" {
+                    account.freeze_utxos(&wallet_secret, &outpoints).await?
+                } else {
+                    account.unfreeze_utxos(&wallet_secret, &outpoints).await?
+                };
+                tprintln!(ctx, "{} frozen UTXOs", frozen_utxos.len());
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("list", "List the mature UTXOs of the selected account"),
+                ("frozen", "List the frozen UTXOs of the selected account"),
+                ("freeze <txid>-<index> [...]", "Freeze UTXOs, excluding them from transactions unless explicitly included"),
+                ("unfreeze <txid>-<index> [...]", "Unfreeze UTXOs"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
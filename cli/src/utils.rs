@@ -1,6 +1,8 @@
 use crate::error::Error;
 use crate::result::Result;
 use kaspa_consensus_core::constants::LEOR_PER_PYRIN;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+use kaspa_wallet_core::tx::{CoinControl, UtxoSelection};
 use std::fmt::Display;
 
 pub fn try_parse_required_nonzero_kaspa_as_sompi_u64<S: ToString + Display>(kaspa_amount: Option<S>) -> Result<u64> {
@@ -58,3 +60,36 @@ pub fn try_parse_optional_kaspa_as_sompi_i64<S: ToString + Display>(kaspa_amount
         Ok(None)
     }
 }
+
+/// Parses an outpoint in the `<transaction id>-<index>` format used when listing UTXOs.
+pub fn try_parse_outpoint(outpoint: &str) -> Result<TransactionOutpoint> {
+    let (transaction_id, index) =
+        outpoint.split_once('-').ok_or_else(|| Error::custom(format!("Supplied outpoint is not valid: '{outpoint}'")))?;
+    let transaction_id =
+        transaction_id.parse().map_err(|_| Error::custom(format!("Supplied outpoint transaction id is not valid: '{outpoint}'")))?;
+    let index = index.parse().map_err(|_| Error::custom(format!("Supplied outpoint index is not valid: '{outpoint}'")))?;
+    Ok(TransactionOutpoint::new(transaction_id, index))
+}
+
+/// Takes the optional `strategy=<strategy>`, `include=<outpoint>[,<outpoint>...]` and
+/// `exclude=<outpoint>[,<outpoint>...]` coin control arguments out of `argv`.
+pub fn try_take_coin_control(argv: &mut Vec<String>) -> Result<Option<CoinControl>> {
+    let mut coin_control = None;
+    let mut remaining = vec![];
+    for arg in argv.drain(..) {
+        let Some((key, value)) = arg.split_once('=') else {
+            remaining.push(arg);
+            continue;
+        };
+        let coin_control = coin_control.get_or_insert_with(CoinControl::default);
+        let outpoints = || value.split(',').filter(|s| !s.is_empty()).map(try_parse_outpoint).collect::<Result<Vec<_>>>();
+        match key {
+            "strategy" => coin_control.selection = value.parse::<UtxoSelection>()?,
+            "include" => coin_control.include.extend(outpoints()?),
+            "exclude" => coin_control.exclude.extend(outpoints()?),
+            _ => return Err(Error::custom(format!("Unknown coin control argument: '{arg}'"))),
+        }
+    }
+    *argv = remaining;
+    Ok(coin_control)
+}
@@ -2,7 +2,13 @@ mod port_mapping_extender;
 mod stores;
 extern crate self as address_manager;
 
-use std::{collections::HashSet, iter, net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
 
 use address_manager::port_mapping_extender::Extender;
 use igd_next::{
@@ -15,18 +21,21 @@ use itertools::{
 };
 use kaspa_consensus_core::config::Config;
 use kaspa_core::{debug, info, task::tick::TickService, time::unix_now, warn};
-use kaspa_database::prelude::{CachePolicy, StoreResultExtensions, DB};
-use kaspa_utils::networking::IpAddress;
+use kaspa_database::prelude::{CachePolicy, DB};
+use kaspa_utils::networking::{IpAddress, IpSubnet};
 use local_ip_address::list_afinet_netifas;
 use parking_lot::Mutex;
-use stores::banned_address_store::{BannedAddressesStore, BannedAddressesStoreReader, ConnectionBanTimestamp, DbBannedAddressesStore};
+use stores::banned_address_store::{BannedSubnetsStore, DbBannedSubnetsStore, DbLegacyBannedAddressesStore};
 use thiserror::Error;
 
-pub use stores::NetAddress;
+pub use stores::{banned_address_store::BanEntry, NetAddress};
 
 const MAX_ADDRESSES: usize = 4096;
 const MAX_CONNECTION_FAILED_COUNT: u64 = 3;
 
+/// The fixed ban duration of the IPs banned by previous versions
+const LEGACY_BAN_DURATION: u64 = 24 * 60 * 60 * 1000;
+
 const UPNP_DEADLINE_SEC: u64 = 2 * 60;
 const UPNP_EXTEND_PERIOD: u64 = UPNP_DEADLINE_SEC / 2;
 
@@ -52,7 +61,8 @@ pub enum UpnpError {
 }
 
 pub struct AddressManager {
-    banned_address_store: DbBannedAddressesStore,
+    banned_subnets_store: DbBannedSubnetsStore,
+    banned_subnets: HashMap<IpSubnet, BanEntry>,
     address_store: address_store_with_cache::Store,
     config: Arc<Config>,
     local_net_addresses: Vec<NetAddress>,
@@ -60,13 +70,18 @@ pub struct AddressManager {
 
 impl AddressManager {
     pub fn new(config: Arc<Config>, db: Arc<DB>, tick_service: Arc<TickService>) -> (Arc<Mutex<Self>>, Option<Extender>) {
+        // We keep all the bans in memory since they must all be checked against every connecting IP, hence the inner cache is disabled
+        let banned_subnets_store = DbBannedSubnetsStore::new(db.clone(), CachePolicy::Empty);
+        let banned_subnets = banned_subnets_store.iterator().map(|res| res.unwrap()).collect();
         let mut instance = Self {
-            banned_address_store: DbBannedAddressesStore::new(db.clone(), CachePolicy::Count(MAX_ADDRESSES)),
-            address_store: address_store_with_cache::new(db),
+            banned_subnets_store,
+            banned_subnets,
+            address_store: address_store_with_cache::new(db.clone()),
             local_net_addresses: Vec::new(),
             config,
         };
 
+        instance.migrate_legacy_bans(db);
         let extender = instance.init_local_addresses(tick_service);
 
         (Arc::new(Mutex::new(instance)), extender)
@@ -294,27 +309,60 @@ impl AddressManager {
         self.address_store.iterate_prioritized_random_addresses(exceptions)
     }
 
-    pub fn ban(&mut self, ip: IpAddress) {
-        self.banned_address_store.set(ip.into(), ConnectionBanTimestamp(unix_now())).unwrap();
-        self.address_store.remove_by_ip(ip.into());
+    /// Moves the bans of single IPs made by previous versions into the subnet bans store, keeping their original expiry
+    fn migrate_legacy_bans(&mut self, db: Arc<DB>) {
+        let mut legacy_store = DbLegacyBannedAddressesStore::new(db);
+        let legacy_bans = legacy_store.iterator().map(|res| res.unwrap()).collect_vec();
+        let now = unix_now();
+        for (ip, timestamp) in legacy_bans {
+            let entry = BanEntry::new(timestamp.0, Some(timestamp.0 + LEGACY_BAN_DURATION), String::new());
+            if !entry.is_expired(now) {
+                self.set_ban(ip.into(), entry);
+            }
+            legacy_store.remove(ip).unwrap();
+        }
+    }
+
+    /// The ban duration applied when none is specified, `None` meaning bans are permanent
+    pub fn default_ban_duration(&self) -> Option<Duration> {
+        (self.config.ban_duration > 0).then(|| Duration::from_secs(self.config.ban_duration))
     }
 
-    pub fn unban(&mut self, ip: IpAddress) {
-        self.banned_address_store.remove(ip.into()).unwrap();
+    /// Bans all the IPs of `subnet` for `duration`, or permanently if `duration` is `None`, and forgets their known addresses.
+    /// Banning an already banned subnet replaces its ban.
+    pub fn ban(&mut self, subnet: IpSubnet, duration: Option<Duration>, reason: String) {
+        let now = unix_now();
+        let expires_at = duration.map(|duration| now.saturating_add(duration.as_millis() as u64));
+        self.set_ban(subnet, BanEntry::new(now, expires_at, reason));
+        self.address_store.remove_by_subnet(subnet);
+    }
+
+    fn set_ban(&mut self, subnet: IpSubnet, entry: BanEntry) {
+        self.banned_subnets_store.set(subnet, entry.clone()).unwrap();
+        self.banned_subnets.insert(subnet, entry);
+    }
+
+    /// Lifts the ban of `subnet`, returning false if this exact subnet is not banned
+    pub fn unban(&mut self, subnet: IpSubnet) -> bool {
+        self.remove_expired_bans();
+        if self.banned_subnets.remove(&subnet).is_none() {
+            return false;
+        }
+        self.banned_subnets_store.remove(subnet).unwrap();
+        true
     }
 
     pub fn is_banned(&mut self, ip: IpAddress) -> bool {
-        const MAX_BANNED_TIME: u64 = 24 * 60 * 60 * 1000;
-        match self.banned_address_store.get(ip.into()).unwrap_option() {
-            Some(timestamp) => {
-                if unix_now() - timestamp.0 > MAX_BANNED_TIME {
-                    self.unban(ip);
-                    false
-                } else {
-                    true
-                }
-            }
-            None => false,
+        self.remove_expired_bans();
+        self.banned_subnets.keys().any(|subnet| subnet.contains(&ip))
+    }
+
+    fn remove_expired_bans(&mut self) {
+        let now = unix_now();
+        let expired = self.banned_subnets.iter().filter(|(_, entry)| entry.is_expired(now)).map(|(subnet, _)| *subnet).collect_vec();
+        for subnet in expired {
+            self.banned_subnets.remove(&subnet);
+            self.banned_subnets_store.remove(subnet).unwrap();
         }
     }
 
@@ -322,8 +370,15 @@ impl AddressManager {
         self.address_store.iterate_addresses().collect_vec()
     }
 
-    pub fn get_all_banned_addresses(&self) -> Vec<IpAddress> {
-        self.banned_address_store.iterator().map(|x| IpAddress::from(x.unwrap().0)).collect_vec()
+    /// Returns the IPs banned individually, the bans of wider subnets being only listed by [`Self::get_all_bans`]
+    pub fn get_all_banned_addresses(&mut self) -> Vec<IpAddress> {
+        self.remove_expired_bans();
+        self.banned_subnets.keys().filter(|subnet| subnet.is_single_address()).map(|subnet| subnet.network()).collect_vec()
+    }
+
+    pub fn get_all_bans(&mut self) -> Vec<(IpSubnet, BanEntry)> {
+        self.remove_expired_bans();
+        self.banned_subnets.iter().map(|(subnet, entry)| (*subnet, entry.clone())).collect_vec()
     }
 }
 
@@ -332,13 +387,12 @@ mod address_store_with_cache {
     // We don't expect it to be expensive since we limit the number of saved addresses.
     use std::{
         collections::{HashMap, HashSet},
-        net::IpAddr,
         sync::Arc,
     };
 
     use itertools::Itertools;
     use kaspa_database::prelude::{CachePolicy, DB};
-    use kaspa_utils::networking::PrefixBucket;
+    use kaspa_utils::networking::{IpSubnet, PrefixBucket};
     use rand::{
         distributions::{WeightedError, WeightedIndex},
         prelude::Distribution,
@@ -450,8 +504,9 @@ mod address_store_with_cache {
             RandomWeightedIterator::new(weights, filtered_addresses)
         }
 
-        pub fn remove_by_ip(&mut self, ip: IpAddr) {
-            for key in self.addresses.keys().filter(|key| key.is_ip(ip)).copied().collect_vec() {
+        pub fn remove_by_subnet(&mut self, subnet: IpSubnet) {
+            for key in self.addresses.iter().filter(|(_, entry)| subnet.contains(&entry.address.ip)).map(|(key, _)| *key).collect_vec()
+            {
                 self.remove_by_key(key);
             }
         }
@@ -623,3 +678,72 @@ mod address_store_with_cache {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::config::params::SIMNET_PARAMS;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use std::str::FromStr;
+    use stores::banned_address_store::ConnectionBanTimestamp;
+
+    fn address_manager(db: Arc<DB>) -> Arc<Mutex<AddressManager>> {
+        let mut config = Config::new(SIMNET_PARAMS);
+        config.disable_upnp = true;
+        AddressManager::new(Arc::new(config), db, Arc::new(TickService::default())).0
+    }
+
+    #[test]
+    fn test_subnet_bans() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let am = address_manager(db.clone());
+        let ip = |s: &str| IpAddress::from_str(s).unwrap();
+        let subnet = |s: &str| IpSubnet::from_str(s).unwrap();
+
+        let mut am_lock = am.lock();
+        am_lock.add_address(NetAddress::new(ip("10.0.1.1"), 16111));
+        am_lock.add_address(NetAddress::new(ip("11.0.0.1"), 16111));
+        am_lock.ban(subnet("10.0.0.0/16"), None, "misbehaving".to_string());
+        am_lock.ban(subnet("12.0.0.1"), Some(Duration::from_secs(3600)), String::new());
+        am_lock.ban(subnet("13.0.0.1"), Some(Duration::ZERO), String::new());
+
+        assert!(am_lock.is_banned(ip("10.0.200.3")));
+        assert!(!am_lock.is_banned(ip("10.1.0.1")));
+        assert!(am_lock.is_banned(ip("12.0.0.1")));
+        assert!(!am_lock.is_banned(ip("13.0.0.1")), "a ban with a zero duration expires immediately");
+        assert_eq!(am_lock.get_all_addresses(), vec![NetAddress::new(ip("11.0.0.1"), 16111)]);
+        assert_eq!(am_lock.get_all_banned_addresses(), vec![ip("12.0.0.1")]);
+        assert!(!am_lock.unban(subnet("10.0.1.1")), "only exact subnets can be unbanned");
+        drop(am_lock);
+        drop(am);
+
+        // Bans are persisted along with their expiry and reason
+        let am = address_manager(db.clone());
+        let mut am_lock = am.lock();
+        let bans: HashMap<_, _> = am_lock.get_all_bans().into_iter().collect();
+        assert_eq!(bans.len(), 2);
+        let entry = &bans[&subnet("10.0.0.0/16")];
+        assert_eq!((entry.expires_at, entry.reason.as_str()), (None, "misbehaving"));
+        let entry = &bans[&subnet("12.0.0.1")];
+        assert_eq!(entry.expires_at, Some(entry.created_at + 3600 * 1000));
+
+        assert!(am_lock.unban(subnet("10.0.0.0/16")));
+        assert!(!am_lock.is_banned(ip("10.0.200.3")));
+    }
+
+    #[test]
+    fn test_legacy_bans_migration() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let now = unix_now();
+        let mut legacy_store = DbLegacyBannedAddressesStore::new(db.clone());
+        legacy_store.set(IpAddress::from_str("1.2.3.4").unwrap().0, ConnectionBanTimestamp(now)).unwrap();
+        legacy_store.set(IpAddress::from_str("2001:db8::1").unwrap().0, ConnectionBanTimestamp(now - LEGACY_BAN_DURATION)).unwrap();
+
+        let am = address_manager(db.clone());
+        let mut am_lock = am.lock();
+        assert!(am_lock.is_banned(IpAddress::from_str("1.2.3.4").unwrap()));
+        assert!(!am_lock.is_banned(IpAddress::from_str("2001:db8::1").unwrap()));
+        assert_eq!(am_lock.get_all_bans()[0].1.expires_at, Some(now + LEGACY_BAN_DURATION));
+        assert_eq!(legacy_store.iterator().count(), 0);
+    }
+}
@@ -1,29 +1,56 @@
 use kaspa_database::{
-    prelude::{CachePolicy, StoreError, StoreResult},
+    prelude::{CachePolicy, StoreResult},
     prelude::{CachedDbAccess, DirectDbWriter, DB},
     registry::DatabaseStorePrefixes,
 };
-use kaspa_utils::mem_size::MemSizeEstimator;
+use kaspa_utils::{
+    mem_size::MemSizeEstimator,
+    networking::{IpAddress, IpSubnet},
+};
 use serde::{Deserialize, Serialize};
 use std::net::{IpAddr, Ipv6Addr};
 use std::{error::Error, fmt::Display, sync::Arc};
 
+/// The ban timestamp of a single IP, as stored by the legacy banned addresses store
 #[derive(Clone, Copy, Serialize, Deserialize)]
 pub struct ConnectionBanTimestamp(pub u64);
 
 impl MemSizeEstimator for ConnectionBanTimestamp {}
 
-pub trait BannedAddressesStoreReader {
-    fn get(&self, address: IpAddr) -> Result<ConnectionBanTimestamp, StoreError>;
+/// A ban of an IP subnet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BanEntry {
+    /// Unix timestamp (in milliseconds) of the ban
+    pub created_at: u64,
+    /// Unix timestamp (in milliseconds) at which the ban is lifted, `None` if the ban is permanent
+    pub expires_at: Option<u64>,
+    pub reason: String,
 }
 
-pub trait BannedAddressesStore: BannedAddressesStoreReader {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()>;
-    fn remove(&mut self, ip: IpAddr) -> StoreResult<()>;
+impl BanEntry {
+    pub fn new(created_at: u64, expires_at: Option<u64>, reason: String) -> Self {
+        Self { created_at, expires_at, reason }
+    }
+
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+impl MemSizeEstimator for BanEntry {}
+
+/// The bans are kept in memory by the address manager, so the store is only written to, and iterated on startup
+pub trait BannedSubnetsStore {
+    fn set(&mut self, subnet: IpSubnet, entry: BanEntry) -> StoreResult<()>;
+    fn remove(&mut self, subnet: IpSubnet) -> StoreResult<()>;
 }
 
 const IPV6_LEN: usize = 16;
 const ADDRESS_KEY_SIZE: usize = IPV6_LEN;
+const SUBNET_KEY_SIZE: usize = IPV6_LEN + 1;
+
+/// IPv4 subnets are keyed as IPv4-mapped IPv6 subnets, hence their prefix length is offset by 96 bits
+const IPV4_MAPPED_PREFIX_LEN: u8 = 96;
 
 #[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
 struct AddressKey([u8; ADDRESS_KEY_SIZE]);
@@ -66,15 +93,63 @@ impl From<AddressKey> for IpAddr {
     }
 }
 
+/// Subnet key, consisting of the 16 bytes of the (IPv4-mapped for IPv4) IPv6 network address followed by the IPv6 prefix length
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+struct SubnetKey([u8; SUBNET_KEY_SIZE]);
+
+impl AsRef<[u8]> for SubnetKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Display for SubnetKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match IpSubnet::try_from(*self) {
+            Ok(subnet) => write!(f, "{subnet}"),
+            Err(_) => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+impl From<IpSubnet> for SubnetKey {
+    fn from(subnet: IpSubnet) -> Self {
+        let mut bytes = [0; SUBNET_KEY_SIZE];
+        bytes[..IPV6_LEN].copy_from_slice(AddressKey::from(subnet.network().0).as_ref());
+        bytes[IPV6_LEN] = match subnet.network().0 {
+            IpAddr::V4(_) => subnet.prefix_len() + IPV4_MAPPED_PREFIX_LEN,
+            IpAddr::V6(_) => subnet.prefix_len(),
+        };
+        Self(bytes)
+    }
+}
+
+impl TryFrom<SubnetKey> for IpSubnet {
+    type Error = ipnet::PrefixLenError;
+
+    fn try_from(key: SubnetKey) -> Result<Self, Self::Error> {
+        let network: IpAddr = AddressKey(key.0[..IPV6_LEN].try_into().unwrap()).into();
+        let prefix_len = match network {
+            IpAddr::V4(_) => key.0[IPV6_LEN].checked_sub(IPV4_MAPPED_PREFIX_LEN).ok_or(ipnet::PrefixLenError)?,
+            IpAddr::V6(_) => key.0[IPV6_LEN],
+        };
+        IpSubnet::new(IpAddress::new(network), prefix_len)
+    }
+}
+
+/// The store of the IPs banned by previous versions, kept only for migrating its entries into [`DbBannedSubnetsStore`]
 #[derive(Clone)]
-pub struct DbBannedAddressesStore {
+pub struct DbLegacyBannedAddressesStore {
     db: Arc<DB>,
     access: CachedDbAccess<AddressKey, ConnectionBanTimestamp>,
 }
 
-impl DbBannedAddressesStore {
-    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedAddresses.into()) }
+impl DbLegacyBannedAddressesStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::BannedAddresses.into()),
+        }
     }
 
     pub fn iterator(&self) -> impl Iterator<Item = Result<(IpAddr, ConnectionBanTimestamp), Box<dyn Error>>> + '_ {
@@ -90,20 +165,62 @@ impl DbBannedAddressesStore {
             Err(e) => Err(e),
         })
     }
+
+    #[cfg(test)]
+    pub fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+    }
+
+    pub fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), ip.into())
+    }
+}
+
+#[derive(Clone)]
+pub struct DbBannedSubnetsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<SubnetKey, BanEntry>,
 }
 
-impl BannedAddressesStoreReader for DbBannedAddressesStore {
-    fn get(&self, ip: IpAddr) -> Result<ConnectionBanTimestamp, StoreError> {
-        self.access.read(ip.into())
+impl DbBannedSubnetsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::BannedSubnets.into()) }
+    }
+
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(IpSubnet, BanEntry), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| match iter_result {
+            Ok((key_bytes, entry)) => match <[u8; SUBNET_KEY_SIZE]>::try_from(&key_bytes[..]) {
+                Ok(subnet_key_slice) => Ok((IpSubnet::try_from(SubnetKey(subnet_key_slice))?, entry)),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        })
     }
 }
 
-impl BannedAddressesStore for DbBannedAddressesStore {
-    fn set(&mut self, ip: IpAddr, timestamp: ConnectionBanTimestamp) -> StoreResult<()> {
-        self.access.write(DirectDbWriter::new(&self.db), ip.into(), timestamp)
+impl BannedSubnetsStore for DbBannedSubnetsStore {
+    fn set(&mut self, subnet: IpSubnet, entry: BanEntry) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), subnet.into(), entry)
     }
 
-    fn remove(&mut self, ip: IpAddr) -> StoreResult<()> {
-        self.access.delete(DirectDbWriter::new(&self.db), ip.into())
+    fn remove(&mut self, subnet: IpSubnet) -> StoreResult<()> {
+        self.access.delete(DirectDbWriter::new(&self.db), subnet.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_subnet_key() {
+        for s in ["10.0.0.0/8", "10.1.2.3/32", "0.0.0.0/0", "2a01:4f8::/32", "::/0", "2a01:4f8:191:1143::2/128"] {
+            let subnet = IpSubnet::from_str(s).unwrap();
+            let key = SubnetKey::from(subnet);
+            assert_eq!(IpSubnet::try_from(key).unwrap(), subnet, "{s}");
+        }
+        // An IPv4 subnet and the IPv6 subnet sharing its prefix bits must not collide
+        assert_ne!(SubnetKey::from(IpSubnet::from_str("0.0.0.0/0").unwrap()), SubnetKey::from(IpSubnet::from_str("::/0").unwrap()));
     }
 }
@@ -1,7 +1,7 @@
 use std::{
     cmp::min,
     collections::{HashMap, HashSet},
-    net::{IpAddr, SocketAddr, ToSocketAddrs},
+    net::{SocketAddr, ToSocketAddrs},
     sync::Arc,
     time::{Duration, SystemTime},
 };
@@ -12,7 +12,7 @@ use itertools::Itertools;
 use kaspa_addressmanager::{AddressManager, NetAddress};
 use kaspa_core::{debug, info, warn};
 use kaspa_p2p_lib::{common::ProtocolError, ConnectionError, Peer};
-use kaspa_utils::triggers::SingleTrigger;
+use kaspa_utils::{networking::IpSubnet, triggers::SingleTrigger};
 use parking_lot::Mutex as ParkingLotMutex;
 use rand::{seq::SliceRandom, thread_rng};
 use tokio::{
@@ -279,19 +279,20 @@ impl ConnectionManager {
         }
     }
 
-    /// Bans the given IP and disconnects from all the peers with that IP.
+    /// Bans all the IPs of the given subnet for `duration` (permanently if `None`) and disconnects from all the peers within it.
+    /// Subnets holding some permanent connection are not banned.
     ///
     /// _GO-KASPAD: BanByIP_
-    pub async fn ban(&self, ip: IpAddr) {
-        if self.ip_has_permanent_connection(ip).await {
+    pub async fn ban(&self, subnet: IpSubnet, duration: Option<Duration>, reason: String) {
+        if self.subnet_has_permanent_connection(subnet).await {
             return;
         }
         for peer in self.p2p_adaptor.active_peers() {
-            if peer.net_address().ip() == ip {
+            if subnet.contains(&peer.net_address().ip().into()) {
                 self.p2p_adaptor.terminate(peer.key()).await;
             }
         }
-        self.address_manager.lock().ban(ip.into());
+        self.address_manager.lock().ban(subnet, duration, reason);
     }
 
     /// Returns whether the given address is banned.
@@ -304,8 +305,12 @@ impl ConnectionManager {
         self.connection_requests.lock().await.contains_key(address)
     }
 
-    /// Returns whether the given IP has some permanent request.
-    pub async fn ip_has_permanent_connection(&self, ip: IpAddr) -> bool {
-        self.connection_requests.lock().await.iter().any(|(address, request)| request.is_permanent && address.ip() == ip)
+    /// Returns whether the given subnet holds the IP of some permanent request.
+    pub async fn subnet_has_permanent_connection(&self, subnet: IpSubnet) -> bool {
+        self.connection_requests
+            .lock()
+            .await
+            .iter()
+            .any(|(address, request)| request.is_permanent && subnet.contains(&address.ip().into()))
     }
 }
@@ -19,6 +19,7 @@ use crate::{
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath,
 };
 use kaspa_hashes::Hash;
@@ -287,6 +288,14 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the UTXO diff applied by a block belonging to the selected parent chain on top of its selected parent,
+    /// i.e. the UTXOs created and spent by the transactions accepted in its mergeset.
+    ///
+    /// Note: outputs created and spent within the same mergeset cancel out and are not part of the diff
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        unimplemented!()
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         unimplemented!()
     }
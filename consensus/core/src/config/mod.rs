@@ -17,6 +17,9 @@ use {
     params::Params,
 };
 
+/// Default duration of peer bans in seconds
+pub const DEFAULT_BAN_DURATION: u64 = 24 * 60 * 60;
+
 /// Various consensus configurations all bundled up under a single struct. Use `Config::new` for directly building from
 /// a `Params` instance. For anything more complex it is recommended to use `ConfigBuilder`. NOTE: this struct can be
 /// implicitly de-refed into `Params`
@@ -35,6 +38,10 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
+    /// Period, in days, during which block bodies and acceptance data are retained beyond the pruning
+    /// point. Has no effect on archival nodes
+    pub retention_period_days: Option<f64>,
+
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
@@ -42,6 +49,12 @@ pub struct Config {
     /// Enable the UTXO index
     pub utxoindex: bool,
 
+    /// Enable the transaction index
+    pub txindex: bool,
+
+    /// Enable the address transaction history index
+    pub addresshistory: bool,
+
     /// Enable RPC commands which affect the state of the node
     pub unsafe_rpc: bool,
 
@@ -66,6 +79,9 @@ pub struct Config {
 
     pub disable_upnp: bool,
 
+    /// Duration of peer bans in seconds when none is specified, 0 meaning bans are permanent
+    pub ban_duration: u64,
+
     /// A scale factor to apply to memory allocation bounds
     pub ram_scale: f64,
 }
@@ -81,8 +97,11 @@ impl Config {
             perf,
             process_genesis: true,
             is_archival: false,
+            retention_period_days: None,
             enable_sanity_checks: false,
             utxoindex: false,
+            txindex: false,
+            addresshistory: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
@@ -94,10 +113,17 @@ impl Config {
             #[cfg(feature = "devnet-prealloc")]
             initial_utxo_set: Default::default(),
             disable_upnp: false,
+            ban_duration: DEFAULT_BAN_DURATION,
             ram_scale: 1.0,
         }
     }
 
+    /// Returns the retention period expressed as a number of blue score units, if such a period is set. Since the
+    /// blue score grows at most at the block rate, the period is covered in full
+    pub fn retention_period_blue_score(&self) -> Option<u64> {
+        self.retention_period_days.map(|days| (days * 86_400_000.0 / self.params.target_time_per_block as f64) as u64)
+    }
+
     pub fn to_builder(&self) -> ConfigBuilder {
         ConfigBuilder { config: self.clone() }
     }
@@ -162,6 +188,11 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn set_retention_period_days(mut self, days: f64) -> Self {
+        self.config.retention_period_days = Some(days);
+        self
+    }
+
     pub fn enable_sanity_checks(mut self) -> Self {
         self.config.enable_sanity_checks = true;
         self
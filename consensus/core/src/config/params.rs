@@ -15,6 +15,41 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
+/// Represents the activation of a consensus fork at a given DAA score. The fork rules apply to
+/// every block whose DAA score is equal to or greater than the activation score.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ForkActivation(u64);
+
+impl ForkActivation {
+    const NEVER: u64 = u64::MAX;
+    const ALWAYS: u64 = 0;
+
+    pub const fn new(daa_score: u64) -> Self {
+        Self(daa_score)
+    }
+
+    /// Returns a fork activation which is never reached
+    pub const fn never() -> Self {
+        Self(Self::NEVER)
+    }
+
+    /// Returns a fork activation which is active from network inception
+    pub const fn always() -> Self {
+        Self(Self::ALWAYS)
+    }
+
+    /// Returns the DAA score at which the fork activates
+    pub const fn daa_score(self) -> u64 {
+        self.0
+    }
+
+    /// Returns whether the fork is active for a block with the given DAA score
+    #[inline]
+    pub fn is_active(self, daa_score: u64) -> bool {
+        daa_score >= self.0
+    }
+}
+
 /// Consensus parameters. Contains settings and configurations which are consensus-sensitive.
 /// Changing one of these on a network node would exclude and prevent it from reaching consensus
 /// with the other unmodified nodes.
@@ -92,7 +127,8 @@ pub struct Params {
     pub max_block_level: BlockLevel,
     pub pruning_proof_m: u64,
 
-    pub hf_relaunch_daa_score: u64,
+    /// Activation of the relaunch hardfork (updated heavy hash matrix multiplication)
+    pub hf_relaunch_activation: ForkActivation,
 }
 
 fn unix_now() -> u64 {
@@ -351,7 +387,8 @@ pub const MAINNET_PARAMS: Params = Params {
     max_block_level: 225,
     pruning_proof_m: 1000,
 
-    hf_relaunch_daa_score: 27_037_930, // Hardfork at GMT Thursday, September 12, 2024 8:00:00 PM
+    // Hardfork at GMT Thursday, September 12, 2024 8:00:00 PM. The new rules apply to blocks with DAA score above 27,037,930
+    hf_relaunch_activation: ForkActivation::new(27_037_931),
 };
 
 pub const TESTNET_PARAMS: Params = Params {
@@ -410,7 +447,7 @@ pub const TESTNET_PARAMS: Params = Params {
     max_block_level: 250,
     pruning_proof_m: Bps::<10>::pruning_proof_m(),
 
-    hf_relaunch_daa_score: u64::MAX,
+    hf_relaunch_activation: ForkActivation::never(),
 };
 
 pub const TESTNET11_PARAMS: Params = Params {
@@ -464,7 +501,7 @@ pub const TESTNET11_PARAMS: Params = Params {
     skip_proof_of_work: false,
     max_block_level: 250,
 
-    hf_relaunch_daa_score: u64::MAX,
+    hf_relaunch_activation: ForkActivation::never(),
 };
 
 pub const SIMNET_PARAMS: Params = Params {
@@ -518,7 +555,7 @@ pub const SIMNET_PARAMS: Params = Params {
     skip_proof_of_work: true, // For simnet only, PoW can be simulated by default
     max_block_level: 250,
 
-    hf_relaunch_daa_score: u64::MAX,
+    hf_relaunch_activation: ForkActivation::never(),
 };
 
 pub const DEVNET_PARAMS: Params = Params {
@@ -576,5 +613,25 @@ pub const DEVNET_PARAMS: Params = Params {
     max_block_level: 250,
     pruning_proof_m: 1000,
 
-    hf_relaunch_daa_score: u64::MAX,
+    hf_relaunch_activation: ForkActivation::never(),
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fork_activation() {
+        // The relaunch hardfork applies to mainnet blocks with DAA score strictly above 27,037,930
+        assert!(!MAINNET_PARAMS.hf_relaunch_activation.is_active(27_037_930));
+        assert!(MAINNET_PARAMS.hf_relaunch_activation.is_active(27_037_931));
+
+        for params in [TESTNET_PARAMS, TESTNET11_PARAMS, SIMNET_PARAMS, DEVNET_PARAMS] {
+            assert!(!params.hf_relaunch_activation.is_active(u64::MAX - 1));
+        }
+
+        assert!(ForkActivation::always().is_active(0));
+        assert!(!ForkActivation::new(10).is_active(9));
+        assert!(ForkActivation::new(10).is_active(10));
+    }
+}
@@ -17,6 +17,24 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --hf-relaunch-daa-score cannot be used on mainnet")]
+    ForkActivationOverrideOnMainnet,
+
+    #[error("Configuration: --archival and --retention-period-days cannot be used together")]
+    MixedArchivalAndRetentionPeriod,
+
+    #[error("Configuration: --retention-period-days must be a positive number of days")]
+    InvalidRetentionPeriod,
+
+    #[error("Configuration: --export-snapshot and --bootstrap-from cannot be used together")]
+    MixedExportSnapshotAndBootstrap,
+
+    #[error("Configuration: --rpccert and --rpckey must be used together")]
+    MixedRpcCertAndKey,
+
+    #[error("Configuration: invalid --pinpeerkey: {0}")]
+    InvalidPinnedPeerKey(String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -1,6 +1,6 @@
-use crate::{hashing, tx::Transaction};
+use crate::{hashing, header::Header, tx::Transaction};
 use kaspa_hashes::Hash;
-use kaspa_merkle::calc_merkle_root;
+use kaspa_merkle::{calc_merkle_root, MerkleProof};
 
 pub fn calc_hash_merkle_root_with_options<'a>(txs: impl ExactSizeIterator<Item = &'a Transaction>, include_mass_field: bool) -> Hash {
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, include_mass_field)))
@@ -10,14 +10,37 @@ pub fn calc_hash_merkle_root<'a>(txs: impl ExactSizeIterator<Item = &'a Transact
     calc_merkle_root(txs.map(|tx| hashing::tx::hash(tx, false)))
 }
 
+/// Verifies that the transaction hash `transaction_hash` is included in the block of `headers[0]` and that every
+/// following header has the previous one as a direct parent. Returns the hash of the last header, which the caller
+/// is expected to check against the selected chain it follows, or `None` if the proof is invalid.
+///
+/// Note that the merkle leaves are full transaction hashes (see [`hashing::tx::hash`]) rather than transaction ids
+pub fn verify_transaction_merkle_proof(transaction_hash: Hash, proof: &MerkleProof, headers: &[Header]) -> Option<Hash> {
+    let (first, rest) = headers.split_first()?;
+    if !proof.verify(transaction_hash, first.hash_merkle_root) {
+        return None;
+    }
+    let mut hash = hashing::header::hash(first);
+    for header in rest {
+        if !header.direct_parents().contains(&hash) {
+            return None;
+        }
+        hash = hashing::header::hash(header);
+    }
+    Some(hash)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::merkle::calc_hash_merkle_root;
+    use crate::merkle::{calc_hash_merkle_root, verify_transaction_merkle_proof};
     use crate::{
+        hashing,
+        header::Header,
         subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
         tx::{scriptvec, ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
     };
     use kaspa_hashes::Hash;
+    use kaspa_merkle::create_merkle_proof;
 
     #[test]
     fn merkle_root_test() {
@@ -250,4 +273,32 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn verify_transaction_merkle_proof_test() {
+        let tx_hashes = (1..=5).map(Hash::from_u64_word).collect::<Vec<_>>();
+        let proof = create_merkle_proof(tx_hashes.iter().copied(), 3).unwrap();
+
+        let mut including = Header::from_precomputed_hash(Default::default(), vec![Hash::from_u64_word(100)]);
+        including.hash_merkle_root = kaspa_merkle::calc_merkle_root(tx_hashes.iter().copied());
+        including.finalize();
+        let mut merging = Header::from_precomputed_hash(Default::default(), vec![Hash::from_u64_word(101), including.hash]);
+        merging.finalize();
+        let mut chain_block = Header::from_precomputed_hash(Default::default(), vec![merging.hash]);
+        chain_block.finalize();
+
+        let headers = vec![including.clone(), merging.clone(), chain_block.clone()];
+        assert_eq!(verify_transaction_merkle_proof(tx_hashes[3], &proof, &headers), Some(chain_block.hash));
+        assert_eq!(verify_transaction_merkle_proof(tx_hashes[3], &proof, &headers[..1]), Some(hashing::header::hash(&including)));
+        assert_eq!(verify_transaction_merkle_proof(tx_hashes[2], &proof, &headers), None);
+        assert_eq!(verify_transaction_merkle_proof(tx_hashes[3], &proof, &[]), None);
+
+        // Headers must be linked by direct parents
+        assert_eq!(verify_transaction_merkle_proof(tx_hashes[3], &proof, &[including.clone(), chain_block.clone()]), None);
+
+        // The cached header hash is not trusted
+        let mut tampered = including;
+        tampered.hash_merkle_root = Hash::from_u64_word(1000);
+        assert_eq!(verify_transaction_merkle_proof(tx_hashes[3], &proof, &[tampered, merging, chain_block]), None);
+    }
 }
@@ -9,10 +9,13 @@ pub mod xoshiro;
 use std::cmp::max;
 
 use crate::matrix::Matrix;
-use kaspa_consensus_core::{hashing, header::Header, BlockLevel};
-use kaspa_consensus_core::config::params::MAINNET_PARAMS;
+use kaspa_consensus_core::{config::params::ForkActivation, hashing, header::Header, BlockLevel};
 use kaspa_hashes::PowHash;
 use kaspa_math::Uint256;
+use num::Float;
+
+/// Target of a Stratum share of difficulty 1, as the mantissa and exponent of 0xffff * 2^208
+const DIFFICULTY_1_TARGET: (u64, i16) = (0xffffu64, 208);
 
 /// State is an intermediate data structure with pre-computed values to speed up mining.
 pub struct State {
@@ -20,47 +23,87 @@ pub struct State {
     pub(crate) target: Uint256,
     // PRE_POW_HASH || TIME || 32 zero byte padding; without NONCE
     pub(crate) hasher: PowHash,
+    // Whether the header is subject to the relaunch hardfork heavy hash rules
+    pub(crate) algo_updated: bool,
 }
 
 impl State {
     #[inline]
-    pub fn new(header: &Header) -> Self {
+    pub fn new(header: &Header, hf_relaunch_activation: ForkActivation) -> Self {
         let target = Uint256::from_compact_target_bits(header.bits);
         // Zero out the time and nonce.
         let pre_pow_hash = hashing::header::hash_override_nonce_time(header, 0, 0);
         // PRE_POW_HASH || TIME || 32 zero byte padding || NONCE
         let hasher = PowHash::new(pre_pow_hash, header.timestamp);
         let matrix = Matrix::generate(pre_pow_hash);
+        let algo_updated = hf_relaunch_activation.is_active(header.daa_score);
 
-        Self { matrix, target, hasher }
+        Self { matrix, target, hasher, algo_updated }
     }
 
     #[inline]
     #[must_use]
     /// PRE_POW_HASH || TIME || 32 zero byte padding || NONCE
-    pub fn calculate_pow(&self, nonce: u64, algo_updated: bool) -> Uint256 {
+    pub fn calculate_pow(&self, nonce: u64) -> Uint256 {
         // Hasher already contains PRE_POW_HASH || TIME || 32 zero byte padding; so only the NONCE is missing
         let hash = self.hasher.clone().finalize_with_nonce(nonce);
-        let hash = self.matrix.heavy_hash(hash, algo_updated);
+        let hash = self.matrix.heavy_hash(hash, self.algo_updated);
         Uint256::from_le_bytes(hash.as_bytes())
     }
 
     #[inline]
     #[must_use]
-    pub fn check_pow(&self, nonce: u64, algo_updated: bool) -> (bool, Uint256) {
-        let pow = self.calculate_pow(nonce, algo_updated);
+    pub fn check_pow(&self, nonce: u64) -> (bool, Uint256) {
+        let pow = self.calculate_pow(nonce);
         // The pow hash must be less or equal than the claimed target.
         (pow <= self.target, pow)
     }
 }
 
-pub fn calc_block_level(header: &Header, max_block_level: BlockLevel) -> BlockLevel {
+/// Converts a Stratum share difficulty into the target which the pow of a share must not exceed.
+/// Returns `None` if the difficulty is not positive or so low that the target overflows.
+pub fn calc_share_target(difficulty: f64) -> Option<Uint256> {
+    if !difficulty.is_finite() || difficulty <= 0.0 {
+        return None;
+    }
+    let (mantissa, exponent, _) = difficulty.recip().integer_decode();
+    let mantissa = mantissa as u128 * DIFFICULTY_1_TARGET.0 as u128;
+    let exponent = DIFFICULTY_1_TARGET.1 as i32 + exponent as i32;
+    if exponent >= 0 {
+        if (u128::BITS - mantissa.leading_zeros()) as i32 + exponent > Uint256::BITS as i32 {
+            return None;
+        }
+        Some(Uint256::from_u128(mantissa) << exponent as u32)
+    } else if exponent > -(u128::BITS as i32) {
+        Some(Uint256::from_u128(mantissa >> -exponent))
+    } else {
+        Some(Uint256::ZERO)
+    }
+}
+
+pub fn calc_block_level(header: &Header, max_block_level: BlockLevel, hf_relaunch_activation: ForkActivation) -> BlockLevel {
     if header.parents_by_level.is_empty() {
         return max_block_level; // Genesis has the max block level
     }
 
-    let state = State::new(header);
-    let (_, pow) = state.check_pow(header.nonce, header.daa_score > MAINNET_PARAMS.hf_relaunch_daa_score);
+    let state = State::new(header, hf_relaunch_activation);
+    let (_, pow) = state.check_pow(header.nonce);
     let signed_block_level = max_block_level as i64 - pow.bits() as i64;
     max(signed_block_level, 0) as BlockLevel
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calc_share_target() {
+        assert_eq!(calc_share_target(1.0), Some(Uint256::from_u64(0xffff) << 208));
+        assert_eq!(calc_share_target(4096.0), Some(Uint256::from_u64(0xffff) << 196));
+        assert_eq!(calc_share_target(0.5), Some(Uint256::from_u64(0xffff) << 209));
+        assert!(calc_share_target(1e-20).is_none());
+        assert!(calc_share_target(0.0).is_none());
+        assert!(calc_share_target(f64::NAN).is_none());
+        assert_eq!(calc_share_target(1e80), Some(Uint256::ZERO));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::matrix::Matrix;
 use js_sys::BigInt;
 use kaspa_consensus_client::Header;
+use kaspa_consensus_core::config::params::Params;
 use kaspa_consensus_core::hashing;
+use kaspa_consensus_core::network::{NetworkId, NetworkIdT, NetworkType};
 use kaspa_hashes::Hash;
 use kaspa_hashes::PowHash;
 use kaspa_math::Uint256;
@@ -21,12 +23,20 @@ pub struct State {
 
 #[wasm_bindgen]
 impl State {
+    /// Creates a PoW state for the given header. The network id is used to determine
+    /// which hardfork rules apply to the header (defaults to mainnet).
     #[wasm_bindgen(constructor)]
-    pub fn new(header: &Header) -> Self {
+    pub fn new(header: &Header, network_id: Option<NetworkIdT>) -> Result<State> {
         // this function replicates crate::State::new() but caches
         // the pre_pow_hash value internally, making it available
         // via the `pre_pow_hash` property getter.
 
+        let network_id = match network_id {
+            Some(network_id) => *NetworkId::try_cast_from(&network_id).map_err(|err| Error::Custom(err.to_string()))?.as_ref(),
+            None => NetworkId::new(NetworkType::Mainnet),
+        };
+        let params = Params::from(network_id);
+
         // obtain locked inner
         let header = header.inner();
 
@@ -36,8 +46,9 @@ impl State {
         // PRE_POW_HASH || TIME || 32 zero byte padding || NONCE
         let hasher = PowHash::new(pre_pow_hash, header.timestamp);
         let matrix = Matrix::generate(pre_pow_hash);
+        let algo_updated = params.hf_relaunch_activation.is_active(header.daa_score);
 
-        Self { inner: crate::State { matrix, target, hasher }, pre_pow_hash }
+        Ok(Self { inner: crate::State { matrix, target, hasher, algo_updated }, pre_pow_hash })
     }
 
     #[wasm_bindgen(getter)]
@@ -48,7 +59,7 @@ impl State {
     #[wasm_bindgen(js_name=checkPow)]
     pub fn check_pow(&self, nonce_jsv: JsValue) -> Result<js_sys::Array> {
         let nonce = nonce_jsv.try_as_u64()?;
-        let (c, v) = self.inner.check_pow(nonce, false); // Won't be activated
+        let (c, v) = self.inner.check_pow(nonce);
         let array = js_sys::Array::new();
         array.push(&JsValue::from(c));
         array.push(&v.to_bigint().map_err(|err| Error::Custom(format!("{err:?}")))?.into());
@@ -20,7 +20,7 @@ use kaspa_consensus_core::{acceptance_data::AcceptanceData, api::{BlockValidatio
     coinbase::CoinbaseResult,
     consensus::{ConsensusError, ConsensusResult},
     tx::TxResult,
-}, errors::{difficulty::DifficultyError, pruning::PruningImportError}, header::Header, muhash::MuHashExtensions, network::NetworkType, pruning::{PruningPointProof, PruningPointsList, PruningPointTrustedData}, trusted::{ExternalGhostdagData, TrustedBlock}, tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry}};
+}, errors::{difficulty::DifficultyError, pruning::PruningImportError}, header::Header, muhash::MuHashExtensions, network::NetworkType, pruning::{PruningPointProof, PruningPointsList, PruningPointTrustedData}, trusted::{ExternalGhostdagData, TrustedBlock}, tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry}, utxo::utxo_diff::UtxoDiff};
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_consensusmanager::{SessionLock, SessionReadGuard};
 use kaspa_core::info;
@@ -46,6 +46,7 @@ use crate::{
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
         },
     },
@@ -826,6 +827,10 @@ impl ConsensusApi for Consensus {
             .collect::<ConsensusResult<Vec<_>>>()
     }
 
+    fn get_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.utxo_diffs_store.get(hash).unwrap_option().ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
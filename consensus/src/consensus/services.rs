@@ -138,7 +138,7 @@ impl ConsensusServices {
             params.deflationary_phase_daa_score,
             params.pre_deflationary_phase_base_subsidy,
             params.target_time_per_block,
-            params.hf_relaunch_daa_score,
+            params.hf_relaunch_activation,
         );
 
         let mass_calculator = MassCalculator::new(
@@ -193,6 +193,7 @@ impl ConsensusServices {
             params.pruning_proof_m,
             params.anticone_finalization_depth(),
             params.ghostdag_k,
+            params.hf_relaunch_activation,
             is_consensus_exiting,
         ));
 
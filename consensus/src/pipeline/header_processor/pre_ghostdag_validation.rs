@@ -10,7 +10,6 @@ use kaspa_consensus_core::BlockLevel;
 use kaspa_core::time::unix_now;
 use kaspa_database::prelude::StoreResultExtensions;
 use std::cmp::max;
-use kaspa_consensus_core::config::params::MAINNET_PARAMS;
 
 impl HeaderProcessor {
     /// Validates the header in isolation including pow check against header declared bits.
@@ -100,8 +99,8 @@ impl HeaderProcessor {
     }
 
     fn check_pow_and_calc_block_level(&self, header: &Header) -> BlockProcessResult<BlockLevel> {
-        let state = kaspa_pow::State::new(header);
-        let (passed, pow) = state.check_pow(header.nonce, header.daa_score > MAINNET_PARAMS.hf_relaunch_daa_score);
+        let state = kaspa_pow::State::new(header, self.hf_relaunch_activation);
+        let (passed, pow) = state.check_pow(header.nonce);
         if passed || self.skip_proof_of_work {
             let signed_block_level = self.max_block_level as i64 - pow.bits() as i64;
             Ok(max(signed_block_level, 0) as BlockLevel)
@@ -23,7 +23,7 @@ use crate::{
             DB,
         },
     },
-    params::Params,
+    params::{ForkActivation, Params},
     pipeline::deps_manager::{BlockProcessingMessage, BlockTask, BlockTaskDependencyManager, TaskId},
     processes::{ghostdag::ordering::SortableBlock, reachability::inquirer as reachability, relations::RelationsStoreExtensions},
 };
@@ -119,6 +119,7 @@ pub struct HeaderProcessor {
     pub(super) mergeset_size_limit: u64,
     pub(super) skip_proof_of_work: bool,
     pub(super) max_block_level: BlockLevel,
+    pub(super) hf_relaunch_activation: ForkActivation,
 
     // DB
     db: Arc<DB>,
@@ -206,6 +207,7 @@ impl HeaderProcessor {
             mergeset_size_limit: params.mergeset_size_limit,
             skip_proof_of_work: params.skip_proof_of_work,
             max_block_level: params.max_block_level,
+            hf_relaunch_activation: params.hf_relaunch_activation,
         }
     }
 
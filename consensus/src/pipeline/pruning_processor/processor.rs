@@ -8,7 +8,7 @@ use crate::{
     model::{
         services::reachability::{MTReachabilityService, ReachabilityService},
         stores::{
-            ghostdag::{CompactGhostdagData, GhostdagStoreReader},
+            ghostdag::{CompactGhostdagData, GhostdagData, GhostdagStoreReader},
             headers::HeaderStoreReader,
             past_pruning_points::PastPruningPointsStoreReader,
             pruning::{PruningStore, PruningStoreReader},
@@ -238,6 +238,29 @@ impl PruningProcessor {
         info!("Pruning point UTXO commitment was verified correctly (sanity test)");
     }
 
+    /// Removes the blocks which are not kept from the mergeset of `hash`, replacing its selected parent with
+    /// origin if it is not kept either. Returns whether the ghostdag data was updated
+    fn prune_ghostdag_mergeset(
+        &self,
+        batch: &mut WriteBatch,
+        hash: Hash,
+        ghostdag: &GhostdagData,
+        is_kept: impl Fn(Hash) -> bool,
+    ) -> bool {
+        if ghostdag.unordered_mergeset().all(&is_kept) {
+            return false;
+        }
+        let mut mutable_ghostdag: ExternalGhostdagData = ghostdag.into();
+        mutable_ghostdag.mergeset_blues.retain(|&h| is_kept(h));
+        mutable_ghostdag.mergeset_reds.retain(|&h| is_kept(h));
+        mutable_ghostdag.blues_anticone_sizes.retain(|&k, _| is_kept(k));
+        if !is_kept(mutable_ghostdag.selected_parent) {
+            mutable_ghostdag.selected_parent = ORIGIN;
+        }
+        self.ghostdag_primary_store.update_batch(batch, hash, &Arc::new(mutable_ghostdag.into())).unwrap();
+        true
+    }
+
     fn prune(&self, new_pruning_point: Hash) {
         if self.config.is_archival {
             warn!("The node is configured as an archival node -- avoiding data pruning. Note this might lead to heavy disk usage.");
@@ -270,6 +293,20 @@ impl PruningProcessor {
             .collect();
         let keep_headers: BlockHashSet = self.past_pruning_points();
 
+        // If a retention period is configured, blocks with a blue score within this period below the pruning point
+        // are retained in full, so that their bodies and acceptance data can still be served. The blue score is used
+        // rather than the DAA score since the merge depth bounding the traversal below is expressed in blue score units
+        let retention_blue_score = self
+            .config
+            .retention_period_blue_score()
+            .map(|period| self.headers_store.get_blue_score(new_pruning_point).unwrap().saturating_sub(period));
+        let is_retained = |hash: Hash| {
+            retention_blue_score.is_some_and(|retention_blue_score| {
+                self.headers_store.get_blue_score(hash).unwrap_option().is_some_and(|blue_score| blue_score >= retention_blue_score)
+            })
+        };
+        let is_kept = |hash: Hash| keep_relations.contains(&hash) || is_retained(hash);
+
         info!("Header and Block pruning: waiting for consensus write permissions...");
 
         let mut prune_guard = self.pruning_lock.blocking_write();
@@ -285,16 +322,8 @@ impl PruningProcessor {
                 let Some(ghostdag) = self.ghostdag_primary_store.get_data(kept).unwrap_option() else {
                     continue;
                 };
-                if ghostdag.unordered_mergeset().any(|h| !keep_relations.contains(&h)) {
-                    let mut mutable_ghostdag: ExternalGhostdagData = ghostdag.as_ref().into();
-                    mutable_ghostdag.mergeset_blues.retain(|h| keep_relations.contains(h));
-                    mutable_ghostdag.mergeset_reds.retain(|h| keep_relations.contains(h));
-                    mutable_ghostdag.blues_anticone_sizes.retain(|k, _| keep_relations.contains(k));
-                    if !keep_relations.contains(&mutable_ghostdag.selected_parent) {
-                        mutable_ghostdag.selected_parent = ORIGIN;
-                    }
+                if self.prune_ghostdag_mergeset(&mut batch, kept, &ghostdag, is_kept) {
                     counter += 1;
-                    self.ghostdag_primary_store.update_batch(&mut batch, kept, &Arc::new(mutable_ghostdag.into())).unwrap();
                 }
             }
             self.db.write(batch).unwrap();
@@ -342,13 +371,32 @@ impl PruningProcessor {
         // Now we traverse the anti-future of the new pruning point starting from origin and going up.
         // The most efficient way to traverse the entire DAG from the bottom-up is via the reachability tree
         let mut queue = VecDeque::<Hash>::from_iter(reachability_read.get_children(ORIGIN).unwrap().iter().copied());
-        let (mut counter, mut traversed) = (0, 0);
+        let (mut counter, mut traversed, mut retained) = (0, 0, 0);
         info!("Header and Block pruning: starting traversal from: {} (genesis: {})", queue.iter().reusable_format(", "), genesis);
         while let Some(current) = queue.pop_front() {
             if reachability_read.is_dag_ancestor_of_result(new_pruning_point, current).unwrap() {
                 continue;
             }
             traversed += 1;
+
+            if let Some(retention_blue_score) = retention_blue_score.filter(|_| is_retained(current)) {
+                retained += 1;
+                // Retained blocks keep their full data, however blocks they merge might get pruned below, in which case
+                // their ghostdag data is updated the same way as for blocks with kept relations
+                if let Some(ghostdag) = self.ghostdag_primary_store.get_data(current).unwrap_option() {
+                    let mut batch = WriteBatch::default();
+                    if self.prune_ghostdag_mergeset(&mut batch, current, &ghostdag, is_kept) {
+                        self.db.write(batch).unwrap();
+                    }
+                }
+                // Tree children have a higher blue score and are thus retained as well. Since blocks only merge blocks
+                // within merge depth, only children close to the retention boundary need to be visited
+                if self.headers_store.get_blue_score(current).unwrap() < retention_blue_score + self.config.merge_depth {
+                    queue.extend(reachability_read.get_children(current).unwrap().iter());
+                }
+                continue;
+            }
+
             // Obtain the tree children of `current` and push them to the queue before possibly being deleted below
             queue.extend(reachability_read.get_children(current).unwrap().iter());
 
@@ -447,7 +495,7 @@ impl PruningProcessor {
         drop(reachability_read);
         drop(prune_guard);
 
-        info!("Header and Block pruning completed: traversed: {}, pruned {}", traversed, counter);
+        info!("Header and Block pruning completed: traversed: {}, pruned {}, retained {}", traversed, counter, retained);
         info!(
             "Header and Block pruning stats: proof size: {}, pruning point and anticone: {}, unique headers in proof and windows: {}, pruning points in history: {}",
             proof.iter().map(|l| l.len()).sum::<usize>(),
@@ -1,6 +1,7 @@
 use std::{convert::TryInto, mem::size_of};
 
 use kaspa_consensus_core::{
+    config::params::ForkActivation,
     BlockHashMap,
     BlockHashSet,
     coinbase::*,
@@ -39,7 +40,7 @@ pub struct CoinbaseManager {
     /// Precomputed subsidy by month table
     subsidy_by_month_table: SubsidyByMonthTable,
 
-    hf_relaunch_daa_score: u64,
+    hf_relaunch_activation: ForkActivation,
 }
 
 /// Struct used to streamline payload parsing
@@ -67,7 +68,7 @@ impl CoinbaseManager {
         deflationary_phase_daa_score: u64,
         pre_deflationary_phase_base_subsidy: u64,
         target_time_per_block: u64,
-        hf_relaunch_daa_score: u64,
+        hf_relaunch_activation: ForkActivation,
     ) -> Self {
         assert!(1000 % target_time_per_block == 0);
         let bps = 1000 / target_time_per_block;
@@ -85,7 +86,7 @@ impl CoinbaseManager {
             target_time_per_block,
             blocks_per_month,
             subsidy_by_month_table,
-            hf_relaunch_daa_score,
+            hf_relaunch_activation,
         }
     }
 
@@ -487,12 +488,12 @@ mod tests {
             params.deflationary_phase_daa_score,
             params.pre_deflationary_phase_base_subsidy,
             params.target_time_per_block,
-            params.hf_relaunch_daa_score,
+            params.hf_relaunch_activation,
         )
     }
 
     /// Return a CoinbaseManager with legacy golang 1 BPS properties
     fn create_legacy_manager() -> CoinbaseManager {
-        CoinbaseManager::new(150, 204, 15778800 - 259200, 1700000000, 1000, 0)
+        CoinbaseManager::new(150, 204, 15778800 - 259200, 1700000000, 1000, ForkActivation::never())
     }
 }
@@ -20,6 +20,7 @@ use kaspa_consensus_core::{
         consensus::{ConsensusError, ConsensusResult},
         pruning::{PruningImportError, PruningImportResult},
     },
+    config::params::ForkActivation,
     header::Header,
     pruning::{PruningPointProof, PruningPointTrustedData},
     trusted::{TrustedBlock, TrustedGhostdagData, TrustedHeader},
@@ -31,7 +32,6 @@ use kaspa_hashes::Hash;
 use kaspa_pow::calc_block_level;
 use kaspa_utils::{binary_heap::BinaryHeapExtensions, vec::VecExtensions};
 use thiserror::Error;
-use kaspa_consensus_core::config::params::MAINNET_PARAMS;
 use crate::{
     consensus::{
         services::{DbDagTraversalManager, DbGhostdagManager, DbParentsManager, DbWindowManager},
@@ -118,6 +118,7 @@ pub struct PruningProofManager {
     pruning_proof_m: u64,
     anticone_finalization_depth: u64,
     ghostdag_k: KType,
+    hf_relaunch_activation: ForkActivation,
 
     is_consensus_exiting: Arc<AtomicBool>,
 }
@@ -137,6 +138,7 @@ impl PruningProofManager {
         pruning_proof_m: u64,
         anticone_finalization_depth: u64,
         ghostdag_k: KType,
+        hf_relaunch_activation: ForkActivation,
         is_consensus_exiting: Arc<AtomicBool>,
     ) -> Self {
         Self {
@@ -168,6 +170,7 @@ impl PruningProofManager {
             pruning_proof_m,
             anticone_finalization_depth,
             ghostdag_k,
+            hf_relaunch_activation,
 
             is_consensus_exiting,
         }
@@ -181,8 +184,8 @@ impl PruningProofManager {
                 continue;
             }
 
-            let state = kaspa_pow::State::new(header);
-            let (_, pow) = state.check_pow(header.nonce, header.daa_score > MAINNET_PARAMS.hf_relaunch_daa_score);
+            let state = kaspa_pow::State::new(header, self.hf_relaunch_activation);
+            let (_, pow) = state.check_pow(header.nonce);
             let signed_block_level = self.max_block_level as i64 - pow.bits() as i64;
             let block_level = max(signed_block_level, 0) as BlockLevel;
             self.headers_store.insert(header.hash, header.clone(), block_level).unwrap();
@@ -299,8 +302,8 @@ impl PruningProofManager {
         let mut up_heap = BinaryHeap::with_capacity(capacity_estimate);
         for header in proof.iter().flatten().cloned() {
             if let Vacant(e) = dag.entry(header.hash) {
-                let state = kaspa_pow::State::new(&header);
-                let (_, pow) = state.check_pow(header.nonce, header.daa_score > MAINNET_PARAMS.hf_relaunch_daa_score); // TODO: Check if pow passes
+                let state = kaspa_pow::State::new(&header, self.hf_relaunch_activation);
+                let (_, pow) = state.check_pow(header.nonce); // TODO: Check if pow passes
                 let signed_block_level = self.max_block_level as i64 - pow.bits() as i64;
                 let block_level = max(signed_block_level, 0) as BlockLevel;
                 self.headers_store.insert(header.hash, header.clone(), block_level).unwrap();
@@ -398,7 +401,7 @@ impl PruningProofManager {
         let headers_estimate = self.estimate_proof_unique_size(proof);
         let proof_pp_header = proof[0].last().expect("checked if empty");
         let proof_pp = proof_pp_header.hash;
-        let proof_pp_level = calc_block_level(proof_pp_header, self.max_block_level);
+        let proof_pp_level = calc_block_level(proof_pp_header, self.max_block_level, self.hf_relaunch_activation);
         let (db_lifetime, db) = kaspa_database::create_temp_db!(ConnBuilder::default().with_files_limit(10));
         let cache_policy = CachePolicy::Count(2 * self.pruning_proof_m as usize);
         let headers_store =
@@ -455,7 +458,7 @@ impl PruningProofManager {
             let level_idx = level as usize;
             let mut selected_tip = None;
             for (i, header) in proof[level as usize].iter().enumerate() {
-                let header_level = calc_block_level(header, self.max_block_level);
+                let header_level = calc_block_level(header, self.max_block_level, self.hf_relaunch_activation);
                 if header_level < level {
                     return Err(PruningImportError::PruningProofWrongBlockLevel(header.hash, header_level, level));
                 }
@@ -22,8 +22,88 @@ pub fn calc_merkle_root(hashes: impl ExactSizeIterator<Item = Hash>) -> Hash {
     merkles.last().unwrap().unwrap()
 }
 
+/// A proof of inclusion of a leaf in a merkle tree built by [`calc_merkle_root`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Position of the leaf amongst the tree leaves
+    pub index: usize,
+    /// The sibling hashes along the path from the leaf up to the root, with missing siblings set to `ZERO_HASH`
+    pub siblings: Vec<Hash>,
+}
+
+impl MerkleProof {
+    pub fn new(index: usize, siblings: Vec<Hash>) -> Self {
+        Self { index, siblings }
+    }
+
+    /// Calculates the merkle root implied by this proof for `leaf`, or `None` if the index does not fit the proof depth
+    pub fn calc_root(&self, leaf: Hash) -> Option<Hash> {
+        if self.index.checked_shr(self.siblings.len() as u32).unwrap_or(0) != 0 {
+            return None;
+        }
+        let mut index = self.index;
+        let mut hash = leaf;
+        for &sibling in self.siblings.iter() {
+            hash = if index & 1 == 0 { merkle_hash(hash, sibling) } else { merkle_hash(sibling, hash) };
+            index >>= 1;
+        }
+        Some(hash)
+    }
+
+    /// Returns whether `leaf` is included in the merkle tree with root `root` at this proof index
+    pub fn verify(&self, leaf: Hash, root: Hash) -> bool {
+        self.calc_root(leaf) == Some(root)
+    }
+}
+
+/// Builds a proof of inclusion of the leaf at `index` in the merkle tree of `hashes`, or `None` if `index` is out of bounds
+pub fn create_merkle_proof(hashes: impl ExactSizeIterator<Item = Hash>, index: usize) -> Option<MerkleProof> {
+    if index >= hashes.len() {
+        return None;
+    }
+    let mut level: Vec<Hash> = hashes.collect();
+    let mut level_index = index;
+    let mut siblings = Vec::new();
+    // Levels are padded on the fly: a missing right sibling hashes as `ZERO_HASH` while fully
+    // missing subtrees are never reached from an existing leaf
+    while level.len() > 1 {
+        siblings.push(level.get(level_index ^ 1).copied().unwrap_or(ZERO_HASH));
+        level = level.chunks(2).map(|pair| merkle_hash(pair[0], pair.get(1).copied().unwrap_or(ZERO_HASH))).collect();
+        level_index >>= 1;
+    }
+    Some(MerkleProof::new(index, siblings))
+}
+
 fn merkle_hash(left: Hash, right: Hash) -> Hash {
     let mut hasher = MerkleBranchHash::new();
     hasher.update(left).update(right);
     hasher.finalize()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_proofs() {
+        for count in 1..=17u64 {
+            let hashes = (0..count).map(|i| Hash::from_u64_word(i + 1)).collect::<Vec<_>>();
+            let root = calc_merkle_root(hashes.iter().copied());
+            for (index, &leaf) in hashes.iter().enumerate() {
+                let proof = create_merkle_proof(hashes.iter().copied(), index).unwrap();
+                assert_eq!(proof.siblings.len(), count.next_power_of_two().trailing_zeros() as usize);
+                assert!(proof.verify(leaf, root), "leaf {index} of {count}");
+                assert!(!proof.verify(Hash::from_u64_word(1000), root));
+
+                // A proof is bound to the position of the leaf
+                let moved = MerkleProof::new(index ^ 1, proof.siblings.clone());
+                if count > 1 {
+                    assert!(!moved.verify(leaf, root));
+                }
+                let out_of_depth = MerkleProof::new(index + (1 << proof.siblings.len()), proof.siblings.clone());
+                assert_eq!(out_of_depth.calc_root(leaf), None);
+            }
+            assert_eq!(create_merkle_proof(hashes.iter().copied(), hashes.len()), None);
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub use standard::*;
 use crate::caches::Cache;
 use crate::data_stack::{DataStack, Stack};
 use crate::opcodes::{deserialize_next_opcode, OpCodeImplementation};
+use crate::trace::{ScriptTracer, TraceStep, TracedScript};
 
 pub mod caches;
 mod data_stack;
@@ -23,6 +24,7 @@ pub mod opcodes;
 pub mod script_builder;
 pub mod script_class;
 pub mod standard;
+pub mod trace;
 
 pub mod prelude {
     pub use super::standard::*;
@@ -81,6 +83,9 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction> {
     cond_stack: Vec<OpCond>, // Following if stacks, and whether it is running
 
     num_ops: i32,
+
+    // Optional hook recording every processed opcode
+    tracer: Option<&'a mut dyn ScriptTracer>,
 }
 
 fn parse_script<T: VerifiableTransaction>(
@@ -153,6 +158,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             sig_cache,
             cond_stack: vec![],
             num_ops: 0,
+            tracer: None,
         }
     }
 
@@ -177,6 +183,7 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
                 sig_cache,
                 cond_stack: Default::default(),
                 num_ops: 0,
+                tracer: None,
             }),
             false => Err(TxScriptError::InvalidIndex(input_idx, tx.tx().inputs.len())),
         }
@@ -191,9 +198,16 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             sig_cache,
             cond_stack: Default::default(),
             num_ops: 0,
+            tracer: None,
         }
     }
 
+    /// Sets a tracer called for every opcode processed by the engine
+    pub fn with_tracer(mut self, tracer: &'a mut dyn ScriptTracer) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
     #[inline]
     pub fn is_executing(&self) -> bool {
         return self.cond_stack.is_empty() || *self.cond_stack.last().expect("Checked not empty") == OpCond::True;
@@ -221,28 +235,28 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
         }
     }
 
-    fn execute_script(&mut self, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
-        let script_result = parse_script(script).try_for_each(|opcode| {
+    fn execute_script(&mut self, script: &[u8], traced_script: TracedScript, verify_only_push: bool) -> Result<(), TxScriptError> {
+        let script_result = parse_script(script).enumerate().try_for_each(|(index, opcode)| {
             let opcode = opcode?;
-            if opcode.is_disabled() {
-                return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
-            }
-
-            if opcode.always_illegal() {
-                return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
-            }
-
-            if verify_only_push && !opcode.is_push_opcode() {
-                return Err(TxScriptError::SignatureScriptNotPushOnly);
+            if self.tracer.is_none() {
+                return self.execute_step(opcode, verify_only_push);
             }
 
-            self.execute_opcode(opcode)?;
-
-            let combined_size = self.astack.len() + self.dstack.len();
-            if combined_size > MAX_STACK_SIZE {
-                return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
-            }
-            Ok(())
+            let executed = self.is_executing() || opcode.is_conditional();
+            let (value, data) = (opcode.value(), opcode.get_data().to_vec());
+            let result = self.execute_step(opcode, verify_only_push);
+            let step = TraceStep {
+                script: traced_script,
+                index,
+                opcode: value,
+                data,
+                executed,
+                cond_stack: self.cond_stack.clone(),
+                data_stack: self.dstack.clone(),
+                error: result.as_ref().err().cloned(),
+            };
+            self.tracer.as_deref_mut().expect("checked above").on_step(step);
+            result
         });
 
         // Moving between scripts - we can't be inside an if
@@ -257,6 +271,28 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
         script_result
     }
 
+    fn execute_step(&mut self, opcode: Box<dyn OpCodeImplementation<T>>, verify_only_push: bool) -> Result<(), TxScriptError> {
+        if opcode.is_disabled() {
+            return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
+        }
+
+        if opcode.always_illegal() {
+            return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
+        }
+
+        if verify_only_push && !opcode.is_push_opcode() {
+            return Err(TxScriptError::SignatureScriptNotPushOnly);
+        }
+
+        self.execute_opcode(opcode)?;
+
+        let combined_size = self.astack.len() + self.dstack.len();
+        if combined_size > MAX_STACK_SIZE {
+            return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
+        }
+        Ok(())
+    }
+
     pub fn execute(&mut self) -> Result<(), TxScriptError> {
         let (scripts, is_p2sh) = match &self.script_source {
             ScriptSource::TxInput { input, utxo_entry, is_p2sh, .. } => {
@@ -295,14 +331,19 @@ impl<'a, T: VerifiableTransaction> TxScriptEngine<'a, T> {
             if is_p2sh && idx == 1 {
                 saved_stack = Some(self.dstack.clone());
             }
-            self.execute_script(s, verify_only_push)
+            let traced_script = match self.script_source {
+                ScriptSource::TxInput { .. } if idx == 0 => TracedScript::SignatureScript,
+                ScriptSource::TxInput { .. } => TracedScript::ScriptPublicKey,
+                ScriptSource::StandAloneScripts(_) => TracedScript::StandAlone(idx),
+            };
+            self.execute_script(s, traced_script, verify_only_push)
         })?;
 
         if is_p2sh {
             self.check_error_condition(false)?;
             self.dstack = saved_stack.ok_or(TxScriptError::EmptyStack)?;
             let script = self.dstack.pop().ok_or(TxScriptError::EmptyStack)?;
-            self.execute_script(script.as_slice(), false)?
+            self.execute_script(script.as_slice(), TracedScript::RedeemScript, false)?
         }
 
         self.check_error_condition(true)?;
@@ -0,0 +1,137 @@
+//!
+//! Step tracing of script execution, recording every opcode run by a [`TxScriptEngine`] along with the
+//! resulting condition and data stacks, in order to find out which opcode and stack state caused a script
+//! to fail.
+//!
+
+use crate::{
+    caches::Cache,
+    opcodes::{opcode_name, OpCond},
+    TxScriptEngine,
+};
+use kaspa_consensus_core::{hashing::sighash::SigHashReusedValues, tx::VerifiableTransaction};
+use kaspa_txscript_errors::TxScriptError;
+
+/// The script an executed opcode belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TracedScript {
+    SignatureScript,
+    ScriptPublicKey,
+    /// The P2SH script revealed by the signature script
+    RedeemScript,
+    /// A script executed on its own, by its index amongst the engine scripts
+    StandAlone(usize),
+}
+
+/// A single opcode processed by the engine
+#[derive(Clone, Debug)]
+pub struct TraceStep {
+    pub script: TracedScript,
+    /// Position of the opcode within its script
+    pub index: usize,
+    pub opcode: u8,
+    /// Data pushed by the opcode, if it is a push opcode
+    pub data: Vec<u8>,
+    /// Whether the opcode was executed, as opposed to skipped by a non-executing branch
+    pub executed: bool,
+    /// Condition stack after processing the opcode
+    pub cond_stack: Vec<OpCond>,
+    /// Data stack after processing the opcode
+    pub data_stack: Vec<Vec<u8>>,
+    /// The error raised by the opcode, which stops script execution
+    pub error: Option<TxScriptError>,
+}
+
+impl TraceStep {
+    pub fn opcode_name(&self) -> &'static str {
+        opcode_name(self.opcode)
+    }
+}
+
+/// A hook called by [`TxScriptEngine`] for every processed opcode
+pub trait ScriptTracer {
+    fn on_step(&mut self, step: TraceStep);
+}
+
+/// A tracer collecting all steps
+#[derive(Clone, Debug, Default)]
+pub struct ScriptTrace {
+    pub steps: Vec<TraceStep>,
+}
+
+impl ScriptTracer for ScriptTrace {
+    fn on_step(&mut self, step: TraceStep) {
+        self.steps.push(step);
+    }
+}
+
+/// The execution trace of the scripts of a transaction input
+#[derive(Clone, Debug)]
+pub struct InputTrace {
+    pub input_index: usize,
+    pub steps: Vec<TraceStep>,
+    pub result: Result<(), TxScriptError>,
+}
+
+/// Executes the scripts of every input of a populated transaction, returning a full execution trace per input
+pub fn trace_transaction_scripts<T: VerifiableTransaction>(tx: &T) -> Vec<InputTrace> {
+    let sig_cache = Cache::new(0);
+    let mut reused_values = SigHashReusedValues::new();
+    tx.populated_inputs()
+        .enumerate()
+        .map(|(input_index, (input, entry))| {
+            let mut trace = ScriptTrace::default();
+            let result = TxScriptEngine::from_transaction_input(tx, input, input_index, entry, &mut reused_values, &sig_cache)
+                .and_then(|engine| engine.with_tracer(&mut trace).execute());
+            InputTrace { input_index, steps: trace.steps, result }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcodes::codes::{OpData1, OpElse, OpEndIf, OpFalse, OpIf, OpTrue, OpVerify};
+    use kaspa_consensus_core::tx::PopulatedTransaction;
+
+    fn trace_script(script: &[u8]) -> (Vec<TraceStep>, Result<(), TxScriptError>) {
+        let sig_cache = Cache::new(0);
+        let mut reused_values = SigHashReusedValues::new();
+        let mut trace = ScriptTrace::default();
+        let result = TxScriptEngine::<PopulatedTransaction>::from_script(script, &mut reused_values, &sig_cache)
+            .with_tracer(&mut trace)
+            .execute();
+        (trace.steps, result)
+    }
+
+    #[test]
+    fn test_trace_conditional_script() {
+        let (steps, result) = trace_script(&[OpTrue, OpIf, OpData1, 0x20, OpElse, OpData1, 0x21, OpEndIf]);
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            steps.iter().map(|s| s.opcode_name()).collect::<Vec<_>>(),
+            ["OpTrue", "OpIf", "OpData1", "OpElse", "OpData1", "OpEndIf"]
+        );
+        assert!(steps.iter().all(|s| s.script == TracedScript::StandAlone(0) && s.error.is_none()));
+        assert_eq!(steps.iter().map(|s| s.index).collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
+
+        // The second push is skipped by the non-executing branch
+        assert!(steps[2].executed && !steps[4].executed);
+        assert_eq!(steps[2].data, vec![0x20]);
+        assert_eq!(steps[2].cond_stack, vec![OpCond::True]);
+        assert_eq!(steps[4].cond_stack, vec![OpCond::False]);
+        assert_eq!(steps[4].data_stack, vec![vec![0x20]]);
+        assert!(steps[5].cond_stack.is_empty());
+    }
+
+    #[test]
+    fn test_trace_failing_script() {
+        let (steps, result) = trace_script(&[OpTrue, OpFalse, OpVerify, OpTrue]);
+        assert_eq!(result, Err(TxScriptError::VerifyError));
+        // Execution stops at the failing opcode, which is recorded along with its error
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[2].opcode, OpVerify);
+        assert_eq!(steps[2].error, Some(TxScriptError::VerifyError));
+        assert_eq!(steps[2].data_stack, vec![vec![1]]);
+    }
+}
@@ -48,11 +48,20 @@ pub enum DatabaseStorePrefixes {
     // ---- Components ----
     Addresses = 128,
     BannedAddresses = 129,
+    BannedSubnets = 130,
 
     // ---- Indexes ----
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    TxIndexAcceptance = 195,
+    TxIndexInclusions = 196,
+    TxIndexChainBlocks = 197,
+    TxIndexSink = 198,
+    AddressHistory = 199,
+    AddressHistoryCounts = 200,
+    AddressHistoryChainBlocks = 201,
+    AddressHistorySink = 202,
 
     // ---- Separator ----
     /// Reserved as a separator
@@ -100,6 +100,12 @@ where
         Ok(())
     }
 
+    /// Deletes all entries in the store using the underlying rocksdb `delete_range` operation
+    pub fn delete_all(&self, writer: impl DbWriter) -> Result<(), StoreError> {
+        self.cache.remove_all();
+        self.inner.delete_all(writer)
+    }
+
     pub fn prefix(&self) -> &[u8] {
         self.inner.prefix()
     }
@@ -151,6 +157,13 @@ where
         Ok(())
     }
 
+    pub fn delete_all(&self, mut writer: impl DbWriter) -> Result<(), StoreError> {
+        let db_key = DbKey::prefix_only(&self.prefix);
+        let (from, to) = rocksdb::PrefixRange(db_key.as_ref()).into_bounds();
+        writer.delete_range(from.unwrap(), to.unwrap())?;
+        Ok(())
+    }
+
     fn seek_iterator(
         &self,
         key: TKey,
@@ -0,0 +1,104 @@
+use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData,
+    tx::{ScriptPublicKey, TransactionId},
+};
+use kaspa_consensusmanager::spawn_blocking;
+use kaspa_database::prelude::StoreResult;
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{
+    errors::AddressHistoryResult,
+    model::{AddressHistoryChanges, AddressHistoryEntry},
+};
+
+///Address history API targeted at retrieval calls.
+pub trait AddressHistoryApi: Send + Sync + Debug {
+    /// Retrieve at most `limit` entries of the history of a script public key, in ascending
+    /// accepting DAA score order, starting at `start_daa_score`. If `start_transaction_id` is set,
+    /// the entries start right after the one of this transaction.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        start_daa_score: u64,
+        start_transaction_id: Option<TransactionId>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>>;
+
+    /// Retrieve the number of entries in the history of a script public key.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_history_count(&self, script_public_key: &ScriptPublicKey) -> StoreResult<u64>;
+
+    /// Retrieve the sink the address history is synced with (used for testing purposes).
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the address history's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> AddressHistoryResult<bool>;
+
+    /// Update the address history with the given virtual selected chain changes, returning the
+    /// history entries that were added and removed.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryResult<AddressHistoryChanges>;
+
+    /// Resync the address history from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> AddressHistoryResult<()>;
+}
+
+/// Async proxy for the address history index
+#[derive(Debug, Clone)]
+pub struct AddressHistoryProxy {
+    inner: Arc<RwLock<dyn AddressHistoryApi>>,
+}
+
+impl AddressHistoryProxy {
+    pub fn new(inner: Arc<RwLock<dyn AddressHistoryApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_history(
+        self,
+        script_public_key: ScriptPublicKey,
+        start_daa_score: u64,
+        start_transaction_id: Option<TransactionId>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        spawn_blocking(move || self.inner.read().get_history(&script_public_key, start_daa_score, start_transaction_id, limit))
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_history_count(self, script_public_key: ScriptPublicKey) -> StoreResult<u64> {
+        spawn_blocking(move || self.inner.read().get_history_count(&script_public_key)).await.unwrap()
+    }
+
+    pub async fn update(
+        self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryResult<AddressHistoryChanges> {
+        spawn_blocking(move || {
+            self.inner.write().update(added_chain_block_hashes, removed_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,18 @@
+use thiserror::Error;
+
+use crate::IDENT;
+use kaspa_consensus_core::errors::consensus::ConsensusError;
+use kaspa_database::prelude::StoreError;
+
+/// Errors originating from the [`AddressHistory`].
+#[derive(Error, Debug)]
+pub enum AddressHistoryError {
+    #[error("[{IDENT}]: {0}")]
+    StoreAccessError(#[from] StoreError),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+}
+
+/// Results originating from the [`AddressHistory`].
+pub type AddressHistoryResult<T> = Result<T, AddressHistoryError>;
@@ -0,0 +1 @@
+pub use kaspa_index_core::address_history::*;
@@ -0,0 +1,324 @@
+use crate::{
+    api::AddressHistoryApi,
+    errors::{AddressHistoryError, AddressHistoryResult},
+    model::{AddressHistoryChanges, AddressHistoryEntries, AddressHistoryEntry},
+    stores::store_manager::Store,
+    IDENT,
+};
+use kaspa_consensus_core::{
+    acceptance_data::AcceptanceData,
+    api::ConsensusApi,
+    tx::{ScriptPublicKey, TransactionId, TransactionOutpoint},
+};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use kaspa_core::{info, trace};
+use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024; // Amount of chain blocks whose acceptance data is fetched from consensus at once during a resync.
+
+/// AddressHistory indexes, per [`ScriptPublicKey`], every transaction accepted by the virtual selected chain which
+/// created or spent an output of the script public key.
+///
+/// Unlike the txindex, the history is retained below the pruning point. It is however only rebuilt from the pruning
+/// point on when a resync is required.
+/// Note: The AddressHistory struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `addresshistory::core::api::AddressHistoryApi` for proper thread safety.
+pub struct AddressHistory {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl AddressHistory {
+    /// Creates a new [`AddressHistory`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> AddressHistoryResult<Arc<RwLock<Self>>> {
+        let mut address_history = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !address_history.is_synced()? {
+            address_history.catch_up()?;
+        }
+        let address_history = Arc::new(RwLock::new(address_history));
+        consensus_manager
+            .register_consensus_reset_handler(Arc::new(AddressHistoryConsensusResetHandler::new(Arc::downgrade(&address_history))));
+        Ok(address_history)
+    }
+
+    /// Collects the history entries of the transactions accepted by the mergeset of chain block `hash`.
+    ///
+    /// The spent outputs are resolved through the UTXO diff of the chain block, except for the ones created within the
+    /// same mergeset which cancel out of it. Mergeset blocks whose body is not available anymore (i.e. below the pruning
+    /// point) are skipped.
+    fn collect_chain_block_entries(
+        consensus: &dyn ConsensusApi,
+        hash: Hash,
+        daa_score: u64,
+        acceptance_data: &AcceptanceData,
+    ) -> AddressHistoryResult<AddressHistoryEntries> {
+        let utxo_diff = consensus.get_block_utxo_diff(hash)?;
+        let mut accepted = Vec::new();
+        for mergeset_block_data in acceptance_data.iter() {
+            let Ok(block) = consensus.get_block(mergeset_block_data.block_hash) else {
+                trace!("[{0}] skipping merged block {1} with no body", IDENT, mergeset_block_data.block_hash);
+                continue;
+            };
+            accepted.extend(
+                mergeset_block_data
+                    .accepted_transactions
+                    .iter()
+                    .map(|entry| (block.transactions.clone(), entry.index_within_block as usize)),
+            );
+        }
+
+        let created: HashMap<TransactionOutpoint, (&ScriptPublicKey, u64)> = accepted
+            .iter()
+            .map(|(transactions, index)| &transactions[*index])
+            .flat_map(|transaction| {
+                let transaction_id = transaction.id();
+                transaction.outputs.iter().enumerate().map(move |(index, output)| {
+                    (TransactionOutpoint::new(transaction_id, index as u32), (&output.script_public_key, output.value))
+                })
+            })
+            .collect();
+
+        let mut entries: HashMap<(&ScriptPublicKey, TransactionId), AddressHistoryEntry> = HashMap::new();
+        let new_entry = |transaction_id| AddressHistoryEntry {
+            transaction_id,
+            accepting_block_hash: hash,
+            accepting_daa_score: daa_score,
+            received: 0,
+            spent: 0,
+        };
+        for transaction in accepted.iter().map(|(transactions, index)| &transactions[*index]) {
+            let transaction_id = transaction.id();
+            for input in transaction.inputs.iter() {
+                let spent = match utxo_diff.remove.get(&input.previous_outpoint) {
+                    Some(utxo_entry) => (&utxo_entry.script_public_key, utxo_entry.amount),
+                    None => match created.get(&input.previous_outpoint) {
+                        Some(spent) => *spent,
+                        None => {
+                            trace!("[{0}] skipping unknown outpoint {1} spent by {2}", IDENT, input.previous_outpoint, transaction_id);
+                            continue;
+                        }
+                    },
+                };
+                entries.entry((spent.0, transaction_id)).or_insert_with(|| new_entry(transaction_id)).spent += spent.1;
+            }
+            for output in transaction.outputs.iter() {
+                entries.entry((&output.script_public_key, transaction_id)).or_insert_with(|| new_entry(transaction_id)).received +=
+                    output.value;
+            }
+        }
+        Ok(entries.into_iter().map(|((script_public_key, _), entry)| (script_public_key.clone(), entry)).collect())
+    }
+
+    /// Indexes the history entries of chain block `hash`, returning them.
+    fn index_chain_block(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        hash: Hash,
+        acceptance_data: &AcceptanceData,
+    ) -> AddressHistoryResult<Arc<AddressHistoryEntries>> {
+        let daa_score = consensus.get_header(hash)?.daa_score;
+        let entries = Arc::new(Self::collect_chain_block_entries(consensus, hash, daa_score, acceptance_data)?);
+        trace!("[{0}] indexing chain block {1} with {2} history entries", IDENT, hash, entries.len());
+        self.store.add_chain_block(hash, daa_score, entries.clone())?;
+        Ok(entries)
+    }
+
+    /// Indexes the chain blocks in `added`, fetching their acceptance data from consensus in chunks.
+    fn index_chain_blocks(&mut self, consensus: &dyn ConsensusApi, added: &[Hash]) -> AddressHistoryResult<()> {
+        for chunk in added.chunks(RESYNC_CHUNK_SIZE) {
+            trace!("[{0}] syncing batch of {1} chain blocks from consensus db", IDENT, chunk.len());
+            let acceptance_data = consensus.get_blocks_acceptance_data(chunk)?;
+            for (hash, acceptance_data) in chunk.iter().copied().zip(acceptance_data.iter()) {
+                self.index_chain_block(consensus, hash, acceptance_data)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Brings the address history up to the sink of consensus by applying the chain changes since its own sink,
+    /// resyncing only when these changes are not available anymore so that the history below the pruning point is kept.
+    fn catch_up(&mut self) -> AddressHistoryResult<()> {
+        let sink = match self.store.get_sink() {
+            Ok(sink) => sink,
+            Err(StoreError::KeyNotFound(_)) => return self.resync(),
+            Err(err) => return Err(err.into()),
+        };
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let pruning_point_daa_score = session.get_header(session.pruning_point())?.daa_score;
+        let chain_path = match session.get_header(sink) {
+            Ok(header) if header.daa_score >= pruning_point_daa_score => session.get_virtual_chain_from_block(sink).ok(),
+            _ => None,
+        };
+        let Some(chain_path) = chain_path else {
+            drop(session);
+            return self.resync();
+        };
+
+        info!("Catching up the address history with {} chain blocks...", chain_path.added.len());
+        for hash in chain_path.removed.iter().copied() {
+            let daa_score = session.get_header(hash)?.daa_score;
+            self.store.remove_chain_block(hash, daa_score)?;
+        }
+        self.index_chain_blocks(&*session, &chain_path.added)?;
+        if let Some(sink) = chain_path.added.last() {
+            self.store.set_sink(*sink)?;
+        }
+        self.prune(&*session)
+    }
+
+    /// Forgets the chain blocks below the current pruning point of consensus.
+    fn prune(&mut self, consensus: &dyn ConsensusApi) -> AddressHistoryResult<()> {
+        let pruning_point_daa_score = consensus.get_header(consensus.pruning_point())?.daa_score;
+        let pruned = self.store.prune(pruning_point_daa_score)?;
+        if pruned > 0 {
+            trace!("[{0}] pruned {1} chain blocks below DAA score {2}", IDENT, pruned, pruning_point_daa_score);
+        }
+        Ok(())
+    }
+}
+
+impl AddressHistoryApi for AddressHistory {
+    /// Retrieve a page of the history of a script public key from the address history db.
+    fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        start_daa_score: u64,
+        start_transaction_id: Option<TransactionId>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        trace!("[{0}] retrieving {1} history entries from DAA score {2}", IDENT, limit, start_daa_score);
+
+        self.store.get_history(script_public_key, start_daa_score, start_transaction_id, limit)
+    }
+
+    /// Retrieve the number of entries in the history of a script public key from the address history db.
+    fn get_history_count(&self, script_public_key: &ScriptPublicKey) -> StoreResult<u64> {
+        trace!("[{0}] retrieving history count", IDENT);
+
+        self.store.get_count(script_public_key)
+    }
+
+    /// Retrieve the stored sink of the address history.
+    fn get_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [AddressHistory] is sync'd. This is done via comparing the committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the address history and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> AddressHistoryResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(sink) => {
+                let res = sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                // Means the sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(AddressHistoryError::StoreAccessError(other_store_errors)),
+        }
+    }
+
+    /// Updates the [AddressHistory] with the virtual selected chain changes supplied:
+    /// 1) Removes the history entries added on behalf of the removed chain blocks.
+    /// 2) Adds the history entries of the added chain blocks and saves the new sink.
+    /// 3) Forgets the chain blocks that fell below the pruning point.
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> AddressHistoryResult<AddressHistoryChanges> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let mut changes = AddressHistoryChanges::default();
+        for hash in removed_chain_block_hashes.iter().copied() {
+            let daa_score = session.get_header(hash)?.daa_score;
+            if let Some(entries) = self.store.remove_chain_block(hash, daa_score)? {
+                changes.removed.extend(entries.iter().cloned());
+            }
+        }
+
+        for (hash, acceptance_data) in added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data.iter()) {
+            changes.added.extend(self.index_chain_block(&*session, hash, acceptance_data)?.iter().cloned());
+        }
+
+        if let Some(sink) = added_chain_block_hashes.last() {
+            self.store.set_sink(*sink)?;
+        }
+
+        self.prune(&*session)?;
+        Ok(changes)
+    }
+
+    /// Deletes and reinstates the address history database, syncing it from scratch via the consensus database.
+    ///
+    /// **Notes:**
+    /// 1) Only the virtual selected chain above the pruning point is indexed, older data being unavailable.
+    /// 2) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> AddressHistoryResult<()> {
+        info!("Resyncing the address history...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let pruning_point = session.pruning_point();
+        let chain_path = session.get_virtual_chain_from_block(pruning_point)?;
+        self.index_chain_blocks(&*session, &chain_path.added)?;
+
+        let sink = chain_path.added.last().copied().unwrap_or(pruning_point);
+        trace!("[{0}] committing sink {1} from consensus db", IDENT, sink);
+        self.store.set_sink(sink)?;
+
+        Ok(())
+    }
+}
+
+impl Debug for AddressHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddressHistory").finish()
+    }
+}
+
+struct AddressHistoryConsensusResetHandler {
+    address_history: Weak<RwLock<AddressHistory>>,
+}
+
+impl AddressHistoryConsensusResetHandler {
+    fn new(address_history: Weak<RwLock<AddressHistory>>) -> Self {
+        Self { address_history }
+    }
+}
+
+impl ConsensusResetHandler for AddressHistoryConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(address_history) = self.address_history.upgrade() {
+            address_history.write().resync().unwrap();
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::ScriptPublicKey;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use rocksdb::WriteBatch;
+
+use crate::stores::history::ScriptPublicKeyBucket;
+
+/// Reader API for `AddressHistoryCountsStore`.
+pub trait AddressHistoryCountsStoreReader {
+    /// Returns the number of history entries of a script public key.
+    fn get(&self, script_public_key: &ScriptPublicKey) -> StoreResult<u64>;
+}
+
+pub trait AddressHistoryCountsStore: AddressHistoryCountsStoreReader {
+    /// Sets the number of history entries of a script public key, removing it when zero.
+    fn set(&mut self, batch: &mut WriteBatch, script_public_key: &ScriptPublicKey, count: u64) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressHistoryCountsStore` trait, so that counting the history of
+/// a script public key does not require iterating over it.
+#[derive(Clone)]
+pub struct DbAddressHistoryCountsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<ScriptPublicKeyBucket, u64>,
+}
+
+impl DbAddressHistoryCountsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressHistoryCounts.into()) }
+    }
+}
+
+impl AddressHistoryCountsStoreReader for DbAddressHistoryCountsStore {
+    fn get(&self, script_public_key: &ScriptPublicKey) -> StoreResult<u64> {
+        match self.access.read(script_public_key.into()) {
+            Ok(count) => Ok(count),
+            Err(StoreError::KeyNotFound(_)) => Ok(0),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl AddressHistoryCountsStore for DbAddressHistoryCountsStore {
+    fn set(&mut self, batch: &mut WriteBatch, script_public_key: &ScriptPublicKey, count: u64) -> StoreResult<()> {
+        match count {
+            0 => self.access.delete(BatchDbWriter::new(batch), script_public_key.into()),
+            count => self.access.write(BatchDbWriter::new(batch), script_public_key.into(), count),
+        }
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
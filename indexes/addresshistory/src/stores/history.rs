@@ -0,0 +1,145 @@
+use std::{fmt::Display, mem::size_of, sync::Arc};
+
+use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion, TransactionId};
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::HASH_SIZE;
+use rocksdb::WriteBatch;
+
+use crate::model::AddressHistoryEntry;
+
+/// [`ScriptPublicKeyBucket`] grouping the history of a script public key.
+/// Consists of 2 bytes of little endian [ScriptPublicKeyVersion], followed by 8 bytes of little endian script length
+/// and the script itself, so that no bucket is the prefix of another one.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct ScriptPublicKeyBucket(Vec<u8>);
+
+impl From<&ScriptPublicKey> for ScriptPublicKeyBucket {
+    fn from(script_public_key: &ScriptPublicKey) -> Self {
+        let script = script_public_key.script();
+        let mut bytes = Vec::with_capacity(size_of::<ScriptPublicKeyVersion>() + size_of::<u64>() + script.len());
+        bytes.extend_from_slice(&script_public_key.version().to_le_bytes());
+        bytes.extend_from_slice(&(script.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(script);
+        Self(bytes)
+    }
+}
+
+impl Display for ScriptPublicKeyBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl AsRef<[u8]> for ScriptPublicKeyBucket {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// [AddressHistoryKey] key which references a history entry within a [ScriptPublicKeyBucket].
+/// Consists of the bucket, followed by 8 bytes of big endian accepting DAA score and 32 bytes of transaction id,
+/// so that the history of a script public key iterates in ascending DAA score order.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct AddressHistoryKey(Vec<u8>);
+
+impl AddressHistoryKey {
+    fn new(bucket: &ScriptPublicKeyBucket, accepting_daa_score: u64, transaction_id: TransactionId) -> Self {
+        let mut bytes = Vec::with_capacity(bucket.0.len() + size_of::<u64>() + HASH_SIZE);
+        bytes.extend_from_slice(&bucket.0);
+        bytes.extend_from_slice(&accepting_daa_score.to_be_bytes());
+        bytes.extend_from_slice(&transaction_id.as_bytes());
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Reader API for `AddressHistoryStore`.
+pub trait AddressHistoryStoreReader {
+    /// Returns at most `limit` entries of the history of a script public key in ascending (DAA score, transaction id)
+    /// order, starting at `start_daa_score`. If `start_transaction_id` is set, the page starts right after the entry of
+    /// this transaction, so that the last entry of a page is the cursor of the next one.
+    fn get_page(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        start_daa_score: u64,
+        start_transaction_id: Option<TransactionId>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>>;
+}
+
+pub trait AddressHistoryStore: AddressHistoryStoreReader {
+    fn insert(&mut self, batch: &mut WriteBatch, script_public_key: &ScriptPublicKey, entry: AddressHistoryEntry) -> StoreResult<()>;
+    fn delete(
+        &mut self,
+        batch: &mut WriteBatch,
+        script_public_key: &ScriptPublicKey,
+        accepting_daa_score: u64,
+        transaction_id: TransactionId,
+    ) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `AddressHistoryStore` trait
+#[derive(Clone)]
+pub struct DbAddressHistoryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryKey, AddressHistoryEntry>,
+}
+
+impl DbAddressHistoryStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::AddressHistory.into()) }
+    }
+}
+
+impl AddressHistoryStoreReader for DbAddressHistoryStore {
+    fn get_page(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        start_daa_score: u64,
+        start_transaction_id: Option<TransactionId>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        let bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let start_key = AddressHistoryKey::new(&bucket, start_daa_score, start_transaction_id.unwrap_or_default());
+        self.access
+            .seek_iterator(Some(bucket.as_ref()), Some(start_key), usize::MAX, false)
+            .map(|res| res.map(|(_, entry)| entry).map_err(|err| StoreError::DataInconsistency(err.to_string())))
+            // The entry of the start transaction is the last one of the previous page
+            .filter(|res| {
+                !matches!(res, Ok(entry) if entry.accepting_daa_score == start_daa_score && Some(entry.transaction_id) == start_transaction_id)
+            })
+            .take(limit)
+            .collect()
+    }
+}
+
+impl AddressHistoryStore for DbAddressHistoryStore {
+    fn insert(&mut self, batch: &mut WriteBatch, script_public_key: &ScriptPublicKey, entry: AddressHistoryEntry) -> StoreResult<()> {
+        let key = AddressHistoryKey::new(&script_public_key.into(), entry.accepting_daa_score, entry.transaction_id);
+        self.access.write(BatchDbWriter::new(batch), key, entry)
+    }
+
+    fn delete(
+        &mut self,
+        batch: &mut WriteBatch,
+        script_public_key: &ScriptPublicKey,
+        accepting_daa_score: u64,
+        transaction_id: TransactionId,
+    ) -> StoreResult<()> {
+        self.access
+            .delete(BatchDbWriter::new(batch), AddressHistoryKey::new(&script_public_key.into(), accepting_daa_score, transaction_id))
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
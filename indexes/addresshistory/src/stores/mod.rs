@@ -0,0 +1,5 @@
+mod chain_blocks;
+mod counts;
+mod history;
+mod sink;
+pub mod store_manager;
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use kaspa_database::{
+    prelude::{CachedDbItem, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+
+/// Reader API for `AddressHistorySinkStore`.
+pub trait AddressHistorySinkStoreReader {
+    fn get(&self) -> StoreResult<Hash>;
+}
+
+pub trait AddressHistorySinkStore: AddressHistorySinkStoreReader {
+    fn set(&mut self, sink: Hash) -> StoreResult<()>;
+    fn remove(&mut self) -> Result<(), StoreError>;
+}
+
+/// A DB + cache implementation of `AddressHistorySinkStore` trait
+#[derive(Clone)]
+pub struct DbAddressHistorySinkStore {
+    db: Arc<DB>,
+    access: CachedDbItem<Hash>,
+}
+
+impl DbAddressHistorySinkStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::AddressHistorySink.into()) }
+    }
+}
+
+impl AddressHistorySinkStoreReader for DbAddressHistorySinkStore {
+    fn get(&self) -> StoreResult<Hash> {
+        self.access.read()
+    }
+}
+
+impl AddressHistorySinkStore for DbAddressHistorySinkStore {
+    fn set(&mut self, sink: Hash) -> StoreResult<()> {
+        self.access.write(DirectDbWriter::new(&self.db), &sink)
+    }
+
+    fn remove(&mut self) -> Result<(), StoreError> {
+        self.access.remove(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,214 @@
+use std::{collections::HashMap, sync::Arc};
+
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId};
+use kaspa_core::trace;
+use kaspa_database::prelude::{CachePolicy, StoreResult, DB};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+use crate::{
+    model::AddressHistoryEntry,
+    stores::{
+        chain_blocks::{
+            ChainBlockEntries, ChainBlockEntriesStore, ChainBlockEntriesStoreReader, ChainBlockKey, DbChainBlockEntriesStore,
+        },
+        counts::{AddressHistoryCountsStore, AddressHistoryCountsStoreReader, DbAddressHistoryCountsStore},
+        history::{AddressHistoryStore, AddressHistoryStoreReader, DbAddressHistoryStore},
+        sink::{AddressHistorySinkStore, AddressHistorySinkStoreReader, DbAddressHistorySinkStore},
+    },
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    sink_store: DbAddressHistorySinkStore,
+    history_store: DbAddressHistoryStore,
+    counts_store: DbAddressHistoryCountsStore,
+    chain_blocks_store: DbChainBlockEntriesStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            sink_store: DbAddressHistorySinkStore::new(db.clone()),
+            history_store: DbAddressHistoryStore::new(db.clone(), CachePolicy::Empty),
+            counts_store: DbAddressHistoryCountsStore::new(db.clone(), CachePolicy::Empty),
+            chain_blocks_store: DbChainBlockEntriesStore::new(db.clone(), CachePolicy::Empty),
+            db,
+        }
+    }
+
+    pub fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        start_daa_score: u64,
+        start_transaction_id: Option<TransactionId>,
+        limit: usize,
+    ) -> StoreResult<Vec<AddressHistoryEntry>> {
+        self.history_store.get_page(script_public_key, start_daa_score, start_transaction_id, limit)
+    }
+
+    pub fn get_count(&self, script_public_key: &ScriptPublicKey) -> StoreResult<u64> {
+        self.counts_store.get(script_public_key)
+    }
+
+    /// Adds the history entries of the transactions accepted by the mergeset of a chain block.
+    ///
+    /// The entries, the counts and the chain block are written at once, so a chain block indexed before an
+    /// interrupted update is skipped as a whole.
+    pub fn add_chain_block(&mut self, hash: Hash, daa_score: u64, entries: ChainBlockEntries) -> StoreResult<()> {
+        let key = ChainBlockKey::new(daa_score, hash);
+        if self.chain_blocks_store.has(key)? {
+            trace!("[{0}] chain block {1} is already indexed", IDENT, hash);
+            return Ok(());
+        }
+        let mut batch = WriteBatch::default();
+        for (script_public_key, entry) in entries.iter() {
+            self.history_store.insert(&mut batch, script_public_key, *entry)?;
+        }
+        for (script_public_key, added) in count_entries(&entries) {
+            let count = self.counts_store.get(script_public_key)? + added;
+            self.counts_store.set(&mut batch, script_public_key, count)?;
+        }
+        self.chain_blocks_store.insert(&mut batch, key, entries)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Removes the history entries added on behalf of a chain block, returning them if the chain block was indexed.
+    pub fn remove_chain_block(&mut self, hash: Hash, daa_score: u64) -> StoreResult<Option<ChainBlockEntries>> {
+        let key = ChainBlockKey::new(daa_score, hash);
+        let Some(entries) = self.chain_blocks_store.get(key)? else {
+            trace!("[{0}] chain block {1} is not indexed", IDENT, hash);
+            return Ok(None);
+        };
+        let mut batch = WriteBatch::default();
+        for (script_public_key, entry) in entries.iter() {
+            self.history_store.delete(&mut batch, script_public_key, entry.accepting_daa_score, entry.transaction_id)?;
+        }
+        for (script_public_key, removed) in count_entries(&entries) {
+            let count = self.counts_store.get(script_public_key)?.saturating_sub(removed);
+            self.counts_store.set(&mut batch, script_public_key, count)?;
+        }
+        self.chain_blocks_store.delete(&mut batch, key)?;
+        self.db.write(batch)?;
+        Ok(Some(entries))
+    }
+
+    /// Forgets which entries were added on behalf of the chain blocks with a DAA score lower than `daa_score`,
+    /// returning the number of such blocks.
+    ///
+    /// The history entries themselves are kept, these chain blocks being out of reach of any reorg.
+    pub fn prune(&mut self, daa_score: u64) -> StoreResult<usize> {
+        let keys = self.chain_blocks_store.get_keys_below(daa_score)?;
+        let mut batch = WriteBatch::default();
+        for key in keys.iter() {
+            self.chain_blocks_store.delete(&mut batch, *key)?;
+        }
+        self.db.write(batch)?;
+        Ok(keys.len())
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    pub fn set_sink(&mut self, sink: Hash) -> StoreResult<()> {
+        self.sink_store.set(sink)
+    }
+
+    /// Resets the address history database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        // We first delete the sink store, so that if the deletion of the other stores fails,
+        // the address history is left in a non-synced state, forcing a resync on the next start.
+        trace!("[{0}] attempting to clear address history database...", IDENT);
+
+        self.sink_store.remove()?;
+        self.history_store.delete_all()?;
+        self.counts_store.delete_all()?;
+        self.chain_blocks_store.delete_all()?;
+
+        trace!("[{0}] cleared address history database", IDENT);
+
+        Ok(())
+    }
+}
+
+/// Returns the number of entries of each script public key.
+fn count_entries(entries: &ChainBlockEntries) -> HashMap<&ScriptPublicKey, u64> {
+    let mut counts = HashMap::new();
+    for (script_public_key, _) in entries.iter() {
+        *counts.entry(script_public_key).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::tx::{ScriptVec, TransactionId};
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn entry(transaction_id: TransactionId, accepting_block_hash: Hash, accepting_daa_score: u64) -> AddressHistoryEntry {
+        AddressHistoryEntry { transaction_id, accepting_block_hash, accepting_daa_score, received: 10, spent: 0 }
+    }
+
+    #[test]
+    fn test_store_chain_block_lifecycle() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+
+        let (script_a, script_b) =
+            (ScriptPublicKey::new(0, ScriptVec::from_slice(&[1, 2, 3])), ScriptPublicKey::new(0, ScriptVec::from_slice(&[1, 2])));
+        let (tx_a, tx_b): (TransactionId, TransactionId) = (1.into(), 2.into());
+        let (chain_block_1, chain_block_2, chain_block_3) =
+            (Hash::from_u64_word(11), Hash::from_u64_word(12), Hash::from_u64_word(13));
+
+        store.add_chain_block(chain_block_1, 101, Arc::new(vec![(script_a.clone(), entry(tx_a, chain_block_1, 101))])).unwrap();
+        store
+            .add_chain_block(
+                chain_block_2,
+                102,
+                Arc::new(vec![
+                    (script_a.clone(), entry(tx_b, chain_block_2, 102)),
+                    (script_b.clone(), entry(tx_b, chain_block_2, 102)),
+                ]),
+            )
+            .unwrap();
+        assert_eq!(store.get_count(&script_a).unwrap(), 2);
+        assert_eq!(store.get_count(&script_b).unwrap(), 1);
+        assert_eq!(
+            store.get_history(&script_a, 0, None, 10).unwrap(),
+            vec![entry(tx_a, chain_block_1, 101), entry(tx_b, chain_block_2, 102)]
+        );
+        assert_eq!(store.get_history(&script_a, 102, None, 10).unwrap(), vec![entry(tx_b, chain_block_2, 102)]);
+        assert_eq!(store.get_history(&script_a, 0, None, 1).unwrap(), vec![entry(tx_a, chain_block_1, 101)]);
+        // The last entry of a page is the cursor of the next one
+        assert_eq!(store.get_history(&script_a, 101, Some(tx_a), 1).unwrap(), vec![entry(tx_b, chain_block_2, 102)]);
+        assert!(store.get_history(&script_a, 102, Some(tx_b), 1).unwrap().is_empty());
+
+        // Re-adding a chain block does not count its entries twice
+        store.add_chain_block(chain_block_1, 101, Arc::new(vec![(script_a.clone(), entry(tx_a, chain_block_1, 101))])).unwrap();
+        assert_eq!(store.get_count(&script_a).unwrap(), 2);
+
+        // A reorg replaces the second chain block with a third one accepting tx_b
+        assert_eq!(store.remove_chain_block(chain_block_2, 102).unwrap().unwrap().len(), 2);
+        assert_eq!(store.get_count(&script_a).unwrap(), 1);
+        assert_eq!(store.get_count(&script_b).unwrap(), 0);
+        assert!(store.get_history(&script_b, 0, None, 10).unwrap().is_empty());
+        store.add_chain_block(chain_block_3, 103, Arc::new(vec![(script_b.clone(), entry(tx_b, chain_block_3, 103))])).unwrap();
+        assert_eq!(store.get_history(&script_b, 0, None, 10).unwrap(), vec![entry(tx_b, chain_block_3, 103)]);
+
+        // Pruning keeps the history but forgets the pruned chain blocks
+        assert_eq!(store.prune(103).unwrap(), 1);
+        assert!(store.remove_chain_block(chain_block_1, 101).unwrap().is_none());
+        assert_eq!(store.get_count(&script_a).unwrap(), 1);
+        assert_eq!(store.get_history(&script_a, 0, None, 10).unwrap(), vec![entry(tx_a, chain_block_1, 101)]);
+
+        store.delete_all().unwrap();
+        assert_eq!(store.get_count(&script_b).unwrap(), 0);
+        assert!(store.get_history(&script_b, 0, None, 10).unwrap().is_empty());
+        assert!(store.get_sink().is_err());
+    }
+}
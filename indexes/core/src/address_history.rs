@@ -0,0 +1,36 @@
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionId};
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// The part played by a transaction accepted by the virtual selected chain in the history of a [`ScriptPublicKey`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct AddressHistoryEntry {
+    pub transaction_id: TransactionId,
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_daa_score: u64,
+    /// The total amount of the outputs created by the transaction for the script public key
+    pub received: u64,
+    /// The total amount of the outputs of the script public key spent by the transaction
+    pub spent: u64,
+}
+
+impl MemSizeEstimator for AddressHistoryEntry {}
+
+/// A list of history entries paired with the [`ScriptPublicKey`] they belong to.
+pub type AddressHistoryEntries = Vec<(ScriptPublicKey, AddressHistoryEntry)>;
+
+/// The history entries added and removed by a change of the virtual selected chain.
+#[derive(Clone, Debug, Default)]
+pub struct AddressHistoryChanges {
+    pub added: AddressHistoryEntries,
+    pub removed: AddressHistoryEntries,
+}
+
+impl AddressHistoryChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
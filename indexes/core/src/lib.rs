@@ -1,3 +1,4 @@
+pub mod address_history;
 pub mod connection;
 pub mod indexed_utxos;
 pub mod notification;
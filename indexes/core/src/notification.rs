@@ -1,4 +1,7 @@
-use crate::indexed_utxos::{UtxoChanges, UtxoSetByScriptPublicKey};
+use crate::{
+    address_history::{AddressHistoryChanges, AddressHistoryEntries},
+    indexed_utxos::{UtxoChanges, UtxoSetByScriptPublicKey},
+};
 use derive_more::Display;
 use kaspa_notify::{
     events::EventType,
@@ -20,6 +23,9 @@ pub enum Notification {
 
     #[display(fmt = "PruningPointUtxoSetOverride notification")]
     PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification),
+
+    #[display(fmt = "AddressHistoryChanged notification")]
+    AddressHistoryChanged(AddressHistoryChangedNotification),
 }
 }
 
@@ -61,6 +67,18 @@ impl NotificationTrait for Notification {
 #[derive(Debug, Clone, Default)]
 pub struct PruningPointUtxoSetOverrideNotification {}
 
+#[derive(Debug, Clone)]
+pub struct AddressHistoryChangedNotification {
+    pub added: Arc<AddressHistoryEntries>,
+    pub removed: Arc<AddressHistoryEntries>,
+}
+
+impl From<AddressHistoryChanges> for AddressHistoryChangedNotification {
+    fn from(item: AddressHistoryChanges) -> Self {
+        Self { added: Arc::new(item.added), removed: Arc::new(item.removed) }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UtxosChangedNotification {
     pub added: Arc<UtxoSetByScriptPublicKey>,
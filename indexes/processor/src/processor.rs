@@ -3,9 +3,12 @@ use crate::{
     IDENT,
 };
 use async_trait::async_trait;
+use kaspa_addresshistory::api::AddressHistoryProxy;
 use kaspa_consensus_notify::{notification as consensus_notification, notification::Notification as ConsensusNotification};
 use kaspa_core::{debug, trace};
-use kaspa_index_core::notification::{Notification, PruningPointUtxoSetOverrideNotification, UtxosChangedNotification};
+use kaspa_index_core::notification::{
+    AddressHistoryChangedNotification, Notification, PruningPointUtxoSetOverrideNotification, UtxosChangedNotification,
+};
 use kaspa_notify::{
     collector::{Collector, CollectorNotificationReceiver},
     error::Result,
@@ -13,6 +16,7 @@ use kaspa_notify::{
     notification::Notification as NotificationTrait,
     notifier::DynNotify,
 };
+use kaspa_txindex::api::TxIndexProxy;
 use kaspa_utils::triggers::SingleTrigger;
 use kaspa_utxoindex::api::UtxoIndexProxy;
 use std::sync::{
@@ -21,7 +25,8 @@ use std::sync::{
 };
 
 /// Processor processes incoming consensus UtxosChanged and PruningPointUtxoSetOverride
-/// notifications submitting them to a UtxoIndex.
+/// notifications submitting them to a UtxoIndex, and VirtualChainChanged notifications
+/// submitting them to a TxIndex and an AddressHistory.
 ///
 /// It also acts as a [`Collector`], converting the incoming consensus notifications
 /// into their pending local versions and relaying them to a local notifier.
@@ -30,6 +35,12 @@ pub struct Processor {
     /// An optional UTXO indexer
     utxoindex: Option<UtxoIndexProxy>,
 
+    /// An optional transaction indexer
+    txindex: Option<TxIndexProxy>,
+
+    /// An optional address history indexer
+    addresshistory: Option<AddressHistoryProxy>,
+
     recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
 
     /// Has this collector been started?
@@ -39,9 +50,16 @@ pub struct Processor {
 }
 
 impl Processor {
-    pub fn new(utxoindex: Option<UtxoIndexProxy>, recv_channel: CollectorNotificationReceiver<ConsensusNotification>) -> Self {
+    pub fn new(
+        utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addresshistory: Option<AddressHistoryProxy>,
+        recv_channel: CollectorNotificationReceiver<ConsensusNotification>,
+    ) -> Self {
         Self {
             utxoindex,
+            txindex,
+            addresshistory,
             recv_channel,
             collect_shutdown: Arc::new(SingleTrigger::new()),
             is_started: Arc::new(AtomicBool::new(false)),
@@ -58,12 +76,13 @@ impl Processor {
 
             while let Ok(notification) = self.recv_channel.recv().await {
                 match self.process_notification(notification).await {
-                    Ok(notification) => match notifier.notify(notification) {
+                    Ok(Some(notification)) => match notifier.notify(notification) {
                         Ok(_) => (),
                         Err(err) => {
                             trace!("[Index processor] notification sender error: {err:?}");
                         }
                     },
+                    Ok(None) => (),
                     Err(err) => {
                         trace!("[Index processor] error while processing a consensus notification: {err:?}");
                     }
@@ -76,13 +95,17 @@ impl Processor {
         });
     }
 
-    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Notification> {
+    /// Processes a consensus notification, returning the index notification to relay, if any.
+    async fn process_notification(self: &Arc<Self>, notification: ConsensusNotification) -> IndexResult<Option<Notification>> {
         match notification {
             ConsensusNotification::UtxosChanged(utxos_changed) => {
-                Ok(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?))
+                Ok(Some(Notification::UtxosChanged(self.process_utxos_changed(utxos_changed).await?)))
             }
             ConsensusNotification::PruningPointUtxoSetOverride(_) => {
-                Ok(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {}))
+                Ok(Some(Notification::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideNotification {})))
+            }
+            ConsensusNotification::VirtualChainChanged(virtual_chain_changed) => {
+                Ok(self.process_virtual_chain_changed(virtual_chain_changed).await?.map(Notification::AddressHistoryChanged))
             }
             _ => Err(IndexError::NotSupported(notification.event_type())),
         }
@@ -106,6 +129,43 @@ impl Processor {
         Err(IndexError::NotSupported(EventType::UtxosChanged))
     }
 
+    async fn process_virtual_chain_changed(
+        self: &Arc<Self>,
+        notification: consensus_notification::VirtualChainChangedNotification,
+    ) -> IndexResult<Option<AddressHistoryChangedNotification>> {
+        trace!("[{IDENT}]: processing {:?}", notification);
+        if self.txindex.is_none() && self.addresshistory.is_none() {
+            return Err(IndexError::NotSupported(EventType::VirtualChainChanged));
+        }
+        if let Some(txindex) = self.txindex.clone() {
+            txindex
+                .update(
+                    notification.added_chain_block_hashes.clone(),
+                    notification.removed_chain_block_hashes.clone(),
+                    notification.added_chain_blocks_acceptance_data.clone(),
+                )
+                .await?;
+        };
+        if let Some(addresshistory) = self.addresshistory.clone() {
+            let changes = addresshistory
+                .update(
+                    notification.added_chain_block_hashes,
+                    notification.removed_chain_block_hashes,
+                    notification.added_chain_blocks_acceptance_data,
+                )
+                .await?;
+            if !changes.is_empty() {
+                debug!(
+                    "IDXPRC, Creating AddressHistoryChanged notifications with {} added and {} removed entries",
+                    changes.added.len(),
+                    changes.removed.len()
+                );
+                return Ok(Some(changes.into()));
+            }
+        };
+        Ok(None)
+    }
+
     async fn join_collecting_task(&self) -> Result<()> {
         trace!("[Index processor] joining");
         self.collect_shutdown.listener.clone().await;
@@ -160,7 +220,7 @@ mod tests {
             tc.init();
             let consensus_manager = Arc::new(ConsensusManager::from_consensus(tc.consensus_clone()));
             let utxoindex = Some(UtxoIndexProxy::new(UtxoIndex::new(consensus_manager, utxoindex_db).unwrap()));
-            let processor = Arc::new(Processor::new(utxoindex, consensus_receiver));
+            let processor = Arc::new(Processor::new(utxoindex, None, None, consensus_receiver));
             let (processor_sender, processor_receiver) = unbounded();
             let notifier = Arc::new(NotifyMock::new(processor_sender));
             processor.clone().start(notifier);
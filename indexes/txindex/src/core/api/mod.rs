@@ -0,0 +1,82 @@
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, tx::TransactionId};
+use kaspa_consensusmanager::spawn_blocking;
+use kaspa_database::prelude::StoreResult;
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{errors::TxIndexResult, model::TxIndexEntry};
+
+///Txindex API targeted at retrieval calls.
+pub trait TxIndexApi: Send + Sync + Debug {
+    /// Retrieve the index entry of a transaction, if the transaction is known to the txindex.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>>;
+
+    /// Retrieve the sink the txindex is synced with (used for testing purposes).
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_sink(&self) -> StoreResult<Hash>;
+
+    /// Checks if the txindex's db is synced with consensus.
+    ///
+    /// Note:
+    /// 1) Use a read lock when accessing this method
+    /// 2) due to potential sync-gaps is_synced is unreliable while consensus is actively resolving virtual states.
+    fn is_synced(&self) -> TxIndexResult<bool>;
+
+    /// Update the txindex with the given virtual selected chain changes, then drop the entries
+    /// that fell below the pruning point.
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()>;
+
+    /// Resync the txindex from the consensus db
+    ///
+    /// Note: Use a write lock when accessing this method
+    fn resync(&mut self) -> TxIndexResult<()>;
+}
+
+/// Async proxy for the transaction index
+#[derive(Debug, Clone)]
+pub struct TxIndexProxy {
+    inner: Arc<RwLock<dyn TxIndexApi>>,
+}
+
+impl TxIndexProxy {
+    pub fn new(inner: Arc<RwLock<dyn TxIndexApi>>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn get_transaction_entry(self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        spawn_blocking(move || self.inner.read().get_transaction_entry(transaction_id)).await.unwrap()
+    }
+
+    pub async fn get_transaction_entries(self, transaction_ids: Vec<TransactionId>) -> StoreResult<Vec<TxIndexEntry>> {
+        spawn_blocking(move || {
+            let txindex = self.inner.read();
+            transaction_ids.into_iter().filter_map(|id| txindex.get_transaction_entry(id).transpose()).collect()
+        })
+        .await
+        .unwrap()
+    }
+
+    pub async fn update(
+        self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        spawn_blocking(move || {
+            self.inner.write().update(added_chain_block_hashes, removed_chain_block_hashes, added_chain_blocks_acceptance_data)
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,45 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use serde::{Deserialize, Serialize};
+
+/// The acceptance of a transaction by a chain block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxAcceptance {
+    /// The chain block whose mergeset accepted the transaction
+    pub accepting_block_hash: Hash,
+    /// The DAA score of the accepting chain block
+    pub accepting_daa_score: u64,
+    /// The block holding the instance of the transaction that got accepted
+    pub including_block_hash: Hash,
+    /// The position of the transaction within the including block
+    pub index_within_block: u32,
+}
+
+impl MemSizeEstimator for TxAcceptance {}
+
+/// The inclusion of a transaction in a block body.
+///
+/// A transaction may be included in several blocks, yet it is only accepted once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TxInclusion {
+    pub block_hash: Hash,
+    pub daa_score: u64,
+    pub index_within_block: u32,
+}
+
+impl TxInclusion {
+    pub fn new(block_hash: Hash, daa_score: u64, index_within_block: u32) -> Self {
+        Self { block_hash, daa_score, index_within_block }
+    }
+}
+
+/// Everything the txindex knows about a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIndexEntry {
+    pub transaction_id: TransactionId,
+    /// The acceptance of the transaction, if it is currently accepted by the virtual selected chain
+    pub acceptance: Option<TxAcceptance>,
+    /// All the indexed blocks including the transaction, ordered by DAA score
+    pub inclusions: Vec<TxInclusion>,
+}
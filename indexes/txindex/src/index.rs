@@ -0,0 +1,205 @@
+use crate::{
+    api::TxIndexApi,
+    errors::{TxIndexError, TxIndexResult},
+    model::{TxInclusion, TxIndexEntry},
+    stores::store_manager::Store,
+    IDENT,
+};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, api::ConsensusApi, tx::TransactionId};
+use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
+use kaspa_core::{info, trace};
+use kaspa_database::prelude::{StoreError, StoreResult, DB};
+use kaspa_hashes::Hash;
+use parking_lot::RwLock;
+use std::{
+    collections::HashSet,
+    fmt::Debug,
+    sync::{Arc, Weak},
+};
+
+const RESYNC_CHUNK_SIZE: usize = 1024; // Amount of chain blocks whose acceptance data is fetched from consensus at once during a resync.
+
+/// TxIndex indexes transactions by [`TransactionId`], tracking the chain block accepting each transaction and
+/// the blocks including it, and retains only the data above the pruning point.
+/// Note: The TxIndex struct by itself is not thread save, only correct usage of the supplied RwLock via `new` makes it so.
+/// please follow guidelines found in the comments under `txindex::core::api::TxIndexApi` for proper thread safety.
+pub struct TxIndex {
+    consensus_manager: Arc<ConsensusManager>,
+    store: Store,
+}
+
+impl TxIndex {
+    /// Creates a new [`TxIndex`] within a [`RwLock`]
+    pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> TxIndexResult<Arc<RwLock<Self>>> {
+        let mut txindex = Self { consensus_manager: consensus_manager.clone(), store: Store::new(db) };
+        if !txindex.is_synced()? {
+            txindex.resync()?;
+        }
+        let txindex = Arc::new(RwLock::new(txindex));
+        consensus_manager.register_consensus_reset_handler(Arc::new(TxIndexConsensusResetHandler::new(Arc::downgrade(&txindex))));
+        Ok(txindex)
+    }
+
+    /// Indexes the transactions of all the blocks in the mergeset of chain block `hash`.
+    ///
+    /// Mergeset blocks whose body is not available anymore (i.e. below the pruning point) are skipped.
+    fn index_chain_block(&mut self, consensus: &dyn ConsensusApi, hash: Hash, acceptance_data: &AcceptanceData) -> TxIndexResult<()> {
+        let daa_score = consensus.get_header(hash)?.daa_score;
+        let mut transactions = Vec::new();
+        let mut accepted = Vec::new();
+        for mergeset_block_data in acceptance_data.iter() {
+            let Ok(block) = consensus.get_block(mergeset_block_data.block_hash) else {
+                trace!("[{0}] skipping merged block {1} with no body", IDENT, mergeset_block_data.block_hash);
+                continue;
+            };
+            let accepted_indices: HashSet<u32> =
+                mergeset_block_data.accepted_transactions.iter().map(|entry| entry.index_within_block).collect();
+            for (index_within_block, transaction) in block.transactions.iter().enumerate() {
+                let inclusion = TxInclusion::new(block.hash(), block.header.daa_score, index_within_block as u32);
+                transactions.push((transaction.id(), inclusion));
+                if accepted_indices.contains(&inclusion.index_within_block) {
+                    accepted.push((transaction.id(), inclusion));
+                }
+            }
+        }
+        trace!("[{0}] indexing chain block {1} with {2} transactions, {3} accepted", IDENT, hash, transactions.len(), accepted.len());
+        self.store.add_chain_block(hash, daa_score, Arc::new(transactions), accepted.into_iter())?;
+        Ok(())
+    }
+
+    /// Drops all the chain blocks below the current pruning point of consensus.
+    fn prune(&mut self, consensus: &dyn ConsensusApi) -> TxIndexResult<()> {
+        let pruning_point_daa_score = consensus.get_header(consensus.pruning_point())?.daa_score;
+        let pruned = self.store.prune(pruning_point_daa_score)?;
+        if pruned > 0 {
+            trace!("[{0}] pruned {1} chain blocks below DAA score {2}", IDENT, pruned, pruning_point_daa_score);
+        }
+        Ok(())
+    }
+}
+
+impl TxIndexApi for TxIndex {
+    /// Retrieve the index entry of a transaction from the txindex db.
+    fn get_transaction_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        trace!("[{0}] retrieving transaction {1}", IDENT, transaction_id);
+
+        self.store.get_entry(transaction_id)
+    }
+
+    /// Retrieve the stored sink of the txindex.
+    fn get_sink(&self) -> StoreResult<Hash> {
+        trace!("[{0}] retrieving sink", IDENT);
+
+        self.store.get_sink()
+    }
+
+    /// Checks to see if the [TxIndex] is sync'd. This is done via comparing the txindex committed sink with the one of the consensus database.
+    ///
+    /// **Note:** Due to sync gaps between the txindex and consensus, this function is only reliable while consensus is not processing new blocks.
+    fn is_synced(&self) -> TxIndexResult<bool> {
+        trace!("[{0}] checking sync status...", IDENT);
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        match self.store.get_sink() {
+            Ok(txindex_sink) => {
+                let res = txindex_sink == session.get_sink();
+                trace!("[{0}] sync status is {1}", IDENT, res);
+                Ok(res)
+            }
+            Err(StoreError::KeyNotFound(_)) => {
+                // Means txindex sink database is empty i.e. not sync'd.
+                trace!("[{0}] sync status is {1}", IDENT, false);
+                Ok(false)
+            }
+            Err(other_store_errors) => Err(TxIndexError::StoreAccessError(other_store_errors)),
+        }
+    }
+
+    /// Updates the [TxIndex] with the virtual selected chain changes supplied:
+    /// 1) Removes whatever was indexed on behalf of the removed chain blocks.
+    /// 2) Indexes the mergesets of the added chain blocks and saves the new sink.
+    /// 3) Drops the chain blocks that fell below the pruning point.
+    fn update(
+        &mut self,
+        added_chain_block_hashes: Arc<Vec<Hash>>,
+        removed_chain_block_hashes: Arc<Vec<Hash>>,
+        added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    ) -> TxIndexResult<()> {
+        trace!("[{0}] updating...", IDENT);
+        trace!("[{0}] adding {1} chain blocks", IDENT, added_chain_block_hashes.len());
+        trace!("[{0}] removing {1} chain blocks", IDENT, removed_chain_block_hashes.len());
+
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        for hash in removed_chain_block_hashes.iter().copied() {
+            let daa_score = session.get_header(hash)?.daa_score;
+            self.store.remove_chain_block(hash, daa_score)?;
+        }
+
+        for (hash, acceptance_data) in added_chain_block_hashes.iter().copied().zip(added_chain_blocks_acceptance_data.iter()) {
+            self.index_chain_block(&*session, hash, acceptance_data)?;
+        }
+
+        if let Some(sink) = added_chain_block_hashes.last() {
+            self.store.set_sink(*sink)?;
+        }
+
+        self.prune(&*session)
+    }
+
+    /// Deletes and reinstates the txindex database, syncing it from scratch via the consensus database.
+    ///
+    /// **Notes:**
+    /// 1) Only the virtual selected chain above the pruning point is indexed, older data being unavailable.
+    /// 2) resyncing while consensus notifies of virtual chain changes, may result in a corrupted db.
+    fn resync(&mut self) -> TxIndexResult<()> {
+        info!("Resyncing the txindex...");
+
+        self.store.delete_all()?;
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let pruning_point = session.pruning_point();
+        let chain_path = session.get_virtual_chain_from_block(pruning_point)?;
+        for chunk in chain_path.added.chunks(RESYNC_CHUNK_SIZE) {
+            trace!("[{0}] resyncing with batch of {1} chain blocks from consensus db", IDENT, chunk.len());
+            let acceptance_data = session.get_blocks_acceptance_data(chunk)?;
+            for (hash, acceptance_data) in chunk.iter().copied().zip(acceptance_data.iter()) {
+                self.index_chain_block(&*session, hash, acceptance_data)?;
+            }
+        }
+
+        let sink = chain_path.added.last().copied().unwrap_or(pruning_point);
+        trace!("[{0}] committing sink {1} from consensus db", IDENT, sink);
+        self.store.set_sink(sink)?;
+
+        Ok(())
+    }
+}
+
+impl Debug for TxIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxIndex").finish()
+    }
+}
+
+struct TxIndexConsensusResetHandler {
+    txindex: Weak<RwLock<TxIndex>>,
+}
+
+impl TxIndexConsensusResetHandler {
+    fn new(txindex: Weak<RwLock<TxIndex>>) -> Self {
+        Self { txindex }
+    }
+}
+
+impl ConsensusResetHandler for TxIndexConsensusResetHandler {
+    fn handle_consensus_reset(&self) {
+        if let Some(txindex) = self.txindex.upgrade() {
+            txindex.write().resync().unwrap();
+        }
+    }
+}
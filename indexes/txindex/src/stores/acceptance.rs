@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use rocksdb::WriteBatch;
+
+use crate::model::TxAcceptance;
+
+/// Reader API for `TxAcceptanceStore`.
+pub trait TxAcceptanceStoreReader {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxAcceptance>>;
+}
+
+pub trait TxAcceptanceStore: TxAcceptanceStoreReader {
+    fn insert(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, acceptance: TxAcceptance) -> StoreResult<()>;
+    fn delete(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxAcceptanceStore` trait
+#[derive(Clone)]
+pub struct DbTxAcceptanceStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<TransactionId, TxAcceptance>,
+}
+
+impl DbTxAcceptanceStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexAcceptance.into()) }
+    }
+}
+
+impl TxAcceptanceStoreReader for DbTxAcceptanceStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Option<TxAcceptance>> {
+        match self.access.read(transaction_id) {
+            Ok(acceptance) => Ok(Some(acceptance)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl TxAcceptanceStore for DbTxAcceptanceStore {
+    fn insert(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, acceptance: TxAcceptance) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), transaction_id, acceptance)
+    }
+
+    fn delete(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), transaction_id)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,116 @@
+use std::{fmt::Display, mem::size_of, sync::Arc};
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, DirectDbWriter, StoreError, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::{Hash, HASH_SIZE};
+use rocksdb::WriteBatch;
+
+use crate::model::TxInclusion;
+
+/// The transactions indexed on behalf of a chain block, i.e. the transactions of all the blocks in its mergeset.
+pub type ChainBlockTransactions = Arc<Vec<(TransactionId, TxInclusion)>>;
+
+/// Size of the [ChainBlockKey] in bytes.
+pub const CHAIN_BLOCK_KEY_SIZE: usize = size_of::<u64>() + HASH_SIZE;
+
+/// [ChainBlockKey] key which references the transactions indexed on behalf of a chain block.
+/// Consists of 8 bytes of big endian DAA score, followed by 32 bytes of block hash, so that
+/// the store iterates chain blocks in ascending DAA score order.
+#[derive(Eq, Hash, PartialEq, Debug, Copy, Clone)]
+pub struct ChainBlockKey([u8; CHAIN_BLOCK_KEY_SIZE]);
+
+impl ChainBlockKey {
+    pub fn new(daa_score: u64, hash: Hash) -> Self {
+        let mut bytes = [0; CHAIN_BLOCK_KEY_SIZE];
+        bytes[..size_of::<u64>()].copy_from_slice(&daa_score.to_be_bytes());
+        bytes[size_of::<u64>()..].copy_from_slice(&hash.as_bytes());
+        Self(bytes)
+    }
+
+    pub fn daa_score(&self) -> u64 {
+        u64::from_be_bytes(self.0[..size_of::<u64>()].try_into().unwrap())
+    }
+
+    pub fn hash(&self) -> Hash {
+        Hash::from_slice(&self.0[size_of::<u64>()..])
+    }
+}
+
+impl Display for ChainBlockKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.daa_score(), self.hash())
+    }
+}
+
+impl AsRef<[u8]> for ChainBlockKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// Reader API for `ChainBlockTransactionsStore`.
+pub trait ChainBlockTransactionsStoreReader {
+    fn get(&self, key: ChainBlockKey) -> StoreResult<Option<ChainBlockTransactions>>;
+
+    /// Returns the keys of all indexed chain blocks with a DAA score lower than `daa_score`.
+    fn get_keys_below(&self, daa_score: u64) -> StoreResult<Vec<ChainBlockKey>>;
+}
+
+pub trait ChainBlockTransactionsStore: ChainBlockTransactionsStoreReader {
+    fn insert(&mut self, batch: &mut WriteBatch, key: ChainBlockKey, transactions: ChainBlockTransactions) -> StoreResult<()>;
+    fn delete(&mut self, batch: &mut WriteBatch, key: ChainBlockKey) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `ChainBlockTransactionsStore` trait
+#[derive(Clone)]
+pub struct DbChainBlockTransactionsStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<ChainBlockKey, ChainBlockTransactions>,
+}
+
+impl DbChainBlockTransactionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexChainBlocks.into()) }
+    }
+}
+
+impl ChainBlockTransactionsStoreReader for DbChainBlockTransactionsStore {
+    fn get(&self, key: ChainBlockKey) -> StoreResult<Option<ChainBlockTransactions>> {
+        match self.access.read(key) {
+            Ok(transactions) => Ok(Some(transactions)),
+            Err(StoreError::KeyNotFound(_)) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn get_keys_below(&self, daa_score: u64) -> StoreResult<Vec<ChainBlockKey>> {
+        let mut keys = Vec::new();
+        for res in self.access.seek_iterator(None, None, usize::MAX, false) {
+            let (key, _) = res.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            let key = ChainBlockKey(<[u8; CHAIN_BLOCK_KEY_SIZE]>::try_from(&key[..]).unwrap());
+            if key.daa_score() >= daa_score {
+                break;
+            }
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+}
+
+impl ChainBlockTransactionsStore for DbChainBlockTransactionsStore {
+    fn insert(&mut self, batch: &mut WriteBatch, key: ChainBlockKey, transactions: ChainBlockTransactions) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), key, transactions)
+    }
+
+    fn delete(&mut self, batch: &mut WriteBatch, key: ChainBlockKey) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), key)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
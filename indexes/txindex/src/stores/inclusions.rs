@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbSetAccess, DirectDbWriter, StoreResult, DB},
+    registry::DatabaseStorePrefixes,
+};
+use rocksdb::WriteBatch;
+
+use crate::model::TxInclusion;
+
+/// Reader API for `TxInclusionsStore`.
+pub trait TxInclusionsStoreReader {
+    /// Returns the inclusions of a transaction, ordered by DAA score.
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Vec<TxInclusion>>;
+}
+
+pub trait TxInclusionsStore: TxInclusionsStoreReader {
+    fn insert(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, inclusion: TxInclusion) -> StoreResult<()>;
+    fn delete(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, inclusion: TxInclusion) -> StoreResult<()>;
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+/// A DB + cache implementation of `TxInclusionsStore` trait, storing a set of inclusions per transaction.
+#[derive(Clone)]
+pub struct DbTxInclusionsStore {
+    db: Arc<DB>,
+    access: CachedDbSetAccess<TransactionId, TxInclusion>,
+}
+
+impl DbTxInclusionsStore {
+    pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
+        Self { db: Arc::clone(&db), access: CachedDbSetAccess::new(db, cache_policy, DatabaseStorePrefixes::TxIndexInclusions.into()) }
+    }
+}
+
+impl TxInclusionsStoreReader for DbTxInclusionsStore {
+    fn get(&self, transaction_id: TransactionId) -> StoreResult<Vec<TxInclusion>> {
+        let mut inclusions = self.access.read(transaction_id)?.read().iter().copied().collect::<Vec<_>>();
+        inclusions.sort_by_key(|inclusion| (inclusion.daa_score, inclusion.block_hash));
+        Ok(inclusions)
+    }
+}
+
+impl TxInclusionsStore for DbTxInclusionsStore {
+    fn insert(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, inclusion: TxInclusion) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), transaction_id, inclusion)
+    }
+
+    fn delete(&mut self, batch: &mut WriteBatch, transaction_id: TransactionId, inclusion: TxInclusion) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), transaction_id, inclusion)
+    }
+
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
@@ -0,0 +1,5 @@
+mod acceptance;
+mod chain_blocks;
+mod inclusions;
+mod sink;
+pub mod store_manager;
@@ -0,0 +1,200 @@
+use std::sync::Arc;
+
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::trace;
+use kaspa_database::prelude::{CachePolicy, StoreResult, DB};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+use crate::{
+    model::{TxAcceptance, TxInclusion, TxIndexEntry},
+    stores::{
+        acceptance::{DbTxAcceptanceStore, TxAcceptanceStore, TxAcceptanceStoreReader},
+        chain_blocks::{
+            ChainBlockKey, ChainBlockTransactions, ChainBlockTransactionsStore, ChainBlockTransactionsStoreReader,
+            DbChainBlockTransactionsStore,
+        },
+        inclusions::{DbTxInclusionsStore, TxInclusionsStore, TxInclusionsStoreReader},
+        sink::{DbTxIndexSinkStore, TxIndexSinkStore, TxIndexSinkStoreReader},
+    },
+    IDENT,
+};
+
+#[derive(Clone)]
+pub struct Store {
+    db: Arc<DB>,
+    sink_store: DbTxIndexSinkStore,
+    acceptance_store: DbTxAcceptanceStore,
+    inclusions_store: DbTxInclusionsStore,
+    chain_blocks_store: DbChainBlockTransactionsStore,
+}
+
+impl Store {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            sink_store: DbTxIndexSinkStore::new(db.clone()),
+            acceptance_store: DbTxAcceptanceStore::new(db.clone(), CachePolicy::Empty),
+            inclusions_store: DbTxInclusionsStore::new(db.clone(), CachePolicy::Empty),
+            chain_blocks_store: DbChainBlockTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            db,
+        }
+    }
+
+    pub fn get_entry(&self, transaction_id: TransactionId) -> StoreResult<Option<TxIndexEntry>> {
+        let acceptance = self.acceptance_store.get(transaction_id)?;
+        let inclusions = self.inclusions_store.get(transaction_id)?;
+        if acceptance.is_none() && inclusions.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(TxIndexEntry { transaction_id, acceptance, inclusions }))
+    }
+
+    /// Indexes the transactions of the mergeset of a chain block, marking the ones in `accepted` as accepted by it.
+    ///
+    /// The inclusions, the acceptances and the chain block are written at once, so that an interrupted update
+    /// leaves no partially indexed chain block.
+    pub fn add_chain_block(
+        &mut self,
+        hash: Hash,
+        daa_score: u64,
+        transactions: ChainBlockTransactions,
+        accepted: impl Iterator<Item = (TransactionId, TxInclusion)>,
+    ) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        for (transaction_id, inclusion) in transactions.iter().copied() {
+            self.inclusions_store.insert(&mut batch, transaction_id, inclusion)?;
+        }
+        for (transaction_id, inclusion) in accepted {
+            self.acceptance_store.insert(
+                &mut batch,
+                transaction_id,
+                TxAcceptance {
+                    accepting_block_hash: hash,
+                    accepting_daa_score: daa_score,
+                    including_block_hash: inclusion.block_hash,
+                    index_within_block: inclusion.index_within_block,
+                },
+            )?;
+        }
+        self.chain_blocks_store.insert(&mut batch, ChainBlockKey::new(daa_score, hash), transactions)?;
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Removes everything that was indexed on behalf of a chain block.
+    pub fn remove_chain_block(&mut self, hash: Hash, daa_score: u64) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        if !self.delete_chain_block(&mut batch, ChainBlockKey::new(daa_score, hash))? {
+            trace!("[{0}] chain block {1} is not indexed", IDENT, hash);
+            return Ok(());
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Removes all chain blocks with a DAA score lower than `daa_score`, returning the number of removed blocks.
+    pub fn prune(&mut self, daa_score: u64) -> StoreResult<usize> {
+        let keys = self.chain_blocks_store.get_keys_below(daa_score)?;
+        let mut batch = WriteBatch::default();
+        for key in keys.iter() {
+            self.delete_chain_block(&mut batch, *key)?;
+        }
+        self.db.write(batch)?;
+        Ok(keys.len())
+    }
+
+    /// Adds to `batch` the deletion of everything indexed on behalf of a chain block, returning whether it was indexed.
+    fn delete_chain_block(&mut self, batch: &mut WriteBatch, key: ChainBlockKey) -> StoreResult<bool> {
+        let Some(transactions) = self.chain_blocks_store.get(key)? else {
+            return Ok(false);
+        };
+        for (transaction_id, inclusion) in transactions.iter().copied() {
+            self.inclusions_store.delete(batch, transaction_id, inclusion)?;
+            if let Some(acceptance) = self.acceptance_store.get(transaction_id)? {
+                if acceptance.accepting_block_hash == key.hash() {
+                    self.acceptance_store.delete(batch, transaction_id)?;
+                }
+            }
+        }
+        self.chain_blocks_store.delete(batch, key)?;
+        Ok(true)
+    }
+
+    pub fn get_sink(&self) -> StoreResult<Hash> {
+        self.sink_store.get()
+    }
+
+    pub fn set_sink(&mut self, sink: Hash) -> StoreResult<()> {
+        self.sink_store.set(sink)
+    }
+
+    /// Resets the txindex database:
+    pub fn delete_all(&mut self) -> StoreResult<()> {
+        // We first delete the sink store, so that if the deletion of the other stores fails,
+        // the txindex is left in a non-synced state, forcing a resync on the next start.
+        trace!("[{0}] attempting to clear txindex database...", IDENT);
+
+        self.sink_store.remove()?;
+        self.acceptance_store.delete_all()?;
+        self.inclusions_store.delete_all()?;
+        self.chain_blocks_store.delete_all()?;
+
+        trace!("[{0}] cleared txindex database", IDENT);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_store_chain_block_lifecycle() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = Store::new(db);
+
+        let (tx_a, tx_b): (TransactionId, TransactionId) = (1.into(), 2.into());
+        let (merged_block, chain_block_1, chain_block_2) = (Hash::from_u64_word(10), Hash::from_u64_word(11), Hash::from_u64_word(12));
+        let inclusion_a = TxInclusion::new(merged_block, 100, 0);
+        let inclusion_b = TxInclusion::new(merged_block, 100, 1);
+
+        // Only tx_a gets accepted by the first chain block
+        store
+            .add_chain_block(
+                chain_block_1,
+                101,
+                Arc::new(vec![(tx_a, inclusion_a), (tx_b, inclusion_b)]),
+                [(tx_a, inclusion_a)].into_iter(),
+            )
+            .unwrap();
+        let entry = store.get_entry(tx_a).unwrap().unwrap();
+        assert_eq!(entry.acceptance.unwrap().accepting_block_hash, chain_block_1);
+        assert_eq!(entry.inclusions, vec![inclusion_a]);
+        let entry = store.get_entry(tx_b).unwrap().unwrap();
+        assert!(entry.acceptance.is_none());
+        assert_eq!(entry.inclusions, vec![inclusion_b]);
+
+        // A reorg replaces the first chain block with a second one accepting both transactions
+        store.remove_chain_block(chain_block_1, 101).unwrap();
+        assert!(store.get_entry(tx_a).unwrap().is_none());
+        assert!(store.get_entry(tx_b).unwrap().is_none());
+        store
+            .add_chain_block(
+                chain_block_2,
+                102,
+                Arc::new(vec![(tx_a, inclusion_a), (tx_b, inclusion_b)]),
+                [(tx_a, inclusion_a), (tx_b, inclusion_b)].into_iter(),
+            )
+            .unwrap();
+        assert_eq!(store.get_entry(tx_b).unwrap().unwrap().acceptance.unwrap().accepting_daa_score, 102);
+
+        // Pruning below the chain block keeps its data, pruning above it drops it
+        assert_eq!(store.prune(102).unwrap(), 0);
+        assert!(store.get_entry(tx_a).unwrap().is_some());
+        assert_eq!(store.prune(103).unwrap(), 1);
+        assert!(store.get_entry(tx_a).unwrap().is_none());
+        assert!(store.get_entry(tx_b).unwrap().is_none());
+    }
+}
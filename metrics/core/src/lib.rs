@@ -1,5 +1,6 @@
 pub mod data;
 pub mod error;
+pub mod prometheus;
 pub mod result;
 
 pub use data::{Metric, MetricGroup, MetricsData, MetricsSnapshot};
@@ -0,0 +1,241 @@
+//! Encoding of the node metrics in the Prometheus text exposition format.
+
+use kaspa_rpc_core::{BandwidthMetrics, ConnectionMetrics, ConsensusMetrics, GetMetricsResponse, ProcessMetrics};
+use std::fmt::{Display, Write};
+
+/// Content type of a scrape response in the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Type of a Prometheus metric family
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PrometheusMetricType {
+    /// A value only increasing during the node lifetime
+    Counter,
+    /// A value going up and down
+    Gauge,
+}
+
+impl Display for PrometheusMetricType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrometheusMetricType::Counter => write!(f, "counter"),
+            PrometheusMetricType::Gauge => write!(f, "gauge"),
+        }
+    }
+}
+
+/// A component contributing its own metric families to the Prometheus scrape responses
+pub trait PrometheusCollector: Send + Sync {
+    fn collect(&self, encoder: &mut PrometheusEncoder);
+}
+
+/// Builds a scrape response in the Prometheus text exposition format.
+///
+/// Metric families are declared with [`PrometheusEncoder::family`] and followed by their
+/// samples, each sample belonging to the last declared family.
+pub struct PrometheusEncoder {
+    namespace: String,
+    family: String,
+    buffer: String,
+}
+
+impl PrometheusEncoder {
+    /// Creates an encoder prefixing every metric family name with `namespace`
+    pub fn new(namespace: &str) -> Self {
+        Self { namespace: namespace.to_string(), family: String::new(), buffer: String::new() }
+    }
+
+    /// Declares a metric family, counters being expected to be named with a `_total` suffix
+    pub fn family(&mut self, name: &str, help: &str, metric_type: PrometheusMetricType) -> &mut Self {
+        self.family = format!("{}_{}", self.namespace, name);
+        let help = help.replace('\\', "\\\\").replace('\n', "\\n");
+        writeln!(self.buffer, "# HELP {} {}", self.family, help).unwrap();
+        writeln!(self.buffer, "# TYPE {} {}", self.family, metric_type).unwrap();
+        self
+    }
+
+    /// Adds a sample of the last declared metric family
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: f64) -> &mut Self {
+        self.buffer.push_str(&self.family);
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(name, value)| format!("{}=\"{}\"", name, value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+                .collect::<Vec<_>>()
+                .join(",");
+            write!(self.buffer, "{{{}}}", labels).unwrap();
+        }
+        match value {
+            value if value.is_nan() => writeln!(self.buffer, " NaN"),
+            f64::INFINITY => writeln!(self.buffer, " +Inf"),
+            f64::NEG_INFINITY => writeln!(self.buffer, " -Inf"),
+            value => writeln!(self.buffer, " {}", value),
+        }
+        .unwrap();
+        self
+    }
+
+    /// Declares a metric family holding a single sample without labels
+    pub fn single(&mut self, name: &str, help: &str, metric_type: PrometheusMetricType, value: f64) -> &mut Self {
+        self.family(name, help, metric_type).sample(&[], value)
+    }
+
+    /// Encodes the process, connection, bandwidth and consensus metrics of a `get_metrics` response
+    pub fn encode_metrics(&mut self, metrics: &GetMetricsResponse) -> &mut Self {
+        if let Some(process_metrics) = metrics.process_metrics.as_ref() {
+            self.encode_process_metrics(process_metrics);
+        }
+        if let Some(connection_metrics) = metrics.connection_metrics.as_ref() {
+            self.encode_connection_metrics(connection_metrics);
+        }
+        if let Some(bandwidth_metrics) = metrics.bandwidth_metrics.as_ref() {
+            self.encode_bandwidth_metrics(bandwidth_metrics);
+        }
+        if let Some(consensus_metrics) = metrics.consensus_metrics.as_ref() {
+            self.encode_consensus_metrics(consensus_metrics);
+        }
+        self
+    }
+
+    fn encode_process_metrics(&mut self, metrics: &ProcessMetrics) {
+        use PrometheusMetricType::*;
+        self.single(
+            "process_resident_memory_bytes",
+            "Resident memory size of the node process",
+            Gauge,
+            metrics.resident_set_size as f64,
+        );
+        self.single(
+            "process_virtual_memory_bytes",
+            "Virtual memory size of the node process",
+            Gauge,
+            metrics.virtual_memory_size as f64,
+        );
+        self.single("process_cpu_cores", "Number of CPU cores available to the node process", Gauge, metrics.core_num as f64);
+        self.single(
+            "process_cpu_usage_ratio",
+            "CPU usage of the node process, 1 per fully used core",
+            Gauge,
+            metrics.cpu_usage as f64,
+        );
+        self.single("process_open_fds", "Number of file handles opened by the node process", Gauge, metrics.fd_num as f64);
+        self.family("process_disk_io_bytes_total", "Bytes read from and written to the disk by the node process", Counter)
+            .sample(&[("direction", "read")], metrics.disk_io_read_bytes as f64)
+            .sample(&[("direction", "write")], metrics.disk_io_write_bytes as f64);
+        self.family("process_disk_io_bytes_per_second", "Disk read and write rates of the node process", Gauge)
+            .sample(&[("direction", "read")], metrics.disk_io_read_per_sec as f64)
+            .sample(&[("direction", "write")], metrics.disk_io_write_per_sec as f64);
+    }
+
+    fn encode_connection_metrics(&mut self, metrics: &ConnectionMetrics) {
+        use PrometheusMetricType::*;
+        self.family("rpc_live_connections", "Number of live wRPC connections", Gauge)
+            .sample(&[("encoding", "borsh")], metrics.borsh_live_connections as f64)
+            .sample(&[("encoding", "json")], metrics.json_live_connections as f64);
+        self.family("rpc_connection_attempts_total", "Number of wRPC connection attempts", Counter)
+            .sample(&[("encoding", "borsh")], metrics.borsh_connection_attempts as f64)
+            .sample(&[("encoding", "json")], metrics.json_connection_attempts as f64);
+        self.family("rpc_handshake_failures_total", "Number of failed wRPC handshakes", Counter)
+            .sample(&[("encoding", "borsh")], metrics.borsh_handshake_failures as f64)
+            .sample(&[("encoding", "json")], metrics.json_handshake_failures as f64);
+        self.single("p2p_active_peers", "Number of active P2P peers", Gauge, metrics.active_peers as f64);
+    }
+
+    fn encode_bandwidth_metrics(&mut self, metrics: &BandwidthMetrics) {
+        self.family("network_bytes_total", "Bytes sent and received by the node, per protocol", PrometheusMetricType::Counter);
+        for (protocol, tx, rx) in [
+            ("borsh", metrics.borsh_bytes_tx, metrics.borsh_bytes_rx),
+            ("json", metrics.json_bytes_tx, metrics.json_bytes_rx),
+            ("p2p", metrics.p2p_bytes_tx, metrics.p2p_bytes_rx),
+            ("grpc", metrics.grpc_bytes_tx, metrics.grpc_bytes_rx),
+        ] {
+            self.sample(&[("protocol", protocol), ("direction", "tx")], tx as f64);
+            self.sample(&[("protocol", protocol), ("direction", "rx")], rx as f64);
+        }
+        self.single(
+            "p2p_compact_block_bytes_saved_total",
+            "Bytes of block data not downloaded thanks to compact block relay",
+            PrometheusMetricType::Counter,
+            metrics.p2p_compact_block_bytes_saved as f64,
+        );
+    }
+
+    fn encode_consensus_metrics(&mut self, metrics: &ConsensusMetrics) {
+        use PrometheusMetricType::*;
+        self.single(
+            "blocks_submitted_total",
+            "Number of blocks submitted to the node",
+            Counter,
+            metrics.node_blocks_submitted_count as f64,
+        );
+        self.single("headers_processed_total", "Number of processed headers", Counter, metrics.node_headers_processed_count as f64);
+        self.single(
+            "dependencies_processed_total",
+            "Number of processed block dependencies",
+            Counter,
+            metrics.node_dependencies_processed_count as f64,
+        );
+        self.single("bodies_processed_total", "Number of processed block bodies", Counter, metrics.node_bodies_processed_count as f64);
+        self.single(
+            "transactions_processed_total",
+            "Number of transactions in the processed block bodies",
+            Counter,
+            metrics.node_transactions_processed_count as f64,
+        );
+        self.single(
+            "chain_blocks_processed_total",
+            "Number of blocks processed as chain blocks",
+            Counter,
+            metrics.node_chain_blocks_processed_count as f64,
+        );
+        self.single("mass_processed_total", "Total mass of the processed blocks", Counter, metrics.node_mass_processed_count as f64);
+        self.single("database_blocks", "Number of blocks in the database", Gauge, metrics.node_database_blocks_count as f64);
+        self.single("database_headers", "Number of headers in the database", Gauge, metrics.node_database_headers_count as f64);
+        self.single("tip_hashes", "Number of DAG tips", Gauge, metrics.network_tip_hashes_count as f64);
+        self.single("difficulty", "Difficulty of the virtual block", Gauge, metrics.network_difficulty);
+        self.single(
+            "past_median_time_seconds",
+            "Past median time of the virtual block",
+            Gauge,
+            metrics.network_past_median_time as f64 / 1000.0,
+        );
+        self.single(
+            "virtual_parent_hashes",
+            "Number of parents of the virtual block",
+            Gauge,
+            metrics.network_virtual_parent_hashes_count as f64,
+        );
+        self.single("virtual_daa_score", "DAA score of the virtual block", Gauge, metrics.network_virtual_daa_score as f64);
+    }
+
+    /// Returns the encoded scrape response
+    pub fn finish(self) -> String {
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_encoder() {
+        let mut encoder = PrometheusEncoder::new("pyrin");
+        encoder.single("blocks_submitted_total", "Number of blocks\nsubmitted", PrometheusMetricType::Counter, 12.0);
+        encoder
+            .family("peer_info", "Connected peers", PrometheusMetricType::Gauge)
+            .sample(&[("user_agent", "/pyrin:0.14.5/\"test\"\\"), ("direction", "outbound")], 1.0)
+            .sample(&[], f64::NAN)
+            .sample(&[], f64::INFINITY);
+        let expected = "# HELP pyrin_blocks_submitted_total Number of blocks\\nsubmitted
+# TYPE pyrin_blocks_submitted_total counter
+pyrin_blocks_submitted_total 12
+# HELP pyrin_peer_info Connected peers
+# TYPE pyrin_peer_info gauge
+pyrin_peer_info{user_agent=\"/pyrin:0.14.5/\\\"test\\\"\\\\\",direction=\"outbound\"} 1
+pyrin_peer_info NaN
+pyrin_peer_info +Inf
+";
+        assert_eq!(encoder.finish(), expected);
+    }
+}
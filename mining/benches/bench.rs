@@ -1,6 +1,19 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use kaspa_mining::model::topological_index::TopologicalIndex;
-use std::collections::{hash_set::Iter, HashMap, HashSet};
+use kaspa_consensus_core::{
+    block::TemplateTransactionSelector,
+    constants::{MAX_TX_IN_SEQUENCE_NUM, TX_VERSION},
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{ScriptPublicKey, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
+};
+use kaspa_mining::{
+    model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex},
+    PackageTransactionsSelector, Policy, TemplateSelectionMode, TransactionsSelector,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::{
+    collections::{hash_set::Iter, HashMap, HashSet},
+    sync::Arc,
+};
 
 #[derive(Default)]
 pub struct Dag<T>
@@ -68,5 +81,92 @@ pub fn bench_compare_topological_index_fns(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_compare_topological_index_fns);
+const BLOCK_MASS: u64 = 500_000;
+const TX_MASS: u64 = 2_000;
+
+/// A mempool of ready transactions with random feerates, a third of them having a chain
+/// of children paying much higher feerates
+fn build_mempool() -> Vec<CandidateTransaction> {
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut transactions = Vec::new();
+    for i in 0..2_000u64 {
+        let mut parent = create_transaction(i, None, rng.gen_range(1..10) * TX_MASS);
+        transactions.push(parent.clone());
+        if rng.gen_ratio(1, 3) {
+            for _ in 0..rng.gen_range(1..=3) {
+                let child = create_transaction(i, Some(&parent), rng.gen_range(20..50) * TX_MASS);
+                transactions.push(child.clone());
+                parent = child;
+            }
+        }
+    }
+    transactions
+}
+
+fn create_transaction(value: u64, parent: Option<&CandidateTransaction>, fee: u64) -> CandidateTransaction {
+    let previous_outpoint = TransactionOutpoint::new(parent.map(|parent| parent.tx.id()).unwrap_or_default(), value as u32);
+    let input = TransactionInput::new(previous_outpoint, vec![], MAX_TX_IN_SEQUENCE_NUM, 1);
+    let output = TransactionOutput::new(value, ScriptPublicKey::from_vec(0, vec![]));
+    let tx = Arc::new(Transaction::new(TX_VERSION, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]));
+    CandidateTransaction { tx, calculated_fee: fee, calculated_mass: TX_MASS }
+}
+
+/// Splits the unmined transactions into the ready ones and the ones chained to unmined transactions
+fn split_mempool(
+    mempool: &[CandidateTransaction],
+    mined: &HashSet<TransactionId>,
+) -> (Vec<CandidateTransaction>, Vec<CandidateTransaction>) {
+    let unmined: HashSet<TransactionId> = mempool.iter().map(|tx| tx.tx.id()).filter(|id| !mined.contains(id)).collect();
+    mempool
+        .iter()
+        .filter(|tx| unmined.contains(&tx.tx.id()))
+        .cloned()
+        .partition(|tx| tx.tx.inputs.iter().all(|input| !unmined.contains(&input.previous_outpoint.transaction_id)))
+}
+
+fn create_selector(
+    mode: TemplateSelectionMode,
+    mempool: &[CandidateTransaction],
+    mined: &HashSet<TransactionId>,
+) -> Box<dyn TemplateTransactionSelector> {
+    let policy = Policy::new(BLOCK_MASS, mode);
+    let (ready, chained) = split_mempool(mempool, mined);
+    match mode {
+        TemplateSelectionMode::Probabilistic => Box::new(TransactionsSelector::new(policy, ready)),
+        TemplateSelectionMode::PackageFeerate => Box::new(PackageTransactionsSelector::new(policy, ready, chained)),
+    }
+}
+
+/// Returns the total fees captured by a sequence of blocks mined out of the mempool
+fn total_fees_captured(mode: TemplateSelectionMode, mempool: &[CandidateTransaction], blocks: usize) -> u64 {
+    let fees: HashMap<TransactionId, u64> = mempool.iter().map(|tx| (tx.tx.id(), tx.calculated_fee)).collect();
+    let mut mined = HashSet::new();
+    let mut total_fees = 0;
+    for _ in 0..blocks {
+        for tx in create_selector(mode, mempool, &mined).select_transactions() {
+            total_fees += fees[&tx.id()];
+            mined.insert(tx.id());
+        }
+    }
+    total_fees
+}
+
+pub fn bench_compare_template_selection_modes(c: &mut Criterion) {
+    const BLOCKS: usize = 5;
+    let mempool = build_mempool();
+    let mut group = c.benchmark_group("template selection");
+    for mode in [TemplateSelectionMode::Probabilistic, TemplateSelectionMode::PackageFeerate] {
+        println!("{mode}: {} total fees captured over {BLOCKS} blocks", total_fees_captured(mode, &mempool, BLOCKS));
+        group.bench_function(format!("select_transactions ({mode})"), |b| {
+            b.iter_batched(
+                || create_selector(mode, &mempool, &HashSet::new()),
+                |mut selector| black_box(selector.select_transactions()),
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_compare_topological_index_fns, bench_compare_template_selection_modes);
 criterion_main!(benches);
@@ -10,6 +10,10 @@ pub enum MiningManagerError {
     /// A mempool rule error
     #[error(transparent)]
     MempoolError(#[from] RuleError),
+
+    /// An error reading or writing the mempool file
+    #[error("mempool persistence error: {0}")]
+    MempoolPersistenceError(String),
 }
 
 pub type MiningManagerResult<T> = std::result::Result<T, MiningManagerError>;
@@ -75,6 +75,18 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    #[error("transaction {0} is submitted as a replacement by fee but does not double spend any transaction in the mempool")]
+    RejectRbfNoDoubleSpend(TransactionId),
+
+    #[error("replacement transaction {0} spends an output of transaction {1} which it would evict from the mempool")]
+    RejectRbfSpendsReplacedTransaction(TransactionId, TransactionId),
+
+    #[error("replacement transaction {0} has a total fee of {1} which is not higher than the total fee of {2} of the transactions it replaces")]
+    RejectRbfInsufficientFee(TransactionId, u64, u64),
+
+    #[error("replacement transaction {0} has a feerate of {1} which is not higher than the feerate of {3} of the transaction {2} it replaces")]
+    RejectRbfInsufficientFeerate(TransactionId, f64, TransactionId, f64),
 }
 
 impl From<NonStandardError> for RuleError {
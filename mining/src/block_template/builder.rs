@@ -1,8 +1,13 @@
-use super::{errors::BuilderResult, policy::Policy};
-use crate::{block_template::selector::TransactionsSelector, model::candidate_tx::CandidateTransaction};
+use super::{
+    errors::BuilderResult,
+    package_selector::PackageTransactionsSelector,
+    policy::{Policy, TemplateSelectionMode},
+    selector::TransactionsSelector,
+};
+use crate::model::candidate_tx::CandidateTransaction;
 use kaspa_consensus_core::{
     api::ConsensusApi,
-    block::{BlockTemplate, TemplateBuildMode},
+    block::{BlockTemplate, TemplateBuildMode, TemplateTransactionSelector},
     coinbase::MinerData,
     merkle::calc_hash_merkle_root,
     tx::COINBASE_TRANSACTION_INDEX,
@@ -17,8 +22,8 @@ pub(crate) struct BlockTemplateBuilder {
 }
 
 impl BlockTemplateBuilder {
-    pub(crate) fn new(max_block_mass: u64) -> Self {
-        let policy = Policy::new(max_block_mass);
+    pub(crate) fn new(max_block_mass: u64, selection_mode: TemplateSelectionMode) -> Self {
+        let policy = Policy::new(max_block_mass, selection_mode);
         Self { policy }
     }
 
@@ -85,16 +90,25 @@ impl BlockTemplateBuilder {
     ///  |  transactions (while block size   |   |
     ///  |  <= policy.BlockMinSize)          |   |
     ///   -----------------------------------  --
+    ///
+    /// The `chained_transactions`, spending outputs of the candidate `transactions`, are only
+    /// considered by the [`TemplateSelectionMode::PackageFeerate`] selection mode.
     pub(crate) fn build_block_template(
         &self,
         consensus: &dyn ConsensusApi,
         miner_data: &MinerData,
         transactions: Vec<CandidateTransaction>,
+        chained_transactions: Vec<CandidateTransaction>,
         build_mode: TemplateBuildMode,
     ) -> BuilderResult<BlockTemplate> {
         let _sw = Stopwatch::<20>::with_threshold("build_block_template op");
         debug!("Considering {} transactions for a new block template", transactions.len());
-        let selector = Box::new(TransactionsSelector::new(self.policy.clone(), transactions));
+        let selector: Box<dyn TemplateTransactionSelector> = match self.policy.selection_mode {
+            TemplateSelectionMode::Probabilistic => Box::new(TransactionsSelector::new(self.policy.clone(), transactions)),
+            TemplateSelectionMode::PackageFeerate => {
+                Box::new(PackageTransactionsSelector::new(self.policy.clone(), transactions, chained_transactions))
+            }
+        };
         Ok(consensus.build_block_template(miner_data.clone(), selector, build_mode)?)
     }
 
@@ -1,5 +1,6 @@
 pub(crate) mod builder;
 pub(crate) mod errors;
 mod model;
+pub(crate) mod package_selector;
 pub(crate) mod policy;
 pub(crate) mod selector;
@@ -0,0 +1,423 @@
+use kaspa_core::{time::Stopwatch, trace, warn};
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    slice::Iter,
+};
+
+use crate::model::{candidate_tx::CandidateTransaction, topological_index::TopologicalIndex};
+
+use super::{model::tx::TransactionIndex, policy::Policy};
+use kaspa_consensus_core::{
+    block::TemplateTransactionSelector,
+    tx::{Transaction, TransactionId},
+};
+
+/// MAX_PACKAGE_SIZE is the maximum number of transactions in a package, that is
+/// a transaction along with its mempool ancestors. Transactions with larger ancestor
+/// sets are not evaluated as packages, which bounds the cost of deep chains.
+const MAX_PACKAGE_SIZE: usize = 25;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TxState {
+    Candidate,
+    Selected,
+    Rejected,
+    /// Can never be selected, either because of an unknown ancestor or because it
+    /// consumes subnetwork gas
+    Excluded,
+}
+
+/// The feerate of the package of a transaction, as evaluated at a given version of the transaction
+struct PackageScore {
+    feerate: f64,
+    index: TransactionIndex,
+    version: u32,
+}
+
+impl PartialEq for PackageScore {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PackageScore {}
+
+impl PartialOrd for PackageScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PackageScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Ties are broken by favoring the lower index, keeping the selection deterministic
+        self.feerate
+            .total_cmp(&other.feerate)
+            .then_with(|| other.index.cmp(&self.index))
+            .then_with(|| self.version.cmp(&other.version))
+    }
+}
+
+/// The unselected part of a package
+struct Package {
+    fee: u64,
+    mass: u64,
+    /// Members of the package which can be included in the block being built
+    ready: Vec<TransactionIndex>,
+}
+
+/// The dependency graph of the candidate transactions, edges going from parents to children
+struct TransactionsGraph {
+    nodes: Vec<TransactionIndex>,
+    children: Vec<Vec<TransactionIndex>>,
+}
+
+impl<'a> TopologicalIndex<'a, Iter<'a, TransactionIndex>, Iter<'a, TransactionIndex>, TransactionIndex> for TransactionsGraph {
+    fn topology_nodes(&'a self) -> Iter<'a, TransactionIndex> {
+        self.nodes.iter()
+    }
+
+    fn topology_node_edges(&'a self, key: &TransactionIndex) -> Option<Iter<'a, TransactionIndex>> {
+        self.children.get(*key).map(|x| x.iter())
+    }
+}
+
+/// A deterministic selector picking, at each step, the package with the highest feerate.
+///
+/// Since a block cannot include a transaction along with its parents, only the ready members
+/// of a package enter the block being built, the rest of the package being left to the
+/// following blocks. A high-fee child thus pays for its parents to be mined first.
+pub struct PackageTransactionsSelector {
+    policy: Policy,
+    /// Transaction store. The ready transactions come first, sorted by subnetwork, followed by
+    /// the transactions chained to them
+    transactions: Vec<CandidateTransaction>,
+    ready_count: usize,
+    /// Mempool ancestors of each transaction, sorted by index
+    ancestors: Vec<Vec<TransactionIndex>>,
+    /// Mempool descendants of each transaction
+    descendants: Vec<Vec<TransactionIndex>>,
+    states: Vec<TxState>,
+    /// Incremented every time the package of a transaction changes, outdating its queued scores
+    versions: Vec<u32>,
+    queue: BinaryHeap<PackageScore>,
+    /// Transactions whose package did not fit in the remaining block mass
+    deferred: Vec<TransactionIndex>,
+
+    /// Indexes of the transactions selected by the most recent call
+    selected_txs: Vec<TransactionIndex>,
+
+    /// Optional state for handling selection rejections. Maps from a selected tx id
+    /// to the index of the tx in the `transactions` vec
+    selected_txs_map: Option<HashMap<TransactionId, TransactionIndex>>,
+
+    overall_rejections: usize,
+    total_mass: u64,
+    total_fees: u64,
+}
+
+impl PackageTransactionsSelector {
+    /// Creates a selector over the `ready_transactions`, which have no parent in the mempool, and the
+    /// `chained_transactions` spending their outputs, which only contribute to the feerate of their ancestors.
+    pub fn new(
+        policy: Policy,
+        mut ready_transactions: Vec<CandidateTransaction>,
+        chained_transactions: Vec<CandidateTransaction>,
+    ) -> Self {
+        let _sw = Stopwatch::<100>::with_threshold("PackageTransactionsSelector::new op");
+        // Sort the ready transactions by subnetwork_id, so selected batches are sorted too.
+        ready_transactions.sort_by(|a, b| a.tx.subnetwork_id.cmp(&b.tx.subnetwork_id));
+
+        let ready_count = ready_transactions.len();
+        let mut transactions = ready_transactions;
+        let mut index_by_id: HashMap<TransactionId, TransactionIndex> =
+            transactions.iter().enumerate().map(|(i, tx)| (tx.tx.id(), i)).collect();
+        for tx in chained_transactions {
+            if let Entry::Vacant(entry) = index_by_id.entry(tx.tx.id()) {
+                entry.insert(transactions.len());
+                transactions.push(tx);
+            }
+        }
+
+        let mut parents: Vec<Vec<TransactionIndex>> = vec![vec![]; transactions.len()];
+        let mut graph = TransactionsGraph { nodes: (0..transactions.len()).collect(), children: vec![vec![]; transactions.len()] };
+        for (index, tx) in transactions.iter().enumerate().skip(ready_count) {
+            for input in tx.tx.inputs.iter() {
+                if let Some(&parent) = index_by_id.get(&input.previous_outpoint.transaction_id) {
+                    if !parents[index].contains(&parent) {
+                        parents[index].push(parent);
+                        graph.children[parent].push(index);
+                    }
+                }
+            }
+        }
+
+        let mut states = vec![TxState::Candidate; transactions.len()];
+        let mut ancestors: Vec<Vec<TransactionIndex>> = vec![vec![]; transactions.len()];
+        match graph.topological_index() {
+            Ok(order) => {
+                for index in order.into_iter().filter(|&index| index >= ready_count) {
+                    // A chained transaction whose parents are unknown cannot be anchored to the ready transactions
+                    if parents[index].is_empty() || parents[index].iter().any(|&parent| states[parent] == TxState::Excluded) {
+                        states[index] = TxState::Excluded;
+                        continue;
+                    }
+                    let mut index_ancestors = parents[index].clone();
+                    parents[index].iter().for_each(|&parent| index_ancestors.extend_from_slice(&ancestors[parent]));
+                    index_ancestors.sort_unstable();
+                    index_ancestors.dedup();
+                    if index_ancestors.len() >= MAX_PACKAGE_SIZE {
+                        states[index] = TxState::Excluded;
+                        continue;
+                    }
+                    ancestors[index] = index_ancestors;
+                }
+            }
+            Err(err) => {
+                warn!("Chained transactions are ignored by the block template selection: {:?}", err);
+                states.iter_mut().skip(ready_count).for_each(|state| *state = TxState::Excluded);
+            }
+        }
+
+        // Gas limits are not implemented yet, so transactions consuming subnetwork gas are never selected
+        for (index, tx) in transactions.iter().enumerate() {
+            if !tx.tx.subnetwork_id.is_builtin_or_native() && tx.tx.gas > 0 {
+                states[index] = TxState::Excluded;
+            }
+        }
+
+        let mut descendants: Vec<Vec<TransactionIndex>> = vec![vec![]; transactions.len()];
+        for (index, index_ancestors) in ancestors.iter().enumerate() {
+            if states[index] != TxState::Excluded {
+                index_ancestors.iter().for_each(|&ancestor| descendants[ancestor].push(index));
+            }
+        }
+
+        let mut selector = Self {
+            policy,
+            versions: vec![0; transactions.len()],
+            transactions,
+            ready_count,
+            ancestors,
+            descendants,
+            states,
+            queue: Default::default(),
+            deferred: Default::default(),
+            selected_txs: Default::default(),
+            selected_txs_map: None,
+            overall_rejections: 0,
+            total_mass: 0,
+            total_fees: 0,
+        };
+        (0..selector.transactions.len()).for_each(|index| selector.enqueue(index));
+        selector
+    }
+
+    /// select_transactions implements a greedy package selection algorithm:
+    /// 1. Every candidate transaction is scored by the feerate of its package, that is
+    ///    the transaction along with its yet unselected mempool ancestors.
+    /// 2. The package with the highest feerate is popped. If its ready members fit
+    ///    into the remaining block mass, they are selected, otherwise it is deferred.
+    /// 3. The packages of the descendants of the selected transactions are rescored.
+    /// 4. Continue iterating the above until all packages were either selected or deferred.
+    ///
+    /// Following calls (after rejections) retry the deferred packages and only return
+    /// newly selected transactions.
+    pub fn select_transactions(&mut self) -> Vec<Transaction> {
+        let _sw = Stopwatch::<15>::with_threshold("select_transaction op");
+        self.selected_txs.clear();
+        self.selected_txs_map = None;
+        for index in std::mem::take(&mut self.deferred) {
+            self.enqueue(index);
+        }
+
+        while let Some(score) = self.queue.pop() {
+            if score.version != self.versions[score.index] {
+                continue;
+            }
+            let Some(package) = self.package(score.index) else {
+                continue;
+            };
+            if package.ready.is_empty() {
+                continue;
+            }
+
+            let ready_mass = package.ready.iter().map(|&index| self.transactions[index].calculated_mass).sum::<u64>();
+            let next_total_mass = self.total_mass.checked_add(ready_mass);
+            if next_total_mass.is_none() || next_total_mass.unwrap() > self.policy.max_block_mass {
+                trace!(
+                    "Package of tx {0} would exceed the max block mass. As such, deferring it.",
+                    self.transactions[score.index].tx.id()
+                );
+                self.deferred.push(score.index);
+                continue;
+            }
+
+            for &index in package.ready.iter() {
+                let selected_tx = &self.transactions[index];
+                self.total_mass += selected_tx.calculated_mass;
+                self.total_fees += selected_tx.calculated_fee;
+                trace!("Adding tx {0} (package fee per megagram: {1})", selected_tx.tx.id(), package.fee * 1_000_000 / package.mass);
+                self.states[index] = TxState::Selected;
+                self.selected_txs.push(index);
+            }
+            for &index in package.ready.iter() {
+                for descendant in self.descendants[index].clone() {
+                    self.enqueue(descendant);
+                }
+            }
+        }
+
+        self.selected_txs.sort();
+
+        self.get_transactions()
+    }
+
+    fn get_transactions(&self) -> Vec<Transaction> {
+        // These transactions leave the selector so we clone
+        self.selected_txs.iter().map(|x| self.transactions[*x].tx.as_ref().clone()).collect()
+    }
+
+    /// Returns the unselected part of the package of a transaction, or `None` if the package can
+    /// never be selected.
+    fn package(&self, index: TransactionIndex) -> Option<Package> {
+        if self.states[index] != TxState::Candidate {
+            return None;
+        }
+        let mut package = Package { fee: 0, mass: 0, ready: vec![] };
+        for &member in self.ancestors[index].iter().chain(std::iter::once(&index)) {
+            match self.states[member] {
+                TxState::Candidate => {
+                    package.fee += self.transactions[member].calculated_fee;
+                    package.mass += self.transactions[member].calculated_mass;
+                    if member < self.ready_count {
+                        package.ready.push(member);
+                    }
+                }
+                TxState::Selected => {}
+                TxState::Rejected | TxState::Excluded => return None,
+            }
+        }
+        Some(package)
+    }
+
+    /// Queues the transaction with the current feerate of its package, outdating its previous scores
+    fn enqueue(&mut self, index: TransactionIndex) {
+        self.versions[index] += 1;
+        if let Some(package) = self.package(index) {
+            if !package.ready.is_empty() {
+                let feerate = package.fee as f64 / package.mass as f64;
+                self.queue.push(PackageScore { feerate, index, version: self.versions[index] });
+            }
+        }
+    }
+}
+
+impl TemplateTransactionSelector for PackageTransactionsSelector {
+    fn select_transactions(&mut self) -> Vec<Transaction> {
+        self.select_transactions()
+    }
+
+    fn reject_selection(&mut self, tx_id: TransactionId) {
+        let selected_txs_map = self
+            .selected_txs_map
+            // We lazy-create the map only when there are actual rejections
+            .get_or_insert_with(|| self.selected_txs.iter().map(|&x| (self.transactions[x].tx.id(), x)).collect());
+        let tx_index = selected_txs_map.remove(&tx_id).expect("only previously selected txs can be rejected (and only once)");
+        let tx = &self.transactions[tx_index];
+        self.total_mass -= tx.calculated_mass;
+        self.total_fees -= tx.calculated_fee;
+        // The packages of the descendants now include a rejected tx, so they will be discarded when evaluated
+        self.states[tx_index] = TxState::Rejected;
+        self.overall_rejections += 1;
+    }
+
+    fn is_successful(&self) -> bool {
+        const SUFFICIENT_MASS_THRESHOLD: f64 = 0.8;
+        const LOW_REJECTION_FRACTION: f64 = 0.2;
+
+        // We consider the operation successful if either mass occupation is above 80% or rejection rate is below 20%
+        self.overall_rejections == 0
+            || (self.total_mass as f64) > self.policy.max_block_mass as f64 * SUFFICIENT_MASS_THRESHOLD
+            || (self.overall_rejections as f64) < self.ready_count as f64 * LOW_REJECTION_FRACTION
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block_template::policy::TemplateSelectionMode;
+    use kaspa_consensus_core::{
+        constants::{MAX_TX_IN_SEQUENCE_NUM, TX_VERSION},
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+    use kaspa_txscript::test_helpers::op_true_script;
+    use std::sync::Arc;
+
+    const TX_MASS: u64 = 1_000;
+
+    #[test]
+    fn test_child_pays_for_parent() {
+        // The block can only hold one of the two ready transactions
+        let policy = Policy::new(TX_MASS * 3 / 2, TemplateSelectionMode::PackageFeerate);
+        let parent = create_transaction(1, &[], 1_000);
+        let child = create_transaction(2, &[&parent], 10_000);
+        let grandchild = create_transaction(3, &[&child], 20_000);
+        let unrelated = create_transaction(4, &[], 2_000);
+
+        let mut selector = PackageTransactionsSelector::new(policy.clone(), vec![unrelated.clone(), parent.clone()], vec![]);
+        assert_eq!(
+            ids(selector.select_transactions()),
+            vec![unrelated.tx.id()],
+            "without its descendants, the parent has the lowest feerate"
+        );
+
+        let mut selector = PackageTransactionsSelector::new(policy, vec![unrelated.clone(), parent.clone()], vec![grandchild, child]);
+        assert_eq!(ids(selector.select_transactions()), vec![parent.tx.id()], "the descendants should pay for the parent");
+        assert!(selector.select_transactions().is_empty(), "chained transactions should never be selected");
+    }
+
+    #[test]
+    fn test_reject_transaction() {
+        // The block can hold two of the three ready transactions
+        let policy = Policy::new(TX_MASS * 2, TemplateSelectionMode::PackageFeerate);
+        let parent = create_transaction(1, &[], 1_000);
+        let child = create_transaction(2, &[&parent], 10_000);
+        let high = create_transaction(3, &[], 3_000);
+        let medium = create_transaction(4, &[], 2_000);
+        let orphan = create_transaction(5, &[&create_transaction(6, &[], 50_000)], 50_000);
+
+        let mut selector =
+            PackageTransactionsSelector::new(policy, vec![medium.clone(), high.clone(), parent.clone()], vec![child, orphan]);
+        let selected = ids(selector.select_transactions());
+        assert_eq!(selected.len(), 2);
+        assert!(selected.contains(&parent.tx.id()) && selected.contains(&high.tx.id()));
+
+        selector.reject_selection(parent.tx.id());
+        assert_eq!(ids(selector.select_transactions()), vec![medium.tx.id()], "the refill should only return the deferred package");
+        assert!(selector.select_transactions().is_empty());
+        assert!(selector.is_successful());
+    }
+
+    fn ids(transactions: Vec<Transaction>) -> Vec<TransactionId> {
+        transactions.iter().map(|tx| tx.id()).collect()
+    }
+
+    fn create_transaction(value: u64, parents: &[&CandidateTransaction], fee: u64) -> CandidateTransaction {
+        let previous_outpoints = match parents.is_empty() {
+            true => vec![TransactionOutpoint::new(TransactionId::default(), 0)],
+            false => parents.iter().map(|parent| TransactionOutpoint::new(parent.tx.id(), 0)).collect(),
+        };
+        let (script_public_key, _) = op_true_script();
+        let inputs = previous_outpoints
+            .into_iter()
+            .map(|previous_outpoint| TransactionInput::new(previous_outpoint, vec![], MAX_TX_IN_SEQUENCE_NUM, 1))
+            .collect();
+        let output = TransactionOutput::new(value, script_public_key);
+        let tx = Arc::new(Transaction::new(TX_VERSION, inputs, vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]));
+
+        CandidateTransaction { tx, calculated_fee: fee, calculated_mass: TX_MASS }
+    }
+}
@@ -1,14 +1,52 @@
+use std::{fmt::Display, str::FromStr};
+
 /// Policy houses the policy (configuration parameters) which is used to control
 /// the generation of block templates. See the documentation for
 /// NewBlockTemplate for more details on each of these parameters are used.
 #[derive(Clone)]
-pub(crate) struct Policy {
+pub struct Policy {
     /// max_block_mass is the maximum block mass to be used when generating a block template.
     pub(crate) max_block_mass: u64,
+    /// selection_mode defines the algorithm selecting the transactions of a block template.
+    pub(crate) selection_mode: TemplateSelectionMode,
 }
 
 impl Policy {
-    pub(crate) fn new(max_block_mass: u64) -> Self {
-        Self { max_block_mass }
+    pub fn new(max_block_mass: u64, selection_mode: TemplateSelectionMode) -> Self {
+        Self { max_block_mass, selection_mode }
+    }
+}
+
+/// The algorithm selecting the mempool transactions included in a block template
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TemplateSelectionMode {
+    /// Samples ready transactions with a probability growing with their feerate,
+    /// ignoring the transactions chained to them
+    #[default]
+    Probabilistic,
+    /// Greedily selects the transaction packages with the highest feerate, a package
+    /// being a transaction along with its mempool ancestors, so that a high-fee child
+    /// raises the priority of its low-fee parents (child pays for parent)
+    PackageFeerate,
+}
+
+impl Display for TemplateSelectionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateSelectionMode::Probabilistic => write!(f, "probabilistic"),
+            TemplateSelectionMode::PackageFeerate => write!(f, "package-feerate"),
+        }
+    }
+}
+
+impl FromStr for TemplateSelectionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "probabilistic" => Ok(TemplateSelectionMode::Probabilistic),
+            "package-feerate" => Ok(TemplateSelectionMode::PackageFeerate),
+            _ => Err(format!("unknown template selection mode '{s}', expected 'probabilistic' or 'package-feerate'")),
+        }
     }
 }
@@ -28,7 +28,7 @@ const ALPHA: i32 = 3;
 /// if REBALANCE_THRESHOLD is 0.95, there's a 1-in-20 chance of collision.
 const REBALANCE_THRESHOLD: f64 = 0.95;
 
-pub(crate) struct TransactionsSelector {
+pub struct TransactionsSelector {
     policy: Policy,
     /// Transaction store
     transactions: Vec<CandidateTransaction>,
@@ -53,7 +53,7 @@ pub(crate) struct TransactionsSelector {
 }
 
 impl TransactionsSelector {
-    pub(crate) fn new(policy: Policy, mut transactions: Vec<CandidateTransaction>) -> Self {
+    pub fn new(policy: Policy, mut transactions: Vec<CandidateTransaction>) -> Self {
         let _sw = Stopwatch::<100>::with_threshold("TransactionsSelector::new op");
         // Sort the transactions by subnetwork_id.
         transactions.sort_by(|a, b| a.tx.subnetwork_id.cmp(&b.tx.subnetwork_id));
@@ -103,7 +103,7 @@ impl TransactionsSelector {
     /// select_transactions loops over the candidate transactions
     /// and appends the ones that will be included in the next block into
     /// selected_txs.
-    pub(crate) fn select_transactions(&mut self) -> Vec<Transaction> {
+    pub fn select_transactions(&mut self) -> Vec<Transaction> {
         let _sw = Stopwatch::<15>::with_threshold("select_transaction op");
         let mut rng = rand::thread_rng();
 
@@ -273,7 +273,10 @@ mod tests {
     use kaspa_txscript::{pay_to_script_hash_signature_script, test_helpers::op_true_script};
     use std::{collections::HashSet, sync::Arc};
 
-    use crate::{mempool::config::DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE, model::candidate_tx::CandidateTransaction};
+    use crate::{
+        block_template::policy::TemplateSelectionMode, mempool::config::DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
+        model::candidate_tx::CandidateTransaction,
+    };
 
     #[test]
     fn test_reject_transaction() {
@@ -281,7 +284,7 @@ mod tests {
 
         // Create a vector of transactions differing by output value so they have unique ids
         let transactions = (0..TX_INITIAL_COUNT).map(|i| create_transaction(LEOR_PER_PYRIN * (i + 1) as u64)).collect_vec();
-        let policy = Policy::new(100_000);
+        let policy = Policy::new(100_000, TemplateSelectionMode::Probabilistic);
         let mut selector = TransactionsSelector::new(policy, transactions);
         let (mut kept, mut rejected) = (HashSet::new(), HashSet::new());
         let mut reject_count = 32;
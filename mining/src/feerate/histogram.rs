@@ -0,0 +1,150 @@
+/// Number of histogram buckets covering each doubling of the feerate
+pub(crate) const FEERATE_BUCKETS_PER_DOUBLING: usize = 4;
+
+/// Total number of histogram buckets. With 4 buckets per doubling, the last bucket starts at
+/// 2^24 times the minimum relay feerate which is far above any realistic fee.
+pub(crate) const FEERATE_BUCKET_COUNT: usize = 96;
+
+/// Aggregated load of the mempool transactions falling in a feerate bucket
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeerateBucketLoad {
+    /// Total mass of the transactions in the bucket
+    pub mass: u64,
+    /// Number of transactions in the bucket
+    pub count: u64,
+}
+
+/// A view of the mempool transactions bucketed by feerate on a logarithmic scale.
+///
+/// Bucket `i` covers feerates in `[min * 2^(i/4), min * 2^((i+1)/4))` where `min` is the
+/// minimum relay feerate. Transactions below the minimum (which can only be high priority ones)
+/// are counted in the first bucket and transactions above the last bound are counted in the last one.
+///
+/// The histogram is maintained incrementally by the transactions pool on every insertion and removal.
+#[derive(Clone, Debug)]
+pub(crate) struct FeerateHistogram {
+    minimum_feerate: f64,
+    buckets: Vec<FeerateBucketLoad>,
+    total_mass: u64,
+    total_count: u64,
+}
+
+impl FeerateHistogram {
+    /// Creates an empty histogram based at `minimum_feerate`. A non-positive minimum (i.e. a mempool
+    /// relaying free transactions) falls back to a base of 1 sompi per gram.
+    pub(crate) fn new(minimum_feerate: f64) -> Self {
+        let minimum_feerate = if minimum_feerate > 0.0 { minimum_feerate } else { 1.0 };
+        Self { minimum_feerate, buckets: vec![FeerateBucketLoad::default(); FEERATE_BUCKET_COUNT], total_mass: 0, total_count: 0 }
+    }
+
+    pub(crate) fn minimum_feerate(&self) -> f64 {
+        self.minimum_feerate
+    }
+
+    pub(crate) fn total_mass(&self) -> u64 {
+        self.total_mass
+    }
+
+    pub(crate) fn total_count(&self) -> u64 {
+        self.total_count
+    }
+
+    pub(crate) fn buckets(&self) -> &[FeerateBucketLoad] {
+        &self.buckets
+    }
+
+    /// Returns the index of the bucket holding transactions paying `fee` for `mass`.
+    ///
+    /// Note that the index is derived from the integer fee and mass only, so that adding and later
+    /// removing the same transaction always hits the same bucket.
+    pub(crate) fn bucket_index(&self, fee: u64, mass: u64) -> usize {
+        if mass == 0 {
+            return 0;
+        }
+        let ratio = fee as f64 / mass as f64 / self.minimum_feerate;
+        if ratio <= 1.0 {
+            return 0;
+        }
+        let index = (ratio.log2() * FEERATE_BUCKETS_PER_DOUBLING as f64).floor() as usize;
+        index.min(FEERATE_BUCKET_COUNT - 1)
+    }
+
+    /// Returns the lowest feerate of bucket `index`. `index` may equal [`FEERATE_BUCKET_COUNT`],
+    /// in which case the upper bound of the last bucket is returned.
+    pub(crate) fn bucket_lower_bound(&self, index: usize) -> f64 {
+        self.minimum_feerate * 2f64.powf(index as f64 / FEERATE_BUCKETS_PER_DOUBLING as f64)
+    }
+
+    pub(crate) fn add(&mut self, fee: u64, mass: u64) {
+        let index = self.bucket_index(fee, mass);
+        let bucket = &mut self.buckets[index];
+        bucket.mass += mass;
+        bucket.count += 1;
+        self.total_mass += mass;
+        self.total_count += 1;
+    }
+
+    pub(crate) fn remove(&mut self, fee: u64, mass: u64) {
+        let index = self.bucket_index(fee, mass);
+        let bucket = &mut self.buckets[index];
+        bucket.mass = bucket.mass.saturating_sub(mass);
+        bucket.count = bucket.count.saturating_sub(1);
+        self.total_mass = self.total_mass.saturating_sub(mass);
+        self.total_count = self.total_count.saturating_sub(1);
+    }
+
+    /// Returns the total mass of the transactions in bucket `index` and all buckets above it
+    pub(crate) fn mass_at_or_above(&self, index: usize) -> u64 {
+        self.buckets.iter().skip(index).map(|bucket| bucket.mass).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feerate_histogram_add_remove() {
+        let mut histogram = FeerateHistogram::new(1.0);
+
+        // Below, at and just above the minimum all fall in the first bucket
+        assert_eq!(histogram.bucket_index(500, 1000), 0);
+        assert_eq!(histogram.bucket_index(1000, 1000), 0);
+        assert_eq!(histogram.bucket_index(1100, 1000), 0);
+        // Doubling the feerate moves 4 buckets up
+        assert_eq!(histogram.bucket_index(2000, 1000), FEERATE_BUCKETS_PER_DOUBLING);
+        assert_eq!(histogram.bucket_index(4000, 1000), 2 * FEERATE_BUCKETS_PER_DOUBLING);
+        // Extreme feerates are capped by the last bucket
+        assert_eq!(histogram.bucket_index(u64::MAX, 1), FEERATE_BUCKET_COUNT - 1);
+        // A massless transaction cannot be evaluated and lands in the first bucket
+        assert_eq!(histogram.bucket_index(1000, 0), 0);
+
+        histogram.add(1000, 1000);
+        histogram.add(2000, 1000);
+        histogram.add(4500, 1500);
+        assert_eq!(histogram.total_count(), 3);
+        assert_eq!(histogram.total_mass(), 3500);
+        assert_eq!(histogram.mass_at_or_above(0), 3500);
+        assert_eq!(histogram.mass_at_or_above(FEERATE_BUCKETS_PER_DOUBLING), 2500);
+        assert_eq!(histogram.mass_at_or_above(FEERATE_BUCKETS_PER_DOUBLING + 1), 1500);
+
+        histogram.remove(2000, 1000);
+        assert_eq!(histogram.total_count(), 2);
+        assert_eq!(histogram.total_mass(), 2500);
+        assert_eq!(histogram.buckets()[FEERATE_BUCKETS_PER_DOUBLING], FeerateBucketLoad::default());
+    }
+
+    #[test]
+    fn test_feerate_histogram_bounds() {
+        let histogram = FeerateHistogram::new(1.0);
+        for index in 0..FEERATE_BUCKET_COUNT {
+            let lower_bound = histogram.bucket_lower_bound(index);
+            let upper_bound = histogram.bucket_lower_bound(index + 1);
+            assert!(lower_bound < upper_bound);
+            // A feerate in the middle of the bucket bounds must map to the bucket
+            let mass = 1_000_000;
+            let fee = ((lower_bound + upper_bound) / 2.0 * mass as f64) as u64;
+            assert_eq!(histogram.bucket_index(fee, mass), index, "feerate {} should fall in bucket {}", fee as f64 / mass as f64, index);
+        }
+    }
+}
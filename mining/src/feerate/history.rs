@@ -0,0 +1,103 @@
+use std::collections::VecDeque;
+
+/// Number of recent block templates considered by the estimator
+pub(crate) const DEFAULT_TEMPLATE_HISTORY_SIZE: usize = 32;
+
+/// A block template is considered congested if the selected transactions fill
+/// at least this fraction of the maximum block mass
+const CONGESTED_TEMPLATE_MASS_RATIO: f64 = 0.9;
+
+/// Summary of the transaction selection performed while building a block template
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TemplateSelectionSample {
+    /// Lowest feerate among the selected transactions, if any was selected
+    pub min_selected_feerate: Option<f64>,
+    /// Ratio between the mass of the selected transactions and the maximum block mass
+    pub mass_ratio: f64,
+    /// Indicates whether some candidate transactions were left out of the template
+    pub has_unselected_candidates: bool,
+}
+
+impl TemplateSelectionSample {
+    pub(crate) fn new(min_selected_feerate: Option<f64>, mass_ratio: f64, has_unselected_candidates: bool) -> Self {
+        Self { min_selected_feerate, mass_ratio, has_unselected_candidates }
+    }
+
+    /// A congested template is a full template that had to leave candidates out, meaning that
+    /// paying less than its minimum selected feerate would not have been enough to get in.
+    fn is_congested(&self) -> bool {
+        self.has_unselected_candidates && self.mass_ratio >= CONGESTED_TEMPLATE_MASS_RATIO
+    }
+}
+
+/// Bounded history of the most recent block template selections
+pub(crate) struct TemplateSelectionHistory {
+    samples: VecDeque<TemplateSelectionSample>,
+    capacity: usize,
+}
+
+impl TemplateSelectionHistory {
+    pub(crate) fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        Self { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub(crate) fn push(&mut self, sample: TemplateSelectionSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Returns the feerate a transaction recently had to pay in order to be selected in a block template.
+    ///
+    /// The floor is defined only when the majority of the recent templates were congested, in which case
+    /// it is the median of their minimum selected feerates. Otherwise the block space was not contended
+    /// and `None` is returned.
+    pub(crate) fn feerate_floor(&self) -> Option<f64> {
+        let mut congested_feerates =
+            self.samples.iter().filter(|sample| sample.is_congested()).filter_map(|sample| sample.min_selected_feerate).collect::<Vec<_>>();
+        if congested_feerates.is_empty() || congested_feerates.len() * 2 <= self.samples.len() {
+            return None;
+        }
+        congested_feerates.sort_by(f64::total_cmp);
+        Some(congested_feerates[congested_feerates.len() / 2])
+    }
+}
+
+impl Default for TemplateSelectionHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEMPLATE_HISTORY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_selection_history_floor() {
+        let mut history = TemplateSelectionHistory::new(4);
+        assert_eq!(history.feerate_floor(), None);
+
+        // Full templates without any left out candidate do not define a floor
+        history.push(TemplateSelectionSample::new(Some(1.0), 1.0, false));
+        history.push(TemplateSelectionSample::new(Some(1.0), 1.0, false));
+        assert_eq!(history.feerate_floor(), None);
+
+        // Half of the samples congested is not a majority
+        history.push(TemplateSelectionSample::new(Some(5.0), 0.95, true));
+        history.push(TemplateSelectionSample::new(Some(3.0), 0.99, true));
+        assert_eq!(history.feerate_floor(), None);
+
+        // Oldest samples are evicted, leaving 3 congested samples out of 4
+        history.push(TemplateSelectionSample::new(Some(4.0), 0.98, true));
+        assert_eq!(history.samples.len(), 4);
+        assert_eq!(history.feerate_floor(), Some(4.0));
+
+        // Partially filled templates are not congested even if candidates were left out
+        history.push(TemplateSelectionSample::new(Some(8.0), 0.5, true));
+        history.push(TemplateSelectionSample::new(Some(8.0), 0.5, true));
+        assert_eq!(history.feerate_floor(), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! Feerate estimation based on the current mempool load and on the recent block template selections.
+//!
+//! Feerates are expressed in sompi per gram of transaction mass.
+
+use self::histogram::{FeerateHistogram, FEERATE_BUCKET_COUNT};
+
+pub(crate) mod histogram;
+pub(crate) mod history;
+
+/// Targets, in seconds, of the inclusion times reported by the normal buckets
+const NORMAL_BUCKET_TARGET_SECONDS: [f64; 2] = [30.0, 60.0];
+
+/// Targets, in seconds, of the inclusion times reported by the low buckets
+const LOW_BUCKET_TARGET_SECONDS: [f64; 2] = [600.0, 3600.0];
+
+/// A feerate along with the expected time it takes a transaction paying it to be included in a block
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeerateBucket {
+    /// Feerate in sompi per gram
+    pub feerate: f64,
+    /// Estimated inclusion time in seconds
+    pub estimated_seconds: f64,
+}
+
+impl FeerateBucket {
+    pub fn new(feerate: f64, estimated_seconds: f64) -> Self {
+        Self { feerate, estimated_seconds }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeerateEstimations {
+    /// Feerate expected to get the transaction included in the next block
+    pub priority_bucket: FeerateBucket,
+    /// Feerates expected to get the transaction included within about a minute, in decreasing order
+    pub normal_buckets: Vec<FeerateBucket>,
+    /// Feerates expected to get the transaction included within about an hour, in decreasing order
+    pub low_buckets: Vec<FeerateBucket>,
+}
+
+impl FeerateEstimations {
+    /// Returns all buckets, ordered by decreasing feerate
+    pub fn ordered_buckets(&self) -> Vec<FeerateBucket> {
+        std::iter::once(self.priority_bucket).chain(self.normal_buckets.iter().copied()).chain(self.low_buckets.iter().copied()).collect()
+    }
+}
+
+/// A non-empty bucket of the mempool feerate histogram
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeerateHistogramBucket {
+    /// Lowest feerate of the bucket in sompi per gram
+    pub feerate: f64,
+    /// Total mass of the mempool transactions in the bucket
+    pub mass: u64,
+    /// Number of mempool transactions in the bucket
+    pub count: u64,
+}
+
+/// Feerate estimations along with the raw data they were computed from
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeerateEstimationsVerbose {
+    pub estimations: FeerateEstimations,
+    /// Non-empty buckets of the mempool feerate histogram, ordered by increasing feerate
+    pub mempool_histogram: Vec<FeerateHistogramBucket>,
+    pub mempool_transaction_count: u64,
+    pub mempool_total_mass: u64,
+    /// Block mass the network is expected to process every second
+    pub network_mass_per_second: u64,
+    /// Minimum feerate recently selected in congested block templates, or the minimum relay
+    /// feerate if block templates were not congested
+    pub recent_templates_feerate_floor: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FeerateEstimatorArgs {
+    pub target_time_per_block: u64,
+    pub maximum_mass_per_block: u64,
+}
+
+impl FeerateEstimatorArgs {
+    pub(crate) fn new(target_time_per_block: u64, maximum_mass_per_block: u64) -> Self {
+        Self { target_time_per_block, maximum_mass_per_block }
+    }
+
+    fn seconds_per_block(&self) -> f64 {
+        self.target_time_per_block as f64 / 1000.0
+    }
+
+    fn network_mass_per_second(&self) -> f64 {
+        self.maximum_mass_per_block as f64 / self.seconds_per_block()
+    }
+}
+
+/// Computes feerate estimations out of a snapshot of the mempool feerate histogram.
+///
+/// The estimator assumes that block templates select transactions by decreasing feerate, so that
+/// a transaction paying feerate `f` waits for all the mempool mass paying at least `f` to be mined.
+/// The expected inclusion time is thus the number of blocks needed to clear this mass (at least one)
+/// times the target time per block.
+pub(crate) struct FeerateEstimator {
+    histogram: FeerateHistogram,
+    feerate_floor: Option<f64>,
+    args: FeerateEstimatorArgs,
+}
+
+impl FeerateEstimator {
+    pub(crate) fn new(histogram: FeerateHistogram, feerate_floor: Option<f64>, args: FeerateEstimatorArgs) -> Self {
+        Self { histogram, feerate_floor, args }
+    }
+
+    /// Returns the lowest histogram bucket index whose feerate gets a transaction included within `target_seconds`
+    fn bucket_index_for_target(&self, target_seconds: f64) -> usize {
+        let capacity = self.args.network_mass_per_second() * target_seconds.max(self.args.seconds_per_block());
+        let mut mass_above = 0u64;
+        for (index, bucket) in self.histogram.buckets().iter().enumerate().rev() {
+            if (mass_above + bucket.mass) as f64 > capacity {
+                return index + 1;
+            }
+            mass_above += bucket.mass;
+        }
+        0
+    }
+
+    fn estimated_seconds(&self, index: usize) -> f64 {
+        let blocks = self.histogram.mass_at_or_above(index) as f64 / self.args.maximum_mass_per_block as f64;
+        blocks.max(1.0) * self.args.seconds_per_block()
+    }
+
+    fn bucket_for_index(&self, index: usize) -> FeerateBucket {
+        FeerateBucket::new(self.histogram.bucket_lower_bound(index), self.estimated_seconds(index))
+    }
+
+    fn priority_bucket(&self) -> FeerateBucket {
+        let index = self.bucket_index_for_target(self.args.seconds_per_block());
+        let bucket = self.bucket_for_index(index);
+        match self.feerate_floor {
+            // Recent templates were congested: the next one will likely not accept less than the floor
+            Some(floor) if floor > bucket.feerate => {
+                let floor_index = (index..=FEERATE_BUCKET_COUNT)
+                    .find(|&i| self.histogram.bucket_lower_bound(i) >= floor)
+                    .unwrap_or(FEERATE_BUCKET_COUNT);
+                FeerateBucket::new(floor, self.estimated_seconds(floor_index))
+            }
+            _ => bucket,
+        }
+    }
+
+    fn buckets_for_targets(&self, targets: &[f64], max_feerate: f64) -> Vec<FeerateBucket> {
+        let mut max_feerate = max_feerate;
+        targets
+            .iter()
+            .map(|&target| {
+                let mut bucket = self.bucket_for_index(self.bucket_index_for_target(target));
+                // Keep buckets ordered by decreasing feerate even if the floor lowered the priority bucket
+                if bucket.feerate > max_feerate {
+                    bucket.feerate = max_feerate;
+                }
+                max_feerate = bucket.feerate;
+                bucket
+            })
+            .collect()
+    }
+
+    pub(crate) fn calc_estimations(&self) -> FeerateEstimations {
+        let priority_bucket = self.priority_bucket();
+        let normal_buckets = self.buckets_for_targets(&NORMAL_BUCKET_TARGET_SECONDS, priority_bucket.feerate);
+        let low_buckets =
+            self.buckets_for_targets(&LOW_BUCKET_TARGET_SECONDS, normal_buckets.last().map_or(priority_bucket.feerate, |b| b.feerate));
+        FeerateEstimations { priority_bucket, normal_buckets, low_buckets }
+    }
+
+    pub(crate) fn calc_estimations_verbose(&self) -> FeerateEstimationsVerbose {
+        let mempool_histogram = self
+            .histogram
+            .buckets()
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| bucket.count > 0)
+            .map(|(index, bucket)| FeerateHistogramBucket {
+                feerate: self.histogram.bucket_lower_bound(index),
+                mass: bucket.mass,
+                count: bucket.count,
+            })
+            .collect();
+        FeerateEstimationsVerbose {
+            estimations: self.calc_estimations(),
+            mempool_histogram,
+            mempool_transaction_count: self.histogram.total_count(),
+            mempool_total_mass: self.histogram.total_mass(),
+            network_mass_per_second: self.args.network_mass_per_second() as u64,
+            recent_templates_feerate_floor: self.feerate_floor.unwrap_or(self.histogram.minimum_feerate()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{histogram::FEERATE_BUCKETS_PER_DOUBLING, *};
+
+    const MAX_BLOCK_MASS: u64 = 500_000;
+
+    fn estimator(histogram: FeerateHistogram, feerate_floor: Option<f64>) -> FeerateEstimator {
+        // One block per second
+        FeerateEstimator::new(histogram, feerate_floor, FeerateEstimatorArgs::new(1000, MAX_BLOCK_MASS))
+    }
+
+    fn assert_ordered(estimations: &FeerateEstimations) {
+        let buckets = estimations.ordered_buckets();
+        assert_eq!(buckets.len(), 1 + NORMAL_BUCKET_TARGET_SECONDS.len() + LOW_BUCKET_TARGET_SECONDS.len());
+        for pair in buckets.windows(2) {
+            assert!(pair[0].feerate >= pair[1].feerate, "buckets are expected to be ordered by decreasing feerate");
+        }
+    }
+
+    #[test]
+    fn test_feerate_estimations_empty_mempool() {
+        let estimations = estimator(FeerateHistogram::new(1.0), None).calc_estimations();
+        assert_ordered(&estimations);
+        // With no competition, the minimum feerate is included in the next block
+        for bucket in estimations.ordered_buckets() {
+            assert_eq!(bucket, FeerateBucket::new(1.0, 1.0));
+        }
+    }
+
+    #[test]
+    fn test_feerate_estimations_congested_mempool() {
+        let mut histogram = FeerateHistogram::new(1.0);
+        // 2 blocks worth of mass paying 16 sompi/gram
+        for _ in 0..10 {
+            histogram.add(16 * 100_000, 100_000);
+        }
+        // 100 blocks worth of mass paying 2 sompi/gram
+        for _ in 0..500 {
+            histogram.add(2 * 100_000, 100_000);
+        }
+        let estimator = estimator(histogram, None);
+        let estimations = estimator.calc_estimations();
+        assert_ordered(&estimations);
+
+        // Getting into the next block requires outbidding the top bucket
+        let top_index = 4 * FEERATE_BUCKETS_PER_DOUBLING;
+        assert_eq!(estimations.priority_bucket.feerate, estimator.histogram.bucket_lower_bound(top_index + 1));
+        assert_eq!(estimations.priority_bucket.estimated_seconds, 1.0);
+
+        // Within 30 and 60 seconds, outbidding the 2 sompi/gram bucket is enough
+        let middle_index = FEERATE_BUCKETS_PER_DOUBLING;
+        for bucket in estimations.normal_buckets.iter() {
+            assert_eq!(bucket.feerate, estimator.histogram.bucket_lower_bound(middle_index + 1));
+            assert_eq!(bucket.estimated_seconds, 2.0);
+        }
+
+        // Within 10 minutes everything is cleared
+        for bucket in estimations.low_buckets.iter() {
+            assert_eq!(bucket.feerate, 1.0);
+            assert_eq!(bucket.estimated_seconds, 102.0);
+        }
+
+        let verbose = estimator.calc_estimations_verbose();
+        assert_eq!(verbose.estimations, estimations);
+        assert_eq!(verbose.mempool_histogram.len(), 2);
+        assert_eq!(verbose.mempool_transaction_count, 510);
+        assert_eq!(verbose.mempool_total_mass, 51_000_000);
+        assert_eq!(verbose.network_mass_per_second, MAX_BLOCK_MASS);
+        assert_eq!(verbose.recent_templates_feerate_floor, 1.0);
+    }
+
+    #[test]
+    fn test_feerate_estimations_with_template_floor() {
+        let mut histogram = FeerateHistogram::new(1.0);
+        histogram.add(3 * 100_000, 100_000);
+        let estimations = estimator(histogram, Some(10.0)).calc_estimations();
+        assert_ordered(&estimations);
+
+        // The mempool is almost empty but recent templates were congested
+        assert_eq!(estimations.priority_bucket, FeerateBucket::new(10.0, 1.0));
+        assert_eq!(estimations.normal_buckets[0].feerate, 1.0);
+    }
+}
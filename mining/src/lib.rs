@@ -8,15 +8,23 @@ use mempool::tx::Priority;
 mod block_template;
 pub(crate) mod cache;
 pub mod errors;
+pub mod feerate;
 pub mod manager;
 mod manager_tests;
 pub mod mempool;
 pub mod model;
 pub mod monitor;
+pub mod persistence;
 
 #[cfg(test)]
 pub mod testutils;
 
+pub use block_template::{
+    package_selector::PackageTransactionsSelector,
+    policy::{Policy, TemplateSelectionMode},
+    selector::TransactionsSelector,
+};
+
 pub struct MiningCounters {
     pub creation_time: Instant,
 
@@ -1,22 +1,28 @@
 use crate::{
-    block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
+    block_template::{builder::BlockTemplateBuilder, errors::BuilderError, policy::TemplateSelectionMode},
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
+    feerate::{
+        history::{TemplateSelectionHistory, TemplateSelectionSample},
+        FeerateEstimations, FeerateEstimationsVerbose, FeerateEstimator, FeerateEstimatorArgs,
+    },
     mempool::{
         config::Config,
-        model::tx::{MempoolTransaction, TxRemovalReason},
+        model::tx::{MempoolTransaction, TransactionPostValidation, TxRemovalReason},
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
-        tx::{Orphan, Priority},
+        tx::{Orphan, Priority, RbfPolicy},
         Mempool,
     },
     model::{
         candidate_tx::CandidateTransaction,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         topological_sort::IntoIterTopologically,
+        tx_insert::TransactionInsertion,
         tx_query::TransactionQuery,
     },
+    persistence::{read_mempool_file, write_mempool_file, MempoolLoadSummary},
     MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
 };
 use itertools::Itertools;
@@ -30,14 +36,19 @@ use kaspa_consensus_core::{
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
-use parking_lot::RwLock;
-use std::sync::Arc;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::mpsc::UnboundedSender;
 
 pub struct MiningManager {
     config: Arc<Config>,
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
+    template_selection_history: Mutex<TemplateSelectionHistory>,
     counters: Arc<MiningCounters>,
 }
 
@@ -53,16 +64,21 @@ impl MiningManager {
         Self::with_config(config, cache_lifetime, counters)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_extended_config(
         target_time_per_block: u64,
         relay_non_std_transactions: bool,
         max_block_mass: u64,
         ram_scale: f64,
+        template_selection_mode: TemplateSelectionMode,
+        mempool_file: Option<PathBuf>,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .with_block_template_selection_mode(template_selection_mode)
+            .with_mempool_file(mempool_file);
         Self::with_config(config, cache_lifetime, counters)
     }
 
@@ -70,7 +86,8 @@ impl MiningManager {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        let template_selection_history = Mutex::new(TemplateSelectionHistory::default());
+        Self { config, block_template_cache, mempool, template_selection_history, counters }
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -103,15 +120,24 @@ impl MiningManager {
         loop {
             attempts += 1;
 
-            let transactions = self.block_candidate_transactions();
-            let block_template_builder = BlockTemplateBuilder::new(self.config.maximum_mass_per_block);
+            let (transactions, chained_transactions) = match self.config.block_template_selection_mode {
+                TemplateSelectionMode::Probabilistic => (self.block_candidate_transactions(), vec![]),
+                TemplateSelectionMode::PackageFeerate => self.block_candidate_packages(),
+            };
+            let candidate_feerates = transactions
+                .iter()
+                .map(|tx| (tx.tx.id(), tx.calculated_fee as f64 / tx.calculated_mass as f64))
+                .collect::<Vec<_>>();
+            let block_template_builder =
+                BlockTemplateBuilder::new(self.config.maximum_mass_per_block, self.config.block_template_selection_mode);
             let build_mode = if attempts < self.config.maximum_build_block_template_attempts {
                 TemplateBuildMode::Standard
             } else {
                 TemplateBuildMode::Infallible
             };
-            match block_template_builder.build_block_template(consensus, miner_data, transactions, build_mode) {
+            match block_template_builder.build_block_template(consensus, miner_data, transactions, chained_transactions, build_mode) {
                 Ok(block_template) => {
+                    self.record_template_selection(&candidate_feerates, &block_template);
                     let block_template = cache_lock.set_immutable_cached_template(block_template);
                     match attempts {
                         1 => {
@@ -193,10 +219,49 @@ impl MiningManager {
         }
     }
 
+    /// Records how the transactions of a newly built block template were selected
+    /// among the candidates, feeding the feerate estimator
+    fn record_template_selection(&self, candidate_feerates: &[(TransactionId, f64)], block_template: &BlockTemplate) {
+        // Skip the coinbase transaction
+        let selected = &block_template.block.transactions[1..];
+        let selected_ids = selected.iter().map(|tx| tx.id()).collect::<HashSet<_>>();
+        let min_selected_feerate =
+            candidate_feerates.iter().filter(|(id, _)| selected_ids.contains(id)).map(|(_, feerate)| *feerate).min_by(f64::total_cmp);
+        let selected_mass: u64 = selected.iter().map(|tx| tx.mass()).sum();
+        let sample = TemplateSelectionSample::new(
+            min_selected_feerate,
+            selected_mass as f64 / self.config.maximum_mass_per_block as f64,
+            selected.len() < candidate_feerates.len(),
+        );
+        self.template_selection_history.lock().push(sample);
+    }
+
+    fn feerate_estimator(&self) -> FeerateEstimator {
+        let histogram = self.mempool.read().feerate_histogram();
+        let feerate_floor = self.template_selection_history.lock().feerate_floor();
+        let args = FeerateEstimatorArgs::new(self.config.target_time_per_block, self.config.maximum_mass_per_block);
+        FeerateEstimator::new(histogram, feerate_floor, args)
+    }
+
+    /// Returns feerate estimations based on the current mempool load and the recent block templates
+    pub fn get_realtime_feerate_estimations(&self) -> FeerateEstimations {
+        self.feerate_estimator().calc_estimations()
+    }
+
+    /// Returns feerate estimations along with the mempool feerate histogram they were computed from
+    pub fn get_realtime_feerate_estimations_verbose(&self) -> FeerateEstimationsVerbose {
+        self.feerate_estimator().calc_estimations_verbose()
+    }
+
     pub(crate) fn block_candidate_transactions(&self) -> Vec<CandidateTransaction> {
         self.mempool.read().block_candidate_transactions()
     }
 
+    /// Returns the ready block candidate transactions along with the transactions chained to them
+    pub(crate) fn block_candidate_packages(&self) -> (Vec<CandidateTransaction>, Vec<CandidateTransaction>) {
+        self.mempool.read().block_candidate_packages()
+    }
+
     /// Clears the block template cache, forcing the next call to get_block_template to build a new block template.
     #[cfg(test)]
     pub(crate) fn clear_block_template(&self) {
@@ -205,13 +270,16 @@ impl MiningManager {
 
     #[cfg(test)]
     pub(crate) fn block_template_builder(&self) -> BlockTemplateBuilder {
-        BlockTemplateBuilder::new(self.config.maximum_mass_per_block)
+        BlockTemplateBuilder::new(self.config.maximum_mass_per_block, self.config.block_template_selection_mode)
     }
 
     /// validate_and_insert_transaction validates the given transaction, and
     /// adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
+    /// If the transaction double spends some mempool transactions, `rbf_policy`
+    /// defines whether it may, must or must not replace them by fee.
+    ///
     /// The returned transactions are clones of objects owned by the mempool.
     pub fn validate_and_insert_transaction(
         &self,
@@ -219,8 +287,9 @@ impl MiningManager {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan)
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        self.validate_and_insert_mutable_transaction(consensus, MutableTransaction::from_tx(transaction), priority, orphan, rbf_policy)
     }
 
     /// Exposed only for tests. Ordinary users should call `validate_and_insert_transaction` instead
@@ -230,16 +299,17 @@ impl MiningManager {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
         // read lock on mempool
-        let mut transaction = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction)?;
+        let mut transaction = self.mempool.read().pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)?;
         // no lock on mempool
         let validation_result = validate_mempool_transaction(consensus, &mut transaction);
         // write lock on mempool
         let mut mempool = self.mempool.write();
-        if let Some(accepted_transaction) =
-            mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan)?
-        {
+        let TransactionPostValidation { removed, accepted } =
+            mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan, rbf_policy)?;
+        if let Some(accepted_transaction) = accepted {
             let unorphaned_transactions = mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction);
             drop(mempool);
 
@@ -250,9 +320,9 @@ impl MiningManager {
             accepted_transactions.extend(self.validate_and_insert_unorphaned_transactions(consensus, unorphaned_transactions));
             self.counters.increase_tx_counts(1, priority);
 
-            Ok(accepted_transactions)
+            Ok(TransactionInsertion::new(removed, accepted_transactions))
         } else {
-            Ok(vec![])
+            Ok(TransactionInsertion::new(removed, vec![]))
         }
     }
 
@@ -297,13 +367,14 @@ impl MiningManager {
                         transaction,
                         priority,
                         Orphan::Forbidden,
+                        RbfPolicy::Forbidden,
                     ) {
-                        Ok(Some(accepted_transaction)) => {
+                        Ok(TransactionPostValidation { accepted: Some(accepted_transaction), .. }) => {
                             accepted_transactions.push(accepted_transaction.clone());
                             self.counters.increase_tx_counts(1, priority);
                             mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction)
                         }
-                        Ok(None) => vec![],
+                        Ok(TransactionPostValidation { accepted: None, .. }) => vec![],
                         Err(err) => {
                             debug!("Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err);
                             vec![]
@@ -319,6 +390,8 @@ impl MiningManager {
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
     /// adds those to the set of known transactions that have not yet been added to any block.
     ///
+    /// Transactions double spending some mempool transactions are handled according to `rbf_policy`.
+    ///
     /// Returns transactions that where unorphaned following the insertion of the provided
     /// transactions. The returned transactions are clones of objects owned by the mempool.
     pub fn validate_and_insert_transaction_batch(
@@ -327,6 +400,7 @@ impl MiningManager {
         transactions: Vec<Transaction>,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
         const TRANSACTION_CHUNK_SIZE: usize = 250;
 
@@ -344,7 +418,7 @@ impl MiningManager {
             let mempool = self.mempool.read();
             let txs = chunk.filter_map(|tx| {
                 let transaction_id = tx.id();
-                match mempool.pre_validate_and_populate_transaction(consensus, tx) {
+                match mempool.pre_validate_and_populate_transaction(consensus, tx, rbf_policy) {
                     Ok(tx) => Some(tx),
                     Err(RuleError::RejectAlreadyAccepted(transaction_id)) => {
                         debug!("Ignoring already accepted transaction {}", transaction_id);
@@ -386,13 +460,20 @@ impl MiningManager {
             let mut mempool = self.mempool.write();
             let txs = chunk.flat_map(|(transaction, validation_result)| {
                 let transaction_id = transaction.id();
-                match mempool.post_validate_and_insert_transaction(consensus, validation_result, transaction, priority, orphan) {
-                    Ok(Some(accepted_transaction)) => {
+                match mempool.post_validate_and_insert_transaction(
+                    consensus,
+                    validation_result,
+                    transaction,
+                    priority,
+                    orphan,
+                    rbf_policy,
+                ) {
+                    Ok(TransactionPostValidation { accepted: Some(accepted_transaction), .. }) => {
                         insert_results.push(Ok(accepted_transaction.clone()));
                         self.counters.increase_tx_counts(1, priority);
                         mempool.get_unorphaned_transactions_after_accepted_transaction(&accepted_transaction)
                     }
-                    Ok(None) => {
+                    Ok(TransactionPostValidation { accepted: None, .. }) => {
                         // Either orphaned or already existing in the mempool
                         vec![]
                     }
@@ -464,6 +545,13 @@ impl MiningManager {
         (transactions, orphans)
     }
 
+    /// Returns the mempool transactions whose ids pass `filter`, without cloning their UTXO entries.
+    ///
+    /// Used by compact block relay to look up transactions by their short ids.
+    pub fn filter_transactions(&self, query: TransactionQuery, filter: impl Fn(&TransactionId) -> bool) -> Vec<Arc<Transaction>> {
+        self.mempool.read().filter_transactions(query, filter)
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -739,6 +827,56 @@ impl MiningManager {
     pub fn unknown_transactions(&self, transactions: Vec<TransactionId>) -> Vec<TransactionId> {
         self.mempool.read().unknown_transactions(transactions)
     }
+
+    /// Writes the transactions of the mempool, orphans included, to the mempool file.
+    /// Returns the number of saved transactions.
+    pub fn save_mempool(&self) -> MiningManagerResult<usize> {
+        let path = self.mempool_file()?;
+        let mempool = self.mempool.read().persisted_mempool();
+        write_mempool_file(path, &mempool).map_err(|err| MiningManagerError::MempoolPersistenceError(err.to_string()))?;
+        Ok(mempool.len())
+    }
+
+    /// Reads the transactions of the mempool file and adds them back to the mempool, revalidating
+    /// them against the current virtual UTXO set. Reloaded transactions keep their priority and
+    /// the DAA score they were originally added at. Transactions of the transaction pool whose inputs were
+    /// spent in the meantime, by themselves or by a double spend getting mined, are dropped with their redeemers.
+    pub fn load_mempool(&self, consensus: &dyn ConsensusApi) -> MiningManagerResult<MempoolLoadSummary> {
+        let path = self.mempool_file()?;
+        if !path.exists() {
+            return Ok(MempoolLoadSummary::default());
+        }
+        let persisted = read_mempool_file(path).map_err(|err| MiningManagerError::MempoolPersistenceError(err.to_string()))?;
+        let (high_priority, low_priority): (Vec<_>, Vec<_>) =
+            persisted.transactions.iter().chain(persisted.orphans.iter()).partition(|x| x.priority == Priority::High);
+        for (priority, transactions) in [(Priority::High, high_priority), (Priority::Low, low_priority)] {
+            let transactions = transactions.into_iter().map(|x| x.transaction.clone()).collect();
+            // Transactions which are no longer valid are simply dropped
+            self.validate_and_insert_transaction_batch(consensus, transactions, priority, Orphan::Allowed, RbfPolicy::Forbidden);
+        }
+        let mut mempool = self.mempool.write();
+        for transaction_id in persisted.transactions.iter().map(|x| x.transaction.id()) {
+            // Orphans are reloaded along so that chained transactions get unorphaned, but a transaction of the
+            // transaction pool cannot be legitimately missing inputs
+            if mempool.has_transaction(&transaction_id, TransactionQuery::OrphansOnly) {
+                mempool.remove_transaction(&transaction_id, true, TxRemovalReason::RevalidationWithMissingOutpoints, "")?;
+            }
+        }
+        let accepted_count = persisted
+            .transactions
+            .iter()
+            .chain(persisted.orphans.iter())
+            .filter(|x| mempool.restore_added_at_daa_score(&x.transaction.id(), x.added_at_daa_score))
+            .count();
+        Ok(MempoolLoadSummary { transaction_count: persisted.len(), accepted_count })
+    }
+
+    fn mempool_file(&self) -> MiningManagerResult<&Path> {
+        self.config
+            .mempool_file
+            .as_deref()
+            .ok_or_else(|| MiningManagerError::MempoolPersistenceError("the mempool is not persisted by this node".to_string()))
+    }
 }
 
 /// Async proxy for the mining manager
@@ -759,6 +897,9 @@ impl MiningManagerProxy {
     /// Validates a transaction and adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
+    /// If the transaction double spends some mempool transactions, `rbf_policy`
+    /// defines whether it may, must or must not replace them by fee.
+    ///
     /// The returned transactions are clones of objects owned by the mempool.
     pub async fn validate_and_insert_transaction(
         self,
@@ -766,13 +907,19 @@ impl MiningManagerProxy {
         transaction: Transaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> MiningManagerResult<Vec<Arc<Transaction>>> {
-        consensus.clone().spawn_blocking(move |c| self.inner.validate_and_insert_transaction(c, transaction, priority, orphan)).await
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<TransactionInsertion> {
+        consensus
+            .clone()
+            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction(c, transaction, priority, orphan, rbf_policy))
+            .await
     }
 
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
     /// adds those to the set of known transactions that have not yet been added to any block.
     ///
+    /// Transactions double spending some mempool transactions are handled according to `rbf_policy`.
+    ///
     /// Returns transactions that where unorphaned following the insertion of the provided
     /// transactions. The returned transactions are clones of objects owned by the mempool.
     pub async fn validate_and_insert_transaction_batch(
@@ -781,10 +928,11 @@ impl MiningManagerProxy {
         transactions: Vec<Transaction>,
         priority: Priority,
         orphan: Orphan,
+        rbf_policy: RbfPolicy,
     ) -> Vec<MiningManagerResult<Arc<Transaction>>> {
         consensus
             .clone()
-            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan))
+            .spawn_blocking(move |c| self.inner.validate_and_insert_transaction_batch(c, transactions, priority, orphan, rbf_policy))
             .await
     }
 
@@ -832,6 +980,25 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.get_all_transactions(query)).await.unwrap()
     }
 
+    /// Returns feerate estimations based on the current mempool load and the recent block templates
+    pub async fn get_realtime_feerate_estimations(self) -> FeerateEstimations {
+        spawn_blocking(move || self.inner.get_realtime_feerate_estimations()).await.unwrap()
+    }
+
+    /// Returns feerate estimations along with the mempool feerate histogram they were computed from
+    pub async fn get_realtime_feerate_estimations_verbose(self) -> FeerateEstimationsVerbose {
+        spawn_blocking(move || self.inner.get_realtime_feerate_estimations_verbose()).await.unwrap()
+    }
+
+    /// Returns the mempool transactions whose ids pass `filter`, without cloning their UTXO entries.
+    pub async fn filter_transactions(
+        self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool + Send + 'static,
+    ) -> Vec<Arc<Transaction>> {
+        spawn_blocking(move || self.inner.filter_transactions(query, filter)).await.unwrap()
+    }
+
     /// get_transactions_by_addresses returns the sending and receiving transactions for
     /// a set of addresses.
     ///
@@ -868,6 +1035,18 @@ impl MiningManagerProxy {
         spawn_blocking(move || self.inner.unknown_transactions(transactions)).await.unwrap()
     }
 
+    /// Writes the transactions of the mempool, orphans included, to the mempool file.
+    /// Returns the number of saved transactions.
+    pub async fn save_mempool(self) -> MiningManagerResult<usize> {
+        spawn_blocking(move || self.inner.save_mempool()).await.unwrap()
+    }
+
+    /// Reads the transactions of the mempool file and adds them back to the mempool, revalidating
+    /// them against the current virtual UTXO set.
+    pub async fn load_mempool(self, consensus: &ConsensusProxy) -> MiningManagerResult<MempoolLoadSummary> {
+        consensus.clone().spawn_blocking(move |c| self.inner.load_mempool(c)).await
+    }
+
     pub fn snapshot(&self) -> MempoolCountersSnapshot {
         self.inner.counters.snapshot()
     }
@@ -7,9 +7,10 @@ mod tests {
         mempool::{
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
             errors::RuleError,
-            tx::{Orphan, Priority},
+            tx::{Orphan, Priority, RbfPolicy},
         },
-        model::{candidate_tx::CandidateTransaction, tx_query::TransactionQuery},
+        model::{candidate_tx::CandidateTransaction, tx_insert::TransactionInsertion, tx_query::TransactionQuery},
+        persistence::MEMPOOL_FILE_NAME,
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -27,6 +28,7 @@ mod tests {
             TransactionOutput, UtxoEntry,
         },
     };
+    use kaspa_database::utils::get_kaspa_tempdir;
     use kaspa_hashes::Hash;
     use kaspa_txscript::{
         pay_to_address_script, pay_to_script_hash_signature_script,
@@ -52,6 +54,7 @@ mod tests {
                 transaction.clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "inserting a valid transaction failed");
         }
@@ -100,6 +103,7 @@ mod tests {
             transaction_not_an_orphan.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "inserting the child transaction {} into the mempool failed", transaction_not_an_orphan.id());
         let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly);
@@ -132,6 +136,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         ));
 
         assert_eq!(
@@ -158,6 +163,7 @@ mod tests {
             transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok(), "mempool should have accepted a valid transaction but did not");
 
@@ -167,6 +173,7 @@ mod tests {
             transaction.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double submit of the same transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDuplicate(transaction_id))) = result {
@@ -200,8 +207,13 @@ mod tests {
             transaction.id()
         );
 
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), transaction.clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            transaction.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the mempool should accept a valid transaction when it is able to populate its UTXO entries");
 
         let mut double_spending_transaction = transaction.clone();
@@ -217,6 +229,7 @@ mod tests {
             double_spending_transaction.clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_err(), "mempool should refuse a double spend transaction but accepts it");
         if let Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, transaction_id))) = result {
@@ -235,6 +248,117 @@ mod tests {
         }
     }
 
+    // test_replace_by_fee verifies that a transaction double spending a mempool transaction is accepted as a
+    // replacement according to the RBF policy and fee rules, evicting the double spent transaction and its redeemers,
+    // orphans included, and that a rejected replacement leaves the mempool unchanged.
+    #[test]
+    fn test_replace_by_fee() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * LEOR_PER_PYRIN]);
+        let parent_tx = create_transaction(&funding_tx, 1000);
+        let child_tx = create_transaction(&parent_tx, 1000);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        for tx in [&parent_tx, &child_tx] {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        // An orphan redeeming the child along with some unknown outpoint
+        let (_, redeem_script) = op_true_script();
+        let signature_script = pay_to_script_hash_signature_script(redeem_script, vec![]).expect("the redeem script is canonical");
+        let unknown_outpoint = TransactionOutpoint::new(create_transaction_without_input(vec![LEOR_PER_PYRIN]).id(), 0);
+        let mut orphan_tx = create_transaction(&child_tx, 1000);
+        orphan_tx.inputs.push(TransactionInput::new(unknown_outpoint, signature_script, MAX_TX_IN_SEQUENCE_NUM, 1));
+        orphan_tx.finalize();
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            orphan_tx.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "the mempool should accept the orphan transaction {}", orphan_tx.id());
+        assert!(mining_manager.has_transaction(&orphan_tx.id(), TransactionQuery::OrphansOnly));
+
+        // A mandatory replacement must double spend some mempool transaction
+        let unrelated_funding_tx = create_transaction_without_input(vec![300 * LEOR_PER_PYRIN]);
+        let unrelated_tx = create_transaction(&unrelated_funding_tx, 1000);
+        consensus.add_transaction(unrelated_funding_tx, 1);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            unrelated_tx.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Mandatory,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectRbfNoDoubleSpend(id))) if id == unrelated_tx.id()),
+            "a mandatory replacement without double spend should be rejected but got {:?}",
+            result
+        );
+
+        // The replacement pays more than the double spent transaction but not more than the whole evicted chain
+        let cheap_replacement_tx = create_transaction(&funding_tx, 1500);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            cheap_replacement_tx.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectDoubleSpendInMempool(_, id))) if id == parent_tx.id()),
+            "a double spend should be rejected when RBF is forbidden but got {:?}",
+            result
+        );
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            cheap_replacement_tx.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Permitted,
+        );
+        assert!(
+            matches!(result, Err(MiningManagerError::MempoolError(RuleError::RejectRbfInsufficientFee(_, 1500, 2000)))),
+            "a replacement paying less than the evicted transactions should be rejected but got {:?}",
+            result
+        );
+        assert!(mining_manager.has_transaction(&parent_tx.id(), TransactionQuery::TransactionsOnly));
+        assert!(mining_manager.has_transaction(&child_tx.id(), TransactionQuery::TransactionsOnly));
+        assert!(mining_manager.has_transaction(&orphan_tx.id(), TransactionQuery::OrphansOnly));
+
+        // A replacement paying enough evicts the double spent transaction and all its redeemers
+        let replacement_tx = create_transaction(&funding_tx, 5000);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            replacement_tx.clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Mandatory,
+        );
+        assert!(result.is_ok(), "the mempool should accept a replacement paying a higher fee but got {:?}", result);
+        let TransactionInsertion { mut removed, accepted } = result.unwrap();
+        let mut expected_removed = vec![parent_tx.id(), child_tx.id(), orphan_tx.id()];
+        removed.sort();
+        expected_removed.sort();
+        assert_eq!(expected_removed, removed, "the double spent transaction and its redeemers should be reported as removed");
+        assert_eq!(1, accepted.len());
+        assert_eq!(replacement_tx.id(), accepted[0].id());
+        assert!(!mining_manager.has_transaction(&parent_tx.id(), TransactionQuery::All));
+        assert!(!mining_manager.has_transaction(&child_tx.id(), TransactionQuery::All));
+        assert!(!mining_manager.has_transaction(&orphan_tx.id(), TransactionQuery::All));
+        assert!(mining_manager.has_transaction(&replacement_tx.id(), TransactionQuery::TransactionsOnly));
+    }
+
     // test_handle_new_block_transactions verifies that all the transactions in the block were successfully removed from the mempool.
     #[test]
     fn test_handle_new_block_transactions() {
@@ -250,6 +374,7 @@ mod tests {
                 transaction.tx.as_ref().clone(),
                 Priority::Low,
                 Orphan::Allowed,
+                RbfPolicy::Forbidden,
             );
             assert!(result.is_ok(), "the insertion of a new valid transaction in the mempool failed");
         }
@@ -294,6 +419,50 @@ mod tests {
         }
     }
 
+    // test_feerate_estimations_follow_mempool verifies that the feerate histogram backing the fee estimations
+    // tracks transactions as they enter and leave the mempool.
+    #[test]
+    fn test_feerate_estimations_follow_mempool() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let estimations = mining_manager.get_realtime_feerate_estimations_verbose();
+        assert_eq!(estimations.mempool_transaction_count, 0);
+        assert_eq!(estimations.mempool_total_mass, 0);
+        assert!(estimations.mempool_histogram.is_empty());
+        assert_eq!(estimations.network_mass_per_second, MAX_BLOCK_MASS * 1000 / TARGET_TIME_PER_BLOCK);
+
+        const TX_COUNT: u32 = 10;
+        let transactions_to_insert = (0..TX_COUNT).map(|i| create_transaction_with_utxo_entry(i, 0)).collect::<Vec<_>>();
+        for transaction in transactions_to_insert.iter() {
+            let result = mining_manager.validate_and_insert_mutable_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "inserting a valid transaction failed");
+        }
+
+        let (transactions_from_pool, _) = mining_manager.get_all_transactions(TransactionQuery::TransactionsOnly);
+        let total_mass: u64 = transactions_from_pool.iter().map(|tx| tx.tx.mass()).sum();
+        let estimations = mining_manager.get_realtime_feerate_estimations_verbose();
+        assert_eq!(estimations.mempool_transaction_count, TX_COUNT as u64);
+        assert_eq!(estimations.mempool_total_mass, total_mass);
+        assert_eq!(estimations.mempool_histogram.iter().map(|bucket| bucket.count).sum::<u64>(), TX_COUNT as u64);
+        assert_eq!(estimations.estimations, mining_manager.get_realtime_feerate_estimations());
+
+        let block = build_block_transactions(transactions_to_insert.iter().map(|mtx| mtx.tx.as_ref()));
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block);
+        assert!(result.is_ok(), "the handling of the block transactions should succeed but returned {result:?}");
+
+        let estimations = mining_manager.get_realtime_feerate_estimations_verbose();
+        assert_eq!(estimations.mempool_transaction_count, 0);
+        assert_eq!(estimations.mempool_total_mass, 0);
+    }
+
     #[test]
     // test_double_spend_with_block verifies that any transactions which are now double spends as a result of the block's new transactions
     // will be removed from the mempool.
@@ -308,6 +477,7 @@ mod tests {
             transaction_in_the_mempool.tx.as_ref().clone(),
             Priority::Low,
             Orphan::Allowed,
+            RbfPolicy::Forbidden,
         );
         assert!(result.is_ok());
 
@@ -340,8 +510,13 @@ mod tests {
         assert_eq!(parent_txs.len(), TX_PAIRS_COUNT);
         assert_eq!(child_txs.len(), TX_PAIRS_COUNT);
         for orphan in child_txs.iter() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), orphan.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                orphan.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid orphan transaction {}", orphan.id());
         }
         let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
@@ -485,10 +660,15 @@ mod tests {
         );
 
         // Add the remaining parent transaction into the mempool
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_txs[0].clone(), Priority::Low, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            parent_txs[0].clone(),
+            Priority::Low,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion of the remaining parent transaction in the mempool failed");
-        let unorphaned_txs = result.unwrap();
+        let unorphaned_txs = result.unwrap().accepted;
         let (populated_txs, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
         assert_eq!(
             unorphaned_txs.len(), SKIPPED_TXS + 1,
@@ -592,8 +772,13 @@ mod tests {
 
         // Try submit children while rejecting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Forbidden);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Forbidden,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_err(), "mempool should reject an orphan transaction with {:?} when asked to do so", test.priority);
             if let Err(MiningManagerError::MempoolError(RuleError::RejectDisallowedOrphan(transaction_id))) = result {
                 assert_eq!(
@@ -613,8 +798,13 @@ mod tests {
 
         // Try submit children while accepting orphans
         for (tx, test) in child_txs.iter().zip(tests.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert_eq!(
                 test.should_enter_orphan_pool,
                 result.is_ok(),
@@ -622,7 +812,7 @@ mod tests {
                 test.name,
                 test.insert_result()
             );
-            if let Ok(unorphaned_txs) = result {
+            if let Ok(TransactionInsertion { accepted: unorphaned_txs, .. }) = result {
                 assert!(unorphaned_txs.is_empty(), "mempool should unorphan no transaction since it only contains orphans");
             } else if let Err(MiningManagerError::MempoolError(RuleError::RejectOrphanPoolIsFull(pool_len, config_len))) = result {
                 assert_eq!(
@@ -642,10 +832,15 @@ mod tests {
 
         // Submit all the parents
         for (i, (tx, test)) in parent_txs.iter().zip(tests.iter()).enumerate() {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), tx.clone(), test.priority, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                test.priority,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "mempool should accept a valid transaction with {:?} when asked to do so", test.priority,);
-            let unorphaned_txs = result.as_ref().unwrap();
+            let unorphaned_txs = &result.as_ref().unwrap().accepted;
             assert_eq!(
                 test.should_unorphan,
                 unorphaned_txs.len() > 1,
@@ -682,8 +877,13 @@ mod tests {
 
         // Add to mempool a transaction that spends child_tx_2 (as high priority)
         let spending_tx = create_transaction(&child_tx_2, 1_000);
-        let result =
-            mining_manager.validate_and_insert_transaction(consensus.as_ref(), spending_tx.clone(), Priority::High, Orphan::Allowed);
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            spending_tx.clone(),
+            Priority::High,
+            Orphan::Allowed,
+            RbfPolicy::Forbidden,
+        );
         assert!(result.is_ok(), "the insertion in the mempool of the spending transaction failed");
 
         // Revalidate, to make sure spending_tx is still valid
@@ -725,6 +925,55 @@ mod tests {
         assert!(orphan_txs.is_empty(), "orphan pool should be empty");
     }
 
+    // test_load_mempool verifies that a reloaded mempool drops the transactions which were mined, double spent
+    // or became invalid while the node was down, together with their redeemers, and keeps the remaining ones.
+    #[test]
+    fn test_load_mempool() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let dir = get_kaspa_tempdir();
+        let new_mining_manager = || {
+            let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS)
+                .with_mempool_file(Some(dir.path().join(MEMPOOL_FILE_NAME)));
+            MiningManager::with_config(config, None, Arc::new(MiningCounters::default()))
+        };
+        let mining_manager = new_mining_manager();
+
+        // Three chains of a parent and a child transaction, plus an orphan missing its parent
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, 3);
+        let orphan_tx = create_transaction(&create_transaction_without_input(vec![100 * LEOR_PER_PYRIN]), 1000);
+        for transaction in parent_txs.iter().chain(child_txs.iter()).chain(std::iter::once(&orphan_tx)) {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the insertion of a new transaction in the mempool failed");
+        }
+        assert!(mining_manager.has_transaction(&orphan_tx.id(), TransactionQuery::OrphansOnly));
+        assert_eq!(7, mining_manager.save_mempool().unwrap());
+
+        // While the node is down, the first parent gets mined, a double spend of the second one gets mined
+        // and the third one becomes invalid
+        consensus.add_transaction(parent_txs[0].clone(), 2);
+        let mut double_spend_tx = parent_txs[1].clone();
+        double_spend_tx.outputs[0].value -= 1;
+        double_spend_tx.finalize();
+        consensus.add_transaction(double_spend_tx, 2);
+        consensus.set_status(parent_txs[2].id(), Err(TxRuleError::TxHasGas));
+
+        let mining_manager = new_mining_manager();
+        let summary = mining_manager.load_mempool(consensus.as_ref()).unwrap();
+        assert_eq!(7, summary.transaction_count);
+        assert_eq!(2, summary.accepted_count);
+
+        // The child of the mined parent is now funded by the UTXO set and the orphan stays an orphan
+        let (transactions, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        assert_eq!(vec![child_txs[0].id()], transactions.iter().map(|x| x.id()).collect::<Vec<_>>());
+        assert_eq!(vec![orphan_tx.id()], orphans.iter().map(|x| x.id()).collect::<Vec<_>>());
+    }
+
     // test_modify_block_template verifies that modifying a block template changes coinbase data correctly.
     #[test]
     fn test_modify_block_template() {
@@ -737,11 +986,21 @@ mod tests {
         let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
 
         for (parent_tx, child_tx) in parent_txs.iter().zip(child_txs.iter()) {
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), parent_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                parent_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid parent transaction {}", parent_tx.id());
-            let result =
-                mining_manager.validate_and_insert_transaction(consensus.as_ref(), child_tx.clone(), Priority::Low, Orphan::Allowed);
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                child_tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
             assert!(result.is_ok(), "the mempool should accept the valid child transaction {}", parent_tx.id());
         }
 
@@ -848,7 +1107,7 @@ mod tests {
 
         // Build a fresh template for coinbase2 as a reference
         let builder = mining_manager.block_template_builder();
-        let result = builder.build_block_template(consensus, &miner_data_2, transactions, TemplateBuildMode::Standard);
+        let result = builder.build_block_template(consensus, &miner_data_2, transactions, vec![], TemplateBuildMode::Standard);
         assert!(result.is_ok(), "build block template failed for miner data 2");
         let expected_template = result.unwrap();
 
@@ -1,4 +1,6 @@
+use crate::block_template::policy::TemplateSelectionMode;
 use kaspa_consensus_core::constants::TX_VERSION;
+use std::path::PathBuf;
 
 pub(crate) const DEFAULT_MAXIMUM_TRANSACTION_COUNT: u64 = 1_000_000;
 pub(crate) const DEFAULT_MAXIMUM_READY_TRANSACTION_COUNT: u64 = 50_000;
@@ -44,9 +46,13 @@ pub struct Config {
     pub maximum_orphan_transaction_count: u64,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
+    pub target_time_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
+    pub block_template_selection_mode: TemplateSelectionMode,
+    /// The file persisting the mempool across node restarts, `None` if the mempool is not persisted
+    pub mempool_file: Option<PathBuf>,
 }
 
 impl Config {
@@ -67,9 +73,12 @@ impl Config {
         maximum_orphan_transaction_count: u64,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
+        target_time_per_block: u64,
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
+        block_template_selection_mode: TemplateSelectionMode,
+        mempool_file: Option<PathBuf>,
     ) -> Self {
         Self {
             maximum_transaction_count,
@@ -87,9 +96,12 @@ impl Config {
             maximum_orphan_transaction_count,
             accept_non_standard,
             maximum_mass_per_block,
+            target_time_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
+            block_template_selection_mode,
+            mempool_file,
         }
     }
 
@@ -115,14 +127,32 @@ impl Config {
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
+            target_time_per_block: target_milliseconds_per_block,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
+            block_template_selection_mode: TemplateSelectionMode::Probabilistic,
+            mempool_file: None,
         }
     }
 
+    /// Returns the minimum relay feerate in sompi per gram
+    pub fn minimum_relay_feerate(&self) -> f64 {
+        self.minimum_relay_transaction_fee as f64 / 1000.0
+    }
+
     pub fn apply_ram_scale(mut self, ram_scale: f64) -> Self {
         self.maximum_transaction_count = (self.maximum_transaction_count as f64 * ram_scale.min(1.0)) as u64; // Allow only scaling down
         self
     }
+
+    pub fn with_block_template_selection_mode(mut self, block_template_selection_mode: TemplateSelectionMode) -> Self {
+        self.block_template_selection_mode = block_template_selection_mode;
+        self
+    }
+
+    pub fn with_mempool_file(mut self, mempool_file: Option<PathBuf>) -> Self {
+        self.mempool_file = mempool_file;
+        self
+    }
 }
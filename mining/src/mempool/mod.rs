@@ -1,9 +1,11 @@
 use crate::{
+    feerate::histogram::FeerateHistogram,
     model::{
         candidate_tx::CandidateTransaction,
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
     },
+    persistence::{PersistedMempool, PersistedTransaction},
     MiningCounters,
 };
 
@@ -12,7 +14,7 @@ use self::{
     model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
     tx::Priority,
 };
-use kaspa_consensus_core::tx::{MutableTransaction, TransactionId};
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
 use kaspa_core::time::Stopwatch;
 use std::sync::Arc;
 
@@ -23,6 +25,7 @@ pub(crate) mod handle_new_block_transactions;
 pub(crate) mod model;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
+pub(crate) mod replace_by_fee;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -79,6 +82,21 @@ impl Mempool {
         (transactions, orphans)
     }
 
+    pub(crate) fn filter_transactions(
+        &self,
+        query: TransactionQuery,
+        filter: impl Fn(&TransactionId) -> bool,
+    ) -> Vec<Arc<Transaction>> {
+        let mut transactions = vec![];
+        if query.include_transaction_pool() {
+            transactions.extend(self.transaction_pool.all().iter().filter(|(id, _)| filter(id)).map(|(_, x)| x.mtx.tx.clone()));
+        }
+        if query.include_orphan_pool() {
+            transactions.extend(self.orphan_pool.all().iter().filter(|(id, _)| filter(id)).map(|(_, x)| x.mtx.tx.clone()));
+        }
+        transactions
+    }
+
     pub(crate) fn get_all_transaction_ids(&self, query: TransactionQuery) -> (Vec<TransactionId>, Vec<TransactionId>) {
         let transactions = if query.include_transaction_pool() { self.transaction_pool.get_all_transaction_ids() } else { vec![] };
         let orphans = if query.include_orphan_pool() { self.orphan_pool.get_all_transaction_ids() } else { vec![] };
@@ -116,6 +134,36 @@ impl Mempool {
         self.transaction_pool.all_ready_transactions()
     }
 
+    pub(crate) fn block_candidate_packages(&self) -> (Vec<CandidateTransaction>, Vec<CandidateTransaction>) {
+        let _sw = Stopwatch::<10>::with_threshold("block_candidate_packages op");
+        (self.transaction_pool.all_ready_transactions(), self.transaction_pool.all_chained_transactions())
+    }
+
+    /// Returns the transactions of both the transaction pool and the orphan pool, for persisting them across node restarts
+    pub(crate) fn persisted_mempool(&self) -> PersistedMempool {
+        let _sw = Stopwatch::<50>::with_threshold("persisted_mempool op");
+        PersistedMempool {
+            transactions: self.transaction_pool.all().values().map(PersistedTransaction::new).collect(),
+            orphans: self.orphan_pool.all().values().map(PersistedTransaction::new).collect(),
+        }
+    }
+
+    /// Restores the DAA score at which a reloaded transaction was originally added, so it keeps its expiration schedule.
+    /// Returns whether the transaction was found in either pool.
+    pub(crate) fn restore_added_at_daa_score(&mut self, transaction_id: &TransactionId, added_at_daa_score: u64) -> bool {
+        match self.transaction_pool.get_mut(transaction_id).or_else(|| self.orphan_pool.get_mut(transaction_id)) {
+            Some(transaction) => {
+                transaction.added_at_daa_score = transaction.added_at_daa_score.min(added_at_daa_score);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn feerate_histogram(&self) -> FeerateHistogram {
+        self.transaction_pool.feerate_histogram().clone()
+    }
+
     pub(crate) fn all_transaction_ids_with_priority(&self, priority: Priority) -> Vec<TransactionId> {
         let _sw = Stopwatch::<15>::with_threshold("all_transaction_ids_with_priority op");
         self.transaction_pool.all_transaction_ids_with_priority(priority)
@@ -147,7 +195,9 @@ impl Mempool {
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
     pub enum Priority {
         Low,
         High,
@@ -158,4 +208,21 @@ pub mod tx {
         Forbidden,
         Allowed,
     }
+
+    /// Replace by fee (RBF) policy applied when a transaction double spends some mempool transactions
+    ///
+    /// A replacement must pay a total fee strictly higher than the total fee of all the transactions it evicts
+    /// (the double spent ones and their redeemers) and a feerate strictly higher than the feerate of each double
+    /// spent transaction.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum RbfPolicy {
+        /// Any double spend of a mempool transaction is rejected
+        #[default]
+        Forbidden,
+        /// A double spend is accepted as a replacement if it meets the RBF rules, a transaction
+        /// without any double spend is inserted as usual
+        Permitted,
+        /// The transaction is accepted only as a replacement of at least one mempool transaction
+        Mandatory,
+    }
 }
@@ -127,8 +127,13 @@ impl OrphanPool {
     }
 
     fn add_orphan(&mut self, virtual_daa_score: u64, transaction: MutableTransaction, priority: Priority) -> RuleResult<()> {
+        self.add_mempool_orphan(MempoolTransaction::new(transaction, priority, virtual_daa_score));
+        Ok(())
+    }
+
+    /// Add a mempool transaction to the pool, bypassing the orphan checks
+    pub(crate) fn add_mempool_orphan(&mut self, transaction: MempoolTransaction) {
         let id = transaction.id();
-        let transaction = MempoolTransaction::new(transaction, priority, virtual_daa_score);
         // Add all entries in outpoint_owner_id
         for input in transaction.mtx.tx.inputs.iter() {
             self.outpoint_owner_id.insert(input.previous_outpoint, id);
@@ -151,7 +156,6 @@ impl OrphanPool {
 
         self.all_orphans.insert(id, transaction);
         debug!("Added transaction to orphan pool: {}", id);
-        Ok(())
     }
 
     pub(crate) fn remove_orphan(
@@ -228,10 +232,6 @@ impl OrphanPool {
         }
     }
 
-    pub(crate) fn remove_redeemers_of(&mut self, transaction_id: &TransactionId) -> RuleResult<Vec<MempoolTransaction>> {
-        self.get_redeemer_ids_in_pool(transaction_id).iter().map(|x| self.remove_single_orphan(x)).collect()
-    }
-
     pub(crate) fn update_orphans_after_transaction_removed(
         &mut self,
         removed_transaction: &MempoolTransaction,
@@ -239,7 +239,16 @@ impl OrphanPool {
     ) -> RuleResult<Vec<MempoolTransaction>> {
         let removed_transaction_id = removed_transaction.id();
         if remove_redeemers {
-            return self.remove_redeemers_of(&removed_transaction_id);
+            // The removed transaction is not an orphan, so its direct redeemers are found by the outpoints they spend
+            let mut removed_orphans = vec![];
+            let mut outpoint = TransactionOutpoint::new(removed_transaction_id, 0);
+            for i in 0..removed_transaction.mtx.tx.outputs.len() {
+                outpoint.index = i as u32;
+                if let Some(orphan_id) = self.outpoint_orphan(&outpoint).map(|x| x.id()) {
+                    removed_orphans.extend(self.remove_orphan(&orphan_id, true, TxRemovalReason::Muted, "")?);
+                }
+            }
+            return Ok(removed_orphans);
         }
 
         let mut outpoint = TransactionOutpoint::new(removed_transaction_id, 0);
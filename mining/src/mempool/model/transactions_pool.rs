@@ -1,4 +1,5 @@
 use crate::{
+    feerate::histogram::FeerateHistogram,
     mempool::{
         config::Config,
         errors::{RuleError, RuleResult},
@@ -62,10 +63,14 @@ pub(crate) struct TransactionsPool {
 
     /// Store of UTXOs
     utxo_set: MempoolUtxoSet,
+
+    /// Mass of the transactions bucketed by feerate, used for fee estimation
+    feerate_histogram: FeerateHistogram,
 }
 
 impl TransactionsPool {
     pub(crate) fn new(config: Arc<Config>) -> Self {
+        let feerate_histogram = FeerateHistogram::new(config.minimum_relay_feerate());
         Self {
             config,
             all_transactions: MempoolTransactionCollection::default(),
@@ -75,6 +80,7 @@ impl TransactionsPool {
             last_expire_scan_daa_score: 0,
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
+            feerate_histogram,
         }
     }
 
@@ -113,6 +119,7 @@ impl TransactionsPool {
         }
 
         self.utxo_set.add_transaction(&transaction.mtx);
+        self.feerate_histogram.add(transaction.mtx.calculated_fee.unwrap_or_default(), transaction.mtx.tx.mass());
         self.all_transactions.insert(id, transaction);
         trace!("Added transaction {}", id);
         Ok(())
@@ -153,10 +160,15 @@ impl TransactionsPool {
 
         // Remove the transaction from the mempool UTXO set
         self.utxo_set.remove_transaction(&removed_tx.mtx, &parent_ids);
+        self.feerate_histogram.remove(removed_tx.mtx.calculated_fee.unwrap_or_default(), removed_tx.mtx.tx.mass());
 
         Ok(removed_tx)
     }
 
+    pub(crate) fn feerate_histogram(&self) -> &FeerateHistogram {
+        &self.feerate_histogram
+    }
+
     pub(crate) fn ready_transaction_count(&self) -> usize {
         self.ready_transactions.len()
     }
@@ -172,6 +184,19 @@ impl TransactionsPool {
             .collect()
     }
 
+    /// all_chained_transactions returns all fully populated mempool transactions having parents in the mempool.
+    /// These transactions cannot be inserted in a block template, but their fees are accounted for by the
+    /// package selection of their ancestors.
+    pub(crate) fn all_chained_transactions(&self) -> Vec<CandidateTransaction> {
+        // The returned transactions are leaving the mempool so they are cloned
+        self.all_transactions
+            .iter()
+            .filter(|(id, _)| !self.ready_transactions.contains(id))
+            .take(self.config.maximum_ready_transaction_count as usize)
+            .map(|(_, tx)| CandidateTransaction::from_mutable(&tx.mtx))
+            .collect()
+    }
+
     /// Is the mempool transaction identified by `transaction_id` unchained, thus having no successor?
     pub(crate) fn transaction_is_unchained(&self, transaction_id: &TransactionId) -> bool {
         if self.all_transactions.contains_key(transaction_id) {
@@ -185,19 +210,22 @@ impl TransactionsPool {
     /// Returns the exceeding low-priority transactions having the lowest fee rates in order
     /// to have room for at least `free_slots` new transactions. The returned transactions
     /// are guaranteed to be unchained (no successor in mempool) and to not be parent of
-    /// `transaction`.
+    /// `transaction`. The `replaced` transactions, about to be removed by a replace by fee,
+    /// are neither counted in the mempool size nor returned.
     ///
     /// An error is returned if the mempool is filled with high priority transactions.
     pub(crate) fn limit_transaction_count(
         &self,
         free_slots: usize,
         transaction: &MutableTransaction,
+        replaced: &[TransactionId],
     ) -> RuleResult<Vec<TransactionId>> {
         assert!(free_slots > 0);
         // Returns a vector of transactions to be removed that the caller has to remove actually.
         // The caller is golang validateAndInsertTransaction equivalent.
         // This behavior differs from golang impl.
-        let trim_size = self.len() + free_slots - usize::min(self.len() + free_slots, self.config.maximum_transaction_count as usize);
+        let len = self.len() - replaced.len();
+        let trim_size = len + free_slots - usize::min(len + free_slots, self.config.maximum_transaction_count as usize);
         let mut transactions_to_remove = Vec::with_capacity(trim_size);
         if trim_size > 0 {
             // TODO: consider introducing an index on all_transactions low-priority items instead.
@@ -205,10 +233,12 @@ impl TransactionsPool {
             // Sorting this vector here may be sub-optimal compared with maintaining a sorted
             // index of all_transactions low-priority items if the proportion of low-priority txs
             // in all_transactions is important.
-            let low_priority_txs = self
-                .all_transactions
-                .values()
-                .filter(|x| x.priority == Priority::Low && self.transaction_is_unchained(&x.id()) && !x.is_parent_of(transaction));
+            let low_priority_txs = self.all_transactions.values().filter(|x| {
+                x.priority == Priority::Low
+                    && self.transaction_is_unchained(&x.id())
+                    && !x.is_parent_of(transaction)
+                    && !replaced.contains(&x.id())
+            });
 
             if trim_size == 1 {
                 // This is the most likely case. Here we just search the minimum, thus avoiding the need to sort altogether.
@@ -227,7 +257,7 @@ impl TransactionsPool {
         }
 
         // An error is returned if the mempool is filled with high priority and other unremovable transactions.
-        let tx_count = self.len() + free_slots - transactions_to_remove.len();
+        let tx_count = len + free_slots - transactions_to_remove.len();
         if tx_count as u64 > self.config.maximum_transaction_count {
             let err = RuleError::RejectMempoolIsFull(tx_count - free_slots, self.config.maximum_transaction_count);
             warn!("{}", err.to_string());
@@ -249,6 +279,10 @@ impl TransactionsPool {
         self.utxo_set.check_double_spends(transaction)
     }
 
+    pub(crate) fn get_double_spending_transaction_ids(&self, transaction: &MutableTransaction) -> Vec<TransactionId> {
+        self.utxo_set.get_double_spending_transaction_ids(transaction)
+    }
+
     pub(crate) fn collect_expired_low_priority_transactions(&mut self, virtual_daa_score: u64) -> Vec<TransactionId> {
         let now = unix_now();
         if virtual_daa_score < self.last_expire_scan_daa_score + self.config.transaction_expire_scan_interval_daa_score
@@ -1,8 +1,12 @@
 use crate::mempool::tx::Priority;
-use kaspa_consensus_core::{tx::MutableTransaction, tx::TransactionId};
+use kaspa_consensus_core::{
+    tx::MutableTransaction,
+    tx::{Transaction, TransactionId},
+};
 use std::{
     cmp::Ordering,
     fmt::{Display, Formatter},
+    sync::Arc,
 };
 
 pub(crate) struct MempoolTransaction {
@@ -53,6 +57,14 @@ impl PartialEq for MempoolTransaction {
     }
 }
 
+/// Outcome of the post validation of a transaction
+pub(crate) struct TransactionPostValidation {
+    /// Ids of the transactions removed from the mempool by a replace by fee
+    pub(crate) removed: Vec<TransactionId>,
+    /// The transaction added to the mempool if it was not orphaned nor already in the mempool
+    pub(crate) accepted: Option<Arc<Transaction>>,
+}
+
 #[derive(PartialEq, Eq)]
 pub(crate) enum TxRemovalReason {
     Muted,
@@ -63,6 +75,7 @@ pub(crate) enum TxRemovalReason {
     DoubleSpend,
     InvalidInBlockTemplate,
     RevalidationWithMissingOutpoints,
+    ReplacedByFee,
 }
 
 impl TxRemovalReason {
@@ -76,6 +89,7 @@ impl TxRemovalReason {
             TxRemovalReason::DoubleSpend => "double spend",
             TxRemovalReason::InvalidInBlockTemplate => "invalid in block template",
             TxRemovalReason::RevalidationWithMissingOutpoints => "revalidation with missing outpoints",
+            TxRemovalReason::ReplacedByFee => "replaced by fee",
         }
     }
 
@@ -80,4 +80,18 @@ impl MempoolUtxoSet {
         }
         Ok(())
     }
+
+    /// Returns the ids of the mempool transactions spending an output which one of this transaction inputs spends
+    pub(crate) fn get_double_spending_transaction_ids(&self, transaction: &MutableTransaction) -> Vec<TransactionId> {
+        let transaction_id = transaction.id();
+        let mut double_spending_ids = Vec::new();
+        for input in transaction.tx.inputs.iter() {
+            if let Some(existing_transaction_id) = self.get_outpoint_owner_id(&input.previous_outpoint) {
+                if *existing_transaction_id != transaction_id && !double_spending_ids.contains(existing_transaction_id) {
+                    double_spending_ids.push(*existing_transaction_id);
+                }
+            }
+        }
+        double_spending_ids
+    }
 }
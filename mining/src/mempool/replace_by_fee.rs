@@ -0,0 +1,197 @@
+use crate::{
+    mempool::{
+        errors::{RuleError, RuleResult},
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TxRemovalReason},
+        },
+        tx::{Priority, RbfPolicy},
+        Mempool,
+    },
+    model::TransactionIdSet,
+};
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId};
+use kaspa_core::debug;
+use kaspa_utils::iter::IterExtensions;
+use std::sync::Arc;
+
+impl Mempool {
+    /// Checks the double spends of `transaction` against the mempool according to the RBF policy.
+    ///
+    /// Returns the ids of the mempool transactions double spent by `transaction`, which are candidates
+    /// for a replacement. The list is always empty when RBF is forbidden since any double spend is then
+    /// rejected.
+    pub(crate) fn validate_double_spends(
+        &self,
+        transaction: &MutableTransaction,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<Vec<TransactionId>> {
+        match rbf_policy {
+            RbfPolicy::Forbidden => {
+                self.transaction_pool.check_double_spends(transaction)?;
+                Ok(vec![])
+            }
+            RbfPolicy::Permitted => Ok(self.transaction_pool.get_double_spending_transaction_ids(transaction)),
+            RbfPolicy::Mandatory => {
+                let double_spends = self.transaction_pool.get_double_spending_transaction_ids(transaction);
+                if double_spends.is_empty() {
+                    return Err(RuleError::RejectRbfNoDoubleSpend(transaction.id()));
+                }
+                Ok(double_spends)
+            }
+        }
+    }
+
+    /// Checks that `transaction` may replace the `double_spends` transactions and all their redeemers, i.e. that it pays
+    /// a higher fee than all of them together and a higher feerate than each double spent transaction.
+    ///
+    /// `transaction` must be fully populated and validated. The mempool is left unchanged and the ids of the
+    /// transactions to be replaced are returned.
+    pub(crate) fn validate_replace_by_fee(
+        &self,
+        transaction: &MutableTransaction,
+        double_spends: &[TransactionId],
+    ) -> RuleResult<Vec<TransactionId>> {
+        if double_spends.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let transaction_id = transaction.id();
+        let fee = transaction.calculated_fee.unwrap();
+        let feerate = fee as f64 / transaction.tx.mass() as f64;
+
+        // Collect the whole chain of transactions to be evicted
+        let mut evicted_ids = TransactionIdSet::new();
+        let mut evicted = Vec::new();
+        for double_spend_id in double_spends.iter() {
+            let double_spend =
+                self.transaction_pool.get(double_spend_id).ok_or(RuleError::RejectMissingTransaction(*double_spend_id))?;
+            if feerate <= double_spend.fee_rate() {
+                return Err(RuleError::RejectRbfInsufficientFeerate(
+                    transaction_id,
+                    feerate,
+                    *double_spend_id,
+                    double_spend.fee_rate(),
+                ));
+            }
+            for id in std::iter::once(*double_spend_id).chain(self.transaction_pool.get_redeemer_ids_in_pool(double_spend_id)) {
+                if evicted_ids.insert(id) {
+                    evicted.push(id);
+                }
+            }
+        }
+
+        // The replacement cannot depend on a transaction it evicts
+        if let Some(input) = transaction.tx.inputs.iter().find(|input| evicted_ids.contains(&input.previous_outpoint.transaction_id)) {
+            return Err(RuleError::RejectRbfSpendsReplacedTransaction(transaction_id, input.previous_outpoint.transaction_id));
+        }
+
+        let evicted_fee = evicted
+            .iter()
+            .filter_map(|id| self.transaction_pool.get(id))
+            .map(|tx| tx.mtx.calculated_fee.unwrap_or_default())
+            .fold(0u64, |acc, fee| acc.saturating_add(fee));
+        if fee <= evicted_fee {
+            return Err(RuleError::RejectRbfInsufficientFee(transaction_id, fee, evicted_fee));
+        }
+        Ok(evicted)
+    }
+
+    /// Removes the `replaced` transactions, as returned by `validate_replace_by_fee`, and the `making_room` ones along
+    /// with their redeemers, then adds `transaction` to the transaction pool.
+    ///
+    /// All of it happens in one step: on any error the removed transactions are restored, leaving the mempool unchanged.
+    /// Returns the accepted transaction and the ids of the replaced transactions, their orphan redeemers included.
+    pub(crate) fn replace_and_add_transaction(
+        &mut self,
+        transaction: MutableTransaction,
+        virtual_daa_score: u64,
+        priority: Priority,
+        replaced: &[TransactionId],
+        making_room: &[TransactionId],
+    ) -> RuleResult<(Arc<Transaction>, Vec<TransactionId>)> {
+        let transaction_id = transaction.id();
+        let mut removed = RemovedTransactions::default();
+        let result = self.evict_transactions(replaced.iter().copied(), &mut removed).and_then(|_| {
+            let replaced_count = removed.ids.len();
+            let making_room = making_room
+                .iter()
+                .flat_map(|id| std::iter::once(*id).chain(self.transaction_pool.get_redeemer_ids_in_pool(id)))
+                .collect::<Vec<_>>();
+            self.evict_transactions(making_room.into_iter(), &mut removed)?;
+            let accepted = self.transaction_pool.add_transaction(transaction, virtual_daa_score, priority)?.mtx.tx.clone();
+            Ok((accepted, replaced_count))
+        });
+
+        match result {
+            Ok((accepted, replaced_count)) => {
+                let (replaced, made_room) = removed.ids.split_at(replaced_count);
+                for (reason, ids) in [(TxRemovalReason::ReplacedByFee, replaced), (TxRemovalReason::MakingRoom, made_room)] {
+                    if !ids.is_empty() {
+                        debug!(
+                            "Removed {} transactions ({}): {} for {}",
+                            ids.len(),
+                            reason,
+                            ids.iter().reusable_format(", "),
+                            transaction_id
+                        );
+                    }
+                }
+                Ok((accepted, replaced.to_vec()))
+            }
+            Err(err) => {
+                self.restore_transactions(removed);
+                Err(err)
+            }
+        }
+    }
+
+    /// Removes the transactions of the transaction pool yielded by `transaction_ids` and the orphans redeeming them,
+    /// keeping them in `removed` so that they can be restored. Ids already removed are skipped.
+    fn evict_transactions(
+        &mut self,
+        transaction_ids: impl Iterator<Item = TransactionId>,
+        removed: &mut RemovedTransactions,
+    ) -> RuleResult<()> {
+        for transaction_id in transaction_ids {
+            if !self.transaction_pool.has(&transaction_id) {
+                continue;
+            }
+            let transaction = self.transaction_pool.remove_transaction(&transaction_id)?;
+            let orphans = self.orphan_pool.update_orphans_after_transaction_removed(&transaction, true)?;
+            removed.ids.push(transaction_id);
+            removed.transactions.push(transaction);
+            removed.ids.extend(orphans.iter().map(|x| x.id()));
+            removed.orphans.extend(orphans);
+        }
+        Ok(())
+    }
+
+    /// Adds back the transactions removed by `evict_transactions`, parents before their redeemers.
+    fn restore_transactions(&mut self, removed: RemovedTransactions) {
+        let mut pending = removed.transactions;
+        while !pending.is_empty() {
+            let pending_ids = pending.iter().map(|x| x.id()).collect::<TransactionIdSet>();
+            let (ready, blocked): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .partition(|x| x.mtx.tx.inputs.iter().all(|input| !pending_ids.contains(&input.previous_outpoint.transaction_id)));
+            for transaction in ready {
+                // Adding back a transaction that was just removed cannot fail
+                self.transaction_pool.add_mempool_transaction(transaction).unwrap();
+            }
+            pending = blocked;
+        }
+        for orphan in removed.orphans {
+            self.orphan_pool.add_mempool_orphan(orphan);
+        }
+    }
+}
+
+/// Transactions removed from the mempool, kept aside until the operation removing them succeeds
+#[derive(Default)]
+struct RemovedTransactions {
+    /// Ids of all the removed transactions, in removal order
+    ids: Vec<TransactionId>,
+    transactions: Vec<MempoolTransaction>,
+    orphans: Vec<MempoolTransaction>,
+}
@@ -2,9 +2,9 @@ use crate::mempool::{
     errors::{RuleError, RuleResult},
     model::{
         pool::Pool,
-        tx::{MempoolTransaction, TxRemovalReason},
+        tx::{MempoolTransaction, TransactionPostValidation, TxRemovalReason},
     },
-    tx::{Orphan, Priority},
+    tx::{Orphan, Priority, RbfPolicy},
     Mempool,
 };
 use kaspa_consensus_core::{
@@ -13,19 +13,19 @@ use kaspa_consensus_core::{
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use kaspa_core::{debug, info};
-use std::sync::Arc;
 
 impl Mempool {
     pub(crate) fn pre_validate_and_populate_transaction(
         &self,
         consensus: &dyn ConsensusApi,
         mut transaction: MutableTransaction,
+        rbf_policy: RbfPolicy,
     ) -> RuleResult<MutableTransaction> {
         self.validate_transaction_unacceptance(&transaction)?;
         // Populate mass in the beginning, it will be used in multiple places throughout the validation and insertion.
         transaction.calculated_compute_mass = Some(consensus.calculate_transaction_compute_mass(&transaction.tx));
         self.validate_transaction_in_isolation(&transaction)?;
-        self.transaction_pool.check_double_spends(&transaction)?;
+        self.validate_double_spends(&transaction, rbf_policy)?;
         self.populate_mempool_entries(&mut transaction);
         Ok(transaction)
     }
@@ -37,7 +37,8 @@ impl Mempool {
         transaction: MutableTransaction,
         priority: Priority,
         orphan: Orphan,
-    ) -> RuleResult<Option<Arc<Transaction>>> {
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionPostValidation> {
         let transaction_id = transaction.id();
 
         // First check if the transaction was not already added to the mempool.
@@ -46,13 +47,13 @@ impl Mempool {
         // concurrently.
         if self.transaction_pool.has(&transaction_id) {
             debug!("Transaction {0} is not post validated since already in the mempool", transaction_id);
-            return Ok(None);
+            return Ok(TransactionPostValidation { removed: vec![], accepted: None });
         }
 
         self.validate_transaction_unacceptance(&transaction)?;
 
         // Re-check double spends since validate_and_insert_transaction is no longer atomic
-        self.transaction_pool.check_double_spends(&transaction)?;
+        let double_spends = self.validate_double_spends(&transaction, rbf_policy)?;
 
         match validation_result {
             Ok(_) => {}
@@ -60,8 +61,10 @@ impl Mempool {
                 if orphan == Orphan::Forbidden {
                     return Err(RuleError::RejectDisallowedOrphan(transaction_id));
                 }
+                // The fee of an orphan is unknown so it cannot replace any transaction
+                self.transaction_pool.check_double_spends(&transaction)?;
                 self.orphan_pool.try_add_orphan(consensus.get_virtual_daa_score(), transaction, priority)?;
-                return Ok(None);
+                return Ok(TransactionPostValidation { removed: vec![], accepted: None });
             }
             Err(err) => {
                 return Err(err);
@@ -70,15 +73,17 @@ impl Mempool {
 
         self.validate_transaction_in_context(&transaction)?;
 
+        // Check if the transaction replaces by fee the double spent transactions and their redeemers
+        let replaced = self.validate_replace_by_fee(&transaction, &double_spends)?;
+
         // Before adding the transaction, check if there is room in the pool
-        self.transaction_pool.limit_transaction_count(1, &transaction)?.iter().try_for_each(|x| {
-            self.remove_transaction(x, true, TxRemovalReason::MakingRoom, format!(" for {}", transaction_id).as_str())
-        })?;
-
-        // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
-        let accepted_transaction =
-            self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority)?.mtx.tx.clone();
-        Ok(Some(accepted_transaction))
+        let making_room = self.transaction_pool.limit_transaction_count(1, &transaction, &replaced)?;
+
+        // Evict the replaced transactions and make room, then add the transaction to the mempool as a MempoolTransaction
+        // and return a clone of the embedded Arc<Transaction>
+        let (accepted_transaction, removed) =
+            self.replace_and_add_transaction(transaction, consensus.get_virtual_daa_score(), priority, &replaced, &making_room)?;
+        Ok(TransactionPostValidation { removed, accepted: Some(accepted_transaction) })
     }
 
     /// Validates that the transaction wasn't already accepted into the DAG
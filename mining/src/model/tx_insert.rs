@@ -0,0 +1,20 @@
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use std::sync::Arc;
+
+/// Outcome of the insertion of a transaction into the mempool
+#[derive(Debug, Default)]
+pub struct TransactionInsertion {
+    /// Ids of the mempool transactions evicted by the inserted transaction when it replaced
+    /// them by fee, including the redeemers of the double spent transactions
+    pub removed: Vec<TransactionId>,
+
+    /// The inserted transaction, if accepted, followed by the transactions it unorphaned.
+    /// The transactions are clones of objects owned by the mempool.
+    pub accepted: Vec<Arc<Transaction>>,
+}
+
+impl TransactionInsertion {
+    pub fn new(removed: Vec<TransactionId>, accepted: Vec<Arc<Transaction>>) -> Self {
+        Self { removed, accepted }
+    }
+}
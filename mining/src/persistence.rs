@@ -0,0 +1,199 @@
+use crate::{
+    manager::MiningManagerProxy,
+    mempool::{model::tx::MempoolTransaction, tx::Priority},
+};
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceFuture},
+    trace, warn,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::Arc,
+};
+
+const PERSISTENCE: &str = "mempool-persistence";
+
+/// Name of the file holding the mempool across node restarts
+pub const MEMPOOL_FILE_NAME: &str = "mempool.dat";
+
+/// Version of the mempool file format, to be increased on any breaking change
+const MEMPOOL_FILE_VERSION: u32 = 2;
+
+/// A mempool transaction as persisted across node restarts
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PersistedTransaction {
+    pub transaction: Transaction,
+    pub priority: Priority,
+    /// The virtual DAA score at the time the transaction was first added to the mempool
+    pub added_at_daa_score: u64,
+}
+
+impl PersistedTransaction {
+    pub(crate) fn new(transaction: &MempoolTransaction) -> Self {
+        Self {
+            transaction: transaction.mtx.tx.as_ref().clone(),
+            priority: transaction.priority,
+            added_at_daa_score: transaction.added_at_daa_score,
+        }
+    }
+}
+
+/// The content of the mempool file. Every transaction is revalidated on reload, and the transactions of the
+/// transaction pool are kept apart from the orphans since they must not come back with missing inputs.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PersistedMempool {
+    pub transactions: Vec<PersistedTransaction>,
+    pub orphans: Vec<PersistedTransaction>,
+}
+
+impl PersistedMempool {
+    pub fn len(&self) -> usize {
+        self.transactions.len() + self.orphans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Outcome of reloading the mempool file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MempoolLoadSummary {
+    /// Number of transactions read from the file
+    pub transaction_count: usize,
+    /// Number of these transactions found in the mempool or the orphan pool after revalidation
+    pub accepted_count: usize,
+}
+
+/// Writes the transactions to the mempool file, replacing it atomically
+pub fn write_mempool_file(path: &Path, mempool: &PersistedMempool) -> io::Result<()> {
+    let temp_path = path.with_extension("new");
+    let mut writer = BufWriter::new(File::create(&temp_path)?);
+    bincode::serialize_into(&mut writer, &MEMPOOL_FILE_VERSION).map_err(io::Error::other)?;
+    bincode::serialize_into(&mut writer, mempool).map_err(io::Error::other)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    drop(writer);
+    fs::rename(temp_path, path)
+}
+
+/// Reads the transactions of the mempool file
+pub fn read_mempool_file(path: &Path) -> io::Result<PersistedMempool> {
+    // Script public keys deserialize from borrowed bytes, so the file is read whole rather than streamed
+    let bytes = fs::read(path)?;
+    let version: u32 = bincode::deserialize(&bytes).map_err(io::Error::other)?;
+    if version != MEMPOOL_FILE_VERSION {
+        return Err(io::Error::other(format!("unsupported mempool file version {version}, expected {MEMPOOL_FILE_VERSION}")));
+    }
+    let offset = bincode::serialized_size(&version).map_err(io::Error::other)? as usize;
+    let mut mempool: PersistedMempool = bincode::deserialize(&bytes[offset..]).map_err(io::Error::other)?;
+    // The transaction id is cached in the file, so it is recomputed rather than trusted
+    mempool.transactions.iter_mut().chain(mempool.orphans.iter_mut()).for_each(|x| x.transaction.finalize());
+    Ok(mempool)
+}
+
+/// Reloads the mempool from its file when the node starts and saves it back when the node stops
+pub struct MempoolPersistence {
+    mining_manager: MiningManagerProxy,
+    consensus_manager: Arc<ConsensusManager>,
+}
+
+impl MempoolPersistence {
+    pub fn new(mining_manager: MiningManagerProxy, consensus_manager: Arc<ConsensusManager>) -> Self {
+        Self { mining_manager, consensus_manager }
+    }
+
+    async fn load(&self) {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        match self.mining_manager.clone().load_mempool(&session).await {
+            Ok(summary) => {
+                info!("Reloaded {} of the {} persisted mempool transactions", summary.accepted_count, summary.transaction_count)
+            }
+            Err(err) => warn!("Failed to reload the persisted mempool: {}", err),
+        }
+    }
+
+    async fn save(&self) {
+        match self.mining_manager.clone().save_mempool().await {
+            Ok(transaction_count) => info!("Saved {} mempool transactions", transaction_count),
+            Err(err) => warn!("Failed to save the mempool: {}", err),
+        }
+    }
+}
+
+impl AsyncService for MempoolPersistence {
+    fn ident(self: Arc<Self>) -> &'static str {
+        PERSISTENCE
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.load().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", PERSISTENCE);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.save().await;
+            trace!("{} stopped", PERSISTENCE);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+    use kaspa_database::utils::get_kaspa_tempdir;
+
+    #[test]
+    fn test_mempool_file() {
+        let dir = get_kaspa_tempdir();
+        let path = dir.path().join(MEMPOOL_FILE_NAME);
+
+        let mut transactions = (0..3u64)
+            .map(|i| {
+                let input = TransactionInput::new(TransactionOutpoint::new(TransactionId::default(), i as u32), vec![i as u8], 0, 1);
+                let output = TransactionOutput::new(i, ScriptPublicKey::from_vec(0, vec![]));
+                PersistedTransaction {
+                    transaction: Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]),
+                    priority: if i == 0 { Priority::High } else { Priority::Low },
+                    added_at_daa_score: 100 + i,
+                }
+            })
+            .collect::<Vec<_>>();
+        let orphans = transactions.split_off(2);
+        let mempool = PersistedMempool { transactions, orphans };
+        write_mempool_file(&path, &mempool).unwrap();
+        let loaded = read_mempool_file(&path).unwrap();
+        assert_eq!((loaded.transactions.len(), loaded.orphans.len()), (2, 1));
+        let expected = mempool.transactions.iter().chain(mempool.orphans.iter());
+        for (loaded, expected) in loaded.transactions.iter().chain(loaded.orphans.iter()).zip(expected) {
+            assert_eq!(loaded.transaction.id(), expected.transaction.id());
+            assert_eq!(loaded.transaction, expected.transaction);
+            assert_eq!(loaded.priority, expected.priority);
+            assert_eq!(loaded.added_at_daa_score, expected.added_at_daa_score);
+        }
+
+        // A file of another version must be refused
+        let mut bytes = fs::read(&path).unwrap();
+        bytes[0] = 0xff;
+        fs::write(&path, bytes).unwrap();
+        assert!(read_mempool_file(&path).is_err());
+    }
+}
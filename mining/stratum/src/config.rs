@@ -0,0 +1,23 @@
+use kaspa_addresses::Prefix;
+use kaspa_consensus_core::config::params::ForkActivation;
+
+/// Share difficulty used when none is specified
+pub const DEFAULT_STRATUM_DIFFICULTY: f64 = 4096.0;
+
+#[derive(Clone, Debug)]
+pub struct StratumConfig {
+    /// Difficulty of the shares expected from the workers
+    pub difficulty: f64,
+
+    /// Address prefix of the network, added to the worker addresses lacking one
+    pub address_prefix: Prefix,
+
+    /// Activation of the relaunch hardfork heavy hash rules
+    pub hf_relaunch_activation: ForkActivation,
+}
+
+impl StratumConfig {
+    pub fn new(difficulty: f64, address_prefix: Prefix, hf_relaunch_activation: ForkActivation) -> Self {
+        Self { difficulty, address_prefix, hf_relaunch_activation }
+    }
+}
@@ -0,0 +1,56 @@
+use kaspa_addresses::AddressError;
+use kaspa_rpc_core::RpcError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("job {0} not found")]
+    JobNotFound(String),
+
+    #[error("duplicate share")]
+    DuplicateShare,
+
+    #[error("share difficulty too low")]
+    LowDifficultyShare,
+
+    #[error("worker is not authorized")]
+    Unauthorized,
+
+    #[error("worker is not subscribed")]
+    NotSubscribed,
+
+    #[error("unknown method {0}")]
+    UnknownMethod(String),
+
+    #[error("invalid parameters: {0}")]
+    InvalidParams(String),
+
+    #[error("invalid nonce {0}")]
+    InvalidNonce(String),
+
+    #[error("nonce {0} is outside of the worker extranonce range")]
+    ExtranonceMismatch(String),
+
+    #[error("address {0} does not belong to the {1} network")]
+    WrongNetworkAddress(String, String),
+
+    #[error("invalid address: {0}")]
+    AddressError(#[from] AddressError),
+
+    #[error("RPC error: {0}")]
+    RpcError(#[from] RpcError),
+}
+
+impl Error {
+    /// Error code reported to the miner, following the codes in use by the Stratum pools
+    pub fn code(&self) -> i32 {
+        match self {
+            Error::JobNotFound(_) => 21,
+            Error::DuplicateShare => 22,
+            Error::LowDifficultyShare => 23,
+            Error::Unauthorized => 24,
+            Error::NotSubscribed => 25,
+            _ => 20,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{error::Error, result::Result};
+use kaspa_consensus_core::{block::Block, config::params::ForkActivation, hashing};
+use kaspa_hashes::Hash;
+use kaspa_math::Uint256;
+use kaspa_pow::State;
+use kaspa_rpc_core::RpcBlock;
+use serde_json::{json, Value};
+use std::{collections::HashSet, sync::Arc};
+
+/// Outcome of a share meeting the share target
+#[derive(Debug)]
+pub enum ShareOutcome {
+    /// The share does not meet the block target
+    Share,
+    /// The share is a block, finalized with the share nonce
+    Block(Block),
+}
+
+/// A block template handed out to a worker
+pub struct Job {
+    id: String,
+    block: Block,
+    pre_pow_hash: Hash,
+    state: State,
+    submitted_nonces: HashSet<u64>,
+}
+
+impl Job {
+    pub fn new(id: String, block: &RpcBlock, hf_relaunch_activation: ForkActivation) -> Result<Self> {
+        let block = Block::try_from(block)?;
+        let pre_pow_hash = hashing::header::hash_override_nonce_time(&block.header, 0, 0);
+        let state = State::new(&block.header, hf_relaunch_activation);
+        Ok(Self { id, block, pre_pow_hash, state, submitted_nonces: HashSet::new() })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Parameters of the `mining.notify` notification announcing this job
+    pub fn notify_params(&self) -> Value {
+        json!([self.id, self.pre_pow_hash.to_le_u64(), self.block.header.timestamp])
+    }
+
+    /// Validates a share against `share_target`, returning the solved block if it also meets the block target
+    pub fn submit(&mut self, nonce: u64, share_target: Uint256) -> Result<ShareOutcome> {
+        if !self.submitted_nonces.insert(nonce) {
+            return Err(Error::DuplicateShare);
+        }
+        let (is_block, pow) = self.state.check_pow(nonce);
+        if is_block {
+            let mut header = self.block.header.as_ref().clone();
+            header.nonce = nonce;
+            header.finalize();
+            return Ok(ShareOutcome::Block(Block::from_arcs(Arc::new(header), self.block.transactions.clone())));
+        }
+        if pow > share_target {
+            return Err(Error::LowDifficultyShare);
+        }
+        Ok(ShareOutcome::Share)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{block_template, EASIEST_BITS, HARDEST_BITS};
+    use kaspa_consensus_core::config::params::ForkActivation;
+
+    fn job(bits: u32) -> Job {
+        Job::new("1".to_string(), &block_template(bits), ForkActivation::never()).unwrap()
+    }
+
+    #[test]
+    fn test_submit() {
+        let mut job = job(EASIEST_BITS);
+        let Ok(ShareOutcome::Block(block)) = job.submit(7, Uint256::MAX) else {
+            panic!("a share meeting the block target should be a block");
+        };
+        assert_eq!(block.header.nonce, 7);
+        assert_eq!(block.hash(), hashing::header::hash(&block.header));
+        assert!(matches!(job.submit(7, Uint256::MAX), Err(Error::DuplicateShare)));
+
+        let mut job = self::job(HARDEST_BITS);
+        assert!(matches!(job.submit(7, Uint256::MAX), Ok(ShareOutcome::Share)));
+        assert!(matches!(job.submit(8, Uint256::ZERO), Err(Error::LowDifficultyShare)));
+        // A share is only accepted once, even if it was rejected
+        assert!(matches!(job.submit(8, Uint256::MAX), Err(Error::DuplicateShare)));
+    }
+}
@@ -0,0 +1,15 @@
+//! Stratum V1 mining server issuing jobs out of the node block templates
+pub mod config;
+pub mod error;
+pub mod job;
+pub mod protocol;
+pub mod result;
+pub mod server;
+pub mod service;
+pub mod stats;
+
+#[cfg(test)]
+mod tests;
+
+/// Port of the Stratum listener when none is specified
+pub const DEFAULT_STRATUM_PORT: u16 = 5555;
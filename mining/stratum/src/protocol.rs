@@ -0,0 +1,128 @@
+//! Stratum V1 messages, exchanged as newline delimited JSON objects
+
+use crate::{error::Error, result::Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+pub const METHOD_SUBSCRIBE: &str = "mining.subscribe";
+pub const METHOD_EXTRANONCE_SUBSCRIBE: &str = "mining.extranonce.subscribe";
+pub const METHOD_AUTHORIZE: &str = "mining.authorize";
+pub const METHOD_SUBMIT: &str = "mining.submit";
+pub const METHOD_NOTIFY: &str = "mining.notify";
+pub const METHOD_SET_DIFFICULTY: &str = "mining.set_difficulty";
+pub const METHOD_SET_EXTRANONCE: &str = "mining.set_extranonce";
+
+/// Number of bytes of the nonce owned by the server, partitioning the nonce space between the workers
+pub const EXTRANONCE_SIZE: usize = 2;
+
+/// Number of bytes of the nonce left to the worker
+pub const WORKER_NONCE_SIZE: usize = 8 - EXTRANONCE_SIZE;
+
+/// A request sent by a worker
+#[derive(Debug, Deserialize)]
+pub struct StratumRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<Value>,
+}
+
+impl StratumRequest {
+    /// Returns the string parameter at `index`
+    pub fn str_param(&self, index: usize) -> Result<&str> {
+        self.params
+            .get(index)
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::InvalidParams(format!("{} expects a string parameter at position {}", self.method, index)))
+    }
+}
+
+/// A response to a worker request
+#[derive(Debug, Serialize)]
+pub struct StratumResponse {
+    pub id: Value,
+    pub result: Value,
+    pub error: Value,
+}
+
+impl StratumResponse {
+    pub fn ok(id: Value, result: Value) -> Self {
+        Self { id, result, error: Value::Null }
+    }
+
+    pub fn err(id: Value, error: &Error) -> Self {
+        Self { id, result: Value::Null, error: json!([error.code(), error.to_string(), null]) }
+    }
+}
+
+/// A notification pushed to a worker
+#[derive(Debug, Serialize)]
+pub struct StratumNotification {
+    pub id: Value,
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl StratumNotification {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { id: Value::Null, method, params }
+    }
+}
+
+/// Formats an extranonce the way it is announced to the workers
+pub fn extranonce_to_hex(extranonce: u16) -> String {
+    format!("{:0width$x}", extranonce, width = EXTRANONCE_SIZE * 2)
+}
+
+/// Parses the hex nonce of a submitted share.
+///
+/// Workers either submit the part of the nonce they own, in which case the extranonce gets
+/// prepended, or the full nonce, which must then lie in the extranonce range of the worker.
+pub fn parse_nonce(nonce: &str, extranonce: u16) -> Result<u64> {
+    let digits = nonce.strip_prefix("0x").unwrap_or(nonce);
+    if digits.is_empty() || digits.len() > 16 {
+        return Err(Error::InvalidNonce(nonce.to_string()));
+    }
+    let value = u64::from_str_radix(digits, 16).map_err(|_| Error::InvalidNonce(nonce.to_string()))?;
+    let prefix = (extranonce as u64) << (WORKER_NONCE_SIZE * 8);
+    if digits.len() <= WORKER_NONCE_SIZE * 2 {
+        return Ok(prefix | value);
+    }
+    if value >> (WORKER_NONCE_SIZE * 8) != extranonce as u64 {
+        return Err(Error::ExtranonceMismatch(nonce.to_string()));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nonce() {
+        assert_eq!(parse_nonce("0x0123456789ab", 0xbeef).unwrap(), 0xbeef_0123_4567_89ab);
+        assert_eq!(parse_nonce("1", 0x0001).unwrap(), 0x0001_0000_0000_0001);
+        assert_eq!(parse_nonce("beef0123456789ab", 0xbeef).unwrap(), 0xbeef_0123_4567_89ab);
+        assert!(matches!(parse_nonce("beee0123456789ab", 0xbeef), Err(Error::ExtranonceMismatch(_))));
+        assert!(matches!(parse_nonce("0x", 0xbeef), Err(Error::InvalidNonce(_))));
+        assert!(matches!(parse_nonce("0123456789abcdef0", 0xbeef), Err(Error::InvalidNonce(_))));
+        assert!(matches!(parse_nonce("xyz", 0xbeef), Err(Error::InvalidNonce(_))));
+        assert_eq!(extranonce_to_hex(0x00ab), "00ab");
+    }
+
+    #[test]
+    fn test_messages() {
+        let request: StratumRequest =
+            serde_json::from_str(r#"{"id":3,"method":"mining.submit","params":["worker","1","0x0123"]}"#).unwrap();
+        assert_eq!(request.str_param(1).unwrap(), "1");
+        assert!(matches!(request.str_param(3), Err(Error::InvalidParams(_))));
+
+        let response = StratumResponse::err(request.id, &Error::DuplicateShare);
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"id":3,"result":null,"error":[22,"duplicate share",null]}"#);
+        let response = StratumResponse::ok(json!(4), json!(true));
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"id":4,"result":true,"error":null}"#);
+        let notification = StratumNotification::new(METHOD_SET_DIFFICULTY, json!([4096.0]));
+        assert_eq!(serde_json::to_string(&notification).unwrap(), r#"{"id":null,"method":"mining.set_difficulty","params":[4096.0]}"#);
+    }
+}
@@ -0,0 +1,361 @@
+use crate::{
+    config::StratumConfig,
+    error::Error,
+    job::{Job, ShareOutcome},
+    protocol::*,
+    result::Result,
+    stats::{ShareStatus, StratumStats, WorkerId},
+};
+use async_trait::async_trait;
+use kaspa_addresses::Address;
+use kaspa_core::{debug, info, warn};
+use kaspa_math::Uint256;
+use kaspa_pow::calc_share_target;
+use kaspa_rpc_core::{api::rpc::DynRpcService, SubmitBlockReport};
+use kaspa_utils::line_session::{serve_lines, write_json_line, LineSession};
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::VecDeque,
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    net::{tcp::OwnedWriteHalf, TcpStream},
+    sync::watch,
+};
+use triggered::Listener;
+
+/// Maximum length of a request line sent by a worker
+const MAX_LINE_LENGTH: usize = 4096;
+
+/// Number of the most recent jobs of a worker still accepting shares
+const MAX_JOBS: usize = 32;
+
+/// Extra data of the block templates mined through the Stratum server
+const EXTRA_DATA: &[u8] = b"pyrin-stratum";
+
+/// Handles the Stratum sessions of the connected workers
+pub struct StratumServer {
+    config: StratumConfig,
+    rpc: DynRpcService,
+    stats: Arc<StratumStats>,
+    share_target: Uint256,
+    templates: watch::Sender<u64>,
+    extranonces: Mutex<Extranonces>,
+    next_job_id: AtomicU64,
+}
+
+impl StratumServer {
+    pub fn new(config: StratumConfig, rpc: DynRpcService) -> Self {
+        // Difficulties too low to be expressed as a target accept any share
+        let share_target = calc_share_target(config.difficulty).unwrap_or(Uint256::MAX);
+        let stats = Arc::new(StratumStats::new(config.difficulty));
+        let (templates, _) = watch::channel(0);
+        Self { config, rpc, stats, share_target, templates, extranonces: Default::default(), next_job_id: AtomicU64::new(0) }
+    }
+
+    pub fn stats(&self) -> Arc<StratumStats> {
+        self.stats.clone()
+    }
+
+    /// Signals all the sessions that a new block template is available, so they issue new jobs to their workers
+    pub fn new_template(&self) {
+        self.templates.send_modify(|version| *version = version.wrapping_add(1));
+    }
+
+    /// Serves a connected worker, with its own extranonce and a new job for every new block template
+    pub async fn serve(self: Arc<Self>, stream: TcpStream, peer: SocketAddr, shutdown: Listener) {
+        let Some(extranonce) = self.extranonces.lock().acquire() else {
+            warn!("Stratum client {} rejected, all the extranonces are in use", peer);
+            return;
+        };
+        debug!("Stratum client {} connected", peer);
+        self.stats.client_connected();
+        let (reader, writer) = stream.into_split();
+        let templates = self.templates.subscribe();
+        let mut session =
+            Session { server: self.clone(), writer, templates, extranonce, subscribed: false, worker: None, jobs: VecDeque::new() };
+        if let Err(err) = serve_lines(reader, MAX_LINE_LENGTH, shutdown, &mut session).await {
+            debug!("Stratum client {} disconnected: {}", peer, err);
+        } else {
+            debug!("Stratum client {} disconnected", peer);
+        }
+        self.stats.client_disconnected();
+        self.extranonces.lock().release(extranonce);
+    }
+}
+
+/// Extranonces of the connected workers, so that no two workers search the same nonce space
+#[derive(Default)]
+struct Extranonces {
+    /// Number of extranonces handed out so far
+    allocated: u32,
+    /// Extranonces released by the disconnected workers
+    released: Vec<u16>,
+}
+
+impl Extranonces {
+    /// Returns an extranonce not in use, or `None` if all of them are
+    fn acquire(&mut self) -> Option<u16> {
+        if let Some(extranonce) = self.released.pop() {
+            return Some(extranonce);
+        }
+        let extranonce = u16::try_from(self.allocated).ok()?;
+        self.allocated += 1;
+        Some(extranonce)
+    }
+
+    fn release(&mut self, extranonce: u16) {
+        self.released.push(extranonce);
+    }
+}
+
+struct Worker {
+    id: WorkerId,
+    address: Address,
+}
+
+/// State of the connection of a worker
+struct Session {
+    server: Arc<StratumServer>,
+    writer: OwnedWriteHalf,
+    /// Signals the new block templates
+    templates: watch::Receiver<u64>,
+    extranonce: u16,
+    subscribed: bool,
+    worker: Option<Worker>,
+    jobs: VecDeque<Job>,
+}
+
+#[async_trait]
+impl LineSession for Session {
+    type Event = ();
+
+    async fn handle_line(&mut self, line: &[u8]) -> io::Result<()> {
+        let request = match serde_json::from_slice::<StratumRequest>(line) {
+            Ok(request) => request,
+            Err(err) => {
+                let error = Error::InvalidParams(err.to_string());
+                return self.write(&StratumResponse::err(Value::Null, &error)).await;
+            }
+        };
+        let result = match request.method.as_str() {
+            METHOD_SUBSCRIBE => self.subscribe(),
+            METHOD_EXTRANONCE_SUBSCRIBE => Ok(json!(true)),
+            METHOD_AUTHORIZE => self.authorize(&request),
+            METHOD_SUBMIT => self.submit(&request).await,
+            method => Err(Error::UnknownMethod(method.to_string())),
+        };
+        match result {
+            Ok(result) => {
+                self.write(&StratumResponse::ok(request.id, result)).await?;
+                if request.method == METHOD_AUTHORIZE {
+                    let extranonce = json!([extranonce_to_hex(self.extranonce), WORKER_NONCE_SIZE]);
+                    self.write(&StratumNotification::new(METHOD_SET_EXTRANONCE, extranonce)).await?;
+                    self.write(&StratumNotification::new(METHOD_SET_DIFFICULTY, json!([self.server.config.difficulty]))).await?;
+                    self.send_job().await?;
+                }
+                Ok(())
+            }
+            Err(err) => self.write(&StratumResponse::err(request.id, &err)).await,
+        }
+    }
+
+    async fn next_event(&mut self) -> Option<()> {
+        self.templates.changed().await.ok()
+    }
+
+    async fn handle_event(&mut self, _: ()) -> io::Result<()> {
+        self.send_job().await
+    }
+}
+
+impl Session {
+    async fn write<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        write_json_line(&mut self.writer, message).await
+    }
+
+    fn subscribe(&mut self) -> Result<Value> {
+        self.subscribed = true;
+        Ok(json!([true, "EthereumStratum/1.0.0"]))
+    }
+
+    fn authorize(&mut self, request: &StratumRequest) -> Result<Value> {
+        if !self.subscribed {
+            return Err(Error::NotSubscribed);
+        }
+        let login = request.str_param(0)?;
+        let (address, name) = login.split_once('.').unwrap_or((login, "default"));
+        let prefix = self.server.config.address_prefix;
+        let address = match address.contains(':') {
+            true => Address::try_from(address)?,
+            false => Address::try_from(format!("{}:{}", prefix, address))?,
+        };
+        if address.prefix != prefix {
+            return Err(Error::WrongNetworkAddress(address.to_string(), prefix.to_string()));
+        }
+        info!("Stratum worker {} authorized with address {}", name, address);
+        self.worker = Some(Worker { id: WorkerId { address: address.to_string(), name: name.to_string() }, address });
+        Ok(json!(true))
+    }
+
+    async fn submit(&mut self, request: &StratumRequest) -> Result<Value> {
+        let Some(worker) = self.worker.as_ref() else {
+            return Err(Error::Unauthorized);
+        };
+        let (job_id, nonce) = (request.str_param(1)?, request.str_param(2)?);
+        let stats = self.server.stats.clone();
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id() == job_id) else {
+            stats.add_share(&worker.id, ShareStatus::Stale);
+            return Err(Error::JobNotFound(job_id.to_string()));
+        };
+        let outcome = parse_nonce(nonce, self.extranonce).and_then(|nonce| job.submit(nonce, self.server.share_target));
+        let block = match outcome {
+            Ok(ShareOutcome::Share) => {
+                stats.add_share(&worker.id, ShareStatus::Valid);
+                return Ok(json!(true));
+            }
+            Ok(ShareOutcome::Block(block)) => {
+                stats.add_share(&worker.id, ShareStatus::Valid);
+                block
+            }
+            Err(err) => {
+                let status = if matches!(err, Error::DuplicateShare) { ShareStatus::Duplicate } else { ShareStatus::Invalid };
+                stats.add_share(&worker.id, status);
+                return Err(err);
+            }
+        };
+
+        // The share is a block, submit it the same way the submit_block RPC does
+        let hash = block.hash();
+        match self.server.rpc.submit_block((&block).into(), false).await {
+            Ok(response) if response.report == SubmitBlockReport::Success => {
+                info!("Stratum worker {} ({}) found block {}", worker.id.name, worker.id.address, hash);
+                stats.add_block(&worker.id, true);
+            }
+            Ok(response) => {
+                warn!("Block {} found by Stratum worker {} was rejected: {:?}", hash, worker.id.name, response.report);
+                stats.add_block(&worker.id, false);
+            }
+            Err(err) => {
+                warn!("Block {} found by Stratum worker {} could not be submitted: {}", hash, worker.id.name, err);
+                stats.add_block(&worker.id, false);
+            }
+        }
+        Ok(json!(true))
+    }
+
+    /// Issues a job out of a new block template paying to the worker address
+    async fn send_job(&mut self) -> io::Result<()> {
+        let Some(worker) = self.worker.as_ref() else {
+            return Ok(());
+        };
+        // The node refuses to build templates while it is not synced, unless unsynced mining is enabled
+        let template = match self.server.rpc.get_block_template(worker.address.clone(), EXTRA_DATA.to_vec()).await {
+            Ok(template) => template,
+            Err(err) => {
+                debug!("No block template for Stratum worker {}: {}", worker.id.name, err);
+                return Ok(());
+            }
+        };
+        let id = format!("{:x}", self.server.next_job_id.fetch_add(1, Ordering::Relaxed));
+        let job = match Job::new(id, &template.block, self.server.config.hf_relaunch_activation) {
+            Ok(job) => job,
+            Err(err) => {
+                warn!("Failed to issue a Stratum job: {}", err);
+                return Ok(());
+            }
+        };
+        let notification = StratumNotification::new(METHOD_NOTIFY, job.notify_params());
+        if self.jobs.len() == MAX_JOBS {
+            self.jobs.pop_front();
+        }
+        self.jobs.push_back(job);
+        self.write(&notification).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{block_template, EASIEST_BITS};
+    use kaspa_addresses::{Prefix, Version};
+    use kaspa_consensus_core::config::params::ForkActivation;
+    use kaspa_rpc_core::test_helpers::RpcCoreMock;
+    use kaspa_utils::{line_session::test_helpers::LineClient, triggers::SingleTrigger};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_extranonces() {
+        let mut extranonces = Extranonces::default();
+        for expected in 0..=u16::MAX {
+            assert_eq!(extranonces.acquire(), Some(expected));
+        }
+        assert_eq!(extranonces.acquire(), None);
+        extranonces.release(42);
+        assert_eq!(extranonces.acquire(), Some(42));
+        assert_eq!(extranonces.acquire(), None);
+    }
+
+    #[tokio::test]
+    async fn test_session() {
+        // Every share meets the block target of the template
+        let rpc = Arc::new(RpcCoreMock::with_block_template(block_template(EASIEST_BITS)));
+        let config = StratumConfig::new(1.0, Prefix::Simnet, ForkActivation::never());
+        let server = Arc::new(StratumServer::new(config, rpc.clone()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let shutdown = SingleTrigger::new();
+        let listener_shutdown = shutdown.listener.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer) = listener.accept().await.unwrap();
+                tokio::spawn(server.clone().serve(stream, peer, listener_shutdown.clone()));
+            }
+        });
+
+        let mut client = LineClient::connect(address).await;
+        let login = format!("{}.rig", Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]));
+        let authorize = format!(r#"{{"id":2,"method":"mining.authorize","params":["{login}"]}}"#);
+        assert_eq!(client.call(&authorize).await, r#"{"id":2,"result":null,"error":[25,"worker is not subscribed",null]}"#);
+        assert_eq!(
+            client.call(r#"{"id":1,"method":"mining.subscribe","params":[]}"#).await,
+            r#"{"id":1,"result":[true,"EthereumStratum/1.0.0"],"error":null}"#
+        );
+        assert_eq!(client.call(&authorize).await, r#"{"id":2,"result":true,"error":null}"#);
+        assert_eq!(client.next_line().await, r#"{"id":null,"method":"mining.set_extranonce","params":["0000",6]}"#);
+        assert_eq!(client.next_line().await, r#"{"id":null,"method":"mining.set_difficulty","params":[1.0]}"#);
+        assert!(client.next_line().await.starts_with(r#"{"id":null,"method":"mining.notify","params":["0","#));
+
+        // Concurrent workers search distinct nonce spaces
+        let mut other_client = LineClient::connect(address).await;
+        other_client.call(r#"{"id":1,"method":"mining.subscribe","params":[]}"#).await;
+        other_client.call(&authorize).await;
+        assert_eq!(other_client.next_line().await, r#"{"id":null,"method":"mining.set_extranonce","params":["0001",6]}"#);
+
+        assert_eq!(
+            client.call(r#"{"id":3,"method":"mining.submit","params":["rig","9","0x1"]}"#).await,
+            r#"{"id":3,"result":null,"error":[21,"job 9 not found",null]}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":4,"method":"mining.submit","params":["rig","0","0x1"]}"#).await,
+            r#"{"id":4,"result":true,"error":null}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":5,"method":"mining.submit","params":["rig","0","0x1"]}"#).await,
+            r#"{"id":5,"result":null,"error":[22,"duplicate share",null]}"#
+        );
+        let submitted_blocks = rpc.submitted_blocks();
+        assert_eq!(submitted_blocks.len(), 1);
+        assert_eq!(submitted_blocks[0].header.nonce, 1);
+
+        shutdown.trigger.trigger();
+    }
+}
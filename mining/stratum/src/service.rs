@@ -0,0 +1,105 @@
+use crate::{config::StratumConfig, server::StratumServer, stats::StratumStats};
+use kaspa_core::{
+    info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use kaspa_notify::scope::{NewBlockTemplateScope, Scope};
+use kaspa_rpc_core::{
+    api::rpc::DynRpcService,
+    notify::{
+        channel::NotificationChannel,
+        connection::{ChannelConnection, ChannelType},
+    },
+    Notification,
+};
+use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Stratum listener serving jobs built out of the node block templates
+pub struct StratumService {
+    net_address: NetAddress,
+    rpc: DynRpcService,
+    server: Arc<StratumServer>,
+    shutdown: SingleTrigger,
+}
+
+impl StratumService {
+    pub const IDENT: &'static str = "stratum-service";
+
+    pub fn new(net_address: NetAddress, config: StratumConfig, rpc: DynRpcService) -> Self {
+        let server = Arc::new(StratumServer::new(config, rpc.clone()));
+        Self { net_address, rpc, server, shutdown: Default::default() }
+    }
+
+    /// Share and block accounting of the workers
+    pub fn stats(&self) -> Arc<StratumStats> {
+        self.server.stats()
+    }
+}
+
+impl AsyncService for StratumService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let listener = TcpListener::bind(std::net::SocketAddr::from(self.net_address))
+                .await
+                .map_err(|err| AsyncServiceError::Service(format!("failed to bind the Stratum listener: {err}")))?;
+            info!("Stratum server listening on {}", self.net_address);
+
+            // Follow the new block templates of the mining manager
+            let channel = NotificationChannel::default();
+            let listener_id =
+                self.rpc.register_new_listener(ChannelConnection::new(Self::IDENT, channel.sender(), ChannelType::Closable));
+            self.rpc
+                .start_notify(listener_id, Scope::NewBlockTemplate(NewBlockTemplateScope {}))
+                .await
+                .map_err(|err| AsyncServiceError::Service(err.to_string()))?;
+            let notifications = channel.receiver();
+
+            // Accept workers until a service shutdown signal is received
+            loop {
+                tokio::select! {
+                    _ = shutdown_signal.clone() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, peer)) => {
+                            tokio::spawn(self.server.clone().serve(stream, peer, shutdown_signal.clone()));
+                        }
+                        Err(err) => warn!("{} failed to accept a connection: {}", Self::IDENT, err),
+                    },
+                    notification = notifications.recv() => match notification {
+                        Ok(Notification::NewBlockTemplate(_)) => self.server.new_template(),
+                        Ok(_) => {}
+                        Err(_) => break,
+                    },
+                }
+            }
+
+            if let Err(err) = self.rpc.unregister_listener(listener_id).await {
+                trace!("{} failed to unregister its notification listener: {}", Self::IDENT, err);
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,237 @@
+use kaspa_metrics_core::prometheus::{PrometheusCollector, PrometheusEncoder, PrometheusMetricType};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Period over which the worker hashrates are estimated
+const HASHRATE_WINDOW: Duration = Duration::from_secs(600);
+
+/// Period after which a worker without any submission is dropped from the accounting
+const WORKER_IDLE_TIMEOUT: Duration = Duration::from_secs(3600);
+
+/// Maximum number of workers accounted, the least recently seen one being dropped to make room for a new one
+const MAX_WORKERS: usize = 10_000;
+
+/// Expected number of hashes to find a share of difficulty 1
+const HASHES_PER_SHARE: f64 = 4_294_967_296.0;
+
+/// Identifies a worker by its payout address and its name
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct WorkerId {
+    pub address: String,
+    pub name: String,
+}
+
+/// Share and block accounting of a worker
+#[derive(Clone, Debug, Default)]
+pub struct WorkerStats {
+    pub valid_shares: u64,
+    pub stale_shares: u64,
+    pub duplicate_shares: u64,
+    pub invalid_shares: u64,
+    pub blocks_accepted: u64,
+    pub blocks_rejected: u64,
+    /// Difficulty of the valid shares found within the hashrate window
+    recent_shares: VecDeque<(Instant, f64)>,
+    first_share: Option<Instant>,
+    last_seen: Option<Instant>,
+}
+
+impl WorkerStats {
+    fn add_share(&mut self, difficulty: f64, now: Instant) {
+        self.valid_shares += 1;
+        self.first_share.get_or_insert(now);
+        self.recent_shares.push_back((now, difficulty));
+        self.prune(now);
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while self.recent_shares.front().is_some_and(|(time, _)| now.duration_since(*time) > HASHRATE_WINDOW) {
+            self.recent_shares.pop_front();
+        }
+    }
+
+    /// Estimated hashrate of the worker in hashes per second
+    pub fn hashrate(&self, now: Instant) -> f64 {
+        let Some(first_share) = self.first_share else {
+            return 0.0;
+        };
+        let elapsed = now.duration_since(first_share).clamp(Duration::from_secs(1), HASHRATE_WINDOW);
+        let difficulty: f64 = self
+            .recent_shares
+            .iter()
+            .filter(|(time, _)| now.duration_since(*time) <= HASHRATE_WINDOW)
+            .map(|(_, difficulty)| difficulty)
+            .sum();
+        difficulty * HASHES_PER_SHARE / elapsed.as_secs_f64()
+    }
+}
+
+/// Kind of a share submitted by a worker
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ShareStatus {
+    Valid,
+    Stale,
+    Duplicate,
+    Invalid,
+}
+
+/// Share accounting of the Stratum server, kept per worker across reconnections until the worker goes idle
+pub struct StratumStats {
+    difficulty: f64,
+    clients: AtomicUsize,
+    workers: Mutex<HashMap<WorkerId, WorkerStats>>,
+}
+
+impl StratumStats {
+    pub fn new(difficulty: f64) -> Self {
+        Self { difficulty, clients: AtomicUsize::new(0), workers: Default::default() }
+    }
+
+    pub fn client_connected(&self) {
+        self.clients.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn client_disconnected(&self) {
+        self.clients.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub fn clients(&self) -> usize {
+        self.clients.load(Ordering::Relaxed)
+    }
+
+    pub fn add_share(&self, worker: &WorkerId, status: ShareStatus) {
+        let now = Instant::now();
+        let mut workers = self.workers.lock();
+        let stats = Self::worker_stats(&mut workers, worker, now);
+        match status {
+            ShareStatus::Valid => stats.add_share(self.difficulty, now),
+            ShareStatus::Stale => stats.stale_shares += 1,
+            ShareStatus::Duplicate => stats.duplicate_shares += 1,
+            ShareStatus::Invalid => stats.invalid_shares += 1,
+        }
+    }
+
+    pub fn add_block(&self, worker: &WorkerId, accepted: bool) {
+        let mut workers = self.workers.lock();
+        let stats = Self::worker_stats(&mut workers, worker, Instant::now());
+        if accepted {
+            stats.blocks_accepted += 1;
+        } else {
+            stats.blocks_rejected += 1;
+        }
+    }
+
+    /// Returns a snapshot of the accounting of every worker active within the idle timeout
+    pub fn workers(&self) -> Vec<(WorkerId, WorkerStats)> {
+        let now = Instant::now();
+        let mut workers = self.workers.lock();
+        Self::evict_idle_workers(&mut workers, now);
+        workers.values_mut().for_each(|stats| stats.prune(now));
+        workers.iter().map(|(id, stats)| (id.clone(), stats.clone())).collect()
+    }
+
+    /// Returns the accounting of `worker`, making room for it if the maximum number of workers is reached
+    fn worker_stats<'a>(workers: &'a mut HashMap<WorkerId, WorkerStats>, worker: &WorkerId, now: Instant) -> &'a mut WorkerStats {
+        if !workers.contains_key(worker) && workers.len() >= MAX_WORKERS {
+            Self::evict_idle_workers(workers, now);
+            if workers.len() >= MAX_WORKERS {
+                let least_recent = workers.iter().min_by_key(|(_, stats)| stats.last_seen).map(|(id, _)| id.clone());
+                least_recent.map(|id| workers.remove(&id));
+            }
+        }
+        let stats = workers.entry(worker.clone()).or_default();
+        stats.last_seen = Some(now);
+        stats
+    }
+
+    fn evict_idle_workers(workers: &mut HashMap<WorkerId, WorkerStats>, now: Instant) {
+        workers.retain(|_, stats| stats.last_seen.is_some_and(|last_seen| now.duration_since(last_seen) <= WORKER_IDLE_TIMEOUT));
+    }
+}
+
+impl PrometheusCollector for StratumStats {
+    fn collect(&self, encoder: &mut PrometheusEncoder) {
+        use PrometheusMetricType::*;
+
+        let now = Instant::now();
+        let workers = self.workers();
+        encoder.single("stratum_clients", "Number of clients connected to the Stratum server", Gauge, self.clients() as f64);
+        encoder.single(
+            "stratum_share_difficulty",
+            "Difficulty of the shares expected from the Stratum workers",
+            Gauge,
+            self.difficulty,
+        );
+        encoder.family("stratum_shares_total", "Number of shares submitted by a Stratum worker", Counter);
+        for (id, stats) in workers.iter() {
+            for (status, count) in [
+                ("valid", stats.valid_shares),
+                ("stale", stats.stale_shares),
+                ("duplicate", stats.duplicate_shares),
+                ("invalid", stats.invalid_shares),
+            ] {
+                encoder.sample(&[("address", &id.address), ("worker", &id.name), ("status", status)], count as f64);
+            }
+        }
+        encoder.family("stratum_blocks_total", "Number of blocks found by a Stratum worker", Counter);
+        for (id, stats) in workers.iter() {
+            encoder.sample(&[("address", &id.address), ("worker", &id.name), ("status", "accepted")], stats.blocks_accepted as f64);
+            encoder.sample(&[("address", &id.address), ("worker", &id.name), ("status", "rejected")], stats.blocks_rejected as f64);
+        }
+        encoder.family("stratum_worker_hashrate_hashes_per_second", "Hashrate of a Stratum worker estimated from its shares", Gauge);
+        for (id, stats) in workers.iter() {
+            encoder.sample(&[("address", &id.address), ("worker", &id.name)], stats.hashrate(now));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_hashrate() {
+        let start = Instant::now();
+        let mut stats = WorkerStats::default();
+        assert_eq!(stats.hashrate(start), 0.0);
+        for i in 0..10 {
+            stats.add_share(2.0, start + Duration::from_secs(i * 10));
+        }
+        let now = start + Duration::from_secs(100);
+        assert_eq!(stats.hashrate(now), 20.0 * HASHES_PER_SHARE / 100.0);
+
+        // Shares older than the window are no longer accounted
+        let now = start + HASHRATE_WINDOW + Duration::from_secs(45);
+        assert_eq!(stats.hashrate(now), 10.0 * HASHES_PER_SHARE / HASHRATE_WINDOW.as_secs_f64());
+        assert_eq!(stats.valid_shares, 10);
+    }
+
+    #[test]
+    fn test_worker_eviction() {
+        let worker = |i: usize| WorkerId { address: "pyrin:worker".to_string(), name: i.to_string() };
+        let start = Instant::now();
+        let mut workers = HashMap::new();
+        StratumStats::worker_stats(&mut workers, &worker(0), start);
+        StratumStats::worker_stats(&mut workers, &worker(1), start + Duration::from_secs(60));
+
+        // Idle workers are dropped
+        StratumStats::evict_idle_workers(&mut workers, start + WORKER_IDLE_TIMEOUT + Duration::from_secs(1));
+        assert!(!workers.contains_key(&worker(0)));
+        assert!(workers.contains_key(&worker(1)));
+
+        // Once full, the least recently seen worker makes room for a new one
+        let now = start + Duration::from_secs(120);
+        for i in 2..=MAX_WORKERS {
+            StratumStats::worker_stats(&mut workers, &worker(i), now);
+        }
+        assert_eq!(workers.len(), MAX_WORKERS);
+        StratumStats::worker_stats(&mut workers, &worker(MAX_WORKERS + 1), now);
+        assert_eq!(workers.len(), MAX_WORKERS);
+        assert!(!workers.contains_key(&worker(1)));
+        assert!(workers.contains_key(&worker(MAX_WORKERS + 1)));
+    }
+}
@@ -0,0 +1,31 @@
+//!
+//! Utilities and helpers for unit testing.
+//!
+
+use kaspa_consensus_core::{block::Block, constants::BLOCK_VERSION, header::Header};
+use kaspa_rpc_core::RpcBlock;
+
+/// Block bits whose target is met by any pow
+pub const EASIEST_BITS: u32 = 0x2100ffff;
+
+/// Block bits whose target is met by no pow in practice
+pub const HARDEST_BITS: u32 = 0x03000001;
+
+/// Returns a block template with the given bits
+pub fn block_template(bits: u32) -> RpcBlock {
+    let header = Header::new_finalized(
+        BLOCK_VERSION,
+        vec![vec![1.into()]],
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        1_700_000_000_000,
+        bits,
+        0,
+        0,
+        0.into(),
+        0,
+        Default::default(),
+    );
+    (&Block::new(header, vec![])).into()
+}
@@ -1,9 +1,10 @@
 use crate::flowcontext::{
+    compact_blocks::RecentBlockTransactions,
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
     transactions::TransactionsSpread,
 };
-use crate::{v5, v6};
+use crate::{v5, v6, v7};
 use async_trait::async_trait;
 use futures::future::join_all;
 use kaspa_addressmanager::AddressManager;
@@ -26,26 +27,28 @@ use kaspa_core::{
 use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
 use kaspa_mining::manager::MiningManagerProxy;
-use kaspa_mining::mempool::tx::{Orphan, Priority};
+use kaspa_mining::mempool::tx::{Orphan, Priority, RbfPolicy};
+use kaspa_mining::model::tx_query::TransactionQuery;
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
     common::ProtocolError,
-    convert::model::version::Version,
+    convert::model::{compact::short_transaction_id, version::Version},
+    encryption::{EncryptionConfigRef, EncryptionHandshake},
     make_message,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage},
+    pb::{kaspad_message::Payload, InvRelayBlockMessage, VersionMessage},
     ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router,
 };
 use kaspa_utils::iter::IterExtensions;
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 use std::{collections::hash_map::Entry, fmt::Display};
 use std::{
     iter::once,
     ops::Deref,
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
     time::Duration,
@@ -58,7 +61,7 @@ use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
 use uuid::Uuid;
 
 /// The P2P protocol version. Currently the only one supported.
-const PROTOCOL_VERSION: u32 = 8;
+const PROTOCOL_VERSION: u32 = 9;
 
 /// See `check_orphan_resolution_range`
 const BASELINE_ORPHAN_RESOLUTION_RANGE: u32 = 5;
@@ -69,6 +72,13 @@ const MAX_ORPHANS_UPPER_BOUND: usize = 1024;
 /// The min time to wait before allowing another parallel request
 const REQUEST_SCOPE_WAIT_TIME: Duration = Duration::from_secs(1);
 
+/// The time span (in seconds) of recent blocks whose transactions are kept for rebuilding compact blocks
+const RECENT_BLOCK_TRANSACTIONS_SPAN: u64 = 10;
+
+/// The largest mempool scanned for the transactions of a compact block. Short ids are salted with the block
+/// hash, so matching them means hashing the id of every scanned transaction while holding the mempool lock.
+const MAX_COMPACT_BLOCK_MEMPOOL_SCAN: usize = 100_000;
+
 /// Represents a block event to be logged
 #[derive(Debug, PartialEq)]
 pub enum BlockLogEvent {
@@ -216,6 +226,8 @@ pub struct FlowContextInner {
     orphans_pool: AsyncRwLock<OrphanBlocksPool>,
     shared_block_requests: Arc<Mutex<HashMap<Hash, RequestScopeMetadata>>>,
     transactions_spread: AsyncRwLock<TransactionsSpread>,
+    recent_block_transactions: Mutex<RecentBlockTransactions>,
+    compact_block_bytes_saved: AtomicU64,
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
@@ -224,6 +236,7 @@ pub struct FlowContextInner {
     mining_manager: MiningManagerProxy,
     pub(crate) tick_service: Arc<TickService>,
     notification_root: Arc<ConsensusNotificationRoot>,
+    encryption_config: Option<EncryptionConfigRef>,
 
     // Special sampling logger used only for high-bps networks where logs must be throttled
     block_event_logger: Option<BlockEventLogger>,
@@ -303,6 +316,7 @@ impl FlowContext {
         mining_manager: MiningManagerProxy,
         tick_service: Arc<TickService>,
         notification_root: Arc<ConsensusNotificationRoot>,
+        encryption_config: Option<EncryptionConfigRef>,
     ) -> Self {
         let hub = Hub::new();
 
@@ -318,6 +332,10 @@ impl FlowContext {
                 orphans_pool: AsyncRwLock::new(OrphanBlocksPool::new(max_orphans)),
                 shared_block_requests: Arc::new(Mutex::new(HashMap::new())),
                 transactions_spread: AsyncRwLock::new(TransactionsSpread::new(hub.clone())),
+                recent_block_transactions: Mutex::new(RecentBlockTransactions::new(
+                    (RECENT_BLOCK_TRANSACTIONS_SPAN * config.bps()).max(1) as usize,
+                )),
+                compact_block_bytes_saved: Default::default(),
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
@@ -327,6 +345,7 @@ impl FlowContext {
                 mining_manager,
                 tick_service,
                 notification_root,
+                encryption_config,
                 block_event_logger: if config.bps() > 1 { Some(BlockEventLogger::new(config.bps() as usize)) } else { None },
                 orphan_resolution_range,
                 max_orphans,
@@ -377,6 +396,36 @@ impl FlowContext {
         &self.mining_manager
     }
 
+    /// Returns the transactions held locally (mempool, orphans and recently processed blocks) whose
+    /// short ids within the given block are among `short_ids`. Used for rebuilding compact blocks.
+    ///
+    /// A mempool larger than [`MAX_COMPACT_BLOCK_MEMPOOL_SCAN`] is not scanned, leaving the transactions
+    /// missing from the recent blocks to be requested from the peer.
+    pub async fn compact_block_candidates(&self, block_hash: Hash, short_ids: &[u64]) -> Vec<Transaction> {
+        let short_ids: Arc<HashSet<u64>> = Arc::new(short_ids.iter().copied().collect());
+        let mut candidates =
+            self.recent_block_transactions.lock().filter(|id| short_ids.contains(&short_transaction_id(block_hash, *id)));
+        if self.mining_manager().clone().transaction_count(TransactionQuery::All).await > MAX_COMPACT_BLOCK_MEMPOOL_SCAN {
+            return candidates;
+        }
+        let mempool_candidates = self
+            .mining_manager()
+            .clone()
+            .filter_transactions(TransactionQuery::All, move |id| short_ids.contains(&short_transaction_id(block_hash, *id)))
+            .await;
+        candidates.extend(mempool_candidates.into_iter().map(|tx| (*tx).clone()));
+        candidates
+    }
+
+    pub fn add_compact_block_bytes_saved(&self, bytes: u64) {
+        self.compact_block_bytes_saved.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// The total amount of bytes which were not downloaded thanks to compact block relay
+    pub fn compact_block_bytes_saved(&self) -> u64 {
+        self.compact_block_bytes_saved.load(Ordering::Relaxed)
+    }
+
     pub fn try_set_ibd_running(&self, peer: PeerKey, relay_daa_score: u64) -> Option<IbdRunningGuard> {
         if self.is_ibd_running.compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst).is_ok() {
             self.ibd_metadata.write().replace(IbdMetadata { peer, daa_score: relay_daa_score });
@@ -545,6 +594,7 @@ impl FlowContext {
             // We only care about waiting for virtual to process the block at this point, before proceeding with post-processing
             // actions such as updating the mempool. We know this will not err since `block_task` already completed w/o error
             let _ = virtual_state_task.await;
+            self.recent_block_transactions.lock().insert(block.transactions.clone());
             if let Ok(txs) = self
                 .mining_manager()
                 .clone()
@@ -612,20 +662,26 @@ impl FlowContext {
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
     /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
     /// peers
+    ///
+    /// Returns the ids of the mempool transactions evicted by the submitted transaction if it replaced them by fee.
     pub async fn submit_rpc_transaction(
         &self,
         consensus: &ConsensusProxy,
         transaction: Transaction,
         orphan: Orphan,
-    ) -> Result<(), ProtocolError> {
-        let accepted_transactions =
-            self.mining_manager().clone().validate_and_insert_transaction(consensus, transaction, Priority::High, orphan).await?;
+        rbf_policy: RbfPolicy,
+    ) -> Result<Vec<TransactionId>, ProtocolError> {
+        let insertion = self
+            .mining_manager()
+            .clone()
+            .validate_and_insert_transaction(consensus, transaction, Priority::High, orphan, rbf_policy)
+            .await?;
         self.broadcast_transactions(
-            accepted_transactions.iter().map(|x| x.id()),
+            insertion.accepted.iter().map(|x| x.id()),
             false, // RPC transactions are considered high priority, so we don't want to throttle them
         )
         .await;
-        Ok(())
+        Ok(insertion.removed)
     }
 
     /// Returns true if the time has come for running the task cleaning mempool transactions.
@@ -676,12 +732,18 @@ impl ConnectionInitializer for FlowContext {
         // TODO: get number of live services
         // TODO: disable_relay_tx from config/cmd
 
+        // Advertise an ephemeral key if encrypted transport is enabled
+        let encryption_handshake =
+            self.encryption_config.as_ref().map(|config| EncryptionHandshake::new(&config.keypair, router.is_outbound()));
+        self_version_message.encryption_key = encryption_handshake.as_ref().map(|handshake| handshake.ephemeral_public_key());
+
         // Perform the handshake
-        let peer_version_message = handshake.handshake(self_version_message.into()).await?;
+        let self_version_message: VersionMessage = self_version_message.into();
+        let peer_version_message = handshake.handshake(self_version_message.clone()).await?;
         // Get time_offset as accurate as possible by computing right after the handshake
         let time_offset = unix_now() as i64 - peer_version_message.timestamp;
 
-        let peer_version: Version = peer_version_message.try_into()?;
+        let peer_version: Version = peer_version_message.clone().try_into()?;
         router.set_identity(peer_version.id);
         // Avoid duplicate connections
         if self.hub.has_peer(router.key()) {
@@ -696,11 +758,28 @@ impl ConnectionInitializer for FlowContext {
             return Err(ProtocolError::WrongNetwork(network_name, peer_version.network));
         }
 
+        // Switch to an encrypted transport if both sides advertised support for it, and otherwise fall back to
+        // plaintext unless the peer has a pinned key
+        if let Some(config) = self.encryption_config.as_ref() {
+            let peer_key = match (encryption_handshake, peer_version.encryption_key.as_ref()) {
+                (Some(encryption_handshake), Some(_)) => {
+                    Some(handshake.establish_encryption(encryption_handshake, &self_version_message, &peer_version_message).await?)
+                }
+                _ => None,
+            };
+            config.verify_peer_key(router.net_address(), peer_key)?;
+            match peer_key {
+                Some(peer_key) => info!("P2P, established encrypted transport with peer {} (key: {})", router, peer_key),
+                None => debug!("P2P, peer {} does not support encrypted transport", router),
+            }
+        }
+
         debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
 
         // Register all flows according to version
         let (flows, applied_protocol_version) = match peer_version.protocol_version {
-            v if v >= PROTOCOL_VERSION => (v6::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+            v if v >= PROTOCOL_VERSION => (v7::register(self.clone(), router.clone()), PROTOCOL_VERSION),
+            8 => (v6::register(self.clone(), router.clone()), 8),
             5 => (v5::register(self.clone(), router.clone()), 5),
             v => return Err(ProtocolError::VersionMismatch(PROTOCOL_VERSION, v)),
         };
@@ -0,0 +1,28 @@
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use std::{collections::VecDeque, sync::Arc};
+
+/// Keeps the transactions of recently processed blocks. Blocks mined in parallel often share
+/// transactions which already left the mempool when the first of them was processed, so
+/// these are needed in order to rebuild the following compact blocks locally.
+pub struct RecentBlockTransactions {
+    blocks: VecDeque<Arc<Vec<Transaction>>>,
+    capacity: usize,
+}
+
+impl RecentBlockTransactions {
+    pub fn new(capacity: usize) -> Self {
+        Self { blocks: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn insert(&mut self, transactions: Arc<Vec<Transaction>>) {
+        if self.blocks.len() == self.capacity {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back(transactions);
+    }
+
+    /// Returns the non-coinbase transactions whose ids pass `filter`
+    pub fn filter(&self, filter: impl Fn(&TransactionId) -> bool) -> Vec<Transaction> {
+        self.blocks.iter().flat_map(|txs| txs.iter().skip(1)).filter(|tx| filter(&tx.id())).cloned().collect()
+    }
+}
@@ -1,3 +1,4 @@
+pub mod compact_blocks;
 pub mod orphans;
 pub(crate) mod process_queue;
 pub mod transactions;
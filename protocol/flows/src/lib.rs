@@ -4,3 +4,4 @@ pub mod flowcontext;
 pub mod service;
 pub mod v5;
 pub mod v6;
+pub mod v7;
@@ -3,17 +3,25 @@ use crate::{
     flow_trait::Flow,
     flowcontext::orphans::OrphanOutput,
 };
-use kaspa_consensus_core::{api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError};
+use kaspa_consensus_core::{
+    api::BlockValidationFutures, block::Block, blockstatus::BlockStatus, errors::block::RuleError,
+    merkle::calc_hash_merkle_root_with_options, tx::Transaction,
+};
 use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusProxy};
 use kaspa_core::debug;
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
     common::ProtocolError,
+    convert::model::compact::{CompactBlock, PartialBlock},
     dequeue, dequeue_with_timeout, make_message, make_request,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage},
+    pb::{
+        kaspad_message::Payload, BlockMessage, InvRelayBlockMessage, RequestBlockLocatorMessage, RequestBlockTransactionsMessage,
+        RequestCompactBlockMessage, RequestRelayBlocksMessage,
+    },
     IncomingRoute, Router, SharedIncomingRoute,
 };
 use kaspa_utils::channel::{JobSender, JobTrySendError as TrySendError};
+use prost::Message;
 use std::{collections::VecDeque, sync::Arc};
 
 pub struct RelayInvMessage {
@@ -63,6 +71,8 @@ pub struct HandleRelayInvsFlow {
     msg_route: IncomingRoute,
     /// A channel sender for sending blocks to be handled by the IBD flow (of this peer)
     ibd_sender: JobSender<Block>,
+    /// Indicates whether relay blocks are requested as compact blocks (supported by the peer since v7)
+    compact_blocks: bool,
 }
 
 #[async_trait::async_trait]
@@ -84,7 +94,13 @@ impl HandleRelayInvsFlow {
         msg_route: IncomingRoute,
         ibd_sender: JobSender<Block>,
     ) -> Self {
-        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender }
+        Self { ctx, router, invs_route: TwoWayIncomingRoute::new(invs_route), msg_route, ibd_sender, compact_blocks: false }
+    }
+
+    /// Requests relay blocks as compact blocks rather than full blocks
+    pub fn with_compact_blocks(mut self) -> Self {
+        self.compact_blocks = true;
+        self
     }
 
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
@@ -228,6 +244,15 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
+        let block = if self.compact_blocks {
+            self.request_compact_block(requested_hash, request_id).await?
+        } else {
+            self.request_full_block(requested_hash, request_id).await?
+        };
+        Ok(Some((block, request_scope)))
+    }
+
+    async fn request_full_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Block, ProtocolError> {
         self.router
             .enqueue(make_request!(
                 Payload::RequestRelayBlocks,
@@ -240,8 +265,72 @@ impl HandleRelayInvsFlow {
         if block.hash() != requested_hash {
             Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
         } else {
-            Ok(Some((block, request_scope)))
+            Ok(block)
+        }
+    }
+
+    /// Requests a compact block and rebuilds its body from locally known transactions, requesting
+    /// only the missing ones from the peer. Falls back to requesting the full block if the body
+    /// cannot be rebuilt unambiguously.
+    async fn request_compact_block(&mut self, requested_hash: Hash, request_id: u32) -> Result<Block, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestCompactBlock,
+                RequestCompactBlockMessage { hash: Some(requested_hash.into()) },
+                request_id
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.msg_route, Payload::CompactBlock)?;
+        let mut received_bytes = msg.encoded_len();
+        let compact: CompactBlock = msg.try_into()?;
+        if compact.hash() != requested_hash {
+            return Err(ProtocolError::OtherOwned(format!(
+                "requested compact block {} but got block {}",
+                requested_hash,
+                compact.hash()
+            )));
+        }
+
+        let candidates = self.ctx.compact_block_candidates(requested_hash, &compact.short_ids).await;
+        let Some(mut partial) = PartialBlock::new(compact, candidates)? else {
+            debug!("Compact block {} has colliding short ids, requesting the full block", requested_hash);
+            return self.request_full_block(requested_hash, request_id).await;
+        };
+
+        let missing = partial.missing_indexes();
+        if !missing.is_empty() {
+            self.router
+                .enqueue(make_request!(
+                    Payload::RequestBlockTransactions,
+                    RequestBlockTransactionsMessage { block_hash: Some(requested_hash.into()), indexes: missing },
+                    request_id
+                ))
+                .await?;
+            let msg = dequeue_with_timeout!(self.msg_route, Payload::BlockTransactions)?;
+            received_bytes += msg.encoded_len();
+            let (hash, transactions): (Hash, Vec<Transaction>) = msg.try_into()?;
+            if hash != requested_hash {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "requested transactions of block {} but got transactions of block {}",
+                    requested_hash, hash
+                )));
+            }
+            partial.fill_missing(transactions)?;
+        }
+
+        let block = partial.into_block().ok_or(ProtocolError::Other("compact block is missing transactions after filling"))?;
+
+        // Locally found transactions might still be wrong (e.g. a short id matching a different transaction), in
+        // which case the block is requested in full rather than being submitted to consensus as invalid
+        let include_mass_field = block.header.daa_score > self.ctx.config.storage_mass_activation_daa_score;
+        if calc_hash_merkle_root_with_options(block.transactions.iter(), include_mass_field) != block.header.hash_merkle_root {
+            debug!("Compact block {} could not be rebuilt correctly, requesting the full block", requested_hash);
+            return self.request_full_block(requested_hash, request_id).await;
         }
+
+        let full_bytes = BlockMessage::from(&block).encoded_len();
+        self.ctx.add_compact_block_bytes_saved(full_bytes.saturating_sub(received_bytes) as u64);
+        Ok(block)
     }
 
     /// Process the orphan block. Returns `Some(BlockProcessingBatch)` if the block has no missing roots, where
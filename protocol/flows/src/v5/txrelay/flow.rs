@@ -10,7 +10,7 @@ use kaspa_mining::{
     errors::MiningManagerError,
     mempool::{
         errors::RuleError,
-        tx::{Orphan, Priority},
+        tx::{Orphan, Priority, RbfPolicy},
     },
     model::tx_query::TransactionQuery,
     P2pTxCountSample,
@@ -219,7 +219,7 @@ impl RelayTransactionsFlow {
             .ctx
             .mining_manager()
             .clone()
-            .validate_and_insert_transaction_batch(&consensus, transactions, Priority::Low, Orphan::Allowed)
+            .validate_and_insert_transaction_batch(&consensus, transactions, Priority::Low, Orphan::Allowed, RbfPolicy::Permitted)
             .await;
 
         for res in insert_results.iter() {
@@ -0,0 +1,91 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_core::debug;
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    common::ProtocolError,
+    convert::model::compact::CompactBlock,
+    make_response,
+    pb::{kaspad_message::Payload, BlockTransactionsMessage, RequestBlockTransactionsMessage, RequestCompactBlockMessage},
+    IncomingRoute, Router,
+};
+use std::sync::Arc;
+
+/// Serves compact block relay requests: compact blocks and the transactions
+/// the requesting peer could not find locally when rebuilding them
+pub struct HandleCompactBlockRequests {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for HandleCompactBlockRequests {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl HandleCompactBlockRequests {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let Some(msg) = self.incoming_route.recv().await else {
+                return Err(ProtocolError::ConnectionClosed);
+            };
+            let request_id = msg.request_id;
+            match msg.payload {
+                Some(Payload::RequestCompactBlock(request)) => self.handle_compact_block_request(request, request_id).await?,
+                Some(Payload::RequestBlockTransactions(request)) => {
+                    self.handle_block_transactions_request(request, request_id).await?
+                }
+                _ => {
+                    return Err(ProtocolError::UnexpectedMessage(
+                        stringify!(Payload::RequestCompactBlock | Payload::RequestBlockTransactions),
+                        msg.payload.as_ref().map(|v| v.into()),
+                    ))
+                }
+            }
+        }
+    }
+
+    async fn handle_compact_block_request(&mut self, msg: RequestCompactBlockMessage, request_id: u32) -> Result<(), ProtocolError> {
+        let hash: Hash = msg.try_into()?;
+        let block = self.ctx.consensus().unguarded_session().async_get_block(hash).await?;
+        let compact = CompactBlock::from_block(&block);
+        self.router.enqueue(make_response!(Payload::CompactBlock, (&compact).into(), request_id)).await?;
+        debug!("relayed compact block with hash {} to peer {}", hash, self.router);
+        Ok(())
+    }
+
+    async fn handle_block_transactions_request(
+        &mut self,
+        msg: RequestBlockTransactionsMessage,
+        request_id: u32,
+    ) -> Result<(), ProtocolError> {
+        let (hash, indexes): (Hash, Vec<u32>) = msg.try_into()?;
+        let block = self.ctx.consensus().unguarded_session().async_get_block(hash).await?;
+        let transactions = indexes
+            .into_iter()
+            .map(|index| {
+                block.transactions.get(index as usize).map(|tx| tx.into()).ok_or_else(|| {
+                    ProtocolError::OtherOwned(format!("requested transaction index {} out of range of block {}", index, hash))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        self.router
+            .enqueue(make_response!(
+                Payload::BlockTransactions,
+                BlockTransactionsMessage { block_hash: Some(hash.into()), transactions },
+                request_id
+            ))
+            .await?;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use super::{
     error::ConversionError,
     model::{
+        compact::CompactBlock,
         trusted::{TrustedDataEntry, TrustedDataPackage},
         version::Version,
     },
@@ -10,7 +11,7 @@ use crate::pb as protowire;
 use kaspa_consensus_core::{
     header::Header,
     pruning::{PruningPointProof, PruningPointsList},
-    tx::{TransactionId, TransactionOutpoint, UtxoEntry},
+    tx::{Transaction, TransactionId, TransactionOutpoint, UtxoEntry},
 };
 use kaspa_hashes::Hash;
 use kaspa_utils::networking::{IpAddress, PeerId};
@@ -33,6 +34,21 @@ impl From<Version> for protowire::VersionMessage {
             disable_relay_tx: item.disable_relay_tx,
             subnetwork_id: item.subnetwork_id.map(|x| x.into()),
             network: item.network.clone(),
+            encryption_key: item.encryption_key.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&CompactBlock> for protowire::CompactBlockMessage {
+    fn from(item: &CompactBlock) -> Self {
+        Self {
+            header: Some(item.header.as_ref().into()),
+            short_ids: item.short_ids.clone(),
+            prefilled_transactions: item
+                .prefilled
+                .iter()
+                .map(|(index, tx)| protowire::PrefilledTransaction { index: *index, transaction: Some(tx.into()) })
+                .collect(),
         }
     }
 }
@@ -54,6 +70,7 @@ impl TryFrom<protowire::VersionMessage> for Version {
             disable_relay_tx: msg.disable_relay_tx,
             subnetwork_id: if msg.subnetwork_id.is_none() { None } else { Some(msg.subnetwork_id.unwrap().try_into()?) },
             network: msg.network.clone(),
+            encryption_key: if msg.encryption_key.is_empty() { None } else { Some(msg.encryption_key) },
         })
     }
 }
@@ -220,3 +237,41 @@ impl TryFrom<protowire::RequestAntipastMessage> for (Hash, Hash) {
         Ok((msg.block_hash.try_into_ex()?, msg.context_hash.try_into_ex()?))
     }
 }
+
+impl TryFrom<protowire::RequestCompactBlockMessage> for Hash {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::RequestCompactBlockMessage) -> Result<Self, Self::Error> {
+        msg.hash.try_into_ex()
+    }
+}
+
+impl TryFrom<protowire::CompactBlockMessage> for CompactBlock {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::CompactBlockMessage) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            Arc::new(msg.header.try_into_ex()?),
+            msg.short_ids,
+            msg.prefilled_transactions
+                .into_iter()
+                .map(|p| Ok((p.index, p.transaction.try_into_ex()?)))
+                .collect::<Result<Vec<_>, Self::Error>>()?,
+        ))
+    }
+}
+
+impl TryFrom<protowire::RequestBlockTransactionsMessage> for (Hash, Vec<u32>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::RequestBlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((msg.block_hash.try_into_ex()?, msg.indexes))
+    }
+}
+
+impl TryFrom<protowire::BlockTransactionsMessage> for (Hash, Vec<Transaction>) {
+    type Error = ConversionError;
+    fn try_from(msg: protowire::BlockTransactionsMessage) -> Result<Self, Self::Error> {
+        Ok((
+            msg.block_hash.try_into_ex()?,
+            msg.transactions.into_iter().map(|tx| tx.try_into()).collect::<Result<Vec<_>, Self::Error>>()?,
+        ))
+    }
+}
@@ -0,0 +1,218 @@
+//!
+//! Model structures which are related to compact block relay. A compact block carries the block header
+//! and a short id per transaction, and the receiver rebuilds the body from transactions it already holds.
+//!
+
+use kaspa_consensus_core::{
+    block::Block,
+    header::Header,
+    tx::{Transaction, TransactionId},
+};
+use kaspa_hashes::Hash;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    sync::Arc,
+};
+use xxhash_rust::xxh3::xxh3_64_with_seed;
+
+use crate::common::ProtocolError;
+
+/// Returns the short id of a transaction within the block with the given hash. Salting with the
+/// block hash means that colliding transactions cannot be crafted ahead of time for all blocks.
+pub fn short_transaction_id(block_hash: Hash, transaction_id: TransactionId) -> u64 {
+    xxh3_64_with_seed(&transaction_id.as_bytes(), block_hash.to_le_u64()[0])
+}
+
+/// A block announced as its header, the short ids of its transactions and a few prefilled transactions
+pub struct CompactBlock {
+    pub header: Arc<Header>,
+    /// Short ids of all non-prefilled transactions, in block order
+    pub short_ids: Vec<u64>,
+    /// Transactions sent in full along with their index within the block, sorted by index
+    pub prefilled: Vec<(u32, Transaction)>,
+}
+
+impl CompactBlock {
+    pub fn new(header: Arc<Header>, short_ids: Vec<u64>, prefilled: Vec<(u32, Transaction)>) -> Self {
+        Self { header, short_ids, prefilled }
+    }
+
+    /// Builds the compact representation of a block. The coinbase is always prefilled since the
+    /// receiver cannot have it beforehand.
+    pub fn from_block(block: &Block) -> Self {
+        let hash = block.hash();
+        let prefilled = block.transactions.first().map(|coinbase| (0, coinbase.clone())).into_iter().collect();
+        let short_ids = block.transactions.iter().skip(1).map(|tx| short_transaction_id(hash, tx.id())).collect();
+        Self { header: block.header.clone(), short_ids, prefilled }
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.header.hash
+    }
+
+    pub fn transaction_count(&self) -> usize {
+        self.short_ids.len() + self.prefilled.len()
+    }
+}
+
+/// A compact block in the process of being rebuilt
+pub struct PartialBlock {
+    header: Arc<Header>,
+    transactions: Vec<Option<Transaction>>,
+    /// The expected short id of each slot, `None` for prefilled slots
+    short_ids: Vec<Option<u64>>,
+}
+
+impl PartialBlock {
+    /// Lays out the compact block and fills its slots from the given local candidate transactions.
+    ///
+    /// Returns `Ok(None)` if two transactions of the block share a short id, in which case the block
+    /// cannot be rebuilt unambiguously and should be requested in full.
+    pub fn new(compact: CompactBlock, candidates: impl IntoIterator<Item = Transaction>) -> Result<Option<Self>, ProtocolError> {
+        let count = compact.transaction_count();
+        let mut transactions: Vec<Option<Transaction>> = vec![None; count];
+        let mut short_ids = vec![None; count];
+
+        let mut last_index = None;
+        for (index, tx) in compact.prefilled {
+            if last_index.is_some_and(|last| index <= last) || index as usize >= count {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "compact block {} has invalid prefilled index {}",
+                    compact.header.hash, index
+                )));
+            }
+            transactions[index as usize] = Some(tx);
+            last_index = Some(index);
+        }
+
+        let mut slots = HashMap::with_capacity(compact.short_ids.len());
+        for (slot, short_id) in transactions.iter().enumerate().filter(|(_, tx)| tx.is_none()).map(|(i, _)| i).zip(compact.short_ids) {
+            if slots.insert(short_id, slot).is_some() {
+                return Ok(None);
+            }
+            short_ids[slot] = Some(short_id);
+        }
+
+        // Map each matching short id to its candidate, or to `None` if distinct candidates collide on it
+        let hash = compact.header.hash;
+        let mut matches: HashMap<u64, Option<Transaction>> = HashMap::new();
+        for tx in candidates {
+            let short_id = short_transaction_id(hash, tx.id());
+            if !slots.contains_key(&short_id) {
+                continue;
+            }
+            match matches.entry(short_id) {
+                Entry::Occupied(mut e) => {
+                    if e.get().as_ref().is_some_and(|other| other.id() != tx.id()) {
+                        e.insert(None);
+                    }
+                }
+                Entry::Vacant(e) => {
+                    e.insert(Some(tx));
+                }
+            }
+        }
+        for (short_id, tx) in matches {
+            transactions[slots[&short_id]] = tx;
+        }
+
+        Ok(Some(Self { header: compact.header, transactions, short_ids }))
+    }
+
+    /// Returns the indexes of the transactions which could not be found locally
+    pub fn missing_indexes(&self) -> Vec<u32> {
+        self.transactions.iter().enumerate().filter(|(_, tx)| tx.is_none()).map(|(i, _)| i as u32).collect()
+    }
+
+    /// Fills the missing slots with the transactions received from the peer, in the order of `missing_indexes`
+    pub fn fill_missing(&mut self, transactions: Vec<Transaction>) -> Result<(), ProtocolError> {
+        let missing = self.missing_indexes();
+        if missing.len() != transactions.len() {
+            return Err(ProtocolError::OtherOwned(format!(
+                "expected {} transactions of block {} but got {}",
+                missing.len(),
+                self.header.hash,
+                transactions.len()
+            )));
+        }
+        for (index, tx) in missing.into_iter().zip(transactions) {
+            let index = index as usize;
+            if self.short_ids[index] != Some(short_transaction_id(self.header.hash, tx.id())) {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "transaction {} does not match the short id at index {} of block {}",
+                    tx.id(),
+                    index,
+                    self.header.hash
+                )));
+            }
+            self.transactions[index] = Some(tx);
+        }
+        Ok(())
+    }
+
+    /// Returns the rebuilt block, or `None` if some transactions are still missing
+    pub fn into_block(self) -> Option<Block> {
+        let transactions = self.transactions.into_iter().collect::<Option<Vec<_>>>()?;
+        Some(Block::from_arcs(self.header, Arc::new(transactions)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE};
+
+    fn tx(payload: u8) -> Transaction {
+        let subnetwork_id = if payload == 0 { SUBNETWORK_ID_COINBASE } else { SUBNETWORK_ID_NATIVE };
+        Transaction::new(0, vec![], vec![], 0, subnetwork_id, 0, vec![payload])
+    }
+
+    fn block(tx_count: u8) -> Block {
+        let header = Header::from_precomputed_hash(Hash::from_le_u64([7, 0, 0, 0]), vec![]);
+        Block::new(header, (0..tx_count).map(tx).collect())
+    }
+
+    #[test]
+    fn test_compact_block_reconstruction() {
+        let block = block(5);
+        let compact = CompactBlock::from_block(&block);
+        assert_eq!(compact.prefilled.len(), 1);
+        assert_eq!(compact.short_ids.len(), 4);
+        assert_eq!(compact.transaction_count(), 5);
+
+        // Transactions 2 and 4 are known locally along with an unrelated one
+        let candidates = vec![block.transactions[2].clone(), block.transactions[4].clone(), tx(100)];
+        let mut partial = PartialBlock::new(compact, candidates).unwrap().unwrap();
+        assert_eq!(partial.missing_indexes(), vec![1, 3]);
+        assert!(partial.fill_missing(vec![block.transactions[3].clone(), block.transactions[1].clone()]).is_err());
+        assert!(partial.fill_missing(vec![block.transactions[1].clone()]).is_err());
+        partial.fill_missing(vec![block.transactions[1].clone(), block.transactions[3].clone()]).unwrap();
+
+        let rebuilt = partial.into_block().unwrap();
+        assert_eq!(rebuilt.hash(), block.hash());
+        assert_eq!(
+            rebuilt.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>(),
+            block.transactions.iter().map(|tx| tx.id()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_compact_block_short_id_collisions() {
+        let block = block(3);
+
+        // Duplicate short ids within the block require the full block
+        let mut compact = CompactBlock::from_block(&block);
+        compact.short_ids[1] = compact.short_ids[0];
+        assert!(PartialBlock::new(compact, vec![]).unwrap().is_none());
+
+        // Invalid prefilled indexes are a protocol error
+        let mut compact = CompactBlock::from_block(&block);
+        compact.prefilled[0].0 = 3;
+        assert!(PartialBlock::new(compact, vec![]).is_err());
+
+        // Short ids are salted by the block hash
+        let other = Hash::from_le_u64([8, 0, 0, 0]);
+        let id = block.transactions[1].id();
+        assert_ne!(short_transaction_id(block.hash(), id), short_transaction_id(other, id));
+    }
+}
@@ -18,6 +18,8 @@ pub struct Version {
     pub user_agent: String,
     pub disable_relay_tx: bool,
     pub subnetwork_id: Option<SubnetworkId>,
+    /// The ephemeral key of an encrypted transport handshake, if the peer supports it
+    pub encryption_key: Option<Vec<u8>>,
 }
 
 impl Version {
@@ -38,6 +40,7 @@ impl Version {
             user_agent: format!("/{}:{}/", name(), version()),
             disable_relay_tx: false,
             subnetwork_id,
+            encryption_key: None,
         }
     }
 
@@ -46,6 +46,12 @@ pub enum KaspadMessagePayloadType {
     IbdChainBlockLocator,
     RequestAntipast,
     RequestNextPruningPointAndItsAnticoneBlocks,
+    RequestCompactBlock,
+    CompactBlock,
+    RequestBlockTransactions,
+    BlockTransactions,
+    EncryptionHandshake,
+    Encrypted,
 }
 
 impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
@@ -100,6 +106,12 @@ impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
             KaspadMessagePayload::RequestNextPruningPointAndItsAnticoneBlocks(_) => {
                 KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks
             }
+            KaspadMessagePayload::RequestCompactBlock(_) => KaspadMessagePayloadType::RequestCompactBlock,
+            KaspadMessagePayload::CompactBlock(_) => KaspadMessagePayloadType::CompactBlock,
+            KaspadMessagePayload::RequestBlockTransactions(_) => KaspadMessagePayloadType::RequestBlockTransactions,
+            KaspadMessagePayload::BlockTransactions(_) => KaspadMessagePayloadType::BlockTransactions,
+            KaspadMessagePayload::EncryptionHandshake(_) => KaspadMessagePayloadType::EncryptionHandshake,
+            KaspadMessagePayload::Encrypted(_) => KaspadMessagePayloadType::Encrypted,
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::core::hub::HubEvent;
+use crate::encryption::{CipherState, EncryptionError};
 use crate::pb::RejectMessage;
 use crate::pb::{kaspad_message::Payload as KaspadMessagePayload, KaspadMessage};
 use crate::{common::ProtocolError, KaspadMessagePayloadType};
@@ -11,12 +12,13 @@ use std::fmt::{Debug, Display};
 use std::net::SocketAddr;
 use std::ops::{Deref, DerefMut};
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, sync::Arc};
 use tokio::select;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{channel as mpsc_channel, Receiver as MpscReceiver, Sender as MpscSender};
-use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
+use tokio::sync::oneshot::{channel as oneshot_channel, Receiver as OneshotReceiver, Sender as OneshotSender};
+use tokio::time::timeout;
 use tonic::Streaming;
 
 use super::peer::{PeerKey, PeerProperties};
@@ -102,11 +104,57 @@ struct RouterMutableState {
 
     /// Duration of the last ping to this peer
     last_ping_duration: u64,
+
+    /// Used on encrypted transport establishment to pass the receive cipher to the router receive loop
+    recv_cipher_signal: Option<OneshotSender<CipherState>>,
 }
 
 impl RouterMutableState {
-    fn new(start_signal: Option<OneshotSender<()>>, shutdown_signal: Option<OneshotSender<()>>) -> Self {
-        Self { start_signal, shutdown_signal, ..Default::default() }
+    fn new(
+        start_signal: Option<OneshotSender<()>>,
+        shutdown_signal: Option<OneshotSender<()>>,
+        recv_cipher_signal: Option<OneshotSender<CipherState>>,
+    ) -> Self {
+        Self { start_signal, shutdown_signal, recv_cipher_signal, ..Default::default() }
+    }
+}
+
+/// The receiving side of a possibly encrypted transport, owned by the router receive loop
+struct IncomingCipher {
+    /// Set once the peer sent its static key, after which it only sends encrypted messages
+    expected: bool,
+    cipher: Option<CipherState>,
+    cipher_receiver: Option<OneshotReceiver<CipherState>>,
+}
+
+impl IncomingCipher {
+    fn new(cipher_receiver: OneshotReceiver<CipherState>) -> Self {
+        Self { expected: false, cipher: None, cipher_receiver: Some(cipher_receiver) }
+    }
+
+    /// Unwraps encrypted messages and rejects unencrypted ones once the transport is encrypted
+    async fn open(&mut self, msg: KaspadMessage) -> Result<KaspadMessage, ProtocolError> {
+        match msg.payload {
+            Some(KaspadMessagePayload::Encrypted(encrypted)) if self.expected => {
+                if self.cipher.is_none() {
+                    // The peer may complete the handshake and switch to the encrypted transport slightly before we do
+                    let receiver = self.cipher_receiver.take().ok_or(EncryptionError::UnexpectedEncryptedMessage)?;
+                    let cipher = timeout(Duration::from_secs(8), receiver)
+                        .await
+                        .map_err(|_| EncryptionError::UnexpectedEncryptedMessage)?
+                        .map_err(|_| EncryptionError::UnexpectedEncryptedMessage)?;
+                    self.cipher = Some(cipher);
+                }
+                Ok(self.cipher.as_mut().expect("cipher was just set").open(encrypted)?)
+            }
+            Some(KaspadMessagePayload::Encrypted(_)) => Err(EncryptionError::UnexpectedEncryptedMessage.into()),
+            Some(ref payload) if self.expected => Err(EncryptionError::UnencryptedMessage(payload.into()).into()),
+            Some(KaspadMessagePayload::EncryptionHandshake(_)) => {
+                self.expected = true;
+                Ok(msg)
+            }
+            _ => Ok(msg),
+        }
     }
 }
 
@@ -134,6 +182,9 @@ pub struct Router {
     /// The outgoing route for sending messages to this peer
     outgoing_route: MpscSender<KaspadMessage>,
 
+    /// The cipher of outgoing messages, set once an encrypted transport is established
+    send_cipher: Mutex<Option<CipherState>>,
+
     /// A channel sender for internal event management. Used to send information from each router to a central hub object
     hub_sender: MpscSender<HubEvent>,
 
@@ -182,6 +233,7 @@ impl Router {
     ) -> Arc<Self> {
         let (start_sender, start_receiver) = oneshot_channel();
         let (shutdown_sender, mut shutdown_receiver) = oneshot_channel();
+        let (recv_cipher_sender, recv_cipher_receiver) = oneshot_channel();
 
         let router = Arc::new(Router {
             identity: Default::default(),
@@ -191,8 +243,9 @@ impl Router {
             routing_map_by_type: RwLock::new(HashMap::new()),
             routing_map_by_id: RwLock::new(HashMap::new()),
             outgoing_route,
+            send_cipher: Mutex::new(None),
             hub_sender,
-            mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender), Some(recv_cipher_sender))),
         });
 
         let router_clone = router.clone();
@@ -200,6 +253,7 @@ impl Router {
         tokio::spawn(async move {
             // Wait for a start signal before entering the receive loop
             let _ = start_receiver.await;
+            let mut incoming_cipher = IncomingCipher::new(recv_cipher_receiver);
             loop {
                 select! {
                     biased; // We use biased polling so that the shutdown signal is always checked first
@@ -211,8 +265,22 @@ impl Router {
 
                     res = incoming_stream.message() => match res {
                         Ok(Some(msg)) => {
-                            trace!("P2P msg: {:?}, router-id: {}, peer: {}", message_summary(&msg), router.identity(), router);
-                            match router.route_to_flow(msg) {
+                            // Opening the message might wait for the receive cipher, which must not hold back the shutdown signal
+                            let res = select! {
+                                biased;
+
+                                _ = &mut shutdown_receiver => {
+                                    debug!("P2P, Router receive loop - shutdown signal received while waiting for the receive cipher, router-id: {}", router.identity());
+                                    break;
+                                }
+
+                                res = incoming_cipher.open(msg) => res,
+                            };
+                            let res = res.and_then(|msg| {
+                                trace!("P2P msg: {:?}, router-id: {}, peer: {}", message_summary(&msg), router.identity(), router);
+                                router.route_to_flow(msg)
+                            });
+                            match res {
                                 Ok(()) => {},
                                 Err(e) => {
                                     match e {
@@ -294,6 +362,20 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Switches this connection to an encrypted transport. All following outgoing messages are encrypted, and
+    /// all incoming messages following the static key message of the peer are expected to be encrypted
+    pub(crate) fn enable_encryption(&self, send_cipher: CipherState, recv_cipher: CipherState) {
+        *self.send_cipher.lock() = Some(send_cipher);
+        if let Some(signal) = self.mutable_state.lock().recv_cipher_signal.take() {
+            let _ = signal.send(recv_cipher);
+        }
+    }
+
+    /// Indicates whether this connection uses an encrypted transport
+    pub fn is_encrypted(&self) -> bool {
+        self.send_cipher.lock().is_some()
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -402,6 +484,12 @@ impl Router {
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Pyrin P2P message should always have a value");
+        // Sealing under the lock keeps the nonce order consistent with the order of the outgoing route
+        let mut send_cipher = self.send_cipher.lock();
+        let msg = match send_cipher.as_mut() {
+            Some(cipher) => cipher.seal(msg)?,
+            None => msg,
+        };
         match self.outgoing_route.try_send(msg) {
             Ok(_) => Ok(()),
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
@@ -64,6 +64,10 @@ impl EchoFlow {
             KaspadMessagePayloadType::IbdChainBlockLocator,
             KaspadMessagePayloadType::RequestAntipast,
             KaspadMessagePayloadType::RequestNextPruningPointAndItsAnticoneBlocks,
+            KaspadMessagePayloadType::RequestCompactBlock,
+            KaspadMessagePayloadType::CompactBlock,
+            KaspadMessagePayloadType::RequestBlockTransactions,
+            KaspadMessagePayloadType::BlockTransactions,
         ]);
         let mut echo_flow = EchoFlow { router, receiver };
         debug!("EchoFlow, start app-layer receiving loop");
@@ -101,6 +105,7 @@ fn build_dummy_version_message() -> VersionMessage {
         disable_relay_tx: false,
         subnetwork_id: None,
         network: "pyrin-mainnet".to_string(),
+        encryption_key: vec![],
     }
 }
 
@@ -0,0 +1,450 @@
+//!
+//! Opt-in encrypted and authenticated transport for P2P connections.
+//!
+//! Peers supporting encryption advertise an ephemeral key in their version message. When both sides do so,
+//! each side sends its static key encrypted under the ephemeral key exchange, and the transport keys are then
+//! derived from the ephemeral-ephemeral and both ephemeral-static exchanges (similar to the Noise XX pattern).
+//! Since the transport keys can only be derived by the holders of the static keys, the `Ready` exchange which
+//! follows serves as key confirmation.
+//!
+
+use crate::{
+    make_message,
+    pb::{kaspad_message::Payload, EncryptedMessage, KaspadMessage, VersionMessage},
+    KaspadMessagePayloadType,
+};
+use blake2b_simd::Params;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload as AeadPayload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use kaspa_utils::networking::ContextualNetAddress;
+use prost::Message;
+use secp256k1::{ecdh::SharedSecret, rand::thread_rng, Keypair, PublicKey, SecretKey, SECP256K1};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    fs,
+    net::SocketAddr,
+    path::Path,
+    str::FromStr,
+    sync::Arc,
+};
+use thiserror::Error;
+
+const KDF_PERSONALIZATION: &[u8; 16] = b"PyrinP2PNoiseKdf";
+
+#[derive(Error, Debug, Clone)]
+pub enum EncryptionError {
+    #[error("invalid encryption key")]
+    InvalidKey,
+
+    #[error("failed to decrypt message")]
+    DecryptionFailed,
+
+    #[error("failed to encrypt message")]
+    EncryptionFailed,
+
+    #[error("encrypted message is malformed")]
+    MalformedMessage,
+
+    #[error("transport nonce exhausted")]
+    NonceExhausted,
+
+    #[error("received unencrypted message {0:?} over an encrypted transport")]
+    UnencryptedMessage(KaspadMessagePayloadType),
+
+    #[error("received encrypted message before the transport was established")]
+    UnexpectedEncryptedMessage,
+
+    #[error("peer {0} has a pinned key but does not support encrypted transport")]
+    PinnedPeerNotEncrypted(SocketAddr),
+
+    #[error("peer {0} static key {1} does not match its pinned key")]
+    PinnedKeyMismatch(SocketAddr, EncryptionPublicKey),
+}
+
+/// The public static key identifying a node over encrypted transports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EncryptionPublicKey(PublicKey);
+
+impl EncryptionPublicKey {
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, EncryptionError> {
+        Ok(Self(PublicKey::from_slice(bytes).map_err(|_| EncryptionError::InvalidKey)?))
+    }
+
+    pub fn to_bytes(&self) -> [u8; 33] {
+        self.0.serialize()
+    }
+}
+
+impl Display for EncryptionPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl FromStr for EncryptionPublicKey {
+    type Err = EncryptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(PublicKey::from_str(s).map_err(|_| EncryptionError::InvalidKey)?))
+    }
+}
+
+/// The static keypair of this node
+#[derive(Clone)]
+pub struct EncryptionKeypair(Keypair);
+
+impl EncryptionKeypair {
+    pub fn generate() -> Self {
+        Self(Keypair::new_global(&mut thread_rng()))
+    }
+
+    /// Loads the keypair stored at `path`, generating and storing a new one if the file does not exist
+    pub fn load_or_generate(path: &Path) -> std::io::Result<Self> {
+        if path.exists() {
+            let secret = SecretKey::from_str(fs::read_to_string(path)?.trim())
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+            return Ok(Self(Keypair::from_secret_key(SECP256K1, &secret)));
+        }
+
+        let keypair = Self::generate();
+        fs::write(path, keypair.0.secret_key().display_secret().to_string())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(keypair)
+    }
+
+    pub fn public_key(&self) -> EncryptionPublicKey {
+        EncryptionPublicKey(self.0.public_key())
+    }
+}
+
+/// A peer key pinned to a peer address, expected in the form `<key>@<address>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PinnedPeerKey {
+    pub key: EncryptionPublicKey,
+    pub address: ContextualNetAddress,
+}
+
+impl Display for PinnedPeerKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.key, self.address)
+    }
+}
+
+impl FromStr for PinnedPeerKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, address) = s.split_once('@').ok_or_else(|| format!("expected <key>@<address> but got {s}"))?;
+        let key = key.parse().map_err(|err: EncryptionError| err.to_string())?;
+        let address = address.parse().map_err(|err: std::net::AddrParseError| err.to_string())?;
+        Ok(Self { key, address })
+    }
+}
+
+/// Encrypted transport settings of this node
+pub struct EncryptionConfig {
+    pub keypair: EncryptionKeypair,
+    /// The static keys expected from peers at the given addresses
+    pub pinned_keys: HashMap<SocketAddr, EncryptionPublicKey>,
+}
+
+impl EncryptionConfig {
+    pub fn new(keypair: EncryptionKeypair, pinned_keys: HashMap<SocketAddr, EncryptionPublicKey>) -> Self {
+        Self { keypair, pinned_keys }
+    }
+
+    /// Verifies the static key of the peer at `address` against its pinned key, if any
+    pub fn verify_peer_key(&self, address: SocketAddr, key: Option<EncryptionPublicKey>) -> Result<(), EncryptionError> {
+        match (self.pinned_keys.get(&address), key) {
+            (None, _) => Ok(()),
+            (Some(_), None) => Err(EncryptionError::PinnedPeerNotEncrypted(address)),
+            (Some(pinned), Some(key)) if *pinned == key => Ok(()),
+            (Some(_), Some(key)) => Err(EncryptionError::PinnedKeyMismatch(address, key)),
+        }
+    }
+}
+
+pub type EncryptionConfigRef = Arc<EncryptionConfig>;
+
+fn kdf(parts: &[&[u8]]) -> [u8; 32] {
+    let mut state = Params::new().hash_length(32).personal(KDF_PERSONALIZATION).to_state();
+    for part in parts {
+        state.update(part);
+    }
+    state.finalize().as_bytes().try_into().expect("hash length is 32")
+}
+
+/// Serializes the fields of a version message which are mixed into the handshake transcript.
+///
+/// The fields are serialized one by one rather than re-encoding the decoded message, since the latter drops
+/// the fields unknown to this node. A field added to the version message must be appended here along with a
+/// protocol version bump.
+fn version_transcript(version: &VersionMessage) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut push = |field: &[u8]| {
+        bytes.extend_from_slice(&(field.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(field);
+    };
+    push(&version.protocol_version.to_le_bytes());
+    push(&version.services.to_le_bytes());
+    push(&version.timestamp.to_le_bytes());
+    match &version.address {
+        Some(address) => {
+            push(&[1]);
+            push(&address.timestamp.to_le_bytes());
+            push(&address.ip);
+            push(&address.port.to_le_bytes());
+        }
+        None => push(&[0]),
+    }
+    push(&version.id);
+    push(version.user_agent.as_bytes());
+    push(&[version.disable_relay_tx as u8]);
+    match &version.subnetwork_id {
+        Some(subnetwork_id) => {
+            push(&[1]);
+            push(&subnetwork_id.bytes);
+        }
+        None => push(&[0]),
+    }
+    push(version.network.as_bytes());
+    push(&version.encryption_key);
+    bytes
+}
+
+/// One direction of an encrypted transport
+pub(crate) struct CipherState {
+    cipher: ChaCha20Poly1305,
+    nonce: u64,
+}
+
+impl Debug for CipherState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CipherState").field("nonce", &self.nonce).finish_non_exhaustive()
+    }
+}
+
+impl CipherState {
+    fn new(key: [u8; 32]) -> Self {
+        Self { cipher: ChaCha20Poly1305::new(Key::from_slice(&key)), nonce: 0 }
+    }
+
+    fn next_nonce(&mut self) -> Result<Nonce, EncryptionError> {
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&self.nonce.to_le_bytes());
+        self.nonce = self.nonce.checked_add(1).ok_or(EncryptionError::NonceExhausted)?;
+        Ok(*Nonce::from_slice(&nonce))
+    }
+
+    fn encrypt(&mut self, msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let nonce = self.next_nonce()?;
+        self.cipher.encrypt(&nonce, AeadPayload { msg, aad }).map_err(|_| EncryptionError::EncryptionFailed)
+    }
+
+    fn decrypt(&mut self, msg: &[u8], aad: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let nonce = self.next_nonce()?;
+        self.cipher.decrypt(&nonce, AeadPayload { msg, aad }).map_err(|_| EncryptionError::DecryptionFailed)
+    }
+
+    /// Wraps the message into an encrypted message
+    pub(crate) fn seal(&mut self, msg: KaspadMessage) -> Result<KaspadMessage, EncryptionError> {
+        let ciphertext = self.encrypt(&msg.encode_to_vec(), &[])?;
+        Ok(make_message!(Payload::Encrypted, EncryptedMessage { ciphertext }))
+    }
+
+    /// Unwraps the message carried by an encrypted message
+    pub(crate) fn open(&mut self, msg: EncryptedMessage) -> Result<KaspadMessage, EncryptionError> {
+        let plaintext = self.decrypt(&msg.ciphertext, &[])?;
+        KaspadMessage::decode(plaintext.as_slice()).map_err(|_| EncryptionError::MalformedMessage)
+    }
+}
+
+/// The local side of an encrypted transport handshake, created before the version exchange
+pub struct EncryptionHandshake {
+    static_keypair: Keypair,
+    ephemeral_keypair: Keypair,
+    is_initiator: bool,
+}
+
+impl EncryptionHandshake {
+    /// Creates a handshake with a fresh ephemeral key. The initiator is the outbound side of the connection.
+    pub fn new(static_keypair: &EncryptionKeypair, is_initiator: bool) -> Self {
+        Self { static_keypair: static_keypair.0, ephemeral_keypair: Keypair::new_global(&mut thread_rng()), is_initiator }
+    }
+
+    /// The ephemeral key to be advertised in the version message
+    pub fn ephemeral_public_key(&self) -> Vec<u8> {
+        self.ephemeral_keypair.public_key().serialize().to_vec()
+    }
+
+    /// Performs the ephemeral key exchange with the key advertised by the peer in its version message.
+    ///
+    /// The version messages of both sides are mixed into the transcript. Since they carry the advertised ephemeral keys,
+    /// the handshake fails if any of them, or any other field of the version messages, was tampered with.
+    pub(crate) fn exchange(
+        self,
+        local_version: &VersionMessage,
+        remote_version: &VersionMessage,
+    ) -> Result<KeyExchange, EncryptionError> {
+        if local_version.encryption_key != self.ephemeral_public_key() {
+            return Err(EncryptionError::InvalidKey);
+        }
+        let remote_ephemeral_key = PublicKey::from_slice(&remote_version.encryption_key).map_err(|_| EncryptionError::InvalidKey)?;
+        let (initiator_version, responder_version) =
+            if self.is_initiator { (local_version, remote_version) } else { (remote_version, local_version) };
+        let (initiator_version, responder_version) = (version_transcript(initiator_version), version_transcript(responder_version));
+        let initiator_version_len = (initiator_version.len() as u64).to_le_bytes();
+        let transcript_hash = kdf(&[b"transcript", &initiator_version_len, &initiator_version, &responder_version]);
+        let ee = SharedSecret::new(&remote_ephemeral_key, &self.ephemeral_keypair.secret_key());
+        let initiator_cipher = CipherState::new(kdf(&[b"handshake-initiator", &transcript_hash, ee.as_ref()]));
+        let responder_cipher = CipherState::new(kdf(&[b"handshake-responder", &transcript_hash, ee.as_ref()]));
+        let (send_cipher, recv_cipher) =
+            if self.is_initiator { (initiator_cipher, responder_cipher) } else { (responder_cipher, initiator_cipher) };
+        Ok(KeyExchange { handshake: self, remote_ephemeral_key, transcript_hash, ee, send_cipher, recv_cipher })
+    }
+}
+
+/// An encrypted transport handshake after the ephemeral key exchange, used to exchange static keys
+pub(crate) struct KeyExchange {
+    handshake: EncryptionHandshake,
+    remote_ephemeral_key: PublicKey,
+    transcript_hash: [u8; 32],
+    ee: SharedSecret,
+    send_cipher: CipherState,
+    recv_cipher: CipherState,
+}
+
+impl KeyExchange {
+    /// Returns the local static key encrypted for the peer
+    pub(crate) fn static_key_message(&mut self) -> Result<Vec<u8>, EncryptionError> {
+        let static_key = self.handshake.static_keypair.public_key().serialize();
+        self.send_cipher.encrypt(&static_key, &self.transcript_hash)
+    }
+
+    /// Decrypts the static key of the peer and derives the transport ciphers.
+    /// Returns the peer static key along with the send and receive ciphers.
+    pub(crate) fn finalize(
+        mut self,
+        remote_message: &[u8],
+    ) -> Result<(EncryptionPublicKey, CipherState, CipherState), EncryptionError> {
+        let remote_static_key = self.recv_cipher.decrypt(remote_message, &self.transcript_hash)?;
+        let remote_static_key = PublicKey::from_slice(&remote_static_key).map_err(|_| EncryptionError::InvalidKey)?;
+        let handshake = &self.handshake;
+
+        // Each side mixes its own static secret with the remote ephemeral key and its ephemeral secret with the remote static key
+        let local_static_ephemeral = SharedSecret::new(&self.remote_ephemeral_key, &handshake.static_keypair.secret_key());
+        let local_ephemeral_static = SharedSecret::new(&remote_static_key, &handshake.ephemeral_keypair.secret_key());
+        let local_static_key = handshake.static_keypair.public_key().serialize();
+        let (initiator_static_key, responder_static_key, es, se) = if handshake.is_initiator {
+            (local_static_key, remote_static_key.serialize(), local_static_ephemeral, local_ephemeral_static)
+        } else {
+            (remote_static_key.serialize(), local_static_key, local_ephemeral_static, local_static_ephemeral)
+        };
+
+        let transcript_hash = kdf(&[b"transcript", &self.transcript_hash, &initiator_static_key, &responder_static_key]);
+        let secrets: [&[u8]; 3] = [self.ee.as_ref(), es.as_ref(), se.as_ref()];
+        let initiator_cipher = CipherState::new(kdf(&[&[b"transport-initiator".as_slice(), &transcript_hash], &secrets[..]].concat()));
+        let responder_cipher = CipherState::new(kdf(&[&[b"transport-responder".as_slice(), &transcript_hash], &secrets[..]].concat()));
+        let (send_cipher, recv_cipher) =
+            if handshake.is_initiator { (initiator_cipher, responder_cipher) } else { (responder_cipher, initiator_cipher) };
+        Ok((EncryptionPublicKey(remote_static_key), send_cipher, recv_cipher))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{PingMessage, ReadyMessage};
+
+    fn version(handshake: &EncryptionHandshake) -> VersionMessage {
+        VersionMessage { protocol_version: 7, encryption_key: handshake.ephemeral_public_key(), ..Default::default() }
+    }
+
+    fn handshake(initiator: &EncryptionKeypair, responder: &EncryptionKeypair) -> (KeyExchange, KeyExchange) {
+        let initiator = EncryptionHandshake::new(initiator, true);
+        let responder = EncryptionHandshake::new(responder, false);
+        let (initiator_version, responder_version) = (version(&initiator), version(&responder));
+        (
+            initiator.exchange(&initiator_version, &responder_version).unwrap(),
+            responder.exchange(&responder_version, &initiator_version).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_encrypted_transport() {
+        let (initiator_keypair, responder_keypair) = (EncryptionKeypair::generate(), EncryptionKeypair::generate());
+        let (mut initiator, mut responder) = handshake(&initiator_keypair, &responder_keypair);
+        let initiator_message = initiator.static_key_message().unwrap();
+        let responder_message = responder.static_key_message().unwrap();
+        let (responder_key, mut initiator_send, mut initiator_recv) = initiator.finalize(&responder_message).unwrap();
+        let (initiator_key, mut responder_send, mut responder_recv) = responder.finalize(&initiator_message).unwrap();
+        assert_eq!(responder_key, responder_keypair.public_key());
+        assert_eq!(initiator_key, initiator_keypair.public_key());
+
+        for nonce in 0..3 {
+            let msg = make_message!(Payload::Ping, PingMessage { nonce });
+            let Some(Payload::Encrypted(sealed)) = initiator_send.seal(msg.clone()).unwrap().payload else { panic!() };
+            assert_eq!(responder_recv.open(sealed).unwrap(), msg);
+        }
+        let msg = make_message!(Payload::Ready, ReadyMessage {});
+        let Some(Payload::Encrypted(sealed)) = responder_send.seal(msg.clone()).unwrap().payload else { panic!() };
+        // Replayed or reordered messages fail to decrypt
+        assert_eq!(initiator_recv.open(sealed.clone()).unwrap(), msg);
+        assert!(initiator_recv.open(sealed).is_err());
+    }
+
+    #[test]
+    fn test_encrypted_transport_tampering() {
+        let (mut initiator, mut responder) = handshake(&EncryptionKeypair::generate(), &EncryptionKeypair::generate());
+        let _ = responder.static_key_message().unwrap();
+        let mut initiator_message = initiator.static_key_message().unwrap();
+        initiator_message[0] ^= 1;
+        assert!(responder.finalize(&initiator_message).is_err());
+
+        // A message encrypted for another handshake is rejected
+        let (mut other, _) = handshake(&EncryptionKeypair::generate(), &EncryptionKeypair::generate());
+        let (_, responder) = handshake(&EncryptionKeypair::generate(), &EncryptionKeypair::generate());
+        assert!(responder.finalize(&other.static_key_message().unwrap()).is_err());
+
+        // A version message altered on the way, here downgrading the protocol version, fails the handshake
+        let initiator = EncryptionHandshake::new(&EncryptionKeypair::generate(), true);
+        let responder = EncryptionHandshake::new(&EncryptionKeypair::generate(), false);
+        let (initiator_version, responder_version) = (version(&initiator), version(&responder));
+        let altered_version = VersionMessage { protocol_version: 5, ..initiator_version.clone() };
+        let mut initiator = initiator.exchange(&initiator_version, &responder_version).unwrap();
+        let mut responder = responder.exchange(&responder_version, &altered_version).unwrap();
+        let (initiator_message, responder_message) =
+            (initiator.static_key_message().unwrap(), responder.static_key_message().unwrap());
+        assert!(responder.finalize(&initiator_message).is_err());
+        assert!(initiator.finalize(&responder_message).is_err());
+
+        // The local version message must advertise the ephemeral key of the handshake
+        let handshake = EncryptionHandshake::new(&EncryptionKeypair::generate(), true);
+        assert!(handshake.exchange(&responder_version, &responder_version).is_err());
+    }
+
+    #[test]
+    fn test_pinned_peer_keys() {
+        let key = EncryptionKeypair::generate().public_key();
+        let pin: PinnedPeerKey = format!("{key}@127.0.0.1:13111").parse().unwrap();
+        assert_eq!(pin.key, key);
+        assert_eq!(pin.to_string().parse::<PinnedPeerKey>().unwrap(), pin);
+        assert!("127.0.0.1:13111".parse::<PinnedPeerKey>().is_err());
+        assert!(format!("{key}@not-an-address").parse::<PinnedPeerKey>().is_err());
+
+        let address: SocketAddr = "127.0.0.1:13111".parse().unwrap();
+        let other = EncryptionKeypair::generate().public_key();
+        let config = EncryptionConfig::new(EncryptionKeypair::generate(), HashMap::from([(address, key)]));
+        assert!(config.verify_peer_key(address, Some(key)).is_ok());
+        assert!(config.verify_peer_key(address, Some(other)).is_err());
+        assert!(config.verify_peer_key(address, None).is_err());
+        assert!(config.verify_peer_key("127.0.0.1:13112".parse().unwrap(), None).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
-use crate::pb::{kaspad_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
+use crate::encryption::{EncryptionHandshake, EncryptionPublicKey};
+use crate::pb::{kaspad_message::Payload, EncryptionHandshakeMessage, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
 use crate::{IncomingRoute, KaspadMessagePayloadType, Router};
 use kaspa_core::debug;
@@ -11,6 +12,7 @@ pub struct KaspadHandshake<'a> {
     version_receiver: IncomingRoute,
     verack_receiver: IncomingRoute,
     ready_receiver: IncomingRoute,
+    encryption_receiver: IncomingRoute,
 }
 
 impl<'a> KaspadHandshake<'a> {
@@ -21,6 +23,7 @@ impl<'a> KaspadHandshake<'a> {
             version_receiver: router.subscribe(vec![KaspadMessagePayloadType::Version]),
             verack_receiver: router.subscribe(vec![KaspadMessagePayloadType::Verack]),
             ready_receiver: router.subscribe(vec![KaspadMessagePayloadType::Ready]),
+            encryption_receiver: router.subscribe(vec![KaspadMessagePayloadType::EncryptionHandshake]),
         }
     }
 
@@ -53,6 +56,29 @@ impl<'a> KaspadHandshake<'a> {
         Ok(())
     }
 
+    /// Switches the connection to an encrypted transport by exchanging static keys under the ephemeral key advertised
+    /// by the peer in its version message. Should only be called after the version exchange if both sides advertised
+    /// an ephemeral key, with the exchanged version messages. Returns the static key of the peer.
+    pub async fn establish_encryption(
+        &mut self,
+        encryption_handshake: EncryptionHandshake,
+        self_version_message: &VersionMessage,
+        peer_version_message: &VersionMessage,
+    ) -> Result<EncryptionPublicKey, ProtocolError> {
+        debug!("starting encryption flow");
+
+        let mut key_exchange = encryption_handshake.exchange(self_version_message, peer_version_message)?;
+        let ciphertext = key_exchange.static_key_message()?;
+        self.router.enqueue(make_message!(Payload::EncryptionHandshake, EncryptionHandshakeMessage { ciphertext })).await?;
+
+        let msg = dequeue_with_timeout!(self.encryption_receiver, Payload::EncryptionHandshake, Duration::from_secs(4))?;
+        let (remote_static_key, send_cipher, recv_cipher) = key_exchange.finalize(&msg.ciphertext)?;
+        self.router.enable_encryption(send_cipher, recv_cipher);
+        debug!("established encrypted transport with peer static key {remote_static_key}");
+
+        Ok(remote_static_key)
+    }
+
     /// Exchange `Ready` messages with the peer. This is the final step of the handshake protocol and should
     /// only be called after all flows corresponding to the version exchange info are registered.
     pub async fn exchange_ready_messages(&mut self) -> Result<(), ProtocolError> {
@@ -12,13 +12,15 @@ use toml::from_str;
 #[cfg(feature = "devnet-prealloc")]
 use kaspa_addresses::Address;
 use kaspa_consensus_core::{
-    config::Config,
+    config::{params::ForkActivation, Config, DEFAULT_BAN_DURATION},
     network::{NetworkId, NetworkType},
 };
 #[cfg(feature = "devnet-prealloc")]
 use kaspa_consensus_core::tx::{TransactionOutpoint, UtxoEntry};
 use kaspa_core::kaspad_env::version;
+use kaspa_mining::TemplateSelectionMode;
 use kaspa_notify::address::tracker::Tracker;
+use kaspa_stratum::config::DEFAULT_STRATUM_DIFFICULTY;
 #[cfg(feature = "devnet-prealloc")]
 use kaspa_txscript::pay_to_address_script;
 use kaspa_utils::networking::ContextualNetAddress;
@@ -39,8 +41,18 @@ pub struct Args {
     pub rpclisten_borsh: Option<WrpcNetAddress>,
     #[serde_as(as = "Option<DisplayFromStr>")]
     pub rpclisten_json: Option<WrpcNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub prometheuslisten: Option<ContextualNetAddress>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stratumlisten: Option<ContextualNetAddress>,
     #[serde(rename = "unsaferpc")]
     pub unsafe_rpc: bool,
+    #[serde(rename = "rpccert")]
+    pub rpc_tls_cert: Option<String>,
+    #[serde(rename = "rpckey")]
+    pub rpc_tls_key: Option<String>,
+    #[serde(rename = "rpcapikeyfile")]
+    pub rpc_api_key_file: Option<String>,
     pub wrpc_verbose: bool,
     #[serde(rename = "loglevel")]
     pub log_level: String,
@@ -56,7 +68,11 @@ pub struct Args {
     #[serde(rename = "uacomment")]
     pub user_agent_comments: Vec<String>,
     pub utxoindex: bool,
+    pub txindex: bool,
+    pub addresshistory: bool,
     pub reset_db: bool,
+    pub export_snapshot: Option<String>,
+    pub bootstrap_from: Option<String>,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
     #[serde(rename = "maxinpeers")]
@@ -72,6 +88,7 @@ pub struct Args {
     pub devnet: bool,
     pub simnet: bool,
     pub archival: bool,
+    pub retention_period_days: Option<f64>,
     pub sanity: bool,
     pub yes: bool,
     #[serde_as(as = "Option<DisplayFromStr>")]
@@ -88,11 +105,21 @@ pub struct Args {
     pub prealloc_amount: u64,
 
     pub disable_upnp: bool,
+    #[serde(rename = "banduration")]
+    pub ban_duration: u64,
     #[serde(rename = "nodnsseed")]
     pub disable_dns_seeding: bool,
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
     pub ram_scale: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub template_selection: TemplateSelectionMode,
+    pub disable_mempool_persistence: bool,
+    pub stratum_difficulty: f64,
+    pub hf_relaunch_daa_score: Option<u64>,
+    pub p2p_encryption: bool,
+    #[serde(rename = "pinpeerkey")]
+    pub pinned_peer_keys: Vec<String>,
 }
 
 impl Default for Args {
@@ -103,9 +130,16 @@ impl Default for Args {
             rpclisten_borsh: None,
             rpclisten_json: None,
             unsafe_rpc: false,
+            rpc_tls_cert: None,
+            rpc_tls_key: None,
+            rpc_api_key_file: None,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            txindex: false,
+            addresshistory: false,
             reset_db: false,
+            export_snapshot: None,
+            bootstrap_from: None,
             outbound_target: 8,
             inbound_limit: 128,
             rpc_max_clients: 128,
@@ -117,9 +151,12 @@ impl Default for Args {
             devnet: false,
             simnet: false,
             archival: false,
+            retention_period_days: None,
             sanity: false,
             logdir: None,
             rpclisten: None,
+            prometheuslisten: None,
+            stratumlisten: None,
             wrpc_verbose: false,
             log_level: "INFO".into(),
             connect_peers: vec![],
@@ -140,9 +177,16 @@ impl Default for Args {
             prealloc_amount: 1_000_000,
 
             disable_upnp: false,
+            ban_duration: DEFAULT_BAN_DURATION,
             disable_dns_seeding: false,
             disable_grpc: false,
             ram_scale: 1.0,
+            template_selection: TemplateSelectionMode::default(),
+            disable_mempool_persistence: false,
+            stratum_difficulty: DEFAULT_STRATUM_DIFFICULTY,
+            hf_relaunch_daa_score: None,
+            p2p_encryption: false,
+            pinned_peer_keys: vec![],
         }
     }
 }
@@ -150,11 +194,15 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.txindex = self.txindex;
+        config.addresshistory = self.addresshistory;
         config.disable_upnp = self.disable_upnp;
+        config.ban_duration = self.ban_duration;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
         config.enable_mainnet_mining = self.enable_mainnet_mining;
         config.is_archival = self.archival;
+        config.retention_period_days = self.retention_period_days;
         // TODO: change to `config.enable_sanity_checks = self.sanity` when we reach stable versions
         config.enable_sanity_checks = true;
         config.user_agent_comments.clone_from(&self.user_agent_comments);
@@ -162,6 +210,9 @@ impl Args {
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
+        if let Some(hf_relaunch_daa_score) = self.hf_relaunch_daa_score {
+            config.params.hf_relaunch_activation = ForkActivation::new(hf_relaunch_daa_score);
+        }
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -253,7 +304,48 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(WrpcNetAddress))
                 .help("Interface:port to listen for wRPC JSON connections (default port: 18110, testnet: 18210)."),
         )
+        .arg(
+            Arg::new("prometheuslisten")
+                .long("prometheuslisten")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to expose the node metrics in Prometheus format under /metrics (default port: 13120)."),
+        )
+        .arg(
+            Arg::new("stratumlisten")
+                .long("stratumlisten")
+                .value_name("IP[:PORT]")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(ContextualNetAddress))
+                .help("Interface:port to serve Stratum mining jobs on (default port: 5555)."),
+        )
         .arg(arg!(--unsaferpc "Enable RPC commands which affect the state of the node"))
+        .arg(
+            Arg::new("rpccert")
+                .long("rpccert")
+                .value_name("rpccert")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("File containing the PEM certificate chain used to serve TLS on the gRPC and wRPC listeners (requires --rpckey)."),
+        )
+        .arg(
+            Arg::new("rpckey")
+                .long("rpckey")
+                .value_name("rpckey")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("File containing the PEM private key of the RPC TLS certificate (requires --rpccert)."),
+        )
+        .arg(
+            Arg::new("rpcapikeyfile")
+                .long("rpcapikeyfile")
+                .value_name("rpcapikeyfile")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Require RPC clients to authenticate with one of the API keys listed in this file, one KEY:GRANTS entry per line \
+                granting a comma separated list of access groups (read, submit, mining, admin, all) or method names (e.g. getBlockTemplate)."),
+        )
         .arg(
             Arg::new("connect-peers")
                 .long("connect")
@@ -305,6 +397,22 @@ pub fn cli() -> Command {
                 .help("Max number of RPC clients for standard connections (default: 128)."),
         )
         .arg(arg!(--"reset-db" "Reset database before starting node. It's needed when switching between subnetworks."))
+        .arg(
+            Arg::new("export-snapshot")
+                .long("export-snapshot")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Export the pruning point UTXO set, pruning proof and trusted data to a snapshot file and exit."),
+        )
+        .arg(
+            Arg::new("bootstrap-from")
+                .long("bootstrap-from")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Bootstrap a fresh node database from a snapshot file before syncing the remainder from peers."),
+        )
         .arg(arg!(--"enable-unsynced-mining" "Allow the node to accept blocks from RPC while not synced (this flag is mainly used for testing)"))
         .arg(
             Arg::new("enable-mainnet-mining")
@@ -314,6 +422,8 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index"))
+        .arg(arg!(--txindex "Enable the transaction index"))
+        .arg(arg!(--addresshistory "Enable the address transaction history index"))
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -335,6 +445,14 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--devnet "Use the development test network"))
         .arg(arg!(--simnet "Use the simulation test network"))
         .arg(arg!(--archival "Run as an archival node: avoids deleting old block data when moving the pruning point (Warning: heavy disk usage)"))
+        .arg(
+            Arg::new("retention-period-days")
+                .long("retention-period-days")
+                .value_name("DAYS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Keep block bodies and acceptance data for the given number of days beyond the pruning point (cannot be used with --archival)."),
+        )
         .arg(arg!(--sanity "Enable various sanity checks which might be compute-intensive (mostly performed during pruning)"))
         .arg(arg!(--yes "Answer yes to all interactive console questions"))
         .arg(
@@ -362,6 +480,14 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Interval in seconds for performance metrics collection."),
         )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
+        .arg(
+            Arg::new("banduration")
+                .long("banduration")
+                .value_name("SECONDS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Default duration in seconds of peer bans, 0 for permanent bans (default: {DEFAULT_BAN_DURATION})")),
+        )
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
         .arg(
@@ -372,6 +498,42 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .help("Apply a scale factor to memory allocation bounds. Nodes with limited RAM (~4-8GB) should set this to ~0.3-0.5 respectively. Nodes with 
 a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance especially for syncing peers faster"),
         )
+        .arg(
+            Arg::new("template-selection")
+                .long("template-selection")
+                .value_name("MODE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(TemplateSelectionMode))
+                .help("Block template transaction selection: 'probabilistic' samples ready transactions by feerate, 'package-feerate' greedily selects by the feerate of transactions along with their mempool descendants (default: probabilistic)."),
+        )
+        .arg(arg!(--"disable-mempool-persistence" "Do not save the mempool on shutdown nor reload it on startup"))
+        .arg(
+            Arg::new("stratum-difficulty")
+                .long("stratum-difficulty")
+                .value_name("DIFFICULTY")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(f64))
+                .help("Difficulty of the shares expected from the Stratum workers (default: 4096)."),
+        )
+        .arg(
+            Arg::new("hf-relaunch-daa-score")
+                .long("hf-relaunch-daa-score")
+                .value_name("DAA_SCORE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("DAA score from which the relaunch hardfork rules apply (allowed only on testnet, devnet and simnet)."),
+        )
+        .arg(arg!(--"p2p-encryption" "Encrypt P2P connections with peers supporting it, falling back to plaintext otherwise"))
+        .arg(
+            Arg::new("pinpeerkey")
+                .long("pinpeerkey")
+                .value_name("KEY@IP[:PORT]")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Require the peer at the given address to use an encrypted transport with the given static key \
+                (implies --p2p-encryption). Can be repeated."),
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -419,7 +581,12 @@ impl Args {
             rpclisten: m.get_one::<ContextualNetAddress>("rpclisten").cloned().or(defaults.rpclisten),
             rpclisten_borsh: m.get_one::<WrpcNetAddress>("rpclisten-borsh").cloned().or(defaults.rpclisten_borsh),
             rpclisten_json: m.get_one::<WrpcNetAddress>("rpclisten-json").cloned().or(defaults.rpclisten_json),
+            prometheuslisten: m.get_one::<ContextualNetAddress>("prometheuslisten").cloned().or(defaults.prometheuslisten),
+            stratumlisten: m.get_one::<ContextualNetAddress>("stratumlisten").cloned().or(defaults.stratumlisten),
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
+            rpc_tls_cert: m.get_one::<String>("rpccert").cloned().or(defaults.rpc_tls_cert),
+            rpc_tls_key: m.get_one::<String>("rpckey").cloned().or(defaults.rpc_tls_key),
+            rpc_api_key_file: m.get_one::<String>("rpcapikeyfile").cloned().or(defaults.rpc_api_key_file),
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
@@ -431,14 +598,19 @@ impl Args {
             rpc_max_clients: arg_match_unwrap_or::<usize>(&m, "rpcmaxclients", defaults.rpc_max_clients),
             max_tracked_addresses: arg_match_unwrap_or::<usize>(&m, "max-tracked-addresses", defaults.max_tracked_addresses),
             reset_db: arg_match_unwrap_or::<bool>(&m, "reset-db", defaults.reset_db),
+            export_snapshot: m.get_one::<String>("export-snapshot").cloned().or(defaults.export_snapshot),
+            bootstrap_from: m.get_one::<String>("bootstrap-from").cloned().or(defaults.bootstrap_from),
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            txindex: arg_match_unwrap_or::<bool>(&m, "txindex", defaults.txindex),
+            addresshistory: arg_match_unwrap_or::<bool>(&m, "addresshistory", defaults.addresshistory),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
             simnet: arg_match_unwrap_or::<bool>(&m, "simnet", defaults.simnet),
             archival: arg_match_unwrap_or::<bool>(&m, "archival", defaults.archival),
+            retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
             sanity: arg_match_unwrap_or::<bool>(&m, "sanity", defaults.sanity),
             yes: arg_match_unwrap_or::<bool>(&m, "yes", defaults.yes),
             user_agent_comments: arg_match_many_unwrap_or::<String>(&m, "user_agent_comments", defaults.user_agent_comments),
@@ -448,9 +620,20 @@ impl Args {
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
+            ban_duration: arg_match_unwrap_or::<u64>(&m, "banduration", defaults.ban_duration),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
+            template_selection: arg_match_unwrap_or::<TemplateSelectionMode>(&m, "template-selection", defaults.template_selection),
+            disable_mempool_persistence: arg_match_unwrap_or::<bool>(
+                &m,
+                "disable-mempool-persistence",
+                defaults.disable_mempool_persistence,
+            ),
+            stratum_difficulty: arg_match_unwrap_or::<f64>(&m, "stratum-difficulty", defaults.stratum_difficulty),
+            hf_relaunch_daa_score: m.get_one::<u64>("hf-relaunch-daa-score").cloned().or(defaults.hf_relaunch_daa_score),
+            p2p_encryption: arg_match_unwrap_or::<bool>(&m, "p2p-encryption", defaults.p2p_encryption),
+            pinned_peer_keys: arg_match_many_unwrap_or::<String>(&m, "pinpeerkey", defaults.pinned_peer_keys),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
@@ -489,6 +672,9 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --logdir=                             Directory to log output.
   -a, --addpeer=                            Add a peer to connect with at startup
       --connect=                            Connect only to the specified peers at startup
+      --p2p-encryption                      Encrypt P2P connections with peers supporting it
+      --pinpeerkey=                         Require the peer at the given address to use an encrypted transport with
+                                            the given static key (eg. <key>@127.0.0.1:13111)
       --nolisten                            Disable listening for incoming connections -- NOTE: Listening is
                                             automatically disabled if the --connect or --proxy options are used
                                             without also specifying listen interfaces via --listen
@@ -510,6 +696,8 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
       --rpckey=                             File containing the certificate key (default:
                                             /Users/aspect/Library/Application Support/Kaspad/rpc.key)
       --rpcmaxclients=                      Max number of RPC clients for standard connections (default: 128)
+      --rpcapikeyfile=                      Require RPC clients to authenticate with one of the API keys listed in
+                                            this file, one per line (eg. miner:read,mining)
       --rpcmaxwebsockets=                   Max number of RPC websocket connections (default: 25)
       --rpcmaxconcurrentreqs=               Max number of concurrent RPC requests that may be processed concurrently
                                             (default: 20)
@@ -547,11 +735,19 @@ fn arg_match_many_unwrap_or<T: Clone + Send + Sync + 'static>(m: &clap::ArgMatch
                                             the active network.
       --reset-db                            Reset database before starting node. It's needed when switching between
                                             subnetworks.
+      --export-snapshot=                    Export the pruning point UTXO set, pruning proof and trusted data to a
+                                            snapshot file and exit
+      --bootstrap-from=                     Bootstrap a fresh node database from a snapshot file before syncing the
+                                            remainder from peers
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --txindex                             Enable the transaction index
+      --addresshistory                      Enable the address transaction history index
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
+      --retention-period-days=              Keep block bodies and acceptance data for the given number of days
+                                            beyond the pruning point (cannot be used with --archival)
       --protocol-version=                   Use non default p2p protocol version (default: 5)
       --enable-unsynced-mining              Allow the node to accept blocks from RPC while not synced
                                             (required when initiating a new network from genesis)
@@ -1,8 +1,9 @@
-use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
+use std::{fs, path::{Path, PathBuf}, process::exit, sync::Arc, time::Duration};
 
 use async_channel::unbounded;
 use serde::{Deserialize, Serialize};
 
+use kaspa_addresshistory::{api::AddressHistoryProxy, AddressHistory};
 use kaspa_addressmanager::AddressManager;
 use kaspa_consensus::{consensus::factory::Factory as ConsensusFactory, pipeline::ProcessingCounters};
 use kaspa_consensus::{
@@ -16,21 +17,31 @@ use kaspa_consensus_core::config::Config;
 use kaspa_consensus_core::utxo::utxo_collection::UtxoCollection;
 use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
 use kaspa_consensusmanager::ConsensusManager;
-use kaspa_core::{core::Core, error, info, trace};
+use kaspa_core::{core::Core, error, info, trace, warn};
 use kaspa_core::{kaspad_env::version, task::tick::TickService};
 use kaspa_core::task::runtime::AsyncRuntime;
 use kaspa_database::prelude::CachePolicy;
 use kaspa_grpc_server::service::GrpcService;
 use kaspa_index_processor::service::IndexService;
+use kaspa_metrics_core::prometheus::PrometheusCollector;
 use kaspa_mining::{
     manager::{MiningManager, MiningManagerProxy},
     MiningCounters,
     monitor::MiningMonitor,
+    persistence::{MempoolPersistence, MEMPOOL_FILE_NAME},
 };
 use kaspa_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
 use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
+use kaspa_p2p_lib::encryption::{EncryptionConfig, EncryptionKeypair, PinnedPeerKey};
 use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
-use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_rpc_service::{
+    access::{RpcAccessPolicy, RpcApiKey},
+    prometheus::{PrometheusService, DEFAULT_PROMETHEUS_PORT},
+    service::RpcCoreService,
+    tls::RpcTlsIdentity,
+};
+use kaspa_stratum::{config::StratumConfig, service::StratumService, DEFAULT_STRATUM_PORT};
+use kaspa_txindex::{api::TxIndexProxy, TxIndex};
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
@@ -38,6 +49,7 @@ use kaspa_utxoindex::{api::UtxoIndexProxy, UtxoIndex};
 use kaspa_wrpc_server::service::{Options as WrpcServerOptions, WebSocketCounters as WrpcServerCounters, WrpcEncoding, WrpcService};
 
 use crate::args::Args;
+use crate::snapshot::{export_snapshot, import_snapshot};
 
 /// Desired soft FD limit that needs to be configured
 /// for the kaspad process.
@@ -51,9 +63,12 @@ pub const MINIMUM_DAEMON_SOFT_FD_LIMIT: u64 = 4 * 1024;
 const DEFAULT_DATA_DIR: &str = "datadir";
 const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
+const TXINDEX_DB: &str = "txindex";
+const ADDRESSHISTORY_DB: &str = "addresshistory";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
+const P2P_KEY_FILE: &str = "p2p.key";
 
 fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -97,6 +112,26 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.hf_relaunch_daa_score.is_some() && args.network().is_mainnet() {
+        return Err(ConfigError::ForkActivationOverrideOnMainnet);
+    }
+    if args.export_snapshot.is_some() && args.bootstrap_from.is_some() {
+        return Err(ConfigError::MixedExportSnapshotAndBootstrap);
+    }
+    if let Some(retention_period_days) = args.retention_period_days {
+        if args.archival {
+            return Err(ConfigError::MixedArchivalAndRetentionPeriod);
+        }
+        if !(retention_period_days > 0.0 && retention_period_days.is_finite()) {
+            return Err(ConfigError::InvalidRetentionPeriod);
+        }
+    }
+    if args.rpc_tls_cert.is_some() != args.rpc_tls_key.is_some() {
+        return Err(ConfigError::MixedRpcCertAndKey);
+    }
+    for pinned_peer_key in args.pinned_peer_keys.iter() {
+        pinned_peer_key.parse::<PinnedPeerKey>().map_err(ConfigError::InvalidPinnedPeerKey)?;
+    }
     Ok(())
 }
 
@@ -210,6 +245,20 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     } else {
         0
     };
+    let tx_files_limit = if args.txindex {
+        let tx_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= tx_files_limit;
+        tx_files_limit
+    } else {
+        0
+    };
+    let address_history_files_limit = if args.addresshistory {
+        let address_history_files_limit = fd_remaining * 10 / 100;
+        fd_remaining -= address_history_files_limit;
+        address_history_files_limit
+    } else {
+        0
+    };
     // Make sure args forms a valid set of properties
     if let Err(err) = validate_args(args) {
         println!("{}", err);
@@ -245,6 +294,8 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
 
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
+    let txindex_db_dir = db_dir.join(TXINDEX_DB);
+    let addresshistory_db_dir = db_dir.join(ADDRESSHISTORY_DB);
     let meta_db_dir = db_dir.join(META_DB);
 
     let mut is_db_reset_needed = args.reset_db;
@@ -264,6 +315,14 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
         info!("Utxoindex Data directory {}", utxoindex_db_dir.display());
         fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
     }
+    if args.txindex {
+        info!("Txindex Data directory {}", txindex_db_dir.display());
+        fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+    }
+    if args.addresshistory {
+        info!("Address history Data directory {}", addresshistory_db_dir.display());
+        fs::create_dir_all(addresshistory_db_dir.as_path()).unwrap();
+    }
 
     // DB used for addresses store and for multi-consensus management
     let mut meta_db = kaspa_database::prelude::ConnBuilder::default()
@@ -336,6 +395,14 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
             fs::create_dir_all(utxoindex_db_dir.as_path()).unwrap();
         }
 
+        if args.txindex {
+            fs::create_dir_all(txindex_db_dir.as_path()).unwrap();
+        }
+
+        if args.addresshistory {
+            fs::create_dir_all(addresshistory_db_dir.as_path()).unwrap();
+        }
+
         // Reopen the DB
         meta_db = kaspa_database::prelude::ConnBuilder::default()
             .with_db_path(meta_db_dir)
@@ -357,6 +424,23 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
 
     let grpc_server_addr = args.rpclisten.unwrap_or(ContextualNetAddress::loopback()).normalize(config.default_rpc_port());
 
+    // Pinned peer keys imply encryption. Validated by validate_args
+    let encryption_config = (args.p2p_encryption || !args.pinned_peer_keys.is_empty()).then(|| {
+        let key_path = app_dir.join(network.to_prefixed()).join(P2P_KEY_FILE);
+        let keypair = EncryptionKeypair::load_or_generate(&key_path).unwrap_or_else(|err| {
+            println!("Failed loading the P2P key {}: {err}", key_path.display());
+            exit(1);
+        });
+        let pinned_keys = args
+            .pinned_peer_keys
+            .iter()
+            .map(|pin| pin.parse::<PinnedPeerKey>().unwrap())
+            .map(|pin| (pin.address.normalize(config.default_p2p_port()).into(), pin.key))
+            .collect();
+        info!("P2P encryption is enabled, node static key: {}", keypair.public_key());
+        Arc::new(EncryptionConfig::new(keypair, pinned_keys))
+    });
+
     let core = Arc::new(Core::new());
 
     // ---
@@ -386,7 +470,38 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
         tx_script_cache_counters.clone(),
         fd_remaining,
     ));
+
+    if let Some(bootstrap_from) = args.bootstrap_from.as_ref() {
+        if MultiConsensusManagementStore::new(meta_db.clone()).active_consensus_dir_name().unwrap().is_some() {
+            warn!("--bootstrap-from is ignored since the node database already holds a consensus (use --reset-db in order to bootstrap it again)");
+        } else {
+            info!("Bootstrapping consensus from snapshot {}", bootstrap_from);
+            match import_snapshot(consensus_factory.as_ref(), &config, Path::new(bootstrap_from)) {
+                Ok(pruning_point) => info!("Consensus was bootstrapped from the snapshot of pruning point {}", pruning_point),
+                Err(err) => {
+                    println!("Failed to bootstrap from snapshot {}: {}", bootstrap_from, err);
+                    exit(1);
+                }
+            }
+        }
+    }
+
     let consensus_manager = Arc::new(ConsensusManager::new(consensus_factory));
+
+    if let Some(snapshot_path) = args.export_snapshot.as_ref() {
+        info!("Exporting consensus snapshot to {}", snapshot_path);
+        let session = consensus_manager.consensus().unguarded_session_blocking();
+        match export_snapshot(&*session, &config, Path::new(snapshot_path)) {
+            Ok(pruning_point) => {
+                println!("Exported the snapshot of pruning point {} to {}", pruning_point, snapshot_path);
+                exit(0);
+            }
+            Err(err) => {
+                println!("Failed to export snapshot to {}: {}", snapshot_path, err);
+                exit(1);
+            }
+        }
+    }
     let consensus_monitor = Arc::new(ConsensusMonitor::new(processing_counters.clone(), tick_service.clone()));
 
     let perf_monitor_builder = PerfMonitorBuilder::new()
@@ -405,15 +520,36 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
     };
 
     let notify_service = Arc::new(NotifyService::new(notification_root.clone(), notification_recv, subscription_context.clone()));
-    let index_service: Option<Arc<IndexService>> = if args.utxoindex {
+    let index_service: Option<Arc<IndexService>> = if args.utxoindex || args.txindex || args.addresshistory {
         // Use only a single thread for none-consensus databases
-        let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
-            .with_db_path(utxoindex_db_dir)
-            .with_files_limit(utxo_files_limit)
-            .build()
-            .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
-        let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
+        let utxoindex = args.utxoindex.then(|| {
+            let utxoindex_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(utxoindex_db_dir)
+                .with_files_limit(utxo_files_limit)
+                .build()
+                .unwrap();
+            UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap())
+        });
+        let txindex = args.txindex.then(|| {
+            let txindex_db =
+                kaspa_database::prelude::ConnBuilder::default().with_db_path(txindex_db_dir).with_files_limit(tx_files_limit).build().unwrap();
+            TxIndexProxy::new(TxIndex::new(consensus_manager.clone(), txindex_db).unwrap())
+        });
+        let addresshistory = args.addresshistory.then(|| {
+            let addresshistory_db = kaspa_database::prelude::ConnBuilder::default()
+                .with_db_path(addresshistory_db_dir)
+                .with_files_limit(address_history_files_limit)
+                .build()
+                .unwrap();
+            AddressHistoryProxy::new(AddressHistory::new(consensus_manager.clone(), addresshistory_db).unwrap())
+        });
+        let index_service = Arc::new(IndexService::new(
+            &notify_service.notifier(),
+            subscription_context.clone(),
+            utxoindex,
+            txindex,
+            addresshistory,
+        ));
 
         Some(index_service)
     } else {
@@ -428,8 +564,10 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
         false,
         config.max_block_mass,
         config.ram_scale,
+        args.template_selection,
+        (!args.disable_mempool_persistence).then(|| db_dir.join(MEMPOOL_FILE_NAME)),
         config.block_template_cache_lifetime,
-        mining_counters,
+        mining_counters.clone(),
     )));
 
     let flow_context = Arc::new(FlowContext::new(
@@ -439,6 +577,7 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
         mining_manager.clone(),
         tick_service.clone(),
         notification_root,
+        encryption_config,
     ));
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
@@ -452,14 +591,34 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
         p2p_tower_counters.clone(),
     ));
 
+    // Validated by validate_args
+    let rpc_api_keys = args.rpc_api_key_file.as_ref().map_or_else(Vec::new, |path| {
+        RpcApiKey::from_file(path).unwrap_or_else(|err| {
+            println!("Failed reading the RPC API key file: {err}");
+            exit(1);
+        })
+    });
+    let rpc_access_policy = Arc::new(RpcAccessPolicy::new(rpc_api_keys));
+    if rpc_access_policy.is_enabled() {
+        info!("RPC clients are required to authenticate with an API key");
+    }
+    let rpc_tls_identity = args.rpc_tls_cert.as_ref().zip(args.rpc_tls_key.as_ref()).map(|(cert, key)| {
+        RpcTlsIdentity::from_files(cert, key).unwrap_or_else(|err| {
+            println!("Failed reading the RPC TLS certificate or key: {err}");
+            exit(1);
+        })
+    });
+
     let rpc_core_service = Arc::new(RpcCoreService::new(
         consensus_manager.clone(),
         notify_service.notifier(),
         index_service.as_ref().map(|x| x.notifier()),
-        mining_manager,
+        mining_manager.clone(),
         flow_context,
         subscription_context,
-        index_service.as_ref().map(|x| x.utxoindex().unwrap()),
+        index_service.as_ref().and_then(|x| x.utxoindex()),
+        index_service.as_ref().and_then(|x| x.txindex()),
+        index_service.as_ref().and_then(|x| x.addresshistory()),
         config.clone(),
         core.clone(),
         processing_counters,
@@ -468,7 +627,16 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
         perf_monitor.clone(),
         p2p_tower_counters.clone(),
         grpc_tower_counters.clone(),
+        rpc_access_policy,
     ));
+    let stratum_service = args.stratumlisten.map(|listen_address| {
+        if !args.stratum_difficulty.is_finite() || args.stratum_difficulty <= 0.0 {
+            println!("The Stratum share difficulty must be a positive number");
+            exit(1);
+        }
+        let stratum_config = StratumConfig::new(args.stratum_difficulty, config.prefix(), config.hf_relaunch_activation);
+        Arc::new(StratumService::new(listen_address.normalize(DEFAULT_STRATUM_PORT), stratum_config, rpc_core_service.clone()))
+    });
     let grpc_service_broadcasters: usize = 3; // TODO: add a command line argument or derive from other arg/config/host-related fields
     let grpc_service = if !args.disable_grpc {
         Some(Arc::new(GrpcService::new(
@@ -478,10 +646,21 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
             args.rpc_max_clients,
             grpc_service_broadcasters,
             grpc_tower_counters,
+            rpc_tls_identity.clone(),
         )))
     } else {
         None
     };
+    let prometheus_service = args.prometheuslisten.map(|listen_address| {
+        let collectors =
+            stratum_service.iter().map(|stratum_service| stratum_service.stats() as Arc<dyn PrometheusCollector>).collect();
+        Arc::new(PrometheusService::new(
+            listen_address.normalize(DEFAULT_PROMETHEUS_PORT),
+            rpc_core_service.clone(),
+            mining_counters,
+            collectors,
+        ))
+    });
 
     // Create an async runtime and register the top-level async services
     let async_runtime = Arc::new(AsyncRuntime::new(args.async_threads));
@@ -497,9 +676,18 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
     if let Some(grpc_service) = grpc_service {
         async_runtime.register(grpc_service)
     }
+    if let Some(prometheus_service) = prometheus_service {
+        async_runtime.register(prometheus_service)
+    }
+    if let Some(stratum_service) = stratum_service {
+        async_runtime.register(stratum_service)
+    }
     async_runtime.register(p2p_service);
     async_runtime.register(consensus_monitor);
     async_runtime.register(mining_monitor);
+    if !args.disable_mempool_persistence {
+        async_runtime.register(Arc::new(MempoolPersistence::new(mining_manager.clone(), consensus_manager.clone())));
+    }
     async_runtime.register(perf_monitor);
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
                                        // Register wRPC servers based on command line arguments
@@ -518,6 +706,7 @@ do you confirm? (answer y/n or pass --yes to the Pyrin command line to confirm a
                 WrpcServerOptions {
                     listen_address: listen_address.to_address(&network.network_type, &encoding).to_string(), // TODO: use a normalized ContextualNetAddress instead of a String
                     verbose: args.wrpc_verbose,
+                    tls_identity: rpc_tls_identity.clone(),
                     ..WrpcServerOptions::default()
                 },
             ))
@@ -0,0 +1,353 @@
+//!
+//! Offline snapshots of the pruning point state, allowing a new node to bootstrap its consensus from a file
+//! instead of downloading the pruning point proof, trusted data and UTXO set from peers.
+//!
+//! A snapshot holds the same data which is sent over P2P during IBD with headers proof. It is not trusted by
+//! the importing node, which runs the validation sequence of IBD: the pruning proof is validated, the trusted
+//! blocks and the headers above the pruning point go through the usual validation, the past pruning points
+//! must form a valid chain from the headers selected tip and the imported UTXO set must match the UTXO
+//! commitment of the pruning point header.
+//!
+
+use bincode::Options;
+use futures::future::try_join_all;
+use kaspa_consensus_core::{
+    api::ConsensusApi,
+    block::Block,
+    config::Config,
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList},
+    trusted::{ExternalGhostdagData, TrustedGhostdagData, TrustedHeader},
+    tx::{Transaction, TransactionOutpoint, UtxoEntry},
+};
+use kaspa_consensusmanager::ConsensusFactory;
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use kaspa_p2p_lib::convert::model::trusted::{TrustedDataEntry, TrustedDataPackage};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::Arc,
+};
+use thiserror::Error;
+
+const SNAPSHOT_MAGIC: [u8; 8] = *b"PYRSNAPS";
+const SNAPSHOT_VERSION: u32 = 2;
+const UTXO_CHUNK_SIZE: usize = 1000;
+const HEADERS_CHUNK_SIZE: usize = 1000;
+
+/// Size limit of the proof, pruning points and trusted data records
+const MAX_RECORD_SIZE: u64 = 256 * 1024 * 1024;
+/// Size limit of a single chunk of headers or UTXOs
+const MAX_CHUNK_RECORD_SIZE: u64 = 32 * 1024 * 1024;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot i/o error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("snapshot serialization error: {0}")]
+    Serialization(#[from] bincode::Error),
+
+    #[error("the file is not a snapshot")]
+    InvalidMagic,
+
+    #[error("unsupported snapshot version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("the snapshot was exported on {0} while the node is running on {1}")]
+    NetworkMismatch(String, String),
+
+    #[error("the snapshot is invalid: {0}")]
+    InvalidSnapshot(&'static str),
+
+    #[error("the snapshot holds a record of {0} bytes exceeding the limit of {1} bytes")]
+    RecordTooLarge(u64, u64),
+
+    #[error("the node has no pruning point to export yet")]
+    NoPruningPoint,
+
+    #[error(transparent)]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error(transparent)]
+    PruningImportError(#[from] PruningImportError),
+
+    #[error(transparent)]
+    RuleError(#[from] RuleError),
+}
+
+pub type SnapshotResult<T> = std::result::Result<T, SnapshotError>;
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotMetadata {
+    version: u32,
+    network: String,
+    pruning_point: Hash,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotTrustedData {
+    daa_window: Vec<(Header, ExternalGhostdagData)>,
+    ghostdag_window: Vec<(Hash, ExternalGhostdagData)>,
+    anticone: Vec<(Header, Vec<Transaction>)>,
+}
+
+/// Exports the pruning point UTXO set, pruning proof, trusted anticone data and the headers above the pruning point
+/// of `consensus` to a snapshot file, returning the pruning point the snapshot was taken at.
+pub fn export_snapshot(consensus: &dyn ConsensusApi, config: &Config, path: &Path) -> SnapshotResult<Hash> {
+    let pruning_point = consensus.pruning_point();
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::NoPruningPoint);
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    let network = config.params.net.to_string();
+    write_record(&mut writer, &SnapshotMetadata { version: SNAPSHOT_VERSION, network, pruning_point })?;
+
+    let proof = consensus.get_pruning_point_proof();
+    write_record(&mut writer, &proof.iter().map(|level| level.iter().map(|h| h.as_ref()).collect::<Vec<_>>()).collect::<Vec<_>>())?;
+    write_record(&mut writer, &consensus.pruning_point_headers().iter().map(|h| h.as_ref()).collect::<Vec<_>>())?;
+
+    let trusted_data = consensus.get_pruning_point_anticone_and_trusted_data()?;
+    let anticone = trusted_data
+        .anticone
+        .iter()
+        .map(|&hash| consensus.get_block(hash).map(|block| (block.header.as_ref().clone(), block.transactions.as_ref().clone())))
+        .collect::<Result<Vec<_>, _>>()?;
+    write_record(
+        &mut writer,
+        &SnapshotTrustedData {
+            daa_window: trusted_data.daa_window_blocks.iter().map(|th| (th.header.as_ref().clone(), th.ghostdag.clone())).collect(),
+            ghostdag_window: trusted_data.ghostdag_blocks.iter().map(|gd| (gd.hash, gd.ghostdag.clone())).collect(),
+            anticone,
+        },
+    )?;
+    info!("Snapshot: exported the pruning proof and {} trusted blocks", trusted_data.anticone.len());
+
+    // Header chunks from the pruning point up to the headers selected tip are written until an empty chunk marks
+    // their end. Like the headers synced during IBD, they allow the importing node to validate the pruning points
+    let headers_selected_tip = consensus.get_headers_selected_tip();
+    let max_blocks = HEADERS_CHUNK_SIZE.max(config.mergeset_size_limit as usize + 1);
+    let mut low = pruning_point;
+    let mut header_count = 0;
+    while low != headers_selected_tip {
+        let hashes = consensus.get_hashes_between(low, headers_selected_tip, max_blocks)?.0;
+        low = *hashes.last().expect("low and high are different");
+        let headers =
+            hashes.into_iter().map(|hash| consensus.get_header(hash).map(|h| h.as_ref().clone())).collect::<Result<Vec<_>, _>>()?;
+        header_count += headers.len();
+        write_record(&mut writer, &headers)?;
+    }
+    write_record(&mut writer, &Vec::<Header>::new())?;
+    info!("Snapshot: exported {} headers up to {}", header_count, headers_selected_tip);
+
+    // UTXO chunks are written until an empty chunk marks the end of the set
+    let mut from_outpoint = None;
+    let mut utxo_count = 0;
+    loop {
+        let chunk = consensus.get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some())?;
+        write_record(&mut writer, &chunk)?;
+        if chunk.is_empty() {
+            break;
+        }
+        utxo_count += chunk.len();
+        from_outpoint = Some(chunk.last().expect("not empty by prev condition").0);
+    }
+    writer.flush()?;
+    info!("Snapshot: exported {} UTXOs of pruning point {}", utxo_count, pruning_point);
+
+    Ok(pruning_point)
+}
+
+/// Imports a snapshot into a new staging consensus created by `factory`, committing it as the active
+/// consensus if the import succeeds. Returns the pruning point of the imported snapshot.
+///
+/// Note: this must be called before the consensus manager is created, since the staging consensus is
+/// started and stopped independently of it
+pub fn import_snapshot(factory: &dyn ConsensusFactory, config: &Config, path: &Path) -> SnapshotResult<Hash> {
+    // The current consensus of a node without a database is a new consensus holding genesis only
+    let (current, current_ctl) = factory.new_active_consensus();
+    let (staging, ctl) = factory.new_staging_consensus();
+    let handles = ctl.start();
+    let result = import_snapshot_into(&*current.unguarded_session_blocking(), &*staging.unguarded_session_blocking(), config, path);
+    ctl.stop();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    drop(staging);
+    drop(current);
+    drop(current_ctl);
+
+    match result {
+        Ok(pruning_point) => {
+            ctl.make_active();
+            drop(ctl);
+            factory.delete_inactive_consensus_entries();
+            Ok(pruning_point)
+        }
+        Err(err) => {
+            drop(ctl);
+            factory.delete_staging_entry();
+            Err(err)
+        }
+    }
+}
+
+/// Imports a snapshot into the empty `staging` consensus following the IBD sequence, where the pruning proof and
+/// pruning points are validated in the context of the `current` consensus. Returns the pruning point of the
+/// imported snapshot.
+pub fn import_snapshot_into(
+    current: &dyn ConsensusApi,
+    staging: &dyn ConsensusApi,
+    config: &Config,
+    path: &Path,
+) -> SnapshotResult<Hash> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != SNAPSHOT_MAGIC {
+        return Err(SnapshotError::InvalidMagic);
+    }
+    let metadata: SnapshotMetadata = read_record(&mut reader, MAX_RECORD_SIZE)?;
+    if metadata.version != SNAPSHOT_VERSION {
+        return Err(SnapshotError::UnsupportedVersion(metadata.version));
+    }
+    if metadata.network != config.params.net.to_string() {
+        return Err(SnapshotError::NetworkMismatch(metadata.network, config.params.net.to_string()));
+    }
+    import_snapshot_data(current, staging, config, metadata.pruning_point, &mut reader)?;
+    Ok(metadata.pruning_point)
+}
+
+fn import_snapshot_data(
+    current: &dyn ConsensusApi,
+    staging: &dyn ConsensusApi,
+    config: &Config,
+    pruning_point: Hash,
+    reader: &mut impl Read,
+) -> SnapshotResult<()> {
+    let proof: PruningPointProof = read_record::<Vec<Vec<Header>>>(reader, MAX_RECORD_SIZE)?
+        .into_iter()
+        .map(|level| level.into_iter().map(finalized_header).collect())
+        .collect();
+    let pruning_points: PruningPointsList =
+        read_record::<Vec<Header>>(reader, MAX_RECORD_SIZE)?.into_iter().map(finalized_header).collect();
+    let trusted_data: SnapshotTrustedData = read_record(reader, MAX_RECORD_SIZE)?;
+
+    if proof.first().and_then(|level| level.last()).map(|h| h.hash) != Some(pruning_point) {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is not the snapshot pruning point"));
+    }
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is the genesis block"));
+    }
+    if pruning_point == current.pruning_point() {
+        return Err(SnapshotError::InvalidSnapshot("the proof pruning point is the same as the current pruning point"));
+    }
+    if pruning_points.first().map(|h| h.hash) != Some(config.genesis.hash)
+        || pruning_points.last().map(|h| h.hash) != Some(pruning_point)
+    {
+        return Err(SnapshotError::InvalidSnapshot("the pruning points list must span from genesis to the snapshot pruning point"));
+    }
+    if trusted_data.anticone.first().map(|(header, _)| header.hash) != Some(pruning_point) {
+        return Err(SnapshotError::InvalidSnapshot("the first trusted block is not the snapshot pruning point"));
+    }
+
+    // As in IBD, the proof is validated and the past pruning points are checked for finality violations in the context
+    // of the current consensus
+    current.validate_pruning_proof(&proof)?;
+    info!("Snapshot: pruning proof for pruning point {} was validated", pruning_point);
+    if current.are_pruning_points_violating_finality(pruning_points.clone()) {
+        return Err(SnapshotError::InvalidSnapshot("the pruning points are violating finality"));
+    }
+
+    let package = TrustedDataPackage::new(
+        trusted_data.daa_window.into_iter().map(|(header, ghostdag)| TrustedHeader::new(finalized_header(header), ghostdag)).collect(),
+        trusted_data.ghostdag_window.into_iter().map(|(hash, ghostdag)| TrustedGhostdagData::new(hash, ghostdag)).collect(),
+    );
+    let entries = trusted_data
+        .anticone
+        .into_iter()
+        .map(|(header, mut transactions)| {
+            transactions.iter_mut().for_each(|tx| tx.finalize());
+            TrustedDataEntry::new(Block::from_arcs(finalized_header(header), Arc::new(transactions)), vec![], vec![])
+        })
+        .collect();
+    let trusted_set = package
+        .build_trusted_subdag(entries)
+        .map_err(|_| SnapshotError::InvalidSnapshot("missing ghostdag data for trusted blocks"))?;
+
+    staging.apply_pruning_proof(proof, &trusted_set)?;
+    staging.import_pruning_points(pruning_points);
+
+    info!("Snapshot: processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        futures::executor::block_on(staging.validate_and_insert_trusted_block(tb).virtual_state_task)?;
+    }
+
+    let mut header_count = 0;
+    loop {
+        let chunk: Vec<Header> = read_record(reader, MAX_CHUNK_RECORD_SIZE)?;
+        if chunk.is_empty() {
+            break;
+        }
+        header_count += chunk.len();
+        let jobs = chunk
+            .into_iter()
+            .map(|header| staging.validate_and_insert_block(Block::from_header_arc(finalized_header(header))).virtual_state_task);
+        futures::executor::block_on(try_join_all(jobs))?;
+    }
+    info!("Snapshot: processed {} headers above pruning point {}", header_count, pruning_point);
+
+    // The past pruning points must form a valid chain from the headers selected tip
+    staging.validate_pruning_points()?;
+
+    let mut multiset = MuHash::new();
+    let mut utxo_count = 0;
+    loop {
+        let chunk: Vec<(TransactionOutpoint, UtxoEntry)> = read_record(reader, MAX_CHUNK_RECORD_SIZE)?;
+        if chunk.is_empty() {
+            break;
+        }
+        utxo_count += chunk.len();
+        staging.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+    }
+    // The UTXO commitment of the pruning point header is verified against the imported multiset
+    staging.import_pruning_point_utxo_set(pruning_point, multiset)?;
+    info!("Snapshot: imported {} UTXOs of pruning point {}", utxo_count, pruning_point);
+
+    Ok(())
+}
+
+/// Headers are rehashed rather than trusting the hash cached in the snapshot
+fn finalized_header(mut header: Header) -> Arc<Header> {
+    header.finalize();
+    Arc::new(header)
+}
+
+/// Records are written as their bincode encoding prefixed by its length
+fn write_record<T: Serialize>(writer: &mut impl Write, record: &T) -> SnapshotResult<()> {
+    let bytes = bincode::serialize(record)?;
+    writer.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Records larger than `limit` bytes are rejected before allocating for them. A record is read as a whole and
+/// then decoded from memory, since some types borrow from the input when decoding
+fn read_record<T: DeserializeOwned>(reader: &mut impl Read, limit: u64) -> SnapshotResult<T> {
+    let mut len = [0u8; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    if len > limit {
+        return Err(SnapshotError::RecordTooLarge(len, limit));
+    }
+    let mut bytes = vec![0u8; len as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(bincode::DefaultOptions::new().with_fixint_encoding().with_limit(limit).deserialize(&bytes)?)
+}
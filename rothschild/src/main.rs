@@ -96,6 +96,7 @@ async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &st
         None,
         false,
         Some(500_000),
+        None,
         Default::default(),
     )
     .await
@@ -125,6 +126,7 @@ async fn main() {
         None,
         false,
         Some(500_000),
+        None,
         Default::default(),
     )
     .await
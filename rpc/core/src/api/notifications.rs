@@ -44,6 +44,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "AddressHistoryChanged notification: {} removed, {} added", "_0.removed.len()", "_0.added.len()")]
+    AddressHistoryChanged(AddressHistoryChangedNotification),
 }
 }
 
@@ -60,6 +63,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::AddressHistoryChanged(v) => to_value(&v),
         }
     }
 }
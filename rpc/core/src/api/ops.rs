@@ -114,6 +114,32 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification,
     PruningPointUtxoSetOverrideNotification,
     NewBlockTemplateNotification,
+
+    // Ops appended after the existing ones so that the latter keep their Borsh ids
+    /// Get a transaction from the transaction index
+    GetTransaction,
+    /// Get transactions from the transaction index
+    GetTransactionsByIds,
+    /// Get feerate estimations based on the current mempool load
+    GetFeeEstimate,
+    /// Submits a transaction replacing by fee the mempool transactions it double spends
+    SubmitTransactionReplacement,
+    /// Authenticates a wRPC connection with an API key
+    Authenticate,
+    /// Get the banned subnets along with their expiry and reason
+    ListBans,
+    /// Writes the mempool to the mempool file of the node
+    SaveMempool,
+    /// Reloads the mempool file of the node, revalidating its transactions
+    LoadMempool,
+    /// Get a page of the transaction history of an address from the address history index
+    GetAddressTransactions,
+    /// Get the number of transactions in the history of an address from the address history index
+    GetAddressTransactionCount,
+    NotifyAddressHistoryChanged,
+    AddressHistoryChangedNotification,
+    /// Get a proof of inclusion of a transaction in a block, linked to the virtual selected chain
+    GetTransactionMerkleProof,
 }
 
 impl RpcApiOps {
@@ -129,6 +155,7 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyAddressHistoryChanged
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -155,6 +182,20 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::AddressHistoryChanged => RpcApiOps::AddressHistoryChangedNotification,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops_borsh_ids() {
+        // The Borsh ids of the ops are part of the wRPC protocol, so new ops are appended
+        assert_eq!(RpcApiOps::Ping.try_to_vec().unwrap(), [0]);
+        assert_eq!(RpcApiOps::NewBlockTemplateNotification.try_to_vec().unwrap(), [53]);
+        assert_eq!(RpcApiOps::GetTransaction.try_to_vec().unwrap(), [54]);
+    }
+}
@@ -4,7 +4,7 @@
 //! All data provided by the RCP server can be trusted by the client
 //! No data submitted by the client to the server can be trusted
 
-use crate::{model::*, notify::connection::ChannelConnection, RpcResult};
+use crate::{api::ops::RpcApiOps, model::*, notify::connection::ChannelConnection, RpcResult};
 use async_trait::async_trait;
 use downcast::{downcast_sync, AnySync};
 use kaspa_notify::{listener::ListenerId, scope::Scope, subscription::Command};
@@ -127,11 +127,27 @@ pub trait RpcApi: Sync + Send + AnySync {
     async fn add_peer_call(&self, request: AddPeerRequest) -> RpcResult<AddPeerResponse>;
 
     /// Submits a transaction to the mempool.
+    ///
+    /// A transaction double spending some mempool transactions is rejected.
     async fn submit_transaction(&self, transaction: RpcTransaction, allow_orphan: bool) -> RpcResult<RpcTransactionId> {
-        Ok(self.submit_transaction_call(SubmitTransactionRequest { transaction, allow_orphan }).await?.transaction_id)
+        Ok(self
+            .submit_transaction_call(SubmitTransactionRequest::new(transaction, allow_orphan, RpcRbfPolicy::Forbidden))
+            .await?
+            .transaction_id)
     }
     async fn submit_transaction_call(&self, request: SubmitTransactionRequest) -> RpcResult<SubmitTransactionResponse>;
 
+    /// Submits a transaction replacing by fee the mempool transactions it double spends.
+    ///
+    /// Returns the id of the replacement along with the ids of all the evicted transactions.
+    async fn submit_transaction_replacement(&self, transaction: RpcTransaction) -> RpcResult<SubmitTransactionReplacementResponse> {
+        self.submit_transaction_replacement_call(SubmitTransactionReplacementRequest::new(transaction)).await
+    }
+    async fn submit_transaction_replacement_call(
+        &self,
+        request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse>;
+
     /// Requests information about a specific block.
     async fn get_block(&self, hash: RpcHash, include_transactions: bool) -> RpcResult<RpcBlock> {
         Ok(self.get_block_call(GetBlockRequest::new(hash, include_transactions)).await?.block)
@@ -237,20 +253,39 @@ pub trait RpcApi: Sync + Send + AnySync {
     }
     async fn get_sink_blue_score_call(&self, request: GetSinkBlueScoreRequest) -> RpcResult<GetSinkBlueScoreResponse>;
 
-    /// Bans the given ip.
-    async fn ban(&self, ip: RpcIpAddress) -> RpcResult<()> {
-        self.ban_call(BanRequest::new(ip)).await?;
+    /// Bans the given subnet for `duration` seconds (the node default if `None`, permanently if 0).
+    async fn ban(&self, subnet: RpcIpSubnet, duration: Option<u64>, reason: Option<String>) -> RpcResult<()> {
+        self.ban_call(BanRequest::new(subnet, duration, reason)).await?;
         Ok(())
     }
     async fn ban_call(&self, request: BanRequest) -> RpcResult<BanResponse>;
 
-    /// Unbans the given ip.
-    async fn unban(&self, ip: RpcIpAddress) -> RpcResult<()> {
-        self.unban_call(UnbanRequest::new(ip)).await?;
+    /// Unbans the given subnet.
+    async fn unban(&self, subnet: RpcIpSubnet) -> RpcResult<()> {
+        self.unban_call(UnbanRequest::new(subnet)).await?;
         Ok(())
     }
     async fn unban_call(&self, request: UnbanRequest) -> RpcResult<UnbanResponse>;
 
+    /// Returns the banned subnets along with their expiry and reason.
+    async fn list_bans(&self) -> RpcResult<Vec<RpcBanInfo>> {
+        Ok(self.list_bans_call(ListBansRequest {}).await?.bans)
+    }
+    async fn list_bans_call(&self, request: ListBansRequest) -> RpcResult<ListBansResponse>;
+
+    /// Writes the mempool, orphans included, to the mempool file of the node.
+    /// Returns the number of saved transactions.
+    async fn save_mempool(&self) -> RpcResult<u64> {
+        Ok(self.save_mempool_call(SaveMempoolRequest {}).await?.transaction_count)
+    }
+    async fn save_mempool_call(&self, request: SaveMempoolRequest) -> RpcResult<SaveMempoolResponse>;
+
+    /// Reloads the mempool file of the node, revalidating its transactions against the current virtual UTXO set.
+    async fn load_mempool(&self) -> RpcResult<LoadMempoolResponse> {
+        self.load_mempool_call(LoadMempoolRequest {}).await
+    }
+    async fn load_mempool_call(&self, request: LoadMempoolRequest) -> RpcResult<LoadMempoolResponse>;
+
     /// Returns info about the node.
     async fn get_info_call(&self, request: GetInfoRequest) -> RpcResult<GetInfoResponse>;
     async fn get_info(&self) -> RpcResult<GetInfoResponse> {
@@ -304,6 +339,89 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetDaaScoreTimestampEstimateRequest,
     ) -> RpcResult<GetDaaScoreTimestampEstimateResponse>;
 
+    /// Retrieves a transaction from the transaction index.
+    /// Requires the node to run with `--txindex`.
+    async fn get_transaction(&self, transaction_id: RpcTransactionId, include_transaction: bool) -> RpcResult<RpcIndexedTransaction> {
+        Ok(self.get_transaction_call(GetTransactionRequest::new(transaction_id, include_transaction)).await?.transaction)
+    }
+    async fn get_transaction_call(&self, request: GetTransactionRequest) -> RpcResult<GetTransactionResponse>;
+
+    /// Retrieves the transactions matching the given ids from the transaction index.
+    /// Ids not found in the index are omitted from the result, and requests of more than 1000 ids are rejected.
+    /// Requires the node to run with `--txindex`.
+    async fn get_transactions_by_ids(
+        &self,
+        transaction_ids: Vec<RpcTransactionId>,
+        include_transactions: bool,
+    ) -> RpcResult<Vec<RpcIndexedTransaction>> {
+        Ok(self
+            .get_transactions_by_ids_call(GetTransactionsByIdsRequest::new(transaction_ids, include_transactions))
+            .await?
+            .transactions)
+    }
+    async fn get_transactions_by_ids_call(&self, request: GetTransactionsByIdsRequest) -> RpcResult<GetTransactionsByIdsResponse>;
+
+    /// Retrieves feerate estimations (in sompi per gram) along with the expected inclusion times.
+    /// When `verbose` is set, the mempool feerate histogram the estimations were computed from is returned as well.
+    async fn get_fee_estimate(&self, verbose: bool) -> RpcResult<GetFeeEstimateResponse> {
+        self.get_fee_estimate_call(GetFeeEstimateRequest::new(verbose)).await
+    }
+    async fn get_fee_estimate_call(&self, request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse>;
+
+    /// Retrieves at most `limit` transactions of the history of an address, in ascending accepting DAA score order,
+    /// starting at `start_daa_score`, or right after the transaction `start_transaction_id` at this DAA score if set.
+    /// Requires the node to run with `--addresshistory`.
+    async fn get_address_transactions(
+        &self,
+        address: RpcAddress,
+        start_daa_score: u64,
+        start_transaction_id: Option<RpcTransactionId>,
+        limit: u32,
+    ) -> RpcResult<Vec<RpcAddressHistoryEntry>> {
+        Ok(self
+            .get_address_transactions_call(GetAddressTransactionsRequest::new(address, start_daa_score, start_transaction_id, limit))
+            .await?
+            .entries)
+    }
+    async fn get_address_transactions_call(&self, request: GetAddressTransactionsRequest)
+        -> RpcResult<GetAddressTransactionsResponse>;
+
+    /// Retrieves the number of transactions in the history of an address.
+    /// Requires the node to run with `--addresshistory`.
+    async fn get_address_transaction_count(&self, address: RpcAddress) -> RpcResult<u64> {
+        Ok(self.get_address_transaction_count_call(GetAddressTransactionCountRequest::new(address)).await?.count)
+    }
+    async fn get_address_transaction_count_call(
+        &self,
+        request: GetAddressTransactionCountRequest,
+    ) -> RpcResult<GetAddressTransactionCountResponse>;
+
+    /// Retrieves a proof of inclusion of a transaction in a block, along with the headers linking this block
+    /// to the chain block merging it, allowing light clients to verify the transaction with [`RpcTransactionMerkleProof::verify`].
+    /// If `block_hash` is `None`, the block is looked up in the transaction index, requiring the node to run with `--txindex`.
+    async fn get_transaction_merkle_proof(
+        &self,
+        transaction_id: RpcTransactionId,
+        block_hash: Option<RpcHash>,
+    ) -> RpcResult<RpcTransactionMerkleProof> {
+        Ok(self.get_transaction_merkle_proof_call(GetTransactionMerkleProofRequest::new(transaction_id, block_hash)).await?.proof)
+    }
+    async fn get_transaction_merkle_proof_call(
+        &self,
+        request: GetTransactionMerkleProofRequest,
+    ) -> RpcResult<GetTransactionMerkleProofResponse>;
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Access control
+
+    /// Checks whether a client presenting `api_key` is allowed to call `op`.
+    ///
+    /// RPC servers call this before dispatching every client request. The default implementation
+    /// grants access to everyone, services enforcing an access policy override it.
+    fn authorize(&self, _api_key: Option<&str>, _op: RpcApiOps) -> RpcResult<()> {
+        Ok(())
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -0,0 +1,23 @@
+use crate::{RpcAddressHistoryByAddressEntry, RpcAddressHistoryEntry};
+use kaspa_addresses::Prefix;
+use kaspa_index_core::address_history::{AddressHistoryEntries, AddressHistoryEntry};
+use kaspa_txscript::extract_script_pub_key_address;
+
+// ----------------------------------------------------------------------------
+// index to rpc_core
+// ----------------------------------------------------------------------------
+
+impl From<&AddressHistoryEntry> for RpcAddressHistoryEntry {
+    fn from(item: &AddressHistoryEntry) -> Self {
+        Self::new(item.transaction_id, item.accepting_block_hash, item.accepting_daa_score, item.received, item.spent)
+    }
+}
+
+pub fn address_history_into_rpc(item: &AddressHistoryEntries, prefix: Option<Prefix>) -> Vec<RpcAddressHistoryByAddressEntry> {
+    item.iter()
+        .map(|(script_public_key, entry)| {
+            let address = prefix.and_then(|x| extract_script_pub_key_address(script_public_key, x).ok());
+            RpcAddressHistoryByAddressEntry::new(address, entry.into())
+        })
+        .collect()
+}
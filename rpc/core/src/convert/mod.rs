@@ -1,3 +1,4 @@
+pub mod address_history;
 pub mod block;
 pub mod notification;
 pub mod scope;
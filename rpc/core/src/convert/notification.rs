@@ -1,5 +1,6 @@
 use crate::{
-    convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
+    convert::{address_history::address_history_into_rpc, utxo::utxo_set_into_rpc},
+    AddressHistoryChangedNotification, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
     NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
     SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
 };
@@ -125,6 +126,7 @@ impl From<&index_notify::Notification> for Notification {
         match item {
             index_notify::Notification::UtxosChanged(msg) => Notification::UtxosChanged(msg.into()),
             index_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
+            index_notify::Notification::AddressHistoryChanged(msg) => Notification::AddressHistoryChanged(msg.into()),
         }
     }
 }
@@ -142,3 +144,14 @@ impl From<&index_notify::UtxosChangedNotification> for UtxosChangedNotification
         Self { added: Arc::new(utxo_set_into_rpc(&item.added, None)), removed: Arc::new(utxo_set_into_rpc(&item.removed, None)) }
     }
 }
+
+impl From<&index_notify::AddressHistoryChangedNotification> for AddressHistoryChangedNotification {
+    // This is not intended to be ever called because no address prefix is available.
+    // Use kaspa_rpc_service::converter::index::IndexConverter instead.
+    fn from(item: &index_notify::AddressHistoryChangedNotification) -> Self {
+        Self {
+            added: Arc::new(address_history_into_rpc(&item.added, None)),
+            removed: Arc::new(address_history_into_rpc(&item.removed, None)),
+        }
+    }
+}
@@ -7,9 +7,9 @@ use workflow_core::channel::ChannelError;
 use pyo3::{exceptions::PyException, PyErr};
 
 use kaspa_consensus_core::{subnets::SubnetworkConversionError, tx::TransactionId};
-use kaspa_utils::networking::IpAddress;
+use kaspa_utils::networking::IpSubnet;
 
-use crate::{api::ctl::RpcState, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
+use crate::{api::ctl::RpcState, api::ops::RpcApiOps, RpcHash, RpcTransactionId, SubmitBlockRejectReason};
 
 #[derive(Clone, Debug, Error)]
 pub enum RpcError {
@@ -31,6 +31,9 @@ pub enum RpcError {
     #[error("Ip address parsing error {0}")]
     ParseIpAddressError(#[from] AddrParseError),
 
+    #[error("Ip subnet parsing error {0}")]
+    ParseIpSubnetError(#[from] ipnet::AddrParseError),
+
     #[error("Wrong rpc api version format")]
     RpcApiVersionFormatError,
 
@@ -61,9 +64,24 @@ pub enum RpcError {
     #[error("Transaction {0} not found")]
     TransactionNotFound(TransactionId),
 
+    #[error("Requested {0} transaction ids, more than the max {1} allowed.")]
+    TransactionIdsExceedingMaximum(usize, usize),
+
+    #[error("Transaction {0} is not included in block {1}")]
+    TransactionNotInBlock(TransactionId, RpcHash),
+
+    #[error("Block {0} is not merged by the virtual selected chain")]
+    BlockNotMerged(RpcHash),
+
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --txindex argument.")]
+    NoTxIndex,
+
+    #[error("Method unavailable. Run the node with the --addresshistory argument.")]
+    NoAddressHistory,
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -76,11 +94,17 @@ pub enum RpcError {
     #[error("Method unavailable in safe mode. Run the node with --unsaferpc argument.")]
     UnavailableInSafeMode,
 
-    #[error("Cannot ban IP {0} because it has some permanent connection.")]
-    IpHasPermanentConnection(IpAddress),
+    #[error("Missing or unknown RPC API key.")]
+    Unauthenticated,
+
+    #[error("Method {0:?} is not allowed for this RPC API key.")]
+    Unauthorized(RpcApiOps),
+
+    #[error("Cannot ban subnet {0} because it has some permanent connection.")]
+    SubnetHasPermanentConnection(IpSubnet),
 
-    #[error("IP {0} is not registered as banned.")]
-    IpIsNotBanned(IpAddress),
+    #[error("Subnet {0} is not registered as banned.")]
+    SubnetIsNotBanned(IpSubnet),
 
     #[error("Block was not submitted: {0}")]
     SubmitBlockError(SubmitBlockRejectReason),
@@ -6,6 +6,7 @@ pub mod convert;
 pub mod error;
 pub mod model;
 pub mod notify;
+pub mod test_helpers;
 pub mod wasm;
 
 pub mod prelude {
@@ -15,6 +16,7 @@ pub mod prelude {
 }
 
 pub use api::notifications::*;
+pub use convert::address_history::*;
 pub use convert::utxo::*;
 pub use error::*;
 pub use model::script_class::*;
@@ -0,0 +1,105 @@
+use crate::{RpcAddress, RpcHash, RpcTransactionId};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+use pyo3::pyclass;
+
+/// Represents a transaction accepted by the virtual selected chain which created or spent outputs of an address
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcAddressHistoryEntry {
+    #[pyo3(get)]
+    pub transaction_id: RpcTransactionId,
+    #[pyo3(get)]
+    pub accepting_block_hash: RpcHash,
+    #[pyo3(get)]
+    pub accepting_daa_score: u64,
+    #[pyo3(get)]
+    pub received: u64,
+    #[pyo3(get)]
+    pub spent: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcAddressHistoryEntry {
+    pub transaction_id: RpcTransactionId,
+    pub accepting_block_hash: RpcHash,
+    pub accepting_daa_score: u64,
+    pub received: u64,
+    pub spent: u64,
+}
+
+impl RpcAddressHistoryEntry {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        accepting_block_hash: RpcHash,
+        accepting_daa_score: u64,
+        received: u64,
+        spent: u64,
+    ) -> Self {
+        Self { transaction_id, accepting_block_hash, accepting_daa_score, received, spent }
+    }
+}
+
+/// Represents a history entry of an address carried by the `AddressHistoryChanged` notification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcAddressHistoryByAddressEntry {
+    #[pyo3(get)]
+    pub address: Option<RpcAddress>,
+    #[pyo3(get)]
+    pub entry: RpcAddressHistoryEntry,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcAddressHistoryByAddressEntry {
+    pub address: Option<RpcAddress>,
+    pub entry: RpcAddressHistoryEntry,
+}
+
+impl RpcAddressHistoryByAddressEntry {
+    pub fn new(address: Option<RpcAddress>, entry: RpcAddressHistoryEntry) -> Self {
+        Self { address, entry }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_ADDRESS_HISTORY: &'static str = r#"
+            /**
+             * Transaction which created or spent outputs of an address.
+             *
+             * @category Node RPC
+             */
+            export interface IAddressHistoryEntry {
+                transactionId : HexString;
+                acceptingBlockHash : HexString;
+                acceptingDaaScore : bigint;
+                received : bigint;
+                spent : bigint;
+            }
+
+            /**
+             * History entry of an address.
+             *
+             * @category Node RPC
+             */
+            export interface IAddressHistoryByAddressEntry {
+                address? : Address;
+                entry : IAddressHistoryEntry;
+            }
+        "#;
+    }
+}
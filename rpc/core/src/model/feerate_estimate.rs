@@ -0,0 +1,179 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+use pyo3::pyclass;
+
+/// A feerate (in sompi per gram) along with the estimated time for a transaction paying it to be included in a block
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcFeerateBucket {
+    #[pyo3(get)]
+    pub feerate: f64,
+    #[pyo3(get)]
+    pub estimated_seconds: f64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcFeerateBucket {
+    pub feerate: f64,
+    pub estimated_seconds: f64,
+}
+
+impl RpcFeerateBucket {
+    pub fn new(feerate: f64, estimated_seconds: f64) -> Self {
+        Self { feerate, estimated_seconds }
+    }
+}
+
+/// Feerate estimations for transactions to be included in the next block (priority), within
+/// about a minute (normal) and within about an hour (low). Buckets are ordered by decreasing feerate.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcFeeEstimate {
+    #[pyo3(get)]
+    pub priority_bucket: RpcFeerateBucket,
+    #[pyo3(get)]
+    pub normal_buckets: Vec<RpcFeerateBucket>,
+    #[pyo3(get)]
+    pub low_buckets: Vec<RpcFeerateBucket>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcFeeEstimate {
+    pub priority_bucket: RpcFeerateBucket,
+    pub normal_buckets: Vec<RpcFeerateBucket>,
+    pub low_buckets: Vec<RpcFeerateBucket>,
+}
+
+impl RpcFeeEstimate {
+    pub fn new(priority_bucket: RpcFeerateBucket, normal_buckets: Vec<RpcFeerateBucket>, low_buckets: Vec<RpcFeerateBucket>) -> Self {
+        Self { priority_bucket, normal_buckets, low_buckets }
+    }
+}
+
+/// A non-empty bucket of the mempool feerate histogram
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcFeerateHistogramBucket {
+    /// Lowest feerate of the bucket
+    #[pyo3(get)]
+    pub feerate: f64,
+    #[pyo3(get)]
+    pub mass: u64,
+    #[pyo3(get)]
+    pub count: u64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcFeerateHistogramBucket {
+    /// Lowest feerate of the bucket
+    pub feerate: f64,
+    pub mass: u64,
+    pub count: u64,
+}
+
+impl RpcFeerateHistogramBucket {
+    pub fn new(feerate: f64, mass: u64, count: u64) -> Self {
+        Self { feerate, mass, count }
+    }
+}
+
+/// Raw data the fee estimate was computed from
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcFeeEstimateVerboseData {
+    /// Non-empty buckets of the mempool feerate histogram, ordered by increasing feerate
+    #[pyo3(get)]
+    pub mempool_histogram: Vec<RpcFeerateHistogramBucket>,
+    #[pyo3(get)]
+    pub mempool_transaction_count: u64,
+    #[pyo3(get)]
+    pub mempool_total_mass: u64,
+    #[pyo3(get)]
+    pub network_mass_per_second: u64,
+    #[pyo3(get)]
+    pub recent_templates_feerate_floor: f64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcFeeEstimateVerboseData {
+    /// Non-empty buckets of the mempool feerate histogram, ordered by increasing feerate
+    pub mempool_histogram: Vec<RpcFeerateHistogramBucket>,
+    pub mempool_transaction_count: u64,
+    pub mempool_total_mass: u64,
+    pub network_mass_per_second: u64,
+    pub recent_templates_feerate_floor: f64,
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_FEE_ESTIMATE: &'static str = r#"
+            /**
+             * A feerate (in sompi per gram) along with the estimated
+             * time (in seconds) for a transaction paying it to be
+             * included in a block.
+             *
+             * @category Node RPC
+             */
+            export interface IFeerateBucket {
+                feerate : number;
+                estimatedSeconds : number;
+            }
+
+            /**
+             * Feerate estimations, ordered by decreasing feerate.
+             *
+             * @category Node RPC
+             */
+            export interface IFeeEstimate {
+                priorityBucket : IFeerateBucket;
+                normalBuckets : IFeerateBucket[];
+                lowBuckets : IFeerateBucket[];
+            }
+
+            /**
+             * A non-empty bucket of the mempool feerate histogram.
+             *
+             * @category Node RPC
+             */
+            export interface IFeerateHistogramBucket {
+                feerate : number;
+                mass : bigint;
+                count : bigint;
+            }
+
+            /**
+             * Raw data a fee estimate was computed from.
+             *
+             * @category Node RPC
+             */
+            export interface IFeeEstimateVerboseData {
+                mempoolHistogram : IFeerateHistogramBucket[];
+                mempoolTransactionCount : bigint;
+                mempoolTotalMass : bigint;
+                networkMassPerSecond : bigint;
+                recentTemplatesFeerateFloor : number;
+            }
+        "#;
+    }
+}
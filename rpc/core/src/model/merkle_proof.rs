@@ -0,0 +1,106 @@
+use crate::{RpcHash, RpcHeader};
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_consensus_core::merkle::verify_transaction_merkle_proof;
+use kaspa_merkle::MerkleProof;
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+use pyo3::pyclass;
+
+/// Represents a proof of inclusion of a transaction in a block, along with the headers linking the
+/// block to a chain block of the virtual selected chain
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcTransactionMerkleProof {
+    /// The block including the transaction
+    #[pyo3(get)]
+    pub block_hash: RpcHash,
+    /// The transaction hash committed to by the block `hashMerkleRoot`, which differs from the transaction id
+    #[pyo3(get)]
+    pub transaction_hash: RpcHash,
+    /// Position of the transaction within the block
+    #[pyo3(get)]
+    pub index: u32,
+    /// Sibling hashes along the merkle path from the transaction up to the block `hashMerkleRoot`
+    #[pyo3(get)]
+    pub merkle_path: Vec<RpcHash>,
+    /// Headers from the including block up to the chain block merging it, each one a direct parent of the next
+    #[pyo3(get)]
+    pub headers: Vec<RpcHeader>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcTransactionMerkleProof {
+    pub block_hash: RpcHash,
+    pub transaction_hash: RpcHash,
+    pub index: u32,
+    pub merkle_path: Vec<RpcHash>,
+    pub headers: Vec<RpcHeader>,
+}
+
+impl RpcTransactionMerkleProof {
+    pub fn new(
+        block_hash: RpcHash,
+        transaction_hash: RpcHash,
+        index: u32,
+        merkle_path: Vec<RpcHash>,
+        headers: Vec<RpcHeader>,
+    ) -> Self {
+        Self { block_hash, transaction_hash, index, merkle_path, headers }
+    }
+
+    /// Verifies the proof without trusting any of the hashes it carries, returning the hash of the chain block
+    /// it leads to, or `None` if the proof is invalid.
+    ///
+    /// The caller is expected to check the returned hash against the selected chain it follows.
+    pub fn verify(&self) -> Option<RpcHash> {
+        let proof = MerkleProof::new(self.index as usize, self.merkle_path.clone());
+        verify_transaction_merkle_proof(self.transaction_hash, &proof, &self.headers)
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_TRANSACTION_MERKLE_PROOF: &'static str = r#"
+            /**
+             * Proof of inclusion of a transaction in a block, along with the headers
+             * linking the block to a chain block of the virtual selected chain.
+             *
+             * @category Node RPC
+             */
+            export interface ITransactionMerkleProof {
+                blockHash : HexString;
+                transactionHash : HexString;
+                index : number;
+                merklePath : HexString[];
+                headers : IHeader[];
+            }
+        "#;
+
+        #[wasm_bindgen]
+        extern "C" {
+            #[wasm_bindgen(typescript_type = "ITransactionMerkleProof")]
+            pub type ITransactionMerkleProof;
+        }
+
+        /// Verifies a transaction merkle proof returned by {@link RpcClient.getTransactionMerkleProof}
+        /// without trusting the node, returning the hash of the chain block the proof leads to,
+        /// or `undefined` if the proof is invalid.
+        ///
+        /// The returned hash should be checked against the selected chain followed by the caller.
+        ///
+        /// @category Node RPC
+        #[wasm_bindgen(js_name = verifyTransactionMerkleProof)]
+        pub fn verify_transaction_merkle_proof_js(proof: ITransactionMerkleProof) -> std::result::Result<Option<String>, JsError> {
+            let proof: RpcTransactionMerkleProof = serde_wasm_bindgen::from_value(proof.into())?;
+            Ok(proof.verify().map(|hash| hash.to_string()))
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::model::*;
+use crate::{model::*, RpcError};
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_consensus_core::api::stats::BlockCount;
 use kaspa_core::debug;
@@ -6,6 +6,7 @@ use kaspa_notify::subscription::{context::SubscriptionContext, single::UtxosChan
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{Display, Formatter},
+    str::FromStr,
     sync::Arc,
 };
 
@@ -370,16 +371,68 @@ impl AddPeerRequest {
 #[serde(rename_all = "camelCase")]
 pub struct AddPeerResponse {}
 
+/// Replace by fee (RBF) policy applied to a submitted transaction double spending some mempool transactions
+///
+/// A replacement must pay a total fee strictly higher than the total fee of all the transactions it
+/// evicts and a feerate strictly higher than the feerate of each double spent transaction.
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(not(target_family = "wasm"), pyclass)]
+pub enum RpcRbfPolicy {
+    /// Any double spend of a mempool transaction is rejected
+    #[default]
+    Forbidden = 0,
+    /// A double spend is accepted as a replacement if it meets the RBF rules
+    Permitted = 1,
+    /// The transaction is accepted only as a replacement of at least one mempool transaction
+    Mandatory = 2,
+}
+
+impl RpcRbfPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RpcRbfPolicy::Forbidden => "forbidden",
+            RpcRbfPolicy::Permitted => "permitted",
+            RpcRbfPolicy::Mandatory => "mandatory",
+        }
+    }
+}
+
+impl Display for RpcRbfPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcRbfPolicy {
+    type Err = RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "forbidden" => Ok(RpcRbfPolicy::Forbidden),
+            "permitted" => Ok(RpcRbfPolicy::Permitted),
+            "mandatory" => Ok(RpcRbfPolicy::Mandatory),
+            _ => Err(RpcError::General(format!("unknown RBF policy '{s}', expected forbidden, permitted or mandatory"))),
+        }
+    }
+}
+
+/// SubmitTransactionRequest submits a transaction to the mempool.
+///
+/// If the transaction double spends some mempool transactions, `rbf_policy` defines whether it may,
+/// must or must not replace them by fee.
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionRequest {
     pub transaction: RpcTransaction,
     pub allow_orphan: bool,
+    #[serde(default)]
+    pub rbf_policy: RpcRbfPolicy,
 }
 
 impl SubmitTransactionRequest {
-    pub fn new(transaction: RpcTransaction, allow_orphan: bool) -> Self {
-        Self { transaction, allow_orphan }
+    pub fn new(transaction: RpcTransaction, allow_orphan: bool, rbf_policy: RpcRbfPolicy) -> Self {
+        Self { transaction, allow_orphan, rbf_policy }
     }
 }
 
@@ -387,11 +440,57 @@ impl SubmitTransactionRequest {
 #[serde(rename_all = "camelCase")]
 pub struct SubmitTransactionResponse {
     pub transaction_id: RpcTransactionId,
+    /// Ids of the mempool transactions evicted by the submitted transaction if it replaced them by fee
+    #[serde(default)]
+    pub removed_transaction_ids: Vec<RpcTransactionId>,
 }
 
 impl SubmitTransactionResponse {
-    pub fn new(transaction_id: RpcTransactionId) -> Self {
-        Self { transaction_id }
+    pub fn new(transaction_id: RpcTransactionId, removed_transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_id, removed_transaction_ids }
+    }
+}
+
+/// SubmitTransactionReplacementRequest submits a transaction replacing by fee some mempool transactions.
+///
+/// The transaction must double spend at least one mempool transaction and is rejected otherwise.
+/// It is never accepted as an orphan.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTransactionReplacementRequest {
+    pub transaction: RpcTransaction,
+}
+
+impl SubmitTransactionReplacementRequest {
+    pub fn new(transaction: RpcTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct SubmitTransactionReplacementResponse {
+    #[pyo3(get)]
+    pub transaction_id: RpcTransactionId,
+    /// Ids of the mempool transactions evicted by the replacement, including the redeemers of the double spent transactions
+    #[pyo3(get)]
+    pub removed_transaction_ids: Vec<RpcTransactionId>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct SubmitTransactionReplacementResponse {
+    pub transaction_id: RpcTransactionId,
+    /// Ids of the mempool transactions evicted by the replacement, including the redeemers of the double spent transactions
+    pub removed_transaction_ids: Vec<RpcTransactionId>,
+}
+
+impl SubmitTransactionReplacementResponse {
+    pub fn new(transaction_id: RpcTransactionId, removed_transaction_ids: Vec<RpcTransactionId>) -> Self {
+        Self { transaction_id, removed_transaction_ids }
     }
 }
 
@@ -735,12 +834,17 @@ impl GetUtxosByAddressesResponse {
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BanRequest {
-    pub ip: RpcIpAddress,
+    /// The banned subnet, a single IP being banned as a full-length subnet
+    #[serde(alias = "ip")]
+    pub subnet: RpcIpSubnet,
+    /// Duration of the ban in seconds, `None` for the node default and 0 for a permanent ban
+    pub duration: Option<u64>,
+    pub reason: Option<String>,
 }
 
 impl BanRequest {
-    pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+    pub fn new(subnet: RpcIpSubnet, duration: Option<u64>, reason: Option<String>) -> Self {
+        Self { subnet, duration, reason }
     }
 }
 
@@ -751,12 +855,14 @@ pub struct BanResponse {}
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnbanRequest {
-    pub ip: RpcIpAddress,
+    /// The banned subnet, exactly as it was banned
+    #[serde(alias = "ip")]
+    pub subnet: RpcIpSubnet,
 }
 
 impl UnbanRequest {
-    pub fn new(ip: RpcIpAddress) -> Self {
-        Self { ip }
+    pub fn new(subnet: RpcIpSubnet) -> Self {
+        Self { subnet }
     }
 }
 
@@ -764,6 +870,79 @@ impl UnbanRequest {
 #[serde(rename_all = "camelCase")]
 pub struct UnbanResponse {}
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBansRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct ListBansResponse {
+    #[pyo3(get)]
+    pub bans: Vec<RpcBanInfo>,
+}
+
+#[cfg(target_family = "wasm")]
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListBansResponse {
+    pub bans: Vec<RpcBanInfo>,
+}
+
+impl ListBansResponse {
+    pub fn new(bans: Vec<RpcBanInfo>) -> Self {
+        Self { bans }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveMempoolRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveMempoolResponse {
+    pub transaction_count: u64,
+}
+
+impl SaveMempoolResponse {
+    pub fn new(transaction_count: u64) -> Self {
+        Self { transaction_count }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadMempoolRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct LoadMempoolResponse {
+    /// Number of transactions read from the mempool file
+    #[pyo3(get)]
+    pub transaction_count: u64,
+    /// Number of these transactions in the mempool after revalidation
+    #[pyo3(get)]
+    pub accepted_count: u64,
+}
+
+#[cfg(target_family = "wasm")]
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadMempoolResponse {
+    pub transaction_count: u64,
+    pub accepted_count: u64,
+}
+
+impl LoadMempoolResponse {
+    pub fn new(transaction_count: u64, accepted_count: u64) -> Self {
+        Self { transaction_count, accepted_count }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EstimateNetworkHashesPerSecondRequest {
@@ -961,6 +1140,8 @@ pub struct BandwidthMetrics {
     pub grpc_bytes_tx: u64,
     #[pyo3(get)]
     pub grpc_bytes_rx: u64,
+    #[pyo3(get)]
+    pub p2p_compact_block_bytes_saved: u64,
 }
 
 #[cfg(target_family = "wasm")]
@@ -975,6 +1156,7 @@ pub struct BandwidthMetrics {
     pub p2p_bytes_rx: u64,
     pub grpc_bytes_tx: u64,
     pub grpc_bytes_rx: u64,
+    pub p2p_compact_block_bytes_saved: u64,
 }
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -1163,6 +1345,188 @@ impl GetDaaScoreTimestampEstimateResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+    pub include_transaction: bool,
+}
+
+impl GetTransactionRequest {
+    pub fn new(transaction_id: RpcTransactionId, include_transaction: bool) -> Self {
+        Self { transaction_id, include_transaction }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionResponse {
+    pub transaction: RpcIndexedTransaction,
+}
+
+impl GetTransactionResponse {
+    pub fn new(transaction: RpcIndexedTransaction) -> Self {
+        Self { transaction }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsRequest {
+    pub transaction_ids: Vec<RpcTransactionId>,
+    pub include_transactions: bool,
+}
+
+impl GetTransactionsByIdsRequest {
+    pub fn new(transaction_ids: Vec<RpcTransactionId>, include_transactions: bool) -> Self {
+        Self { transaction_ids, include_transactions }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsByIdsResponse {
+    pub transactions: Vec<RpcIndexedTransaction>,
+}
+
+impl GetTransactionsByIdsResponse {
+    pub fn new(transactions: Vec<RpcIndexedTransaction>) -> Self {
+        Self { transactions }
+    }
+}
+
+/// GetFeeEstimateRequest requests feerate estimations based on the current mempool load
+/// and on the recent block templates. When `verbose` is set, the response also carries
+/// the raw mempool feerate histogram the estimations were computed from.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateRequest {
+    pub verbose: bool,
+}
+
+impl GetFeeEstimateRequest {
+    pub fn new(verbose: bool) -> Self {
+        Self { verbose }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct GetFeeEstimateResponse {
+    #[pyo3(get)]
+    pub estimate: RpcFeeEstimate,
+    #[pyo3(get)]
+    pub verbose: Option<RpcFeeEstimateVerboseData>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct GetFeeEstimateResponse {
+    pub estimate: RpcFeeEstimate,
+    pub verbose: Option<RpcFeeEstimateVerboseData>,
+}
+
+impl GetFeeEstimateResponse {
+    pub fn new(estimate: RpcFeeEstimate, verbose: Option<RpcFeeEstimateVerboseData>) -> Self {
+        Self { estimate, verbose }
+    }
+}
+
+/// GetAddressTransactionsRequest requests a page of the transaction history of an address,
+/// in ascending accepting DAA score order.
+///
+/// This call is only available when the node was started with `--addresshistory`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressTransactionsRequest {
+    pub address: RpcAddress,
+    /// Accepting DAA score of the first history entry to return
+    pub start_daa_score: u64,
+    /// If set, the page starts right after the entry of this transaction at `start_daa_score`.
+    /// The next page is requested by passing the DAA score and transaction id of the last entry of a page.
+    pub start_transaction_id: Option<RpcTransactionId>,
+    /// Maximum number of history entries to return
+    pub limit: u32,
+}
+
+impl GetAddressTransactionsRequest {
+    pub fn new(address: RpcAddress, start_daa_score: u64, start_transaction_id: Option<RpcTransactionId>, limit: u32) -> Self {
+        Self { address, start_daa_score, start_transaction_id, limit }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressTransactionsResponse {
+    pub entries: Vec<RpcAddressHistoryEntry>,
+}
+
+impl GetAddressTransactionsResponse {
+    pub fn new(entries: Vec<RpcAddressHistoryEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+/// GetAddressTransactionCountRequest requests the number of transactions in the history of an address.
+///
+/// This call is only available when the node was started with `--addresshistory`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressTransactionCountRequest {
+    pub address: RpcAddress,
+}
+
+impl GetAddressTransactionCountRequest {
+    pub fn new(address: RpcAddress) -> Self {
+        Self { address }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressTransactionCountResponse {
+    pub count: u64,
+}
+
+impl GetAddressTransactionCountResponse {
+    pub fn new(count: u64) -> Self {
+        Self { count }
+    }
+}
+
+/// GetTransactionMerkleProofRequest requests a proof of inclusion of a transaction in a block, along with
+/// the headers linking this block to the chain block merging it.
+///
+/// When `block_hash` is omitted, the block including the accepted instance of the transaction is looked up
+/// in the transaction index, which is only available when the node was started with `--txindex`
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionMerkleProofRequest {
+    pub transaction_id: RpcTransactionId,
+    pub block_hash: Option<RpcHash>,
+}
+
+impl GetTransactionMerkleProofRequest {
+    pub fn new(transaction_id: RpcTransactionId, block_hash: Option<RpcHash>) -> Self {
+        Self { transaction_id, block_hash }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionMerkleProofResponse {
+    pub proof: RpcTransactionMerkleProof,
+}
+
+impl GetTransactionMerkleProofResponse {
+    pub fn new(proof: RpcTransactionMerkleProof) -> Self {
+        Self { proof }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -1471,6 +1835,41 @@ pub struct NotifyNewBlockTemplateResponse {}
 #[serde(rename_all = "camelCase")]
 pub struct NewBlockTemplateNotification {}
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// AddressHistoryChangedNotification
+
+/// NotifyAddressHistoryChangedRequest registers this connection for addressHistoryChanged notifications.
+///
+/// This call is only available when the node was started with `--addresshistory`
+///
+/// See: AddressHistoryChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyAddressHistoryChangedRequest {
+    pub command: Command,
+}
+
+impl NotifyAddressHistoryChangedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyAddressHistoryChangedResponse {}
+
+/// AddressHistoryChangedNotification is sent whenever the address history index has been updated,
+/// listing the history entries added by the new chain blocks and the ones removed by a reorg.
+///
+/// See: NotifyAddressHistoryChangedRequest
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressHistoryChangedNotification {
+    pub added: Arc<Vec<RpcAddressHistoryByAddressEntry>>,
+    pub removed: Arc<Vec<RpcAddressHistoryByAddressEntry>>,
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -1492,3 +1891,28 @@ impl SubscribeResponse {
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnsubscribeResponse {}
+
+///
+///  wRPC request for RpcApiOps::Authenticate
+///
+///  Presents the API key granting access to the RPC methods for the rest of the connection.
+///  gRPC clients provide the key as a bearer token in the `authorization` metadata instead.
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateRequest {
+    pub api_key: String,
+}
+
+impl AuthenticateRequest {
+    pub fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::Authenticate request
+///
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticateResponse {}
@@ -1,26 +1,34 @@
 pub mod address;
+pub mod address_history;
 pub mod block;
 pub mod blue_work;
+pub mod feerate_estimate;
 pub mod hash;
 pub mod header;
 pub mod hex_cnv;
 pub mod mempool;
+pub mod merkle_proof;
 pub mod message;
 pub mod network;
 pub mod peer;
 pub mod script_class;
 pub mod subnets;
 pub mod tx;
+pub mod txindex;
 
 pub use address::*;
+pub use address_history::*;
 pub use block::*;
 pub use blue_work::*;
+pub use feerate_estimate::*;
 pub use hash::*;
 pub use header::*;
 pub use hex_cnv::*;
 pub use mempool::*;
+pub use merkle_proof::*;
 pub use message::*;
 pub use network::*;
 pub use peer::*;
 pub use subnets::*;
 pub use tx::*;
+pub use txindex::*;
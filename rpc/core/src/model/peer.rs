@@ -1,5 +1,5 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use kaspa_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, PeerId};
+use kaspa_utils::networking::{ContextualNetAddress, IpAddress, IpSubnet, NetAddress, PeerId};
 use serde::{Deserialize, Serialize};
 
 #[cfg(not(target_family = "wasm"))]
@@ -7,6 +7,7 @@ use pyo3::pyclass;
 
 pub type RpcNodeId = PeerId;
 pub type RpcIpAddress = IpAddress;
+pub type RpcIpSubnet = IpSubnet;
 pub type RpcPeerAddress = NetAddress;
 pub type RpcContextualPeerAddress = ContextualNetAddress;
 
@@ -51,3 +52,38 @@ pub struct RpcPeerInfo {
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcBanInfo {
+    #[pyo3(get)]
+    pub subnet: RpcIpSubnet,
+    /// Unix timestamp (in milliseconds) of the ban
+    #[pyo3(get)]
+    pub created_at: u64,
+    /// Unix timestamp (in milliseconds) at which the ban is lifted, `None` if the ban is permanent
+    #[pyo3(get)]
+    pub expires_at: Option<u64>,
+    #[pyo3(get)]
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcBanInfo {
+    pub subnet: RpcIpSubnet,
+    /// Unix timestamp (in milliseconds) of the ban
+    pub created_at: u64,
+    /// Unix timestamp (in milliseconds) at which the ban is lifted, `None` if the ban is permanent
+    pub expires_at: Option<u64>,
+    pub reason: String,
+}
+
+impl RpcBanInfo {
+    pub fn new(subnet: RpcIpSubnet, created_at: u64, expires_at: Option<u64>, reason: String) -> Self {
+        Self { subnet, created_at, expires_at, reason }
+    }
+}
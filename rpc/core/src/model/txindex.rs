@@ -0,0 +1,153 @@
+use crate::{RpcHash, RpcTransaction, RpcTransactionId};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_family = "wasm"))]
+use pyo3::pyclass;
+
+/// Represents the acceptance of a transaction by a chain block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcTransactionAcceptance {
+    #[pyo3(get)]
+    pub accepting_block_hash: RpcHash,
+    #[pyo3(get)]
+    pub accepting_daa_score: u64,
+    #[pyo3(get)]
+    pub including_block_hash: RpcHash,
+    #[pyo3(get)]
+    pub index_within_block: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcTransactionAcceptance {
+    pub accepting_block_hash: RpcHash,
+    pub accepting_daa_score: u64,
+    pub including_block_hash: RpcHash,
+    pub index_within_block: u32,
+}
+
+impl RpcTransactionAcceptance {
+    pub fn new(
+        accepting_block_hash: RpcHash,
+        accepting_daa_score: u64,
+        including_block_hash: RpcHash,
+        index_within_block: u32,
+    ) -> Self {
+        Self { accepting_block_hash, accepting_daa_score, including_block_hash, index_within_block }
+    }
+}
+
+/// Represents the inclusion of a transaction in a block
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcTransactionInclusion {
+    #[pyo3(get)]
+    pub block_hash: RpcHash,
+    #[pyo3(get)]
+    pub daa_score: u64,
+    #[pyo3(get)]
+    pub index_within_block: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcTransactionInclusion {
+    pub block_hash: RpcHash,
+    pub daa_score: u64,
+    pub index_within_block: u32,
+}
+
+impl RpcTransactionInclusion {
+    pub fn new(block_hash: RpcHash, daa_score: u64, index_within_block: u32) -> Self {
+        Self { block_hash, daa_score, index_within_block }
+    }
+}
+
+/// Represents a transaction found in the transaction index
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_family = "wasm"))]
+#[pyclass]
+pub struct RpcIndexedTransaction {
+    #[pyo3(get)]
+    pub transaction_id: RpcTransactionId,
+    #[pyo3(get)]
+    pub transaction: Option<RpcTransaction>,
+    #[pyo3(get)]
+    pub acceptance: Option<RpcTransactionAcceptance>,
+    #[pyo3(get)]
+    pub inclusions: Vec<RpcTransactionInclusion>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(target_family = "wasm")]
+pub struct RpcIndexedTransaction {
+    pub transaction_id: RpcTransactionId,
+    pub transaction: Option<RpcTransaction>,
+    pub acceptance: Option<RpcTransactionAcceptance>,
+    pub inclusions: Vec<RpcTransactionInclusion>,
+}
+
+impl RpcIndexedTransaction {
+    pub fn new(
+        transaction_id: RpcTransactionId,
+        transaction: Option<RpcTransaction>,
+        acceptance: Option<RpcTransactionAcceptance>,
+        inclusions: Vec<RpcTransactionInclusion>,
+    ) -> Self {
+        Self { transaction_id, transaction, acceptance, inclusions }
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "wasm32-sdk")] {
+        use wasm_bindgen::prelude::*;
+
+        #[wasm_bindgen(typescript_custom_section)]
+        const TS_INDEXED_TRANSACTION: &'static str = r#"
+            /**
+             * Acceptance of a transaction by a chain block.
+             *
+             * @category Node RPC
+             */
+            export interface ITransactionAcceptance {
+                acceptingBlockHash : HexString;
+                acceptingDaaScore : bigint;
+                includingBlockHash : HexString;
+                indexWithinBlock : number;
+            }
+
+            /**
+             * Inclusion of a transaction in a block.
+             *
+             * @category Node RPC
+             */
+            export interface ITransactionInclusion {
+                blockHash : HexString;
+                daaScore : bigint;
+                indexWithinBlock : number;
+            }
+
+            /**
+             * Transaction found in the transaction index.
+             *
+             * @category Node RPC
+             */
+            export interface IIndexedTransaction {
+                transactionId : HexString;
+                transaction? : ITransaction;
+                acceptance? : ITransactionAcceptance;
+                inclusions : ITransactionInclusion[];
+            }
+        "#;
+    }
+}
@@ -1,3 +1,8 @@
+//!
+//! Helpers for testing the consumers of the RPC API.
+//!
+
+use crate::{api::rpc::RpcApi, notify::connection::ChannelConnection, *};
 use async_channel::{unbounded, Receiver};
 use async_trait::async_trait;
 use kaspa_notify::events::EVENT_TYPE_ARRAY;
@@ -6,19 +11,22 @@ use kaspa_notify::notifier::{Notifier, Notify};
 use kaspa_notify::scope::Scope;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
-use kaspa_rpc_core::{api::rpc::RpcApi, *};
-use kaspa_rpc_core::{notify::connection::ChannelConnection, RpcResult};
+use parking_lot::Mutex;
 use std::sync::Arc;
 
-pub(super) type RpcCoreNotifier = Notifier<Notification, ChannelConnection>;
+pub type RpcCoreNotifier = Notifier<Notification, ChannelConnection>;
 
-pub(super) struct RpcCoreMock {
+/// RPC service relaying the notifications of its core notifier, optionally serving a fixed block template
+/// and recording the submitted blocks
+pub struct RpcCoreMock {
     core_notifier: Arc<RpcCoreNotifier>,
     _sync_receiver: Receiver<()>,
+    block_template: Option<RpcBlock>,
+    submitted_blocks: Mutex<Vec<RpcBlock>>,
 }
 
 impl RpcCoreMock {
-    pub(super) fn new() -> Self {
+    pub fn new() -> Self {
         let (sync_sender, sync_receiver) = unbounded();
         let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
         let subscription_context = SubscriptionContext::new();
@@ -32,37 +40,49 @@ impl RpcCoreMock {
             policies,
             Some(sync_sender),
         ));
-        Self { core_notifier, _sync_receiver: sync_receiver }
+        Self { core_notifier, _sync_receiver: sync_receiver, block_template: None, submitted_blocks: Default::default() }
+    }
+
+    pub fn with_block_template(block_template: RpcBlock) -> Self {
+        Self { block_template: Some(block_template), ..Self::new() }
     }
 
-    pub(super) fn core_notifier(&self) -> Arc<RpcCoreNotifier> {
+    pub fn submitted_blocks(&self) -> Vec<RpcBlock> {
+        self.submitted_blocks.lock().clone()
+    }
+
+    pub fn core_notifier(&self) -> Arc<RpcCoreNotifier> {
         self.core_notifier.clone()
     }
 
-    pub(super) fn subscription_context(&self) -> SubscriptionContext {
+    pub fn subscription_context(&self) -> SubscriptionContext {
         self.core_notifier.subscription_context().clone()
     }
 
-    #[allow(dead_code)]
-    pub(super) fn notify_new_block_template(&self) -> kaspa_notify::error::Result<()> {
+    pub fn notify_new_block_template(&self) -> kaspa_notify::error::Result<()> {
         let notification = Notification::NewBlockTemplate(NewBlockTemplateNotification {});
         self.core_notifier.notify(notification)
     }
 
-    #[allow(dead_code)]
-    pub(super) async fn notify_complete(&self) {
+    pub async fn notify_complete(&self) {
         assert!(self._sync_receiver.recv().await.is_ok(), "the notifier sync channel is unexpectedly empty and closed");
     }
 
-    pub(super) fn start(&self) {
+    pub fn start(&self) {
         self.core_notifier.clone().start();
     }
 
-    pub(super) async fn join(&self) {
+    pub async fn join(&self) {
         self.core_notifier.join().await.expect("core notifier shutdown")
     }
 }
 
+impl Default for RpcCoreMock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl RpcApi for RpcCoreMock {
     // This fn needs to succeed while the client connects
@@ -98,12 +118,14 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
-    async fn submit_block_call(&self, _request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
-        Err(RpcError::NotImplemented)
+    async fn submit_block_call(&self, request: SubmitBlockRequest) -> RpcResult<SubmitBlockResponse> {
+        self.submitted_blocks.lock().push(request.block);
+        Ok(SubmitBlockResponse { report: SubmitBlockReport::Success })
     }
 
     async fn get_block_template_call(&self, _request: GetBlockTemplateRequest) -> RpcResult<GetBlockTemplateResponse> {
-        Err(RpcError::NotImplemented)
+        let block = self.block_template.clone().ok_or(RpcError::NotImplemented)?;
+        Ok(GetBlockTemplateResponse { block, is_synced: true })
     }
 
     async fn get_peer_addresses_call(&self, _request: GetPeerAddressesRequest) -> RpcResult<GetPeerAddressesResponse> {
@@ -134,6 +156,13 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn submit_transaction_replacement_call(
+        &self,
+        _request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_block_call(&self, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         Err(RpcError::NotImplemented)
     }
@@ -203,6 +232,39 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn list_bans_call(&self, _request: ListBansRequest) -> RpcResult<ListBansResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn save_mempool_call(&self, _request: SaveMempoolRequest) -> RpcResult<SaveMempoolResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn load_mempool_call(&self, _request: LoadMempoolRequest) -> RpcResult<LoadMempoolResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_transactions_call(
+        &self,
+        _request: GetAddressTransactionsRequest,
+    ) -> RpcResult<GetAddressTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_transaction_count_call(
+        &self,
+        _request: GetAddressTransactionCountRequest,
+    ) -> RpcResult<GetAddressTransactionCountResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_merkle_proof_call(
+        &self,
+        _request: GetTransactionMerkleProofRequest,
+    ) -> RpcResult<GetTransactionMerkleProofResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn estimate_network_hashes_per_second_call(
         &self,
         _request: EstimateNetworkHashesPerSecondRequest,
@@ -228,6 +290,18 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(&self, _request: GetTransactionRequest) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transactions_by_ids_call(&self, _request: GetTransactionsByIdsRequest) -> RpcResult<GetTransactionsByIdsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -9,6 +9,7 @@ use kaspa_consensus_client::Transaction;
 use kaspa_consensus_client::UtxoEntryReference;
 use kaspa_rpc_macros::declare_typescript_wasm_interface as declare;
 pub use serde_wasm_bindgen::from_value;
+use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 use workflow_wasm::convert::*;
 use workflow_wasm::extensions::*;
@@ -528,9 +529,18 @@ declare! {
      */
     export interface IBanRequest {
         /**
-         * IPv4 or IPv6 address to ban.
+         * IPv4 or IPv6 address or subnet (in CIDR notation) to ban.
          */
-        ip : string;
+        subnet : string;
+        /**
+         * Duration of the ban in seconds, defaults to the node ban
+         * duration. A duration of 0 bans the subnet permanently.
+         */
+        duration? : bigint;
+        /**
+         * Reason of the ban.
+         */
+        reason? : string;
     }
     "#,
 }
@@ -862,6 +872,253 @@ try_from! ( args: GetDaaScoreTimestampEstimateResponse, IGetDaaScoreTimestampEst
 
 // ---
 
+declare! {
+    IGetTransactionRequest,
+    r#"
+    /**
+     * Retrieves a transaction from the transaction index.
+     * Requires the node to run with `--txindex`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionRequest {
+        transactionId : HexString;
+        includeTransaction : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionRequest, GetTransactionRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionResponse {
+        transaction : IIndexedTransaction;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionResponse, IGetTransactionResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionsByIdsRequest,
+    r#"
+    /**
+     * Retrieves transactions from the transaction index.
+     * Requires the node to run with `--txindex`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsRequest {
+        transactionIds : HexString[];
+        includeTransactions : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionsByIdsRequest, GetTransactionsByIdsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionsByIdsResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionsByIdsResponse {
+        transactions : IIndexedTransaction[];
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionsByIdsResponse, IGetTransactionsByIdsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetTransactionMerkleProofRequest,
+    r#"
+    /**
+     * Retrieves a proof of inclusion of a transaction in a block, along with the headers
+     * linking the block to the chain block merging it.
+     * When `blockHash` is omitted, the block is looked up in the transaction index,
+     * which requires the node to run with `--txindex`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionMerkleProofRequest {
+        transactionId : HexString;
+        blockHash? : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IGetTransactionMerkleProofRequest, GetTransactionMerkleProofRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetTransactionMerkleProofResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetTransactionMerkleProofResponse {
+        proof : ITransactionMerkleProof;
+    }
+    "#,
+}
+
+try_from! ( args: GetTransactionMerkleProofResponse, IGetTransactionMerkleProofResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetFeeEstimateRequest,
+    r#"
+    /**
+     * Retrieves feerate estimations based on the current mempool load.
+     * When `verbose` is set, the mempool feerate histogram is returned as well.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateRequest {
+        verbose? : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IGetFeeEstimateRequest, GetFeeEstimateRequest, {
+    let verbose = args.try_get_bool("verbose")?.unwrap_or(false);
+    Ok(GetFeeEstimateRequest { verbose })
+});
+
+declare! {
+    IGetFeeEstimateResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetFeeEstimateResponse {
+        estimate : IFeeEstimate;
+        verbose? : IFeeEstimateVerboseData;
+    }
+    "#,
+}
+
+try_from! ( args: GetFeeEstimateResponse, IGetFeeEstimateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetAddressTransactionsRequest,
+    r#"
+    /**
+     * Retrieves a page of the transaction history of an address, in ascending accepting DAA score order.
+     * Requires the node to run with `--addresshistory`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetAddressTransactionsRequest {
+        address : Address | string;
+        startDaaScore : bigint;
+        /**
+         * If set, the page starts right after the entry of this transaction at `startDaaScore`.
+         */
+        startTransactionId? : HexString;
+        limit : number;
+    }
+    "#,
+}
+
+try_from! ( args: IGetAddressTransactionsRequest, GetAddressTransactionsRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressTransactionsResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetAddressTransactionsResponse {
+        entries : IAddressHistoryEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: GetAddressTransactionsResponse, IGetAddressTransactionsResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IGetAddressTransactionCountRequest,
+    r#"
+    /**
+     * Retrieves the number of transactions in the history of an address.
+     * Requires the node to run with `--addresshistory`.
+     * 
+     * @category Node RPC
+     */
+    export interface IGetAddressTransactionCountRequest {
+        address : Address | string;
+    }
+    "#,
+}
+
+try_from! ( args: IGetAddressTransactionCountRequest, GetAddressTransactionCountRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetAddressTransactionCountResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface IGetAddressTransactionCountResponse {
+        count : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: GetAddressTransactionCountResponse, IGetAddressTransactionCountResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetCurrentNetworkRequest,
     r#"
@@ -1303,23 +1560,31 @@ declare! {
      */
     export interface ISubmitTransactionRequest {
         transaction : Transaction,
-        allowOrphan? : boolean
+        allowOrphan? : boolean,
+        /**
+         * Replace by fee policy applied if the transaction double spends
+         * some mempool transactions: `forbidden` (default), `permitted`
+         * or `mandatory`.
+         */
+        rbfPolicy? : string
     }
     "#,
 }
 
 try_from! ( args: ISubmitTransactionRequest, SubmitTransactionRequest, {
-    let (transaction, allow_orphan) = if let Some(transaction) = args.try_get_value("transaction")? {
+    let (transaction, allow_orphan, rbf_policy) = if let Some(transaction) = args.try_get_value("transaction")? {
         let allow_orphan = args.try_get_bool("allowOrphan")?.unwrap_or(false);
-        (transaction, allow_orphan)
+        let rbf_policy = args.try_get_string("rbfPolicy")?.map(|policy| RpcRbfPolicy::from_str(&policy)).transpose()?.unwrap_or_default();
+        (transaction, allow_orphan, rbf_policy)
     } else {
-        (args.into(), false)
+        (args.into(), false, RpcRbfPolicy::default())
     };
 
     let request = if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
         SubmitTransactionRequest {
             transaction : transaction.into(),
             allow_orphan,
+            rbf_policy,
         }
     } else {
         from_value(transaction)?
@@ -1337,6 +1602,11 @@ declare! {
      */
     export interface ISubmitTransactionResponse {
         transactionId : HexString;
+        /**
+         * Ids of the mempool transactions evicted by the submitted
+         * transaction if it replaced them by fee.
+         */
+        removedTransactionIds : HexString[];
     }
     "#,
 }
@@ -1347,6 +1617,59 @@ try_from! ( args: SubmitTransactionResponse, ISubmitTransactionResponse, {
 
 // ---
 
+declare! {
+    ISubmitTransactionReplacementRequest,
+    // "ISubmitTransactionReplacementRequest | Transaction",
+    r#"
+    /**
+     * Submit a transaction replacing by fee the mempool transactions it double spends.
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionReplacementRequest {
+        transaction : Transaction,
+    }
+    "#,
+}
+
+try_from! ( args: ISubmitTransactionReplacementRequest, SubmitTransactionReplacementRequest, {
+    let transaction = if let Some(transaction) = args.try_get_value("transaction")? {
+        transaction
+    } else {
+        args.into()
+    };
+
+    let request = if let Ok(transaction) = Transaction::try_owned_from(&transaction) {
+        SubmitTransactionReplacementRequest {
+            transaction : transaction.into(),
+        }
+    } else {
+        from_value(transaction)?
+    };
+    Ok(request)
+});
+
+declare! {
+    ISubmitTransactionReplacementResponse,
+    r#"
+    /**
+     * 
+     * 
+     * @category Node RPC
+     */
+    export interface ISubmitTransactionReplacementResponse {
+        transactionId : HexString;
+        removedTransactionIds : HexString[];
+    }
+    "#,
+}
+
+try_from! ( args: SubmitTransactionReplacementResponse, ISubmitTransactionReplacementResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IUnbanRequest,
     r#"
@@ -1357,9 +1680,10 @@ declare! {
      */
     export interface IUnbanRequest {
         /**
-         * IPv4 or IPv6 address to unban.
+         * IPv4 or IPv6 address or subnet (in CIDR notation) to unban,
+         * exactly as it was banned.
          */
-        ip : string;
+        subnet : string;
     }
     "#,
 }
@@ -1383,3 +1707,123 @@ declare! {
 try_from! ( args: UnbanResponse, IUnbanResponse, {
     Ok(to_value(&args)?.into())
 });
+
+// ---
+
+declare! {
+    IListBansRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IListBansRequest { }
+    "#,
+}
+
+try_from! ( args: IListBansRequest, ListBansRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IListBansResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IListBansResponse {
+        bans : IBanInfo[];
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IBanInfo {
+        subnet : string;
+        /**
+         * Unix timestamp (in milliseconds) of the ban.
+         */
+        createdAt : bigint;
+        /**
+         * Unix timestamp (in milliseconds) at which the ban is lifted,
+         * undefined if the ban is permanent.
+         */
+        expiresAt? : bigint;
+        reason : string;
+    }
+    "#,
+}
+
+try_from! ( args: ListBansResponse, IListBansResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ISaveMempoolRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISaveMempoolRequest { }
+    "#,
+}
+
+try_from! ( args: ISaveMempoolRequest, SaveMempoolRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ISaveMempoolResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ISaveMempoolResponse {
+        transactionCount : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: SaveMempoolResponse, ISaveMempoolResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ILoadMempoolRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ILoadMempoolRequest { }
+    "#,
+}
+
+try_from! ( args: ILoadMempoolRequest, LoadMempoolRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    ILoadMempoolResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface ILoadMempoolResponse {
+        /**
+         * Number of transactions read from the mempool file.
+         */
+        transactionCount : bigint;
+        /**
+         * Number of these transactions in the mempool after revalidation.
+         */
+        acceptedCount : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: LoadMempoolResponse, ILoadMempoolResponse, {
+    Ok(to_value(&args)?.into())
+});
@@ -86,7 +86,7 @@ impl GrpcClient {
     pub const DIRECT_MODE_LISTENER_ID: ListenerId = 0;
 
     pub async fn connect(url: String) -> Result<GrpcClient> {
-        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, Default::default()).await
+        Self::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, None, Default::default()).await
     }
 
     /// Connects to a gRPC server.
@@ -112,6 +112,8 @@ impl GrpcClient {
     ///
     /// `timeout_duration`: request timeout duration
     ///
+    /// `api_key`: when provided is sent as a bearer token in the `authorization` metadata of the connection
+    ///
     /// `counters`: collects some bandwidth metrics
     pub async fn connect_with_args(
         notification_mode: NotificationMode,
@@ -121,6 +123,7 @@ impl GrpcClient {
         connection_event_sender: Option<Sender<ConnectionEvent>>,
         override_handle_stop_notify: bool,
         timeout_duration: Option<u64>,
+        api_key: Option<String>,
         counters: Arc<TowerConnectionCounters>,
     ) -> Result<GrpcClient> {
         let schema = Regex::new(r"^grpc://").unwrap();
@@ -132,6 +135,7 @@ impl GrpcClient {
             connection_event_sender,
             override_handle_stop_notify,
             timeout_duration.unwrap_or(REQUEST_TIMEOUT_DURATION),
+            api_key,
             counters,
         )
         .await?;
@@ -271,6 +275,16 @@ impl RpcApi for GrpcClient {
     route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
     route!(get_coin_supply_call, GetCoinSupply);
     route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    route!(get_transaction_call, GetTransaction);
+    route!(get_transactions_by_ids_call, GetTransactionsByIds);
+    route!(get_fee_estimate_call, GetFeeEstimate);
+    route!(submit_transaction_replacement_call, SubmitTransactionReplacement);
+    route!(list_bans_call, ListBans);
+    route!(save_mempool_call, SaveMempool);
+    route!(load_mempool_call, LoadMempool);
+    route!(get_address_transactions_call, GetAddressTransactions);
+    route!(get_address_transaction_count_call, GetAddressTransactionCount);
+    route!(get_transaction_merkle_proof_call, GetTransactionMerkleProof);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
@@ -351,6 +365,7 @@ pub const CONNECT_TIMEOUT_DURATION: u64 = 20_000;
 pub const REQUEST_TIMEOUT_DURATION: u64 = 5_000;
 pub const TIMEOUT_MONITORING_INTERVAL: u64 = 10_000;
 pub const RECONNECT_INTERVAL: u64 = 2_000;
+pub const AUTHORIZATION_METADATA_KEY: &str = "authorization";
 
 type KaspadRequestSender = async_channel::Sender<KaspadRequest>;
 type KaspadRequestReceiver = async_channel::Receiver<KaspadRequest>;
@@ -420,6 +435,9 @@ struct Inner {
     timeout_timer_interval: u64,
     timeout_duration: u64,
 
+    // API key sent as a bearer token when connecting
+    api_key: Option<String>,
+
     // Connection monitor allowing to reconnect automatically to the server
     connector_is_running: AtomicBool,
     connector_shutdown: DuplexTrigger,
@@ -444,6 +462,7 @@ impl Inner {
         connection_event_sender: Option<Sender<ConnectionEvent>>,
         override_handle_stop_notify: bool,
         timeout_duration: u64,
+        api_key: Option<String>,
         counters: Arc<TowerConnectionCounters>,
     ) -> Self {
         let resolver: DynResolver = match server_features.handle_message_id {
@@ -464,6 +483,7 @@ impl Inner {
             timeout_shutdown: DuplexTrigger::new(),
             timeout_duration,
             timeout_timer_interval: TIMEOUT_MONITORING_INTERVAL,
+            api_key,
             connector_is_running: AtomicBool::new(false),
             connector_shutdown: DuplexTrigger::new(),
             connector_timer_interval: RECONNECT_INTERVAL,
@@ -479,15 +499,22 @@ impl Inner {
         connection_event_sender: Option<Sender<ConnectionEvent>>,
         override_handle_stop_notify: bool,
         timeout_duration: u64,
+        api_key: Option<String>,
         counters: Arc<TowerConnectionCounters>,
     ) -> Result<Arc<Self>> {
         // Request channel
         let (request_sender, request_receiver) = async_channel::unbounded();
 
         // Try to connect to the server
-        let (stream, server_features) =
-            Inner::try_connect(url.clone(), request_sender.clone(), request_receiver.clone(), timeout_duration, counters.clone())
-                .await?;
+        let (stream, server_features) = Inner::try_connect(
+            url.clone(),
+            request_sender.clone(),
+            request_receiver.clone(),
+            timeout_duration,
+            api_key.as_deref(),
+            counters.clone(),
+        )
+        .await?;
 
         // create the inner object
         let inner = Arc::new(Inner::new(
@@ -498,6 +525,7 @@ impl Inner {
             connection_event_sender,
             override_handle_stop_notify,
             timeout_duration,
+            api_key,
             counters,
         ));
 
@@ -517,6 +545,7 @@ impl Inner {
         request_sender: KaspadRequestSender,
         request_receiver: KaspadRequestReceiver,
         request_timeout: u64,
+        api_key: Option<&str>,
         counters: Arc<TowerConnectionCounters>,
     ) -> Result<(Streaming<KaspadResponse>, ServerFeatures)> {
         // gRPC endpoint
@@ -568,8 +597,15 @@ impl Inner {
             }
         };
 
+        // Authenticate the stream with the API key if provided
+        let mut request = tonic::Request::new(request_stream);
+        if let Some(api_key) = api_key {
+            let authorization = format!("Bearer {api_key}").parse().map_err(|_| Error::String("invalid API key".to_string()))?;
+            request.metadata_mut().insert(AUTHORIZATION_METADATA_KEY, authorization);
+        }
+
         // Actual KaspadRequest to KaspadResponse stream
-        let mut stream: Streaming<KaspadResponse> = client.message_stream(request_stream).await?.into_inner();
+        let mut stream: Streaming<KaspadResponse> = client.message_stream(request).await?.into_inner();
 
         // Collect server capabilities as stated in GetInfoResponse
         let mut server_features = ServerFeatures::default();
@@ -611,6 +647,7 @@ impl Inner {
             self.request_sender.clone(),
             self.request_receiver.clone(),
             self.timeout_duration,
+            self.api_key.as_deref(),
             self.counters.clone(),
         )
         .await?;
@@ -0,0 +1,47 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcAddressHistoryEntry, protowire::RpcAddressHistoryEntry, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_daa_score: item.accepting_daa_score,
+        received: item.received,
+        spent: item.spent,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcAddressHistoryByAddressEntry, protowire::RpcAddressHistoryByAddressEntry, {
+    Self { address: item.address.as_ref().map_or("".to_string(), |x| x.into()), entry: Some((&item.entry).into()) }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcAddressHistoryEntry, kaspa_rpc_core::RpcAddressHistoryEntry, {
+    Self::new(
+        RpcHash::from_str(&item.transaction_id)?,
+        RpcHash::from_str(&item.accepting_block_hash)?,
+        item.accepting_daa_score,
+        item.received,
+        item.spent,
+    )
+});
+
+try_from!(item: &protowire::RpcAddressHistoryByAddressEntry, kaspa_rpc_core::RpcAddressHistoryByAddressEntry, {
+    let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
+    Self::new(
+        address,
+        item.entry
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAddressHistoryByAddressEntry".to_string(), "entry".to_string()))?
+            .try_into()?,
+    )
+});
@@ -0,0 +1,66 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::RpcError;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcFeerateBucket, protowire::RpcFeerateBucket, {
+    Self { feerate: item.feerate, estimated_seconds: item.estimated_seconds }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeeEstimate, protowire::RpcFeeEstimate, {
+    Self {
+        priority_bucket: Some((&item.priority_bucket).into()),
+        normal_buckets: item.normal_buckets.iter().map(|x| x.into()).collect(),
+        low_buckets: item.low_buckets.iter().map(|x| x.into()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeerateHistogramBucket, protowire::RpcFeerateHistogramBucket, {
+    Self { feerate: item.feerate, mass: item.mass, count: item.count }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeeEstimateVerboseData, protowire::RpcFeeEstimateVerboseData, {
+    Self {
+        mempool_histogram: item.mempool_histogram.iter().map(|x| x.into()).collect(),
+        mempool_transaction_count: item.mempool_transaction_count,
+        mempool_total_mass: item.mempool_total_mass,
+        network_mass_per_second: item.network_mass_per_second,
+        recent_templates_feerate_floor: item.recent_templates_feerate_floor,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcFeerateBucket, kaspa_rpc_core::RpcFeerateBucket, {
+    Self::new(item.feerate, item.estimated_seconds)
+});
+
+try_from!(item: &protowire::RpcFeeEstimate, kaspa_rpc_core::RpcFeeEstimate, {
+    Self::new(
+        item.priority_bucket
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcFeeEstimate".to_string(), "priority_bucket".to_string()))?
+            .try_into()?,
+        item.normal_buckets.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        item.low_buckets.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    )
+});
+
+try_from!(item: &protowire::RpcFeerateHistogramBucket, kaspa_rpc_core::RpcFeerateHistogramBucket, {
+    Self::new(item.feerate, item.mass, item.count)
+});
+
+try_from!(item: &protowire::RpcFeeEstimateVerboseData, kaspa_rpc_core::RpcFeeEstimateVerboseData, {
+    Self {
+        mempool_histogram: item.mempool_histogram.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        mempool_transaction_count: item.mempool_transaction_count,
+        mempool_total_mass: item.mempool_total_mass,
+        network_mass_per_second: item.network_mass_per_second,
+        recent_templates_feerate_floor: item.recent_templates_feerate_floor,
+    }
+});
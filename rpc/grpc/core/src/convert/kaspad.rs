@@ -57,6 +57,16 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetServerInfo);
     impl_into_kaspad_request!(GetSyncStatus);
     impl_into_kaspad_request!(GetDaaScoreTimestampEstimate);
+    impl_into_kaspad_request!(GetTransaction);
+    impl_into_kaspad_request!(GetTransactionsByIds);
+    impl_into_kaspad_request!(GetFeeEstimate);
+    impl_into_kaspad_request!(SubmitTransactionReplacement);
+    impl_into_kaspad_request!(ListBans);
+    impl_into_kaspad_request!(SaveMempool);
+    impl_into_kaspad_request!(LoadMempool);
+    impl_into_kaspad_request!(GetAddressTransactions);
+    impl_into_kaspad_request!(GetAddressTransactionCount);
+    impl_into_kaspad_request!(GetTransactionMerkleProof);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -66,6 +76,7 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_request!(NotifyAddressHistoryChanged);
 
     macro_rules! impl_into_kaspad_request {
         ($name:tt) => {
@@ -188,6 +199,16 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetServerInfo);
     impl_into_kaspad_response!(GetSyncStatus);
     impl_into_kaspad_response!(GetDaaScoreTimestampEstimate);
+    impl_into_kaspad_response!(GetTransaction);
+    impl_into_kaspad_response!(GetTransactionsByIds);
+    impl_into_kaspad_response!(GetFeeEstimate);
+    impl_into_kaspad_response!(SubmitTransactionReplacement);
+    impl_into_kaspad_response!(ListBans);
+    impl_into_kaspad_response!(SaveMempool);
+    impl_into_kaspad_response!(LoadMempool);
+    impl_into_kaspad_response!(GetAddressTransactions);
+    impl_into_kaspad_response!(GetAddressTransactionCount);
+    impl_into_kaspad_response!(GetTransactionMerkleProof);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -197,6 +218,7 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyAddressHistoryChanged);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
@@ -0,0 +1,32 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash, RpcResult};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcTransactionMerkleProof, protowire::RpcTransactionMerkleProof, {
+    Self {
+        block_hash: item.block_hash.to_string(),
+        transaction_hash: item.transaction_hash.to_string(),
+        index: item.index,
+        merkle_path: item.merkle_path.iter().map(|x| x.to_string()).collect(),
+        headers: item.headers.iter().map(|x| x.into()).collect(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcTransactionMerkleProof, kaspa_rpc_core::RpcTransactionMerkleProof, {
+    Self::new(
+        RpcHash::from_str(&item.block_hash)?,
+        RpcHash::from_str(&item.transaction_hash)?,
+        item.index,
+        item.merkle_path.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        item.headers.iter().map(kaspa_rpc_core::RpcHeader::try_from).collect::<RpcResult<Vec<_>>>()?,
+    )
+});
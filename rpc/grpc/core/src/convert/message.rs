@@ -23,7 +23,7 @@ use kaspa_consensus_core::network::NetworkId;
 use kaspa_core::debug;
 use kaspa_notify::subscription::Command;
 use kaspa_rpc_core::{
-    RpcContextualPeerAddress, RpcError, RpcExtraData, RpcHash, RpcIpAddress, RpcNetworkType, RpcPeerAddress, RpcResult,
+    RpcContextualPeerAddress, RpcError, RpcExtraData, RpcHash, RpcIpAddress, RpcIpSubnet, RpcNetworkType, RpcPeerAddress, RpcResult,
     SubmitBlockRejectReason, SubmitBlockReport,
 };
 use std::str::FromStr;
@@ -136,6 +136,14 @@ from!(item: &kaspa_rpc_core::SubmitBlockReport, RejectReason, {
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcRbfPolicy, protowire::RpcRbfPolicy, {
+    match item {
+        kaspa_rpc_core::RpcRbfPolicy::Forbidden => protowire::RpcRbfPolicy::RbfForbidden,
+        kaspa_rpc_core::RpcRbfPolicy::Permitted => protowire::RpcRbfPolicy::RbfPermitted,
+        kaspa_rpc_core::RpcRbfPolicy::Mandatory => protowire::RpcRbfPolicy::RbfMandatory,
+    }
+});
+
 from!(item: &kaspa_rpc_core::SubmitBlockRequest, protowire::SubmitBlockRequestMessage, {
     Self { block: Some((&item.block).into()), allow_non_daa_blocks: item.allow_non_daa_blocks }
 });
@@ -242,10 +250,29 @@ from!(item: &kaspa_rpc_core::AddPeerRequest, protowire::AddPeerRequestMessage, {
 from!(RpcResult<&kaspa_rpc_core::AddPeerResponse>, protowire::AddPeerResponseMessage);
 
 from!(item: &kaspa_rpc_core::SubmitTransactionRequest, protowire::SubmitTransactionRequestMessage, {
-    Self { transaction: Some((&item.transaction).into()), allow_orphan: item.allow_orphan }
+    Self {
+        transaction: Some((&item.transaction).into()),
+        allow_orphan: item.allow_orphan,
+        rbf_policy: protowire::RpcRbfPolicy::from(&item.rbf_policy) as i32,
+    }
 });
 from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionResponse>, protowire::SubmitTransactionResponseMessage, {
-    Self { transaction_id: item.transaction_id.to_string(), error: None }
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        removed_transaction_ids: item.removed_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::SubmitTransactionReplacementRequest, protowire::SubmitTransactionReplacementRequestMessage, {
+    Self { transaction: Some((&item.transaction).into()) }
+});
+from!(item: RpcResult<&kaspa_rpc_core::SubmitTransactionReplacementResponse>, protowire::SubmitTransactionReplacementResponseMessage, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        removed_transaction_ids: item.removed_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetSubnetworkRequest, protowire::GetSubnetworkRequestMessage, {
@@ -352,12 +379,60 @@ from!(item: RpcResult<&kaspa_rpc_core::GetSinkBlueScoreResponse>, protowire::Get
     Self { blue_score: item.blue_score, error: None }
 });
 
-from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::BanRequest, protowire::BanRequestMessage, {
+    Self {
+        ip: item.subnet.to_string(),
+        duration: item.duration.unwrap_or_default(),
+        permanent: item.duration == Some(0),
+        reason: item.reason.clone().unwrap_or_default(),
+    }
+});
 from!(_item: RpcResult<&kaspa_rpc_core::BanResponse>, protowire::BanResponseMessage, { Self { error: None } });
 
-from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, { Self { ip: item.ip.to_string() } });
+from!(item: &kaspa_rpc_core::UnbanRequest, protowire::UnbanRequestMessage, { Self { ip: item.subnet.to_string() } });
 from!(_item: RpcResult<&kaspa_rpc_core::UnbanResponse>, protowire::UnbanResponseMessage, { Self { error: None } });
 
+from!(&kaspa_rpc_core::ListBansRequest, protowire::ListBansRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::ListBansResponse>, protowire::ListBansResponseMessage, {
+    Self { bans: item.bans.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::SaveMempoolRequest, protowire::SaveMempoolRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::SaveMempoolResponse>, protowire::SaveMempoolResponseMessage, {
+    Self { transaction_count: item.transaction_count, error: None }
+});
+
+from!(&kaspa_rpc_core::LoadMempoolRequest, protowire::LoadMempoolRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::LoadMempoolResponse>, protowire::LoadMempoolResponseMessage, {
+    Self { transaction_count: item.transaction_count, accepted_count: item.accepted_count, error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetAddressTransactionsRequest, protowire::GetAddressTransactionsRequestMessage, {
+    Self {
+        address: (&item.address).into(),
+        start_daa_score: item.start_daa_score,
+        start_transaction_id: item.start_transaction_id.map_or(Default::default(), |x| x.to_string()),
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetAddressTransactionsResponse>, protowire::GetAddressTransactionsResponseMessage, {
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetAddressTransactionCountRequest, protowire::GetAddressTransactionCountRequestMessage, {
+    Self { address: (&item.address).into() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetAddressTransactionCountResponse>, protowire::GetAddressTransactionCountResponseMessage, {
+    Self { count: item.count, error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionMerkleProofRequest, protowire::GetTransactionMerkleProofRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), block_hash: item.block_hash.map_or(Default::default(), |x| x.to_string()) }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionMerkleProofResponse>, protowire::GetTransactionMerkleProofResponseMessage, {
+    Self { proof: Some((&item.proof).into()), error: None }
+});
+
 from!(item: &kaspa_rpc_core::EstimateNetworkHashesPerSecondRequest, protowire::EstimateNetworkHashesPerSecondRequestMessage, {
     Self { window_size: item.window_size, start_hash: item.start_hash.map_or(Default::default(), |x| x.to_string()) }
 });
@@ -394,6 +469,28 @@ from!(item: RpcResult<&kaspa_rpc_core::GetDaaScoreTimestampEstimateResponse>, pr
     Self { timestamps: item.timestamps.clone(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::GetTransactionRequest, protowire::GetTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), include_transaction: item.include_transaction }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionResponse>, protowire::GetTransactionResponseMessage, {
+    Self { transaction: Some((&item.transaction).into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetTransactionsByIdsRequest, protowire::GetTransactionsByIdsRequestMessage, {
+    Self {
+        transaction_ids: item.transaction_ids.iter().map(|x| x.to_string()).collect(),
+        include_transactions: item.include_transactions,
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetTransactionsByIdsResponse>, protowire::GetTransactionsByIdsResponseMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetFeeEstimateRequest, protowire::GetFeeEstimateRequestMessage, { Self { verbose: item.verbose } });
+from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateResponse>, protowire::GetFeeEstimateResponseMessage, {
+    Self { estimate: Some((&item.estimate).into()), verbose: item.verbose.as_ref().map(|x| x.into()), error: None }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -478,10 +575,23 @@ from!(item: &kaspa_rpc_core::NotifySinkBlueScoreChangedRequest, protowire::Notif
 });
 from!(RpcResult<&kaspa_rpc_core::NotifySinkBlueScoreChangedResponse>, protowire::NotifySinkBlueScoreChangedResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyAddressHistoryChangedRequest, protowire::NotifyAddressHistoryChangedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyAddressHistoryChangedResponse>, protowire::NotifyAddressHistoryChangedResponseMessage);
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
 
+from!(item: protowire::RpcRbfPolicy, kaspa_rpc_core::RpcRbfPolicy, {
+    match item {
+        protowire::RpcRbfPolicy::RbfForbidden => kaspa_rpc_core::RpcRbfPolicy::Forbidden,
+        protowire::RpcRbfPolicy::RbfPermitted => kaspa_rpc_core::RpcRbfPolicy::Permitted,
+        protowire::RpcRbfPolicy::RbfMandatory => kaspa_rpc_core::RpcRbfPolicy::Mandatory,
+    }
+});
+
 from!(item: RejectReason, kaspa_rpc_core::SubmitBlockReport, {
     match item {
         RejectReason::None => kaspa_rpc_core::SubmitBlockReport::Success,
@@ -641,10 +751,32 @@ try_from!(item: &protowire::SubmitTransactionRequestMessage, kaspa_rpc_core::Sub
             .ok_or_else(|| RpcError::MissingRpcFieldError("SubmitTransactionRequestMessage".to_string(), "transaction".to_string()))?
             .try_into()?,
         allow_orphan: item.allow_orphan,
+        rbf_policy: protowire::RpcRbfPolicy::try_from(item.rbf_policy).map_err(|_| RpcError::PrimitiveToEnumConversionError)?.into(),
     }
 });
 try_from!(item: &protowire::SubmitTransactionResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionResponse>, {
-    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        removed_transaction_ids: item.removed_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::SubmitTransactionReplacementRequestMessage, kaspa_rpc_core::SubmitTransactionReplacementRequest, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| {
+                RpcError::MissingRpcFieldError("SubmitTransactionReplacementRequestMessage".to_string(), "transaction".to_string())
+            })?
+            .try_into()?,
+    }
+});
+try_from!(item: &protowire::SubmitTransactionReplacementResponseMessage, RpcResult<kaspa_rpc_core::SubmitTransactionReplacementResponse>, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        removed_transaction_ids: item.removed_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+    }
 });
 
 try_from!(item: &protowire::GetSubnetworkRequestMessage, kaspa_rpc_core::GetSubnetworkRequest, {
@@ -746,12 +878,74 @@ try_from!(item: &protowire::GetSinkBlueScoreResponseMessage, RpcResult<kaspa_rpc
     Self { blue_score: item.blue_score }
 });
 
-try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::BanRequestMessage, kaspa_rpc_core::BanRequest, {
+    Self {
+        subnet: RpcIpSubnet::from_str(&item.ip)?,
+        duration: if item.permanent { Some(0) } else { (item.duration > 0).then_some(item.duration) },
+        reason: (!item.reason.is_empty()).then(|| item.reason.clone()),
+    }
+});
 try_from!(&protowire::BanResponseMessage, RpcResult<kaspa_rpc_core::BanResponse>);
 
-try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, { Self { ip: RpcIpAddress::from_str(&item.ip)? } });
+try_from!(item: &protowire::UnbanRequestMessage, kaspa_rpc_core::UnbanRequest, { Self { subnet: RpcIpSubnet::from_str(&item.ip)? } });
 try_from!(&protowire::UnbanResponseMessage, RpcResult<kaspa_rpc_core::UnbanResponse>);
 
+try_from!(&protowire::ListBansRequestMessage, kaspa_rpc_core::ListBansRequest);
+try_from!(item: &protowire::ListBansResponseMessage, RpcResult<kaspa_rpc_core::ListBansResponse>, {
+    Self { bans: item.bans.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::SaveMempoolRequestMessage, kaspa_rpc_core::SaveMempoolRequest);
+try_from!(item: &protowire::SaveMempoolResponseMessage, RpcResult<kaspa_rpc_core::SaveMempoolResponse>, {
+    Self { transaction_count: item.transaction_count }
+});
+
+try_from!(&protowire::LoadMempoolRequestMessage, kaspa_rpc_core::LoadMempoolRequest);
+try_from!(item: &protowire::LoadMempoolResponseMessage, RpcResult<kaspa_rpc_core::LoadMempoolResponse>, {
+    Self { transaction_count: item.transaction_count, accepted_count: item.accepted_count }
+});
+
+try_from!(item: &protowire::GetAddressTransactionsRequestMessage, kaspa_rpc_core::GetAddressTransactionsRequest, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        start_daa_score: item.start_daa_score,
+        start_transaction_id: if item.start_transaction_id.is_empty() {
+            None
+        } else {
+            Some(RpcHash::from_str(&item.start_transaction_id)?)
+        },
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetAddressTransactionsResponseMessage, RpcResult<kaspa_rpc_core::GetAddressTransactionsResponse>, {
+    Self {
+        entries: item.entries.iter().map(kaspa_rpc_core::RpcAddressHistoryEntry::try_from).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetAddressTransactionCountRequestMessage, kaspa_rpc_core::GetAddressTransactionCountRequest, {
+    Self { address: item.address.as_str().try_into()? }
+});
+try_from!(item: &protowire::GetAddressTransactionCountResponseMessage, RpcResult<kaspa_rpc_core::GetAddressTransactionCountResponse>, {
+    Self { count: item.count }
+});
+
+try_from!(item: &protowire::GetTransactionMerkleProofRequestMessage, kaspa_rpc_core::GetTransactionMerkleProofRequest, {
+    Self {
+        transaction_id: kaspa_rpc_core::RpcTransactionId::from_str(&item.transaction_id)?,
+        block_hash: if item.block_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.block_hash)?) },
+    }
+});
+try_from!(item: &protowire::GetTransactionMerkleProofResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionMerkleProofResponse>, {
+    Self {
+        proof: item
+            .proof
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionMerkleProofResponseMessage".to_string(), "proof".to_string()))?
+            .try_into()?,
+    }
+});
+
 try_from!(item: &protowire::EstimateNetworkHashesPerSecondRequestMessage, kaspa_rpc_core::EstimateNetworkHashesPerSecondRequest, {
     Self {
         window_size: item.window_size,
@@ -791,6 +985,54 @@ try_from!(item: &protowire::GetDaaScoreTimestampEstimateResponseMessage, RpcResu
     Self { timestamps: item.timestamps.clone() }
 });
 
+try_from!(item: &protowire::GetTransactionRequestMessage, kaspa_rpc_core::GetTransactionRequest, {
+    Self {
+        transaction_id: kaspa_rpc_core::RpcTransactionId::from_str(&item.transaction_id)?,
+        include_transaction: item.include_transaction,
+    }
+});
+try_from!(item: &protowire::GetTransactionResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionResponse>, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetTransactionResponseMessage".to_string(), "transaction".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::GetTransactionsByIdsRequestMessage, kaspa_rpc_core::GetTransactionsByIdsRequest, {
+    Self {
+        transaction_ids: item
+            .transaction_ids
+            .iter()
+            .map(|x| kaspa_rpc_core::RpcTransactionId::from_str(x))
+            .collect::<Result<Vec<_>, _>>()?,
+        include_transactions: item.include_transactions,
+    }
+});
+try_from!(item: &protowire::GetTransactionsByIdsResponseMessage, RpcResult<kaspa_rpc_core::GetTransactionsByIdsResponse>, {
+    Self {
+        transactions: item
+            .transactions
+            .iter()
+            .map(kaspa_rpc_core::RpcIndexedTransaction::try_from)
+            .collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::GetFeeEstimateRequestMessage, kaspa_rpc_core::GetFeeEstimateRequest, { Self { verbose: item.verbose } });
+try_from!(item: &protowire::GetFeeEstimateResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateResponse>, {
+    Self {
+        estimate: item
+            .estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateResponseMessage".to_string(), "estimate".to_string()))?
+            .try_into()?,
+        verbose: item.verbose.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -880,6 +1122,11 @@ try_from!(item: &protowire::NotifySinkBlueScoreChangedRequestMessage, kaspa_rpc_
 });
 try_from!(&protowire::NotifySinkBlueScoreChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifySinkBlueScoreChangedResponse>);
 
+try_from!(item: &protowire::NotifyAddressHistoryChangedRequestMessage, kaspa_rpc_core::NotifyAddressHistoryChangedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyAddressHistoryChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifyAddressHistoryChangedResponse>);
+
 // ----------------------------------------------------------------------------
 // Unit tests
 // ----------------------------------------------------------------------------
@@ -1,11 +1,15 @@
 pub mod address;
+pub mod address_history;
 pub mod block;
 pub mod error;
+pub mod feerate_estimate;
 pub mod header;
 pub mod kaspad;
 pub mod mempool;
+pub mod merkle_proof;
 pub mod message;
 pub mod metrics;
 pub mod notification;
 pub mod peer;
 pub mod tx;
+pub mod txindex;
@@ -2,12 +2,13 @@ use crate::protowire::{
     kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
 };
 use crate::protowire::{
-    FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
-    PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
-    StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
-    VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
+    AddressHistoryChangedNotificationMessage, FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifyPruningPointUtxoSetOverrideResponseMessage,
+    NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage, PruningPointUtxoSetOverrideNotificationMessage,
+    SinkBlueScoreChangedNotificationMessage, StopNotifyingPruningPointUtxoSetOverrideRequestMessage,
+    StopNotifyingPruningPointUtxoSetOverrideResponseMessage, StopNotifyingUtxosChangedRequestMessage,
+    StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage, VirtualChainChangedNotificationMessage,
+    VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use kaspa_notify::subscription::Command;
@@ -34,6 +35,7 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         }
+        Notification::AddressHistoryChanged(ref notification) => Payload::AddressHistoryChangedNotification(notification.into()),
     }
 });
 
@@ -74,6 +76,13 @@ from!(item: &kaspa_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&kaspa_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: &kaspa_rpc_core::AddressHistoryChangedNotification, AddressHistoryChangedNotificationMessage, {
+    Self {
+        added: item.added.iter().map(|x| x.into()).collect::<Vec<_>>(),
+        removed: item.removed.iter().map(|x| x.into()).collect::<Vec<_>>(),
+    }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -117,6 +126,7 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::AddressHistoryChangedNotification(ref notification) => Notification::AddressHistoryChanged(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +181,13 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, kaspa_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, kaspa_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+try_from!(item: &AddressHistoryChangedNotificationMessage, kaspa_rpc_core::AddressHistoryChangedNotification, {
+    Self {
+        added: Arc::new(item.added.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?),
+        removed: Arc::new(item.removed.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?),
+    }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
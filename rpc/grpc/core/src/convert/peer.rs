@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::{RpcError, RpcNodeId, RpcPeerAddress};
+use kaspa_rpc_core::{RpcError, RpcIpSubnet, RpcNodeId, RpcPeerAddress};
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -25,6 +25,15 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
 from!(item: &kaspa_rpc_core::RpcPeerAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 from!(item: &kaspa_rpc_core::RpcIpAddress, protowire::GetPeerAddressesKnownAddressMessage, { Self { addr: item.to_string() } });
 
+from!(item: &kaspa_rpc_core::RpcBanInfo, protowire::RpcBanInfo, {
+    Self {
+        subnet: item.subnet.to_string(),
+        created_at: item.created_at,
+        expires_at: item.expires_at.unwrap_or_default(),
+        reason: item.reason.clone(),
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -45,3 +54,12 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
 
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, kaspa_rpc_core::RpcPeerAddress, { Self::from_str(&item.addr)? });
 try_from!(item: &protowire::GetPeerAddressesKnownAddressMessage, kaspa_rpc_core::RpcIpAddress, { Self::from_str(&item.addr)? });
+
+try_from!(item: &protowire::RpcBanInfo, kaspa_rpc_core::RpcBanInfo, {
+    Self {
+        subnet: RpcIpSubnet::from_str(&item.subnet)?,
+        created_at: item.created_at,
+        expires_at: (item.expires_at > 0).then_some(item.expires_at),
+        reason: item.reason.clone(),
+    }
+});
@@ -0,0 +1,56 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcTransactionAcceptance, protowire::RpcTransactionAcceptance, {
+    Self {
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_daa_score: item.accepting_daa_score,
+        including_block_hash: item.including_block_hash.to_string(),
+        index_within_block: item.index_within_block,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcTransactionInclusion, protowire::RpcTransactionInclusion, {
+    Self { block_hash: item.block_hash.to_string(), daa_score: item.daa_score, index_within_block: item.index_within_block }
+});
+
+from!(item: &kaspa_rpc_core::RpcIndexedTransaction, protowire::RpcIndexedTransaction, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        transaction: item.transaction.as_ref().map(|x| x.into()),
+        acceptance: item.acceptance.as_ref().map(|x| x.into()),
+        inclusions: item.inclusions.iter().map(|x| x.into()).collect::<Vec<_>>(),
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcTransactionAcceptance, kaspa_rpc_core::RpcTransactionAcceptance, {
+    Self::new(
+        RpcHash::from_str(&item.accepting_block_hash)?,
+        item.accepting_daa_score,
+        RpcHash::from_str(&item.including_block_hash)?,
+        item.index_within_block,
+    )
+});
+
+try_from!(item: &protowire::RpcTransactionInclusion, kaspa_rpc_core::RpcTransactionInclusion, {
+    Self::new(RpcHash::from_str(&item.block_hash)?, item.daa_score, item.index_within_block)
+});
+
+try_from!(item: &protowire::RpcIndexedTransaction, kaspa_rpc_core::RpcIndexedTransaction, {
+    Self::new(
+        RpcHash::from_str(&item.transaction_id)?,
+        item.transaction.as_ref().map(|x| x.try_into()).transpose()?,
+        item.acceptance.as_ref().map(|x| x.try_into()).transpose()?,
+        item.inclusions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    )
+});
@@ -1,10 +1,10 @@
 use kaspa_notify::{scope::Scope, subscription::Command};
 
 use crate::protowire::{
-    kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyBlockAddedRequestMessage,
-    NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
-    NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage, NotifyVirtualChainChangedRequestMessage,
-    NotifyVirtualDaaScoreChangedRequestMessage,
+    kaspad_request, kaspad_response, KaspadRequest, KaspadResponse, NotifyAddressHistoryChangedRequestMessage,
+    NotifyBlockAddedRequestMessage, NotifyFinalityConflictRequestMessage, NotifyNewBlockTemplateRequestMessage,
+    NotifyPruningPointUtxoSetOverrideRequestMessage, NotifySinkBlueScoreChangedRequestMessage, NotifyUtxosChangedRequestMessage,
+    NotifyVirtualChainChangedRequestMessage, NotifyVirtualDaaScoreChangedRequestMessage,
 };
 
 impl KaspadRequest {
@@ -64,6 +64,11 @@ impl kaspad_request::Payload {
                     command: command.into(),
                 })
             }
+            Scope::AddressHistoryChanged(_) => {
+                kaspad_request::Payload::NotifyAddressHistoryChangedRequest(NotifyAddressHistoryChangedRequestMessage {
+                    command: command.into(),
+                })
+            }
         }
     }
 
@@ -79,6 +84,7 @@ impl kaspad_request::Payload {
                 | Payload::NotifyVirtualDaaScoreChangedRequest(_)
                 | Payload::NotifyPruningPointUtxoSetOverrideRequest(_)
                 | Payload::NotifyNewBlockTemplateRequest(_)
+                | Payload::NotifyAddressHistoryChangedRequest(_)
                 | Payload::StopNotifyingUtxosChangedRequest(_)
                 | Payload::StopNotifyingPruningPointUtxoSetOverrideRequest(_)
         )
@@ -108,6 +114,7 @@ impl kaspad_response::Payload {
             Payload::VirtualDaaScoreChangedNotification(_) => true,
             Payload::PruningPointUtxoSetOverrideNotification(_) => true,
             Payload::NewBlockTemplateNotification(_) => true,
+            Payload::AddressHistoryChangedNotification(_) => true,
             _ => false,
         }
     }
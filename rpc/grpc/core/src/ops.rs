@@ -1,5 +1,5 @@
 use crate::protowire::{kaspad_request::Payload as RequestPayload, kaspad_response::Payload as ResponsePayload, *};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{api::ops::RpcApiOps, RpcError};
 use workflow_core::enums::Describe;
 
 macro_rules! payload_type_enum {
@@ -81,6 +81,16 @@ pub enum KaspadPayloadOps {
     GetServerInfo,
     GetSyncStatus,
     GetDaaScoreTimestampEstimate,
+    GetTransaction,
+    GetTransactionsByIds,
+    GetFeeEstimate,
+    SubmitTransactionReplacement,
+    ListBans,
+    SaveMempool,
+    LoadMempool,
+    GetAddressTransactions,
+    GetAddressTransactionCount,
+    GetTransactionMerkleProof,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -91,6 +101,7 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyAddressHistoryChanged,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
@@ -101,3 +112,65 @@ pub enum KaspadPayloadOps {
     // The conversion from a notification ResponsePayload into KaspadPayloadOps fails.
 }
 }
+
+impl From<KaspadPayloadOps> for RpcApiOps {
+    fn from(item: KaspadPayloadOps) -> Self {
+        match item {
+            KaspadPayloadOps::SubmitBlock => RpcApiOps::SubmitBlock,
+            KaspadPayloadOps::GetBlockTemplate => RpcApiOps::GetBlockTemplate,
+            KaspadPayloadOps::GetCurrentNetwork => RpcApiOps::GetCurrentNetwork,
+            KaspadPayloadOps::GetBlock => RpcApiOps::GetBlock,
+            KaspadPayloadOps::GetBlocks => RpcApiOps::GetBlocks,
+            KaspadPayloadOps::GetInfo => RpcApiOps::GetInfo,
+            KaspadPayloadOps::Shutdown => RpcApiOps::Shutdown,
+            KaspadPayloadOps::GetPeerAddresses => RpcApiOps::GetPeerAddresses,
+            KaspadPayloadOps::GetSink => RpcApiOps::GetSink,
+            KaspadPayloadOps::GetMempoolEntry => RpcApiOps::GetMempoolEntry,
+            KaspadPayloadOps::GetMempoolEntries => RpcApiOps::GetMempoolEntries,
+            KaspadPayloadOps::GetConnectedPeerInfo => RpcApiOps::GetConnectedPeerInfo,
+            KaspadPayloadOps::AddPeer => RpcApiOps::AddPeer,
+            KaspadPayloadOps::SubmitTransaction => RpcApiOps::SubmitTransaction,
+            KaspadPayloadOps::GetSubnetwork => RpcApiOps::GetSubnetwork,
+            KaspadPayloadOps::GetVirtualChainFromBlock => RpcApiOps::GetVirtualChainFromBlock,
+            KaspadPayloadOps::GetBlockCount => RpcApiOps::GetBlockCount,
+            KaspadPayloadOps::GetBlockDagInfo => RpcApiOps::GetBlockDagInfo,
+            KaspadPayloadOps::ResolveFinalityConflict => RpcApiOps::ResolveFinalityConflict,
+            KaspadPayloadOps::GetHeaders => RpcApiOps::GetHeaders,
+            KaspadPayloadOps::GetUtxosByAddresses => RpcApiOps::GetUtxosByAddresses,
+            KaspadPayloadOps::GetBalanceByAddress => RpcApiOps::GetBalanceByAddress,
+            KaspadPayloadOps::GetBalancesByAddresses => RpcApiOps::GetBalancesByAddresses,
+            KaspadPayloadOps::GetSinkBlueScore => RpcApiOps::GetSinkBlueScore,
+            KaspadPayloadOps::Ban => RpcApiOps::Ban,
+            KaspadPayloadOps::Unban => RpcApiOps::Unban,
+            KaspadPayloadOps::EstimateNetworkHashesPerSecond => RpcApiOps::EstimateNetworkHashesPerSecond,
+            KaspadPayloadOps::GetMempoolEntriesByAddresses => RpcApiOps::GetMempoolEntriesByAddresses,
+            KaspadPayloadOps::GetCoinSupply => RpcApiOps::GetCoinSupply,
+            KaspadPayloadOps::Ping => RpcApiOps::Ping,
+            KaspadPayloadOps::GetMetrics => RpcApiOps::GetMetrics,
+            KaspadPayloadOps::GetServerInfo => RpcApiOps::GetServerInfo,
+            KaspadPayloadOps::GetSyncStatus => RpcApiOps::GetSyncStatus,
+            KaspadPayloadOps::GetDaaScoreTimestampEstimate => RpcApiOps::GetDaaScoreTimestampEstimate,
+            KaspadPayloadOps::GetTransaction => RpcApiOps::GetTransaction,
+            KaspadPayloadOps::GetTransactionsByIds => RpcApiOps::GetTransactionsByIds,
+            KaspadPayloadOps::GetFeeEstimate => RpcApiOps::GetFeeEstimate,
+            KaspadPayloadOps::SubmitTransactionReplacement => RpcApiOps::SubmitTransactionReplacement,
+            KaspadPayloadOps::ListBans => RpcApiOps::ListBans,
+            KaspadPayloadOps::SaveMempool => RpcApiOps::SaveMempool,
+            KaspadPayloadOps::LoadMempool => RpcApiOps::LoadMempool,
+            KaspadPayloadOps::GetAddressTransactions => RpcApiOps::GetAddressTransactions,
+            KaspadPayloadOps::GetAddressTransactionCount => RpcApiOps::GetAddressTransactionCount,
+            KaspadPayloadOps::GetTransactionMerkleProof => RpcApiOps::GetTransactionMerkleProof,
+            KaspadPayloadOps::NotifyBlockAdded => RpcApiOps::NotifyBlockAdded,
+            KaspadPayloadOps::NotifyNewBlockTemplate => RpcApiOps::NotifyNewBlockTemplate,
+            KaspadPayloadOps::NotifyFinalityConflict => RpcApiOps::NotifyFinalityConflict,
+            KaspadPayloadOps::NotifyUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            KaspadPayloadOps::NotifySinkBlueScoreChanged => RpcApiOps::NotifySinkBlueScoreChanged,
+            KaspadPayloadOps::NotifyPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+            KaspadPayloadOps::NotifyVirtualDaaScoreChanged => RpcApiOps::NotifyVirtualDaaScoreChanged,
+            KaspadPayloadOps::NotifyVirtualChainChanged => RpcApiOps::NotifyVirtualChainChanged,
+            KaspadPayloadOps::NotifyAddressHistoryChanged => RpcApiOps::NotifyAddressHistoryChanged,
+            KaspadPayloadOps::StopNotifyingUtxosChanged => RpcApiOps::NotifyUtxosChanged,
+            KaspadPayloadOps::StopNotifyingPruningPointUtxoSetOverride => RpcApiOps::NotifyPruningPointUtxoSetOverride,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::{connection_handler::ConnectionHandler, manager::Manager};
 use kaspa_core::debug;
 use kaspa_notify::{notifier::Notifier, subscription::context::SubscriptionContext};
 use kaspa_rpc_core::{api::rpc::DynRpcService, notify::connection::ChannelConnection, Notification, RpcResult};
+use kaspa_rpc_service::tls::RpcTlsIdentity;
 use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::{ops::Deref, sync::Arc};
@@ -40,6 +41,7 @@ impl Adaptor {
         subscription_context: SubscriptionContext,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        tls_identity: Option<RpcTlsIdentity>,
     ) -> Arc<Self> {
         let (manager_sender, manager_receiver) = mpsc_channel(Self::manager_channel_size());
         let connection_handler = ConnectionHandler::new(
@@ -51,7 +53,7 @@ impl Adaptor {
             broadcasters,
             counters,
         );
-        let server_termination = connection_handler.serve(serve_address);
+        let server_termination = connection_handler.serve(serve_address, tls_identity);
         let adaptor = Arc::new(Adaptor::new(Some(server_termination), connection_handler, manager, serve_address));
         adaptor.manager.clone().start_event_loop(manager_receiver);
         adaptor.start();
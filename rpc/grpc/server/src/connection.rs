@@ -69,6 +69,9 @@ struct Inner {
     /// The socket address of this client
     net_address: SocketAddr,
 
+    /// The API key presented by this client when opening the message stream
+    api_key: Option<String>,
+
     /// The outgoing route for sending messages to this client
     outgoing_route: GrpcSender,
 
@@ -215,6 +218,7 @@ impl Display for Connection {
 impl Connection {
     pub(crate) fn new(
         net_address: SocketAddr,
+        api_key: Option<String>,
         server_context: ServerContext,
         interface: Arc<Interface>,
         manager_sender: MpscSender<ManagerEvent>,
@@ -227,6 +231,7 @@ impl Connection {
             inner: Arc::new(Inner {
                 connection_id: Uuid::new_v4(),
                 net_address,
+                api_key,
                 outgoing_route,
                 manager_sender,
                 server_context,
@@ -310,6 +315,10 @@ impl Connection {
         self.inner.net_address
     }
 
+    pub fn api_key(&self) -> Option<&str> {
+        self.inner.api_key.as_deref()
+    }
+
     pub fn identity(&self) -> ConnectionId {
         self.inner.connection_id
     }
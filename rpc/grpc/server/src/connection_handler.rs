@@ -26,6 +26,7 @@ use kaspa_rpc_core::{
     notify::{channel::NotificationChannel, connection::ChannelConnection},
     Notification, RpcResult,
 };
+use kaspa_rpc_service::tls::RpcTlsIdentity;
 use kaspa_utils::networking::NetAddress;
 use kaspa_utils_tower::{
     counters::TowerConnectionCounters,
@@ -46,7 +47,11 @@ use tokio::{
     time::timeout,
 };
 use tokio_stream::{wrappers::ReceiverStream, StreamExt};
-use tonic::{codec::CompressionEncoding, transport::Server as TonicServer, Request, Response};
+use tonic::{
+    codec::CompressionEncoding,
+    transport::{Identity, Server as TonicServer, ServerTlsConfig},
+    Request, Response,
+};
 
 #[derive(Clone)]
 pub struct ServerContext {
@@ -80,6 +85,10 @@ pub struct ConnectionHandler {
 
 const GRPC_SERVER: &str = "grpc-server";
 
+/// The request metadata key carrying the client API key as a bearer token
+pub const AUTHORIZATION_METADATA_KEY: &str = "authorization";
+const BEARER_PREFIX: &str = "Bearer ";
+
 impl ConnectionHandler {
     pub(crate) fn new(
         network_bps: u64,
@@ -121,12 +130,12 @@ impl ConnectionHandler {
         Self { manager_sender, server_context, interface, running, counters }
     }
 
-    /// Launches a gRPC server listener loop
-    pub(crate) fn serve(&self, serve_address: NetAddress) -> OneshotSender<()> {
+    /// Launches a gRPC server listener loop, serving TLS if an identity is provided
+    pub(crate) fn serve(&self, serve_address: NetAddress, tls_identity: Option<RpcTlsIdentity>) -> OneshotSender<()> {
         let (termination_sender, termination_receiver) = oneshot_channel::<()>();
         let (signal_sender, signal_receiver) = oneshot_channel::<()>();
         let connection_handler = self.clone();
-        info!("GRPC Server starting on: {}", serve_address);
+        info!("GRPC Server starting on: {}{}", serve_address, if tls_identity.is_some() { " (TLS)" } else { "" });
 
         let bytes_tx = self.counters.bytes_tx.clone();
         let bytes_rx = self.counters.bytes_rx.clone();
@@ -141,7 +150,14 @@ impl ConnectionHandler {
             // TODO: check whether we should set tcp_keepalive
             // const GRPC_KEEP_ALIVE_PING_INTERVAL: Duration = Duration::from_secs(5);
             // const GRPC_KEEP_ALIVE_PING_TIMEOUT: Duration = Duration::from_secs(120);
-            let serve_result = TonicServer::builder()
+            let mut server_builder = TonicServer::builder();
+            if let Some(tls_identity) = tls_identity {
+                let tls_config = ServerTlsConfig::new().identity(Identity::from_pem(tls_identity.cert, tls_identity.key));
+                server_builder = server_builder
+                    .tls_config(tls_config)
+                    .unwrap_or_else(|err| panic!("GRPC Server {serve_address} TLS setup failed with error: {err:?}"));
+            }
+            let serve_result = server_builder
                 // .http2_keepalive_interval(Some(GRPC_KEEP_ALIVE_PING_INTERVAL))
                 // .http2_keepalive_timeout(Some(GRPC_KEEP_ALIVE_PING_TIMEOUT))
                 .layer(measure_request_body_size_layer(bytes_rx, |b| b))
@@ -254,6 +270,14 @@ impl Rpc for ConnectionHandler {
 
         debug!("GRPC, Incoming message stream from {:?}", remote_address);
 
+        // Extract the API key the client may provide as a bearer token
+        let api_key = request
+            .metadata()
+            .get(AUTHORIZATION_METADATA_KEY)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+            .map(|key| key.trim().to_string());
+
         // Build the in/out pipes
         let (outgoing_route, outgoing_receiver) = mpsc_channel(Self::outgoing_route_channel_size());
         let incoming_stream = request.into_inner();
@@ -261,6 +285,7 @@ impl Rpc for ConnectionHandler {
         // Build the connection object
         let connection = Connection::new(
             remote_address,
+            api_key,
             self.server_context(),
             self.interface(),
             self.manager_sender(),
@@ -75,6 +75,16 @@ impl Factory {
                 GetServerInfo,
                 GetSyncStatus,
                 GetDaaScoreTimestampEstimate,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetFeeEstimate,
+                SubmitTransactionReplacement,
+                ListBans,
+                SaveMempool,
+                LoadMempool,
+                GetAddressTransactions,
+                GetAddressTransactionCount,
+                GetTransactionMerkleProof,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -83,6 +93,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyAddressHistoryChanged,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -35,7 +35,10 @@ impl RequestHandler {
 
     pub async fn handle_request(&self, request: KaspadRequest) -> GrpcServerResult<KaspadResponse> {
         let id = request.id;
-        let mut response = self.method.call(self.server_ctx.clone(), self.connection.clone(), request).await?;
+        let mut response = match self.server_ctx.core_service.authorize(self.connection.api_key(), self.rpc_op.into()) {
+            Ok(()) => self.method.call(self.server_ctx.clone(), self.connection.clone(), request).await?,
+            Err(err) => KaspadResponse { id, payload: Some(self.rpc_op.to_error_response(err)) },
+        };
         response.id = id;
         Ok(response)
     }
@@ -5,7 +5,7 @@ use kaspa_core::{
     task::service::{AsyncService, AsyncServiceFuture},
     trace, warn,
 };
-use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_rpc_service::{service::RpcCoreService, tls::RpcTlsIdentity};
 use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 use std::sync::Arc;
@@ -20,6 +20,7 @@ pub struct GrpcService {
     started: SingleTrigger,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    tls_identity: Option<RpcTlsIdentity>,
 }
 
 impl GrpcService {
@@ -32,6 +33,7 @@ impl GrpcService {
         rpc_max_clients: usize,
         broadcasters: usize,
         counters: Arc<TowerConnectionCounters>,
+        tls_identity: Option<RpcTlsIdentity>,
     ) -> Self {
         Self {
             net_address: address,
@@ -42,6 +44,7 @@ impl GrpcService {
             started: Default::default(),
             shutdown: Default::default(),
             counters,
+            tls_identity,
         }
     }
 
@@ -71,6 +74,7 @@ impl AsyncService for GrpcService {
             self.core_service.subscription_context(),
             self.broadcasters,
             self.counters.clone(),
+            self.tls_identity.clone(),
         );
 
         // Signal the server was started
@@ -1,9 +1,8 @@
-use super::rpc_core_mock::RpcCoreMock;
 use crate::{adaptor::Adaptor, manager::Manager};
 use kaspa_core::info;
 use kaspa_grpc_client::GrpcClient;
 use kaspa_notify::scope::{NewBlockTemplateScope, Scope};
-use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspa_rpc_core::{api::rpc::RpcApi, test_helpers::RpcCoreMock};
 use kaspa_utils::networking::{ContextualNetAddress, NetAddress};
 use std::sync::Arc;
 
@@ -199,6 +198,7 @@ fn create_server(core_service: Arc<RpcCoreMock>) -> Arc<Adaptor> {
         core_service.subscription_context(),
         3,
         Default::default(),
+        None,
     )
 }
 
@@ -1,3 +1 @@
-mod rpc_core_mock;
-
 mod client_server;
@@ -53,7 +53,10 @@ impl ToTokens for RpcTable {
                     interface.method(#rpc_api_ops::#handler, method!(|server_ctx: #server_ctx_type, connection_ctx: #connection_ctx_type, request: #request_type| async move {
                         let verbose = server_ctx.verbose();
                         if verbose { workflow_log::log_info!("request: {:?}",request); }
-                        let response: #response_type = server_ctx.rpc_service(&connection_ctx).#fn_call(request).await
+                        let rpc_service = server_ctx.rpc_service(&connection_ctx);
+                        rpc_service.authorize(connection_ctx.api_key().as_deref(), #rpc_api_ops::#handler)
+                            .map_err(|e|ServerError::Text(e.to_string()))?;
+                        let response: #response_type = rpc_service.#fn_call(request).await
                             .map_err(|e|ServerError::Text(e.to_string()))?;
                         if verbose { workflow_log::log_info!("response: {:?}",response); }
                         Ok(response)
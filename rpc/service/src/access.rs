@@ -0,0 +1,278 @@
+//! Access control of the RPC API
+//!
+//! Clients authenticate by presenting an API key (a bearer token) when connecting to one of the RPC
+//! listeners. Each key carries the set of [`RpcApiOps`] it is allowed to call, usually built from
+//! one or several [`RpcAccessGroup`]s.
+
+use kaspa_rpc_core::{api::ops::RpcApiOps, RpcError, RpcResult};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs, io,
+    path::Path,
+    str::FromStr,
+};
+
+/// A named set of RPC methods that can be granted to an API key
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RpcAccessGroup {
+    /// Methods querying the node state, the DAG, the mempool and the indexes, including notifications
+    ReadOnly,
+    /// Methods submitting transactions to the mempool
+    Submit,
+    /// Methods used by miners and pools to get block templates and submit blocks
+    Mining,
+    /// Methods managing the node itself, like peer handling and shutdown
+    Admin,
+}
+
+impl RpcAccessGroup {
+    pub const ALL: [RpcAccessGroup; 4] = [Self::ReadOnly, Self::Submit, Self::Mining, Self::Admin];
+
+    /// Returns true if `op` belongs to this group
+    pub fn contains(&self, op: RpcApiOps) -> bool {
+        use RpcApiOps::*;
+        match self {
+            Self::ReadOnly => {
+                matches!(
+                    op,
+                    Ping | GetMetrics
+                        | GetServerInfo
+                        | GetSyncStatus
+                        | GetCurrentNetwork
+                        | GetSink
+                        | GetMempoolEntry
+                        | GetMempoolEntries
+                        | GetBlock
+                        | GetSubnetwork
+                        | GetVirtualChainFromBlock
+                        | GetBlocks
+                        | GetBlockCount
+                        | GetBlockDagInfo
+                        | GetHeaders
+                        | GetUtxosByAddresses
+                        | GetBalanceByAddress
+                        | GetBalancesByAddresses
+                        | GetSinkBlueScore
+                        | GetInfo
+                        | EstimateNetworkHashesPerSecond
+                        | GetMempoolEntriesByAddresses
+                        | GetCoinSupply
+                        | GetDaaScoreTimestampEstimate
+                        | GetTransaction
+                        | GetTransactionsByIds
+                        | GetFeeEstimate
+                        | GetAddressTransactions
+                        | GetAddressTransactionCount
+                        | GetTransactionMerkleProof
+                ) || op.is_subscription()
+            }
+            Self::Submit => matches!(op, SubmitTransaction | SubmitTransactionReplacement),
+            Self::Mining => matches!(op, GetBlockTemplate | SubmitBlock),
+            Self::Admin => {
+                matches!(
+                    op,
+                    GetPeerAddresses
+                        | GetConnectedPeerInfo
+                        | AddPeer
+                        | Ban
+                        | Unban
+                        | ListBans
+                        | SaveMempool
+                        | LoadMempool
+                        | ResolveFinalityConflict
+                        | Shutdown
+                )
+            }
+        }
+    }
+
+    /// Returns all the methods of this group
+    pub fn ops(&self) -> impl Iterator<Item = RpcApiOps> + '_ {
+        RpcApiOps::list().into_iter().filter(|op| self.contains(*op))
+    }
+}
+
+impl Display for RpcAccessGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::ReadOnly => "read",
+            Self::Submit => "submit",
+            Self::Mining => "mining",
+            Self::Admin => "admin",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for RpcAccessGroup {
+    type Err = RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read" => Ok(Self::ReadOnly),
+            "submit" => Ok(Self::Submit),
+            "mining" => Ok(Self::Mining),
+            "admin" => Ok(Self::Admin),
+            _ => Err(RpcError::General(format!("unknown RPC access group '{s}'"))),
+        }
+    }
+}
+
+/// An API key and the RPC methods it grants access to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcApiKey {
+    pub key: String,
+    pub allowed_ops: HashSet<RpcApiOps>,
+}
+
+impl RpcApiKey {
+    pub fn new(key: String, allowed_ops: HashSet<RpcApiOps>) -> Self {
+        Self { key, allowed_ops }
+    }
+
+    pub fn with_groups(key: String, groups: &[RpcAccessGroup]) -> Self {
+        Self::new(key, groups.iter().flat_map(|group| group.ops()).collect())
+    }
+
+    /// Parses the API key definitions listed one per line, skipping blank lines and `#` comments
+    pub fn parse_list(s: &str) -> RpcResult<Vec<Self>> {
+        s.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')).map(str::parse).collect()
+    }
+
+    /// Reads the API key definitions listed in the provided file, keeping the keys out of the command line
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Vec<Self>> {
+        Self::parse_list(&fs::read_to_string(path)?).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+/// Parses an API key definition of the form `<key>:<grant>[,<grant>...]` where each grant is either
+/// an access group (`read`, `submit`, `mining`, `admin` or `all`) or the camelCase name of a single
+/// RPC method (e.g. `getBlockTemplate`).
+impl FromStr for RpcApiKey {
+    type Err = RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, grants) = s.rsplit_once(':').ok_or_else(|| {
+            RpcError::General(format!("RPC API key definition '{s}' must be of the form <key>:<grant>[,<grant>...]"))
+        })?;
+        if key.is_empty() {
+            return Err(RpcError::General("RPC API key cannot be empty".to_string()));
+        }
+        let mut allowed_ops = HashSet::new();
+        for grant in grants.split(',').map(str::trim) {
+            if grant == "all" {
+                allowed_ops.extend(RpcAccessGroup::ALL.iter().flat_map(|group| group.ops()));
+            } else if let Ok(group) = grant.parse::<RpcAccessGroup>() {
+                allowed_ops.extend(group.ops());
+            } else {
+                let op = RpcApiOps::list()
+                    .into_iter()
+                    .find(|op| RpcAccessGroup::ALL.iter().any(|group| group.contains(*op)) && op_name(*op) == grant)
+                    .ok_or_else(|| RpcError::General(format!("unknown RPC access grant '{grant}'")))?;
+                allowed_ops.insert(op);
+            }
+        }
+        Ok(Self::new(key.to_string(), allowed_ops))
+    }
+}
+
+/// Returns the camelCase name of `op` as used by the JSON encoding of the wRPC protocol
+fn op_name(op: RpcApiOps) -> String {
+    let name = format!("{op:?}");
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_ascii_lowercase().to_string() + chars.as_str()).unwrap_or_default()
+}
+
+/// The access policy enforced by the RPC core service on every request
+///
+/// A policy without any API key is open and grants every client access to all methods, leaving
+/// [`Config::unsafe_rpc`](kaspa_consensus_core::config::Config) as the only gate.
+#[derive(Clone, Debug, Default)]
+pub struct RpcAccessPolicy {
+    api_keys: HashMap<String, HashSet<RpcApiOps>>,
+}
+
+impl RpcAccessPolicy {
+    pub fn new(api_keys: Vec<RpcApiKey>) -> Self {
+        let mut policy = Self::default();
+        for api_key in api_keys {
+            policy.api_keys.entry(api_key.key).or_default().extend(api_key.allowed_ops);
+        }
+        policy
+    }
+
+    /// Returns true if clients must present an API key
+    pub fn is_enabled(&self) -> bool {
+        !self.api_keys.is_empty()
+    }
+
+    /// Checks that a client presenting `api_key` is allowed to call `op`
+    pub fn authorize(&self, api_key: Option<&str>, op: RpcApiOps) -> RpcResult<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        let allowed_ops = api_key.and_then(|key| self.api_keys.get(key)).ok_or(RpcError::Unauthenticated)?;
+        if !allowed_ops.contains(&op) {
+            return Err(RpcError::Unauthorized(op));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_groups_cover_all_methods() {
+        // Every client callable method must belong to exactly one group, authentication and the
+        // notifications sent by the server being the only ops left out
+        for op in RpcApiOps::list() {
+            let count = RpcAccessGroup::ALL.iter().filter(|group| group.contains(op)).count();
+            let is_ungrouped = op == RpcApiOps::Authenticate || format!("{op:?}").ends_with("Notification");
+            assert_eq!(count, if is_ungrouped { 0 } else { 1 }, "{op:?} belongs to {count} groups");
+        }
+    }
+
+    #[test]
+    fn test_api_key_parsing() {
+        let key: RpcApiKey = "secret:read,getBlockTemplate".parse().unwrap();
+        assert_eq!(key.key, "secret");
+        assert!(key.allowed_ops.contains(&RpcApiOps::GetInfo));
+        assert!(key.allowed_ops.contains(&RpcApiOps::NotifyUtxosChanged));
+        assert!(key.allowed_ops.contains(&RpcApiOps::GetBlockTemplate));
+        assert!(!key.allowed_ops.contains(&RpcApiOps::SubmitBlock));
+
+        let key: RpcApiKey = "a:b:all".parse().unwrap();
+        assert_eq!(key.key, "a:b");
+        assert!(key.allowed_ops.contains(&RpcApiOps::Shutdown));
+
+        assert!("secret".parse::<RpcApiKey>().is_err());
+        assert!(":read".parse::<RpcApiKey>().is_err());
+        assert!("secret:write".parse::<RpcApiKey>().is_err());
+        assert!("secret:blockAddedNotification".parse::<RpcApiKey>().is_err());
+
+        let keys = RpcApiKey::parse_list("# miners\nminer:read,mining\n\n  admin:all  \n").unwrap();
+        assert_eq!(keys.iter().map(|key| key.key.as_str()).collect::<Vec<_>>(), ["miner", "admin"]);
+        assert!(RpcApiKey::parse_list("miner:read\nsecret\n").is_err());
+    }
+
+    #[test]
+    fn test_access_policy() {
+        let open = RpcAccessPolicy::default();
+        assert!(open.authorize(None, RpcApiOps::Shutdown).is_ok());
+
+        let policy = RpcAccessPolicy::new(vec![
+            RpcApiKey::with_groups("miner".to_string(), &[RpcAccessGroup::ReadOnly]),
+            RpcApiKey::with_groups("miner".to_string(), &[RpcAccessGroup::Mining]),
+            RpcApiKey::with_groups("admin".to_string(), &RpcAccessGroup::ALL),
+        ]);
+        assert!(matches!(policy.authorize(None, RpcApiOps::GetInfo), Err(RpcError::Unauthenticated)));
+        assert!(matches!(policy.authorize(Some("unknown"), RpcApiOps::GetInfo), Err(RpcError::Unauthenticated)));
+        assert!(policy.authorize(Some("miner"), RpcApiOps::GetInfo).is_ok());
+        assert!(policy.authorize(Some("miner"), RpcApiOps::SubmitBlock).is_ok());
+        assert!(matches!(policy.authorize(Some("miner"), RpcApiOps::Shutdown), Err(RpcError::Unauthorized(RpcApiOps::Shutdown))));
+        assert!(policy.authorize(Some("admin"), RpcApiOps::Shutdown).is_ok());
+    }
+}
@@ -1,25 +1,35 @@
 use async_trait::async_trait;
 use kaspa_addresses::Address;
 use kaspa_consensus_core::{
+    api::ConsensusApi,
     block::Block,
     config::Config,
     hashing::tx::hash,
     header::Header,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput},
-    ChainPath,
+    BlockHashMap, ChainPath, HashMapCustomHasher,
 };
 use kaspa_consensus_notify::notification::{self as consensus_notify, Notification as ConsensusNotification};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusProxy};
+use kaspa_hashes::Hash;
 use kaspa_math::Uint256;
+use kaspa_merkle::create_merkle_proof;
 use kaspa_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcHash, RpcMempoolEntry,
-    RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionOutput, RpcTransactionOutputVerboseData,
+    BlockAddedNotification, Notification, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcError, RpcHash, RpcHeader,
+    RpcIndexedTransaction, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcResult, RpcTransaction, RpcTransactionAcceptance,
+    RpcTransactionInclusion, RpcTransactionInput, RpcTransactionMerkleProof, RpcTransactionOutput, RpcTransactionOutputVerboseData,
     RpcTransactionVerboseData,
 };
+use kaspa_txindex::model::TxIndexEntry;
 use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
-use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    fmt::Debug,
+    sync::Arc,
+};
 
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
@@ -146,6 +156,90 @@ impl ConsensusConverter {
         }
     }
 
+    /// Converts a txindex [`TxIndexEntry`] into an [`RpcIndexedTransaction`], optionally including the transaction itself.
+    ///
+    /// The transaction is read from the body of the block holding its accepted instance, falling back to the earliest
+    /// including block. It is omitted if no such block body is available anymore.
+    pub async fn get_indexed_transaction(
+        &self,
+        consensus: &ConsensusProxy,
+        entry: &TxIndexEntry,
+        include_transaction: bool,
+    ) -> RpcIndexedTransaction {
+        let transaction = if include_transaction {
+            let location = entry
+                .acceptance
+                .map(|x| (x.including_block_hash, x.index_within_block))
+                .or_else(|| entry.inclusions.first().map(|x| (x.block_hash, x.index_within_block)));
+            match location {
+                Some((block_hash, index_within_block)) => consensus.async_get_block(block_hash).await.ok().and_then(|block| {
+                    block
+                        .transactions
+                        .get(index_within_block as usize)
+                        .map(|transaction| self.get_transaction(consensus, transaction, Some(&block.header), true))
+                }),
+                None => None,
+            }
+        } else {
+            None
+        };
+        let acceptance = entry.acceptance.map(|x| {
+            RpcTransactionAcceptance::new(x.accepting_block_hash, x.accepting_daa_score, x.including_block_hash, x.index_within_block)
+        });
+        let inclusions =
+            entry.inclusions.iter().map(|x| RpcTransactionInclusion::new(x.block_hash, x.daa_score, x.index_within_block)).collect();
+        RpcIndexedTransaction::new(entry.transaction_id, transaction, acceptance, inclusions)
+    }
+
+    /// Builds a proof of inclusion of a transaction in the body of a block, along with the headers linking this block
+    /// to the chain block merging it.
+    pub async fn get_transaction_merkle_proof(
+        &self,
+        consensus: &ConsensusProxy,
+        transaction_id: TransactionId,
+        block_hash: Hash,
+    ) -> RpcResult<RpcTransactionMerkleProof> {
+        let block = consensus.async_get_block(block_hash).await?;
+        let index = block
+            .transactions
+            .iter()
+            .position(|transaction| transaction.id() == transaction_id)
+            .ok_or(RpcError::TransactionNotInBlock(transaction_id, block_hash))?;
+        // Once storage mass is activated, the merkle leaves commit to the transaction mass as well
+        let include_mass_field = block.header.daa_score > self.config.storage_mass_activation_daa_score;
+        let leaves = block.transactions.iter().map(|transaction| hash(transaction, include_mass_field)).collect::<Vec<_>>();
+        let proof = create_merkle_proof(leaves.iter().copied(), index).expect("the index is within the block transactions");
+        let headers = consensus.clone().spawn_blocking(move |c| Self::get_merging_chain_path(c, block_hash)).await?;
+        Ok(RpcTransactionMerkleProof::new(block_hash, leaves[index], index as u32, proof.siblings, headers))
+    }
+
+    /// Returns the headers from `hash` up to the chain block merging it, each one being a direct parent of the next.
+    ///
+    /// The future of `hash` is traversed in ascending blue work order. Since all the ancestors of the merging chain
+    /// block have a lower blue work and all other chain blocks in this future are its descendants, it is the first
+    /// chain block reached.
+    fn get_merging_chain_path(consensus: &dyn ConsensusApi, hash: Hash) -> RpcResult<Vec<RpcHeader>> {
+        let mut came_from = BlockHashMap::new();
+        let mut queue = BinaryHeap::new();
+        queue.push(Reverse((consensus.get_header(hash)?.blue_work, hash)));
+        while let Some(Reverse((_, current))) = queue.pop() {
+            if consensus.is_chain_block(current)? {
+                let mut path = vec![current];
+                while let Some(&parent) = came_from.get(path.last().unwrap()) {
+                    path.push(parent);
+                }
+                return path.into_iter().rev().map(|hash| Ok(consensus.get_header(hash)?.as_ref().clone())).collect();
+            }
+            for child in consensus.get_block_children(current).unwrap_or_default() {
+                if let Entry::Vacant(entry) = came_from.entry(child) {
+                    entry.insert(current);
+                    queue.push(Reverse((consensus.get_header(child)?.blue_work, child)));
+                }
+            }
+        }
+        Err(RpcError::BlockNotMerged(hash))
+    }
+
     fn get_transaction_input(&self, input: &TransactionInput) -> RpcTransactionInput {
         input.into()
     }
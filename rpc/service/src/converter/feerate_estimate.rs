@@ -0,0 +1,47 @@
+use kaspa_mining::feerate::{FeerateBucket, FeerateEstimations, FeerateEstimationsVerbose, FeerateHistogramBucket};
+use kaspa_rpc_core::{RpcFeeEstimate, RpcFeeEstimateVerboseData, RpcFeerateBucket, RpcFeerateHistogramBucket};
+
+pub trait FeerateBucketConverter {
+    fn into_rpc(self) -> RpcFeerateBucket;
+}
+
+impl FeerateBucketConverter for FeerateBucket {
+    fn into_rpc(self) -> RpcFeerateBucket {
+        RpcFeerateBucket::new(self.feerate, self.estimated_seconds)
+    }
+}
+
+pub trait FeeEstimateConverter {
+    fn into_rpc(self) -> RpcFeeEstimate;
+}
+
+impl FeeEstimateConverter for FeerateEstimations {
+    fn into_rpc(self) -> RpcFeeEstimate {
+        RpcFeeEstimate::new(
+            self.priority_bucket.into_rpc(),
+            self.normal_buckets.into_iter().map(FeerateBucketConverter::into_rpc).collect(),
+            self.low_buckets.into_iter().map(FeerateBucketConverter::into_rpc).collect(),
+        )
+    }
+}
+
+pub trait FeeEstimateVerboseConverter {
+    fn into_rpc(self) -> (RpcFeeEstimate, RpcFeeEstimateVerboseData);
+}
+
+impl FeeEstimateVerboseConverter for FeerateEstimationsVerbose {
+    fn into_rpc(self) -> (RpcFeeEstimate, RpcFeeEstimateVerboseData) {
+        let verbose = RpcFeeEstimateVerboseData {
+            mempool_histogram: self
+                .mempool_histogram
+                .into_iter()
+                .map(|FeerateHistogramBucket { feerate, mass, count }| RpcFeerateHistogramBucket::new(feerate, mass, count))
+                .collect(),
+            mempool_transaction_count: self.mempool_transaction_count,
+            mempool_total_mass: self.mempool_total_mass,
+            network_mass_per_second: self.network_mass_per_second,
+            recent_templates_feerate_floor: self.recent_templates_feerate_floor,
+        };
+        (self.estimations.into_rpc(), verbose)
+    }
+}
@@ -3,7 +3,10 @@ use kaspa_consensus_core::config::Config;
 use kaspa_index_core::indexed_utxos::UtxoSetByScriptPublicKey;
 use kaspa_index_core::notification::{self as index_notify, Notification as IndexNotification};
 use kaspa_notify::converter::Converter;
-use kaspa_rpc_core::{utxo_set_into_rpc, Notification, RpcUtxosByAddressesEntry, UtxosChangedNotification};
+use kaspa_rpc_core::{
+    address_history_into_rpc, utxo_set_into_rpc, AddressHistoryChangedNotification, Notification, RpcUtxosByAddressesEntry,
+    UtxosChangedNotification,
+};
 use std::sync::Arc;
 
 /// Conversion of consensus_core to rpc_core structures
@@ -27,6 +30,16 @@ impl IndexConverter {
     pub fn get_utxos_by_addresses_entries(&self, item: &UtxoSetByScriptPublicKey) -> Vec<RpcUtxosByAddressesEntry> {
         utxo_set_into_rpc(item, Some(self.config.prefix()))
     }
+
+    pub fn get_address_history_changed_notification(
+        &self,
+        address_history_changed: index_notify::AddressHistoryChangedNotification,
+    ) -> AddressHistoryChangedNotification {
+        AddressHistoryChangedNotification {
+            added: Arc::new(address_history_into_rpc(&address_history_changed.added, Some(self.config.prefix()))),
+            removed: Arc::new(address_history_into_rpc(&address_history_changed.removed, Some(self.config.prefix()))),
+        }
+    }
 }
 
 #[async_trait]
@@ -37,6 +50,9 @@ impl Converter for IndexConverter {
     async fn convert(&self, incoming: IndexNotification) -> Notification {
         match incoming {
             index_notify::Notification::UtxosChanged(msg) => Notification::UtxosChanged(self.get_utxo_changed_notification(msg)),
+            index_notify::Notification::AddressHistoryChanged(msg) => {
+                Notification::AddressHistoryChanged(self.get_address_history_changed_notification(msg))
+            }
             _ => (&incoming).into(),
         }
     }
@@ -1,3 +1,4 @@
 pub mod consensus;
+pub mod feerate_estimate;
 pub mod index;
 pub mod protocol;
@@ -1,3 +1,6 @@
+pub mod access;
 pub mod collector;
 pub mod converter;
+pub mod prometheus;
 pub mod service;
+pub mod tls;
@@ -0,0 +1,168 @@
+use crate::service::RpcCoreService;
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use kaspa_core::{
+    debug, info,
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
+    trace, warn,
+};
+use kaspa_metrics_core::prometheus::{PrometheusCollector, PrometheusEncoder, PrometheusMetricType, PROMETHEUS_CONTENT_TYPE};
+use kaspa_mining::MiningCounters;
+use kaspa_rpc_core::{api::rpc::RpcApi, RpcResult};
+use kaspa_utils::{networking::NetAddress, triggers::SingleTrigger};
+use std::{convert::Infallible, sync::Arc};
+
+/// Prefix of the exported metric families
+const NAMESPACE: &str = "pyrin";
+
+/// Port of the Prometheus listener when none is specified
+pub const DEFAULT_PROMETHEUS_PORT: u16 = 13120;
+
+/// Path under which the metrics are exposed
+const METRICS_PATH: &str = "/metrics";
+
+/// HTTP listener exposing the node metrics in the Prometheus text exposition format
+pub struct PrometheusService {
+    net_address: NetAddress,
+    core_service: Arc<RpcCoreService>,
+    mining_counters: Arc<MiningCounters>,
+    collectors: Vec<Arc<dyn PrometheusCollector>>,
+    shutdown: SingleTrigger,
+}
+
+impl PrometheusService {
+    pub const IDENT: &'static str = "prometheus-service";
+
+    pub fn new(
+        net_address: NetAddress,
+        core_service: Arc<RpcCoreService>,
+        mining_counters: Arc<MiningCounters>,
+        collectors: Vec<Arc<dyn PrometheusCollector>>,
+    ) -> Self {
+        Self { net_address, core_service, mining_counters, collectors, shutdown: Default::default() }
+    }
+
+    async fn handle(self: Arc<Self>, request: Request<Body>) -> Response<Body> {
+        if request.method() != Method::GET || request.uri().path() != METRICS_PATH {
+            return Response::builder().status(StatusCode::NOT_FOUND).body(Body::empty()).unwrap();
+        }
+        match self.scrape().await {
+            Ok(metrics) => Response::builder().header(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE).body(Body::from(metrics)).unwrap(),
+            Err(err) => {
+                debug!("{} failed to collect the metrics: {}", Self::IDENT, err);
+                Response::builder().status(StatusCode::INTERNAL_SERVER_ERROR).body(Body::from(err.to_string())).unwrap()
+            }
+        }
+    }
+
+    async fn scrape(&self) -> RpcResult<String> {
+        use PrometheusMetricType::*;
+
+        let mut encoder = PrometheusEncoder::new(NAMESPACE);
+        encoder.encode_metrics(&self.core_service.get_metrics(true, true, true, true).await?);
+
+        let mempool = self.mining_counters.snapshot();
+        encoder.single("mempool_transactions", "Number of transactions in the mempool", Gauge, mempool.txs_sample as f64);
+        encoder.single(
+            "mempool_ready_transactions",
+            "Number of mempool transactions having all their inputs in the UTXO set",
+            Gauge,
+            mempool.ready_txs_sample as f64,
+        );
+        encoder.single("mempool_orphans", "Number of transactions in the orphan pool", Gauge, mempool.orphans_sample as f64);
+        encoder
+            .family("mempool_received_transactions_total", "Number of transactions submitted to the mempool", Counter)
+            .sample(&[("source", "rpc")], mempool.high_priority_tx_counts as f64)
+            .sample(&[("source", "p2p")], mempool.low_priority_tx_counts as f64);
+        encoder.single(
+            "mempool_accepted_transactions_total",
+            "Number of transactions accepted by chain blocks",
+            Counter,
+            mempool.tx_accepted_counts as f64,
+        );
+
+        let peers = self.core_service.get_connected_peer_info().await?.peer_info;
+        encoder.family("peer_info", "Connected P2P peers, with a constant value of 1", Gauge);
+        for peer in peers.iter() {
+            let protocol_version = peer.advertised_protocol_version.to_string();
+            encoder.sample(
+                &[
+                    ("id", &peer.id.to_string()),
+                    ("address", &peer.address.to_string()),
+                    ("direction", if peer.is_outbound { "outbound" } else { "inbound" }),
+                    ("user_agent", &peer.user_agent),
+                    ("protocol_version", &protocol_version),
+                    ("ibd", if peer.is_ibd_peer { "true" } else { "false" }),
+                ],
+                1.0,
+            );
+        }
+        encoder.family("peer_last_ping_duration_seconds", "Duration of the last ping to a P2P peer", Gauge);
+        peers.iter().for_each(|peer| {
+            encoder.sample(&[("id", &peer.id.to_string())], peer.last_ping_duration as f64 / 1000.0);
+        });
+        encoder.family("peer_connected_seconds", "Time elapsed since a P2P peer connected", Gauge);
+        peers.iter().for_each(|peer| {
+            encoder.sample(&[("id", &peer.id.to_string())], peer.time_connected as f64 / 1000.0);
+        });
+        encoder.family("peer_time_offset_seconds", "Clock offset of a P2P peer", Gauge);
+        peers.iter().for_each(|peer| {
+            encoder.sample(&[("id", &peer.id.to_string())], peer.time_offset as f64 / 1000.0);
+        });
+
+        self.collectors.iter().for_each(|collector| collector.collect(&mut encoder));
+
+        Ok(encoder.finish())
+    }
+}
+
+impl AsyncService for PrometheusService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        Self::IDENT
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        trace!("{} starting", Self::IDENT);
+
+        // Prepare a shutdown signal receiver
+        let shutdown_signal = self.shutdown.listener.clone();
+
+        Box::pin(async move {
+            let this = self.clone();
+            let make_service = make_service_fn(move |_| {
+                let this = this.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request| {
+                        let this = this.clone();
+                        async move { Ok::<_, Infallible>(this.handle(request).await) }
+                    }))
+                }
+            });
+            let server = Server::try_bind(&self.net_address.into())
+                .map_err(|err| AsyncServiceError::Service(format!("failed to bind the Prometheus listener: {err}")))?
+                .serve(make_service);
+            info!("Prometheus metrics exposed on http://{}{}", self.net_address, METRICS_PATH);
+
+            // Serve the metrics until a service shutdown signal is received
+            if let Err(err) = server.with_graceful_shutdown(shutdown_signal).await {
+                warn!("{} error while serving: {}", Self::IDENT, err);
+            }
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", Self::IDENT);
+        self.shutdown.trigger.trigger();
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", Self::IDENT);
+            Ok(())
+        })
+    }
+}
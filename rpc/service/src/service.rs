@@ -1,9 +1,12 @@
 //! Core server implementation for ClientAPI
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
+use crate::access::RpcAccessPolicy;
+use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use crate::service::NetworkType::{Mainnet, Testnet};
 use async_trait::async_trait;
+use kaspa_addresshistory::api::AddressHistoryProxy;
 use kaspa_consensus_core::api::counters::ProcessingCounters;
 use kaspa_consensus_core::errors::block::RuleError;
 use kaspa_consensus_core::{
@@ -35,7 +38,10 @@ use kaspa_index_core::{
     notifier::IndexNotifier,
 };
 use kaspa_mining::model::tx_query::TransactionQuery;
-use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
+use kaspa_mining::{
+    manager::MiningManagerProxy,
+    mempool::tx::{Orphan, RbfPolicy},
+};
 use kaspa_notify::listener::ListenerLifespan;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
@@ -53,13 +59,14 @@ use kaspa_p2p_lib::common::ProtocolError;
 use kaspa_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use kaspa_rpc_core::{
     api::{
-        ops::RPC_API_VERSION,
+        ops::{RpcApiOps, RPC_API_VERSION},
         rpc::{RpcApi, MAX_SAFE_WINDOW_SIZE},
     },
     model::*,
     notify::connection::ChannelConnection,
     Notification, RpcError, RpcResult,
 };
+use kaspa_txindex::api::TxIndexProxy;
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
@@ -68,6 +75,7 @@ use std::{
     collections::HashMap,
     iter::once,
     sync::{atomic::Ordering, Arc},
+    time::Duration,
     vec,
 };
 use tokio::join;
@@ -96,6 +104,8 @@ pub struct RpcCoreService {
     mining_manager: MiningManagerProxy,
     flow_context: Arc<FlowContext>,
     utxoindex: Option<UtxoIndexProxy>,
+    txindex: Option<TxIndexProxy>,
+    addresshistory: Option<AddressHistoryProxy>,
     config: Arc<Config>,
     consensus_converter: Arc<ConsensusConverter>,
     index_converter: Arc<IndexConverter>,
@@ -109,10 +119,17 @@ pub struct RpcCoreService {
     perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
     p2p_tower_counters: Arc<TowerConnectionCounters>,
     grpc_tower_counters: Arc<TowerConnectionCounters>,
+    access_policy: Arc<RpcAccessPolicy>,
 }
 
 const RPC_CORE: &str = "rpc-core";
 
+/// Maximum number of entries returned by a single `get_address_transactions` call
+const MAX_ADDRESS_HISTORY_PAGE_SIZE: usize = 1000;
+
+/// Maximum number of transactions looked up by a single `get_transactions_by_ids` call
+const MAX_TRANSACTIONS_BY_IDS: usize = 1000;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -125,6 +142,8 @@ impl RpcCoreService {
         flow_context: Arc<FlowContext>,
         subscription_context: SubscriptionContext,
         utxoindex: Option<UtxoIndexProxy>,
+        txindex: Option<TxIndexProxy>,
+        addresshistory: Option<AddressHistoryProxy>,
         config: Arc<Config>,
         core: Arc<Core>,
         processing_counters: Arc<ProcessingCounters>,
@@ -133,6 +152,7 @@ impl RpcCoreService {
         perf_monitor: Arc<PerfMonitor<Arc<TickService>>>,
         p2p_tower_counters: Arc<TowerConnectionCounters>,
         grpc_tower_counters: Arc<TowerConnectionCounters>,
+        access_policy: Arc<RpcAccessPolicy>,
     ) -> Self {
         // This notifier UTXOs subscription granularity to index-processor or consensus notifier
         let policies = match index_notifier {
@@ -151,6 +171,7 @@ impl RpcCoreService {
         let mut consensus_events: EventSwitches = EVENT_TYPE_ARRAY[..].into();
         consensus_events[EventType::UtxosChanged] = false;
         consensus_events[EventType::PruningPointUtxoSetOverride] = index_notifier.is_none();
+        consensus_events[EventType::AddressHistoryChanged] = false;
         let consensus_converter = Arc::new(ConsensusConverter::new(consensus_manager.clone(), config.clone()));
         let consensus_collector = Arc::new(CollectorFromConsensus::new(
             "rpc-core <= consensus",
@@ -172,7 +193,8 @@ impl RpcCoreService {
                 ListenerLifespan::Static(policies),
             );
 
-            let index_events: EventSwitches = [EventType::UtxosChanged, EventType::PruningPointUtxoSetOverride].as_ref().into();
+            let index_events: EventSwitches =
+                [EventType::UtxosChanged, EventType::PruningPointUtxoSetOverride, EventType::AddressHistoryChanged].as_ref().into();
             let index_collector =
                 Arc::new(CollectorFromIndex::new("rpc-core <= index", index_notify_channel.receiver(), index_converter.clone()));
             let index_subscriber =
@@ -195,6 +217,8 @@ impl RpcCoreService {
             mining_manager,
             flow_context,
             utxoindex,
+            txindex,
+            addresshistory,
             config,
             consensus_converter,
             index_converter,
@@ -208,6 +232,7 @@ impl RpcCoreService {
             perf_monitor,
             p2p_tower_counters,
             grpc_tower_counters,
+            access_policy,
         }
     }
 
@@ -498,12 +523,36 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             true => Orphan::Allowed,
             false => Orphan::Forbidden,
         };
-        self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await.map_err(|err| {
-            let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
-            debug!("{err}");
-            err
-        })?;
-        Ok(SubmitTransactionResponse::new(transaction_id))
+        let rbf_policy = match request.rbf_policy {
+            RpcRbfPolicy::Forbidden => RbfPolicy::Forbidden,
+            RpcRbfPolicy::Permitted => RbfPolicy::Permitted,
+            RpcRbfPolicy::Mandatory => RbfPolicy::Mandatory,
+        };
+        let removed_transaction_ids =
+            self.flow_context.submit_rpc_transaction(&session, transaction, orphan, rbf_policy).await.map_err(|err| {
+                let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                debug!("{err}");
+                err
+            })?;
+        Ok(SubmitTransactionResponse::new(transaction_id, removed_transaction_ids))
+    }
+
+    async fn submit_transaction_replacement_call(
+        &self,
+        request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        let transaction: Transaction = (&request.transaction).try_into()?;
+        let transaction_id = transaction.id();
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let removed_transaction_ids =
+            self.flow_context.submit_rpc_transaction(&session, transaction, Orphan::Forbidden, RbfPolicy::Mandatory).await.map_err(
+                |err| {
+                    let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
+                    debug!("{err}");
+                    err
+                },
+            )?;
+        Ok(SubmitTransactionReplacementResponse::new(transaction_id, removed_transaction_ids))
     }
 
     async fn get_current_network_call(&self, _: GetCurrentNetworkRequest) -> RpcResult<GetCurrentNetworkResponse> {
@@ -647,6 +696,104 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetDaaScoreTimestampEstimateResponse::new(timestamps))
     }
 
+    async fn get_transaction_call(&self, request: GetTransactionRequest) -> RpcResult<GetTransactionResponse> {
+        let Some(txindex) = self.txindex.clone() else {
+            return Err(RpcError::NoTxIndex);
+        };
+        let entry = txindex
+            .get_transaction_entry(request.transaction_id)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?
+            .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+        let session = self.consensus_manager.consensus().session().await;
+        let transaction = self.consensus_converter.get_indexed_transaction(&session, &entry, request.include_transaction).await;
+        Ok(GetTransactionResponse::new(transaction))
+    }
+
+    async fn get_transactions_by_ids_call(&self, request: GetTransactionsByIdsRequest) -> RpcResult<GetTransactionsByIdsResponse> {
+        let Some(txindex) = self.txindex.clone() else {
+            return Err(RpcError::NoTxIndex);
+        };
+        if request.transaction_ids.len() > MAX_TRANSACTIONS_BY_IDS {
+            return Err(RpcError::TransactionIdsExceedingMaximum(request.transaction_ids.len(), MAX_TRANSACTIONS_BY_IDS));
+        }
+        let entries =
+            txindex.get_transaction_entries(request.transaction_ids).await.map_err(|err| RpcError::General(err.to_string()))?;
+        let session = self.consensus_manager.consensus().session().await;
+        let mut transactions = Vec::with_capacity(entries.len());
+        for entry in entries.iter() {
+            transactions.push(self.consensus_converter.get_indexed_transaction(&session, entry, request.include_transactions).await);
+        }
+        Ok(GetTransactionsByIdsResponse::new(transactions))
+    }
+
+    async fn get_address_transactions_call(
+        &self,
+        request: GetAddressTransactionsRequest,
+    ) -> RpcResult<GetAddressTransactionsResponse> {
+        let Some(addresshistory) = self.addresshistory.clone() else {
+            return Err(RpcError::NoAddressHistory);
+        };
+        let limit = (request.limit as usize).min(MAX_ADDRESS_HISTORY_PAGE_SIZE);
+        let entries = addresshistory
+            .get_history(pay_to_address_script(&request.address), request.start_daa_score, request.start_transaction_id, limit)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        Ok(GetAddressTransactionsResponse::new(entries.iter().map(|x| x.into()).collect()))
+    }
+
+    async fn get_address_transaction_count_call(
+        &self,
+        request: GetAddressTransactionCountRequest,
+    ) -> RpcResult<GetAddressTransactionCountResponse> {
+        let Some(addresshistory) = self.addresshistory.clone() else {
+            return Err(RpcError::NoAddressHistory);
+        };
+        let count = addresshistory
+            .get_history_count(pay_to_address_script(&request.address))
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        Ok(GetAddressTransactionCountResponse::new(count))
+    }
+
+    async fn get_transaction_merkle_proof_call(
+        &self,
+        request: GetTransactionMerkleProofRequest,
+    ) -> RpcResult<GetTransactionMerkleProofResponse> {
+        let block_hash = match request.block_hash {
+            Some(block_hash) => block_hash,
+            None => {
+                let Some(txindex) = self.txindex.clone() else {
+                    return Err(RpcError::NoTxIndex);
+                };
+                let entry = txindex
+                    .get_transaction_entry(request.transaction_id)
+                    .await
+                    .map_err(|err| RpcError::General(err.to_string()))?
+                    .ok_or(RpcError::TransactionNotFound(request.transaction_id))?;
+                // Prefer the block holding the accepted instance of the transaction
+                entry
+                    .acceptance
+                    .map(|x| x.including_block_hash)
+                    .or_else(|| entry.inclusions.first().map(|x| x.block_hash))
+                    .ok_or(RpcError::TransactionNotFound(request.transaction_id))?
+            }
+        };
+        let session = self.consensus_manager.consensus().session().await;
+        let proof = self.consensus_converter.get_transaction_merkle_proof(&session, request.transaction_id, block_hash).await?;
+        Ok(GetTransactionMerkleProofResponse::new(proof))
+    }
+
+    async fn get_fee_estimate_call(&self, request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        if request.verbose {
+            let (estimate, verbose) = self.mining_manager.clone().get_realtime_feerate_estimations_verbose().await.into_rpc();
+            Ok(GetFeeEstimateResponse::new(estimate, Some(verbose)))
+        } else {
+            let estimate = self.mining_manager.clone().get_realtime_feerate_estimations().await.into_rpc();
+            Ok(GetFeeEstimateResponse::new(estimate, None))
+        }
+    }
+
     async fn ping_call(&self, _: PingRequest) -> RpcResult<PingResponse> {
         Ok(PingResponse {})
     }
@@ -719,7 +866,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
     }
 
     async fn get_peer_addresses_call(&self, _: GetPeerAddressesRequest) -> RpcResult<GetPeerAddressesResponse> {
-        let address_manager = self.flow_context.address_manager.lock();
+        let mut address_manager = self.flow_context.address_manager.lock();
         Ok(GetPeerAddressesResponse::new(address_manager.get_all_addresses(), address_manager.get_all_banned_addresses()))
     }
 
@@ -729,11 +876,15 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             return Err(RpcError::UnavailableInSafeMode);
         }
         if let Some(connection_manager) = self.flow_context.connection_manager() {
-            let ip = request.ip.into();
-            if connection_manager.ip_has_permanent_connection(ip).await {
-                return Err(RpcError::IpHasPermanentConnection(request.ip));
+            if connection_manager.subnet_has_permanent_connection(request.subnet).await {
+                return Err(RpcError::SubnetHasPermanentConnection(request.subnet));
             }
-            connection_manager.ban(ip).await;
+            let duration = match request.duration {
+                None => self.flow_context.address_manager.lock().default_ban_duration(),
+                Some(0) => None,
+                Some(seconds) => Some(Duration::from_secs(seconds)),
+            };
+            connection_manager.ban(request.subnet, duration, request.reason.unwrap_or_default()).await;
         } else {
             return Err(RpcError::NoConnectionManager);
         }
@@ -745,15 +896,40 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             warn!("Unban RPC command called while node in safe RPC mode -- ignoring.");
             return Err(RpcError::UnavailableInSafeMode);
         }
-        let mut address_manager = self.flow_context.address_manager.lock();
-        if address_manager.is_banned(request.ip) {
-            address_manager.unban(request.ip)
-        } else {
-            return Err(RpcError::IpIsNotBanned(request.ip));
+        if !self.flow_context.address_manager.lock().unban(request.subnet) {
+            return Err(RpcError::SubnetIsNotBanned(request.subnet));
         }
         Ok(UnbanResponse {})
     }
 
+    async fn list_bans_call(&self, _: ListBansRequest) -> RpcResult<ListBansResponse> {
+        let bans = self.flow_context.address_manager.lock().get_all_bans();
+        Ok(ListBansResponse::new(
+            bans.into_iter()
+                .map(|(subnet, entry)| RpcBanInfo::new(subnet, entry.created_at, entry.expires_at, entry.reason))
+                .collect(),
+        ))
+    }
+
+    async fn save_mempool_call(&self, _: SaveMempoolRequest) -> RpcResult<SaveMempoolResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("SaveMempool RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let transaction_count = self.mining_manager.clone().save_mempool().await?;
+        Ok(SaveMempoolResponse::new(transaction_count as u64))
+    }
+
+    async fn load_mempool_call(&self, _: LoadMempoolRequest) -> RpcResult<LoadMempoolResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("LoadMempool RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let session = self.consensus_manager.consensus().session().await;
+        let summary = self.mining_manager.clone().load_mempool(&session).await?;
+        Ok(LoadMempoolResponse::new(summary.transaction_count as u64, summary.accepted_count as u64))
+    }
+
     async fn get_connected_peer_info_call(&self, _: GetConnectedPeerInfoRequest) -> RpcResult<GetConnectedPeerInfoResponse> {
         let peers = self.flow_context.hub().active_peers();
         let peer_info = self.protocol_converter.get_peers_info(&peers);
@@ -837,6 +1013,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             p2p_bytes_rx: self.p2p_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
             grpc_bytes_tx: self.grpc_tower_counters.bytes_tx.load(Ordering::Relaxed) as u64,
             grpc_bytes_rx: self.grpc_tower_counters.bytes_rx.load(Ordering::Relaxed) as u64,
+            p2p_compact_block_bytes_saved: self.flow_context.compact_block_bytes_saved(),
         });
 
         let consensus_metrics = if req.consensus_metrics {
@@ -894,6 +1071,13 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetSyncStatusResponse { is_synced })
     }
 
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Access control
+
+    fn authorize(&self, api_key: Option<&str>, op: RpcApiOps) -> RpcResult<()> {
+        self.access_policy.authorize(api_key, op)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
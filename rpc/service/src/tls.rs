@@ -0,0 +1,26 @@
+use std::{fs, io, path::Path};
+
+/// PEM encoded certificate chain and private key used by the RPC listeners to serve TLS
+#[derive(Clone)]
+pub struct RpcTlsIdentity {
+    pub cert: Vec<u8>,
+    pub key: Vec<u8>,
+}
+
+impl RpcTlsIdentity {
+    pub fn new(cert: Vec<u8>, key: Vec<u8>) -> Self {
+        Self { cert, key }
+    }
+
+    /// Reads the PEM certificate chain and private key from the provided files
+    pub fn from_files(cert_path: impl AsRef<Path>, key_path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(fs::read(cert_path)?, fs::read(key_path)?))
+    }
+}
+
+impl std::fmt::Debug for RpcTlsIdentity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never leak the private key in logs
+        f.debug_struct("RpcTlsIdentity").finish_non_exhaustive()
+    }
+}
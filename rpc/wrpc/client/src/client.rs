@@ -67,6 +67,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::AddressHistoryChangedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -371,6 +372,18 @@ impl KaspaRpcClient {
         &self.inner.rpc_ctl
     }
 
+    /// Presents an API key to the node granting access to the RPC methods for the
+    /// rest of the connection. This must be repeated on every (re)connection.
+    pub async fn authenticate(&self, api_key: String) -> RpcResult<()> {
+        let _response: AuthenticateResponse = self
+            .inner
+            .rpc_client
+            .call(RpcApiOps::Authenticate, AuthenticateRequest::new(api_key))
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+
     /// Start background RPC services.
     pub async fn start(&self) -> Result<()> {
         if !self.inner.background_services_running.load(Ordering::SeqCst) {
@@ -587,6 +600,8 @@ impl RpcApi for KaspaRpcClient {
             AddPeer,
             Ban,
             EstimateNetworkHashesPerSecond,
+            GetAddressTransactions,
+            GetAddressTransactionCount,
             GetBalanceByAddress,
             GetBalancesByAddresses,
             GetBlock,
@@ -609,14 +624,22 @@ impl RpcApi for KaspaRpcClient {
             GetSink,
             GetSyncStatus,
             GetSubnetwork,
+            GetTransaction,
+            GetTransactionsByIds,
+            GetTransactionMerkleProof,
+            GetFeeEstimate,
             GetUtxosByAddresses,
             GetSinkBlueScore,
             GetVirtualChainFromBlock,
+            ListBans,
+            LoadMempool,
             Ping,
             ResolveFinalityConflict,
+            SaveMempool,
             Shutdown,
             SubmitBlock,
             SubmitTransaction,
+            SubmitTransactionReplacement,
             Unban,
         ]
     );
@@ -50,6 +50,8 @@ struct ConnectionInner {
     pub grpc_client: Option<Arc<GrpcClient>>,
     // not using an atomic in case an Id will change type in the future...
     pub listener_id: Mutex<Option<ListenerId>>,
+    /// The API key presented by the client through an `Authenticate` request
+    pub api_key: Mutex<Option<String>>,
 }
 
 impl ConnectionInner {
@@ -87,7 +89,9 @@ impl Connection {
         assert!(grpc_client.is_none() || grpc_client.as_ref().unwrap().notification_mode() == NotificationMode::Direct);
         // Should a gRPC client be provided, no listener_id is required for subscriptions so the listener id is set to default
         let listener_id = Mutex::new(grpc_client.clone().map(|_| ListenerId::default()));
-        Connection { inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id }) }
+        Connection {
+            inner: Arc::new(ConnectionInner { id, peer: *peer, messenger, grpc_client, listener_id, api_key: Default::default() }),
+        }
     }
 
     /// Obtain the connection id
@@ -120,6 +124,14 @@ impl Connection {
         self.inner.listener_id.lock().unwrap().replace(listener_id);
     }
 
+    pub fn api_key(&self) -> Option<String> {
+        self.inner.api_key.lock().unwrap().clone()
+    }
+
+    pub fn set_api_key(&self, api_key: String) {
+        self.inner.api_key.lock().unwrap().replace(api_key);
+    }
+
     pub fn peer(&self) -> &SocketAddr {
         &self.inner.peer
     }
@@ -35,6 +35,8 @@ impl Router {
                 AddPeer,
                 Ban,
                 EstimateNetworkHashesPerSecond,
+                GetAddressTransactions,
+                GetAddressTransactionCount,
                 GetBalanceByAddress,
                 GetBalancesByAddresses,
                 GetBlock,
@@ -57,15 +59,23 @@ impl Router {
                 GetMetrics,
                 GetSink,
                 GetSubnetwork,
+                GetTransaction,
+                GetTransactionsByIds,
+                GetTransactionMerkleProof,
+                GetFeeEstimate,
                 GetSyncStatus,
                 GetUtxosByAddresses,
                 GetSinkBlueScore,
                 GetVirtualChainFromBlock,
+                ListBans,
+                LoadMempool,
                 Ping,
                 ResolveFinalityConflict,
+                SaveMempool,
                 Shutdown,
                 SubmitBlock,
                 SubmitTransaction,
+                SubmitTransactionReplacement,
                 Unban,
             ]
         );
@@ -74,6 +84,10 @@ impl Router {
             RpcApiOps::Subscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Scope| {
                 Box::pin(async move {
+                    manager
+                        .rpc_service(&connection)
+                        .authorize(connection.api_key().as_deref(), RpcApiOps::Subscribe)
+                        .map_err(|err| err.to_string())?;
                     manager.start_notify(&connection, scope).await.map_err(|err| err.to_string())?;
                     Ok(SubscribeResponse::new(connection.id()))
                 })
@@ -92,6 +106,17 @@ impl Router {
             }),
         );
 
+        interface.method(
+            RpcApiOps::Authenticate,
+            workflow_rpc::server::Method::new(move |_manager: Server, connection: Connection, request: AuthenticateRequest| {
+                Box::pin(async move {
+                    // The key is checked against the access policy on every subsequent request
+                    connection.set_api_key(request.api_key);
+                    Ok(AuthenticateResponse {})
+                })
+            }),
+        );
+
         Router { interface: Arc::new(interface), server_context }
     }
 }
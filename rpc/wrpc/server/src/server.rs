@@ -125,6 +125,7 @@ impl Server {
                 None,
                 true,
                 None,
+                None,
                 Default::default(),
             )
             .await
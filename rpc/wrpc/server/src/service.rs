@@ -1,4 +1,4 @@
-use crate::{connection::*, router::*, server::*};
+use crate::{connection::*, router::*, server::*, tls};
 use async_trait::async_trait;
 use kaspa_core::{
     info,
@@ -6,10 +6,13 @@ use kaspa_core::{
     trace, warn,
 };
 use kaspa_rpc_core::api::ops::RpcApiOps;
-use kaspa_rpc_service::service::RpcCoreService;
+use kaspa_rpc_service::{service::RpcCoreService, tls::RpcTlsIdentity};
 use kaspa_utils::triggers::SingleTrigger;
 use std::sync::Arc;
-use tokio::sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender};
+use tokio::{
+    net::TcpListener,
+    sync::oneshot::{channel as oneshot_channel, Sender as OneshotSender},
+};
 use workflow_rpc::server::prelude::*;
 pub use workflow_rpc::server::{Encoding as WrpcEncoding, WebSocketConfig, WebSocketCounters};
 
@@ -20,11 +23,13 @@ pub struct Options {
     pub listen_address: String,
     pub grpc_proxy_address: Option<String>,
     pub verbose: bool,
+    /// Certificate and key used to serve TLS (`wss://`) connections
+    pub tls_identity: Option<RpcTlsIdentity>,
 }
 
 impl Default for Options {
     fn default() -> Self {
-        Options { listen_address: "127.0.0.1:17110".to_owned(), verbose: false, grpc_proxy_address: None }
+        Options { listen_address: "127.0.0.1:17110".to_owned(), verbose: false, grpc_proxy_address: None, tls_identity: None }
     }
 }
 
@@ -99,7 +104,11 @@ pub struct WrpcService {
     options: Arc<Options>,
     server: RpcServer,
     rpc_handler: Arc<KaspaRpcHandler>,
+    router: Arc<Router>,
+    encoding: Encoding,
+    counters: Arc<WebSocketCounters>,
     shutdown: SingleTrigger,
+    tls_shutdown: SingleTrigger,
 }
 
 impl WrpcService {
@@ -122,10 +131,19 @@ impl WrpcService {
             *encoding,
             rpc_handler.clone(),
             router.interface.clone(),
-            Some(counters),
+            Some(counters.clone()),
         );
 
-        WrpcService { options, server, rpc_handler, shutdown: SingleTrigger::default() }
+        WrpcService {
+            options,
+            server,
+            rpc_handler,
+            router,
+            encoding: *encoding,
+            counters,
+            shutdown: SingleTrigger::default(),
+            tls_shutdown: SingleTrigger::default(),
+        }
     }
 
     /// Start listening on the configured address (will panic if the socket listen() fails)
@@ -138,15 +156,45 @@ impl WrpcService {
         let service = self.clone();
         tokio::spawn(async move {
             let _ = termination_receiver.await;
-            service.server.stop().unwrap_or_else(|err| warn!("wRPC unable to signal shutdown: `{err}`"));
-            service.server.join().await.unwrap_or_else(|err| warn!("wRPC error: `{err}"));
+            if service.options.tls_identity.is_some() {
+                service.tls_shutdown.trigger.trigger();
+            } else {
+                service.server.stop().unwrap_or_else(|err| warn!("wRPC unable to signal shutdown: `{err}`"));
+                service.server.join().await.unwrap_or_else(|err| warn!("wRPC error: `{err}"));
+            }
         });
 
         // Spawn a task running the server
-        info!("WRPC Server starting on: {}", listen_address);
+        info!("WRPC Server starting on: {}{}", listen_address, if self.options.tls_identity.is_some() { " (TLS)" } else { "" });
         tokio::spawn(async move {
             let config = WebSocketConfig { max_message_size: Some(MAX_WRPC_MESSAGE_SIZE), ..Default::default() };
-            let serve_result = self.server.listen(&listen_address, Some(config)).await;
+            let serve_result = match self.options.tls_identity {
+                Some(ref tls_identity) => {
+                    // Terminate TLS on the listening socket itself and dispatch the WebSocket connections to the RPC handler
+                    let socket_address = tls::tls_listen_address(&listen_address)
+                        .unwrap_or_else(|err| panic!("WRPC Server {listen_address} setup failed with error: {err}"));
+                    let acceptor = tls::tls_acceptor(tls_identity)
+                        .unwrap_or_else(|err| panic!("WRPC Server {listen_address} TLS setup failed with error: {err}"));
+                    match TcpListener::bind(socket_address).await {
+                        Ok(listener) => {
+                            tls::serve(
+                                listener,
+                                acceptor,
+                                self.encoding,
+                                self.rpc_handler.clone(),
+                                self.router.interface.clone(),
+                                config,
+                                self.counters.clone(),
+                                self.tls_shutdown.listener.clone(),
+                            )
+                            .await;
+                            Ok(())
+                        }
+                        Err(err) => Err(WebSocketError::from(err)),
+                    }
+                }
+                None => self.server.listen(&listen_address, Some(config)).await,
+            };
             match serve_result {
                 Ok(_) => info!("WRPC Server stopped on: {}", listen_address),
                 Err(err) => panic!("WRPC Server {listen_address} stopped with error: {err:?}"),
@@ -0,0 +1,213 @@
+//! TLS listeners of the wRPC server
+//!
+//! The WebSocket server of the RPC framework only accepts plain TCP streams, so TLS listeners are
+//! served here instead: TLS is terminated on the accepted socket itself and the WebSocket connection
+//! is then dispatched to the same [`KaspaRpcHandler`] and protocol handler as on a plain listener,
+//! keeping the address of the remote peer.
+
+use crate::{connection::Connection, server::Server, service::KaspaRpcHandler};
+use futures::{SinkExt, StreamExt};
+use kaspa_core::debug;
+use kaspa_rpc_core::api::ops::RpcApiOps;
+use kaspa_rpc_service::tls::RpcTlsIdentity;
+use kaspa_utils::triggers::Listener;
+use rustls_pemfile::Item;
+use std::{
+    io::{Error, ErrorKind, Result},
+    net::SocketAddr,
+    sync::{atomic::Ordering, Arc},
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+};
+use tokio_rustls::{
+    rustls::{Certificate, PrivateKey, ServerConfig},
+    server::TlsStream,
+    TlsAcceptor,
+};
+use tokio_tungstenite::{accept_async_with_config, WebSocketStream};
+use workflow_rpc::server::prelude::*;
+
+type WebSocketTlsStream = WebSocketStream<TlsStream<TcpStream>>;
+
+/// Returns the `<host>:<port>` a TLS listener binds to, stripping the optional `wrpc://` scheme of its listen address
+pub fn tls_listen_address(address: &str) -> Result<&str> {
+    match address.split_once("://") {
+        Some(("wrpc", socket_address)) => Ok(socket_address),
+        Some((scheme, _)) => {
+            Err(Error::new(ErrorKind::InvalidInput, format!("unsupported scheme '{scheme}' in wRPC listen address '{address}'")))
+        }
+        None => Ok(address),
+    }
+}
+
+/// Builds a TLS acceptor from a PEM encoded certificate chain and private key
+pub(crate) fn tls_acceptor(identity: &RpcTlsIdentity) -> Result<TlsAcceptor> {
+    let certs = rustls_pemfile::certs(&mut identity.cert.as_slice())?.into_iter().map(Certificate).collect::<Vec<_>>();
+    if certs.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidData, "no certificate found in the TLS certificate file"));
+    }
+    let key = rustls_pemfile::read_all(&mut identity.key.as_slice())?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "no private key found in the TLS key file"))?;
+    let config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Serves wRPC over TLS on `listener` until `shutdown` is triggered, which also closes the open connections
+pub(crate) async fn serve(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    encoding: Encoding,
+    rpc_handler: Arc<KaspaRpcHandler>,
+    interface: Arc<Interface<Server, Connection, RpcApiOps>>,
+    config: WebSocketConfig,
+    counters: Arc<WebSocketCounters>,
+    shutdown: Listener,
+) {
+    match encoding {
+        Encoding::Borsh => {
+            let protocol = Arc::new(BorshProtocol::<Server, Connection, RpcApiOps, Id64>::new(interface));
+            Arc::new(TlsServer { acceptor, rpc_handler, protocol, config, counters, shutdown }).listen(listener).await
+        }
+        Encoding::SerdeJson => {
+            let protocol = Arc::new(JsonProtocol::<Server, Connection, RpcApiOps, Id64>::new(interface));
+            Arc::new(TlsServer { acceptor, rpc_handler, protocol, config, counters, shutdown }).listen(listener).await
+        }
+    }
+}
+
+struct TlsServer<Protocol> {
+    acceptor: TlsAcceptor,
+    rpc_handler: Arc<KaspaRpcHandler>,
+    protocol: Arc<Protocol>,
+    config: WebSocketConfig,
+    counters: Arc<WebSocketCounters>,
+    shutdown: Listener,
+}
+
+impl<Protocol> TlsServer<Protocol>
+where
+    Protocol: ProtocolHandler<Server, Connection, RpcApiOps> + 'static,
+{
+    async fn listen(self: Arc<Self>, listener: TcpListener) {
+        loop {
+            tokio::select! {
+                biased;
+                _ = self.shutdown.clone() => break,
+                accepted = listener.accept() => {
+                    if let Ok((stream, peer)) = accepted {
+                        if self.rpc_handler.accept(&peer) {
+                            self.accept(stream, peer);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn accept(self: &Arc<Self>, stream: TcpStream, peer: SocketAddr) {
+        self.counters.total_connections.fetch_add(1, Ordering::Relaxed);
+        self.counters.active_connections.fetch_add(1, Ordering::Relaxed);
+
+        let this = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = this.handle_connection(stream, peer).await {
+                debug!("WRPC TLS connection with {} closed with error: {}", peer, err);
+            }
+            this.counters.active_connections.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+
+    async fn handle_connection(&self, stream: TcpStream, peer: SocketAddr) -> WebSocketResult<()> {
+        let ws_stream = match self.handshake(stream).await {
+            Ok(ws_stream) => ws_stream,
+            Err(err) => {
+                self.counters.handshake_failures.fetch_add(1, Ordering::Relaxed);
+                return Err(err);
+            }
+        };
+
+        let (sink_sender, sink_receiver) = unbounded_channel::<Message>();
+        let messenger = Arc::new(Messenger::new(self.protocol.encoding(), &sink_sender));
+        let connection = self.rpc_handler.server.connect(&peer, messenger).await.map_err(|err| err.to_string())?;
+        let result = self.connection_task(&connection, ws_stream, &sink_sender, sink_receiver).await;
+        self.rpc_handler.server.disconnect(connection).await;
+        result
+    }
+
+    async fn handshake(&self, stream: TcpStream) -> WebSocketResult<WebSocketTlsStream> {
+        let tls_stream = self.acceptor.accept(stream).await?;
+        Ok(accept_async_with_config(tls_stream, Some(self.config)).await?)
+    }
+
+    async fn connection_task(
+        &self,
+        connection: &Connection,
+        ws_stream: WebSocketTlsStream,
+        sink_sender: &WebSocketSink,
+        mut sink_receiver: UnboundedReceiver<Message>,
+    ) -> WebSocketResult<()> {
+        let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+        loop {
+            tokio::select! {
+                _ = self.shutdown.clone() => {
+                    ws_sender.send(Message::Close(None)).await?;
+                    break;
+                }
+                msg = sink_receiver.recv() => {
+                    // The channel cannot close since `sink_sender` is held for the whole connection
+                    let msg = msg.unwrap();
+                    let is_close = matches!(msg, Message::Close(_));
+                    self.counters.tx_bytes.fetch_add(msg.len(), Ordering::Relaxed);
+                    ws_sender.send(msg).await?;
+                    if is_close {
+                        break;
+                    }
+                }
+                msg = ws_receiver.next() => {
+                    let msg = msg.ok_or(WebSocketError::AbnormalClose)??;
+                    match msg {
+                        Message::Binary(_) | Message::Text(_) => {
+                            self.counters.rx_bytes.fetch_add(msg.len(), Ordering::Relaxed);
+                            self.protocol.handle_message(connection.clone(), msg, sink_sender).await?;
+                        }
+                        Message::Close(_) => {
+                            self.protocol.handle_message(connection.clone(), msg, sink_sender).await?;
+                            break;
+                        }
+                        Message::Ping(data) => {
+                            self.counters.rx_bytes.fetch_add(data.len(), Ordering::Relaxed);
+                            ws_sender.send(Message::Pong(data)).await?;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tls_listen_address() {
+        assert_eq!(tls_listen_address("127.0.0.1:17110").unwrap(), "127.0.0.1:17110");
+        assert_eq!(tls_listen_address("wrpc://0.0.0.0:18110").unwrap(), "0.0.0.0:18110");
+        assert_eq!(tls_listen_address("wrpc://[::1]:17110").unwrap(), "[::1]:17110");
+        assert_eq!(tls_listen_address("localhost:17110").unwrap(), "localhost:17110");
+        assert!(tls_listen_address("ws://127.0.0.1:17110").is_err());
+    }
+}
@@ -912,6 +912,11 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for an address history changed notification event.
+    /// Address history changed notification event is produced when the virtual
+    /// selected chain changes the transaction history of some addresses
+    /// (requires the node to run with `--addresshistory`).
+    AddressHistoryChanged,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -979,13 +984,26 @@ build_wrpc_wasm_bindgen_interface!(
         /// Obtains basic information about the synchronization status of the Kaspa node.
         /// Returned information: Syncing status.
         GetSyncStatus,
+        /// Lists the subnets banned by the Kaspa node.
+        /// Returned information: Banned subnets, with the time of
+        /// their ban, its expiry and its reason.
+        ListBans,
+        /// Writes the mempool of the Kaspa node to its mempool file.
+        /// Returned information: Number of saved transactions.
+        SaveMempool,
+        /// Reloads the mempool file of the Kaspa node, revalidating its
+        /// transactions against the current virtual UTXO set.
+        /// Returned information: Number of read transactions and
+        /// number of transactions accepted back in the mempool.
+        LoadMempool,
     ],
     [
         // functions with `request` argument
         /// Adds a peer to the Kaspa node's list of known peers.
         /// Returned information: None.
         AddPeer,
-        /// Bans a peer from connecting to the Kaspa node for a specified duration.
+        /// Bans a peer IP or subnet from connecting to the Kaspa node
+        /// for a specified duration.
         /// Returned information: None.
         Ban,
         /// Estimates the network's current hash rate in hashes per second.
@@ -994,6 +1012,14 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves the balance of a specific address in the Kaspa BlockDAG.
         /// Returned information: Balance of the address.
         GetBalanceByAddress,
+        /// Retrieves a page of the transaction history of an address
+        /// (requires the node to run with `--addresshistory`).
+        /// Returned information: List of address history entries.
+        GetAddressTransactions,
+        /// Retrieves the number of transactions in the history of an address
+        /// (requires the node to run with `--addresshistory`).
+        /// Returned information: Transaction count.
+        GetAddressTransactionCount,
         /// Retrieves balances for multiple addresses in the Kaspa BlockDAG.
         /// Returned information: Balances of the addresses.
         GetBalancesByAddresses,
@@ -1028,6 +1054,23 @@ build_wrpc_wasm_bindgen_interface!(
         /// Retrieves information about a subnetwork in the Kaspa BlockDAG.
         /// Returned information: Subnetwork information.
         GetSubnetwork,
+        /// Retrieves a transaction from the transaction index
+        /// (requires the node to run with `--txindex`).
+        /// Returned information: Indexed transaction information.
+        GetTransaction,
+        /// Retrieves multiple transactions from the transaction index
+        /// (requires the node to run with `--txindex`).
+        /// Returned information: List of indexed transactions.
+        GetTransactionsByIds,
+        /// Retrieves a proof of inclusion of a transaction in a block, along with
+        /// the headers linking the block to the virtual selected chain, which can be
+        /// checked with {@link verifyTransactionMerkleProof}.
+        /// Returned information: Transaction merkle proof.
+        GetTransactionMerkleProof,
+        /// Retrieves feerate estimations (in sompi per gram) for transactions
+        /// to be included in the next block, within a minute or within an hour.
+        /// Returned information: Fee estimate and optionally the mempool feerate histogram.
+        GetFeeEstimate,
         /// Retrieves unspent transaction outputs (UTXOs) associated with
         /// specific addresses.
         /// Returned information: List of UTXOs.
@@ -1044,6 +1087,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// Submits a transaction to the Kaspa network.
         /// Returned information: None.
         SubmitTransaction,
+        /// Submits a transaction replacing by fee the mempool
+        /// transactions it double spends.
+        /// Returned information: Ids of the evicted transactions.
+        SubmitTransactionReplacement,
         /// Unbans a previously banned peer, allowing it to connect
         /// to the Kaspa node again.
         /// Returned information: None.
@@ -21,6 +21,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    AddressHistoryChanged = "address-history-changed",
 }
 
 /**
@@ -36,7 +37,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IAddressHistoryChanged;
 
 /**
  * RPC notification event data map.
@@ -55,6 +57,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "address-history-changed" : IAddressHistoryChanged,
 }
 
 /**
@@ -80,6 +83,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeAddressHistoryChanged},
  * 
  * @category Node RPC
  */
@@ -237,3 +241,18 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IAddressHistoryChanged,
+    r#"
+    /**
+     * Address history changed notification event is produced when the virtual
+     * selected chain changes the transaction history of some addresses.
+     * 
+     * @category Node RPC
+     */
+    export interface IAddressHistoryChanged {
+        [key: string]: any;
+    }
+    "#,
+}
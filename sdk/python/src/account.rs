@@ -15,6 +15,8 @@ use kaspa_wallet_core::events::Events;
 use kaspa_wallet_core::prelude::{PaymentDestination, PaymentOutputs, Secret};
 use kaspa_wallet_core::utils::kaspa_to_sompi;
 
+use crate::pskt::{py_error, Pskt};
+
 type ListenerCallback = Arc<Mutex<HashMap<String, Py<PyFunction>>>>;
 
 #[pyclass]
@@ -163,6 +165,7 @@ impl PyAccount {
                         PaymentDestination::PaymentOutputs(PaymentOutputs::from((Address::try_from(address).unwrap(), amount_leor))),
                         priority_fee_leor.into(),
                         None,
+                        None,
                         Secret::new(vec![]),
                         None,
                         &abortable,
@@ -196,7 +199,7 @@ impl PyAccount {
                     let change_address = account.clone().change_address().unwrap();
                     let destination = PaymentDestination::PaymentOutputs(PaymentOutputs::from((change_address.clone(), amount_leor)));
                     // let estimate = account.clone().estimate(destination, priority_fee_leor.into(), None, &abortable).await.unwrap();
-                    let estimate = account.clone().estimate(destination, priority_fee_leor.into(), None, &abortable).await.unwrap();
+                    let estimate = account.clone().estimate(destination, priority_fee_leor.into(), None, None, &abortable).await.unwrap();
 
                     Ok(estimate.aggregated_fees())
                 })
@@ -205,6 +208,47 @@ impl PyAccount {
         }
     }
 
+    pub fn create_pskt<'a>(&self, py: Python<'a>, address: String, priority_fee_pyi: f64, amount_pyi: f64) -> PyResult<&'a PyAny> {
+        match &self.account {
+            Some(account) => {
+                let account = account.clone();
+                let priority_fee_leor = kaspa_to_sompi(priority_fee_pyi);
+                let amount_leor = kaspa_to_sompi(amount_pyi);
+                let address = Address::try_from(address).map_err(py_error)?;
+
+                pyo3_asyncio::tokio::future_into_py(py, async move {
+                    let abortable = Abortable::default();
+
+                    let (_summary, pskts) = account.create_pskt(
+                        PaymentDestination::PaymentOutputs(PaymentOutputs::from((address, amount_leor))),
+                        priority_fee_leor.into(),
+                        None,
+                        &abortable,
+                    ).await.map_err(py_error)?;
+
+                    Ok(pskts.into_iter().map(|inner| Pskt { inner }).collect::<Vec<_>>())
+                })
+            }
+            None => Err(PyErr::new::<pyo3::exceptions::PyAttributeError, _>("Account not initialized")),
+        }
+    }
+
+    /// Signs the PSKT inputs spending from this account and returns the updated PSKT
+    pub fn sign_pskt<'a>(&self, py: Python<'a>, pskt: Pskt) -> PyResult<&'a PyAny> {
+        match &self.account {
+            Some(account) => {
+                let account = account.clone();
+
+                pyo3_asyncio::tokio::future_into_py(py, async move {
+                    let mut inner = pskt.inner;
+                    account.sign_pskt(&mut inner, Secret::new(vec![]), None).await.map_err(py_error)?;
+                    Ok(Pskt { inner })
+                })
+            }
+            None => Err(PyErr::new::<pyo3::exceptions::PyAttributeError, _>("Account not initialized")),
+        }
+    }
+
     pub fn change_address(&self) -> PyResult<String> {
         match &self.account {
             Some(account) => {
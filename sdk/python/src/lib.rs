@@ -1,6 +1,7 @@
 mod wallet;
 mod bip32;
 mod account;
+mod pskt;
 mod rpc;
 mod rpc_types;
 mod rpc_core;
@@ -8,6 +9,7 @@ mod rpc_core;
 use pyo3::prelude::*;
 use pyo3::types::PyFunction;
 use account::{PyAccount, PyBalance};
+use kaspa_rpc_core::RpcTransactionMerkleProof;
 use kaspa_wallet_core::utils::{kaspa_to_sompi, sompi_to_kaspa};
 
 #[pyfunction]
@@ -20,6 +22,13 @@ fn from_leor(value: u64) -> PyResult<f64> {
     Ok(sompi_to_kaspa(value))
 }
 
+/// Verifies a proof returned by `RPC.get_transaction_merkle_proof` without trusting the node, returning the hash
+/// of the chain block the proof leads to, or None if the proof is invalid
+#[pyfunction]
+fn verify_transaction_merkle_proof(proof: RpcTransactionMerkleProof) -> PyResult<Option<String>> {
+    Ok(proof.verify().map(|hash| hash.to_string()))
+}
+
 #[pyfunction]
 fn call_with_callback(callback: Py<PyFunction>, data: &PyAny) -> PyResult<()> {
     Python::with_gil(|py| {
@@ -34,11 +43,13 @@ fn pyrin(_py: Python, m: &PyModule) -> PyResult<()> {
 
     m.add_function(wrap_pyfunction!(to_leor, m)?)?;
     m.add_function(wrap_pyfunction!(from_leor, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_transaction_merkle_proof, m)?)?;
 
     m.add_class::<wallet::Wallet>()?;
     m.add_class::<PyAccount>()?;
     m.add_class::<PyBalance>()?;
     m.add_class::<bip32::Bip32>()?;
+    m.add_class::<pskt::Pskt>()?;
     m.add_class::<rpc::RPC>()?;
 
     m.add_function(wrap_pyfunction!(call_with_callback, m)?)?;
@@ -0,0 +1,79 @@
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+
+use kaspa_consensus_core::hashing::sighash_type::SigHashType;
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_utils::hex::FromHex;
+use kaspa_wallet_core::tx::Pskt as NativePskt;
+
+pub fn py_error(err: impl ToString) -> PyErr {
+    PyErr::new::<PyException, _>(err.to_string())
+}
+
+/// Partially signed transaction, exchanged between participants in its serialized string form
+#[pyclass]
+#[derive(Clone)]
+pub struct Pskt {
+    pub inner: NativePskt,
+}
+
+impl Pskt {
+    pub fn extract_transaction(&self) -> PyResult<Transaction> {
+        let mut pskt = self.inner.clone();
+        pskt.finalize().map_err(py_error)?;
+        pskt.extract().map_err(py_error)
+    }
+}
+
+#[pymethods]
+impl Pskt {
+    #[new]
+    fn new(serialized: String) -> PyResult<Self> {
+        Ok(Pskt { inner: serialized.parse().map_err(py_error)? })
+    }
+
+    #[getter]
+    fn id(&self) -> String {
+        self.inner.id().to_string()
+    }
+
+    #[getter]
+    fn is_finalized(&self) -> bool {
+        self.inner.is_finalized()
+    }
+
+    fn set_redeem_script(&mut self, index: usize, redeem_script: String) -> PyResult<()> {
+        let redeem_script = Vec::<u8>::from_hex(&redeem_script).map_err(py_error)?;
+        self.inner.set_redeem_script(index, redeem_script).map_err(py_error)
+    }
+
+    fn set_sighash_type(&mut self, index: usize, sighash_type: u8) -> PyResult<()> {
+        let sighash_type = SigHashType::from_u8(sighash_type).map_err(py_error)?;
+        self.inner.set_sighash_type(index, sighash_type).map_err(py_error)
+    }
+
+    /// Signs the inputs which can be spent by the given hex encoded private keys and returns the number of signatures added
+    fn sign(&mut self, private_keys: Vec<String>) -> PyResult<usize> {
+        let private_keys = private_keys
+            .iter()
+            .map(|key| Vec::<u8>::from_hex(key).map_err(py_error)?.try_into().map_err(|_| py_error("invalid private key length")))
+            .collect::<PyResult<Vec<_>>>()?;
+        self.inner.sign(&private_keys).map_err(py_error)
+    }
+
+    fn combine(&self, other: &Pskt) -> PyResult<Pskt> {
+        Ok(Pskt { inner: self.inner.clone().combine(other.inner.clone()).map_err(py_error)? })
+    }
+
+    fn finalize(&mut self) -> PyResult<()> {
+        self.inner.finalize().map_err(py_error)
+    }
+
+    fn serialize(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+}
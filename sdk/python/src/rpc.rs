@@ -14,13 +14,14 @@ use workflow_core::prelude::spawn;
 use kaspa_addresses::Address;
 use kaspa_consensus_core::network::{NetworkId, NetworkType};
 use kaspa_consensus_core::tx::TransactionId;
-use kaspa_notify::scope::{BlockAddedScope, FinalityConflictResolvedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope};
-use kaspa_rpc_core::{Notification, RpcAddress, RpcBlock, RpcContextualPeerAddress, RpcExtraData, RpcHash, RpcIpAddress, RpcSubnetworkId};
+use kaspa_notify::scope::{AddressHistoryChangedScope, BlockAddedScope, FinalityConflictResolvedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope};
+use kaspa_rpc_core::{Notification, RpcAddress, RpcBlock, RpcContextualPeerAddress, RpcExtraData, RpcHash, RpcIpSubnet, RpcSubnetworkId};
 use kaspa_rpc_core::api::ctl::RpcState;
 use kaspa_rpc_core::notify::connection::{ChannelConnection, ChannelType};
 use kaspa_wallet_core::prelude::KaspaRpcClient;
 use kaspa_wallet_core::rpc::WrpcEncoding;
 
+use crate::pskt::Pskt;
 use crate::rpc_core::RpcCore;
 use crate::rpc_types::{py_rpc_block_type, py_rpc_transaction_type};
 
@@ -174,6 +175,12 @@ impl RPC {
 
                                         emit_event("virtual-chain", (added_chain_block_hashes, removed_chain_block_hashes, accepted_transaction_ids,), &listeners);
                                     },
+                                    Notification::AddressHistoryChanged(ref payload) => {
+                                        let added: Vec<_> = payload.added.iter().cloned().collect();
+                                        let removed: Vec<_> = payload.removed.iter().cloned().collect();
+
+                                        emit_event("address-history-changed", (added, removed,), &listeners);
+                                    },
                                     _ => {}
                                 }
 
@@ -256,6 +263,10 @@ impl RPC {
         listen_event!(self, py, "virtual-chain".to_string(), callback, Scope::VirtualChainChanged(VirtualChainChangedScope {include_accepted_transaction_ids}))
     }
 
+    pub fn on_address_history_changed<'a>(&mut self, py: Python<'a>, callback: Py<PyFunction>) -> PyResult<&'a PyAny> {
+        listen_event!(self, py, "address-history-changed".to_string(), callback, Scope::AddressHistoryChanged(AddressHistoryChangedScope {}))
+    }
+
     pub fn ping<'a>(&mut self, py: Python<'a>) -> PyResult<&'a PyAny> {
         let client = Arc::new(self.client.clone().unwrap());
 
@@ -416,6 +427,24 @@ impl RPC {
         })
     }
 
+    pub fn submit_pskt<'a>(&mut self, py: Python<'a>, pskt: &Pskt) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+        let transaction = pskt.extract_transaction()?;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().submit_transaction((&transaction).into(), false).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn submit_transaction_replacement<'a>(&mut self, py: Python<'a>, transaction: &PyDict) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+        let transaction = py_rpc_transaction_type(transaction)?;
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().submit_transaction_replacement(transaction).await.map_err(PyErr::from)
+        })
+    }
+
     pub fn get_block<'a>(&mut self, py: Python<'a>, hash: String, include_transactions: bool) -> PyResult<&'a PyAny> {
         let client = Arc::new(self.client.clone().unwrap());
 
@@ -528,21 +557,45 @@ impl RPC {
         })
     }
 
-    pub fn ban<'a>(&mut self, py: Python<'a>, ip: String) -> PyResult<&'a PyAny> {
+    pub fn ban<'a>(&mut self, py: Python<'a>, ip: String, duration: Option<u64>, reason: Option<String>) -> PyResult<&'a PyAny> {
         let client = Arc::new(self.client.clone().unwrap());
-        let ip = RpcIpAddress::from_str(ip.as_str()).expect("Failed to parse ip");
+        let subnet = RpcIpSubnet::from_str(ip.as_str()).expect("Failed to parse ip or subnet");
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            client.rpc_api().ban(ip).await.map_err(PyErr::from)
+            client.rpc_api().ban(subnet, duration, reason).await.map_err(PyErr::from)
         })
     }
 
     pub fn unban<'a>(&mut self, py: Python<'a>, ip: String) -> PyResult<&'a PyAny> {
         let client = Arc::new(self.client.clone().unwrap());
-        let ip = RpcIpAddress::from_str(ip.as_str()).expect("Failed to parse ip");
+        let subnet = RpcIpSubnet::from_str(ip.as_str()).expect("Failed to parse ip or subnet");
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().unban(subnet).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn list_bans<'a>(&mut self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().list_bans().await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn save_mempool<'a>(&mut self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().save_mempool().await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn load_mempool<'a>(&mut self, py: Python<'a>) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
 
         pyo3_asyncio::tokio::future_into_py(py, async move {
-            client.rpc_api().unban(ip).await.map_err(PyErr::from)
+            client.rpc_api().load_mempool().await.map_err(PyErr::from)
         })
     }
 
@@ -590,4 +643,57 @@ impl RPC {
             client.rpc_api().get_daa_score_timestamp_estimate(daa_scores).await.map_err(PyErr::from)
         })
     }
+
+    pub fn get_transaction<'a>(&mut self, py: Python<'a>, transaction_id: String, include_transaction: bool) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+        let transaction_id = TransactionId::from_str(transaction_id.as_str()).expect("Failed to parse transaction id");
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().get_transaction(transaction_id, include_transaction).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn get_transactions_by_ids<'a>(&mut self, py: Python<'a>, transaction_ids: Vec<String>, include_transactions: bool) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+        let transaction_ids = transaction_ids.iter().map(|transaction_id| TransactionId::from_str(transaction_id.as_str()).expect("Failed to parse transaction id")).collect();
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().get_transactions_by_ids(transaction_ids, include_transactions).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn get_transaction_merkle_proof<'a>(&mut self, py: Python<'a>, transaction_id: String, block_hash: Option<String>) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+        let transaction_id = TransactionId::from_str(transaction_id.as_str()).expect("Failed to parse transaction id");
+        let block_hash = block_hash.map(|block_hash| RpcHash::from_str(block_hash.as_str()).expect("Failed to parse block hash"));
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().get_transaction_merkle_proof(transaction_id, block_hash).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn get_address_transactions<'a>(&mut self, py: Python<'a>, address: String, start_daa_score: u64, limit: u32, start_transaction_id: Option<String>) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+        let start_transaction_id = start_transaction_id.map(|transaction_id| TransactionId::from_str(transaction_id.as_str()).expect("Failed to parse transaction id"));
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().get_address_transactions(Address::try_from(address).expect("Failed to parse address"), start_daa_score, start_transaction_id, limit).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn get_address_transaction_count<'a>(&mut self, py: Python<'a>, address: String) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().get_address_transaction_count(Address::try_from(address).expect("Failed to parse address")).await.map_err(PyErr::from)
+        })
+    }
+
+    pub fn get_fee_estimate<'a>(&mut self, py: Python<'a>, verbose: bool) -> PyResult<&'a PyAny> {
+        let client = Arc::new(self.client.clone().unwrap());
+
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            client.rpc_api().get_fee_estimate(verbose).await.map_err(PyErr::from)
+        })
+    }
 }
\ No newline at end of file
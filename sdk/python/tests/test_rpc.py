@@ -204,6 +204,52 @@ class TestWallet(unittest.IsolatedAsyncioTestCase):
         result = await rpc.get_mempool_entry("a419045a31afad611c32344fa269e712499d3e97f74271e4a2deffa734ba9f71", True, True)
         print("result", result)
 
+    @unittest.skip
+    async def test_get_transaction(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        result = await rpc.get_transaction("a419045a31afad611c32344fa269e712499d3e97f74271e4a2deffa734ba9f71", True)
+        print("transaction_id", result.transaction_id)
+        print("acceptance", result.acceptance)
+        print("inclusions", result.inclusions)
+
+    @unittest.skip
+    async def test_get_transactions_by_ids(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        result = await rpc.get_transactions_by_ids(["a419045a31afad611c32344fa269e712499d3e97f74271e4a2deffa734ba9f71"], False)
+        print("get_transactions_by_ids", result)
+
+    @unittest.skip
+    async def test_get_transaction_merkle_proof(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        proof = await rpc.get_transaction_merkle_proof("a419045a31afad611c32344fa269e712499d3e97f74271e4a2deffa734ba9f71", None)
+        print("block_hash", proof.block_hash, "headers", len(proof.headers))
+        print("chain_block", pyrin.verify_transaction_merkle_proof(proof))
+
+    @unittest.skip
+    async def test_get_address_transactions(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        result = await rpc.get_address_transactions("pyrin:qzn54t6vpasykvudztupcpwn2gelxf8y9p84szksr73me39mzf69uaalnymtx", 0, 100)
+        for entry in result:
+            print("transaction_id", entry.transaction_id, "received", entry.received, "spent", entry.spent)
+
+    @unittest.skip
+    async def test_get_address_transaction_count(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        count = await rpc.get_address_transaction_count("pyrin:qzn54t6vpasykvudztupcpwn2gelxf8y9p84szksr73me39mzf69uaalnymtx")
+        print("count", count)
+
+    @unittest.skip
+    async def test_get_fee_estimate(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        result = await rpc.get_fee_estimate(True)
+        print("get_fee_estimate", result.estimate.priority_bucket.feerate)
+
     @unittest.skip
     async def test_get_mempool_entries(self):
         rpc = pyrin.RPC()
@@ -263,6 +309,37 @@ class TestWallet(unittest.IsolatedAsyncioTestCase):
             }],
         }, True)
 
+    @unittest.skip
+    async def test_submit_transaction_replacement(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        result = await rpc.submit_transaction_replacement({
+            "version": 0,
+            "lock_time": 0,
+            "subnetwork_id": "0000000000000000000000000000000000000000",
+            "gas": 0,
+            "payload": [],
+            "mass": 0,
+            "inputs": [{
+                "previous_outpoint": {
+                    "transaction_id": "9e30e8d0327480c6c9c6b227537c018827a25e7e2e51280e8328acdbcf0fe76c",
+                    "index": 0,
+                },
+                "signature_script": [],
+                "sequence": 0,
+                "sig_op_count": 0,
+                "verbose_data": None,
+            }],
+            "outputs": [{
+                "script_public_key": {
+                    "version": 0,
+                    "script": list(b" \x1aP\x9d\xde\xa1\x81\x93\xbd&}\x01\x07\xb2(zv\x1f\x89f\xe9 \xf4\x9c\xc5\xfb\xfb\t\xcd\xfd\x0fg\xfe\xac"),
+                },
+                "value": 99990000,
+            }],
+        })
+        print("submit_transaction_replacement", result.transaction_id, result.removed_transaction_ids)
+
     @unittest.skip
     async def test_get_block(self):
         rpc = pyrin.RPC()
@@ -433,6 +510,28 @@ class TestWallet(unittest.IsolatedAsyncioTestCase):
         await rpc.connect()
         await rpc.unban("192.168.1.2")
 
+    @unittest.skip
+    async def test_list_bans(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        await rpc.ban("192.168.0.0/16", 3600, "test")
+        bans = await rpc.list_bans()
+        print("bans:", [(ban.subnet, ban.expires_at, ban.reason) for ban in bans])
+
+    @unittest.skip
+    async def test_save_mempool(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        transaction_count = await rpc.save_mempool()
+        print("saved mempool transactions:", transaction_count)
+
+    @unittest.skip
+    async def test_load_mempool(self):
+        rpc = pyrin.RPC()
+        await rpc.connect()
+        result = await rpc.load_mempool()
+        print("reloaded mempool transactions:", result.accepted_count, "of", result.transaction_count)
+
     @unittest.skip
     async def test_get_info(self):
         rpc = pyrin.RPC()
@@ -4,7 +4,7 @@ use futures::{future::try_join_all, Future};
 use itertools::Itertools;
 use kaspa_alloc::init_allocator_with_default_settings;
 use kaspa_consensus::{
-    config::ConfigBuilder,
+    config::{Config, ConfigBuilder},
     consensus::Consensus,
     constants::perf::PerfParams,
     model::stores::{
@@ -17,7 +17,7 @@ use kaspa_consensus::{
 };
 use kaspa_consensus_core::{
     api::ConsensusApi, block::Block, blockstatus::BlockStatus, config::bps::calculate_ghostdag_k, errors::block::BlockProcessResult,
-    BlockHashSet, BlockLevel, HashMapCustomHasher,
+    network::NetworkId, BlockHashSet, BlockLevel, HashMapCustomHasher,
 };
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::{info, task::service::AsyncService, task::tick::TickService, time::unix_now, trace, warn};
@@ -26,9 +26,11 @@ use kaspa_database::{create_temp_db, load_existing_db};
 use kaspa_hashes::Hash;
 use kaspa_perf_monitor::{builder::Builder, counters::CountersSnapshot};
 use kaspa_utils::fd_budget;
+use replay::{replay, ReplaySource};
 use simulator::network::KaspaNetworkSimulator;
 use std::{collections::VecDeque, sync::Arc, time::Duration};
 
+pub mod replay;
 pub mod simulator;
 
 /// Kaspa Network Simulator
@@ -55,7 +57,7 @@ struct Args {
     #[arg(short, long, default_value_t = 600)]
     sim_time: u64,
 
-    /// Target number of blocks the simulation should produce (overrides --sim-time if specified)
+    /// Target number of blocks the simulation should produce (overrides --sim-time if specified), or number of blocks to replay
     #[arg(short = 'n', long)]
     target_blocks: Option<u64>,
 
@@ -117,6 +119,16 @@ struct Args {
     rocksdb_files_limit: Option<i32>,
     #[arg(long)]
     rocksdb_mem_budget: Option<usize>,
+
+    /// Replay the blocks of an existing node datadir (or consensus database directory) into a fresh consensus instead of
+    /// simulating a DAG, and report the throughput of the header, body and virtual processors. The node must be stopped
+    /// and hold the blocks from genesis on (archival node)
+    #[arg(long)]
+    replay_dir: Option<String>,
+
+    /// Network of the replayed datadir (mainnet, testnet-10, testnet-11, devnet, simnet)
+    #[arg(long, default_value = "mainnet")]
+    replay_network: String,
 }
 
 #[cfg(feature = "heap")]
@@ -169,6 +181,14 @@ fn main_impl(mut args: Args) {
         m.stop()
     });
 
+    if let Some(replay_dir) = args.replay_dir.clone() {
+        replay_datadir(&args, &rt, &replay_dir);
+        if let Some(stop_perf_monitor) = stop_perf_monitor {
+            _ = rt.block_on(stop_perf_monitor);
+        }
+        return;
+    }
+
     if args.miners > 1 {
         warn!(
             "Warning: number of miners was configured to {}. Currently each miner added doubles the simulation
@@ -177,20 +197,7 @@ fn main_impl(mut args: Args) {
         );
     }
     args.bps = if args.testnet11 { Testnet11Bps::bps() as f64 } else { args.bps };
-    let mut params = if args.testnet11 { TESTNET11_PARAMS } else { DEVNET_PARAMS };
-    params.storage_mass_activation_daa_score = 400;
-    params.storage_mass_parameter = 10_000;
-    let mut builder = ConfigBuilder::new(params)
-        .apply_args(|config| apply_args_to_consensus_params(&args, &mut config.params))
-        .apply_args(|config| apply_args_to_perf_params(&args, &mut config.perf))
-        .adjust_perf_params_to_consensus_params()
-        .apply_args(|config| config.ram_scale = args.ram_scale)
-        .skip_proof_of_work()
-        .enable_sanity_checks();
-    if !args.test_pruning {
-        builder = builder.set_archival();
-    }
-    let config = Arc::new(builder.build());
+    let config = simulation_config(&args);
     let default_fd = fd_budget::limit() / 2;
     let mut conn_builder = ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd);
     if let Some(rocksdb_files_limit) = args.rocksdb_files_limit {
@@ -270,6 +277,47 @@ fn main_impl(mut args: Args) {
     drop(consensus);
 }
 
+fn simulation_config(args: &Args) -> Arc<Config> {
+    let mut params = if args.testnet11 { TESTNET11_PARAMS } else { DEVNET_PARAMS };
+    params.storage_mass_activation_daa_score = 400;
+    params.storage_mass_parameter = 10_000;
+    let mut builder = ConfigBuilder::new(params)
+        .apply_args(|config| apply_args_to_consensus_params(args, &mut config.params))
+        .apply_args(|config| apply_args_to_perf_params(args, &mut config.perf))
+        .adjust_perf_params_to_consensus_params()
+        .apply_args(|config| config.ram_scale = args.ram_scale)
+        .skip_proof_of_work()
+        .enable_sanity_checks();
+    if !args.test_pruning {
+        builder = builder.set_archival();
+    }
+    Arc::new(builder.build())
+}
+
+fn replay_datadir(args: &Args, rt: &tokio::runtime::Runtime, replay_dir: &str) {
+    let network_id: NetworkId = match args.replay_network.parse() {
+        Ok(network_id) => network_id,
+        Err(err) => {
+            warn!("Invalid replay network {}: {}", args.replay_network, err);
+            return;
+        }
+    };
+    let config = Arc::new(
+        ConfigBuilder::new(Params::from(network_id))
+            .apply_args(|config| apply_args_to_perf_params(args, &mut config.perf))
+            .adjust_perf_params_to_consensus_params()
+            .apply_args(|config| config.ram_scale = args.ram_scale)
+            .build(),
+    );
+    let files_limit = args.rocksdb_files_limit.unwrap_or(fd_budget::limit() / 4);
+    let result = ReplaySource::open(replay_dir, files_limit)
+        .and_then(|source| rt.block_on(replay(&source, config, args.target_blocks, args.headers_first)));
+    match result {
+        Ok(sink) => info!("Replay reached sink {}", sink),
+        Err(err) => warn!("Replay of {} failed: {}", replay_dir, err),
+    }
+}
+
 fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
     // We have no actual PoW in the simulation, so the true max is most reflective,
     // however we avoid the actual max since it is reserved for the DB prefix scheme
@@ -447,4 +495,36 @@ mod tests {
         kaspa_core::panic::configure_panic();
         main_impl(args);
     }
+
+    #[test]
+    fn test_replay_via_simpa() {
+        let mut args = Args::parse_from(std::iter::empty::<&str>());
+        args.bps = 1.0;
+        args.target_blocks = Some(200);
+        args.tpb = 2;
+
+        kaspa_core::log::try_init_logger(&args.log_level);
+        kaspa_core::panic::configure_panic();
+
+        // Simulate a DAG into a permanent directory which is then replayed as a consensus database directory
+        let config = simulation_config(&args);
+        let tempdir = kaspa_database::utils::get_kaspa_tempdir();
+        let dir = tempdir.path().join("simulation").to_str().unwrap().to_string();
+        let expected_sink = {
+            let mut sim = KaspaNetworkSimulator::new(args.delay, args.bps, args.target_blocks, config.clone(), Some(dir.clone()));
+            let (consensus, handles, lifetime) = sim.init(args.miners, args.tpb, false, None, None, None).run(u64::MAX);
+            consensus.shutdown(handles);
+            let sink = consensus.get_sink();
+            // Release the DB so it can be opened by the replay
+            drop(consensus);
+            drop(lifetime);
+            sink
+        };
+
+        let source = ReplaySource::open(&dir, 64).unwrap();
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        assert_eq!(source.topologically_ordered_hashes(config.genesis.hash, Some(50)).unwrap().len(), 50);
+        let sink = rt.block_on(replay(&source, config, None, true)).unwrap();
+        assert_eq!(sink, expected_sink);
+    }
 }
@@ -0,0 +1,316 @@
+//! Replays the blocks of an existing node datadir into a fresh consensus, measuring
+//! the throughput of the header, body and virtual processors on real data.
+
+use async_channel::unbounded;
+use futures::{future::join_all, Future};
+use kaspa_consensus::{
+    config::Config,
+    consensus::{factory::MultiConsensusManagementStore, Consensus},
+    model::stores::{
+        block_transactions::{BlockTransactionsStoreReader, DbBlockTransactionsStore},
+        headers::{DbHeadersStore, HeaderStoreReader},
+        relations::{DbRelationsStore, RelationsStoreReader},
+    },
+    pipeline::ProcessingCountersSnapshot,
+};
+use kaspa_consensus_core::{
+    api::ConsensusApi, block::Block, blockstatus::BlockStatus, errors::block::BlockProcessResult, BlockHashMap, HashMapCustomHasher,
+};
+use kaspa_consensus_notify::root::ConsensusNotificationRoot;
+use kaspa_core::{info, time::unix_now, trace, warn};
+use kaspa_database::{
+    create_temp_db,
+    prelude::{CachePolicy, ConnBuilder, DB},
+};
+use kaspa_hashes::Hash;
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Sub-directories of a node datadir holding the consensus databases and the database pointing at the active one
+const CONSENSUS_DB: &str = "consensus";
+const META_DB: &str = "meta";
+
+/// Number of blocks submitted before awaiting the processing of the previous chunk
+const CHUNK_SIZE: usize = 1000;
+
+/// Interval at which the throughput of the processors is reported during the replay
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Read access to the headers and bodies of the blocks stored by a node
+pub struct ReplaySource {
+    headers_store: DbHeadersStore,
+    block_transactions_store: DbBlockTransactionsStore,
+    relations_store: DbRelationsStore,
+}
+
+impl ReplaySource {
+    /// Opens either a node datadir, replaying its active consensus, or a consensus database directory
+    pub fn open(dir: &str, files_limit: i32) -> Result<Self, String> {
+        let dir = PathBuf::from(dir);
+        let db_dir = if dir.join(META_DB).is_dir() { Self::active_consensus_dir(&dir)? } else { dir };
+        info!("Replaying the consensus database {}", db_dir.display());
+        let db = ConnBuilder::default()
+            .with_db_path(db_dir.clone())
+            .with_files_limit(files_limit)
+            .with_create_if_missing(false)
+            .build()
+            .map_err(|err| format!("failed to open {} (make sure the node is stopped): {err}", db_dir.display()))?;
+        Ok(Self::new(db))
+    }
+
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            headers_store: DbHeadersStore::new(db.clone(), CachePolicy::Empty, CachePolicy::Empty),
+            block_transactions_store: DbBlockTransactionsStore::new(db.clone(), CachePolicy::Empty),
+            relations_store: DbRelationsStore::new(db, 0, CachePolicy::Empty, CachePolicy::Empty),
+        }
+    }
+
+    fn active_consensus_dir(datadir: &Path) -> Result<PathBuf, String> {
+        let meta_db = ConnBuilder::default()
+            .with_db_path(datadir.join(META_DB))
+            .with_files_limit(1)
+            .with_create_if_missing(false)
+            .build()
+            .map_err(|err| {
+                format!("failed to open the meta database of {} (make sure the node is stopped): {err}", datadir.display())
+            })?;
+        match MultiConsensusManagementStore::new(meta_db).active_consensus_dir_name() {
+            Ok(Some(name)) => Ok(datadir.join(CONSENSUS_DB).join(name)),
+            Ok(None) => Err(format!("the datadir {} holds no active consensus", datadir.display())),
+            Err(err) => Err(format!("failed to read the active consensus of {}: {err}", datadir.display())),
+        }
+    }
+
+    fn block(&self, hash: Hash, header_only: bool) -> Result<Block, String> {
+        let header = self.headers_store.get_header(hash).map_err(|err| format!("missing header of block {hash}: {err}"))?;
+        let transactions = match header_only {
+            true => Default::default(),
+            false => self.block_transactions_store.get(hash).map_err(|err| format!("missing body of block {hash}: {err}"))?,
+        };
+        Ok(Block::from_arcs(header, transactions))
+    }
+
+    /// Returns the hashes of the blocks following `genesis` in topological order, a block coming
+    /// after all its parents, up to `limit` blocks
+    pub fn topologically_ordered_hashes(&self, genesis: Hash, limit: Option<u64>) -> Result<Vec<Hash>, String> {
+        let limit = limit.map_or(usize::MAX, |limit| limit as usize);
+        let mut remaining_parents = BlockHashMap::<usize>::new();
+        let mut queue: VecDeque<Hash> = std::iter::once(genesis).collect();
+        let mut hashes = Vec::new();
+        while let Some(current) = queue.pop_front() {
+            if current != genesis {
+                hashes.push(current);
+                if hashes.len() == limit {
+                    break;
+                }
+            }
+            let children = self.relations_store.get_children(current).map_err(|err| match current == genesis {
+                true => {
+                    format!("the source holds no relations of genesis {genesis}, replaying requires the blocks of an archival node")
+                }
+                false => format!("missing children of block {current}: {err}"),
+            })?;
+            for &child in children.read().iter() {
+                let remaining = match remaining_parents.get_mut(&child) {
+                    Some(remaining) => remaining,
+                    None => {
+                        let header =
+                            self.headers_store.get_header(child).map_err(|err| format!("missing header of block {child}: {err}"))?;
+                        remaining_parents.entry(child).or_insert(header.direct_parents().len())
+                    }
+                };
+                *remaining -= 1;
+                if *remaining == 0 {
+                    queue.push_back(child);
+                }
+            }
+        }
+        Ok(hashes)
+    }
+}
+
+/// Throughput of a processing stage, measured up to the last time its counter moved
+struct StageThroughput {
+    name: &'static str,
+    unit: &'static str,
+    count: u64,
+    elapsed: Duration,
+}
+
+impl StageThroughput {
+    fn new(name: &'static str, unit: &'static str) -> Self {
+        Self { name, unit, count: 0, elapsed: Duration::ZERO }
+    }
+
+    fn update(&mut self, count: u64, elapsed: Duration) {
+        if count != self.count {
+            self.count = count;
+            self.elapsed = elapsed;
+        }
+    }
+
+    fn rate(&self) -> f64 {
+        if self.elapsed.is_zero() {
+            0.0
+        } else {
+            self.count as f64 / self.elapsed.as_secs_f64()
+        }
+    }
+}
+
+/// Tracks the processing counters of the consensus the blocks are replayed into
+struct ThroughputReport {
+    start: Instant,
+    last_report: Instant,
+    start_snapshot: ProcessingCountersSnapshot,
+    headers: StageThroughput,
+    bodies: StageThroughput,
+    transactions: StageThroughput,
+    chain_blocks: StageThroughput,
+}
+
+impl ThroughputReport {
+    fn new(snapshot: ProcessingCountersSnapshot) -> Self {
+        let now = Instant::now();
+        Self {
+            start: now,
+            last_report: now,
+            start_snapshot: snapshot,
+            headers: StageThroughput::new("header processor", "headers"),
+            bodies: StageThroughput::new("body processor", "blocks"),
+            transactions: StageThroughput::new("body processor", "transactions"),
+            chain_blocks: StageThroughput::new("virtual processor", "chain blocks"),
+        }
+    }
+
+    fn update(&mut self, snapshot: &ProcessingCountersSnapshot) {
+        let elapsed = self.start.elapsed();
+        let delta = snapshot - &self.start_snapshot;
+        self.headers.update(delta.header_counts, elapsed);
+        self.bodies.update(delta.body_counts, elapsed);
+        self.transactions.update(delta.txs_counts, elapsed);
+        self.chain_blocks.update(delta.chain_block_counts, elapsed);
+    }
+
+    fn stages(&self) -> [&StageThroughput; 4] {
+        [&self.headers, &self.bodies, &self.transactions, &self.chain_blocks]
+    }
+
+    /// Logs the throughput of the stages since the previous call
+    fn log_interval(&mut self, snapshot: &ProcessingCountersSnapshot, previous: &ProcessingCountersSnapshot) {
+        let interval = self.last_report.elapsed().as_secs_f64();
+        let delta = snapshot - previous;
+        info!(
+            "Replay throughput over the last {:.2}s: header processor {:.2} headers/s, body processor {:.2} blocks/s ({:.2} tx/s), virtual processor {:.2} chain blocks/s",
+            interval,
+            delta.header_counts as f64 / interval,
+            delta.body_counts as f64 / interval,
+            delta.txs_counts as f64 / interval,
+            delta.chain_block_counts as f64 / interval,
+        );
+        self.last_report = Instant::now();
+    }
+
+    fn log_summary(&self, num_blocks: usize, header_only: bool) {
+        info!("Replayed {} {} in {:?}", num_blocks, if header_only { "headers" } else { "blocks" }, self.start.elapsed());
+        for stage in self.stages().into_iter().filter(|stage| stage.count > 0) {
+            info!("  {}: {} {} in {:?} ({:.2} {}/s)", stage.name, stage.count, stage.unit, stage.elapsed, stage.rate(), stage.unit);
+        }
+    }
+}
+
+/// Replays the blocks of `source` into a fresh consensus built out of `config` and reports the throughput of
+/// each processing stage. With `headers_first`, all the headers are validated before the block bodies.
+/// Returns the sink of the fresh consensus.
+pub async fn replay(source: &ReplaySource, config: Arc<Config>, limit: Option<u64>, headers_first: bool) -> Result<Hash, String> {
+    let genesis = config.genesis.hash;
+    let hashes = source.topologically_ordered_hashes(genesis, limit)?;
+    if hashes.is_empty() {
+        return Err(format!("the source holds no block on top of genesis {genesis}"));
+    }
+
+    let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(128));
+    let (dummy_notification_sender, _) = unbounded();
+    let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
+    let consensus = Arc::new(Consensus::new(
+        db,
+        config,
+        Default::default(),
+        notification_root,
+        Default::default(),
+        Default::default(),
+        unix_now(),
+    ));
+    let handles = consensus.run_processors();
+
+    let passes: &[bool] = if headers_first { &[true, false] } else { &[false] };
+    let mut result = Ok(());
+    for &header_only in passes {
+        info!("Replaying {} {}...", hashes.len(), if header_only { "headers" } else { "blocks" });
+        result = replay_pass(source, &consensus, &hashes, header_only).await;
+        if result.is_err() {
+            break;
+        }
+    }
+    let sink = consensus.get_sink();
+    consensus.shutdown(handles);
+    result.map(|_| sink)
+}
+
+async fn replay_pass(source: &ReplaySource, consensus: &Consensus, hashes: &[Hash], header_only: bool) -> Result<(), String> {
+    let counters = consensus.processing_counters().clone();
+    let mut report = ThroughputReport::new(counters.snapshot());
+    let mut previous_snapshot = counters.snapshot();
+    let mut invalid = 0usize;
+
+    let mut pending = Vec::new();
+    for chunk in hashes.chunks(CHUNK_SIZE) {
+        let current = submit_chunk(source, consensus, chunk, header_only)?;
+        invalid += await_chunk(std::mem::replace(&mut pending, current), header_only).await;
+
+        let snapshot = counters.snapshot();
+        report.update(&snapshot);
+        if report.last_report.elapsed() >= REPORT_INTERVAL {
+            report.log_interval(&snapshot, &previous_snapshot);
+            previous_snapshot = snapshot;
+        }
+    }
+    invalid += await_chunk(pending, header_only).await;
+    report.update(&counters.snapshot());
+
+    report.log_summary(hashes.len(), header_only);
+    if invalid > 0 {
+        warn!("{} of the replayed blocks were not accepted as valid", invalid);
+    }
+    Ok(())
+}
+
+fn submit_chunk(
+    source: &ReplaySource,
+    consensus: &Consensus,
+    chunk: &[Hash],
+    header_only: bool,
+) -> Result<Vec<impl Future<Output = BlockProcessResult<BlockStatus>>>, String> {
+    chunk.iter().map(|&hash| Ok(consensus.validate_and_insert_block(source.block(hash, header_only)?).virtual_state_task)).collect()
+}
+
+/// Awaits the processing of a chunk and returns the number of blocks not accepted as valid
+async fn await_chunk(futures: Vec<impl Future<Output = BlockProcessResult<BlockStatus>>>, header_only: bool) -> usize {
+    join_all(futures)
+        .await
+        .into_iter()
+        .filter(|result| match result {
+            Ok(status) if header_only => !status.is_header_only(),
+            Ok(status) => !status.is_utxo_valid_or_pending(),
+            Err(err) => {
+                trace!("Replayed block rejected: {}", err);
+                true
+            }
+        })
+        .count()
+}
@@ -0,0 +1,33 @@
+use kaspa_consensus::{config::ConfigBuilder, consensus::test_consensus::TestConsensus, params::MAINNET_PARAMS};
+use kaspa_consensus_core::{api::ConsensusApi, block::MutableBlock, coinbase::MinerData, tx::ScriptPublicKey};
+use kaspa_hashes::Hash;
+
+/// Returns a config builder with shallow depths, as used by simpa when testing pruning, so that the pruning point
+/// moves in a few thousand blocks
+pub fn pruning_config_builder() -> ConfigBuilder {
+    ConfigBuilder::new(MAINNET_PARAMS).skip_proof_of_work().edit_consensus_params(|p| {
+        p.min_difficulty_window_len = p.legacy_difficulty_window_size;
+        p.pruning_proof_m = 16;
+        p.legacy_difficulty_window_size = 64;
+        p.legacy_timestamp_deviation_tolerance = 16;
+        p.new_timestamp_deviation_tolerance = 16;
+        p.sampled_difficulty_window_size = p.sampled_difficulty_window_size.min(32);
+        p.finality_depth = 128;
+        p.merge_depth = 128;
+        p.mergeset_size_limit = 32;
+        p.pruning_depth = p.anticone_finalization_depth();
+    })
+}
+
+/// Adds a chain block on top of `parent`, one target block time after it in order to keep the difficulty steady.
+/// Headers are hashed for real so that they can be exported and imported
+pub async fn add_chain_block(consensus: &TestConsensus, parent: Hash) -> Hash {
+    let miner_data = MinerData::new(ScriptPublicKey::from_vec(0, vec![]), vec![]);
+    let MutableBlock { mut header, transactions } =
+        consensus.build_utxo_valid_block_with_parents(Hash::default(), vec![parent], miner_data, vec![]);
+    header.timestamp = consensus.get_header(parent).unwrap().timestamp + consensus.params().target_time_per_block;
+    header.finalize();
+    let hash = header.hash;
+    consensus.validate_and_insert_block(MutableBlock::new(header, transactions).to_immutable()).virtual_state_task.await.unwrap();
+    hash
+}
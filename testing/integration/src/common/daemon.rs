@@ -47,6 +47,7 @@ impl ClientManager {
             None,
             false,
             Some(500_000),
+            None,
             Default::default(),
         )
         .await
@@ -70,6 +71,7 @@ impl ClientManager {
             None,
             false,
             Some(500_000),
+            None,
             Default::default(),
         )
         .await
@@ -7,6 +7,7 @@ use std::{
 pub mod args;
 pub mod client;
 pub mod client_notify;
+pub mod consensus;
 pub mod daemon;
 pub mod listener;
 pub mod utils;
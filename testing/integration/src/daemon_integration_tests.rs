@@ -4,6 +4,7 @@ use crate::common::{
     daemon::Daemon,
     utils::{fetch_spendable_utxos, generate_tx, mine_block, wait_for},
 };
+use futures::{SinkExt, StreamExt};
 use kaspa_addresses::Address;
 use kaspa_alloc::init_allocator_with_default_settings;
 use kaspa_consensus::params::SIMNET_PARAMS;
@@ -11,11 +12,23 @@ use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::{task::runtime::AsyncRuntime, trace};
 use kaspa_grpc_client::GrpcClient;
 use kaspa_notify::scope::{BlockAddedScope, UtxosChangedScope, VirtualDaaScoreChangedScope};
-use kaspa_rpc_core::{api::rpc::RpcApi, Notification, RpcTransactionId};
+use kaspa_p2p_lib::encryption::EncryptionKeypair;
+use kaspa_rpc_core::{api::rpc::RpcApi, notify::mode::NotificationMode, Notification, RpcTransactionId};
 use kaspa_txscript::pay_to_address_script;
+use kaspa_wrpc_server::service::WrpcEncoding;
 use kaspad_lib::args::Args;
 use rand::thread_rng;
-use std::{sync::Arc, time::Duration};
+use serde_json::json;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+};
+use tokio_rustls::{
+    rustls::{Certificate, ClientConfig, RootCertStore, ServerName},
+    TlsConnector,
+};
+use tokio_tungstenite::{tungstenite::Message, WebSocketStream};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_sanity_test() {
@@ -113,6 +126,158 @@ async fn daemon_mining_test() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_encrypted_p2p_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        p2p_encryption: true,
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let mut kaspad1 = Daemon::new_random_with_args(args.clone(), total_fd_limit);
+    let rpc_client1 = kaspad1.start().await;
+
+    // The static key of daemon #1 is generated in its appdir on startup
+    let appdir = PathBuf::from(kaspad1.args.read().appdir.clone().unwrap());
+    let key = EncryptionKeypair::load_or_generate(&appdir.join(kaspad1.network.to_prefixed()).join("p2p.key")).unwrap().public_key();
+    let wrong_key = EncryptionKeypair::generate().public_key();
+
+    // Daemon #2 pins the key of daemon #1 while daemon #3 pins a wrong key
+    let pinned_args = |key| Args { pinned_peer_keys: vec![format!("{key}@127.0.0.1:{}", kaspad1.p2p_port)], ..args.clone() };
+    let mut kaspad2 = Daemon::new_random_with_args(pinned_args(key), total_fd_limit);
+    let mut kaspad3 = Daemon::new_random_with_args(pinned_args(wrong_key), total_fd_limit);
+    let rpc_client2 = kaspad2.start().await;
+    let rpc_client3 = kaspad3.start().await;
+
+    rpc_client2.add_peer(format!("127.0.0.1:{}", kaspad1.p2p_port).try_into().unwrap(), true).await.unwrap();
+    rpc_client3.add_peer(format!("127.0.0.1:{}", kaspad1.p2p_port).try_into().unwrap(), true).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await; // Let them connect
+    assert_eq!(rpc_client2.get_connected_peer_info().await.unwrap().peer_info.len(), 1);
+    assert_eq!(rpc_client3.get_connected_peer_info().await.unwrap().peer_info.len(), 0);
+
+    // Mine 10 blocks to daemon #1 and expect them to be relayed over the encrypted transport
+    let pay_address = Address::new(kaspad1.network.into(), kaspa_addresses::Version::PubKey, &[0; 32]);
+    for _ in 0..10 {
+        let template = rpc_client1.get_block_template(pay_address.clone(), vec![]).await.unwrap();
+        rpc_client1.submit_block(template.block, false).await.unwrap();
+    }
+    let check_client = rpc_client2.clone();
+    wait_for(
+        50,
+        40,
+        move || {
+            async fn blocks_relayed(client: GrpcClient) -> bool {
+                client.get_block_dag_info().await.unwrap().block_count == 10
+            }
+            Box::pin(blocks_relayed(check_client.clone()))
+        },
+        "the blocks were not relayed over the encrypted transport",
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_grpc_api_key_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let rpc_api_key_file = Some("testdata/rpc_api_keys".to_string());
+    let args = Args { devnet: true, disable_upnp: true, rpc_api_key_file, ..Default::default() };
+    let total_fd_limit = 10;
+    let mut kaspad = Daemon::new_random_with_args(args, total_fd_limit);
+    kaspad.run();
+    // Wait for the node to initialize before connecting to RPC
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    async fn connect(port: u16, api_key: Option<&str>) -> GrpcClient {
+        let url = format!("grpc://127.0.0.1:{port}");
+        let api_key = api_key.map(str::to_string);
+        GrpcClient::connect_with_args(NotificationMode::Direct, url, None, false, None, false, None, api_key, Default::default())
+            .await
+            .unwrap()
+    }
+
+    // Clients without a key or with an unknown key are rejected
+    for api_key in [None, Some("wrong")] {
+        let rpc_client = connect(kaspad.rpc_port, api_key).await;
+        assert!(rpc_client.get_info().await.is_err(), "the request of a client presenting {api_key:?} should be rejected");
+        rpc_client.disconnect().await.unwrap();
+    }
+
+    // A client sending the bearer token is granted the read methods only
+    let rpc_client = connect(kaspad.rpc_port, Some("secret")).await;
+    assert!(rpc_client.get_info().await.is_ok());
+    assert!(rpc_client.get_connected_peer_info().await.is_err(), "the read grant should not include admin methods");
+    rpc_client.disconnect().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_wrpc_tls_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let testdata = PathBuf::from("testdata/tls");
+    let args = Args {
+        devnet: true,
+        disable_upnp: true,
+        rpc_tls_cert: Some(testdata.join("rpc.crt").to_str().unwrap().to_string()),
+        rpc_tls_key: Some(testdata.join("rpc.key").to_str().unwrap().to_string()),
+        rpc_api_key_file: Some("testdata/rpc_api_keys".to_string()),
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+    // The default gRPC client of the daemon does not speak TLS so the daemon is only run
+    let mut kaspad = Daemon::new_random_with_args(args, total_fd_limit);
+    let json_address = kaspad.args.read().rpclisten_json.clone().unwrap();
+    let port = json_address.to_address(&kaspad.network.network_type(), &WrpcEncoding::SerdeJson).normalize(0).port;
+    kaspad.run();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    // Plaintext WebSocket connections are refused
+    assert!(tokio_tungstenite::connect_async(format!("ws://127.0.0.1:{port}")).await.is_err());
+
+    // Complete the TLS handshake trusting the self-signed certificate of the daemon
+    let mut roots = RootCertStore::empty();
+    let cert = std::fs::read(testdata.join("rpc.crt")).unwrap();
+    for der in rustls_pemfile::certs(&mut cert.as_slice()).unwrap() {
+        roots.add(&Certificate(der)).unwrap();
+    }
+    let config = ClientConfig::builder().with_safe_defaults().with_root_certificates(roots).with_no_client_auth();
+    let stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+    let server_name = ServerName::try_from("127.0.0.1").unwrap();
+    let stream = TlsConnector::from(Arc::new(config)).connect(server_name, stream).await.unwrap();
+    let (mut ws_stream, _) = tokio_tungstenite::client_async(format!("wss://127.0.0.1:{port}"), stream).await.unwrap();
+
+    async fn call<S>(ws_stream: &mut WebSocketStream<S>, method: &str, params: serde_json::Value) -> serde_json::Value
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let request = json!({ "id": 1, "method": method, "params": params }).to_string();
+        ws_stream.send(Message::Text(request)).await.unwrap();
+        loop {
+            if let Message::Text(response) = ws_stream.next().await.unwrap().unwrap() {
+                return serde_json::from_str(&response).unwrap();
+            }
+        }
+    }
+
+    // Requests are rejected until the connection authenticates with a known API key
+    assert!(call(&mut ws_stream, "getInfo", json!({})).await.get("error").is_some());
+    assert!(call(&mut ws_stream, "authenticate", json!({ "apiKey": "wrong" })).await.get("error").is_none());
+    assert!(call(&mut ws_stream, "getInfo", json!({})).await.get("error").is_some());
+    assert!(call(&mut ws_stream, "authenticate", json!({ "apiKey": "secret" })).await.get("error").is_none());
+    let response = call(&mut ws_stream, "getInfo", json!({})).await;
+    assert!(response.get("error").is_none(), "getInfo failed: {response}");
+    assert!(response["params"]["serverVersion"].is_string());
+}
+
 /// `cargo test --release --package kaspa-testing-integration --lib -- daemon_integration_tests::daemon_utxos_propagation_test`
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_utxos_propagation_test() {
@@ -23,5 +23,11 @@ pub mod mempool_benchmarks;
 #[cfg(feature = "devnet-prealloc")]
 pub mod subscribe_benchmarks;
 
+#[cfg(test)]
+pub mod pruning_tests;
+
 #[cfg(test)]
 pub mod rpc_tests;
+
+#[cfg(test)]
+pub mod snapshot_tests;
@@ -0,0 +1,89 @@
+//!
+//! Integration tests of block pruning
+//!
+
+use crate::common::{
+    consensus::{add_chain_block, pruning_config_builder},
+    utils::wait_for,
+};
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_consensus::consensus::test_consensus::TestConsensus;
+use kaspa_consensus_core::api::ConsensusApi;
+use kaspa_consensusmanager::ConsensusManager;
+use kaspa_rpc_service::converter::consensus::ConsensusConverter;
+use std::sync::Arc;
+
+/// Blocks within the retention period below the pruning point keep their bodies and acceptance data, which are
+/// served by the RPC `getBlock` and `getBlocks` methods and by the P2P IBD block requests
+#[tokio::test]
+async fn retention_period_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("info");
+
+    const RETENTION_PERIOD_BLOCKS: f64 = 64.0;
+    let config = pruning_config_builder().build();
+    let retention_period_days = RETENTION_PERIOD_BLOCKS * config.target_time_per_block as f64 / 86_400_000.0;
+    let config = config.to_builder().set_retention_period_days(retention_period_days).build();
+    let retention_period = config.retention_period_blue_score().unwrap() as usize;
+
+    let consensus = TestConsensus::new(&config);
+    let wait_handles = consensus.init();
+
+    // Chain blocks are indexed by their blue score
+    let mut chain = vec![config.genesis.hash];
+    for _ in 0..3000 {
+        chain.push(add_chain_block(&consensus, *chain.last().unwrap()).await);
+    }
+    let pruning_point = consensus.pruning_point();
+    let pruning_point_blue_score = consensus.get_header(pruning_point).unwrap().blue_score as usize;
+    assert_eq!(chain[pruning_point_blue_score], pruning_point);
+    let retention_blue_score = pruning_point_blue_score.checked_sub(retention_period).unwrap();
+    assert!(retention_blue_score > 1, "the retention period should not reach genesis");
+
+    // Pruning runs in the background, wait for it to go past the retention boundary
+    let consensus_ref = &consensus;
+    let last_pruned = chain[retention_blue_score - 1];
+    wait_for(
+        100,
+        600,
+        move || async move { consensus_ref.get_block(last_pruned).is_err() },
+        "the blocks below the retention period were not pruned",
+    )
+    .await;
+
+    let consensus_manager = Arc::new(ConsensusManager::from_consensus(consensus.consensus_clone()));
+    let converter = ConsensusConverter::new(consensus_manager.clone(), Arc::new(config.clone()));
+    let session = consensus_manager.consensus().session().await;
+
+    assert!(session.async_get_block(chain[1]).await.is_err());
+    for &hash in chain[retention_blue_score..pruning_point_blue_score].iter() {
+        // P2P IBD block requests
+        let block = session.async_get_block(hash).await.unwrap();
+        assert!(!block.transactions.is_empty());
+
+        // RPC getBlock
+        let block = session.async_get_block_even_if_header_only(hash).await.unwrap();
+        let rpc_block = converter.get_block(&session, &block, true, true).await.unwrap();
+        assert!(!rpc_block.verbose_data.unwrap().is_header_only);
+        assert_eq!(rpc_block.transactions.len(), block.transactions.len());
+
+        assert!(!session.async_get_block_acceptance_data(hash).await.unwrap().is_empty());
+    }
+
+    // RPC getBlocks with the oldest retained block as low hash
+    let low_hash = chain[retention_blue_score];
+    session.async_get_ghostdag_data(low_hash).await.unwrap();
+    let max_blocks = config.mergeset_size_limit as usize + 1;
+    let (block_hashes, _) = session.async_get_hashes_between(low_hash, session.async_get_sink().await, max_blocks).await.unwrap();
+    assert!(!block_hashes.is_empty());
+    assert_eq!(block_hashes, chain[retention_blue_score + 1..retention_blue_score + 1 + block_hashes.len()]);
+    for hash in block_hashes {
+        let block = session.async_get_block_even_if_header_only(hash).await.unwrap();
+        let rpc_block = converter.get_block(&session, &block, true, true).await.unwrap();
+        assert!(!rpc_block.verbose_data.unwrap().is_header_only);
+        assert!(!rpc_block.transactions.is_empty());
+    }
+
+    drop(session);
+    consensus.shutdown(wait_handles);
+}
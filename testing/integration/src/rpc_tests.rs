@@ -4,15 +4,15 @@ use crate::common::{client_notify::ChannelNotify, daemon::Daemon};
 use futures_util::future::try_join_all;
 use kaspa_addresses::{Address, Prefix, Version};
 use kaspa_consensus::params::SIMNET_GENESIS;
-use kaspa_consensus_core::{constants::MAX_SOMPI, subnets::SubnetworkId, tx::Transaction};
+use kaspa_consensus_core::{constants::MAX_SOMPI, hashing, subnets::SubnetworkId, tx::Transaction};
 use kaspa_core::info;
 use kaspa_grpc_core::ops::KaspadPayloadOps;
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        AddressHistoryChangedScope, BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope,
+        Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
 use kaspa_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -49,6 +49,8 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        txindex: true,
+        addresshistory: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -273,16 +275,25 @@ async fn sanity_test() {
                 tst!(op, {
                     let peer_address = ContextualNetAddress::from_str("5.6.7.8").unwrap();
                     let ip = peer_address.normalize(1).ip;
+                    let subnet = RpcIpSubnet::from_str("5.6.0.0/16").unwrap();
 
                     let _ = rpc_client.add_peer_call(AddPeerRequest { peer_address, is_permanent: false }).await.unwrap();
-                    let _ = rpc_client.ban_call(BanRequest { ip }).await.unwrap();
+                    rpc_client.ban(ip.into(), None, Some("test".to_string())).await.unwrap();
+                    rpc_client.ban(subnet, Some(0), None).await.unwrap();
 
                     let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
                     assert!(response.banned_addresses.contains(&ip));
+                    let bans = rpc_client.list_bans().await.unwrap();
+                    assert_eq!(bans.len(), 2);
+                    assert!(bans.iter().any(|ban| ban.subnet == ip.into() && ban.expires_at.is_some() && ban.reason == "test"));
+                    assert!(bans.iter().any(|ban| ban.subnet == subnet && ban.expires_at.is_none()));
 
-                    let _ = rpc_client.unban_call(UnbanRequest { ip }).await.unwrap();
+                    let _ = rpc_client.unban_call(UnbanRequest { subnet: ip.into() }).await.unwrap();
                     let response = rpc_client.get_peer_addresses_call(GetPeerAddressesRequest {}).await.unwrap();
                     assert!(!response.banned_addresses.contains(&ip));
+                    assert!(rpc_client.unban(ip.into()).await.is_err());
+                    rpc_client.unban(subnet).await.unwrap();
+                    assert!(rpc_client.list_bans().await.unwrap().is_empty());
                 })
             }
 
@@ -290,6 +301,24 @@ async fn sanity_test() {
                 tst!(op, "see Ban")
             }
 
+            KaspadPayloadOps::ListBans => {
+                tst!(op, "see Ban")
+            }
+
+            KaspadPayloadOps::SaveMempool => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let transaction_count = rpc_client.save_mempool().await.unwrap();
+                    let response = rpc_client.load_mempool().await.unwrap();
+                    assert_eq!(response.transaction_count, transaction_count);
+                    assert_eq!(response.accepted_count, transaction_count);
+                })
+            }
+
+            KaspadPayloadOps::LoadMempool => {
+                tst!(op, "see SaveMempool")
+            }
+
             KaspadPayloadOps::SubmitTransaction => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -301,6 +330,17 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::SubmitTransactionReplacement => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Build an erroneous transaction...
+                    let transaction = Transaction::new(0, vec![], vec![], 0, SubnetworkId::default(), 0, vec![]);
+                    let result = rpc_client.submit_transaction_replacement((&transaction).into()).await;
+                    // ...that gets rejected by the mempool since it does not replace any transaction
+                    assert!(result.is_err());
+                })
+            }
+
             KaspadPayloadOps::GetSubnetwork => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -546,6 +586,84 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Err because the transaction is unknown to the txindex
+                    let result = rpc_client.get_transaction_call(GetTransactionRequest::new(0.into(), true)).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionsByIds => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Unknown transactions are omitted from the response
+                    let result =
+                        rpc_client.get_transactions_by_ids_call(GetTransactionsByIdsRequest::new(vec![0.into()], true)).await.unwrap();
+                    assert!(result.transactions.is_empty());
+
+                    // Requests exceeding the maximum number of ids are rejected
+                    let transaction_ids = (0..1001u64).map(|i| i.into()).collect();
+                    let result =
+                        rpc_client.get_transactions_by_ids_call(GetTransactionsByIdsRequest::new(transaction_ids, false)).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetAddressTransactions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let result = rpc_client
+                        .get_address_transactions_call(GetAddressTransactionsRequest::new(address, 0, None, 100))
+                        .await
+                        .unwrap();
+                    assert!(result.entries.is_empty());
+                })
+            }
+
+            KaspadPayloadOps::GetAddressTransactionCount => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let result =
+                        rpc_client.get_address_transaction_count_call(GetAddressTransactionCountRequest::new(address)).await.unwrap();
+                    assert_eq!(result.count, 0);
+                })
+            }
+
+            KaspadPayloadOps::GetTransactionMerkleProof => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Err because the transaction is unknown to the txindex
+                    let result =
+                        rpc_client.get_transaction_merkle_proof_call(GetTransactionMerkleProofRequest::new(0.into(), None)).await;
+                    assert!(result.is_err());
+
+                    // Genesis is a chain block, so its coinbase is linked by the genesis header alone
+                    let transaction = &SIMNET_GENESIS.build_genesis_transactions()[0];
+                    let proof = rpc_client.get_transaction_merkle_proof(transaction.id(), Some(SIMNET_GENESIS.hash)).await.unwrap();
+                    assert_eq!(proof.block_hash, SIMNET_GENESIS.hash);
+                    assert_eq!(proof.transaction_hash, hashing::tx::hash(transaction, false));
+                    assert_eq!(proof.index, 0);
+                    assert!(proof.merkle_path.is_empty());
+                    assert_eq!(proof.headers.iter().map(|x| x.hash).collect::<Vec<_>>(), vec![SIMNET_GENESIS.hash]);
+                    // Note: the simnet genesis header does not commit to its coinbase, so the proof itself cannot verify
+                    assert_eq!(proof.verify(), None);
+                })
+            }
+
+            KaspadPayloadOps::GetFeeEstimate => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let result = rpc_client.get_fee_estimate_call(GetFeeEstimateRequest::new(true)).await.unwrap();
+                    assert!(result.estimate.priority_bucket.feerate >= 1.0);
+                    assert!(result.estimate.normal_buckets.iter().all(|b| b.feerate <= result.estimate.priority_bucket.feerate));
+                    assert!(result.verbose.is_some());
+                })
+            }
+
             KaspadPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -607,6 +725,13 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KaspadPayloadOps::NotifyAddressHistoryChanged => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, AddressHistoryChangedScope {}.into()).await.unwrap();
+                })
+            }
             KaspadPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
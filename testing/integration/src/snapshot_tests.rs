@@ -0,0 +1,82 @@
+//!
+//! Integration tests of the offline consensus snapshots
+//!
+
+use crate::common::consensus::{add_chain_block, pruning_config_builder};
+use futures::executor::block_on;
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_consensus::consensus::test_consensus::TestConsensus;
+use kaspa_consensus_core::{api::ConsensusApi, errors::pruning::PruningImportError, muhash::MuHashExtensions};
+use kaspa_database::utils::get_kaspa_tempdir;
+use kaspa_muhash::MuHash;
+use kaspad_lib::snapshot::{export_snapshot, import_snapshot_into, SnapshotError};
+use std::path::Path;
+
+/// Like the daemon, the test runs outside of an async runtime since importing a snapshot blocks on consensus processing
+#[test]
+fn snapshot_round_trip_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("info");
+
+    let config = pruning_config_builder().build();
+    let source = TestConsensus::new(&config);
+    let source_handles = source.init();
+    let mut tip = config.genesis.hash;
+    for _ in 0..3000 {
+        tip = block_on(add_chain_block(&source, tip));
+    }
+    let pruning_point = source.pruning_point();
+    assert_ne!(pruning_point, config.genesis.hash, "the pruning point should have moved");
+
+    let tempdir = get_kaspa_tempdir();
+    let path = tempdir.path().join("snapshot.bin");
+    assert_eq!(export_snapshot(&**source, &config, &path).unwrap(), pruning_point);
+
+    // Like a node without a database, the snapshot is imported into an empty staging consensus while the current
+    // consensus holds genesis only
+    let current = TestConsensus::new(&config);
+    let staging_config = config.to_builder().skip_adding_genesis().build();
+    let import = |path: &Path| {
+        let staging = TestConsensus::new(&staging_config);
+        let handles = staging.init();
+        let result = import_snapshot_into(&**current, &**staging, &config, path);
+        staging.shutdown(handles);
+        (staging, result)
+    };
+
+    // A snapshot with a tampered UTXO entry does not match the UTXO commitment of the pruning point. The file ends with
+    // the last UTXO entry, whose DAA score and coinbase flag are followed by the empty chunk marking the end of the set,
+    // encoded as its 8 bytes length and the 8 bytes length of the empty vector
+    let mut tampered = std::fs::read(&path).unwrap();
+    let daa_score_offset = tampered.len() - 16 - 1 - 8;
+    tampered[daa_score_offset] ^= 1;
+    let tampered_path = tempdir.path().join("tampered.bin");
+    std::fs::write(&tampered_path, tampered).unwrap();
+    assert!(matches!(
+        import(&tampered_path).1,
+        Err(SnapshotError::PruningImportError(PruningImportError::ImportedMultisetHashMismatch(..)))
+    ));
+
+    // A length prefix beyond the record size limit is rejected before allocating
+    let mut oversized = std::fs::read(&path).unwrap()[..8].to_vec();
+    oversized.extend(u64::MAX.to_le_bytes());
+    let oversized_path = tempdir.path().join("oversized.bin");
+    std::fs::write(&oversized_path, oversized).unwrap();
+    assert!(matches!(import(&oversized_path).1, Err(SnapshotError::RecordTooLarge(u64::MAX, _))));
+
+    let (staging, result) = import(&path);
+    assert_eq!(result.unwrap(), pruning_point);
+    assert_eq!(staging.pruning_point(), pruning_point);
+    assert_eq!(staging.get_headers_selected_tip(), source.get_headers_selected_tip());
+
+    // The imported UTXO set is the one of the source and matches the UTXO commitment of the pruning point
+    let utxos = |consensus: &TestConsensus| consensus.get_pruning_point_utxos(pruning_point, None, usize::MAX, false).unwrap();
+    let imported_utxos = utxos(&staging);
+    assert!(!imported_utxos.is_empty());
+    assert_eq!(imported_utxos, utxos(&source));
+    let mut multiset = MuHash::new();
+    imported_utxos.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+    assert_eq!(multiset.finalize(), staging.get_header(pruning_point).unwrap().utxo_commitment);
+
+    source.shutdown(source_handles);
+}
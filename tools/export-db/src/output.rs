@@ -0,0 +1,223 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+
+#[cfg(feature = "parquet")]
+use crate::parquet_writer::ParquetWriter;
+
+pub type ExportResult<T> = Result<T, Box<dyn Error>>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One JSON object per line
+    Jsonl,
+    /// Comma separated values with a header row
+    Csv,
+    /// Apache Parquet, written in row groups of 64K rows (requires the `parquet` feature)
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Csv => "csv",
+            #[cfg(feature = "parquet")]
+            OutputFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    U64,
+    Bool,
+    String,
+    /// Nested data, kept as is in JSON Lines and written as a JSON string in the flat formats
+    Json,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Column {
+    pub name: &'static str,
+    /// Only read by the typed Parquet schema
+    #[cfg_attr(not(feature = "parquet"), allow(dead_code))]
+    pub column_type: ColumnType,
+}
+
+impl Column {
+    pub const fn new(name: &'static str, column_type: ColumnType) -> Self {
+        Self { name, column_type }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    U64(u64),
+    Bool(bool),
+    String(String),
+    Json(serde_json::Value),
+}
+
+impl Value {
+    fn into_json(self) -> serde_json::Value {
+        match self {
+            Value::U64(v) => v.into(),
+            Value::Bool(v) => v.into(),
+            Value::String(v) => v.into(),
+            Value::Json(v) => v,
+        }
+    }
+
+    pub(crate) fn into_text(self) -> String {
+        match self {
+            Value::U64(v) => v.to_string(),
+            Value::Bool(v) => v.to_string(),
+            Value::String(v) | Value::Json(serde_json::Value::String(v)) => v,
+            Value::Json(serde_json::Value::Null) => String::new(),
+            Value::Json(v) => v.to_string(),
+        }
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        Value::U64(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        Value::Json(value)
+    }
+}
+
+/// A sink receiving the rows of a single store, one at a time
+pub trait RecordWriter {
+    fn write(&mut self, row: Vec<Value>) -> ExportResult<()>;
+
+    /// Flushes the remaining rows and finalizes the output
+    fn finish(self: Box<Self>) -> ExportResult<()>;
+}
+
+pub fn create_writer(format: OutputFormat, path: &Path, columns: &'static [Column]) -> ExportResult<Box<dyn RecordWriter>> {
+    let file = File::create(path)?;
+    Ok(match format {
+        OutputFormat::Jsonl => Box::new(JsonLinesWriter::new(BufWriter::new(file), columns)),
+        OutputFormat::Csv => Box::new(CsvWriter::new(file, columns)?),
+        #[cfg(feature = "parquet")]
+        OutputFormat::Parquet => Box::new(ParquetWriter::new(file, columns)?),
+    })
+}
+
+pub struct JsonLinesWriter<W: Write> {
+    writer: W,
+    columns: &'static [Column],
+}
+
+impl<W: Write> JsonLinesWriter<W> {
+    pub fn new(writer: W, columns: &'static [Column]) -> Self {
+        Self { writer, columns }
+    }
+}
+
+impl<W: Write> RecordWriter for JsonLinesWriter<W> {
+    fn write(&mut self, row: Vec<Value>) -> ExportResult<()> {
+        let object: serde_json::Map<String, serde_json::Value> =
+            self.columns.iter().zip(row).map(|(column, value)| (column.name.to_string(), value.into_json())).collect();
+        serde_json::to_writer(&mut self.writer, &object)?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> ExportResult<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+pub struct CsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(writer: W, columns: &'static [Column]) -> ExportResult<Self> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(columns.iter().map(|column| column.name))?;
+        Ok(Self { writer })
+    }
+}
+
+impl<W: Write> RecordWriter for CsvWriter<W> {
+    fn write(&mut self, row: Vec<Value>) -> ExportResult<()> {
+        Ok(self.writer.write_record(row.into_iter().map(Value::into_text))?)
+    }
+
+    fn finish(mut self: Box<Self>) -> ExportResult<()> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    pub(crate) const COLUMNS: &[Column] = &[
+        Column::new("hash", ColumnType::String),
+        Column::new("daa_score", ColumnType::U64),
+        Column::new("is_chain_block", ColumnType::Bool),
+        Column::new("parents", ColumnType::Json),
+    ];
+
+    pub(crate) fn rows() -> Vec<Vec<Value>> {
+        vec![
+            vec!["aa".to_string().into(), 1u64.into(), true.into(), json!(["bb", "cc"]).into()],
+            vec!["dd".to_string().into(), u64::MAX.into(), false.into(), json!([]).into()],
+        ]
+    }
+
+    #[test]
+    fn test_json_lines_writer() {
+        let mut output = Vec::new();
+        let mut writer = Box::new(JsonLinesWriter::new(&mut output, COLUMNS));
+        rows().into_iter().for_each(|row| writer.write(row).unwrap());
+        writer.finish().unwrap();
+        let lines: Vec<serde_json::Value> =
+            String::from_utf8(output).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                json!({"hash": "aa", "daa_score": 1, "is_chain_block": true, "parents": ["bb", "cc"]}),
+                json!({"hash": "dd", "daa_score": u64::MAX, "is_chain_block": false, "parents": []}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_writer() {
+        let mut output = Vec::new();
+        let mut writer = Box::new(CsvWriter::new(&mut output, COLUMNS).unwrap());
+        rows().into_iter().for_each(|row| writer.write(row).unwrap());
+        writer.finish().unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "hash,daa_score,is_chain_block,parents\naa,1,true,\"[\"\"bb\"\",\"\"cc\"\"]\"\ndd,18446744073709551615,false,[]\n"
+        );
+    }
+}
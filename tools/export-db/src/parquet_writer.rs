@@ -0,0 +1,136 @@
+//! Parquet output of the exported stores, only built with the `parquet` feature
+
+use std::io::Write;
+use std::sync::Arc;
+
+use parquet::basic::Type as PhysicalType;
+use parquet::data_type::{BoolType, ByteArray, ByteArrayType, Int64Type};
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
+
+use crate::output::{Column, ColumnType, ExportResult, RecordWriter, Value};
+
+/// Number of rows buffered in memory before a Parquet row group is flushed
+const PARQUET_ROW_GROUP_SIZE: usize = 64 * 1024;
+
+enum ColumnBuffer {
+    Int64(Vec<i64>),
+    Bool(Vec<bool>),
+    ByteArray(Vec<ByteArray>),
+}
+
+impl ColumnBuffer {
+    fn new(column_type: ColumnType) -> Self {
+        match column_type {
+            ColumnType::U64 => ColumnBuffer::Int64(Vec::new()),
+            ColumnType::Bool => ColumnBuffer::Bool(Vec::new()),
+            ColumnType::String | ColumnType::Json => ColumnBuffer::ByteArray(Vec::new()),
+        }
+    }
+
+    fn push(&mut self, value: Value) {
+        match (self, value) {
+            // Unsigned values are stored with the UINT_64 logical type, so the bit pattern is preserved
+            (ColumnBuffer::Int64(values), Value::U64(v)) => values.push(v as i64),
+            (ColumnBuffer::Bool(values), Value::Bool(v)) => values.push(v),
+            (ColumnBuffer::ByteArray(values), value) => values.push(ByteArray::from(value.into_text().into_bytes())),
+            (_, value) => panic!("value {value:?} does not match the column type"),
+        }
+    }
+}
+
+pub struct ParquetWriter<W: Write + Send> {
+    writer: SerializedFileWriter<W>,
+    buffers: Vec<ColumnBuffer>,
+    buffered_rows: usize,
+}
+
+impl<W: Write + Send> ParquetWriter<W> {
+    pub fn new(writer: W, columns: &'static [Column]) -> ExportResult<Self> {
+        let fields: Vec<String> = columns
+            .iter()
+            .map(|column| match column.column_type {
+                ColumnType::U64 => format!("REQUIRED {} {} (INTEGER(64,false));", PhysicalType::INT64, column.name),
+                ColumnType::Bool => format!("REQUIRED {} {};", PhysicalType::BOOLEAN, column.name),
+                ColumnType::String | ColumnType::Json => format!("REQUIRED {} {} (UTF8);", PhysicalType::BYTE_ARRAY, column.name),
+            })
+            .collect();
+        let schema = parse_message_type(&format!("message schema {{ {} }}", fields.join(" ")))?;
+        let writer = SerializedFileWriter::new(writer, Arc::new(schema), Arc::new(WriterProperties::builder().build()))?;
+        let buffers = columns.iter().map(|column| ColumnBuffer::new(column.column_type)).collect();
+        Ok(Self { writer, buffers, buffered_rows: 0 })
+    }
+
+    fn flush_row_group(&mut self) -> ExportResult<()> {
+        if self.buffered_rows == 0 {
+            return Ok(());
+        }
+        let mut row_group = self.writer.next_row_group()?;
+        for buffer in self.buffers.iter_mut() {
+            let mut column = row_group.next_column()?.expect("the schema has a column per buffer");
+            match buffer {
+                ColumnBuffer::Int64(values) => {
+                    column.typed::<Int64Type>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+                ColumnBuffer::Bool(values) => {
+                    column.typed::<BoolType>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+                ColumnBuffer::ByteArray(values) => {
+                    column.typed::<ByteArrayType>().write_batch(values, None, None)?;
+                    values.clear();
+                }
+            }
+            column.close()?;
+        }
+        row_group.close()?;
+        self.buffered_rows = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> RecordWriter for ParquetWriter<W> {
+    fn write(&mut self, row: Vec<Value>) -> ExportResult<()> {
+        for (buffer, value) in self.buffers.iter_mut().zip(row) {
+            buffer.push(value);
+        }
+        self.buffered_rows += 1;
+        if self.buffered_rows >= PARQUET_ROW_GROUP_SIZE {
+            self.flush_row_group()?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>) -> ExportResult<()> {
+        self.flush_row_group()?;
+        self.writer.close()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::tests::{rows, COLUMNS};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::Field;
+
+    #[test]
+    fn test_parquet_writer() {
+        let file = tempfile::tempfile().unwrap();
+        let mut writer = Box::new(ParquetWriter::new(file.try_clone().unwrap(), COLUMNS).unwrap());
+        rows().into_iter().for_each(|row| writer.write(row).unwrap());
+        writer.finish().unwrap();
+
+        let reader = SerializedFileReader::new(file).unwrap();
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 2);
+        let records: Vec<_> = reader.get_row_iter(None).unwrap().map(|row| row.unwrap()).collect();
+        let fields: Vec<_> = records[1].get_column_iter().map(|(_, field)| field.clone()).collect();
+        assert_eq!(
+            fields,
+            vec![Field::Str("dd".to_string()), Field::ULong(u64::MAX), Field::Bool(false), Field::Str("[]".to_string())]
+        );
+    }
+}
@@ -0,0 +1,371 @@
+use std::sync::Arc;
+
+use clap::ValueEnum;
+use indicatif::ProgressBar;
+use serde_json::json;
+
+use kaspa_addresses::Prefix;
+use kaspa_consensus::model::stores::ghostdag::GhostdagData;
+use kaspa_consensus::model::stores::headers::{CompactHeaderData, HeaderWithBlockLevel};
+use kaspa_consensus_core::acceptance_data::AcceptanceData;
+use kaspa_consensus_core::tx::ScriptPublicKey;
+use kaspa_consensus_core::BlockHasher;
+use kaspa_database::prelude::{CachePolicy, CachedDbAccess, StoreError, DB};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use kaspa_hashes::Hash;
+use kaspa_index_core::indexed_utxos::CompactUtxoEntry;
+use kaspa_txscript::extract_script_pub_key_address;
+
+use crate::output::{Column, ColumnType, ExportResult, RecordWriter, Value};
+use crate::types::{parse_utxoindex_key, AddressTransactionOutput, BlockBody};
+
+/// The stores which can be exported
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Store {
+    /// Block headers, one row per block
+    Headers,
+    /// GHOSTDAG data of the level 0 DAG, one row per block
+    Ghostdag,
+    /// Transactions accepted by chain blocks, one row per accepted transaction
+    AcceptanceData,
+    /// Block bodies, one row per transaction
+    BlockTransactions,
+    /// The UTXO set as indexed by the utxoindex, one row per UTXO
+    Utxoindex,
+}
+
+const HEADERS_COLUMNS: &[Column] = &[
+    Column::new("hash", ColumnType::String),
+    Column::new("block_level", ColumnType::U64),
+    Column::new("version", ColumnType::U64),
+    Column::new("parents_by_level", ColumnType::Json),
+    Column::new("hash_merkle_root", ColumnType::String),
+    Column::new("accepted_id_merkle_root", ColumnType::String),
+    Column::new("utxo_commitment", ColumnType::String),
+    Column::new("timestamp", ColumnType::U64),
+    Column::new("bits", ColumnType::U64),
+    Column::new("nonce", ColumnType::U64),
+    Column::new("daa_score", ColumnType::U64),
+    Column::new("blue_work", ColumnType::String),
+    Column::new("blue_score", ColumnType::U64),
+    Column::new("pruning_point", ColumnType::String),
+];
+
+const GHOSTDAG_COLUMNS: &[Column] = &[
+    Column::new("hash", ColumnType::String),
+    Column::new("blue_score", ColumnType::U64),
+    Column::new("blue_work", ColumnType::String),
+    Column::new("selected_parent", ColumnType::String),
+    Column::new("mergeset_blues", ColumnType::Json),
+    Column::new("mergeset_reds", ColumnType::Json),
+    Column::new("blues_anticone_sizes", ColumnType::Json),
+];
+
+const ACCEPTANCE_DATA_COLUMNS: &[Column] = &[
+    Column::new("chain_block_hash", ColumnType::String),
+    Column::new("mergeset_block_hash", ColumnType::String),
+    Column::new("transaction_id", ColumnType::String),
+    Column::new("index_within_block", ColumnType::U64),
+];
+
+const BLOCK_TRANSACTIONS_COLUMNS: &[Column] = &[
+    Column::new("block_hash", ColumnType::String),
+    Column::new("index_within_block", ColumnType::U64),
+    Column::new("transaction_id", ColumnType::String),
+    Column::new("version", ColumnType::U64),
+    Column::new("inputs", ColumnType::Json),
+    Column::new("outputs", ColumnType::Json),
+    Column::new("lock_time", ColumnType::U64),
+    Column::new("subnetwork_id", ColumnType::String),
+    Column::new("gas", ColumnType::U64),
+    Column::new("payload", ColumnType::String),
+    Column::new("mass", ColumnType::U64),
+];
+
+const UTXOINDEX_COLUMNS: &[Column] = &[
+    Column::new("transaction_id", ColumnType::String),
+    Column::new("index", ColumnType::U64),
+    Column::new("amount", ColumnType::U64),
+    Column::new("block_daa_score", ColumnType::U64),
+    Column::new("is_coinbase", ColumnType::Bool),
+    Column::new("script_public_key", ColumnType::Json),
+    Column::new("address", ColumnType::Json),
+];
+
+impl Store {
+    /// The base name of the output file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Store::Headers => "headers",
+            Store::Ghostdag => "ghostdag",
+            Store::AcceptanceData => "acceptance_data",
+            Store::BlockTransactions => "block_transactions",
+            Store::Utxoindex => "utxoindex",
+        }
+    }
+
+    pub fn columns(&self) -> &'static [Column] {
+        match self {
+            Store::Headers => HEADERS_COLUMNS,
+            Store::Ghostdag => GHOSTDAG_COLUMNS,
+            Store::AcceptanceData => ACCEPTANCE_DATA_COLUMNS,
+            Store::BlockTransactions => BLOCK_TRANSACTIONS_COLUMNS,
+            Store::Utxoindex => UTXOINDEX_COLUMNS,
+        }
+    }
+}
+
+/// Inclusive DAA score and blue score bounds restricting the exported blocks
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScoreRange {
+    pub min_daa_score: Option<u64>,
+    pub max_daa_score: Option<u64>,
+    pub min_blue_score: Option<u64>,
+    pub max_blue_score: Option<u64>,
+}
+
+impl ScoreRange {
+    pub fn is_bounded(&self) -> bool {
+        self.has_daa_score_bounds() || self.has_blue_score_bounds()
+    }
+
+    pub fn has_daa_score_bounds(&self) -> bool {
+        self.min_daa_score.is_some() || self.max_daa_score.is_some()
+    }
+
+    pub fn has_blue_score_bounds(&self) -> bool {
+        self.min_blue_score.is_some() || self.max_blue_score.is_some()
+    }
+
+    pub fn contains_daa_score(&self, daa_score: u64) -> bool {
+        self.min_daa_score.map_or(true, |min| daa_score >= min) && self.max_daa_score.map_or(true, |max| daa_score <= max)
+    }
+
+    pub fn contains_blue_score(&self, blue_score: u64) -> bool {
+        self.min_blue_score.map_or(true, |min| blue_score >= min) && self.max_blue_score.map_or(true, |max| blue_score <= max)
+    }
+
+    pub fn contains(&self, daa_score: u64, blue_score: u64) -> bool {
+        self.contains_daa_score(daa_score) && self.contains_blue_score(blue_score)
+    }
+}
+
+/// Streams the content of the consensus and utxoindex stores into a [`RecordWriter`]
+pub struct Exporter {
+    consensus_db: Arc<DB>,
+    utxoindex_db: Option<Arc<DB>>,
+    prefix: Prefix,
+    range: ScoreRange,
+    with_addresses: bool,
+    compact_headers: CachedDbAccess<Hash, CompactHeaderData, BlockHasher>,
+}
+
+impl Exporter {
+    pub fn new(consensus_db: Arc<DB>, utxoindex_db: Option<Arc<DB>>, prefix: Prefix, range: ScoreRange, with_addresses: bool) -> Self {
+        let compact_headers =
+            CachedDbAccess::new(consensus_db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::HeadersCompact.into());
+        Self { consensus_db, utxoindex_db, prefix, range, with_addresses, compact_headers }
+    }
+
+    /// Writes all the rows of `store` falling in the score range and returns their count
+    pub fn export(&self, store: Store, writer: &mut dyn RecordWriter, progress: &ProgressBar) -> ExportResult<u64> {
+        let mut rows = 0u64;
+        let mut write = |row: Vec<Value>| -> ExportResult<()> {
+            rows += 1;
+            writer.write(row)
+        };
+
+        match store {
+            Store::Headers => {
+                let access = CachedDbAccess::<Hash, HeaderWithBlockLevel, BlockHasher>::new(
+                    self.consensus_db.clone(),
+                    CachePolicy::Empty,
+                    DatabaseStorePrefixes::Headers.into(),
+                );
+                for entry in access.iterator() {
+                    let (_, HeaderWithBlockLevel { header, block_level }) = entry?;
+                    progress.inc(1);
+                    if !self.range.contains(header.daa_score, header.blue_score) {
+                        continue;
+                    }
+                    write(vec![
+                        header.hash.to_string().into(),
+                        (block_level as u64).into(),
+                        (header.version as u64).into(),
+                        serde_json::to_value(&header.parents_by_level)?.into(),
+                        header.hash_merkle_root.to_string().into(),
+                        header.accepted_id_merkle_root.to_string().into(),
+                        header.utxo_commitment.to_string().into(),
+                        header.timestamp.into(),
+                        (header.bits as u64).into(),
+                        header.nonce.into(),
+                        header.daa_score.into(),
+                        format!("{:x}", header.blue_work).into(),
+                        header.blue_score.into(),
+                        header.pruning_point.to_string().into(),
+                    ])?;
+                }
+            }
+
+            Store::Ghostdag => {
+                let prefix = DatabaseStorePrefixes::Ghostdag.into_iter().chain(0u8.to_le_bytes()).collect();
+                let access =
+                    CachedDbAccess::<Hash, Arc<GhostdagData>, BlockHasher>::new(self.consensus_db.clone(), CachePolicy::Empty, prefix);
+                for entry in access.iterator() {
+                    let (key, data) = entry?;
+                    progress.inc(1);
+                    let hash = Hash::from_slice(&key);
+                    if !self.block_in_range(hash)? {
+                        continue;
+                    }
+                    write(vec![
+                        hash.to_string().into(),
+                        data.blue_score.into(),
+                        format!("{:x}", data.blue_work).into(),
+                        data.selected_parent.to_string().into(),
+                        serde_json::to_value(&data.mergeset_blues)?.into(),
+                        serde_json::to_value(&data.mergeset_reds)?.into(),
+                        serde_json::to_value(&data.blues_anticone_sizes)?.into(),
+                    ])?;
+                }
+            }
+
+            Store::AcceptanceData => {
+                let access = CachedDbAccess::<Hash, AcceptanceData, BlockHasher>::new(
+                    self.consensus_db.clone(),
+                    CachePolicy::Empty,
+                    DatabaseStorePrefixes::AcceptanceData.into(),
+                );
+                for entry in access.iterator() {
+                    let (key, acceptance_data) = entry?;
+                    progress.inc(1);
+                    let chain_block_hash = Hash::from_slice(&key);
+                    if !self.block_in_range(chain_block_hash)? {
+                        continue;
+                    }
+                    for mergeset_block in acceptance_data.iter() {
+                        for accepted in mergeset_block.accepted_transactions.iter() {
+                            write(vec![
+                                chain_block_hash.to_string().into(),
+                                mergeset_block.block_hash.to_string().into(),
+                                accepted.transaction_id.to_string().into(),
+                                (accepted.index_within_block as u64).into(),
+                            ])?;
+                        }
+                    }
+                }
+            }
+
+            Store::BlockTransactions => {
+                let access = CachedDbAccess::<Hash, BlockBody, BlockHasher>::new(
+                    self.consensus_db.clone(),
+                    CachePolicy::Empty,
+                    DatabaseStorePrefixes::BlockTransactions.into(),
+                );
+                for entry in access.iterator() {
+                    let (key, body) = entry?;
+                    progress.inc(1);
+                    let block_hash = Hash::from_slice(&key);
+                    if !self.block_in_range(block_hash)? {
+                        continue;
+                    }
+                    for (index, tx) in body.0.iter().enumerate() {
+                        let outputs = if self.with_addresses {
+                            let outputs: Vec<_> = tx
+                                .outputs
+                                .iter()
+                                .map(|output| AddressTransactionOutput {
+                                    value: output.value,
+                                    script_public_key: output.script_public_key.clone(),
+                                    address: self.address(&output.script_public_key),
+                                })
+                                .collect();
+                            serde_json::to_value(outputs)?
+                        } else {
+                            serde_json::to_value(&tx.outputs)?
+                        };
+                        write(vec![
+                            block_hash.to_string().into(),
+                            (index as u64).into(),
+                            tx.id().to_string().into(),
+                            (tx.version as u64).into(),
+                            serde_json::to_value(&tx.inputs)?.into(),
+                            outputs.into(),
+                            tx.lock_time.into(),
+                            tx.subnetwork_id.to_string().into(),
+                            tx.gas.into(),
+                            kaspa_utils::hex::ToHex::to_hex(&tx.payload).into(),
+                            tx.mass().into(),
+                        ])?;
+                    }
+                }
+            }
+
+            Store::Utxoindex => {
+                let Some(utxoindex_db) = self.utxoindex_db.clone() else {
+                    return Err("the utxoindex database was not found in the datadir".into());
+                };
+                let access = CachedDbAccess::<Hash, CompactUtxoEntry>::new(
+                    utxoindex_db,
+                    CachePolicy::Empty,
+                    DatabaseStorePrefixes::UtxoIndex.into(),
+                );
+                for entry in access.iterator() {
+                    let (key, utxo) = entry?;
+                    progress.inc(1);
+                    if !self.range.contains_daa_score(utxo.block_daa_score) {
+                        continue;
+                    }
+                    let (script_public_key, outpoint) = parse_utxoindex_key(&key).ok_or("malformed utxoindex key")?;
+                    write(vec![
+                        outpoint.transaction_id.to_string().into(),
+                        (outpoint.index as u64).into(),
+                        utxo.amount.into(),
+                        utxo.block_daa_score.into(),
+                        utxo.is_coinbase.into(),
+                        serde_json::to_value(&script_public_key)?.into(),
+                        json!(self.address(&script_public_key)).into(),
+                    ])?;
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Checks the score range against the header of `hash`. Blocks without a header are only exported when no range is set.
+    fn block_in_range(&self, hash: Hash) -> ExportResult<bool> {
+        if !self.range.is_bounded() {
+            return Ok(true);
+        }
+        match self.compact_headers.read(hash) {
+            Ok(header) => Ok(self.range.contains(header.daa_score, header.blue_score)),
+            Err(StoreError::KeyNotFound(_)) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn address(&self, script_public_key: &ScriptPublicKey) -> Option<String> {
+        extract_script_pub_key_address(script_public_key, self.prefix).ok().map(|address| address.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score_range() {
+        let open = ScoreRange::default();
+        assert!(!open.is_bounded());
+        assert!(open.contains(0, u64::MAX));
+
+        let range = ScoreRange { min_daa_score: Some(10), max_daa_score: Some(20), min_blue_score: None, max_blue_score: Some(5) };
+        assert!(range.is_bounded());
+        assert!(range.contains(10, 5));
+        assert!(range.contains(20, 0));
+        assert!(!range.contains(9, 0));
+        assert!(!range.contains(21, 0));
+        assert!(!range.contains(15, 6));
+    }
+}
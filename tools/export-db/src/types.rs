@@ -1,11 +1,14 @@
+use std::mem::size_of;
 use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 
-use kaspa_consensus_core::subnets::SubnetworkId;
-use kaspa_consensus_core::tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutput};
+use kaspa_consensus_core::tx::{
+    ScriptPublicKey, ScriptPublicKeyVersion, ScriptVec, Transaction, TransactionIndexType, TransactionInput, TransactionOutpoint,
+    TransactionOutput,
+};
+use kaspa_hashes::Hash;
 use kaspa_utils::mem_size::MemSizeEstimator;
-use kaspa_utils::serde_bytes;
 
 // consensus/src/model/stores/block_transactions.rs
 #[derive(Clone, Serialize, Deserialize)]
@@ -23,26 +26,33 @@ impl MemSizeEstimator for BlockBody {
     }
 }
 
+// indexes/utxoindex/src/stores/indexed_utxos.rs
+/// Splits a utxoindex key into its script public key and outpoint. The key consists of 2 bytes of little
+/// endian script version, 8 bytes of little endian script length, the script itself, 32 bytes of
+/// transaction id and 4 bytes of little endian output index.
+pub fn parse_utxoindex_key(key: &[u8]) -> Option<(ScriptPublicKey, TransactionOutpoint)> {
+    const VERSION_SIZE: usize = size_of::<ScriptPublicKeyVersion>();
+    const LENGTH_SIZE: usize = size_of::<u64>();
+    const OUTPOINT_SIZE: usize = kaspa_hashes::HASH_SIZE + size_of::<TransactionIndexType>();
+
+    let version = ScriptPublicKeyVersion::from_le_bytes(key.get(..VERSION_SIZE)?.try_into().ok()?);
+    let script_len = u64::from_le_bytes(key.get(VERSION_SIZE..VERSION_SIZE + LENGTH_SIZE)?.try_into().ok()?) as usize;
+    let script = key.get(VERSION_SIZE + LENGTH_SIZE..VERSION_SIZE + LENGTH_SIZE + script_len)?;
+    let outpoint = key.get(VERSION_SIZE + LENGTH_SIZE + script_len..)?;
+    if outpoint.len() != OUTPOINT_SIZE {
+        return None;
+    }
+    let (transaction_id, index) = outpoint.split_at(kaspa_hashes::HASH_SIZE);
+    Some((
+        ScriptPublicKey::new(version, ScriptVec::from_slice(script)),
+        TransactionOutpoint::new(Hash::from_slice(transaction_id), TransactionIndexType::from_le_bytes(index.try_into().ok()?)),
+    ))
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct AddressTransactionOutput {
     pub value: u64,
     pub script_public_key: ScriptPublicKey,
-    pub address: String,
+    pub address: Option<String>,
 }
-
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct AddressTransaction {
-    pub version: u16,
-    pub inputs: Vec<TransactionInput>,
-    pub outputs: Vec<AddressTransactionOutput>,
-    pub lock_time: u64,
-    pub subnetwork_id: SubnetworkId,
-    pub gas: u64,
-    #[serde(with = "serde_bytes")]
-    pub payload: Vec<u8>,
-    pub mass: u64,
-    pub id: String,
-}
\ No newline at end of file
@@ -191,3 +191,6 @@ pub mod triggers;
 pub mod vec;
 
 pub mod fd_budget;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod line_session;
@@ -0,0 +1,168 @@
+//! Sessions of clients exchanging line-delimited messages over a TCP connection,
+//! as the JSON-RPC protocols of the Stratum and wallet servers do
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::io;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use triggered::Listener;
+
+/// State of the session of a connected client, driven by [`serve_lines`]
+#[async_trait]
+pub trait LineSession: Send {
+    /// Event awaited by the session besides the lines sent by the client
+    type Event: Send;
+
+    /// Handles a line sent by the client, including its trailing `\n`
+    async fn handle_line(&mut self, line: &[u8]) -> io::Result<()>;
+
+    /// Waits for the next event of the session, `None` ending the session.
+    ///
+    /// The future is dropped whenever a line is read first, so it must be cancel safe.
+    async fn next_event(&mut self) -> Option<Self::Event> {
+        std::future::pending().await
+    }
+
+    async fn handle_event(&mut self, _event: Self::Event) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Event awaited by [`serve_lines`], the line being read into the line buffer
+enum Event<E> {
+    Read(usize),
+    Session(Option<E>),
+}
+
+/// Serves the session of a connected client until it disconnects, sends a line longer than
+/// `max_line_length` or `shutdown` is triggered
+pub async fn serve_lines<R, S>(reader: R, max_line_length: usize, shutdown: Listener, session: &mut S) -> io::Result<()>
+where
+    R: AsyncRead + Unpin + Send,
+    S: LineSession,
+{
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+    loop {
+        let event = {
+            let mut reader = (&mut reader).take((max_line_length - line.len()) as u64);
+            tokio::select! {
+                _ = shutdown.clone() => return Ok(()),
+                read = reader.read_until(b'\n', &mut line) => Event::Read(read?),
+                event = session.next_event() => Event::Session(event),
+            }
+        };
+        match event {
+            Event::Read(0) if line.len() < max_line_length => return Ok(()),
+            Event::Read(_) if line.ends_with(b"\n") => session.handle_line(&std::mem::take(&mut line)).await?,
+            Event::Read(_) if line.len() >= max_line_length => {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "request line too long"));
+            }
+            Event::Read(_) => {}
+            Event::Session(Some(event)) => session.handle_event(event).await?,
+            Event::Session(None) => return Ok(()),
+        }
+    }
+}
+
+/// Writes `message` as a JSON line
+pub async fn write_json_line<W, T>(writer: &mut W, message: &T) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin + Send,
+    T: Serialize + ?Sized,
+{
+    let mut line = serde_json::to_vec(message)?;
+    line.push(b'\n');
+    writer.write_all(&line).await
+}
+
+pub mod test_helpers {
+    use std::net::SocketAddr;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+        net::{
+            tcp::{OwnedReadHalf, OwnedWriteHalf},
+            TcpStream,
+        },
+    };
+
+    /// Client of a line-delimited session, for the tests of the servers
+    pub struct LineClient {
+        writer: OwnedWriteHalf,
+        lines: Lines<BufReader<OwnedReadHalf>>,
+    }
+
+    impl LineClient {
+        pub async fn connect(address: SocketAddr) -> Self {
+            let (reader, writer) = TcpStream::connect(address).await.unwrap().into_split();
+            Self { writer, lines: BufReader::new(reader).lines() }
+        }
+
+        pub async fn send(&mut self, bytes: &[u8]) {
+            self.writer.write_all(bytes).await.unwrap();
+        }
+
+        pub async fn next_line(&mut self) -> String {
+            self.lines.next_line().await.unwrap().unwrap()
+        }
+
+        /// Sends the `request` line and returns the next line received
+        pub async fn call(&mut self, request: &str) -> String {
+            self.send(format!("{request}\n").as_bytes()).await;
+            self.next_line().await
+        }
+
+        /// Returns whether the server closed the connection without sending any further line
+        pub async fn is_closed(&mut self) -> bool {
+            self.lines.next_line().await.unwrap().is_none()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_helpers::LineClient, *};
+    use crate::triggers::SingleTrigger;
+    use tokio::net::{tcp::OwnedWriteHalf, TcpListener};
+
+    const MAX_LINE_LENGTH: usize = 16;
+
+    struct EchoSession {
+        writer: OwnedWriteHalf,
+    }
+
+    #[async_trait]
+    impl LineSession for EchoSession {
+        type Event = ();
+
+        async fn handle_line(&mut self, line: &[u8]) -> io::Result<()> {
+            write_json_line(&mut self.writer, &String::from_utf8_lossy(line).trim_end()).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_serve_lines() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let shutdown = SingleTrigger::new();
+        let listener_shutdown = shutdown.listener.clone();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, writer) = stream.into_split();
+            serve_lines(reader, MAX_LINE_LENGTH, listener_shutdown, &mut EchoSession { writer }).await
+        });
+
+        let mut client = LineClient::connect(address).await;
+        assert_eq!(client.call("hello").await, r#""hello""#);
+
+        // A line split across writes is handled once complete, up to the limit
+        client.send(b"0123456").await;
+        assert_eq!(client.call("0123456").await, r#""01234560123456""#);
+
+        // A longer one closes the session before any response
+        client.send(&[b' '; MAX_LINE_LENGTH]).await;
+        assert!(client.is_closed().await);
+
+        shutdown.trigger.trigger();
+    }
+}
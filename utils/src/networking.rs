@@ -7,7 +7,7 @@ use std::{
 
 // #![allow(dead_code)]
 use borsh::{BorshDeserialize, BorshSerialize};
-use ipnet::IpNet;
+use ipnet::{IpNet, PrefixLenError};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
@@ -229,6 +229,122 @@ impl BorshDeserialize for IpAddress {
     }
 }
 
+/// An IP subnet in CIDR notation (e.g. `192.168.0.0/16`), newtype of [IpNet].
+///
+/// A plain IP address parses as the subnet holding only that address. IPv4-mapped IPv6
+/// addresses are normalized to their IPv4 form, so they match the IPv4 subnets containing them.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+#[repr(transparent)]
+pub struct IpSubnet(IpNet);
+
+impl IpSubnet {
+    pub fn new(ip: IpAddress, prefix_len: u8) -> Result<Self, PrefixLenError> {
+        const IPV4_MAPPED_PREFIX_LEN: u8 = 96;
+        let net = match ip.0 {
+            IpAddr::V6(ipv6) => match ipv6.to_ipv4_mapped() {
+                Some(ipv4) if prefix_len >= IPV4_MAPPED_PREFIX_LEN => IpNet::new(ipv4.into(), prefix_len - IPV4_MAPPED_PREFIX_LEN)?,
+                _ => IpNet::new(ip.0, prefix_len)?,
+            },
+            IpAddr::V4(_) => IpNet::new(ip.0, prefix_len)?,
+        };
+        Ok(Self(net.trunc()))
+    }
+
+    /// The network address of the subnet
+    pub fn network(&self) -> IpAddress {
+        self.0.network().into()
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.0.prefix_len()
+    }
+
+    /// Returns true if the subnet holds a single IP address
+    pub fn is_single_address(&self) -> bool {
+        self.0.prefix_len() == self.0.max_prefix_len()
+    }
+
+    pub fn contains(&self, ip: &IpAddress) -> bool {
+        self.0.contains(&normalize_ip(ip.0))
+    }
+}
+
+fn normalize_ip(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ipv6) => ipv6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl IntoPy<Py<PyAny>> for IpSubnet {
+    fn into_py(self, py: Python) -> Py<PyAny> {
+        self.to_string().into_py(py)
+    }
+}
+
+impl From<IpAddress> for IpSubnet {
+    fn from(ip: IpAddress) -> Self {
+        Self(IpNet::from(normalize_ip(ip.0)))
+    }
+}
+
+impl From<IpAddr> for IpSubnet {
+    fn from(ip: IpAddr) -> Self {
+        IpAddress::from(ip).into()
+    }
+}
+
+impl FromStr for IpSubnet {
+    type Err = ipnet::AddrParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match IpAddress::from_str(s) {
+            Ok(ip) => Ok(ip.into()),
+            Err(_) => {
+                let net = IpNet::from_str(s)?;
+                Ok(Self::new(net.addr().into(), net.prefix_len()).expect("the prefix length was validated by the parser"))
+            }
+        }
+    }
+}
+
+impl Display for IpSubnet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for IpSubnet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for IpSubnet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        IpSubnet::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl BorshSerialize for IpSubnet {
+    fn serialize<W: borsh::maybestd::io::Write>(&self, writer: &mut W) -> ::core::result::Result<(), borsh::maybestd::io::Error> {
+        borsh::BorshSerialize::serialize(&self.network(), writer)?;
+        borsh::BorshSerialize::serialize(&self.prefix_len(), writer)?;
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for IpSubnet {
+    fn deserialize(buf: &mut &[u8]) -> ::core::result::Result<Self, borsh::maybestd::io::Error> {
+        let network: IpAddress = BorshDeserialize::deserialize(buf)?;
+        let prefix_len: u8 = BorshDeserialize::deserialize(buf)?;
+        Self::new(network, prefix_len)
+            .map_err(|err| borsh::maybestd::io::Error::new(borsh::maybestd::io::ErrorKind::InvalidInput, err.to_string()))
+    }
+}
+
 /// A network address, equivalent of a [SocketAddr].
 #[derive(PartialEq, Eq, Hash, Copy, Clone, Serialize, Deserialize, Debug, BorshSerialize, BorshDeserialize)]
 pub struct NetAddress {
@@ -454,6 +570,34 @@ mod tests {
         assert_eq!(id, id2);
     }
 
+    #[test]
+    fn test_ip_subnet() {
+        let subnet = IpSubnet::from_str("192.168.1.77/16").unwrap();
+        assert_eq!(subnet.to_string(), "192.168.0.0/16");
+        assert!(subnet.contains(&IpAddress::from_str("192.168.200.1").unwrap()));
+        assert!(subnet.contains(&IpAddress::from_str("::ffff:192.168.0.1").unwrap()));
+        assert!(!subnet.contains(&IpAddress::from_str("192.169.0.1").unwrap()));
+        assert!(!subnet.is_single_address());
+
+        let single = IpSubnet::from_str("::ffff:10.0.0.1").unwrap();
+        assert_eq!(single, IpSubnet::from_str("10.0.0.1/32").unwrap());
+        assert!(single.is_single_address());
+        assert!(single.contains(&IpAddress::from_str("10.0.0.1").unwrap()));
+        assert!(!single.contains(&IpAddress::from_str("10.0.0.2").unwrap()));
+
+        let subnet = IpSubnet::from_str("2a01:4f8::/32").unwrap();
+        assert!(subnet.contains(&IpAddress::from_str("2a01:4f8:191:1143::2").unwrap()));
+        assert_eq!(IpSubnet::from_str("::ffff:10.1.0.0/112").unwrap(), IpSubnet::from_str("10.1.0.0/16").unwrap());
+        assert!(IpSubnet::from_str("10.0.0.0/33").is_err());
+        assert!(IpSubnet::from_str("not an ip").is_err());
+
+        let bin = subnet.try_to_vec().unwrap();
+        assert_eq!(subnet, BorshDeserialize::try_from_slice(&bin).unwrap());
+        let json = serde_json::to_string(&subnet).unwrap();
+        assert_eq!(json, r#""2a01:4f8::/32""#);
+        assert_eq!(subnet, serde_json::from_str::<IpSubnet>(&json).unwrap());
+    }
+
     #[test]
     fn test_net_address_from_str() {
         let addr_v4 = NetAddress::from_str("1.2.3.4:5678");
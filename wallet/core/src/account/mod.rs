@@ -16,11 +16,15 @@ use crate::storage::account::AccountSettings;
 use crate::storage::AccountMetadata;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
-use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
+use crate::tx::{
+    CoinControl, Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Pskt, Signer, TimeLock,
+};
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
 use crate::utxo::UtxoContextBinding;
 use kaspa_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey, PrivateKeyBytes};
 use kaspa_consensus_client::UtxoEntryReference;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+use kaspa_txscript::extract_script_pub_key_address;
 use kaspa_wallet_keys::derivation::gen0::WalletDerivationManagerV0;
 use workflow_core::abortable::Abortable;
 
@@ -153,6 +157,79 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(())
     }
 
+    /// UTXOs that are never spent by this account unless explicitly included
+    /// using [`CoinControl`].
+    fn frozen_utxos(&self) -> HashSet<TransactionOutpoint> {
+        self.context().settings.frozen_utxos.iter().cloned().collect()
+    }
+
+    /// Freezes the given UTXOs and returns the resulting list of frozen UTXOs.
+    async fn freeze_utxos(&self, wallet_secret: &Secret, outpoints: &[TransactionOutpoint]) -> Result<Vec<TransactionOutpoint>> {
+        let frozen_utxos = {
+            let mut context = self.context();
+            let frozen_utxos = &mut context.settings.frozen_utxos;
+            outpoints.iter().for_each(|outpoint| {
+                if !frozen_utxos.contains(outpoint) {
+                    frozen_utxos.push(*outpoint);
+                }
+            });
+            frozen_utxos.clone()
+        };
+
+        let account = self.to_storage()?;
+        self.wallet().store().as_account_store()?.store_single(&account, None).await?;
+
+        self.wallet().store().commit(wallet_secret).await?;
+        Ok(frozen_utxos)
+    }
+
+    /// Unfreezes the given UTXOs and returns the resulting list of frozen UTXOs.
+    async fn unfreeze_utxos(&self, wallet_secret: &Secret, outpoints: &[TransactionOutpoint]) -> Result<Vec<TransactionOutpoint>> {
+        let frozen_utxos = {
+            let mut context = self.context();
+            context.settings.frozen_utxos.retain(|outpoint| !outpoints.contains(outpoint));
+            context.settings.frozen_utxos.clone()
+        };
+
+        let account = self.to_storage()?;
+        self.wallet().store().as_account_store()?.store_single(&account, None).await?;
+
+        self.wallet().store().commit(wallet_secret).await?;
+        Ok(frozen_utxos)
+    }
+
+    /// Time-locked outputs tracked by this account.
+    fn timelocks(&self) -> Vec<TimeLock> {
+        self.context().settings.timelocks.clone()
+    }
+
+    /// Tracks a time-locked output owned by this account, its UTXOs being accounted
+    /// for as locked funds until claimed using [`Account::claim_timelocked`].
+    /// Returns the P2SH address funds are locked to.
+    async fn add_timelock(&self, wallet_secret: &Secret, timelock: TimeLock) -> Result<Address> {
+        let address = timelock.address()?;
+        let is_new = {
+            let mut context = self.context();
+            let timelocks = &mut context.settings.timelocks;
+            !timelocks.contains(&timelock) && {
+                timelocks.push(timelock.clone());
+                true
+            }
+        };
+
+        if is_new {
+            let account = self.to_storage()?;
+            self.wallet().store().as_account_store()?.store_single(&account, None).await?;
+            self.wallet().store().commit(wallet_secret).await?;
+
+            if self.wallet().is_connected() {
+                self.utxo_context().register_timelocks(&[timelock], self.wallet().current_daa_score()).await?;
+            }
+        }
+
+        Ok(address)
+    }
+
     fn get_list_string(&self) -> Result<String> {
         let name = style(self.name_with_id()).blue();
         let balance = self.balance_as_strings(None)?;
@@ -241,6 +318,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
             }
         }
 
+        self.utxo_context().register_timelocks(&self.timelocks(), Some(current_daa_score)).await?;
         self.utxo_context().update_balance().await?;
 
         Ok(())
@@ -316,11 +394,13 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
+    /// The spent UTXOs can be controlled using an optional [`CoinControl`].
     async fn send(
         self: Arc<Self>,
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        coin_control: Option<CoinControl>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
         abortable: &Abortable,
@@ -329,7 +409,13 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
 
-        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = GeneratorSettings::try_new_with_coin_control(
+            self.clone().as_dyn_arc(),
+            destination,
+            priority_fee_sompi,
+            payload,
+            &coin_control.unwrap_or_default(),
+        )?;
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -348,6 +434,49 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok((generator.summary(), ids))
     }
 
+    /// Claims the time-locked UTXOs whose lock time is reached into the change address.
+    /// DAA score and timestamp locked UTXOs are claimed by separate transactions.
+    async fn claim_timelocked(
+        self: Arc<Self>,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+        notifier: Option<GenerationNotifier>,
+    ) -> Result<(Vec<GeneratorSummary>, Vec<kaspa_hashes::Hash>)> {
+        let current_daa_score = self.wallet().current_daa_score().ok_or(Error::NotConnected)?;
+        let past_median_time = self.wallet().rpc_api().get_block_dag_info().await?.past_median_time;
+        let claimable = self.utxo_context().claimable_timelocked(current_daa_score, past_median_time);
+        if claimable.is_empty() {
+            return Err(Error::NoClaimableTimeLocks);
+        }
+
+        let keydata = self.prv_key_data(wallet_secret).await?;
+        let signer =
+            Arc::new(Signer::try_new_with_timelocks(self.clone().as_dyn_arc(), keydata, payment_secret, &self.timelocks())?);
+
+        let mut summaries = vec![];
+        let mut ids = vec![];
+        for (lock_time, utxos) in claimable {
+            let settings = GeneratorSettings::try_new_with_timelocked(self.clone().as_dyn_arc(), utxos, lock_time)?;
+            let generator = Generator::try_new(settings, Some(signer.clone()), Some(abortable))?;
+
+            let mut stream = generator.stream();
+            while let Some(transaction) = stream.try_next().await? {
+                transaction.try_sign()?;
+                ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
+
+                if let Some(notifier) = notifier.as_ref() {
+                    notifier(&transaction);
+                }
+                yield_executor().await;
+            }
+
+            summaries.push(generator.summary());
+        }
+
+        Ok((summaries, ids))
+    }
+
     async fn mnemonic(&self, wallet_secret: Secret) -> Result<String> {
         let keydata = self.prv_key_data(wallet_secret).await?;
         let mnemonic = keydata.as_mnemonic(None)?.unwrap();
@@ -448,9 +577,16 @@ pub trait Account: AnySync + Send + Sync + 'static {
         destination: PaymentDestination,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        coin_control: Option<CoinControl>,
         abortable: &Abortable,
     ) -> Result<GeneratorSummary> {
-        let settings = GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+        let settings = GeneratorSettings::try_new_with_coin_control(
+            self.as_dyn_arc(),
+            destination,
+            priority_fee_sompi,
+            payload,
+            &coin_control.unwrap_or_default(),
+        )?;
 
         let generator = Generator::try_new(settings, None, Some(abortable))?;
 
@@ -462,6 +598,67 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(generator.summary())
     }
 
+    /// Creates unsigned [`Pskt`] containers paying the destination from this account.
+    /// Inputs spending from multisig addresses carry their redeem script so that
+    /// each cosigner can sign the containers independently.
+    async fn create_pskt(
+        self: Arc<Self>,
+        destination: PaymentDestination,
+        priority_fee_sompi: Fees,
+        payload: Option<Vec<u8>>,
+        abortable: &Abortable,
+    ) -> Result<(GeneratorSummary, Vec<Pskt>)> {
+        let settings = GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, priority_fee_sompi, payload)?;
+
+        let generator = Generator::try_new(settings, None, Some(abortable))?;
+        let account = self.clone().as_derivation_capable().ok();
+
+        let mut stream = generator.stream();
+        let mut pskts = vec![];
+        while let Some(transaction) = stream.try_next().await? {
+            let mut pskt = Pskt::try_from(&transaction)?;
+            if let Some(account) = account.as_ref() {
+                account.update_pskt(&mut pskt)?;
+            }
+            pskts.push(pskt);
+            yield_executor().await;
+        }
+
+        Ok((generator.summary(), pskts))
+    }
+
+    /// Signs the [`Pskt`] inputs spending from this account's addresses and
+    /// returns the number of signatures added.
+    async fn sign_pskt(self: Arc<Self>, pskt: &mut Pskt, wallet_secret: Secret, payment_secret: Option<Secret>) -> Result<usize> {
+        let account = self.clone().as_derivation_capable()?;
+        let derivation = account.derivation();
+        let prefix = self.wallet().address_prefix()?;
+
+        let addresses = pskt
+            .inputs
+            .iter()
+            .filter_map(|input| input.utxo_entry.as_ref())
+            .filter_map(|entry| extract_script_pub_key_address(&entry.script_public_key, prefix).ok())
+            .collect::<AHashSet<_>>();
+        let addresses = addresses.iter().filter(|address| derivation.addresses_indexes(&[address]).is_ok()).collect::<Vec<_>>();
+        let (receive, change) = derivation.addresses_indexes(&addresses)?;
+
+        let mut private_keys = vec![];
+        for keydata in self.signing_key_data(wallet_secret).await? {
+            let keys = account.create_private_keys(&keydata, &payment_secret, &receive, &change)?;
+            private_keys.extend(keys.into_iter().map(|(_, key)| key.secret_bytes()));
+        }
+        let count = pskt.sign(&private_keys);
+        private_keys.zeroize();
+        count
+    }
+
+    /// Private key data used to sign for this account. Multisig accounts
+    /// may hold the private keys of several of their cosigners.
+    async fn signing_key_data(&self, wallet_secret: Secret) -> Result<Vec<PrvKeyData>> {
+        Ok(vec![self.prv_key_data(wallet_secret).await?])
+    }
+
     fn as_derivation_capable(self: Arc<Self>) -> Result<Arc<dyn DerivationCapableAccount>> {
         Err(Error::AccountAddressDerivationCaps)
     }
@@ -648,6 +845,38 @@ pub trait DerivationCapableAccount: Account {
         Ok(address)
     }
 
+    /// Returns the redeem script of the given account address if it is a multisig address.
+    fn redeem_script(&self, address: &Address) -> Result<Option<Vec<u8>>> {
+        let derivation = self.derivation();
+        let Ok((receive, change)) = derivation.addresses_indexes(&[address]) else {
+            return Ok(None);
+        };
+        if let Some((_, index)) = receive.first() {
+            derivation.receive_address_manager().redeem_script(*index)
+        } else if let Some((_, index)) = change.first() {
+            derivation.change_address_manager().redeem_script(*index)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Attaches the redeem scripts of this account's multisig addresses to the [`Pskt`] inputs spending from them.
+    fn update_pskt(&self, pskt: &mut Pskt) -> Result<()> {
+        let prefix = self.wallet().address_prefix()?;
+        for index in 0..pskt.inputs.len() {
+            let Some(entry) = pskt.inputs[index].utxo_entry.as_ref() else {
+                continue;
+            };
+            let Ok(address) = extract_script_pub_key_address(&entry.script_public_key, prefix) else {
+                continue;
+            };
+            if let Some(redeem_script) = self.redeem_script(&address)? {
+                pskt.set_redeem_script(index, redeem_script)?;
+            }
+        }
+        Ok(())
+    }
+
     fn cosigner_index(&self) -> u32 {
         0
     }
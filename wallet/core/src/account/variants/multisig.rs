@@ -176,13 +176,28 @@ impl Account for MultiSig {
         Err(Error::AccountKindFeature)
     }
 
+    async fn signing_key_data(&self, wallet_secret: Secret) -> Result<Vec<PrvKeyData>> {
+        let prv_key_data_ids = self.prv_key_data_ids.as_ref().ok_or(Error::AccountKindFeature)?;
+        let prv_key_data_store = self.wallet().store().as_prv_key_data_store()?;
+        let mut keydata = Vec::with_capacity(prv_key_data_ids.len());
+        for prv_key_data_id in prv_key_data_ids.iter() {
+            keydata.push(
+                prv_key_data_store
+                    .load_key_data(&wallet_secret, prv_key_data_id)
+                    .await?
+                    .ok_or(Error::PrivateKeyNotFound(*prv_key_data_id))?,
+            );
+        }
+        Ok(keydata)
+    }
+
     fn as_dyn_arc(self: Arc<Self>) -> Arc<dyn Account> {
         self
     }
 
     fn sig_op_count(&self) -> u8 {
-        // TODO @maxim
-        1
+        // OpCheckMultiSig counts one signature operation per public key
+        self.xpub_keys.len() as u8
     }
 
     fn minimum_signatures(&self) -> u16 {
@@ -246,6 +261,10 @@ impl DerivationCapableAccount for MultiSig {
     fn account_index(&self) -> u64 {
         0
     }
+
+    fn cosigner_index(&self) -> u32 {
+        self.cosigner_index.unwrap_or(0) as u32
+    }
 }
 
 #[cfg(test)]
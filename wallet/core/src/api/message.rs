@@ -6,8 +6,9 @@
 //!
 
 use crate::imports::*;
-use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
+use crate::tx::{CoinControl, Fees, GeneratorSummary, PaymentDestination};
 use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::TransactionOutpoint;
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
@@ -451,6 +452,7 @@ pub struct AccountsSendRequest {
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    pub coin_control: Option<CoinControl>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -541,6 +543,7 @@ pub struct AccountsEstimateRequest {
     pub destination: PaymentDestination,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    pub coin_control: Option<CoinControl>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -549,6 +552,34 @@ pub struct AccountsEstimateResponse {
     pub generator_summary: GeneratorSummary,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsFreezeUtxosRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub outpoints: Vec<TransactionOutpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsFreezeUtxosResponse {
+    pub frozen_utxos: Vec<TransactionOutpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsUnfreezeUtxosRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub outpoints: Vec<TransactionOutpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsUnfreezeUtxosResponse {
+    pub frozen_utxos: Vec<TransactionOutpoint>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsDataGetRequest {
@@ -610,11 +641,71 @@ pub struct TransactionsReplaceMetadataResponse {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AddressBookEnumerateRequest {}
+pub struct AddressBookEnumerateRequest {
+    pub search: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookEnumerateResponse {
+    pub entries: Vec<AddressBookEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookUpdateRequest {
+    pub wallet_secret: Secret,
+    pub entry: AddressBookEntry,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookUpdateResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookRemoveRequest {
+    pub wallet_secret: Secret,
+    pub address: Address,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBookRemoveResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsEnumerateRequest {
+    pub search: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsEnumerateResponse {
+    pub labels: Vec<Label>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsUpdateRequest {
+    pub wallet_secret: Secret,
+    pub label: Label,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsUpdateResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsRemoveRequest {
+    pub wallet_secret: Secret,
+    pub target: LabelTarget,
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct AddressBookEnumerateResponse {}
+pub struct LabelsRemoveResponse {}
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
@@ -11,6 +11,7 @@ use crate::api::message::*;
 use crate::imports::*;
 use crate::storage::{PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, WalletDescriptor};
 use crate::tx::GeneratorSummary;
+use kaspa_consensus_core::tx::TransactionOutpoint;
 use workflow_core::channel::Receiver;
 
 ///
@@ -427,6 +428,42 @@ pub trait WalletApi: Send + Sync + AnySync {
     /// an error.
     async fn accounts_estimate_call(self: Arc<Self>, request: AccountsEstimateRequest) -> Result<AccountsEstimateResponse>;
 
+    /// Wrapper around [`Self::accounts_freeze_utxos_call()`](Self::accounts_freeze_utxos_call)
+    async fn accounts_freeze_utxos(
+        self: Arc<Self>,
+        account_id: AccountId,
+        wallet_secret: Secret,
+        outpoints: Vec<TransactionOutpoint>,
+    ) -> Result<Vec<TransactionOutpoint>> {
+        Ok(self.accounts_freeze_utxos_call(AccountsFreezeUtxosRequest { account_id, wallet_secret, outpoints }).await?.frozen_utxos)
+    }
+
+    /// Freezes account UTXOs, excluding them from transactions unless they are
+    /// explicitly included using [`CoinControl`](crate::tx::CoinControl). Frozen
+    /// UTXOs are persisted in the wallet storage. Returns an
+    /// [`AccountsFreezeUtxosResponse`] containing all frozen UTXOs of the account.
+    async fn accounts_freeze_utxos_call(self: Arc<Self>, request: AccountsFreezeUtxosRequest) -> Result<AccountsFreezeUtxosResponse>;
+
+    /// Wrapper around [`Self::accounts_unfreeze_utxos_call()`](Self::accounts_unfreeze_utxos_call)
+    async fn accounts_unfreeze_utxos(
+        self: Arc<Self>,
+        account_id: AccountId,
+        wallet_secret: Secret,
+        outpoints: Vec<TransactionOutpoint>,
+    ) -> Result<Vec<TransactionOutpoint>> {
+        Ok(self
+            .accounts_unfreeze_utxos_call(AccountsUnfreezeUtxosRequest { account_id, wallet_secret, outpoints })
+            .await?
+            .frozen_utxos)
+    }
+
+    /// Unfreezes previously frozen account UTXOs. Returns an [`AccountsUnfreezeUtxosResponse`]
+    /// containing the remaining frozen UTXOs of the account.
+    async fn accounts_unfreeze_utxos_call(
+        self: Arc<Self>,
+        request: AccountsUnfreezeUtxosRequest,
+    ) -> Result<AccountsUnfreezeUtxosResponse>;
+
     /// Get a range of transaction records for a specific account id.
     async fn transactions_data_get_range(
         self: Arc<Self>,
@@ -463,10 +500,68 @@ pub trait WalletApi: Send + Sync + AnySync {
         request: TransactionsReplaceMetadataRequest,
     ) -> Result<TransactionsReplaceMetadataResponse>;
 
+    /// Wrapper around [`Self::address_book_enumerate_call()`](Self::address_book_enumerate_call)
+    async fn address_book_enumerate(self: Arc<Self>, search: Option<String>) -> Result<Vec<AddressBookEntry>> {
+        Ok(self.address_book_enumerate_call(AddressBookEnumerateRequest { search }).await?.entries)
+    }
+
+    /// Returns the address book entries of the currently open wallet. If `search`
+    /// is supplied, only entries whose alias, title or address contain the
+    /// (case-insensitive) search string are returned.
     async fn address_book_enumerate_call(
         self: Arc<Self>,
         request: AddressBookEnumerateRequest,
     ) -> Result<AddressBookEnumerateResponse>;
+
+    /// Wrapper around [`Self::address_book_update_call()`](Self::address_book_update_call)
+    async fn address_book_update(self: Arc<Self>, wallet_secret: Secret, entry: AddressBookEntry) -> Result<()> {
+        self.address_book_update_call(AddressBookUpdateRequest { wallet_secret, entry }).await?;
+        Ok(())
+    }
+
+    /// Adds an entry to the address book, replacing any existing entry
+    /// for the same address. The address book is stored encrypted in
+    /// the wallet storage.
+    async fn address_book_update_call(self: Arc<Self>, request: AddressBookUpdateRequest) -> Result<AddressBookUpdateResponse>;
+
+    /// Wrapper around [`Self::address_book_remove_call()`](Self::address_book_remove_call)
+    async fn address_book_remove(self: Arc<Self>, wallet_secret: Secret, address: Address) -> Result<()> {
+        self.address_book_remove_call(AddressBookRemoveRequest { wallet_secret, address }).await?;
+        Ok(())
+    }
+
+    /// Removes the address book entry of the supplied address.
+    async fn address_book_remove_call(self: Arc<Self>, request: AddressBookRemoveRequest) -> Result<AddressBookRemoveResponse>;
+
+    /// Wrapper around [`Self::labels_enumerate_call()`](Self::labels_enumerate_call)
+    async fn labels_enumerate(self: Arc<Self>, search: Option<String>) -> Result<Vec<Label>> {
+        Ok(self.labels_enumerate_call(LabelsEnumerateRequest { search }).await?.labels)
+    }
+
+    /// Returns the labels of the currently open wallet. If `search` is supplied,
+    /// only labels whose text, note or target contain the (case-insensitive)
+    /// search string are returned.
+    async fn labels_enumerate_call(self: Arc<Self>, request: LabelsEnumerateRequest) -> Result<LabelsEnumerateResponse>;
+
+    /// Wrapper around [`Self::labels_update_call()`](Self::labels_update_call)
+    async fn labels_update(self: Arc<Self>, wallet_secret: Secret, label: Label) -> Result<()> {
+        self.labels_update_call(LabelsUpdateRequest { wallet_secret, label }).await?;
+        Ok(())
+    }
+
+    /// Attaches a label and an optional note to an address, a transaction
+    /// outpoint or a transaction id, replacing any existing label of the
+    /// same target. Labels are stored encrypted in the wallet storage.
+    async fn labels_update_call(self: Arc<Self>, request: LabelsUpdateRequest) -> Result<LabelsUpdateResponse>;
+
+    /// Wrapper around [`Self::labels_remove_call()`](Self::labels_remove_call)
+    async fn labels_remove(self: Arc<Self>, wallet_secret: Secret, target: LabelTarget) -> Result<()> {
+        self.labels_remove_call(LabelsRemoveRequest { wallet_secret, target }).await?;
+        Ok(())
+    }
+
+    /// Removes the label attached to the supplied target.
+    async fn labels_remove_call(self: Arc<Self>, request: LabelsRemoveRequest) -> Result<LabelsRemoveResponse>;
 }
 
 /// alias for `Arc<dyn WalletApi + Send + Sync + 'static>`
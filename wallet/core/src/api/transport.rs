@@ -104,10 +104,17 @@ impl WalletApi for WalletClient {
         AccountsAddresses,
         AccountsTransfer,
         AccountsEstimate,
+        AccountsFreezeUtxos,
+        AccountsUnfreezeUtxos,
         TransactionsDataGet,
         TransactionsReplaceNote,
         TransactionsReplaceMetadata,
         AddressBookEnumerate,
+        AddressBookUpdate,
+        AddressBookRemove,
+        LabelsEnumerate,
+        LabelsUpdate,
+        LabelsRemove,
     ]}
 }
 
@@ -184,10 +191,17 @@ impl WalletServer {
         AccountsAddresses,
         AccountsTransfer,
         AccountsEstimate,
+        AccountsFreezeUtxos,
+        AccountsUnfreezeUtxos,
         TransactionsDataGet,
         TransactionsReplaceNote,
         TransactionsReplaceMetadata,
         AddressBookEnumerate,
+        AddressBookUpdate,
+        AddressBookRemove,
+        LabelsEnumerate,
+        LabelsUpdate,
+        LabelsRemove,
     ]}
 }
 
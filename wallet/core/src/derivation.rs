@@ -161,6 +161,20 @@ impl AddressManager {
         Ok(addresses)
     }
 
+    /// Returns the redeem script of the multisig address at the given index,
+    /// or `None` if this manager does not derive multisig addresses.
+    pub fn redeem_script(&self, index: u32) -> Result<Option<Vec<u8>>> {
+        if self.pubkey_managers.len() <= 1 {
+            return Ok(None);
+        }
+
+        let mut keys = vec![];
+        for manager in self.pubkey_managers.iter() {
+            keys.extend(manager.get_range(index..index + 1)?);
+        }
+        Ok(Some(create_multisig_redeem_script(self.minimum_signatures, &keys, self.ecdsa)?))
+    }
+
     fn update_address_to_index_map(&self, offset: u32, addresses: &[Address]) -> Result<()> {
         let address_to_index_map = &mut self.inner().address_to_index_map;
         for (index, address) in addresses.iter().enumerate() {
@@ -439,17 +453,22 @@ pub trait AddressDerivationManagerTrait: AnySync + Send + Sync + 'static {
     ) -> Result<Vec<(Address, secp256k1::SecretKey)>>;
 }
 
+pub fn create_multisig_redeem_script(minimum_signatures: usize, keys: &[secp256k1::PublicKey], ecdsa: bool) -> Result<Vec<u8>> {
+    let script = if !ecdsa {
+        multisig_redeem_script(keys.iter().map(|pk| pk.x_only_public_key().0.serialize()), minimum_signatures)
+    } else {
+        multisig_redeem_script_ecdsa(keys.iter().map(|pk| pk.serialize()), minimum_signatures)
+    }?;
+    Ok(script)
+}
+
 pub fn create_multisig_address(
     minimum_signatures: usize,
     keys: Vec<secp256k1::PublicKey>,
     prefix: Prefix,
     ecdsa: bool,
 ) -> Result<Address> {
-    let script = if !ecdsa {
-        multisig_redeem_script(keys.iter().map(|pk| pk.x_only_public_key().0.serialize()), minimum_signatures)
-    } else {
-        multisig_redeem_script_ecdsa(keys.iter().map(|pk| pk.serialize()), minimum_signatures)
-    }?;
+    let script = create_multisig_redeem_script(minimum_signatures, &keys, ecdsa)?;
     let script_pub_key = pay_to_script_hash_script(&script);
     let address = extract_script_pub_key_address(&script_pub_key, prefix)?;
     Ok(address)
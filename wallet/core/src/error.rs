@@ -2,11 +2,13 @@
 //! Error types used by the wallet framework.
 //!
 
-use crate::imports::{AccountId, AccountKind, AssocPrvKeyDataIds, PrvKeyDataId};
+use crate::imports::{AccountId, AccountKind, AssocPrvKeyDataIds, LabelTarget, PrvKeyDataId};
 use base64::DecodeError;
 use downcast::DowncastError;
+use kaspa_addresses::Address;
 use kaspa_bip32::Error as BIP32Error;
 use kaspa_consensus_core::sign::Error as CoreSignError;
+use kaspa_consensus_core::tx::TransactionOutpoint;
 use kaspa_rpc_core::RpcError as KaspaRpcError;
 use kaspa_wrpc_client::error::Error as KaspaWorkflowRpcError;
 use std::sync::PoisonError;
@@ -326,6 +328,58 @@ pub enum Error {
 
     #[error(transparent)]
     Metrics(#[from] kaspa_metrics_core::error::Error),
+
+    #[error("Invalid PSKT: {0}")]
+    InvalidPskt(String),
+
+    #[error("PSKTs do not describe the same transaction")]
+    PsktMismatch,
+
+    #[error("PSKT input {0} is missing its UTXO entry")]
+    PsktMissingUtxoEntry(usize),
+
+    #[error("PSKT input {0} has {1} of {2} required signatures")]
+    PsktInsufficientSignatures(usize, usize, usize),
+
+    #[error("PSKT input {0} is not finalized")]
+    PsktNotFinalized(usize),
+
+    #[error("UTXO {0} is not a mature UTXO of this account")]
+    CoinControlUtxoNotFound(TransactionOutpoint),
+
+    #[error("UTXO {0} can not be both included and excluded")]
+    CoinControlConflict(TransactionOutpoint),
+
+    #[error("Invalid UTXO selection strategy '{0}' (must be one of: default|largest|smallest|exact|random)")]
+    InvalidUtxoSelection(String),
+
+    #[error("Invalid lock time {0}")]
+    InvalidLockTime(u64),
+
+    #[error("Time lock owner {0} must be a P2PK address")]
+    InvalidTimeLockAddress(Address),
+
+    #[error("No time-locked UTXOs can be claimed yet")]
+    NoClaimableTimeLocks,
+
+    #[error("Address book entry for {0} not found")]
+    AddressBookEntryNotFound(Address),
+
+    #[error("Label for {0} not found")]
+    LabelNotFound(LabelTarget),
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error("HTTP -> {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Webhook {0} responded with HTTP status {1}")]
+    WebhookStatus(String, u16),
+
+    #[error("Event sink '{0}' not found")]
+    SinkNotFound(String),
+
+    #[error("Event sink '{0}' already exists")]
+    SinkAlreadyExists(String),
 }
 
 impl From<Aborted> for Error {
@@ -245,7 +245,7 @@ impl Events {
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
 #[serde(rename_all = "kebab-case")]
 pub enum EventKind {
     All,
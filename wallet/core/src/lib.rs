@@ -102,6 +102,8 @@ pub mod result;
 pub mod rpc;
 pub mod serializer;
 pub mod settings;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sink;
 pub mod storage;
 pub mod tx;
 pub mod utils;
@@ -0,0 +1,120 @@
+//!
+//! Event sink configuration persisted in the application folder.
+//!
+
+use crate::events::EventKind;
+use crate::imports::*;
+use crate::storage::local::Storage;
+
+/// Destination of the events forwarded by a sink.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum SinkTarget {
+    /// HTTP POST of each event to `url`, signed with HMAC-SHA256 if a `secret` is supplied
+    Webhook { url: String, secret: Option<String> },
+    /// JSON line written to a Unix domain socket
+    UnixSocket { path: String },
+    /// JSON line appended to a file
+    File { path: String },
+}
+
+impl std::fmt::Display for SinkTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SinkTarget::Webhook { url, secret } => {
+                write!(f, "webhook {url}{}", if secret.is_some() { " (signed)" } else { "" })
+            }
+            SinkTarget::UnixSocket { path } => write!(f, "unix socket {path}"),
+            SinkTarget::File { path } => write!(f, "file {path}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SinkConfig {
+    /// Unique sink name
+    pub name: String,
+    pub target: SinkTarget,
+    /// Forwarded event kinds, [`SinkConfig::DEFAULT_EVENTS`] if empty
+    #[serde(default)]
+    pub events: Vec<EventKind>,
+    /// Accounts whose events are forwarded, all accounts if empty
+    #[serde(default)]
+    pub accounts: Vec<AccountId>,
+}
+
+impl SinkConfig {
+    /// Balance and transaction events forwarded unless configured otherwise
+    pub const DEFAULT_EVENTS: [EventKind; 6] =
+        [EventKind::Balance, EventKind::Pending, EventKind::Maturity, EventKind::Reorg, EventKind::Stasis, EventKind::Discovery];
+
+    pub fn new(name: String, target: SinkTarget) -> Self {
+        Self { name, target, events: vec![], accounts: vec![] }
+    }
+
+    pub fn with_events(mut self, events: Vec<EventKind>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn with_accounts(mut self, accounts: Vec<AccountId>) -> Self {
+        self.accounts = accounts;
+        self
+    }
+
+    /// Returns `true` if an event of the given kind, bound to the given account, should be forwarded.
+    /// Events that are not bound to an account are forwarded only if no account filter is set.
+    pub fn accepts(&self, kind: EventKind, account_id: Option<&AccountId>) -> bool {
+        let events = if self.events.is_empty() { Self::DEFAULT_EVENTS.as_slice() } else { self.events.as_slice() };
+        if !events.iter().any(|event| *event == EventKind::All || *event == kind) {
+            return false;
+        }
+
+        self.accounts.is_empty() || account_id.is_some_and(|account_id| self.accounts.contains(account_id))
+    }
+}
+
+/// Event sink configuration stored as JSON in the application folder.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SinksConfig {
+    pub sinks: Vec<SinkConfig>,
+}
+
+impl SinksConfig {
+    const STORAGE: &'static str = "event-sinks.json";
+
+    fn storage() -> Result<Storage> {
+        Storage::try_new(Self::STORAGE)
+    }
+
+    pub async fn try_load() -> Result<Self> {
+        let storage = Self::storage()?;
+        if storage.exists().await? {
+            Ok(workflow_store::fs::read_json(storage.filename()).await?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub async fn try_store(&self) -> Result<()> {
+        let storage = Self::storage()?;
+        storage.ensure_dir().await?;
+        workflow_store::fs::write_json(storage.filename(), self).await?;
+        Ok(())
+    }
+
+    pub fn insert(&mut self, sink: SinkConfig) -> Result<()> {
+        if self.sinks.iter().any(|existing| existing.name == sink.name) {
+            return Err(Error::SinkAlreadyExists(sink.name));
+        }
+        self.sinks.push(sink);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<SinkConfig> {
+        let index = self.sinks.iter().position(|sink| sink.name == name).ok_or_else(|| Error::SinkNotFound(name.to_string()))?;
+        Ok(self.sinks.remove(index))
+    }
+}
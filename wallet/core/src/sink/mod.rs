@@ -0,0 +1,423 @@
+//!
+//! Event sinks forwarding wallet [`Events`] outside of the process.
+//!
+//! Selected events are delivered as signed HTTP webhooks, JSON lines
+//! written to a Unix socket, or JSON lines appended to a file. Pending
+//! notifications are kept in a durable [`Outbox`] and retried until
+//! they are delivered.
+//!
+
+pub mod config;
+pub mod outbox;
+pub mod stream;
+pub mod webhook;
+
+pub use config::{SinkConfig, SinkTarget, SinksConfig};
+pub use outbox::{Outbox, OutboxEntry};
+pub use stream::{FileSink, UnixSocketSink};
+pub use webhook::{verify_webhook_signature, webhook_signature, WebhookSink};
+
+use crate::imports::*;
+use crate::storage::local::Storage;
+use crate::storage::Binding;
+use workflow_core::time::unixtime_as_millis_u64;
+
+/// Event forwarded to a sink.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SinkNotification {
+    /// Unique notification id, identical across delivery retries
+    pub id: String,
+    /// Time the event was received (unix time in milliseconds)
+    pub timestamp: u64,
+    pub kind: EventKind,
+    /// Account the event is bound to, if any
+    pub account_id: Option<AccountId>,
+    /// Serialized [`Events`] variant
+    pub event: serde_json::Value,
+}
+
+impl SinkNotification {
+    pub fn try_new(event: &Events) -> Result<Self> {
+        Ok(Self {
+            id: format!("{:016x}", rand::random::<u64>()),
+            timestamp: unixtime_as_millis_u64(),
+            kind: EventKind::from(event),
+            account_id: account_id(event),
+            event: serde_json::to_value(event)?,
+        })
+    }
+}
+
+/// Account the event is bound to, if any
+fn account_id(event: &Events) -> Option<AccountId> {
+    match event {
+        Events::Balance { id, .. } => Some(AccountId::from(*id)),
+        Events::Pending { record }
+        | Events::Reorg { record }
+        | Events::Stasis { record }
+        | Events::Maturity { record }
+        | Events::Discovery { record } => match record.binding() {
+            Binding::Account(account_id) => Some(*account_id),
+            Binding::Custom(id) => Some(AccountId::from(*id)),
+        },
+        _ => None,
+    }
+}
+
+/// Destination of the forwarded events.
+#[async_trait]
+pub trait Sink: Send + Sync {
+    async fn send(&self, notification: &SinkNotification) -> Result<()>;
+}
+
+fn create_sink(target: &SinkTarget) -> Result<Arc<dyn Sink>> {
+    let sink: Arc<dyn Sink> = match target {
+        SinkTarget::Webhook { url, secret } => Arc::new(WebhookSink::try_new(url, secret.as_deref())?),
+        SinkTarget::UnixSocket { path } => Arc::new(UnixSocketSink::try_new(path)?),
+        SinkTarget::File { path } => Arc::new(FileSink::try_new(path)?),
+    };
+    Ok(sink)
+}
+
+struct Inner {
+    sinks: Mutex<Vec<(SinkConfig, Arc<dyn Sink>)>>,
+    outbox: AsyncMutex<Outbox>,
+    /// Locks held while delivering to a sink, so that each sink has at most
+    /// one delivery in progress
+    deliveries: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+    task_ctl: DuplexChannel,
+    running: AtomicBool,
+}
+
+/// Forwards the events received from the wallet [`Multiplexer`]
+/// to the configured sinks.
+#[derive(Clone)]
+pub struct EventSinks {
+    inner: Arc<Inner>,
+}
+
+impl EventSinks {
+    /// Interval at which the outbox is checked for retries
+    const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+    pub fn try_new(config: &SinksConfig) -> Result<Self> {
+        Self::try_new_with_storage(config, Outbox::default_storage()?)
+    }
+
+    pub fn try_new_with_storage(config: &SinksConfig, outbox: Storage) -> Result<Self> {
+        let sinks = Self::create_sinks(config)?;
+        let inner = Inner {
+            sinks: Mutex::new(sinks),
+            outbox: AsyncMutex::new(Outbox::new(outbox)),
+            deliveries: Mutex::new(HashMap::new()),
+            task_ctl: DuplexChannel::oneshot(),
+            running: AtomicBool::new(false),
+        };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    fn create_sinks(config: &SinksConfig) -> Result<Vec<(SinkConfig, Arc<dyn Sink>)>> {
+        config.sinks.iter().map(|sink| Ok((sink.clone(), create_sink(&sink.target)?))).collect()
+    }
+
+    /// Replaces the configured sinks. Pending notifications of removed sinks are discarded.
+    pub async fn update(&self, config: &SinksConfig) -> Result<()> {
+        *self.inner.sinks.lock().unwrap() = Self::create_sinks(config)?;
+        let names = config.sinks.iter().map(|sink| sink.name.as_str()).collect::<Vec<_>>();
+        let mut outbox = self.inner.outbox.lock().await;
+        outbox.retain_sinks(&names);
+        outbox.try_store().await
+    }
+
+    pub fn sinks(&self) -> Vec<SinkConfig> {
+        self.inner.sinks.lock().unwrap().iter().map(|(config, _)| config.clone()).collect()
+    }
+
+    /// Number of notifications pending delivery
+    pub async fn pending(&self) -> usize {
+        self.inner.outbox.lock().await.entries().len()
+    }
+
+    /// Queues the event for every sink accepting it and delivers it in
+    /// the background.
+    pub async fn handle_event(&self, event: &Events) -> Result<()> {
+        let kind = EventKind::from(event);
+        let account_id = account_id(event);
+        let sinks = self
+            .inner
+            .sinks
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(config, _)| config.accepts(kind, account_id.as_ref()))
+            .map(|(config, sink)| (config.name.clone(), sink.clone()))
+            .collect::<Vec<_>>();
+        if sinks.is_empty() {
+            return Ok(());
+        }
+
+        let notification = SinkNotification::try_new(event)?;
+        let mut outbox = self.inner.outbox.lock().await;
+        for (name, _) in sinks.iter() {
+            outbox.push(OutboxEntry::new(name.clone(), notification.clone()));
+        }
+        outbox.try_store().await?;
+        drop(outbox);
+
+        for (name, sink) in sinks {
+            self.spawn_delivery(name, sink);
+        }
+        Ok(())
+    }
+
+    fn configured_sinks(&self) -> Vec<(String, Arc<dyn Sink>)> {
+        self.inner.sinks.lock().unwrap().iter().map(|(config, sink)| (config.name.clone(), sink.clone())).collect()
+    }
+
+    fn delivery_lock(&self, name: &str) -> Arc<AsyncMutex<()>> {
+        self.inner.deliveries.lock().unwrap().entry(name.to_string()).or_insert_with(|| Arc::new(AsyncMutex::new(()))).clone()
+    }
+
+    /// Delivers the notifications of the sink `name` in a separate task, so
+    /// that a slow sink does not hold back the other sinks nor the events.
+    /// Does nothing if a delivery to this sink is already in progress since
+    /// it picks up the notifications queued in the meantime.
+    fn spawn_delivery(&self, name: String, sink: Arc<dyn Sink>) {
+        let this = self.clone();
+        spawn(async move {
+            let lock = this.delivery_lock(&name);
+            let Some(_guard) = lock.try_lock() else {
+                return;
+            };
+            this.deliver(&name, &sink).await.unwrap_or_else(|err| log_error!("Event sink '{name}': {err}"));
+        });
+    }
+
+    /// Delivers the notifications of the sink `name` in the order they were
+    /// queued, stopping at the first one that is not due: a notification
+    /// waiting for a retry is never overtaken by a newer one.
+    ///
+    /// The delivered notifications are removed from the persisted outbox
+    /// once the sink has no more notification due, so a restart in between
+    /// delivers them again under the same id.
+    async fn deliver(&self, name: &str, sink: &Arc<dyn Sink>) -> Result<()> {
+        loop {
+            let now = unixtime_as_millis_u64();
+            let Some(entry) = self.inner.outbox.lock().await.next_due(name, now) else {
+                return self.inner.outbox.lock().await.try_store().await;
+            };
+            // the outbox is not locked while sending, keeping it available to the other sinks
+            let result = sink.send(&entry.notification).await;
+            let mut outbox = self.inner.outbox.lock().await;
+            match result {
+                Ok(()) => outbox.remove(&entry),
+                Err(err) => {
+                    if !outbox.reschedule(&entry, now) {
+                        log_error!(
+                            "Event sink '{}': dropping notification {} after {} attempts: {err}",
+                            entry.sink,
+                            entry.notification.id,
+                            Outbox::MAX_ATTEMPTS
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Delivers the notifications that are due to every sink concurrently
+    /// and waits for the deliveries to complete.
+    pub async fn flush(&self) -> Result<()> {
+        let deliveries = self.configured_sinks().into_iter().map(|(name, sink)| async move {
+            let lock = self.delivery_lock(&name);
+            let _guard = lock.lock().await;
+            self.deliver(&name, &sink).await
+        });
+        join_all(deliveries).await.into_iter().collect::<Result<Vec<_>>>()?;
+        Ok(())
+    }
+
+    /// Loads the outbox and starts forwarding the events received from the `multiplexer`.
+    pub async fn start(&self, multiplexer: &Multiplexer<Box<Events>>) -> Result<()> {
+        let sinks = self.sinks();
+        let mut outbox = self.inner.outbox.lock().await;
+        outbox.try_load().await?;
+        outbox.retain_sinks(&sinks.iter().map(|sink| sink.name.as_str()).collect::<Vec<_>>());
+        outbox.try_store().await?;
+        drop(outbox);
+        self.inner.running.store(true, Ordering::SeqCst);
+
+        let task_ctl_receiver = self.inner.task_ctl.request.receiver.clone();
+        let task_ctl_sender = self.inner.task_ctl.response.sender.clone();
+        let events = multiplexer.channel();
+
+        let this = self.clone();
+        spawn(async move {
+            loop {
+                select! {
+                    _ = task_ctl_receiver.recv().fuse() => {
+                        break;
+                    },
+
+                    _ = sleep(Self::RETRY_INTERVAL).fuse() => {
+                        for (name, sink) in this.configured_sinks() {
+                            this.spawn_delivery(name, sink);
+                        }
+                    },
+
+                    msg = events.receiver.recv().fuse() => {
+                        match msg {
+                            Ok(event) => {
+                                this.handle_event(&event).await.unwrap_or_else(|err| log_error!("Event sinks: {err}"));
+                            },
+                            Err(err) => {
+                                log_error!("Event sinks: error while receiving multiplexer message: {err}");
+                                break;
+                            }
+                        }
+                    },
+                }
+            }
+
+            task_ctl_sender.send(()).await.unwrap();
+        });
+
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        if self.inner.running.swap(false, Ordering::SeqCst) {
+            self.inner.task_ctl.signal(()).await.map_err(|err| Error::custom(err.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utxo::UtxoContextId;
+
+    fn account_id(byte: u8) -> AccountId {
+        AccountId(kaspa_hashes::Hash::from_bytes([byte; 32]))
+    }
+
+    #[test]
+    fn test_sink_config_accepts() {
+        let target = SinkTarget::File { path: "events.jsonl".to_string() };
+
+        let sink = SinkConfig::new("default".to_string(), target.clone());
+        assert!(sink.accepts(EventKind::Balance, None));
+        assert!(sink.accepts(EventKind::Maturity, Some(&account_id(1))));
+        assert!(!sink.accepts(EventKind::DaaScoreChange, None));
+
+        let sink = sink.with_events(vec![EventKind::All]);
+        assert!(sink.accepts(EventKind::DaaScoreChange, None));
+
+        let sink =
+            SinkConfig::new("account".to_string(), target).with_events(vec![EventKind::Maturity]).with_accounts(vec![account_id(1)]);
+        assert!(sink.accepts(EventKind::Maturity, Some(&account_id(1))));
+        assert!(!sink.accepts(EventKind::Maturity, Some(&account_id(2))));
+        assert!(!sink.accepts(EventKind::Maturity, None));
+        assert!(!sink.accepts(EventKind::Pending, Some(&account_id(1))));
+
+        let json = serde_json::to_string(&sink).unwrap();
+        assert_eq!(serde_json::from_str::<SinkConfig>(&json).unwrap(), sink);
+    }
+
+    #[tokio::test]
+    async fn test_outbox_order_and_persistence() -> Result<()> {
+        let folder = std::env::temp_dir().join(format!("kaspa-event-sinks-{:016x}", rand::random::<u64>()));
+        let storage = Storage::try_new_with_folder(folder.to_str().unwrap(), "event-sinks.outbox")?;
+        let notification = |score| SinkNotification::try_new(&Events::DaaScoreChange { current_daa_score: score }).unwrap();
+        let (first, second, other) = (
+            OutboxEntry::new("a".to_string(), notification(1)),
+            OutboxEntry::new("a".to_string(), notification(2)),
+            OutboxEntry::new("b".to_string(), notification(3)),
+        );
+
+        let mut outbox = Outbox::new(storage.clone());
+        outbox.push(first.clone());
+        outbox.push(second.clone());
+        outbox.push(other.clone());
+        // the queued notifications are persisted before any delivery
+        outbox.try_store().await?;
+        let mut stored = Outbox::new(storage.clone());
+        stored.try_load().await?;
+        assert_eq!(stored.entries().iter().map(|entry| &entry.sink).collect::<Vec<_>>(), vec!["a", "a", "b"]);
+
+        assert_eq!(outbox.next_due("a", 0).unwrap().notification.id, first.notification.id);
+        assert!(outbox.reschedule(&first, 0));
+
+        // the newer notification waits for the rescheduled one, other sinks are not held back
+        assert!(outbox.next_due("a", 0).is_none());
+        assert_eq!(outbox.next_due("b", 0).unwrap().notification.id, other.notification.id);
+
+        // delivered notifications are removed from the persisted outbox
+        outbox.remove(&other);
+        outbox.try_store().await?;
+        let mut stored = Outbox::new(storage.clone());
+        stored.try_load().await?;
+        assert_eq!(stored.entries().iter().map(|entry| &entry.sink).collect::<Vec<_>>(), vec!["a", "a"]);
+        assert_eq!(stored.entries()[0].attempts, 1);
+
+        let retry = outbox.entries()[0].next_attempt;
+        assert_eq!(outbox.next_due("a", retry).unwrap().notification.id, first.notification.id);
+        outbox.remove(&first);
+        assert_eq!(outbox.next_due("a", retry).unwrap().notification.id, second.notification.id);
+
+        storage.purge().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_event_sinks_delivery() -> Result<()> {
+        let folder = std::env::temp_dir().join(format!("kaspa-event-sinks-{:016x}", rand::random::<u64>()));
+        let folder = folder.to_str().unwrap();
+        let storage = Storage::try_new_with_folder(folder, "event-sinks.outbox")?;
+        storage.ensure_dir().await?;
+        let path = std::path::Path::new(folder).join("events.jsonl");
+
+        let mut config = SinksConfig::default();
+        config.insert(
+            SinkConfig::new("file".to_string(), SinkTarget::File { path: path.to_str().unwrap().to_string() })
+                .with_events(vec![EventKind::DaaScoreChange]),
+        )?;
+        config.insert(SinkConfig::new(
+            "webhook".to_string(),
+            SinkTarget::Webhook { url: "http://127.0.0.1:1/".to_string(), secret: Some("secret".to_string()) },
+        ))?;
+        assert!(config.insert(SinkConfig::new("file".to_string(), SinkTarget::UnixSocket { path: "socket".to_string() })).is_err());
+
+        let sinks = EventSinks::try_new_with_storage(&config, storage.clone())?;
+        sinks.handle_event(&Events::DaaScoreChange { current_daa_score: 1 }).await?;
+        sinks.handle_event(&Events::DaaScoreChange { current_daa_score: 2 }).await?;
+        let balance = Events::Balance { balance: None, id: UtxoContextId::from(account_id(1)) };
+        sinks.handle_event(&balance).await?;
+        sinks.flush().await?;
+
+        let lines = tokio::fs::read_to_string(&path).await?;
+        let notifications = lines.lines().map(serde_json::from_str::<SinkNotification>).collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(notifications.len(), 2);
+        assert!(notifications.iter().all(|notification| notification.kind == EventKind::DaaScoreChange));
+        assert_eq!(notifications[1].event["data"]["currentDaaScore"], 2);
+
+        // the unreachable webhook keeps the balance notification in the outbox
+        assert_eq!(sinks.pending().await, 1);
+        let mut outbox = Outbox::new(storage.clone());
+        outbox.try_load().await?;
+        assert_eq!(outbox.entries().len(), 1);
+        assert_eq!(outbox.entries()[0].sink, "webhook");
+        assert_eq!(outbox.entries()[0].attempts, 1);
+        assert_eq!(outbox.entries()[0].notification.account_id, Some(account_id(1)));
+
+        config.remove("webhook")?;
+        sinks.update(&config).await?;
+        assert_eq!(sinks.pending().await, 0);
+
+        storage.purge().await?;
+        tokio::fs::remove_file(&path).await?;
+        Ok(())
+    }
+}
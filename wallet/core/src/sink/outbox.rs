@@ -0,0 +1,122 @@
+//!
+//! Durable outbox holding the notifications pending delivery.
+//!
+
+use crate::imports::*;
+use crate::sink::SinkNotification;
+use crate::storage::local::Storage;
+
+/// Notification pending delivery to the sink `sink`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxEntry {
+    pub sink: String,
+    pub notification: SinkNotification,
+    /// Number of failed delivery attempts
+    pub attempts: u32,
+    /// Time of the next delivery attempt (unix time in milliseconds)
+    pub next_attempt: u64,
+}
+
+impl OutboxEntry {
+    pub fn new(sink: String, notification: SinkNotification) -> Self {
+        Self { sink, notification, attempts: 0, next_attempt: 0 }
+    }
+
+    fn matches(&self, other: &OutboxEntry) -> bool {
+        self.sink == other.sink && self.notification.id == other.notification.id
+    }
+}
+
+/// Notifications pending delivery. Failed deliveries are retried with an
+/// exponential backoff and dropped after [`Outbox::MAX_ATTEMPTS`].
+///
+/// Every change is persisted by the next [`Outbox::try_store`], so that the
+/// entries queued by a single call are written at once and a notification
+/// is never lost to a restart between its queuing and its delivery.
+pub struct Outbox {
+    storage: Storage,
+    entries: Vec<OutboxEntry>,
+    /// Whether the entries changed since they were last stored
+    dirty: bool,
+}
+
+impl Outbox {
+    pub const MAX_ATTEMPTS: u32 = 20;
+    const RETRY_DELAY_MIN: u64 = 1000;
+    const RETRY_DELAY_MAX: u64 = 10 * 60 * 1000;
+
+    pub fn default_storage() -> Result<Storage> {
+        Storage::try_new("event-sinks.outbox")
+    }
+
+    pub fn new(storage: Storage) -> Self {
+        Self { storage, entries: vec![], dirty: false }
+    }
+
+    pub async fn try_load(&mut self) -> Result<()> {
+        if self.storage.exists().await? {
+            self.entries = workflow_store::fs::read_json(self.storage.filename()).await?;
+        }
+        Ok(())
+    }
+
+    /// Persists the entries if they changed since they were last stored
+    pub async fn try_store(&mut self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.storage.ensure_dir().await?;
+        workflow_store::fs::write_json(self.storage.filename(), &self.entries).await?;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub fn entries(&self) -> &[OutboxEntry] {
+        &self.entries
+    }
+
+    pub fn push(&mut self, entry: OutboxEntry) {
+        self.dirty = true;
+        self.entries.push(entry);
+    }
+
+    /// Oldest entry of `sink` if it is due for delivery at `now`. Entries
+    /// of a sink are delivered in the order they were queued, so a newer
+    /// entry is never returned before the older ones were delivered or
+    /// dropped.
+    pub fn next_due(&self, sink: &str, now: u64) -> Option<OutboxEntry> {
+        self.entries.iter().find(|entry| entry.sink == sink).filter(|entry| entry.next_attempt <= now).cloned()
+    }
+
+    pub fn remove(&mut self, entry: &OutboxEntry) {
+        let len = self.entries.len();
+        self.entries.retain(|existing| !existing.matches(entry));
+        self.dirty |= self.entries.len() != len;
+    }
+
+    /// Reschedules a failed delivery, dropping the entry once it reaches
+    /// [`Outbox::MAX_ATTEMPTS`]. Returns `false` if the entry was dropped.
+    pub fn reschedule(&mut self, entry: &OutboxEntry, now: u64) -> bool {
+        let Some(index) = self.entries.iter().position(|existing| existing.matches(entry)) else {
+            return false;
+        };
+        self.dirty = true;
+        let existing = &mut self.entries[index];
+        existing.attempts += 1;
+        if existing.attempts >= Self::MAX_ATTEMPTS {
+            self.entries.remove(index);
+            return false;
+        }
+        let delay = Self::RETRY_DELAY_MIN.saturating_mul(1 << (existing.attempts - 1).min(20)).min(Self::RETRY_DELAY_MAX);
+        existing.next_attempt = now + delay;
+        true
+    }
+
+    /// Removes the entries of sinks that are no longer configured
+    pub fn retain_sinks(&mut self, sinks: &[&str]) {
+        let len = self.entries.len();
+        self.entries.retain(|entry| sinks.contains(&entry.sink.as_str()));
+        self.dirty |= self.entries.len() != len;
+    }
+}
@@ -0,0 +1,64 @@
+//!
+//! Unix socket and file sinks writing notifications as JSON lines.
+//!
+
+use crate::imports::*;
+use crate::sink::{Sink, SinkNotification};
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+
+fn json_line(notification: &SinkNotification) -> Result<Vec<u8>> {
+    let mut line = serde_json::to_vec(notification)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+/// Writes each notification as a JSON line to a Unix domain socket,
+/// connecting for every notification.
+pub struct UnixSocketSink {
+    path: PathBuf,
+}
+
+impl UnixSocketSink {
+    pub fn try_new(path: &str) -> Result<Self> {
+        Ok(Self { path: workflow_store::fs::resolve_path(path)? })
+    }
+}
+
+#[async_trait]
+impl Sink for UnixSocketSink {
+    async fn send(&self, notification: &SinkNotification) -> Result<()> {
+        cfg_if! {
+            if #[cfg(unix)] {
+                let mut stream = tokio::net::UnixStream::connect(&self.path).await?;
+                stream.write_all(&json_line(notification)?).await?;
+                stream.shutdown().await?;
+                Ok(())
+            } else {
+                let _ = notification;
+                Err(Error::custom(format!("Unix socket {} is not supported on this platform", self.path.display())))
+            }
+        }
+    }
+}
+
+/// Appends each notification as a JSON line to a file.
+pub struct FileSink {
+    path: PathBuf,
+}
+
+impl FileSink {
+    pub fn try_new(path: &str) -> Result<Self> {
+        Ok(Self { path: workflow_store::fs::resolve_path(path)? })
+    }
+}
+
+#[async_trait]
+impl Sink for FileSink {
+    async fn send(&self, notification: &SinkNotification) -> Result<()> {
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        file.write_all(&json_line(notification)?).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+//!
+//! HTTP webhook sink.
+//!
+
+use crate::imports::*;
+use crate::sink::{Sink, SinkNotification};
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+
+pub const HEADER_EVENT_ID: &str = "X-Event-Id";
+pub const HEADER_EVENT_KIND: &str = "X-Event-Kind";
+pub const HEADER_EVENT_TIMESTAMP: &str = "X-Event-Timestamp";
+pub const HEADER_EVENT_SIGNATURE: &str = "X-Event-Signature";
+
+fn mac(secret: &str, timestamp: u64, body: &str) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    mac
+}
+
+/// Hex-encoded HMAC-SHA256 of `<timestamp>.<body>` keyed with `secret`,
+/// sent in the [`HEADER_EVENT_SIGNATURE`] header as `sha256=<signature>`.
+pub fn webhook_signature(secret: &str, timestamp: u64, body: &str) -> String {
+    mac(secret, timestamp, body).finalize().into_bytes().to_vec().to_hex()
+}
+
+/// Verifies a webhook `signature` (with or without the `sha256=` prefix) in constant time.
+pub fn verify_webhook_signature(secret: &str, timestamp: u64, body: &str, signature: &str) -> bool {
+    let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let mut bytes = vec![0u8; signature.len() / 2];
+    if faster_hex::hex_decode(signature.as_bytes(), &mut bytes).is_err() {
+        return false;
+    }
+    mac(secret, timestamp, body).verify_slice(&bytes).is_ok()
+}
+
+/// Posts each notification as a JSON body to `url`.
+pub struct WebhookSink {
+    url: String,
+    secret: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    const TIMEOUT: Duration = Duration::from_secs(30);
+
+    pub fn try_new(url: &str, secret: Option<&str>) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(Self::TIMEOUT).build()?;
+        Ok(Self { url: url.to_string(), secret: secret.map(String::from), client })
+    }
+}
+
+#[async_trait]
+impl Sink for WebhookSink {
+    async fn send(&self, notification: &SinkNotification) -> Result<()> {
+        let body = serde_json::to_string(notification)?;
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(HEADER_EVENT_ID, &notification.id)
+            .header(HEADER_EVENT_KIND, notification.kind.to_string())
+            .header(HEADER_EVENT_TIMESTAMP, notification.timestamp.to_string());
+        if let Some(secret) = &self.secret {
+            let signature = webhook_signature(secret, notification.timestamp, &body);
+            request = request.header(HEADER_EVENT_SIGNATURE, format!("sha256={signature}"));
+        }
+
+        let status = request.body(body).send().await?.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(Error::WebhookStatus(self.url.clone(), status.as_u16()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_signature() {
+        let body = r#"{"id":"1"}"#;
+        let signature = webhook_signature("secret", 1700000000000, body);
+        assert_eq!(signature, "c1effdcf6986703e8ee1e898e44d372c4dd456b29af173ad82eb3a401a27acac");
+
+        assert!(verify_webhook_signature("secret", 1700000000000, body, &signature));
+        assert!(verify_webhook_signature("secret", 1700000000000, body, &format!("sha256={signature}")));
+        assert!(!verify_webhook_signature("other", 1700000000000, body, &signature));
+        assert!(!verify_webhook_signature("secret", 1700000000001, body, &signature));
+        assert!(!verify_webhook_signature("secret", 1700000000000, r#"{"id":"2"}"#, &signature));
+        assert!(!verify_webhook_signature("secret", 1700000000000, body, "invalid"));
+    }
+}
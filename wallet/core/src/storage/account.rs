@@ -3,8 +3,10 @@
 //!
 
 use crate::imports::*;
+use crate::tx::TimeLock;
+use kaspa_consensus_core::tx::TransactionOutpoint;
 
-const ACCOUNT_SETTINGS_VERSION: u32 = 0;
+const ACCOUNT_SETTINGS_VERSION: u32 = 2;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,6 +15,12 @@ pub struct AccountSettings {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Vec<u8>>,
+    /// UTXOs excluded from transaction generation unless explicitly included
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frozen_utxos: Vec<TransactionOutpoint>,
+    /// Time-locked outputs tracked by the account until claimed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timelocks: Vec<TimeLock>,
 }
 
 impl BorshSerialize for AccountSettings {
@@ -20,6 +28,8 @@ impl BorshSerialize for AccountSettings {
         BorshSerialize::serialize(&ACCOUNT_SETTINGS_VERSION, writer)?;
         BorshSerialize::serialize(&self.name, writer)?;
         BorshSerialize::serialize(&self.meta, writer)?;
+        BorshSerialize::serialize(&self.frozen_utxos, writer)?;
+        BorshSerialize::serialize(&self.timelocks, writer)?;
 
         Ok(())
     }
@@ -27,11 +37,13 @@ impl BorshSerialize for AccountSettings {
 
 impl BorshDeserialize for AccountSettings {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let _version: u32 = BorshDeserialize::deserialize(buf)?;
+        let version: u32 = BorshDeserialize::deserialize(buf)?;
         let name = BorshDeserialize::deserialize(buf)?;
         let meta = BorshDeserialize::deserialize(buf)?;
+        let frozen_utxos = if version > 0 { BorshDeserialize::deserialize(buf)? } else { vec![] };
+        let timelocks = if version > 1 { BorshDeserialize::deserialize(buf)? } else { vec![] };
 
-        Ok(Self { name, meta })
+        Ok(Self { name, meta, frozen_utxos, timelocks })
     }
 }
 
@@ -126,6 +138,7 @@ impl BorshDeserialize for AccountStorage {
 mod tests {
     use super::*;
     use crate::tests::*;
+    use crate::tx::LockTime;
 
     #[test]
     fn test_storage_account_storage_wrapper() -> Result<()> {
@@ -150,4 +163,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_storage_account_settings_frozen_utxos() -> Result<()> {
+        let frozen_utxos = vec![TransactionOutpoint::new(TransactionId::from_u64_word(1), 2)];
+        let settings_in = AccountSettings { name: Some("frozen".to_string()), meta: None, frozen_utxos, timelocks: vec![] };
+        let settings_out = AccountSettings::try_from_slice(&settings_in.try_to_vec()?)?;
+        assert_eq!(settings_in.name, settings_out.name);
+        assert_eq!(settings_in.frozen_utxos, settings_out.frozen_utxos);
+
+        // Settings stored before UTXO freezing deserialize without frozen UTXOs
+        let mut settings_v0 = vec![];
+        BorshSerialize::serialize(&0u32, &mut settings_v0)?;
+        BorshSerialize::serialize(&settings_in.name, &mut settings_v0)?;
+        BorshSerialize::serialize(&settings_in.meta, &mut settings_v0)?;
+        let settings_out = AccountSettings::try_from_slice(&settings_v0)?;
+        assert_eq!(settings_in.name, settings_out.name);
+        assert!(settings_out.frozen_utxos.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_account_settings_timelocks() -> Result<()> {
+        let owner = Address::new(Prefix::Testnet, kaspa_addresses::Version::PubKey, &[1u8; 32]);
+        let timelocks = vec![TimeLock::try_new(owner, LockTime::DaaScore(1000))?];
+        let settings_in = AccountSettings { timelocks, ..Default::default() };
+        let settings_out = AccountSettings::try_from_slice(&settings_in.try_to_vec()?)?;
+        assert_eq!(settings_in.timelocks, settings_out.timelocks);
+
+        // Settings stored before time locks deserialize without time locks
+        let mut settings_v1 = vec![];
+        BorshSerialize::serialize(&1u32, &mut settings_v1)?;
+        BorshSerialize::serialize(&settings_in.name, &mut settings_v1)?;
+        BorshSerialize::serialize(&settings_in.meta, &mut settings_v1)?;
+        BorshSerialize::serialize(&settings_in.frozen_utxos, &mut settings_v1)?;
+        let settings_out = AccountSettings::try_from_slice(&settings_v1)?;
+        assert!(settings_out.timelocks.is_empty());
+
+        Ok(())
+    }
 }
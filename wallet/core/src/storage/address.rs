@@ -4,10 +4,44 @@
 
 use crate::imports::*;
 
-// TODO
-#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[wasm_bindgen(typescript_custom_section)]
+const TS_ADDRESS_BOOK_ENTRY: &'static str = r#"
+/**
+ * Address book entry associating a contact with one of its addresses.
+ *
+ * @category Wallet API
+ */
+export interface IAddressBookEntry {
+    alias: string;
+    title: string;
+    address: string;
+}
+"#;
+
+/// Address book entry associating a contact with one of its addresses.
+///
+/// Entries are unique by address. A contact with multiple addresses is
+/// represented by multiple entries sharing the same `alias`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AddressBookEntry {
+    /// Short contact name
     pub alias: String,
+    /// Free-form description of the contact or of the address
     pub title: String,
     pub address: Address,
 }
+
+impl AddressBookEntry {
+    pub fn new(alias: String, title: String, address: Address) -> Self {
+        Self { alias, title, address }
+    }
+
+    /// Case-insensitive match of `search` against the alias, the title and the address
+    pub fn matches(&self, search: &str) -> bool {
+        let search = search.to_lowercase();
+        self.alias.to_lowercase().contains(&search)
+            || self.title.to_lowercase().contains(&search)
+            || self.address.to_string().contains(&search)
+    }
+}
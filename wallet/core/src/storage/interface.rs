@@ -109,6 +109,23 @@ pub trait AddressBookStore: Send + Sync {
     async fn search(&self, _search: &str) -> Result<Vec<Arc<AddressBookEntry>>> {
         Err(Error::NotImplemented)
     }
+    /// Stores an entry, replacing the existing entry for the same address
+    async fn store(&self, _entry: AddressBookEntry) -> Result<()> {
+        Err(Error::NotImplemented)
+    }
+    async fn remove(&self, _address: &Address) -> Result<()> {
+        Err(Error::NotImplemented)
+    }
+}
+
+#[async_trait]
+pub trait LabelStore: Send + Sync {
+    async fn iter(&self) -> Result<StorageStream<Arc<Label>>>;
+    async fn search(&self, search: &str) -> Result<Vec<Arc<Label>>>;
+    async fn load(&self, target: &LabelTarget) -> Result<Option<Arc<Label>>>;
+    /// Stores a label, replacing the existing label of the same target
+    async fn store(&self, label: Label) -> Result<()>;
+    async fn remove(&self, target: &LabelTarget) -> Result<()>;
 }
 
 pub struct TransactionRangeResult {
@@ -247,6 +264,7 @@ pub trait Interface: Send + Sync + AnySync {
     fn as_prv_key_data_store(&self) -> Result<Arc<dyn PrvKeyDataStore>>;
     fn as_account_store(&self) -> Result<Arc<dyn AccountStore>>;
     fn as_address_book_store(&self) -> Result<Arc<dyn AddressBookStore>>;
+    fn as_label_store(&self) -> Result<Arc<dyn LabelStore>>;
     fn as_transaction_record_store(&self) -> Result<Arc<dyn TransactionRecordStore>>;
 }
 
@@ -0,0 +1,116 @@
+//!
+//! Free-form labels and notes attached to addresses,
+//! transaction outpoints and transaction ids.
+//!
+
+use crate::imports::*;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_LABEL: &'static str = r#"
+/**
+ * Item a label is attached to.
+ *
+ * @category Wallet API
+ */
+export type ILabelTarget =
+    { type: "address", value: string }
+    | { type: "outpoint", value: ITransactionOutpoint }
+    | { type: "transaction", value: HexString };
+
+/**
+ * Free-form label and note attached to an address,
+ * a transaction outpoint or a transaction id.
+ *
+ * @category Wallet API
+ */
+export interface ILabel {
+    target: ILabelTarget;
+    label: string;
+    note?: string;
+}
+"#;
+
+/// Item a [`Label`] is attached to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum LabelTarget {
+    Address(Address),
+    Outpoint(TransactionOutpoint),
+    Transaction(TransactionId),
+}
+
+impl std::fmt::Display for LabelTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelTarget::Address(address) => write!(f, "{address}"),
+            LabelTarget::Outpoint(outpoint) => write!(f, "{}-{}", outpoint.transaction_id, outpoint.index),
+            LabelTarget::Transaction(transaction_id) => write!(f, "{transaction_id}"),
+        }
+    }
+}
+
+/// Parses an address, a `<txid>-<index>` outpoint or a transaction id.
+impl FromStr for LabelTarget {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Ok(address) = Address::try_from(s) {
+            Ok(LabelTarget::Address(address))
+        } else if let Some((transaction_id, index)) = s.split_once('-') {
+            let transaction_id = TransactionId::from_hex(transaction_id)?;
+            let index = index.parse::<u32>().map_err(|_| Error::custom(format!("Invalid outpoint index '{index}'")))?;
+            Ok(LabelTarget::Outpoint(TransactionOutpoint::new(transaction_id, index)))
+        } else {
+            Ok(LabelTarget::Transaction(TransactionId::from_hex(s)?))
+        }
+    }
+}
+
+/// Free-form label and note attached to an address,
+/// a transaction outpoint or a transaction id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Label {
+    pub target: LabelTarget,
+    pub label: String,
+    pub note: Option<String>,
+}
+
+impl Label {
+    pub fn new(target: LabelTarget, label: String, note: Option<String>) -> Self {
+        Self { target, label, note }
+    }
+
+    /// Case-insensitive match of `search` against the label, the note and the target
+    pub fn matches(&self, search: &str) -> bool {
+        let search = search.to_lowercase();
+        self.label.to_lowercase().contains(&search)
+            || self.note.as_ref().is_some_and(|note| note.to_lowercase().contains(&search))
+            || self.target.to_string().contains(&search)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+
+    #[test]
+    fn test_label_target() {
+        let address = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        let transaction_id = TransactionId::from_u64_word(1);
+        let outpoint = TransactionOutpoint::new(transaction_id, 3);
+
+        for target in [LabelTarget::Address(address), LabelTarget::Outpoint(outpoint), LabelTarget::Transaction(transaction_id)] {
+            assert_eq!(target.to_string().parse::<LabelTarget>().unwrap(), target);
+        }
+        assert!("invalid".parse::<LabelTarget>().is_err());
+
+        let label = Label::new(LabelTarget::Outpoint(outpoint), "Savings".to_string(), Some("Cold storage".to_string()));
+        assert!(label.matches("savings"));
+        assert!(label.matches("COLD"));
+        assert!(label.matches(&format!("{transaction_id}-3")));
+        assert!(!label.matches("exchange"));
+    }
+}
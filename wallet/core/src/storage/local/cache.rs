@@ -16,6 +16,7 @@ pub struct Cache {
     pub accounts: Collection<AccountId, AccountStorage>,
     pub metadata: Collection<AccountId, AccountMetadata>,
     pub address_book: Vec<AddressBookEntry>,
+    pub labels: Vec<Label>,
 }
 
 impl Cache {
@@ -34,8 +35,19 @@ impl Cache {
         let user_hint = wallet.user_hint;
         let wallet_title = wallet.title;
         let address_book = payload.0.address_book.into_iter().collect();
+        let labels = payload.0.labels.into_iter().collect();
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            labels,
+        })
     }
 
     pub fn from_payload(
@@ -53,8 +65,19 @@ impl Cache {
         let accounts: Collection<AccountId, AccountStorage> = payload.accounts.try_into()?;
         let metadata: Collection<AccountId, AccountMetadata> = Collection::default();
         let address_book = payload.address_book.into_iter().collect();
+        let labels = payload.labels.into_iter().collect();
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            labels,
+        })
     }
 
     pub fn to_wallet(
@@ -67,7 +90,8 @@ impl Cache {
         let accounts: Vec<AccountStorage> = (&self.accounts).try_into()?;
         let metadata: Vec<AccountMetadata> = (&self.metadata).try_into()?;
         let address_book = self.address_book.clone();
-        let payload = Payload::new(prv_key_data, accounts, address_book);
+        let labels = self.labels.clone();
+        let payload = Payload::new(prv_key_data, accounts, address_book, labels);
         let payload = Decrypted::new(payload).encrypt(secret, self.encryption_kind)?;
 
         Ok(WalletStorage {
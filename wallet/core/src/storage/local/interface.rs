@@ -6,7 +6,7 @@
 
 use crate::imports::*;
 use crate::storage::interface::{
-    AddressBookStore, CreateArgs, OpenArgs, StorageDescriptor, StorageStream, WalletDescriptor, WalletExportOptions,
+    AddressBookStore, CreateArgs, LabelStore, OpenArgs, StorageDescriptor, StorageStream, WalletDescriptor, WalletExportOptions,
 };
 use crate::storage::local::cache::*;
 use crate::storage::local::streams::*;
@@ -334,6 +334,10 @@ impl Interface for LocalStore {
         Ok(self.inner()?)
     }
 
+    fn as_label_store(&self) -> Result<Arc<dyn LabelStore>> {
+        Ok(self.inner()?)
+    }
+
     fn as_transaction_record_store(&self) -> Result<Arc<dyn TransactionRecordStore>> {
         Ok(self.inner()?.transactions.clone())
     }
@@ -609,6 +613,10 @@ impl AccountStore for LocalStoreInner {
 
 #[async_trait]
 impl AddressBookStore for LocalStoreInner {
+    async fn is_empty(&self) -> Result<bool> {
+        Ok(self.cache.read().unwrap().address_book.is_empty())
+    }
+
     async fn iter(&self) -> Result<StorageStream<Arc<AddressBookEntry>>> {
         Ok(Box::pin(AddressBookEntryStream::new(self.cache.clone())))
     }
@@ -620,9 +628,77 @@ impl AddressBookStore for LocalStoreInner {
             .unwrap()
             .address_book
             .iter()
-            .filter_map(|entry| if entry.alias.contains(search) { Some(Arc::new(entry.clone())) } else { None })
+            .filter_map(|entry| if entry.matches(search) { Some(Arc::new(entry.clone())) } else { None })
+            .collect();
+
+        Ok(matches)
+    }
+
+    async fn store(&self, entry: AddressBookEntry) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        if let Some(existing) = cache.address_book.iter_mut().find(|existing| existing.address == entry.address) {
+            *existing = entry;
+        } else {
+            cache.address_book.push(entry);
+        }
+        self.set_modified(true);
+        Ok(())
+    }
+
+    async fn remove(&self, address: &Address) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        let len = cache.address_book.len();
+        cache.address_book.retain(|entry| entry.address != *address);
+        if cache.address_book.len() == len {
+            return Err(Error::AddressBookEntryNotFound(address.clone()));
+        }
+        self.set_modified(true);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LabelStore for LocalStoreInner {
+    async fn iter(&self) -> Result<StorageStream<Arc<Label>>> {
+        Ok(Box::pin(LabelStream::new(self.cache.clone())))
+    }
+
+    async fn search(&self, search: &str) -> Result<Vec<Arc<Label>>> {
+        let matches = self
+            .cache
+            .read()
+            .unwrap()
+            .labels
+            .iter()
+            .filter_map(|label| if label.matches(search) { Some(Arc::new(label.clone())) } else { None })
             .collect();
 
         Ok(matches)
     }
+
+    async fn load(&self, target: &LabelTarget) -> Result<Option<Arc<Label>>> {
+        Ok(self.cache.read().unwrap().labels.iter().find(|label| label.target == *target).cloned().map(Arc::new))
+    }
+
+    async fn store(&self, label: Label) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        if let Some(existing) = cache.labels.iter_mut().find(|existing| existing.target == label.target) {
+            *existing = label;
+        } else {
+            cache.labels.push(label);
+        }
+        self.set_modified(true);
+        Ok(())
+    }
+
+    async fn remove(&self, target: &LabelTarget) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        let len = cache.labels.len();
+        cache.labels.retain(|label| label.target != *target);
+        if cache.labels.len() == len {
+            return Err(Error::LabelNotFound(target.clone()));
+        }
+        self.set_modified(true);
+        Ok(())
+    }
 }
@@ -3,7 +3,7 @@
 //!
 
 use crate::imports::*;
-use crate::storage::{AddressBookEntry, PrvKeyData, PrvKeyDataId};
+use crate::storage::{AddressBookEntry, Label, PrvKeyData, PrvKeyDataId};
 use kaspa_bip32::Mnemonic;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -13,14 +13,20 @@ pub struct Payload {
     pub accounts: Vec<AccountStorage>,
     pub address_book: Vec<AddressBookEntry>,
     pub encrypt_transactions: Option<EncryptionKind>,
+    pub labels: Vec<Label>,
 }
 
 impl Payload {
     const STORAGE_MAGIC: u32 = 0x41544144;
-    const STORAGE_VERSION: u32 = 0;
-
-    pub fn new(prv_key_data: Vec<PrvKeyData>, accounts: Vec<AccountStorage>, address_book: Vec<AddressBookEntry>) -> Self {
-        Self { prv_key_data, accounts, address_book, encrypt_transactions: None }
+    const STORAGE_VERSION: u32 = 1;
+
+    pub fn new(
+        prv_key_data: Vec<PrvKeyData>,
+        accounts: Vec<AccountStorage>,
+        address_book: Vec<AddressBookEntry>,
+        labels: Vec<Label>,
+    ) -> Self {
+        Self { prv_key_data, accounts, address_book, encrypt_transactions: None, labels }
     }
 }
 
@@ -61,6 +67,7 @@ impl BorshSerialize for Payload {
         BorshSerialize::serialize(&self.accounts, writer)?;
         BorshSerialize::serialize(&self.address_book, writer)?;
         BorshSerialize::serialize(&self.encrypt_transactions, writer)?;
+        BorshSerialize::serialize(&self.labels, writer)?;
 
         Ok(())
     }
@@ -68,14 +75,15 @@ impl BorshSerialize for Payload {
 
 impl BorshDeserialize for Payload {
     fn deserialize(buf: &mut &[u8]) -> IoResult<Self> {
-        let StorageHeader { version: _, .. } =
+        let StorageHeader { version, .. } =
             StorageHeader::deserialize(buf)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
         let prv_key_data = BorshDeserialize::deserialize(buf)?;
         let accounts = BorshDeserialize::deserialize(buf)?;
         let address_book = BorshDeserialize::deserialize(buf)?;
         let encrypt_transactions = BorshDeserialize::deserialize(buf)?;
+        let labels = if version > 0 { BorshDeserialize::deserialize(buf)? } else { vec![] };
 
-        Ok(Self { prv_key_data, accounts, address_book, encrypt_transactions })
+        Ok(Self { prv_key_data, accounts, address_book, encrypt_transactions, labels })
     }
 }
 
@@ -86,10 +94,36 @@ mod tests {
 
     #[test]
     fn test_storage_wallet_payload() -> Result<()> {
-        let storable_in = Payload::new(vec![], vec![], vec![]);
+        let storable_in = Payload::new(vec![], vec![], vec![], vec![]);
         let guard = StorageGuard::new(&storable_in);
         let _storable_out = guard.validate()?;
 
         Ok(())
     }
+
+    #[test]
+    fn test_storage_wallet_payload_address_book() -> Result<()> {
+        let address = Address::new(Prefix::Testnet, kaspa_addresses::Version::PubKey, &[1u8; 32]);
+        let address_book = vec![AddressBookEntry::new("alice".to_string(), "Alice".to_string(), address.clone())];
+        let labels = vec![
+            Label::new(LabelTarget::Address(address), "donations".to_string(), None),
+            Label::new(LabelTarget::Transaction(TransactionId::from_u64_word(1)), "rent".to_string(), Some("March".to_string())),
+        ];
+        let storable_in = Payload::new(vec![], vec![], address_book.clone(), labels.clone());
+        let guard = StorageGuard::new(&storable_in);
+        let storable_out = guard.validate()?;
+        assert_eq!(storable_out.address_book, address_book);
+        assert_eq!(storable_out.labels, labels);
+
+        // payloads stored before labels were introduced
+        let mut legacy = vec![];
+        StorageHeader::new(Payload::STORAGE_MAGIC, 0).serialize(&mut legacy)?;
+        BorshSerialize::serialize(&(Vec::<PrvKeyData>::new(), Vec::<AccountStorage>::new(), address_book.clone()), &mut legacy)?;
+        BorshSerialize::serialize(&Option::<EncryptionKind>::None, &mut legacy)?;
+        let storable_out = Payload::try_from_slice(&legacy)?;
+        assert_eq!(storable_out.address_book, address_book);
+        assert!(storable_out.labels.is_empty());
+
+        Ok(())
+    }
 }
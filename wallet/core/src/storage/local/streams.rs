@@ -123,3 +123,32 @@ impl Stream for AddressBookEntryStream {
         }
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct LabelStream {
+    inner: StoreStreamInner,
+}
+
+impl LabelStream {
+    pub(crate) fn new(cache: Arc<RwLock<Cache>>) -> Self {
+        Self { inner: StoreStreamInner::new(cache) }
+    }
+}
+
+impl Stream for LabelStream {
+    type Item = Result<Arc<Label>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let cache = self.inner.cache.clone();
+        let cache = cache.read().unwrap();
+        let vec = &cache.labels;
+
+        if self.inner.cursor < vec.len() {
+            let label = vec[self.inner.cursor].clone();
+            self.inner.cursor += 1;
+            Poll::Ready(Some(Ok(Arc::new(label))))
+        } else {
+            Poll::Ready(None)
+        }
+    }
+}
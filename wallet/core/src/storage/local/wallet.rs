@@ -141,7 +141,7 @@ mod tests {
             Some(Hint::new("hint".to_string())),
             &Secret::from("secret"),
             EncryptionKind::XChaCha20Poly1305,
-            Payload::new(vec![], vec![], vec![]),
+            Payload::new(vec![], vec![], vec![], vec![]),
             vec![],
         )?;
         let guard = StorageGuard::new(&storable_in);
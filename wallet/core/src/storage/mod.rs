@@ -11,6 +11,7 @@ pub mod hint;
 pub mod id;
 pub mod interface;
 pub mod keydata;
+pub mod label;
 pub mod local;
 pub mod metadata;
 pub mod storable;
@@ -22,9 +23,11 @@ pub use binding::Binding;
 pub use hint::Hint;
 pub use id::IdT;
 pub use interface::{
-    AccountStore, Interface, PrvKeyDataStore, StorageDescriptor, TransactionRecordStore, WalletDescriptor, WalletExportOptions,
+    AccountStore, AddressBookStore, Interface, LabelStore, PrvKeyDataStore, StorageDescriptor, TransactionRecordStore,
+    WalletDescriptor, WalletExportOptions,
 };
 pub use keydata::{AssocPrvKeyDataIds, PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, PrvKeyDataMap, PrvKeyDataPayload};
+pub use label::{Label, LabelTarget};
 pub use local::interface::make_filename;
 pub use metadata::AccountMetadata;
 pub use storable::Storable;
@@ -151,6 +151,13 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn submit_transaction_replacement_call(
+        &self,
+        _request: SubmitTransactionReplacementRequest,
+    ) -> RpcResult<SubmitTransactionReplacementResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_block_call(&self, _request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         Err(RpcError::NotImplemented)
     }
@@ -220,6 +227,39 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn list_bans_call(&self, _request: ListBansRequest) -> RpcResult<ListBansResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn save_mempool_call(&self, _request: SaveMempoolRequest) -> RpcResult<SaveMempoolResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn load_mempool_call(&self, _request: LoadMempoolRequest) -> RpcResult<LoadMempoolResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_transactions_call(
+        &self,
+        _request: GetAddressTransactionsRequest,
+    ) -> RpcResult<GetAddressTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_transaction_count_call(
+        &self,
+        _request: GetAddressTransactionCountRequest,
+    ) -> RpcResult<GetAddressTransactionCountResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transaction_merkle_proof_call(
+        &self,
+        _request: GetTransactionMerkleProofRequest,
+    ) -> RpcResult<GetTransactionMerkleProofResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn estimate_network_hashes_per_second_call(
         &self,
         _request: EstimateNetworkHashesPerSecondRequest,
@@ -245,6 +285,18 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_transaction_call(&self, _request: GetTransactionRequest) -> RpcResult<GetTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_transactions_by_ids_call(&self, _request: GetTransactionsByIdsRequest) -> RpcResult<GetTransactionsByIdsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_fee_estimate_call(&self, _request: GetFeeEstimateRequest) -> RpcResult<GetFeeEstimateResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -294,6 +294,8 @@ struct Inner {
     final_transaction_payload: Vec<u8>,
     // final transaction payload mass
     final_transaction_payload_mass: u64,
+    // lock time of all generated transactions
+    lock_time: u64,
     // execution context
     context: Mutex<Context>,
 }
@@ -318,6 +320,7 @@ impl std::fmt::Debug for Inner {
             .field("final_transaction_outputs_compute_mass", &self.final_transaction_outputs_compute_mass)
             .field("final_transaction_payload", &self.final_transaction_payload)
             .field("final_transaction_payload_mass", &self.final_transaction_payload_mass)
+            .field("lock_time", &self.lock_time)
             // .field("context", &self.context)
             .finish()
     }
@@ -346,6 +349,7 @@ impl Generator {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context,
+            lock_time,
         } = settings;
 
         let network_type = NetworkType::from(network_id);
@@ -447,6 +451,7 @@ impl Generator {
             final_transaction_payload,
             final_transaction_payload_mass,
             destination_utxo_context,
+            lock_time,
         };
 
         Ok(Self { inner: Arc::new(inner) })
@@ -981,7 +986,7 @@ impl Generator {
                     0,
                     inputs,
                     final_outputs,
-                    0,
+                    self.inner.lock_time,
                     SUBNETWORK_ID_NATIVE,
                     0,
                     self.inner.final_transaction_payload.clone(),
@@ -1021,7 +1026,7 @@ impl Generator {
                 let output_value = aggregate_input_value - transaction_fees;
                 let script_public_key = pay_to_address_script(&self.inner.change_address);
                 let output = TransactionOutput::new(output_value, script_public_key.clone());
-                let tx = Transaction::new(0, inputs, vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+                let tx = Transaction::new(0, inputs, vec![output], self.inner.lock_time, SUBNETWORK_ID_NATIVE, 0, vec![]);
                 context.number_of_transactions += 1;
 
                 let utxo_entry_reference =
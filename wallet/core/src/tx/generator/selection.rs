@@ -0,0 +1,362 @@
+//!
+//! UTXO selection strategies and [`CoinControl`] deciding which UTXOs
+//! are consumed by the [`Generator`](crate::tx::Generator) and in which order.
+//!
+
+use crate::imports::*;
+use crate::result::Result;
+use crate::utxo::UtxoEntryReference;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+use rand::seq::SliceRandom;
+
+/// Value a UTXO selection must cover, as estimated before generating transactions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelectionTarget {
+    /// Value to cover besides the fees of the selected inputs: payment outputs, priority fee and base transaction fees.
+    pub value: u64,
+    /// Fees incurred by every selected input.
+    pub fee_per_input: u64,
+    /// Fees of a change output. A selection exceeding `value` by less than this amount
+    /// is not worth a change output.
+    pub change_cost: u64,
+}
+
+impl SelectionTarget {
+    pub fn new(value: u64, fee_per_input: u64, change_cost: u64) -> Self {
+        Self { value, fee_per_input, change_cost }
+    }
+
+    /// Value contributed by a UTXO once the fees of its own input are paid.
+    pub fn effective_value(&self, utxo: &UtxoEntryReference) -> u64 {
+        utxo.amount().saturating_sub(self.fee_per_input)
+    }
+}
+
+/// A UTXO selection strategy, ordering the UTXOs polled by the [`Generator`](crate::tx::Generator).
+/// The generator stops polling UTXOs as soon as the transaction value is covered, so the leading
+/// UTXOs are the ones that end up being spent.
+pub trait UtxoSelectionStrategy: Send + Sync {
+    /// Returns `utxos` in the order they should be consumed in. `target` is [`None`]
+    /// for sweep transactions, which consume all UTXOs.
+    fn select(&self, utxos: Vec<UtxoEntryReference>, target: Option<&SelectionTarget>) -> Vec<UtxoEntryReference>;
+}
+
+/// Spends the largest UTXOs first, minimizing the number of inputs and therefore the fees.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LargestFirst;
+
+impl UtxoSelectionStrategy for LargestFirst {
+    fn select(&self, mut utxos: Vec<UtxoEntryReference>, _target: Option<&SelectionTarget>) -> Vec<UtxoEntryReference> {
+        utxos.sort_by_key(|utxo| std::cmp::Reverse(utxo.amount()));
+        utxos
+    }
+}
+
+/// Spends the smallest UTXOs first, consolidating dust at the cost of higher fees.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SmallestFirst;
+
+impl UtxoSelectionStrategy for SmallestFirst {
+    fn select(&self, mut utxos: Vec<UtxoEntryReference>, _target: Option<&SelectionTarget>) -> Vec<UtxoEntryReference> {
+        utxos.sort_by_key(|utxo| utxo.amount());
+        utxos
+    }
+}
+
+/// Searches for a set of UTXOs matching the target closely enough for the transaction
+/// not to need a change output, spending it first. Falls back to [`LargestFirst`] if
+/// no such set is found within `max_tries` search steps.
+#[derive(Clone, Copy, Debug)]
+pub struct BranchAndBound {
+    pub max_tries: usize,
+}
+
+impl BranchAndBound {
+    pub const DEFAULT_MAX_TRIES: usize = 100_000;
+
+    pub fn new(max_tries: usize) -> Self {
+        Self { max_tries }
+    }
+
+    /// Depth-first search over the inclusion of each UTXO, largest first, returning
+    /// the indexes of the matching set with the lowest excess.
+    fn search(&self, utxos: &[UtxoEntryReference], target: &SelectionTarget) -> Option<Vec<usize>> {
+        let values = utxos.iter().map(|utxo| target.effective_value(utxo)).take_while(|value| *value > 0).collect::<Vec<_>>();
+        let mut remaining = vec![0u64; values.len() + 1];
+        for index in (0..values.len()).rev() {
+            remaining[index] = remaining[index + 1] + values[index];
+        }
+        let upper_bound = target.value + target.change_cost;
+
+        let mut best: Option<(u64, Vec<usize>)> = None;
+        let mut selection = vec![];
+        let mut value = 0;
+        let mut index = 0;
+        for _ in 0..self.max_tries {
+            let backtrack = if value > upper_bound || value + remaining[index] < target.value {
+                true
+            } else if value >= target.value {
+                let excess = value - target.value;
+                if best.as_ref().map_or(true, |(best_excess, _)| excess < *best_excess) {
+                    best = Some((excess, selection.clone()));
+                }
+                true
+            } else {
+                false
+            };
+
+            if backtrack {
+                if matches!(best, Some((0, _))) {
+                    break;
+                }
+                // Exclude the last included UTXO and explore the following ones instead
+                let Some(last) = selection.pop() else {
+                    break;
+                };
+                value -= values[last];
+                index = last + 1;
+            } else {
+                selection.push(index);
+                value += values[index];
+                index += 1;
+            }
+        }
+
+        best.map(|(_, selection)| selection)
+    }
+}
+
+impl Default for BranchAndBound {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_MAX_TRIES)
+    }
+}
+
+impl UtxoSelectionStrategy for BranchAndBound {
+    fn select(&self, utxos: Vec<UtxoEntryReference>, target: Option<&SelectionTarget>) -> Vec<UtxoEntryReference> {
+        let utxos = LargestFirst.select(utxos, target);
+        let Some(selection) = target.and_then(|target| self.search(&utxos, target)) else {
+            return utxos;
+        };
+
+        // The matching set is followed by the remaining UTXOs, which are only consumed
+        // if the fees turn out to exceed the estimate
+        let selection = selection.into_iter().collect::<HashSet<_>>();
+        let (mut selected, rest): (Vec<_>, Vec<_>) = utxos.into_iter().enumerate().partition(|(index, _)| selection.contains(index));
+        selected.extend(rest);
+        selected.into_iter().map(|(_, utxo)| utxo).collect()
+    }
+}
+
+/// Spends UTXOs in a random order, avoiding selection patterns which could link
+/// the transaction to the wallet.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomSelection;
+
+impl UtxoSelectionStrategy for RandomSelection {
+    fn select(&self, mut utxos: Vec<UtxoEntryReference>, _target: Option<&SelectionTarget>) -> Vec<UtxoEntryReference> {
+        utxos.shuffle(&mut rand::thread_rng());
+        utxos
+    }
+}
+
+/// Built-in UTXO selection strategies.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UtxoSelection {
+    /// UTXOs are consumed in the order kept by the account UTXO context.
+    #[default]
+    Default,
+    LargestFirst,
+    SmallestFirst,
+    BranchAndBound,
+    Random,
+}
+
+impl UtxoSelectionStrategy for UtxoSelection {
+    fn select(&self, utxos: Vec<UtxoEntryReference>, target: Option<&SelectionTarget>) -> Vec<UtxoEntryReference> {
+        match self {
+            UtxoSelection::Default => utxos,
+            UtxoSelection::LargestFirst => LargestFirst.select(utxos, target),
+            UtxoSelection::SmallestFirst => SmallestFirst.select(utxos, target),
+            UtxoSelection::BranchAndBound => BranchAndBound::default().select(utxos, target),
+            UtxoSelection::Random => RandomSelection.select(utxos, target),
+        }
+    }
+}
+
+impl FromStr for UtxoSelection {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "default" => Ok(UtxoSelection::Default),
+            "largest" | "largest-first" => Ok(UtxoSelection::LargestFirst),
+            "smallest" | "smallest-first" => Ok(UtxoSelection::SmallestFirst),
+            "exact" | "branch-and-bound" => Ok(UtxoSelection::BranchAndBound),
+            "random" => Ok(UtxoSelection::Random),
+            _ => Err(Error::InvalidUtxoSelection(s.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for UtxoSelection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            UtxoSelection::Default => "default",
+            UtxoSelection::LargestFirst => "largest-first",
+            UtxoSelection::SmallestFirst => "smallest-first",
+            UtxoSelection::BranchAndBound => "branch-and-bound",
+            UtxoSelection::Random => "random",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Explicit control over the UTXOs spent by a transaction.
+///
+/// Frozen UTXOs and `exclude` UTXOs are never spent. UTXOs listed in `include` are consumed
+/// ahead of the UTXOs ordered by the `selection` strategy, frozen or not.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CoinControl {
+    pub selection: UtxoSelection,
+    pub include: Vec<TransactionOutpoint>,
+    pub exclude: Vec<TransactionOutpoint>,
+}
+
+impl CoinControl {
+    pub fn new(selection: UtxoSelection, include: Vec<TransactionOutpoint>, exclude: Vec<TransactionOutpoint>) -> Self {
+        Self { selection, include, exclude }
+    }
+
+    /// Applies the coin control to `utxos` using the built-in `selection` strategy.
+    pub fn select(
+        &self,
+        utxos: Vec<UtxoEntryReference>,
+        frozen: &HashSet<TransactionOutpoint>,
+        target: Option<&SelectionTarget>,
+    ) -> Result<Vec<UtxoEntryReference>> {
+        self.select_with_strategy(&self.selection, utxos, frozen, target)
+    }
+
+    /// Applies the coin control to `utxos`, ordering the UTXOs which are not explicitly
+    /// included with a custom `strategy`.
+    pub fn select_with_strategy(
+        &self,
+        strategy: &dyn UtxoSelectionStrategy,
+        utxos: Vec<UtxoEntryReference>,
+        frozen: &HashSet<TransactionOutpoint>,
+        target: Option<&SelectionTarget>,
+    ) -> Result<Vec<UtxoEntryReference>> {
+        let exclude = self.exclude.iter().collect::<HashSet<_>>();
+        if let Some(outpoint) = self.include.iter().find(|outpoint| exclude.contains(outpoint)) {
+            return Err(Error::CoinControlConflict(*outpoint));
+        }
+
+        let mut include = vec![];
+        for outpoint in self.include.iter() {
+            if !include.contains(outpoint) {
+                include.push(*outpoint);
+            }
+        }
+        let positions = include.iter().enumerate().map(|(position, outpoint)| (*outpoint, position)).collect::<HashMap<_, _>>();
+
+        let mut included = vec![None; include.len()];
+        let mut candidates = vec![];
+        for utxo in utxos {
+            let outpoint = outpoint_of(&utxo);
+            if let Some(position) = positions.get(&outpoint) {
+                included[*position] = Some(utxo);
+            } else if !exclude.contains(&outpoint) && !frozen.contains(&outpoint) {
+                candidates.push(utxo);
+            }
+        }
+        let included = include
+            .iter()
+            .zip(included)
+            .map(|(outpoint, utxo)| utxo.ok_or(Error::CoinControlUtxoNotFound(*outpoint)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // The strategy only has to cover what is left once the included UTXOs are spent
+        let target = target.map(|target| SelectionTarget {
+            value: target.value.saturating_sub(included.iter().map(|utxo| target.effective_value(utxo)).sum()),
+            ..*target
+        });
+        let mut selected = included;
+        selected.extend(strategy.select(candidates, target.as_ref()));
+        Ok(selected)
+    }
+}
+
+/// Returns the consensus outpoint of a UTXO entry.
+pub(crate) fn outpoint_of(utxo: &UtxoEntryReference) -> TransactionOutpoint {
+    let id = utxo.id_as_ref();
+    TransactionOutpoint::new(id.transaction_id, id.index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utxos(amounts: &[u64]) -> Vec<UtxoEntryReference> {
+        amounts.iter().map(|amount| UtxoEntryReference::simulated(*amount)).collect()
+    }
+
+    fn amounts(utxos: &[UtxoEntryReference]) -> Vec<u64> {
+        utxos.iter().map(|utxo| utxo.amount()).collect()
+    }
+
+    #[test]
+    fn test_utxo_selection_ordering() {
+        let entries = utxos(&[300, 100, 500, 200]);
+        assert_eq!(amounts(&LargestFirst.select(entries.clone(), None)), [500, 300, 200, 100]);
+        assert_eq!(amounts(&SmallestFirst.select(entries.clone(), None)), [100, 200, 300, 500]);
+        assert_eq!(amounts(&UtxoSelection::Default.select(entries.clone(), None)), [300, 100, 500, 200]);
+
+        let mut shuffled = amounts(&RandomSelection.select(entries, None));
+        shuffled.sort();
+        assert_eq!(shuffled, [100, 200, 300, 500]);
+    }
+
+    #[test]
+    fn test_utxo_selection_branch_and_bound() {
+        let entries = utxos(&[1000, 700, 450, 300, 60]);
+
+        // 700 + 300 match exactly once the input fees are paid, while largest first would create change
+        let target = SelectionTarget::new(980, 10, 5);
+        assert_eq!(amounts(&BranchAndBound::default().select(entries.clone(), Some(&target))), [700, 300, 1000, 450, 60]);
+
+        // 450 + 60 exceed the target by less than the change cost
+        let target = SelectionTarget::new(485, 10, 20);
+        assert_eq!(amounts(&BranchAndBound::default().select(entries.clone(), Some(&target))), [450, 60, 1000, 700, 300]);
+
+        // Without a match the UTXOs are spent largest first
+        let target = SelectionTarget::new(2700, 10, 5);
+        assert_eq!(amounts(&BranchAndBound::default().select(entries, Some(&target))), [1000, 700, 450, 300, 60]);
+    }
+
+    #[test]
+    fn test_coin_control() -> Result<()> {
+        let entries = utxos(&[100, 200, 300, 400]);
+        let outpoints = entries.iter().map(outpoint_of).collect::<Vec<_>>();
+        let frozen = HashSet::from([outpoints[3], outpoints[2]]);
+
+        // Frozen UTXOs are skipped
+        let selected = CoinControl::default().select(entries.clone(), &frozen, None)?;
+        assert_eq!(amounts(&selected), [100, 200]);
+
+        // Included UTXOs are spent first even if frozen, excluded UTXOs are skipped
+        let coin_control = CoinControl::new(UtxoSelection::LargestFirst, vec![outpoints[2]], vec![outpoints[1]]);
+        let selected = coin_control.select(entries.clone(), &frozen, None)?;
+        assert_eq!(amounts(&selected), [300, 100]);
+
+        let coin_control = CoinControl::new(UtxoSelection::Default, vec![outpoints[0]], vec![outpoints[0]]);
+        assert!(matches!(coin_control.select(entries.clone(), &frozen, None), Err(Error::CoinControlConflict(_))));
+
+        let unknown = TransactionOutpoint::new(TransactionId::from_u64_word(1), 0);
+        let coin_control = CoinControl::new(UtxoSelection::Default, vec![unknown], vec![]);
+        assert!(matches!(coin_control.select(entries, &frozen, None), Err(Error::CoinControlUtxoNotFound(_))));
+
+        Ok(())
+    }
+}
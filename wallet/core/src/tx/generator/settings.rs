@@ -6,9 +6,11 @@
 use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
-use crate::tx::{Fees, PaymentDestination};
-use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
+use crate::tx::{CoinControl, Fees, LockTime, MassCalculator, PaymentDestination, SelectionTarget};
+use crate::utxo::{NetworkParams, UtxoContext, UtxoEntryReference, UtxoIterator};
 use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::{TransactionInput, TransactionOutpoint, TransactionOutput};
+use kaspa_txscript::pay_to_address_script;
 use workflow_core::channel::Multiplexer;
 
 pub struct GeneratorSettings {
@@ -34,6 +36,8 @@ pub struct GeneratorSettings {
     pub final_transaction_payload: Option<Vec<u8>>,
     // transaction is a transfer between accounts
     pub destination_utxo_context: Option<UtxoContext>,
+    // lock time applied to all generated transactions (0 if not locked)
+    pub lock_time: u64,
 }
 
 // impl std::fmt::Debug for GeneratorSettings {
@@ -60,6 +64,24 @@ impl GeneratorSettings {
         final_transaction_destination: PaymentDestination,
         final_priority_fee: Fees,
         final_transaction_payload: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        Self::try_new_with_coin_control(
+            account,
+            final_transaction_destination,
+            final_priority_fee,
+            final_transaction_payload,
+            &CoinControl::default(),
+        )
+    }
+
+    /// Creates settings spending the account UTXOs selected by `coin_control`,
+    /// never spending the account frozen UTXOs unless explicitly included.
+    pub fn try_new_with_coin_control(
+        account: Arc<dyn Account>,
+        final_transaction_destination: PaymentDestination,
+        final_priority_fee: Fees,
+        final_transaction_payload: Option<Vec<u8>>,
+        coin_control: &CoinControl,
     ) -> Result<Self> {
         let network_id = account.utxo_context().processor().network_id()?;
         let change_address = account.change_address()?;
@@ -67,7 +89,17 @@ impl GeneratorSettings {
         let sig_op_count = account.sig_op_count();
         let minimum_signatures = account.minimum_signatures();
 
-        let utxo_iterator = UtxoIterator::new(account.utxo_context());
+        let target = Self::selection_target(
+            network_id,
+            &change_address,
+            sig_op_count,
+            minimum_signatures,
+            &final_transaction_destination,
+            &final_priority_fee,
+            final_transaction_payload.as_ref(),
+        );
+        let utxos = account.utxo_context().context().mature.clone();
+        let utxo_iterator = coin_control.select(utxos, &account.frozen_utxos(), target.as_ref())?.into_iter();
 
         let settings = GeneratorSettings {
             network_id,
@@ -82,11 +114,39 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            lock_time: 0,
         };
 
         Ok(settings)
     }
 
+    /// Creates settings claiming time-locked UTXOs of the account into its change address.
+    /// The redeem script pushed along with each input signature is accounted for as an
+    /// additional signature.
+    pub fn try_new_with_timelocked(account: Arc<dyn Account>, utxos: Vec<UtxoEntryReference>, lock_time: LockTime) -> Result<Self> {
+        let network_id = account.utxo_context().processor().network_id()?;
+        let change_address = account.change_address()?;
+        let multiplexer = account.wallet().multiplexer().clone();
+
+        let settings = GeneratorSettings {
+            network_id,
+            multiplexer: Some(multiplexer),
+            sig_op_count: 1,
+            minimum_signatures: 2,
+            change_address,
+            utxo_iterator: Box::new(utxos.into_iter()),
+            source_utxo_context: Some(account.utxo_context().clone()),
+
+            final_transaction_priority_fee: Fees::None,
+            final_transaction_destination: PaymentDestination::Change,
+            final_transaction_payload: None,
+            destination_utxo_context: None,
+            lock_time: 0,
+        };
+
+        Ok(settings.lock_time(lock_time))
+    }
+
     pub fn try_new_with_context(
         utxo_context: UtxoContext,
         change_address: Address,
@@ -113,6 +173,7 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            lock_time: 0,
         };
 
         Ok(settings)
@@ -142,13 +203,60 @@ impl GeneratorSettings {
             final_transaction_destination,
             final_transaction_payload,
             destination_utxo_context: None,
+            lock_time: 0,
         };
 
         Ok(settings)
     }
 
+    /// Estimates the value the UTXO selection must cover, [`None`] for sweep transactions.
+    /// Storage mass is not accounted for, the generator consuming further UTXOs if needed.
+    fn selection_target(
+        network_id: NetworkId,
+        change_address: &Address,
+        sig_op_count: u8,
+        minimum_signatures: u16,
+        final_transaction_destination: &PaymentDestination,
+        final_priority_fee: &Fees,
+        final_transaction_payload: Option<&Vec<u8>>,
+    ) -> Option<SelectionTarget> {
+        let PaymentDestination::PaymentOutputs(outputs) = final_transaction_destination else {
+            return None;
+        };
+        let value = outputs.amount() + final_priority_fee.additional();
+        if !final_priority_fee.sender_pays() {
+            // Fees are deducted from the payment output
+            return Some(SelectionTarget::new(value, 0, 0));
+        }
+
+        let calc = MassCalculator::new(&network_id.into(), &NetworkParams::from(network_id));
+        let outputs = outputs
+            .iter()
+            .map(|output| TransactionOutput::new(output.amount, pay_to_address_script(&output.address)))
+            .collect::<Vec<_>>();
+        let base_mass = calc.blank_transaction_mass()
+            + calc.calc_mass_for_outputs(&outputs)
+            + calc.calc_mass_for_payload(final_transaction_payload.map_or(0, |payload| payload.len()));
+        let input = TransactionInput::new(TransactionOutpoint::new(Default::default(), 0), vec![], 0, sig_op_count);
+        let input_mass = calc.calc_mass_for_input(&input) + calc.calc_signature_mass(minimum_signatures);
+        let change_mass = calc.calc_mass_for_output(&TransactionOutput::new(0, pay_to_address_script(change_address)));
+
+        Some(SelectionTarget::new(
+            value + calc.calc_minimum_transaction_fee_from_mass(base_mass),
+            calc.calc_minimum_transaction_fee_from_mass(input_mass),
+            calc.calc_minimum_transaction_fee_from_mass(change_mass),
+        ))
+    }
+
     pub fn utxo_context_transfer(mut self, destination_utxo_context: &UtxoContext) -> Self {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Locks generated transactions until the given DAA score or timestamp.
+    /// Such transactions are rejected by the network until the lock time is reached.
+    pub fn lock_time(mut self, lock_time: LockTime) -> Self {
+        self.lock_time = lock_time.value();
+        self
+    }
 }
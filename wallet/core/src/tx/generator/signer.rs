@@ -3,8 +3,12 @@
 //!
 
 use crate::imports::*;
+use crate::tx::{sign_timelocked_inputs, TimeLock};
 use kaspa_bip32::PrivateKey;
-use kaspa_consensus_core::{sign::sign_with_multiple_v2, tx::SignableTransaction};
+use kaspa_consensus_core::{
+    sign::{sign_with_multiple_v2, Error as SignError},
+    tx::SignableTransaction,
+};
 
 pub trait SignerT: Send + Sync + 'static {
     fn try_sign(&self, transaction: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction>;
@@ -15,6 +19,8 @@ struct Inner {
     account: Arc<dyn Account>,
     payment_secret: Option<Secret>,
     keys: Mutex<AHashMap<Address, [u8; 32]>>,
+    /// Time locks by their P2SH address
+    timelocks: AHashMap<Address, TimeLock>,
 }
 
 pub struct Signer {
@@ -23,7 +29,20 @@ pub struct Signer {
 
 impl Signer {
     pub fn new(account: Arc<dyn Account>, keydata: PrvKeyData, payment_secret: Option<Secret>) -> Self {
-        Self { inner: Arc::new(Inner { keydata, account, payment_secret, keys: Mutex::new(AHashMap::new()) }) }
+        Self::try_new_with_timelocks(account, keydata, payment_secret, &[]).expect("signer without time locks")
+    }
+
+    /// Creates a signer also capable of spending the given time-locked outputs
+    /// owned by the account.
+    pub fn try_new_with_timelocks(
+        account: Arc<dyn Account>,
+        keydata: PrvKeyData,
+        payment_secret: Option<Secret>,
+        timelocks: &[TimeLock],
+    ) -> Result<Self> {
+        let timelocks =
+            timelocks.iter().map(|timelock| Ok((timelock.address()?, timelock.clone()))).collect::<Result<AHashMap<_, _>>>()?;
+        Ok(Self { inner: Arc::new(Inner { keydata, account, payment_secret, keys: Mutex::new(AHashMap::new()), timelocks }) })
     }
 
     fn ingest(&self, addresses: &[Address]) -> Result<()> {
@@ -45,12 +64,36 @@ impl Signer {
 
 impl SignerT for Signer {
     fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
-        self.ingest(addresses)?;
+        let (timelocked, addresses): (Vec<_>, Vec<_>) =
+            addresses.iter().cloned().partition(|address| self.inner.timelocks.contains_key(address));
+        if timelocked.is_empty() {
+            self.ingest(&addresses)?;
+        } else {
+            let owners = timelocked.iter().map(|address| self.inner.timelocks[address].owner.clone());
+            self.ingest(&addresses.iter().cloned().chain(owners).collect::<Vec<_>>())?;
+        }
 
         let keys = self.inner.keys.lock().unwrap();
         let mut keys_for_signing = addresses.iter().map(|address| *keys.get(address).unwrap()).collect::<Vec<_>>();
         // TODO - refactor for multisig
-        let signable_tx = sign_with_multiple_v2(mutable_tx, &keys_for_signing).fully_signed()?;
+        let signable_tx = if timelocked.is_empty() {
+            sign_with_multiple_v2(mutable_tx, &keys_for_signing).fully_signed()?
+        } else {
+            let mut timelocks = timelocked
+                .iter()
+                .map(|address| {
+                    let timelock = &self.inner.timelocks[address];
+                    (timelock.clone(), *keys.get(&timelock.owner).unwrap())
+                })
+                .collect::<Vec<_>>();
+            let signable_tx = sign_timelocked_inputs(sign_with_multiple_v2(mutable_tx, &keys_for_signing).unwrap(), &timelocks);
+            timelocks.iter_mut().for_each(|(_, key)| key.zeroize());
+            let signable_tx = signable_tx?;
+            if signable_tx.tx.inputs.iter().any(|input| input.signature_script.is_empty()) {
+                return Err(SignError::PartiallySigned.into());
+            }
+            signable_tx
+        };
         keys_for_signing.zeroize();
         Ok(signable_tx)
     }
@@ -410,6 +410,7 @@ where
         final_transaction_priority_fee: final_priority_fee,
         final_transaction_destination,
         final_transaction_payload,
+        lock_time: 0,
     };
 
     Generator::try_new(settings, None, None)
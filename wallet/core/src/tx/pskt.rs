@@ -0,0 +1,494 @@
+//!
+//! Partially signed transactions (PSKT) used for multi-party signing.
+//!
+//! A [`Pskt`] carries an unsigned transaction along with everything the
+//! participants need in order to sign it independently: the UTXO entries
+//! being spent, the sighash type and redeem script of each input and the
+//! signatures collected so far. The container moves through the following roles:
+//!
+//! - **Creator** builds the container from an unsigned transaction ([`Pskt::new`]).
+//! - **Updater** attaches UTXO entries, sighash types and redeem scripts.
+//! - **Signer** adds signatures for the keys it holds ([`Pskt::sign`]).
+//! - **Combiner** merges the signatures of several copies ([`Pskt::combine`]).
+//! - **Finalizer** builds the signature scripts ([`Pskt::finalize`]).
+//! - **Extractor** produces the final transaction ([`Pskt::extract`]).
+//!
+
+use crate::imports::*;
+use crate::tx::PendingTransaction;
+use kaspa_consensus_core::hashing::sighash::{calc_ecdsa_signature_hash, calc_schnorr_signature_hash, SigHashReusedValues};
+use kaspa_consensus_core::hashing::sighash_type::{SigHashType, SIG_HASH_ALL};
+use kaspa_consensus_core::tx::{MutableTransaction, PopulatedTransaction, Transaction, UtxoEntry};
+use kaspa_txscript::opcodes::codes::{Op16, OpCheckMultiSig, OpCheckMultiSigECDSA, OpData32, OpData33, OpTrue};
+use kaspa_txscript::script_builder::ScriptBuilder;
+use kaspa_txscript::script_class::ScriptClass;
+use kaspa_txscript::{get_sig_op_count, pay_to_script_hash_script, pay_to_script_hash_signature_script};
+use kaspa_utils::serde_bytes;
+
+/// Prefix of the serialized PSKT string form.
+pub const PSKT_PREFIX: &str = "PSKT";
+/// Version of the PSKT container format.
+pub const PSKT_VERSION: u16 = 0;
+
+/// A signature collected for a PSKT input. The signature
+/// is stored with its trailing sighash type byte.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PsktSignature {
+    #[serde(with = "serde_bytes")]
+    pub public_key: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
+
+/// Per-input data of a [`Pskt`]. An empty redeem script or signature script means that it is not set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PsktInput {
+    pub utxo_entry: Option<UtxoEntry>,
+    pub sighash_type: u8,
+    #[serde(default, with = "serde_bytes", skip_serializing_if = "Vec::is_empty")]
+    pub redeem_script: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatures: Vec<PsktSignature>,
+    #[serde(default, with = "serde_bytes", skip_serializing_if = "Vec::is_empty")]
+    pub signature_script: Vec<u8>,
+}
+
+impl Default for PsktInput {
+    fn default() -> Self {
+        Self {
+            utxo_entry: None,
+            sighash_type: SIG_HASH_ALL.to_u8(),
+            redeem_script: vec![],
+            signatures: vec![],
+            signature_script: vec![],
+        }
+    }
+}
+
+impl PsktInput {
+    pub fn is_finalized(&self) -> bool {
+        !self.signature_script.is_empty()
+    }
+}
+
+/// Partially signed transaction container. See the [module](self) documentation for details.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pskt {
+    pub version: u16,
+    pub transaction: Transaction,
+    pub inputs: Vec<PsktInput>,
+}
+
+/// The keys allowed to sign an input, as found in its script public key or redeem script
+struct InputKeys {
+    public_keys: Vec<Vec<u8>>,
+    required: usize,
+    ecdsa: bool,
+    multisig: bool,
+}
+
+impl Pskt {
+    // Creator
+
+    /// Creates a PSKT from an unsigned transaction. Any signature scripts
+    /// present in the transaction are discarded.
+    pub fn new(mut transaction: Transaction) -> Self {
+        transaction.inputs.iter_mut().for_each(|input| input.signature_script.clear());
+        transaction.finalize();
+        let inputs = vec![PsktInput::default(); transaction.inputs.len()];
+        Self { version: PSKT_VERSION, transaction, inputs }
+    }
+
+    /// Creates a PSKT from an unsigned transaction and the UTXO entries it spends.
+    pub fn with_entries(transaction: Transaction, entries: Vec<UtxoEntry>) -> Result<Self> {
+        let mut pskt = Self::new(transaction);
+        if entries.len() != pskt.inputs.len() {
+            return Err(Error::InvalidPskt(format!("expected {} UTXO entries, got {}", pskt.inputs.len(), entries.len())));
+        }
+        for (input, entry) in pskt.inputs.iter_mut().zip(entries) {
+            input.utxo_entry = Some(entry);
+        }
+        Ok(pskt)
+    }
+
+    pub fn id(&self) -> TransactionId {
+        self.transaction.id()
+    }
+
+    // Updater
+
+    pub fn set_utxo_entry(&mut self, index: usize, entry: UtxoEntry) -> Result<()> {
+        self.input_for_update(index)?.utxo_entry = Some(entry);
+        Ok(())
+    }
+
+    pub fn set_sighash_type(&mut self, index: usize, sighash_type: SigHashType) -> Result<()> {
+        self.input_for_update(index)?.sighash_type = sighash_type.to_u8();
+        Ok(())
+    }
+
+    /// Attaches the redeem script of a pay-to-script-hash input and sets the
+    /// input signature operation count accordingly.
+    pub fn set_redeem_script(&mut self, index: usize, redeem_script: Vec<u8>) -> Result<()> {
+        let script_public_key = pay_to_script_hash_script(&redeem_script);
+        let input = self.input_for_update(index)?;
+        if input.utxo_entry.as_ref().is_some_and(|entry| entry.script_public_key != script_public_key) {
+            return Err(Error::InvalidPskt(format!("redeem script does not match the script public key of input {index}")));
+        }
+        input.redeem_script = redeem_script.clone();
+
+        let signature_script = pay_to_script_hash_signature_script(redeem_script, vec![])?;
+        let sig_op_count = get_sig_op_count::<PopulatedTransaction>(&signature_script, &script_public_key);
+        self.transaction.inputs[index].sig_op_count = sig_op_count as u8;
+        self.transaction.finalize();
+        Ok(())
+    }
+
+    fn input_for_update(&mut self, index: usize) -> Result<&mut PsktInput> {
+        if self.inputs.iter().any(|input| !input.signatures.is_empty() || input.is_finalized()) {
+            return Err(Error::InvalidPskt("inputs can not be updated once signing has started".to_string()));
+        }
+        self.inputs.get_mut(index).ok_or_else(|| Error::InvalidPskt(format!("input index {index} is out of range")))
+    }
+
+    // Signer
+
+    /// Signs every input which can be spent by one of the given private keys
+    /// and returns the number of signatures added.
+    pub fn sign(&mut self, private_keys: &[[u8; 32]]) -> Result<usize> {
+        self.validate()?;
+        let keypairs = private_keys
+            .iter()
+            .map(|key| secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, key))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let tx = MutableTransaction::with_entries(self.transaction.clone(), self.utxo_entries()?);
+        let mut reused_values = SigHashReusedValues::new();
+
+        let mut count = 0;
+        for (index, input) in self.inputs.iter_mut().enumerate() {
+            if input.is_finalized() {
+                continue;
+            }
+            let Some(input_keys) = Self::input_keys(index, input)? else {
+                continue;
+            };
+            let sighash_type = SigHashType::from_u8(input.sighash_type).map_err(|err| Error::InvalidPskt(err.to_string()))?;
+
+            for keypair in keypairs.iter() {
+                let public_key = if input_keys.ecdsa {
+                    keypair.public_key().serialize().to_vec()
+                } else {
+                    keypair.x_only_public_key().0.serialize().to_vec()
+                };
+                if !input_keys.public_keys.contains(&public_key) || input.signatures.iter().any(|sig| sig.public_key == public_key) {
+                    continue;
+                }
+
+                let signature = if input_keys.ecdsa {
+                    let sig_hash = calc_ecdsa_signature_hash(&tx.as_verifiable(), index, sighash_type, &mut reused_values);
+                    let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice())?;
+                    keypair.secret_key().sign_ecdsa(msg).serialize_compact()
+                } else {
+                    let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), index, sighash_type, &mut reused_values);
+                    let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice())?;
+                    *keypair.sign_schnorr(msg).as_ref()
+                };
+                let signature = signature.into_iter().chain([sighash_type.to_u8()]).collect();
+                input.signatures.push(PsktSignature { public_key, signature });
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
+
+    // Combiner
+
+    /// Merges the signatures and input data of another copy of the same PSKT.
+    pub fn combine(mut self, other: Pskt) -> Result<Pskt> {
+        if self.version != other.version || self.transaction != other.transaction || self.inputs.len() != other.inputs.len() {
+            return Err(Error::PsktMismatch);
+        }
+
+        for (input, other) in self.inputs.iter_mut().zip(other.inputs) {
+            if input.sighash_type != other.sighash_type {
+                return Err(Error::PsktMismatch);
+            }
+            match (&input.utxo_entry, other.utxo_entry) {
+                (Some(entry), Some(other)) if *entry != other => return Err(Error::PsktMismatch),
+                (None, entry) => input.utxo_entry = entry,
+                _ => {}
+            }
+            if input.redeem_script.is_empty() {
+                input.redeem_script = other.redeem_script;
+            } else if !other.redeem_script.is_empty() && input.redeem_script != other.redeem_script {
+                return Err(Error::PsktMismatch);
+            }
+            for signature in other.signatures {
+                if !input.signatures.iter().any(|sig| sig.public_key == signature.public_key) {
+                    input.signatures.push(signature);
+                }
+            }
+            if !input.is_finalized() {
+                input.signature_script = other.signature_script;
+            }
+        }
+
+        Ok(self)
+    }
+
+    // Finalizer
+
+    /// Builds the signature script of every input from the collected signatures.
+    /// Fails if any input does not have enough signatures.
+    pub fn finalize(&mut self) -> Result<()> {
+        self.validate()?;
+        for (index, input) in self.inputs.iter_mut().enumerate() {
+            if input.is_finalized() {
+                continue;
+            }
+            let input_keys = Self::input_keys(index, input)?
+                .ok_or_else(|| Error::InvalidPskt(format!("input {index} has an unsupported script public key")))?;
+
+            // signatures must appear in the order of the keys they belong to
+            let signatures = input_keys
+                .public_keys
+                .iter()
+                .filter_map(|public_key| input.signatures.iter().find(|sig| &sig.public_key == public_key))
+                .take(input_keys.required)
+                .collect::<Vec<_>>();
+            if signatures.len() < input_keys.required {
+                return Err(Error::PsktInsufficientSignatures(index, signatures.len(), input_keys.required));
+            }
+
+            let mut builder = ScriptBuilder::new();
+            for signature in signatures {
+                builder.add_data(&signature.signature)?;
+            }
+            if input_keys.multisig {
+                builder.add_data(&input.redeem_script)?;
+            }
+            input.signature_script = builder.drain();
+            input.signatures.clear();
+        }
+        Ok(())
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.inputs.iter().all(PsktInput::is_finalized)
+    }
+
+    // Extractor
+
+    /// Returns the fully signed transaction. All inputs must be finalized.
+    pub fn extract(&self) -> Result<Transaction> {
+        if let Some(index) = self.inputs.iter().position(|input| !input.is_finalized()) {
+            return Err(Error::PsktNotFinalized(index));
+        }
+        self.partial_transaction()
+    }
+
+    /// Returns the transaction with the signature scripts of the finalized inputs, leaving
+    /// the signature scripts of the other inputs empty. Meant for inspecting a PSKT that
+    /// can not be finalized, use [`Pskt::extract`] to get a transaction for submission.
+    pub fn partial_transaction(&self) -> Result<Transaction> {
+        self.validate()?;
+        let mut transaction = self.transaction.clone();
+        for (tx_input, input) in transaction.inputs.iter_mut().zip(self.inputs.iter()) {
+            tx_input.signature_script = input.signature_script.clone();
+        }
+        transaction.finalize();
+        Ok(transaction)
+    }
+
+    /// Returns the UTXO entries spent by the transaction. All entries must be present.
+    pub fn utxo_entries(&self) -> Result<Vec<UtxoEntry>> {
+        self.inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| input.utxo_entry.clone().ok_or(Error::PsktMissingUtxoEntry(index)))
+            .collect()
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.version != PSKT_VERSION {
+            return Err(Error::InvalidPskt(format!("unsupported version {}", self.version)));
+        }
+        if self.inputs.len() != self.transaction.inputs.len() {
+            return Err(Error::InvalidPskt("input count does not match the transaction".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Returns the keys able to sign the input, or `None` if the input can not be signed
+    /// by this implementation (unknown script or missing redeem script).
+    fn input_keys(index: usize, input: &PsktInput) -> Result<Option<InputKeys>> {
+        let entry = input.utxo_entry.as_ref().ok_or(Error::PsktMissingUtxoEntry(index))?;
+        let script = entry.script_public_key.script();
+        let keys = match ScriptClass::from_script(&entry.script_public_key) {
+            ScriptClass::PubKey => InputKeys { public_keys: vec![script[1..33].to_vec()], required: 1, ecdsa: false, multisig: false },
+            ScriptClass::PubKeyECDSA => {
+                InputKeys { public_keys: vec![script[1..34].to_vec()], required: 1, ecdsa: true, multisig: false }
+            }
+            ScriptClass::ScriptHash if input.redeem_script.is_empty() => return Ok(None),
+            ScriptClass::ScriptHash => parse_multisig_redeem_script(&input.redeem_script)
+                .ok_or_else(|| Error::InvalidPskt(format!("input {index} has an unsupported redeem script")))?,
+            ScriptClass::NonStandard => return Ok(None),
+        };
+        Ok(Some(keys))
+    }
+}
+
+/// Parses a redeem script created by
+/// [`multisig_redeem_script`](kaspa_txscript::multisig_redeem_script) or its ECDSA counterpart.
+fn parse_multisig_redeem_script(script: &[u8]) -> Option<InputKeys> {
+    let small_int = |op: u8| (OpTrue..=Op16).contains(&op).then(|| (op - OpTrue + 1) as usize);
+
+    let (&first, mut rest) = script.split_first()?;
+    let required = small_int(first)?;
+    let mut public_keys = vec![];
+    let mut key_op = None;
+    while let Some((&op, tail)) = rest.split_first() {
+        if op != OpData32 && op != OpData33 {
+            break;
+        }
+        if key_op.is_some_and(|key_op| key_op != op) || tail.len() < op as usize {
+            return None;
+        }
+        key_op = Some(op);
+        public_keys.push(tail[..op as usize].to_vec());
+        rest = &tail[op as usize..];
+    }
+
+    let &[count, checksig] = rest else {
+        return None;
+    };
+    let ecdsa = key_op? == OpData33;
+    let expected_checksig = if ecdsa { OpCheckMultiSigECDSA } else { OpCheckMultiSig };
+    if checksig != expected_checksig || small_int(count) != Some(public_keys.len()) {
+        return None;
+    }
+    (required <= public_keys.len()).then_some(InputKeys { public_keys, required, ecdsa, multisig: true })
+}
+
+impl TryFrom<&PendingTransaction> for Pskt {
+    type Error = Error;
+    fn try_from(pending: &PendingTransaction) -> Result<Self> {
+        let signable = pending.signable_transaction();
+        let entries = signable.entries.into_iter().enumerate().map(|(index, entry)| entry.ok_or(Error::PsktMissingUtxoEntry(index)));
+        Pskt::with_entries(signable.tx, entries.collect::<Result<Vec<_>>>()?)
+    }
+}
+
+impl std::fmt::Display for Pskt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| std::fmt::Error)?;
+        write!(f, "{PSKT_PREFIX}{}", json.as_bytes().to_hex())
+    }
+}
+
+impl FromStr for Pskt {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let hex = s.trim().strip_prefix(PSKT_PREFIX).ok_or_else(|| Error::InvalidPskt(format!("missing '{PSKT_PREFIX}' prefix")))?;
+        let json = Vec::<u8>::from_hex(hex).map_err(|err| Error::InvalidPskt(err.to_string()))?;
+        let pskt: Pskt = serde_json::from_slice(&json)?;
+        pskt.validate()?;
+        Ok(pskt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutpoint, TransactionOutput, VerifiableTransaction},
+    };
+    use kaspa_txscript::{caches::Cache, multisig_redeem_script, multisig_redeem_script_ecdsa, TxScriptEngine};
+
+    fn keys() -> Vec<secp256k1::Keypair> {
+        (1..=3u8).map(|i| secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &[i; 32]).unwrap()).collect()
+    }
+
+    fn pskt(script_public_keys: Vec<ScriptPublicKey>) -> Pskt {
+        let inputs = (0..script_public_keys.len())
+            .map(|index| TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(7), index as u32), vec![], 0, 1))
+            .collect();
+        let outputs = vec![TransactionOutput::new(5000, ScriptPublicKey::from_vec(0, vec![0x51]))];
+        let tx = Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let entries = script_public_keys.into_iter().map(|spk| UtxoEntry::new(10000, spk, 0, false)).collect();
+        Pskt::with_entries(tx, entries).unwrap()
+    }
+
+    fn verify(tx: &Transaction, entries: Vec<UtxoEntry>) {
+        let populated = PopulatedTransaction::new(tx, entries);
+        let cache = Cache::new(10_000);
+        let mut reused_values = SigHashReusedValues::new();
+        for (index, (input, entry)) in populated.populated_inputs().enumerate() {
+            TxScriptEngine::from_transaction_input(&populated, input, index, entry, &mut reused_values, &cache)
+                .unwrap()
+                .execute()
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_pskt_multisig_workflow() {
+        let keys = keys();
+        let redeem_script = multisig_redeem_script(keys.iter().map(|kp| kp.x_only_public_key().0.serialize()), 2).unwrap();
+        let ecdsa_redeem_script = multisig_redeem_script_ecdsa(keys.iter().map(|kp| kp.public_key().serialize()), 2).unwrap();
+        let p2pk = ScriptPublicKey::from_vec(
+            0,
+            std::iter::once(OpData32).chain(keys[2].x_only_public_key().0.serialize()).chain([0xac]).collect(),
+        );
+
+        // creator and updater
+        let mut pskt = pskt(vec![pay_to_script_hash_script(&redeem_script), pay_to_script_hash_script(&ecdsa_redeem_script), p2pk]);
+        assert!(pskt.set_redeem_script(0, ecdsa_redeem_script.clone()).is_err());
+        pskt.set_redeem_script(0, redeem_script).unwrap();
+        pskt.set_redeem_script(1, ecdsa_redeem_script).unwrap();
+        assert_eq!(pskt.transaction.inputs[0].sig_op_count, 3);
+
+        // the container survives serialization
+        let serialized = pskt.to_string();
+        assert!(serialized.starts_with(PSKT_PREFIX));
+        let mut first: Pskt = serialized.parse().unwrap();
+        assert_eq!(first, pskt);
+        let mut second = first.clone();
+
+        // each participant signs on its own
+        assert_eq!(first.sign(&[keys[0].secret_bytes()]).unwrap(), 2);
+        assert_eq!(second.sign(&[keys[2].secret_bytes()]).unwrap(), 3);
+        assert!(first.set_sighash_type(0, SIG_HASH_ALL).is_err());
+        assert!(matches!(first.clone().finalize(), Err(Error::PsktInsufficientSignatures(0, 1, 2))));
+
+        // combiner, finalizer and extractor
+        let mut combined = first.combine(second).unwrap();
+        combined.finalize().unwrap();
+        assert!(combined.is_finalized());
+        let tx = combined.extract().unwrap();
+        assert_eq!(tx.id(), pskt.id());
+        verify(&tx, combined.utxo_entries().unwrap());
+    }
+
+    #[test]
+    fn test_pskt_combine_mismatch() {
+        let keys = keys();
+        let p2pk = ScriptPublicKey::from_vec(
+            0,
+            std::iter::once(OpData32).chain(keys[0].x_only_public_key().0.serialize()).chain([0xac]).collect(),
+        );
+        let pskt = pskt(vec![p2pk.clone()]);
+        let other = self::pskt(vec![p2pk.clone(), p2pk]);
+        assert!(matches!(pskt.clone().combine(other), Err(Error::PsktMismatch)));
+        assert!(matches!(pskt.extract(), Err(Error::PsktNotFinalized(0))));
+        assert!(pskt.partial_transaction().unwrap().inputs.iter().all(|input| input.signature_script.is_empty()));
+        assert!("PSKT00".parse::<Pskt>().is_err());
+        assert!(parse_multisig_redeem_script(&[OpTrue, OpData32]).is_none());
+    }
+}
@@ -0,0 +1,199 @@
+//!
+//! Time-locked payments: transaction lock times and time-locked
+//! pay-to-script-hash (P2SH) outputs spendable only once a DAA score
+//! or a timestamp has been reached.
+//!
+
+use crate::imports::*;
+use kaspa_addresses::Version;
+use kaspa_consensus_core::constants::LOCK_TIME_THRESHOLD;
+use kaspa_consensus_core::hashing::sighash::{calc_schnorr_signature_hash, SigHashReusedValues};
+use kaspa_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
+use kaspa_consensus_core::tx::SignableTransaction;
+use kaspa_txscript::opcodes::codes::{OpCheckLockTimeVerify, OpCheckSig};
+use kaspa_txscript::script_builder::ScriptBuilder;
+use kaspa_txscript::{extract_script_pub_key_address, pay_to_script_hash_script, pay_to_script_hash_signature_script};
+
+/// Lock time of a transaction or of a time-locked output.
+///
+/// Consensus interprets lock time values below [`LOCK_TIME_THRESHOLD`]
+/// as DAA scores and values above it as timestamps in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(tag = "type", content = "value", rename_all = "camelCase")]
+pub enum LockTime {
+    DaaScore(u64),
+    Timestamp(u64),
+}
+
+impl LockTime {
+    pub fn try_new(value: u64) -> Result<Self> {
+        match value {
+            0 => Err(Error::InvalidLockTime(value)),
+            value if value < LOCK_TIME_THRESHOLD => Ok(LockTime::DaaScore(value)),
+            value => Ok(LockTime::Timestamp(value)),
+        }
+    }
+
+    /// Lock time value as stored in the transaction `lock_time` field
+    pub fn value(&self) -> u64 {
+        match self {
+            LockTime::DaaScore(value) | LockTime::Timestamp(value) => *value,
+        }
+    }
+
+    pub fn is_daa_score(&self) -> bool {
+        matches!(self, LockTime::DaaScore(_))
+    }
+
+    /// Returns `true` if a transaction carrying this lock time is finalized
+    /// at the given DAA score and past median time.
+    pub fn is_reached(&self, daa_score: u64, past_median_time: u64) -> bool {
+        match self {
+            LockTime::DaaScore(value) => *value < daa_score,
+            LockTime::Timestamp(value) => *value < past_median_time,
+        }
+    }
+}
+
+impl std::fmt::Display for LockTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LockTime::DaaScore(value) => write!(f, "DAA score {value}"),
+            LockTime::Timestamp(value) => write!(f, "{}", workflow_core::time::unixtime_to_locale_string(*value)),
+        }
+    }
+}
+
+impl FromStr for LockTime {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let value = s.trim().parse::<u64>().map_err(|_| Error::custom(format!("Invalid lock time '{s}'")))?;
+        LockTime::try_new(value)
+    }
+}
+
+/// A time-locked output owned by `address`, spendable by its key once `lock_time` is reached.
+///
+/// Funds are locked by paying to the [`TimeLock::address`] P2SH address, which commits to the
+/// [`TimeLock::redeem_script`] `<lock_time> OP_CHECKLOCKTIMEVERIFY <public key> OP_CHECKSIG`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeLock {
+    /// The P2PK address whose key can claim the funds
+    pub owner: Address,
+    pub lock_time: LockTime,
+}
+
+impl TimeLock {
+    pub fn try_new(owner: Address, lock_time: LockTime) -> Result<Self> {
+        if owner.version != Version::PubKey {
+            return Err(Error::InvalidTimeLockAddress(owner));
+        }
+        Ok(Self { owner, lock_time })
+    }
+
+    pub fn redeem_script(&self) -> Result<Vec<u8>> {
+        Ok(ScriptBuilder::new()
+            .add_lock_time(self.lock_time.value())?
+            .add_op(OpCheckLockTimeVerify)?
+            .add_data(self.owner.payload.as_slice())?
+            .add_op(OpCheckSig)?
+            .drain())
+    }
+
+    pub fn script_public_key(&self) -> Result<ScriptPublicKey> {
+        Ok(pay_to_script_hash_script(&self.redeem_script()?))
+    }
+
+    /// The P2SH address funds are locked to
+    pub fn address(&self) -> Result<Address> {
+        Ok(extract_script_pub_key_address(&self.script_public_key()?, self.owner.prefix)?)
+    }
+}
+
+/// Signs the inputs of `mutable_tx` spending time-locked outputs, using the private
+/// key of each time lock owner. Other inputs are left untouched.
+pub fn sign_timelocked_inputs(mut mutable_tx: SignableTransaction, timelocks: &[(TimeLock, [u8; 32])]) -> Result<SignableTransaction> {
+    let mut map = AHashMap::new();
+    for (timelock, private_key) in timelocks {
+        let schnorr_key = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, private_key)?;
+        map.insert(timelock.script_public_key()?, (timelock.redeem_script()?, schnorr_key));
+    }
+
+    let mut reused_values = SigHashReusedValues::new();
+    for i in 0..mutable_tx.tx.inputs.len() {
+        let Some(entry) = mutable_tx.entries[i].as_ref() else {
+            continue;
+        };
+        if let Some((redeem_script, schnorr_key)) = map.get(&entry.script_public_key) {
+            let sig_hash = calc_schnorr_signature_hash(&mutable_tx.as_verifiable(), i, SIG_HASH_ALL, &mut reused_values);
+            let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice())?;
+            let sig: [u8; 64] = *schnorr_key.sign_schnorr(msg).as_ref();
+            // OP_DATA_65 <SIGNATURE+SIGHASH_TYPE> followed by the redeem script push
+            let signature = std::iter::once(65u8).chain(sig).chain([SIG_HASH_ALL.to_u8()]).collect();
+            mutable_tx.tx.inputs[i].signature_script = pay_to_script_hash_signature_script(redeem_script.clone(), signature)?;
+        }
+    }
+
+    Ok(mutable_tx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{
+        Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry, VerifiableTransaction,
+    };
+    use kaspa_txscript::caches::Cache;
+    use kaspa_txscript::TxScriptEngine;
+    use kaspa_txscript_errors::TxScriptError;
+
+    fn timelock(lock_time: u64) -> (TimeLock, [u8; 32]) {
+        let secret_key = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+        let public_key = secret_key.x_only_public_key(secp256k1::SECP256K1).0;
+        let owner = Address::new(Prefix::Testnet, Version::PubKey, &public_key.serialize());
+        (TimeLock::try_new(owner, LockTime::try_new(lock_time).unwrap()).unwrap(), secret_key.secret_bytes())
+    }
+
+    fn claim(timelock: &(TimeLock, [u8; 32]), tx_lock_time: u64) -> std::result::Result<(), TxScriptError> {
+        let entry = UtxoEntry::new(1000, timelock.0.script_public_key().unwrap(), 0, false);
+        let input = TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(1), 0), vec![], 0, 1);
+        let output = TransactionOutput::new(900, ScriptPublicKey::from_vec(0, vec![]));
+        let tx = Transaction::new(0, vec![input], vec![output], tx_lock_time, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let signed =
+            sign_timelocked_inputs(SignableTransaction::with_entries(tx, vec![entry]), std::slice::from_ref(timelock)).unwrap();
+
+        let tx = signed.as_verifiable();
+        let (input, entry) = tx.populated_inputs().next().unwrap();
+        let sig_cache = Cache::new(0);
+        let mut reused_values = SigHashReusedValues::new();
+        TxScriptEngine::from_transaction_input(&tx, input, 0, entry, &mut reused_values, &sig_cache)?.execute()
+    }
+
+    #[test]
+    fn test_lock_time() {
+        assert!(LockTime::try_new(0).is_err());
+        assert_eq!(LockTime::try_new(1000).unwrap(), LockTime::DaaScore(1000));
+        assert_eq!(LockTime::try_new(LOCK_TIME_THRESHOLD).unwrap(), LockTime::Timestamp(LOCK_TIME_THRESHOLD));
+        assert_eq!(LockTime::from_str("1000").unwrap().value(), 1000);
+
+        assert!(!LockTime::DaaScore(1000).is_reached(1000, u64::MAX));
+        assert!(LockTime::DaaScore(1000).is_reached(1001, 0));
+        assert!(!LockTime::Timestamp(LOCK_TIME_THRESHOLD).is_reached(u64::MAX, LOCK_TIME_THRESHOLD));
+        assert!(LockTime::Timestamp(LOCK_TIME_THRESHOLD).is_reached(0, LOCK_TIME_THRESHOLD + 1));
+    }
+
+    #[test]
+    fn test_timelock_claim() {
+        let timelock = timelock(1000);
+        assert_eq!(timelock.0.address().unwrap().version, Version::ScriptHash);
+        assert!(TimeLock::try_new(timelock.0.address().unwrap(), LockTime::DaaScore(1000)).is_err());
+
+        assert_eq!(claim(&timelock, 1000), Ok(()));
+        assert_eq!(claim(&timelock, 2000), Ok(()));
+        assert!(matches!(claim(&timelock, 999), Err(TxScriptError::UnsatisfiedLockTime(_))));
+        // A timestamp lock time can not satisfy a DAA score lock
+        assert!(matches!(claim(&timelock, LOCK_TIME_THRESHOLD + 1000), Err(TxScriptError::UnsatisfiedLockTime(_))));
+    }
+}
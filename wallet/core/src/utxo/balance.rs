@@ -88,6 +88,25 @@ export interface IBalance {
      * have arrived.
      */
     stasisUtxoCount: number;
+    /**
+     * Total amount of Kaspa (in SOMPI) held in time-locked
+     * outputs whose lock time has not yet been reached.
+     */
+    locked: bigint;
+    /**
+     * Total amount of Kaspa (in SOMPI) held in time-locked
+     * outputs whose lock time has been reached and that
+     * can be claimed into the account.
+     */
+    claimable: bigint;
+    /**
+     * Number of time-locked UTXOs that can not be claimed yet.
+     */
+    lockedUtxoCount: number;
+    /**
+     * Number of time-locked UTXOs that can be claimed.
+     */
+    claimableUtxoCount: number;
 }
 "#;
 
@@ -100,6 +119,14 @@ pub struct Balance {
     pub mature_utxo_count: usize,
     pub pending_utxo_count: usize,
     pub stasis_utxo_count: usize,
+    #[serde(default)]
+    pub locked: u64,
+    #[serde(default)]
+    pub claimable: u64,
+    #[serde(default)]
+    pub locked_utxo_count: usize,
+    #[serde(default)]
+    pub claimable_utxo_count: usize,
     #[serde(skip)]
     mature_delta: Delta,
     #[serde(skip)]
@@ -124,9 +151,22 @@ impl Balance {
             mature_utxo_count,
             pending_utxo_count,
             stasis_utxo_count,
+            locked: 0,
+            claimable: 0,
+            locked_utxo_count: 0,
+            claimable_utxo_count: 0,
         }
     }
 
+    /// Sets the time-locked funds, `locked` until their lock time is reached and `claimable` afterwards.
+    pub fn with_timelocked(mut self, locked: u64, locked_utxo_count: usize, claimable: u64, claimable_utxo_count: usize) -> Self {
+        self.locked = locked;
+        self.locked_utxo_count = locked_utxo_count;
+        self.claimable = claimable;
+        self.claimable_utxo_count = claimable_utxo_count;
+        self
+    }
+
     pub fn is_empty(&self) -> bool {
         self.mature == 0 && self.pending == 0
     }
@@ -174,6 +214,10 @@ impl From<AtomicBalance> for Balance {
             mature_utxo_count: atomic_balance.mature_utxos.load(Ordering::SeqCst),
             pending_utxo_count: atomic_balance.pending_utxos.load(Ordering::SeqCst),
             stasis_utxo_count: atomic_balance.stasis_utxos.load(Ordering::SeqCst),
+            locked: 0,
+            claimable: 0,
+            locked_utxo_count: 0,
+            claimable_utxo_count: 0,
             mature_delta: Delta::default(),
             pending_delta: Delta::default(),
         }
@@ -183,6 +227,7 @@ impl From<AtomicBalance> for Balance {
 pub struct BalanceStrings {
     pub mature: String,
     pub pending: Option<String>,
+    pub locked: Option<String>,
 }
 
 impl From<(Option<&Balance>, &NetworkType, Option<usize>)> for BalanceStrings {
@@ -191,26 +236,32 @@ impl From<(Option<&Balance>, &NetworkType, Option<usize>)> for BalanceStrings {
         if let Some(balance) = balance {
             let mut mature = utils::sompi_to_kaspa_string(balance.mature);
             let mut pending = if balance.pending > 0 { Some(utils::sompi_to_kaspa_string(balance.pending)) } else { None };
+            let locked = balance.locked + balance.claimable;
+            let mut locked = if locked > 0 { Some(utils::sompi_to_kaspa_string(locked)) } else { None };
             if let Some(padding) = padding {
                 mature = mature.pad_to_width(padding);
                 pending = pending.map(|pending| pending.pad_to_width(padding));
+                locked = locked.map(|locked| locked.pad_to_width(padding));
             }
             Self {
                 mature: format!("{} {}", balance.mature_delta.style(&mature, DeltaStyle::Mature), suffix),
                 pending: pending.map(|pending| format!("{} {}", balance.pending_delta.style(&pending, DeltaStyle::Pending), suffix)),
+                locked: locked.map(|locked| format!("{} {}", style(locked).dim(), suffix)),
             }
         } else {
-            Self { mature: format!("N/A {suffix}"), pending: None }
+            Self { mature: format!("N/A {suffix}"), pending: None, locked: None }
         }
     }
 }
 
 impl std::fmt::Display for BalanceStrings {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(pending) = &self.pending {
-            write!(f, "{} ({} pending)", self.mature, pending)
-        } else {
-            write!(f, "{}", self.mature)
+        write!(f, "{}", self.mature)?;
+        match (&self.pending, &self.locked) {
+            (Some(pending), Some(locked)) => write!(f, " ({} pending, {} time-locked)", pending, locked),
+            (Some(pending), None) => write!(f, " ({} pending)", pending),
+            (None, Some(locked)) => write!(f, " ({} time-locked)", locked),
+            (None, None) => Ok(()),
         }
     }
 }
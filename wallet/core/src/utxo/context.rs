@@ -9,13 +9,14 @@ use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
 use crate::storage::TransactionRecord;
-use crate::tx::PendingTransaction;
+use crate::tx::{LockTime, PendingTransaction, TimeLock};
 use crate::utxo::{
     Maturity, NetworkParams, OutgoingTransaction, PendingUtxoEntryReference, UtxoContextBinding, UtxoEntryId, UtxoEntryReference,
     UtxoEntryReferenceExtension, UtxoProcessor,
 };
 use kaspa_hashes::Hash;
 use sorted_insert::SortedInsertBinaryByKey;
+use workflow_core::time::unixtime_as_millis_u64;
 
 static UTXO_CONTEXT_ID_SEQUENCER: AtomicU64 = AtomicU64::new(0);
 fn next_utxo_context_id() -> Hash {
@@ -96,6 +97,10 @@ pub struct Context {
     balance: Option<Balance>,
     /// Addresses monitored by this UTXO context
     addresses: Arc<DashSet<Arc<Address>>>,
+    /// Mature time-locked UTXOs, kept apart from the spendable `mature` set
+    pub(crate) timelocked: Vec<UtxoEntryReference>,
+    /// Lock times of the time-locked scripts monitored by this UTXO context
+    timelocks: AHashMap<ScriptPublicKey, LockTime>,
 }
 
 impl Default for Context {
@@ -108,6 +113,8 @@ impl Default for Context {
             outgoing: AHashMap::default(),
             balance: None,
             addresses: Arc::new(DashSet::new()),
+            timelocked: vec![],
+            timelocks: AHashMap::default(),
         }
     }
 }
@@ -124,8 +131,24 @@ impl Context {
         self.pending.clear();
         self.outgoing.clear();
         self.addresses.clear();
+        self.timelocked.clear();
+        self.timelocks.clear();
         self.balance = None;
     }
+
+    /// Inserts a mature UTXO entry, time-locked entries being kept in the `timelocked` set.
+    fn insert_mature(&mut self, utxo_entry: UtxoEntryReference) {
+        if self.timelocks.contains_key(&utxo_entry.utxo.script_public_key) {
+            self.timelocked.push(utxo_entry);
+        } else {
+            self.mature.sorted_insert_binary_asc_by_key(utxo_entry, |entry| entry.amount_as_ref());
+        }
+    }
+
+    /// Lock time of a time-locked UTXO entry
+    pub fn lock_time(&self, utxo_entry: &UtxoEntryReference) -> Option<LockTime> {
+        self.timelocks.get(&utxo_entry.utxo.script_public_key).copied()
+    }
 }
 
 struct Inner {
@@ -251,6 +274,7 @@ impl UtxoContext {
             let mut context = self.context();
             let pending_utxo_entries = pending_tx.utxo_entries();
             context.mature.retain(|entry| !pending_utxo_entries.contains_key(&entry.id()));
+            context.timelocked.retain(|entry| !pending_utxo_entries.contains_key(&entry.id()));
 
             let outgoing_transaction = OutgoingTransaction::new(current_daa_score, self.clone(), pending_tx.clone());
             self.processor().register_outgoing_transaction(outgoing_transaction.clone());
@@ -283,7 +307,11 @@ impl UtxoContext {
 
         let outgoing_transaction = context.outgoing.remove(&pending_tx.id()).expect("outgoing transaction");
         outgoing_transaction.utxo_entries().iter().for_each(|(_, entry)| {
-            context.mature.push(entry.clone());
+            if context.timelocks.contains_key(&entry.utxo.script_public_key) {
+                context.timelocked.push(entry.clone());
+            } else {
+                context.mature.push(entry.clone());
+            }
         });
 
         Ok(())
@@ -296,7 +324,7 @@ impl UtxoContext {
         if let std::collections::hash_map::Entry::Vacant(e) = context.map.entry(utxo_entry.id().clone()) {
             e.insert(utxo_entry.clone());
             if force_maturity {
-                context.mature.sorted_insert_binary_asc_by_key(utxo_entry.clone(), |entry| entry.amount_as_ref());
+                context.insert_mature(utxo_entry.clone());
             } else {
                 let params = NetworkParams::from(self.processor().network_id()?);
                 match utxo_entry.maturity(&params, current_daa_score) {
@@ -313,7 +341,7 @@ impl UtxoContext {
                             .insert(utxo_entry.id().clone(), PendingUtxoEntryReference::new(utxo_entry, self.clone()));
                     }
                     Maturity::Confirmed => {
-                        context.mature.sorted_insert_binary_asc_by_key(utxo_entry.clone(), |entry| entry.amount_as_ref());
+                        context.insert_mature(utxo_entry.clone());
                     }
                 }
             }
@@ -351,14 +379,16 @@ impl UtxoContext {
             }
         }
 
-        context.mature.retain(|entry| {
+        let mut retain = |entry: &UtxoEntryReference| {
             if remove_mature_ids.contains(&entry.id()) {
                 removed.push(UtxoEntryVariant::Mature(entry.clone()));
                 false
             } else {
                 true
             }
-        });
+        };
+        context.mature.retain(&mut retain);
+        context.timelocked.retain(retain);
 
         Ok(removed)
     }
@@ -371,7 +401,7 @@ impl UtxoContext {
             for utxo_entry in utxos.iter() {
                 let mut context = self.context();
                 if context.pending.remove(utxo_entry.id_as_ref()).is_some() {
-                    context.mature.sorted_insert_binary_asc_by_key(utxo_entry.clone(), |entry| entry.amount_as_ref());
+                    context.insert_mature(utxo_entry.clone());
                 } else {
                     log_error!("Error: non-pending utxo promotion!");
                     // unreachable!("Error: non-pending utxo promotion!");
@@ -444,7 +474,7 @@ impl UtxoContext {
                         }
                         Maturity::Confirmed => {
                             mature.push(utxo_entry.clone());
-                            context.mature.sorted_insert_binary_asc_by_key(utxo_entry.clone(), |entry| entry.amount_as_ref());
+                            context.insert_mature(utxo_entry.clone());
                         }
                     }
                 } else {
@@ -508,7 +538,56 @@ impl UtxoContext {
 
         let mature = (mature + consumed).saturating_sub(outgoing);
 
-        Balance::new(mature, pending, outgoing, context.mature.len(), context.pending.len(), context.stasis.len())
+        // time-locked UTXOs are claimable once their lock time is reached,
+        // approximating the past median time using the local clock
+        let current_daa_score = self.processor().current_daa_score().unwrap_or_default();
+        let now = unixtime_as_millis_u64();
+        let (claimable, locked): (Vec<_>, Vec<_>) = context.timelocked.iter().partition(|entry| {
+            context.lock_time(entry).map(|lock_time| lock_time.is_reached(current_daa_score, now)).unwrap_or_default()
+        });
+
+        Balance::new(mature, pending, outgoing, context.mature.len(), context.pending.len(), context.stasis.len()).with_timelocked(
+            locked.iter().map(|entry| entry.amount()).sum(),
+            locked.len(),
+            claimable.iter().map(|entry| entry.amount()).sum(),
+            claimable.len(),
+        )
+    }
+
+    /// Monitors the given time-locked outputs, tracking their UTXOs as locked funds until claimed.
+    pub async fn register_timelocks(&self, timelocks: &[TimeLock], current_daa_score: Option<u64>) -> Result<()> {
+        let mut addresses = vec![];
+        for timelock in timelocks {
+            addresses.push(timelock.address()?);
+            self.context().timelocks.insert(timelock.script_public_key()?, timelock.lock_time);
+        }
+
+        if !addresses.is_empty() {
+            self.scan_and_register_addresses(addresses, current_daa_score).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Mature time-locked UTXOs whose lock time is reached, grouped by the lock time kind
+    /// as DAA score and timestamp locks can not be claimed within the same transaction.
+    pub fn claimable_timelocked(&self, current_daa_score: u64, past_median_time: u64) -> Vec<(LockTime, Vec<UtxoEntryReference>)> {
+        let context = self.context();
+        let mut groups: Vec<(LockTime, Vec<UtxoEntryReference>)> = vec![];
+        for entry in context.timelocked.iter() {
+            let Some(lock_time) = context.lock_time(entry) else { continue };
+            if !lock_time.is_reached(current_daa_score, past_median_time) {
+                continue;
+            }
+            match groups.iter_mut().find(|(group, _)| group.is_daa_score() == lock_time.is_daa_score()) {
+                Some((group, entries)) => {
+                    *group = LockTime::max(*group, lock_time);
+                    entries.push(entry.clone());
+                }
+                None => groups.push((lock_time, vec![entry.clone()])),
+            }
+        }
+        groups
     }
 
     pub(crate) async fn handle_utxo_added(&self, utxos: Vec<UtxoEntryReference>, current_daa_score: u64) -> Result<()> {
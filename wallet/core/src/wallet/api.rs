@@ -343,13 +343,15 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_send_call(self: Arc<Self>, request: AccountsSendRequest) -> Result<AccountsSendResponse> {
-        let AccountsSendRequest { account_id, wallet_secret, payment_secret, destination, priority_fee_sompi, payload } = request;
+        let AccountsSendRequest { account_id, wallet_secret, payment_secret, destination, priority_fee_sompi, payload, coin_control } =
+            request;
 
         let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
 
         let abortable = Abortable::new();
-        let (generator_summary, transaction_ids) =
-            account.send(destination, priority_fee_sompi, payload, wallet_secret, payment_secret, &abortable, None).await?;
+        let (generator_summary, transaction_ids) = account
+            .send(destination, priority_fee_sompi, payload, coin_control, wallet_secret, payment_secret, &abortable, None)
+            .await?;
 
         Ok(AccountsSendResponse { generator_summary, transaction_ids })
     }
@@ -423,7 +425,7 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_estimate_call(self: Arc<Self>, request: AccountsEstimateRequest) -> Result<AccountsEstimateResponse> {
-        let AccountsEstimateRequest { account_id, destination, priority_fee_sompi, payload } = request;
+        let AccountsEstimateRequest { account_id, destination, priority_fee_sompi, payload, coin_control } = request;
 
         let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
 
@@ -440,12 +442,33 @@ impl WalletApi for super::Wallet {
 
         let abortable = Abortable::new();
         self.inner.estimation_abortables.lock().unwrap().insert(account_id, abortable.clone());
-        let result = account.estimate(destination, priority_fee_sompi, payload, &abortable).await;
+        let result = account.estimate(destination, priority_fee_sompi, payload, coin_control, &abortable).await;
         self.inner.estimation_abortables.lock().unwrap().remove(&account_id);
 
         Ok(AccountsEstimateResponse { generator_summary: result? })
     }
 
+    async fn accounts_freeze_utxos_call(self: Arc<Self>, request: AccountsFreezeUtxosRequest) -> Result<AccountsFreezeUtxosResponse> {
+        let AccountsFreezeUtxosRequest { account_id, wallet_secret, outpoints } = request;
+
+        let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
+        let frozen_utxos = account.freeze_utxos(&wallet_secret, &outpoints).await?;
+
+        Ok(AccountsFreezeUtxosResponse { frozen_utxos })
+    }
+
+    async fn accounts_unfreeze_utxos_call(
+        self: Arc<Self>,
+        request: AccountsUnfreezeUtxosRequest,
+    ) -> Result<AccountsUnfreezeUtxosResponse> {
+        let AccountsUnfreezeUtxosRequest { account_id, wallet_secret, outpoints } = request;
+
+        let account = self.get_account_by_id(&account_id).await?.ok_or(Error::AccountNotFound(account_id))?;
+        let frozen_utxos = account.unfreeze_utxos(&wallet_secret, &outpoints).await?;
+
+        Ok(AccountsUnfreezeUtxosResponse { frozen_utxos })
+    }
+
     async fn transactions_data_get_call(self: Arc<Self>, request: TransactionsDataGetRequest) -> Result<TransactionsDataGetResponse> {
         let TransactionsDataGetRequest { account_id, network_id, filter, start, end } = request;
 
@@ -491,8 +514,62 @@ impl WalletApi for super::Wallet {
 
     async fn address_book_enumerate_call(
         self: Arc<Self>,
-        _request: AddressBookEnumerateRequest,
+        request: AddressBookEnumerateRequest,
     ) -> Result<AddressBookEnumerateResponse> {
-        return Err(Error::NotImplemented);
+        let AddressBookEnumerateRequest { search } = request;
+
+        let store = self.store().as_address_book_store()?;
+        let entries =
+            if let Some(search) = search { store.search(&search).await? } else { store.iter().await?.try_collect::<Vec<_>>().await? };
+        let entries = entries.into_iter().map(|entry| (*entry).clone()).collect();
+
+        Ok(AddressBookEnumerateResponse { entries })
+    }
+
+    async fn address_book_update_call(self: Arc<Self>, request: AddressBookUpdateRequest) -> Result<AddressBookUpdateResponse> {
+        let AddressBookUpdateRequest { wallet_secret, entry } = request;
+
+        self.store().as_address_book_store()?.store(entry).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(AddressBookUpdateResponse {})
+    }
+
+    async fn address_book_remove_call(self: Arc<Self>, request: AddressBookRemoveRequest) -> Result<AddressBookRemoveResponse> {
+        let AddressBookRemoveRequest { wallet_secret, address } = request;
+
+        self.store().as_address_book_store()?.remove(&address).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(AddressBookRemoveResponse {})
+    }
+
+    async fn labels_enumerate_call(self: Arc<Self>, request: LabelsEnumerateRequest) -> Result<LabelsEnumerateResponse> {
+        let LabelsEnumerateRequest { search } = request;
+
+        let store = self.store().as_label_store()?;
+        let labels =
+            if let Some(search) = search { store.search(&search).await? } else { store.iter().await?.try_collect::<Vec<_>>().await? };
+        let labels = labels.into_iter().map(|label| (*label).clone()).collect();
+
+        Ok(LabelsEnumerateResponse { labels })
+    }
+
+    async fn labels_update_call(self: Arc<Self>, request: LabelsUpdateRequest) -> Result<LabelsUpdateResponse> {
+        let LabelsUpdateRequest { wallet_secret, label } = request;
+
+        self.store().as_label_store()?.store(label).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(LabelsUpdateResponse {})
+    }
+
+    async fn labels_remove_call(self: Arc<Self>, request: LabelsRemoveRequest) -> Result<LabelsRemoveResponse> {
+        let LabelsRemoveRequest { wallet_secret, target } = request;
+
+        self.store().as_label_store()?.remove(&target).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(LabelsRemoveResponse {})
     }
 }
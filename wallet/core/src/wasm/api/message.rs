@@ -4,7 +4,8 @@ use super::extensions::*;
 use crate::account::descriptor::IAccountDescriptor;
 use crate::api::message::*;
 use crate::imports::*;
-use crate::tx::{Fees, PaymentDestination, PaymentOutputs};
+use crate::tx::{CoinControl, Fees, PaymentDestination, PaymentOutputs};
+use kaspa_consensus_core::tx::TransactionOutpoint;
 use crate::wasm::tx::fees::IFees;
 use crate::wasm::tx::GeneratorSummary;
 use js_sys::Array;
@@ -1340,6 +1341,28 @@ try_from! ( args: AccountsCreateNewAddressResponse, IAccountsCreateNewAddressRes
 
 // ---
 
+#[wasm_bindgen(typescript_custom_section)]
+const TS_COIN_CONTROL: &'static str = r#"
+/**
+ * UTXO selection strategy used when generating transactions.
+ * 
+ * @category Wallet API
+ */
+export type UtxoSelection = "default" | "largest-first" | "smallest-first" | "branch-and-bound" | "random";
+
+/**
+ * Explicit control over the UTXOs spent by a transaction. Frozen and
+ * excluded UTXOs are never spent, included UTXOs are spent first.
+ * 
+ * @category Wallet API
+ */
+export interface ICoinControl {
+    selection? : UtxoSelection;
+    include? : ITransactionOutpoint[];
+    exclude? : ITransactionOutpoint[];
+}
+"#;
+
 declare! {
     IAccountsSendRequest,
     r#"
@@ -1373,6 +1396,10 @@ declare! {
          * If not supplied, the destination will be the change address resulting in a UTXO compound transaction.
          */
         destination? : IPaymentOutput[];
+        /**
+         * Optional control over the UTXOs spent by the transaction.
+         */
+        coinControl? : ICoinControl;
     }
     "#,
 }
@@ -1387,8 +1414,9 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
+    let coin_control = args.try_get_value("coinControl")?.map(from_value::<CoinControl>).transpose()?;
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload })
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, priority_fee_sompi, destination, payload, coin_control })
 });
 
 declare! {
@@ -1704,6 +1732,7 @@ declare! {
         destination : IPaymentOutput[];
         priorityFeeSompi : IFees | bigint;
         payload? : Uint8Array | string;
+        coinControl? : ICoinControl;
     }
     "#,
 }
@@ -1716,8 +1745,9 @@ try_from! ( args: IAccountsEstimateRequest, AccountsEstimateRequest, {
     let outputs = args.get_value("destination")?;
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
+    let coin_control = args.try_get_value("coinControl")?.map(from_value::<CoinControl>).transpose()?;
 
-    Ok(AccountsEstimateRequest { account_id, priority_fee_sompi, destination, payload })
+    Ok(AccountsEstimateRequest { account_id, priority_fee_sompi, destination, payload, coin_control })
 });
 
 declare! {
@@ -1742,6 +1772,92 @@ try_from! ( args: AccountsEstimateResponse, IAccountsEstimateResponse, {
 
 // ---
 
+declare! {
+    IAccountsFreezeUtxosRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsFreezeUtxosRequest {
+        accountId : HexString;
+        walletSecret : string;
+        outpoints : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsFreezeUtxosRequest, AccountsFreezeUtxosRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let outpoints = from_value::<Vec<TransactionOutpoint>>(args.get_value("outpoints")?)?;
+    Ok(AccountsFreezeUtxosRequest { account_id, wallet_secret, outpoints })
+});
+
+declare! {
+    IAccountsFreezeUtxosResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsFreezeUtxosResponse {
+        frozenUtxos : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: AccountsFreezeUtxosResponse, IAccountsFreezeUtxosResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IAccountsUnfreezeUtxosRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsUnfreezeUtxosRequest {
+        accountId : HexString;
+        walletSecret : string;
+        outpoints : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsUnfreezeUtxosRequest, AccountsUnfreezeUtxosRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let outpoints = from_value::<Vec<TransactionOutpoint>>(args.get_value("outpoints")?)?;
+    Ok(AccountsUnfreezeUtxosRequest { account_id, wallet_secret, outpoints })
+});
+
+declare! {
+    IAccountsUnfreezeUtxosResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsUnfreezeUtxosResponse {
+        frozenUtxos : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: AccountsUnfreezeUtxosResponse, IAccountsUnfreezeUtxosResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     ITransactionsDataGetRequest,
     r#"
@@ -1943,12 +2059,15 @@ declare! {
      *  
      * @category Wallet API
      */
-    export interface IAddressBookEnumerateRequest { }
+    export interface IAddressBookEnumerateRequest {
+        search? : string;
+    }
     "#,
 }
 
-try_from! ( _args: IAddressBookEnumerateRequest, AddressBookEnumerateRequest, {
-    Ok(AddressBookEnumerateRequest { })
+try_from! ( args: IAddressBookEnumerateRequest, AddressBookEnumerateRequest, {
+    let search = args.try_get_string("search")?;
+    Ok(AddressBookEnumerateRequest { search })
 });
 
 declare! {
@@ -1960,13 +2079,208 @@ declare! {
      * @category Wallet API
      */
     export interface IAddressBookEnumerateResponse {
-        // TODO
+        entries : IAddressBookEntry[];
+    }
+    "#,
+}
+
+try_from! ( args: AddressBookEnumerateResponse, IAddressBookEnumerateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IAddressBookUpdateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookUpdateRequest {
+        walletSecret : string;
+        entry : IAddressBookEntry;
     }
     "#,
 }
 
-try_from! ( _args: AddressBookEnumerateResponse, IAddressBookEnumerateResponse, {
-    Err(Error::NotImplemented)
+try_from! ( args: IAddressBookUpdateRequest, AddressBookUpdateRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let entry = from_value::<AddressBookEntry>(args.get_value("entry")?)?;
+    Ok(AddressBookUpdateRequest { wallet_secret, entry })
+});
+
+declare! {
+    IAddressBookUpdateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookUpdateResponse { }
+    "#,
+}
+
+try_from! ( _args: AddressBookUpdateResponse, IAddressBookUpdateResponse, {
+    Ok(IAddressBookUpdateResponse::default())
+});
+
+// ---
+
+declare! {
+    IAddressBookRemoveRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookRemoveRequest {
+        walletSecret : string;
+        address : string;
+    }
+    "#,
+}
+
+try_from! ( args: IAddressBookRemoveRequest, AddressBookRemoveRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let address = from_value::<Address>(args.get_value("address")?)?;
+    Ok(AddressBookRemoveRequest { wallet_secret, address })
+});
+
+declare! {
+    IAddressBookRemoveResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAddressBookRemoveResponse { }
+    "#,
+}
+
+try_from! ( _args: AddressBookRemoveResponse, IAddressBookRemoveResponse, {
+    Ok(IAddressBookRemoveResponse::default())
+});
+
+// ---
+
+declare! {
+    ILabelsEnumerateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsEnumerateRequest {
+        search? : string;
+    }
+    "#,
+}
+
+try_from! ( args: ILabelsEnumerateRequest, LabelsEnumerateRequest, {
+    let search = args.try_get_string("search")?;
+    Ok(LabelsEnumerateRequest { search })
+});
+
+declare! {
+    ILabelsEnumerateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsEnumerateResponse {
+        labels : ILabel[];
+    }
+    "#,
+}
+
+try_from! ( args: LabelsEnumerateResponse, ILabelsEnumerateResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    ILabelsUpdateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsUpdateRequest {
+        walletSecret : string;
+        label : ILabel;
+    }
+    "#,
+}
+
+try_from! ( args: ILabelsUpdateRequest, LabelsUpdateRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let label = from_value::<Label>(args.get_value("label")?)?;
+    Ok(LabelsUpdateRequest { wallet_secret, label })
+});
+
+declare! {
+    ILabelsUpdateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsUpdateResponse { }
+    "#,
+}
+
+try_from! ( _args: LabelsUpdateResponse, ILabelsUpdateResponse, {
+    Ok(ILabelsUpdateResponse::default())
+});
+
+// ---
+
+declare! {
+    ILabelsRemoveRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsRemoveRequest {
+        walletSecret : string;
+        target : ILabelTarget;
+    }
+    "#,
+}
+
+try_from! ( args: ILabelsRemoveRequest, LabelsRemoveRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let target = from_value::<LabelTarget>(args.get_value("target")?)?;
+    Ok(LabelsRemoveRequest { wallet_secret, target })
+});
+
+declare! {
+    ILabelsRemoveResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsRemoveResponse { }
+    "#,
+}
+
+try_from! ( _args: LabelsRemoveResponse, ILabelsRemoveResponse, {
+    Ok(ILabelsRemoveResponse::default())
 });
 
 // ---
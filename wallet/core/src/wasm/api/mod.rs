@@ -52,8 +52,15 @@ declare_wasm_handlers!([
     AccountsAddresses,
     AccountsTransfer,
     AccountsEstimate,
+    AccountsFreezeUtxos,
+    AccountsUnfreezeUtxos,
     TransactionsDataGet,
     TransactionsReplaceNote,
     TransactionsReplaceMetadata,
     AddressBookEnumerate,
+    AddressBookUpdate,
+    AddressBookRemove,
+    LabelsEnumerate,
+    LabelsUpdate,
+    LabelsRemove,
 ]);
@@ -35,6 +35,18 @@ impl Balance {
         self.inner.outgoing.into()
     }
 
+    /// Amount of time-locked funds whose lock time has not yet been reached.
+    #[wasm_bindgen(getter)]
+    pub fn locked(&self) -> BigInt {
+        self.inner.locked.into()
+    }
+
+    /// Amount of time-locked funds whose lock time has been reached and that can be claimed.
+    #[wasm_bindgen(getter)]
+    pub fn claimable(&self) -> BigInt {
+        self.inner.claimable.into()
+    }
+
     #[wasm_bindgen(js_name = "toBalanceStrings")]
     pub fn to_balance_strings(&self, network_type: &NetworkTypeT) -> Result<BalanceStrings> {
         let network_type = NetworkType::try_from(network_type)?;
@@ -71,6 +83,11 @@ impl BalanceStrings {
     pub fn pending(&self) -> Option<String> {
         self.inner.pending.clone()
     }
+
+    #[wasm_bindgen(getter)]
+    pub fn locked(&self) -> Option<String> {
+        self.inner.locked.clone()
+    }
 }
 
 impl From<native::BalanceStrings> for BalanceStrings {
@@ -9,6 +9,7 @@ cfg_if! {
         pub mod balance;
         pub mod message;
         pub mod notify;
+        pub mod pskt;
         pub mod signer;
         pub mod tx;
         pub mod utils;
@@ -19,6 +20,7 @@ cfg_if! {
         pub use self::balance::*;
         pub use self::message::*;
         pub use self::notify::*;
+        pub use self::pskt::*;
         pub use self::signer::*;
         pub use self::tx::*;
         pub use self::utils::*;
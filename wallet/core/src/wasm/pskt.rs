@@ -0,0 +1,112 @@
+//!
+//! WASM32 bindings for partially signed transactions (PSKT).
+//!
+
+use crate::imports::*;
+use crate::result::Result;
+use crate::tx::pskt as native;
+use crate::wasm::PrivateKeyArrayT;
+use kaspa_consensus_client::Transaction;
+use kaspa_consensus_core::hashing::sighash_type::SigHashType;
+use kaspa_wallet_keys::privatekey::PrivateKey;
+use kaspa_wasm_core::types::BinaryT;
+use kaspa_wrpc_wasm::RpcClient;
+
+/// Partially signed transaction used to collect signatures from multiple
+/// parties before the transaction is broadcast to the network.
+/// PSKTs are exchanged between participants in their serialized string form.
+/// @see {@link PSKT.serialize}, {@link PSKT.deserialize}
+/// @category Wallet SDK
+#[wasm_bindgen(inspectable, js_name = PSKT)]
+pub struct Pskt {
+    inner: native::Pskt,
+}
+
+#[wasm_bindgen(js_class = PSKT)]
+impl Pskt {
+    /// Creates a PSKT from an unsigned {@link Transaction}. The transaction
+    /// must carry the UTXO entries of all its inputs.
+    #[wasm_bindgen(constructor)]
+    pub fn ctor(transaction: &Transaction) -> Result<Pskt> {
+        let (transaction, entries) = transaction.tx_and_utxos();
+        Ok(Self { inner: native::Pskt::with_entries(transaction, entries)? })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.inner.id().to_string()
+    }
+
+    #[wasm_bindgen(getter, js_name = isFinalized)]
+    pub fn is_finalized(&self) -> bool {
+        self.inner.is_finalized()
+    }
+
+    /// Attaches the redeem script of a pay-to-script-hash input.
+    #[wasm_bindgen(js_name = setRedeemScript)]
+    pub fn set_redeem_script(&mut self, index: usize, redeem_script: BinaryT) -> Result<()> {
+        self.inner.set_redeem_script(index, redeem_script.try_as_vec_u8()?)
+    }
+
+    /// Sets the sighash type used to sign an input.
+    #[wasm_bindgen(js_name = setSighashType)]
+    pub fn set_sighash_type(&mut self, index: usize, sighash_type: u8) -> Result<()> {
+        let sighash_type = SigHashType::from_u8(sighash_type).map_err(Error::custom)?;
+        self.inner.set_sighash_type(index, sighash_type)
+    }
+
+    /// Signs the inputs which can be spent by the supplied private keys
+    /// and returns the number of signatures added.
+    pub fn sign(&mut self, js_value: PrivateKeyArrayT) -> Result<usize> {
+        if let Ok(keys) = js_value.dyn_into::<Array>() {
+            let keys = keys
+                .iter()
+                .map(PrivateKey::try_cast_from)
+                .collect::<std::result::Result<Vec<_>, kaspa_wallet_keys::error::Error>>()?;
+            let mut keys = keys.iter().map(|key| key.as_ref().secret_bytes()).collect::<Vec<_>>();
+            let count = self.inner.sign(&keys);
+            keys.zeroize();
+            count
+        } else {
+            Err(Error::custom("Please supply an array of keys"))
+        }
+    }
+
+    /// Returns a new PSKT containing the signatures of both PSKTs.
+    pub fn combine(&self, other: &Pskt) -> Result<Pskt> {
+        Ok(Self { inner: self.inner.clone().combine(other.inner.clone())? })
+    }
+
+    /// Builds the signature scripts of all inputs from the collected signatures.
+    pub fn finalize(&mut self) -> Result<()> {
+        self.inner.finalize()
+    }
+
+    /// Returns the signed {@link Transaction}. The PSKT must be finalized.
+    pub fn extract(&self) -> Result<Transaction> {
+        Ok(self.inner.extract()?.into())
+    }
+
+    /// Finalizes the PSKT and submits its transaction to the supplied {@link RpcClient}.
+    pub async fn submit(&mut self, wasm_rpc_client: &RpcClient) -> Result<String> {
+        self.inner.finalize()?;
+        let transaction = self.inner.extract()?;
+        let rpc: Arc<DynRpcApi> = wasm_rpc_client.client().clone();
+        let txid = rpc.submit_transaction((&transaction).into(), false).await?;
+        Ok(txid.to_string())
+    }
+
+    pub fn serialize(&self) -> String {
+        self.inner.to_string()
+    }
+
+    pub fn deserialize(pskt: &str) -> Result<Pskt> {
+        Ok(Self { inner: pskt.parse()? })
+    }
+}
+
+impl From<native::Pskt> for Pskt {
+    fn from(inner: native::Pskt) -> Self {
+        Self { inner }
+    }
+}
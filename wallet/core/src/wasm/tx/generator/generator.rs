@@ -1,6 +1,6 @@
 use crate::imports::*;
 use crate::result::Result;
-use crate::tx::{generator as native, Fees, PaymentDestination, PaymentOutputs};
+use crate::tx::{generator as native, Fees, LockTime, PaymentDestination, PaymentOutputs};
 use crate::utxo::{TryIntoUtxoEntryReferences, UtxoEntryReference};
 use crate::wasm::tx::generator::*;
 use crate::wasm::tx::IFees;
@@ -76,6 +76,12 @@ interface IGeneratorSettingsObject {
      * Optional data payload to be included in the transaction.
      */
     payload?: Uint8Array | HexString;
+    /**
+     * Optional lock time applied to all generated transactions, either a DAA score
+     * or a timestamp in milliseconds (values above `500_000_000_000`). Transactions
+     * are rejected by the network until the lock time is reached.
+     */
+    lockTime?: bigint;
 
     /**
      * Optional NetworkId or network id as string (i.e. `mainnet` or `testnet-11`). Required when {@link IGeneratorSettingsObject.entries} is array
@@ -154,6 +160,7 @@ impl Generator {
             sig_op_count,
             minimum_signatures,
             payload,
+            lock_time,
         } = settings;
 
         let settings = match source {
@@ -196,6 +203,8 @@ impl Generator {
               // }
         };
 
+        let settings = if let Some(lock_time) = lock_time { settings.lock_time(lock_time) } else { settings };
+
         let abortable = Abortable::default();
         let generator = native::Generator::try_new(settings, None, Some(&abortable))?;
 
@@ -251,6 +260,7 @@ struct GeneratorSettings {
     pub sig_op_count: u8,
     pub minimum_signatures: u16,
     pub payload: Option<Vec<u8>>,
+    pub lock_time: Option<LockTime>,
 }
 
 impl TryFrom<IGeneratorSettingsObject> for GeneratorSettings {
@@ -288,6 +298,11 @@ impl TryFrom<IGeneratorSettingsObject> for GeneratorSettings {
 
         let payload = args.get_vec_u8("payload").ok();
 
+        let lock_time = args
+            .try_get_value("lockTime")?
+            .map(|lock_time| -> Result<LockTime> { LockTime::try_new(lock_time.try_as_u64()?) })
+            .transpose()?;
+
         let settings = GeneratorSettings {
             network_id,
             source: generator_source,
@@ -298,6 +313,7 @@ impl TryFrom<IGeneratorSettingsObject> for GeneratorSettings {
             sig_op_count,
             minimum_signatures,
             payload,
+            lock_time,
         };
 
         Ok(settings)
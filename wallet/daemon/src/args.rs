@@ -0,0 +1,140 @@
+use crate::{error::Error, result::Result, DEFAULT_WALLET_DAEMON_PORT};
+use clap::{Arg, Command};
+use kaspa_consensus_core::network::NetworkId;
+use kaspa_core::kaspad_env::version;
+use kaspa_utils::hex::ToHex;
+use kaspa_wallet_core::{prelude::Secret, storage::local::default_storage_folder};
+use rand::RngCore;
+use std::{
+    fs,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+use workflow_store::fs::resolve_path;
+
+/// Environment variable holding the wallet password when no password file is specified
+pub const PASSWORD_ENV_VAR: &str = "PYRINWALLET_PASSWORD";
+
+/// Name of the file holding the generated auth token, in the wallet storage folder
+pub const AUTH_TOKEN_FILE: &str = "pyrinwallet.token";
+
+#[derive(Debug)]
+pub struct Args {
+    pub wallet_file: Option<String>,
+    pub storage_folder: Option<String>,
+    pub network_id: NetworkId,
+    pub rpc_server: Option<String>,
+    pub listen: SocketAddr,
+    pub password_file: Option<String>,
+    pub auth_token_file: Option<String>,
+}
+
+impl Args {
+    pub fn parse() -> Result<Self> {
+        let m = cli().get_matches();
+        let network_id = m.get_one::<String>("network").unwrap();
+        Ok(Args {
+            wallet_file: m.get_one::<String>("wallet").cloned(),
+            storage_folder: m.get_one::<String>("storage-folder").cloned(),
+            network_id: network_id.parse().map_err(|err| Error::custom(format!("invalid network id {network_id}: {err}")))?,
+            rpc_server: m.get_one::<String>("rpcserver").cloned(),
+            listen: m.get_one::<SocketAddr>("listen").cloned().unwrap(),
+            password_file: m.get_one::<String>("password-file").cloned(),
+            auth_token_file: m.get_one::<String>("authtoken-file").cloned(),
+        })
+    }
+
+    /// Reads the wallet password out of the password file, or out of the environment if none is specified
+    pub fn wallet_secret(&self) -> Result<Secret> {
+        match self.password_file.as_ref() {
+            // Editors usually end the file with a newline, which is not part of the password
+            Some(path) => Ok(Secret::from(fs::read_to_string(resolve_path(path)?)?.trim_end_matches(['\r', '\n']))),
+            None => std::env::var(PASSWORD_ENV_VAR)
+                .map(Secret::from)
+                .map_err(|_| Error::custom(format!("specify the wallet password with --password-file or {PASSWORD_ENV_VAR}"))),
+        }
+    }
+
+    /// Reads the token the clients authenticate with, generating it on first use
+    pub fn auth_token(&self) -> Result<(String, PathBuf)> {
+        let path = match self.auth_token_file.as_ref() {
+            Some(path) => resolve_path(path)?,
+            None => resolve_path(self.storage_folder.as_deref().unwrap_or(default_storage_folder()))?.join(AUTH_TOKEN_FILE),
+        };
+        if path.exists() {
+            let token = fs::read_to_string(&path)?.trim().to_string();
+            if token.is_empty() {
+                return Err(Error::custom(format!("auth token file {} is empty", path.display())));
+            }
+            return Ok((token, path));
+        }
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = bytes.as_slice().to_hex();
+        write_private_file(&path, &token)?;
+        Ok((token, path))
+    }
+}
+
+/// Writes a file only readable by the current user
+fn write_private_file(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+pub fn cli() -> Command {
+    Command::new("pyrinwallet")
+        .about(format!("{} (pyrinwallet) v{}", env!("CARGO_PKG_DESCRIPTION"), version()))
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(Arg::new("wallet").long("wallet").value_name("name").require_equals(true).help("Name of the wallet file to open."))
+        .arg(
+            Arg::new("storage-folder")
+                .long("storage-folder")
+                .value_name("path")
+                .require_equals(true)
+                .help("Folder holding the wallet files (default: ~/.pyrin)."),
+        )
+        .arg(
+            Arg::new("network")
+                .long("network")
+                .value_name("network")
+                .require_equals(true)
+                .default_value("mainnet")
+                .help("Network of the wallet (mainnet, testnet-<suffix>, devnet, simnet)."),
+        )
+        .arg(
+            Arg::new("rpcserver")
+                .long("rpcserver")
+                .value_name("url")
+                .require_equals(true)
+                .help("wRPC (Borsh) url of the node the wallet syncs with (default: the local node)."),
+        )
+        .arg(
+            Arg::new("listen")
+                .long("listen")
+                .value_name("ip:port")
+                .require_equals(true)
+                .default_value(format!("127.0.0.1:{DEFAULT_WALLET_DAEMON_PORT}"))
+                .value_parser(clap::value_parser!(SocketAddr))
+                .help("Interface:port to serve the wallet RPC on."),
+        )
+        .arg(
+            Arg::new("password-file")
+                .long("password-file")
+                .value_name("path")
+                .require_equals(true)
+                .help(format!("File holding the wallet password (default: read from the {PASSWORD_ENV_VAR} environment variable).")),
+        )
+        .arg(Arg::new("authtoken-file").long("authtoken-file").value_name("path").require_equals(true).help(format!(
+            "File holding the token clients authenticate with, generated when missing (default: <storage folder>/{AUTH_TOKEN_FILE})."
+        )))
+}
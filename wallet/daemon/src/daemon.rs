@@ -0,0 +1,92 @@
+use crate::{
+    args::Args,
+    error::Error,
+    events::{EventBroadcaster, EVENT_CHANNEL_CAPACITY},
+    result::Result,
+    server::DaemonServer,
+};
+use kaspa_core::{info, warn};
+use kaspa_utils::triggers::SingleTrigger;
+use kaspa_wallet_core::{api::transport::WalletServer, prelude::*, storage::local::set_default_storage_folder};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// Opens the wallet, keeps its accounts synced with the node and serves the wallet API until stopped
+pub async fn run(args: Args) -> Result<()> {
+    if let Some(folder) = args.storage_folder.clone() {
+        // SAFETY: the wallet subsystem is not initialized yet
+        unsafe { set_default_storage_folder(folder)? };
+    }
+    let wallet_secret = args.wallet_secret()?;
+    let (auth_token, auth_token_path) = args.auth_token()?;
+
+    let wallet = Arc::new(Wallet::try_new(Wallet::local_store()?, None, Some(args.network_id))?);
+    let events = Arc::new(EventBroadcaster::new(EVENT_CHANNEL_CAPACITY));
+    let wallet_server = Arc::new(WalletServer::new(wallet.clone(), events.clone()));
+    wallet_server.start();
+    wallet.start().await?;
+
+    // The connection is retried in the background until the node is reachable
+    wallet.clone().connect_call(ConnectRequest { url: args.rpc_server.clone(), network_id: args.network_id }).await?;
+    let request =
+        WalletOpenRequest { wallet_secret, filename: args.wallet_file.clone(), account_descriptors: false, legacy_accounts: None };
+    wallet.clone().wallet_open_call(request).await?;
+    wallet.clone().accounts_activate_call(AccountsActivateRequest { account_ids: None }).await?;
+    info!("Wallet {} opened on {}", args.wallet_file.as_deref().unwrap_or("(default)"), args.network_id);
+
+    let listener =
+        TcpListener::bind(args.listen).await.map_err(|err| Error::custom(format!("failed to bind {}: {err}", args.listen)))?;
+    if !args.listen.ip().is_loopback() {
+        warn!("The wallet RPC is exposed beyond the local host on {}, make sure the connections are secured", args.listen);
+    }
+    info!("Wallet RPC listening on {}, clients authenticate with the token of {}", args.listen, auth_token_path.display());
+
+    let server = Arc::new(DaemonServer::new(wallet_server.clone(), events, auth_token));
+    let shutdown = SingleTrigger::new();
+    let mut stop_signals = StopSignals::new()?;
+    loop {
+        tokio::select! {
+            _ = stop_signals.recv() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, peer)) => {
+                    tokio::spawn(server.clone().serve(stream, peer, shutdown.listener.clone()));
+                }
+                Err(err) => warn!("Failed to accept a wallet RPC connection: {}", err),
+            },
+        }
+    }
+
+    info!("Shutting down the wallet...");
+    shutdown.trigger.trigger();
+    wallet.clone().wallet_close_call(WalletCloseRequest {}).await?;
+    wallet.clone().disconnect_call(DisconnectRequest {}).await?;
+    wallet.stop().await?;
+    wallet_server.stop_task().await?;
+    Ok(())
+}
+
+/// Signals requesting the daemon to stop: ctrl+c, and on unix the SIGTERM sent by service managers
+struct StopSignals {
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+}
+
+impl StopSignals {
+    fn new() -> Result<Self> {
+        #[cfg(unix)]
+        let stop_signals = Self { terminate: tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())? };
+        #[cfg(not(unix))]
+        let stop_signals = Self {};
+        Ok(stop_signals)
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = self.terminate.recv() => {}
+        }
+        #[cfg(not(unix))]
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
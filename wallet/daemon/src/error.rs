@@ -0,0 +1,50 @@
+use kaspa_wallet_core::error::Error as WalletError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("parse error: {0}")]
+    ParseError(String),
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("method {0} not found")]
+    MethodNotFound(String),
+
+    #[error("invalid parameters: {0}")]
+    InvalidParams(String),
+
+    #[error("client is not authenticated")]
+    Unauthorized,
+
+    #[error("{0}")]
+    Custom(String),
+
+    #[error("wallet error: {0}")]
+    WalletError(#[from] WalletError),
+
+    #[error("storage error: {0}")]
+    StoreError(#[from] workflow_store::error::Error),
+
+    #[error("i/o error: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl Error {
+    pub fn custom<T: Into<String>>(msg: T) -> Self {
+        Error::Custom(msg.into())
+    }
+
+    /// JSON-RPC 2.0 error code reported to the client
+    pub fn code(&self) -> i32 {
+        match self {
+            Error::ParseError(_) => -32700,
+            Error::InvalidRequest(_) => -32600,
+            Error::MethodNotFound(_) => -32601,
+            Error::InvalidParams(_) => -32602,
+            Error::Unauthorized => -32001,
+            _ => -32000,
+        }
+    }
+}
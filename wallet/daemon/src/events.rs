@@ -0,0 +1,59 @@
+use crate::protocol::{JsonRpcNotification, METHOD_EVENT};
+use async_trait::async_trait;
+use kaspa_core::warn;
+use kaspa_wallet_core::{api::transport::EventHandler, events::Events};
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// Number of wallet events buffered for the slowest subscriber before it starts missing events
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// A wallet event, serialized once for all the subscribed clients
+#[derive(Debug)]
+pub struct WalletEvent {
+    /// Event type, as found in the `type` field of the serialized event
+    pub kind: String,
+
+    /// Newline terminated notification line
+    pub line: Vec<u8>,
+}
+
+/// Forwards the wallet events to the client sessions
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<Arc<WalletEvent>>,
+}
+
+impl EventBroadcaster {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<WalletEvent>> {
+        self.sender.subscribe()
+    }
+}
+
+#[async_trait]
+impl EventHandler for EventBroadcaster {
+    async fn handle_event(&self, event: &Events) {
+        // Skip the serialization while no client is listening
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        let params = match serde_json::to_value(event) {
+            Ok(params) => params,
+            Err(err) => {
+                warn!("Unable to serialize wallet event: {}", err);
+                return;
+            }
+        };
+        let kind = params.get("type").and_then(|kind| kind.as_str()).unwrap_or_default().to_string();
+        let Ok(mut line) = serde_json::to_vec(&JsonRpcNotification::new(METHOD_EVENT, params)) else {
+            return;
+        };
+        line.push(b'\n');
+        // Sending only fails when the last subscriber is gone in the meantime
+        let _ = self.sender.send(Arc::new(WalletEvent { kind, line }));
+    }
+}
@@ -0,0 +1,11 @@
+//! Headless wallet daemon serving the wallet API to local services over an authenticated JSON-RPC interface
+pub mod args;
+pub mod daemon;
+pub mod error;
+pub mod events;
+pub mod protocol;
+pub mod result;
+pub mod server;
+
+/// Port of the wallet RPC listener when none is specified
+pub const DEFAULT_WALLET_DAEMON_PORT: u16 = 8110;
@@ -0,0 +1,18 @@
+use kaspa_core::{error, info};
+use kaspa_wallet_daemon::{args::Args, daemon::run};
+
+#[tokio::main]
+async fn main() {
+    kaspa_core::log::init_logger(None, "");
+    let result = match Args::parse() {
+        Ok(args) => run(args).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(()) => info!("Pyrin wallet has stopped..."),
+        Err(err) => {
+            error!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! JSON-RPC 2.0 messages of the wallet daemon, exchanged as newline delimited JSON objects.
+//!
+//! Besides the methods below, every wallet API operation is exposed under its kebab-case
+//! name (e.g. `accounts-enumerate`), taking the camelCase serialization of the matching
+//! request of `kaspa_wallet_core::api::message` as parameters.
+
+use crate::{error::Error, result::Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Authenticates the connection, expects `{ "token": <auth token> }`
+pub const METHOD_AUTHENTICATE: &str = "authenticate";
+/// Starts streaming wallet events, optionally restricted to `{ "events": [<event type>, ..] }`
+pub const METHOD_SUBSCRIBE: &str = "subscribe";
+/// Stops streaming wallet events
+pub const METHOD_UNSUBSCRIBE: &str = "unsubscribe";
+/// Notification carrying a wallet event
+pub const METHOD_EVENT: &str = "event";
+
+/// A request sent by a client
+#[derive(Debug, Deserialize)]
+pub struct JsonRpcRequest {
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl JsonRpcRequest {
+    /// Deserializes the parameters, a missing parameter object standing for an empty one
+    pub fn params<T: for<'de> Deserialize<'de>>(&self) -> Result<T> {
+        let params = if self.params.is_null() { Value::Object(Default::default()) } else { self.params.clone() };
+        serde_json::from_value(params).map_err(|err| Error::InvalidParams(err.to_string()))
+    }
+
+    /// Returns the parameters serialized the way the wallet API transport expects them
+    pub fn params_json(&self) -> String {
+        if self.params.is_null() {
+            "{}".to_string()
+        } else {
+            self.params.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct JsonRpcError {
+    pub code: i32,
+    pub message: String,
+}
+
+impl From<&Error> for JsonRpcError {
+    fn from(error: &Error) -> Self {
+        Self { code: error.code(), message: error.to_string() }
+    }
+}
+
+/// A response to a client request
+#[derive(Debug, Serialize)]
+pub struct JsonRpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+impl JsonRpcResponse {
+    pub fn ok(id: Value, result: Value) -> Self {
+        Self { jsonrpc: JSONRPC_VERSION, id, result: Some(result), error: None }
+    }
+
+    pub fn err(id: Value, error: &Error) -> Self {
+        Self { jsonrpc: JSONRPC_VERSION, id, result: None, error: Some(error.into()) }
+    }
+}
+
+/// A notification pushed to a client
+#[derive(Debug, Serialize)]
+pub struct JsonRpcNotification {
+    pub jsonrpc: &'static str,
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl JsonRpcNotification {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self { jsonrpc: JSONRPC_VERSION, method, params }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuthenticateParams {
+    pub token: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SubscribeParams {
+    /// Types of the streamed events, all events being streamed when empty
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_messages() {
+        let request: JsonRpcRequest = serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"method":"subscribe"}"#).unwrap();
+        assert_eq!(request.params_json(), "{}");
+        assert!(request.params::<SubscribeParams>().unwrap().events.is_empty());
+        assert!(matches!(request.params::<AuthenticateParams>(), Err(Error::InvalidParams(_))));
+
+        let request: JsonRpcRequest =
+            serde_json::from_str(r#"{"jsonrpc":"2.0","id":"a","method":"subscribe","params":{"events":["balance"]}}"#).unwrap();
+        assert_eq!(request.params::<SubscribeParams>().unwrap().events, vec!["balance".to_string()]);
+        assert_eq!(request.params_json(), r#"{"events":["balance"]}"#);
+
+        let response = JsonRpcResponse::err(request.id, &Error::Unauthorized);
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"jsonrpc":"2.0","id":"a","error":{"code":-32001,"message":"client is not authenticated"}}"#
+        );
+        let response = JsonRpcResponse::ok(json!(2), json!({}));
+        assert_eq!(serde_json::to_string(&response).unwrap(), r#"{"jsonrpc":"2.0","id":2,"result":{}}"#);
+        let notification = JsonRpcNotification::new(METHOD_EVENT, json!({"type":"wallet-close"}));
+        assert_eq!(
+            serde_json::to_string(&notification).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"event","params":{"type":"wallet-close"}}"#
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{
+    error::Error,
+    events::{EventBroadcaster, WalletEvent},
+    protocol::*,
+    result::Result,
+};
+use async_trait::async_trait;
+use kaspa_core::{debug, warn};
+use kaspa_utils::line_session::{serve_lines, write_json_line, LineSession};
+use kaspa_wallet_core::{api::transport::WalletServer, error::Error as WalletError};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{collections::HashSet, io, net::SocketAddr, sync::Arc};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{tcp::OwnedWriteHalf, TcpStream},
+    sync::broadcast::{self, error::RecvError},
+};
+use triggered::Listener;
+
+/// Maximum length of a request line sent by a client
+const MAX_LINE_LENGTH: usize = 1024 * 1024;
+
+/// Serves the wallet API to the connected clients
+pub struct DaemonServer {
+    wallet_server: Arc<WalletServer>,
+    events: Arc<EventBroadcaster>,
+    auth_token: String,
+}
+
+impl DaemonServer {
+    pub fn new(wallet_server: Arc<WalletServer>, events: Arc<EventBroadcaster>, auth_token: String) -> Self {
+        Self { wallet_server, events, auth_token }
+    }
+
+    fn is_valid_token(&self, token: &str) -> bool {
+        // Compare in constant time so the token cannot be guessed byte by byte
+        let (expected, token) = (self.auth_token.as_bytes(), token.as_bytes());
+        expected.len() == token.len() && expected.iter().zip(token).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+
+    /// Serves a connected client, streaming it the wallet events it subscribes to
+    pub async fn serve(self: Arc<Self>, stream: TcpStream, peer: SocketAddr, shutdown: Listener) {
+        debug!("Wallet client {} connected", peer);
+        let (reader, writer) = stream.into_split();
+        let mut session = Session { server: self.clone(), peer, writer, authenticated: false, subscription: None };
+        if let Err(err) = serve_lines(reader, MAX_LINE_LENGTH, shutdown, &mut session).await {
+            debug!("Wallet client {} disconnected: {}", peer, err);
+        } else {
+            debug!("Wallet client {} disconnected", peer);
+        }
+    }
+}
+
+/// Wallet events streamed to a client
+struct Subscription {
+    receiver: broadcast::Receiver<Arc<WalletEvent>>,
+    /// Types of the streamed events, all of them when empty
+    kinds: HashSet<String>,
+}
+
+/// State of the connection of a client
+struct Session {
+    server: Arc<DaemonServer>,
+    peer: SocketAddr,
+    writer: OwnedWriteHalf,
+    authenticated: bool,
+    subscription: Option<Subscription>,
+}
+
+#[async_trait]
+impl LineSession for Session {
+    /// A wallet event, or the number of events dropped because the client lagged behind
+    type Event = std::result::Result<Arc<WalletEvent>, u64>;
+
+    async fn handle_line(&mut self, line: &[u8]) -> io::Result<()> {
+        let request = match serde_json::from_slice::<JsonRpcRequest>(line) {
+            Ok(request) => request,
+            Err(err) => {
+                let error = Error::ParseError(err.to_string());
+                return self.write(&JsonRpcResponse::err(Value::Null, &error)).await;
+            }
+        };
+        let result = match request.method.as_str() {
+            METHOD_AUTHENTICATE => self.authenticate(&request),
+            _ if !self.authenticated => Err(Error::Unauthorized),
+            METHOD_SUBSCRIBE => self.subscribe(&request),
+            METHOD_UNSUBSCRIBE => {
+                self.subscription = None;
+                Ok(json!(true))
+            }
+            method => self.call(method, &request).await,
+        };
+        match result {
+            Ok(result) => self.write(&JsonRpcResponse::ok(request.id, result)).await,
+            Err(err) => self.write(&JsonRpcResponse::err(request.id, &err)).await,
+        }
+    }
+
+    async fn next_event(&mut self) -> Option<Self::Event> {
+        match self.subscription.as_mut() {
+            Some(subscription) => match subscription.receiver.recv().await {
+                Ok(event) => Some(Ok(event)),
+                Err(RecvError::Lagged(count)) => Some(Err(count)),
+                Err(RecvError::Closed) => None,
+            },
+            None => std::future::pending().await,
+        }
+    }
+
+    async fn handle_event(&mut self, received: Self::Event) -> io::Result<()> {
+        match received {
+            Ok(event) => self.forward_event(&event).await,
+            Err(count) => {
+                warn!("Wallet client {} is too slow, {} events were dropped", self.peer, count);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Session {
+    async fn write<T: Serialize>(&mut self, message: &T) -> io::Result<()> {
+        write_json_line(&mut self.writer, message).await
+    }
+
+    async fn forward_event(&mut self, event: &WalletEvent) -> io::Result<()> {
+        match self.subscription.as_ref() {
+            Some(subscription) if subscription.kinds.is_empty() || subscription.kinds.contains(&event.kind) => {
+                self.writer.write_all(&event.line).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn authenticate(&mut self, request: &JsonRpcRequest) -> Result<Value> {
+        let params = request.params::<AuthenticateParams>()?;
+        self.authenticated = self.server.is_valid_token(&params.token);
+        if !self.authenticated {
+            return Err(Error::Unauthorized);
+        }
+        Ok(json!(true))
+    }
+
+    fn subscribe(&mut self, request: &JsonRpcRequest) -> Result<Value> {
+        let params = request.params::<SubscribeParams>()?;
+        let kinds = params.events.into_iter().collect();
+        // Keep the receiver of a previous subscription so no event gets lost while changing the filter
+        let receiver = match self.subscription.take() {
+            Some(subscription) => subscription.receiver,
+            None => self.server.events.subscribe(),
+        };
+        self.subscription = Some(Subscription { receiver, kinds });
+        Ok(json!(true))
+    }
+
+    async fn call(&self, method: &str, request: &JsonRpcRequest) -> Result<Value> {
+        let response = self.server.wallet_server.call_with_serde(method, &request.params_json()).await.map_err(|err| match err {
+            WalletError::NotImplemented => Error::MethodNotFound(method.to_string()),
+            WalletError::SerdeJson(err) => Error::InvalidParams(err.to_string()),
+            err => err.into(),
+        })?;
+        serde_json::from_str(&response).map_err(|err| Error::custom(format!("invalid wallet response: {err}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EVENT_CHANNEL_CAPACITY;
+    use kaspa_utils::{line_session::test_helpers::LineClient, triggers::SingleTrigger};
+    use kaspa_wallet_core::{api::transport::EventHandler, events::Events, prelude::Wallet};
+    use tokio::net::TcpListener;
+
+    /// Serves a single client session with the auth token `secret`
+    async fn start_session() -> (LineClient, Arc<EventBroadcaster>, SingleTrigger) {
+        let wallet = Arc::new(Wallet::try_new(Wallet::resident_store().unwrap(), None, None).unwrap());
+        let events = Arc::new(EventBroadcaster::new(EVENT_CHANNEL_CAPACITY));
+        let wallet_server = Arc::new(WalletServer::new(wallet, events.clone()));
+        let server = Arc::new(DaemonServer::new(wallet_server, events.clone(), "secret".to_string()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let shutdown = SingleTrigger::new();
+        let listener_shutdown = shutdown.listener.clone();
+        tokio::spawn(async move {
+            let (stream, peer) = listener.accept().await.unwrap();
+            server.serve(stream, peer, listener_shutdown).await;
+        });
+
+        (LineClient::connect(address).await, events, shutdown)
+    }
+
+    #[tokio::test]
+    async fn test_session() {
+        let (mut client, events, shutdown) = start_session().await;
+        assert_eq!(
+            client.call(r#"{"id":1,"method":"ping","params":{"message":"hello"}}"#).await,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32001,"message":"client is not authenticated"}}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":2,"method":"authenticate","params":{"token":"secreT"}}"#).await,
+            r#"{"jsonrpc":"2.0","id":2,"error":{"code":-32001,"message":"client is not authenticated"}}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":3,"method":"authenticate","params":{"token":"secret"}}"#).await,
+            r#"{"jsonrpc":"2.0","id":3,"result":true}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":4,"method":"ping","params":{"message":"hello"}}"#).await,
+            r#"{"jsonrpc":"2.0","id":4,"result":{"message":"hello"}}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":5,"method":"unknown-method"}"#).await,
+            r#"{"jsonrpc":"2.0","id":5,"error":{"code":-32601,"message":"method unknown-method not found"}}"#
+        );
+        assert!(client.call("{").await.contains(r#""code":-32700"#));
+        assert_eq!(
+            client.call(r#"{"id":6,"method":"subscribe","params":{"events":["wallet-close"]}}"#).await,
+            r#"{"jsonrpc":"2.0","id":6,"result":true}"#
+        );
+
+        // Only the subscribed event types are streamed
+        events.handle_event(&Events::WalletPing).await;
+        events.handle_event(&Events::WalletClose).await;
+        assert_eq!(client.next_line().await, r#"{"jsonrpc":"2.0","method":"event","params":{"type":"wallet-close"}}"#);
+
+        shutdown.trigger.trigger();
+    }
+    #[tokio::test]
+    async fn test_session_resubscribe() {
+        let (mut client, events, shutdown) = start_session().await;
+        assert_eq!(
+            client.call(r#"{"id":1,"method":"authenticate","params":{"token":"secret"}}"#).await,
+            r#"{"jsonrpc":"2.0","id":1,"result":true}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":2,"method":"subscribe","params":{"events":["wallet-close"]}}"#).await,
+            r#"{"jsonrpc":"2.0","id":2,"result":true}"#
+        );
+        assert_eq!(
+            client.call(r#"{"id":3,"method":"subscribe","params":{"events":["wallet-ping"]}}"#).await,
+            r#"{"jsonrpc":"2.0","id":3,"result":true}"#
+        );
+
+        // The filter of the latest subscription replaces the previous one
+        events.handle_event(&Events::WalletClose).await;
+        events.handle_event(&Events::WalletPing).await;
+        assert_eq!(client.next_line().await, r#"{"jsonrpc":"2.0","method":"event","params":{"type":"wallet-ping"}}"#);
+
+        assert_eq!(client.call(r#"{"id":4,"method":"unsubscribe"}"#).await, r#"{"jsonrpc":"2.0","id":4,"result":true}"#);
+        events.handle_event(&Events::WalletPing).await;
+        assert_eq!(
+            client.call(r#"{"id":5,"method":"ping","params":{"message":"hello"}}"#).await,
+            r#"{"jsonrpc":"2.0","id":5,"result":{"message":"hello"}}"#
+        );
+
+        shutdown.trigger.trigger();
+    }
+
+    #[tokio::test]
+    async fn test_session_line_too_long() {
+        let (mut client, _events, shutdown) = start_session().await;
+
+        // A request line up to the limit is parsed
+        let line = format!(r#"{{"id":1,"method":"ping","params":{{"message":"{}"}}}}"#, " ".repeat(MAX_LINE_LENGTH - 100));
+        assert!(client.call(&line).await.contains(r#""code":-32001"#));
+
+        // A longer one closes the session before any response
+        client.send(&vec![b' '; MAX_LINE_LENGTH]).await;
+        assert!(client.is_closed().await);
+
+        shutdown.trigger.trigger();
+    }
+}